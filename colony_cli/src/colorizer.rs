@@ -0,0 +1,110 @@
+use colony_core::interface::output::{OutputSink, OutputTarget, PlayerAppearance};
+
+/// ANSI foreground colors for `PlayerAppearance::color_index`, one per palette slot.
+const PALETTE: [&str; 8] = ["34", "31", "32", "33", "35", "36", "94", "91"];
+
+const RESET: &str = "\x1b[0m";
+
+/// Colors every player's name and owner tag in the output of `inner`, so the same player
+/// has the same color on the map, in status output and in events.
+pub struct ColorSink<S> {
+    inner: S,
+    /// Words to highlight with their color code, longest first so names win over tags
+    highlights: Vec<(String, &'static str)>,
+}
+
+impl<S: OutputSink> ColorSink<S> {
+    pub fn new(inner: S) -> Self {
+        ColorSink { inner, highlights: Vec::new() }
+    }
+
+    fn colorize(&self, text: &str) -> String {
+        let mut colored = String::with_capacity(text.len());
+        let mut rest = text;
+        let mut previous: Option<char> = None;
+
+        'scan: while let Some(next) = rest.chars().next() {
+            if !previous.is_some_and(char::is_alphanumeric) {
+                for (word, color) in &self.highlights {
+                    let Some(after) = rest.strip_prefix(word.as_str()) else { continue };
+                    if after.chars().next().is_some_and(char::is_alphanumeric) {
+                        continue;
+                    }
+                    colored.push_str(&format!("\x1b[{color}m{word}{RESET}"));
+                    previous = word.chars().last();
+                    rest = after;
+                    continue 'scan;
+                }
+            }
+            colored.push(next);
+            previous = Some(next);
+            rest = &rest[next.len_utf8()..];
+        }
+        colored
+    }
+}
+
+impl<S: OutputSink> OutputSink for ColorSink<S> {
+    fn emit(&mut self, target: OutputTarget, text: &str) {
+        let text = self.colorize(text);
+        self.inner.emit(target, &text);
+    }
+
+    fn emit_error(&mut self, target: OutputTarget, text: &str) {
+        let text = self.colorize(text);
+        self.inner.emit_error(target, &text);
+    }
+
+    fn players_changed(&mut self, players: &[(String, PlayerAppearance)]) {
+        self.highlights = players.iter()
+            .flat_map(|(name, appearance)| {
+                let color = PALETTE[usize::from(appearance.color_index) % PALETTE.len()];
+                [(name.clone(), color), (appearance.tag.clone(), color)]
+            })
+            .filter(|(word, _)| !word.is_empty())
+            .collect();
+        self.highlights.sort_by_key(|(word, _)| std::cmp::Reverse(word.len()));
+        self.inner.players_changed(players);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use colony_core::interface::output::CaptureSink;
+
+    use super::*;
+
+    fn sink() -> ColorSink<CaptureSink> {
+        let mut sink = ColorSink::new(CaptureSink::new());
+        sink.players_changed(&[
+            (String::from("Alice"), PlayerAppearance { color_index: 0, tag: String::from("AL") }),
+            (String::from("Al"), PlayerAppearance { color_index: 1, tag: String::from("A2") }),
+        ]);
+        sink
+    }
+
+    #[test]
+    fn test_names_and_tags_get_their_player_color() {
+        let sink = sink();
+
+        assert_eq!(sink.colorize("Alice's turn."), "\x1b[34mAlice\x1b[0m's turn.");
+        assert_eq!(sink.colorize("c418 (AL) c2 (A2)"), "c418 (\x1b[34mAL\x1b[0m) c2 (\x1b[31mA2\x1b[0m)");
+        assert_eq!(sink.colorize("Al attacks"), "\x1b[31mAl\x1b[0m attacks");
+    }
+
+    #[test]
+    fn test_words_containing_a_name_are_left_alone() {
+        let sink = sink();
+
+        assert_eq!(sink.colorize("Alien ALL Malice"), "Alien ALL Malice");
+    }
+
+    #[test]
+    fn test_output_passes_through_colored() {
+        let mut sink = sink();
+
+        sink.emit(OutputTarget::Broadcast, "Alice ends their turn.");
+
+        assert_eq!(sink.inner.entries[0].1, "\x1b[34mAlice\x1b[0m ends their turn.");
+    }
+}
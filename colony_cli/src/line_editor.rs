@@ -0,0 +1,154 @@
+use std::io;
+use std::mem;
+use std::path::PathBuf;
+
+use colony_core::interface::input::{Completer, InputError, InputSource, ReadLine};
+use rustyline::completion::Completer as RustylineCompleter;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::{DefaultHistory, History};
+use rustyline::validate::Validator;
+use rustyline::{CompletionType, Config, Context, Editor, Helper};
+
+/// History file in the working directory, next to the default save folder.
+pub const HISTORY_FILE: &str = ".colony_history";
+
+/// Terminal line editor: arrow-key history kept across sessions, Ctrl-C cancelling
+/// the current line and tab completion from the game.
+pub struct LineEditor {
+    history: DefaultHistory,
+    history_path: PathBuf,
+}
+
+impl LineEditor {
+    /// Loads earlier history from `history_path`; a missing or unreadable file starts empty.
+    pub fn new(history_path: impl Into<PathBuf>) -> Self {
+        let history_path = history_path.into();
+        let mut history = DefaultHistory::new();
+        if history_path.exists() && let Err(error) = history.load(&history_path) {
+            eprintln!("WARNING: Command history not loaded - {error}");
+        }
+        LineEditor { history, history_path }
+    }
+
+    /// Adds a non-blank line to history and writes it out right away, so history
+    /// survives the game being killed.
+    fn remember(&mut self, line: &str) {
+        if line.trim().is_empty() {
+            return;
+        }
+        let result = self.history.add(line)
+            .and_then(|_| self.history.append(&self.history_path));
+        if let Err(error) = result {
+            eprintln!("WARNING: Command history not saved - {error}");
+        }
+    }
+}
+
+impl InputSource for LineEditor {
+    fn next_line(&mut self, prompt: &str, completer: Completer) -> Result<ReadLine, InputError> {
+        let config = Config::builder().completion_type(CompletionType::List).build();
+        let mut editor = Editor::with_config(config).map_err(to_input_error)?;
+        editor.set_helper(Some(CompletionHelper { completer }));
+
+        // The editor borrows the completer, so it lives for one line and history is lent to it
+        mem::swap(editor.history_mut(), &mut self.history);
+        let result = editor.readline(prompt);
+        mem::swap(editor.history_mut(), &mut self.history);
+
+        match result {
+            Ok(line) => {
+                self.remember(&line);
+                Ok(ReadLine::Line(line))
+            }
+            Err(ReadlineError::Interrupted) => Ok(ReadLine::Interrupted),
+            Err(ReadlineError::Eof) => Err(InputError::EndOfInput),
+            Err(error) => Err(to_input_error(error)),
+        }
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+}
+
+fn to_input_error(error: ReadlineError) -> InputError {
+    match error {
+        ReadlineError::Io(error) => InputError::Io(error),
+        error => InputError::Io(io::Error::other(error)),
+    }
+}
+
+/// Byte offset where the word under the cursor starts, which completions replace.
+fn word_start(typed: &str) -> usize {
+    let last_word = typed.rsplit(char::is_whitespace).next().unwrap_or("");
+    typed.len() - last_word.len()
+}
+
+/// Hands tab completion to the game's completer for the text left of the cursor.
+struct CompletionHelper<'a> {
+    completer: Completer<'a>,
+}
+
+impl RustylineCompleter for CompletionHelper<'_> {
+    type Candidate = String;
+
+    fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<String>)> {
+        let typed = &line[..pos];
+        Ok((word_start(typed), (self.completer)(typed)))
+    }
+}
+
+impl Hinter for CompletionHelper<'_> {
+    type Hint = String;
+}
+
+impl Highlighter for CompletionHelper<'_> {}
+
+impl Validator for CompletionHelper<'_> {}
+
+impl Helper for CompletionHelper<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_start_is_after_last_whitespace() {
+        assert_eq!(word_start(""), 0);
+        assert_eq!(word_start("bui"), 0);
+        assert_eq!(word_start("build "), 6);
+        assert_eq!(word_start("build  kepler_4 po"), 16);
+    }
+
+    #[test]
+    fn test_helper_replaces_word_under_cursor() {
+        let completer = |typed: &str| vec![format!("[{typed}]")];
+        let helper = CompletionHelper { completer: &completer };
+        let history = DefaultHistory::new();
+
+        let (start, candidates) = helper.complete("status tu", 9, &Context::new(&history)).unwrap();
+
+        assert_eq!(start, 7);
+        assert_eq!(candidates, ["[status tu]"]);
+    }
+
+    #[test]
+    fn test_history_persists_between_sessions() {
+        let dir = std::env::temp_dir().join(format!("colony_cli_history_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(HISTORY_FILE);
+        let _ = std::fs::remove_file(&path);
+
+        let mut session = LineEditor::new(&path);
+        session.remember("status turn");
+        session.remember("   ");
+        session.remember("end");
+
+        let next_session = LineEditor::new(&path);
+        assert_eq!(next_session.history.len(), 2);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,15 +1,81 @@
-use colony_core::game::{Game};
-use colony_core::game_configuration::{GameConfiguration};
+mod colorizer;
+#[cfg(feature = "line-editor")]
+mod line_editor;
+mod progress;
+
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+use colony_core::Game;
+use colony_core::GameConfiguration;
+use colony_core::{DataFilePaths, validate_data_files};
+use colony_core::engine::configs;
+use colony_core::engine::progress::{NoProgress, ProgressSink};
+use colony_core::interface::input::{InputSource, StdinSource};
+use colony_core::interface::output::{OutputSink, RenderStyle, StdoutSink};
+use colony_core::interface::replay::ReplayViewer;
+use colony_core::interface::setup_file::{self, SetupLayers};
+use colony_core::sim::{self, SimData, SimOptions};
+
+use colorizer::ColorSink;
+use progress::CounterProgress;
 
 fn main() {
-    #[cfg(debug_assertions)]
-    let config_result = GameConfiguration::debug_default();
+    // Overrides COLONY_DATA_DIR and the default data directory
+    if let Some(path) = option_value("--data-dir") {
+        configs::set_data_dir(path);
+    }
+    if std::env::args().skip(1).any(|argument| argument == "--validate-data") {
+        run_data_validation();
+    }
+    let missing = configs::missing_data_files();
+    if !missing.is_empty() {
+        eprintln!("CRITICAL ERROR: {} data file(s) could not be read:", missing.len());
+        for error in &missing {
+            eprintln!("  {}: {}", error.path.display(), error.source);
+        }
+        if let Ok(dir) = std::env::current_dir() {
+            eprintln!("Working directory: {}", dir.display());
+        }
+        eprintln!(
+            "Run from the repository root, or set {} or pass --data-dir <path> to point at the data directory.",
+            configs::DATA_DIR_ENV
+        );
+        std::process::exit(1);
+    }
+
+    if let Some(games) = option_value("--simulate") {
+        run_simulation(&games);
+        return;
+    }
+    let arguments: Vec<String> = std::env::args().skip(1).collect();
+    if let [mode, action, path, ..] = &arguments[..]
+        && mode == "replay"
+        && action == "view"
+    {
+        run_replay_viewer(path);
+        return;
+    }
 
-    #[cfg(not(debug_assertions))]
-    let config_result = GameConfiguration::new();
+    let mut input = input_source();
+
+    let save_setup = option_value("--save-setup").map(PathBuf::from);
+    let config_result = match setup_layers().or_else(|| save_setup.as_ref().map(|_| SetupLayers::new())) {
+        Some(layers) => setup_file::configure(&layers, input.as_mut(), save_setup.as_deref()),
+        #[cfg(debug_assertions)]
+        None => GameConfiguration::debug_default(),
+        #[cfg(not(debug_assertions))]
+        None => GameConfiguration::from_prompts(input.as_mut()),
+    };
 
     let game_configuration = match config_result {
-        Ok(config) => config,
+        Ok(config) => config
+            .with_terminal_width(terminal_width())
+            .with_render_style(render_style())
+            .with_event_log(option_value("--event-log").map(PathBuf::from))
+            .with_replay(option_value("--record-replay").map(PathBuf::from))
+            .with_dev_mode(std::env::args().skip(1).any(|argument| argument == "--dev"))
+            .with_advisor(!std::env::args().skip(1).any(|argument| argument == "--no-advisor")),
         Err(error) => {
             eprintln!("CRITICAL ERROR: Colonial Command initialization failed - {}", error);
             eprintln!("Connection terminated. Please restart the protocol.");
@@ -24,12 +90,181 @@ fn main() {
             std::process::exit(1);
         }
     };
-    match game.run() {
-        Ok(config) => config,
+    game.install_crash_hook();
+    let mut output = output_sink();
+    if let Err(error) = game.offer_crash_recovery(input.as_mut(), output.as_mut()) {
+        eprintln!("Could not check for crash saves: {}", error);
+    }
+    if let Err(error) = game.run_with(input.as_mut(), output.as_mut()) {
+        eprintln!("CRITICAL ERROR: {}", error);
+        if let Some(path) = game.emergency_save() {
+            eprintln!("Progress up to the last command was saved to {}.", path.display());
+        }
+        eprintln!("Connection terminated. Please restart the protocol.");
+        std::process::exit(1);
+    }
+}
+
+/// The line editor when a person is typing at a terminal, plain stdin otherwise
+/// (piped scripts, or builds without the `line-editor` feature).
+fn input_source() -> Box<dyn InputSource> {
+    #[cfg(feature = "line-editor")]
+    {
+        if std::io::stdin().is_terminal() {
+            return Box::new(line_editor::LineEditor::new(line_editor::HISTORY_FILE));
+        }
+    }
+    Box::new(StdinSource::stdin())
+}
+
+/// Player colors on a terminal, unless disabled through the `NO_COLOR` convention.
+fn output_sink() -> Box<dyn OutputSink> {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    if std::io::stdout().is_terminal() && !no_color {
+        Box::new(ColorSink::new(StdoutSink))
+    } else {
+        Box::new(StdoutSink)
+    }
+}
+
+/// `--ascii` or `--unicode` when given on the command line. Otherwise plain ASCII on Windows
+/// consoles other than Windows Terminal and under locales without UTF-8, where box drawing
+/// characters come out garbled.
+fn render_style() -> RenderStyle {
+    for argument in std::env::args().skip(1) {
+        match argument.as_str() {
+            "--ascii" => return RenderStyle::AsciiOnly,
+            "--unicode" => return RenderStyle::Unicode,
+            _ => {}
+        }
+    }
+
+    if cfg!(windows) && std::env::var_os("WT_SESSION").is_none() {
+        return RenderStyle::AsciiOnly;
+    }
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"].into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty());
+    match locale {
+        Some(locale) if !locale.to_ascii_lowercase().replace('-', "").contains("utf8") => RenderStyle::AsciiOnly,
+        _ => RenderStyle::Unicode,
+    }
+}
+
+/// Value given with `<name> <value>` or `<name>=<value>` on the command line.
+fn option_value(name: &str) -> Option<String> {
+    option_values(name).into_iter().next()
+}
+
+/// Every value given with `<name> <value>` or `<name>=<value>`, in command line order.
+fn option_values(name: &str) -> Vec<String> {
+    let mut arguments = std::env::args().skip(1);
+    let prefix = format!("{name}=");
+    let mut values = Vec::new();
+    while let Some(argument) = arguments.next() {
+        if argument == name {
+            values.extend(arguments.next());
+        } else if let Some(value) = argument.strip_prefix(&prefix) {
+            values.push(value.to_string());
+        }
+    }
+    values
+}
+
+/// Setup values from `--setup <file>` and each `--set <field>=<value>`, the flags winning;
+/// None when neither is given. Exits on files or values that cannot be read.
+fn setup_layers() -> Option<SetupLayers> {
+    let setup_path = option_value("--setup");
+    let assignments = option_values("--set");
+    if setup_path.is_none() && assignments.is_empty() {
+        return None;
+    }
+
+    let mut layers = SetupLayers::new();
+    let mut added = setup_path.map_or(Ok(()), |path| layers.add_file(std::path::Path::new(&path)));
+    for assignment in &assignments {
+        added = added.and_then(|()| layers.add_flag(assignment));
+    }
+    if let Err(error) = added {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    }
+    Some(layers)
+}
+
+/// Checks the data files for `--validate-data` without starting a game, exiting with
+/// status 0 when they are all valid and 1 otherwise.
+fn run_data_validation() -> ! {
+    let report = validate_data_files(&DataFilePaths::in_dir(configs::data_dir()));
+    if report.is_valid() {
+        println!("{}", report);
+        std::process::exit(0);
+    }
+    eprintln!("{}", report);
+    std::process::exit(1);
+}
+
+/// Plays `games` bot games for `--simulate` and prints the summary, with `--seed`,
+/// `--turn-cap` and `--bots <personality,...>` adjusting the run and `--sim-csv <path>`
+/// also writing one line per game. A counter on stderr shows the games finished so far.
+fn run_simulation(games: &str) {
+    let mut options = SimOptions::new(parse_or_exit("--simulate", games));
+    if let Some(seed) = option_value("--seed") {
+        options.seed = parse_or_exit("--seed", &seed);
+    }
+    if let Some(turn_cap) = option_value("--turn-cap") {
+        options.turn_cap = parse_or_exit("--turn-cap", &turn_cap);
+    }
+    if let Some(bots) = option_value("--bots") {
+        options.personalities = bots.split(',').map(|bot| parse_or_exit("--bots", bot)).collect();
+    }
+
+    let mut progress: Box<dyn ProgressSink> = if std::io::stderr().is_terminal() {
+        Box::new(CounterProgress)
+    } else {
+        Box::new(NoProgress)
+    };
+    let report = match SimData::load().and_then(|data| sim::simulate_with_progress(&options, &data, progress.as_mut())) {
+        Ok(report) => report,
         Err(error) => {
-            eprintln!("CRITICAL ERROR: {}", error);
-            eprintln!("Connection terminated. Please restart the protocol.");
-            return;
+            eprintln!("Simulation failed: {}", error);
+            std::process::exit(1);
         }
+    };
+    print!("{}", report);
+    if let Some(path) = option_value("--sim-csv") {
+        if let Err(error) = std::fs::write(&path, report.to_csv()) {
+            eprintln!("Failed to write {}: {}", path, error);
+            std::process::exit(1);
+        }
+        println!("\nOne line per game written to {}", path);
+    }
+}
+
+/// Steps through the replay at `path` for `replay view <path>`.
+fn run_replay_viewer(path: &str) {
+    let viewer = ReplayViewer::open(std::path::Path::new(path)).unwrap_or_else(|error| {
+        eprintln!("Failed to open replay {}: {}", path, error);
+        std::process::exit(1);
+    });
+    let mut viewer = viewer.with_terminal_width(terminal_width()).with_render_style(render_style());
+    if let Err(error) = viewer.run_with(input_source().as_mut(), output_sink().as_mut()) {
+        eprintln!("CRITICAL ERROR: {}", error);
+        std::process::exit(1);
     }
-}
\ No newline at end of file
+}
+
+fn parse_or_exit<T: std::str::FromStr>(option: &str, value: &str) -> T
+where
+    T::Err: std::fmt::Display,
+{
+    value.parse().unwrap_or_else(|error| {
+        eprintln!("Invalid value '{}' for {}: {}", value, option, error);
+        std::process::exit(1);
+    })
+}
+
+/// Terminal width as exported by the shell in `COLUMNS`, if available.
+fn terminal_width() -> Option<usize> {
+    std::env::var("COLUMNS").ok()?.trim().parse().ok()
+}
@@ -0,0 +1,20 @@
+//! Progress of long operations, drawn as one line that counts up in place.
+
+use std::io::{self, Write};
+
+use colony_core::engine::progress::ProgressSink;
+
+/// Redraws "<stage>: <current>/<total>" over itself on stderr, so stdout stays clean for
+/// reports that get redirected. The line is finished once the stage completes.
+pub struct CounterProgress;
+
+impl ProgressSink for CounterProgress {
+    fn report(&mut self, stage: &str, current: usize, total: usize) {
+        let mut stderr = io::stderr().lock();
+        let _ = write!(stderr, "\r{}: {}/{}", stage, current, total);
+        if current >= total {
+            let _ = writeln!(stderr);
+        }
+        let _ = stderr.flush();
+    }
+}
@@ -0,0 +1,53 @@
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+/// Runs the CLI on the workspace's `data/` with the given bytes piped to stdin. It runs in a
+/// scratch directory, so saves written on fatal errors stay out of the workspace.
+fn run_with_input(input: &[u8]) -> Output {
+    let data_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("data");
+    let scratch_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("input_handling");
+    std::fs::create_dir_all(&scratch_dir).expect("Failed to create the scratch directory");
+    let mut child = Command::new(env!("CARGO_BIN_EXE_colony_cli"))
+        .arg("--data-dir")
+        .arg(data_dir)
+        .current_dir(scratch_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start colony_cli");
+
+    child.stdin.take()
+        .expect("stdin is piped")
+        .write_all(input)
+        .expect("Failed to write to stdin");
+
+    child.wait_with_output().expect("Failed to wait for colony_cli")
+}
+
+#[test]
+fn test_truncated_script_exits_cleanly_on_eof() {
+    let output = run_with_input(b"status turn\nhelp\n");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Current turn: 1"));
+    assert!(stdout.contains("Terminating session..."));
+}
+
+#[test]
+fn test_empty_input_exits_cleanly() {
+    let output = run_with_input(b"");
+
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_invalid_utf8_terminates_piped_session() {
+    let output = run_with_input(b"status turn\n\xff\xfe\nstatus turn\n");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("not valid UTF-8"));
+}
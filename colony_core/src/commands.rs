@@ -1,13 +0,0 @@
-pub mod command;
-pub mod help;
-pub mod build;
-pub mod build_ship;
-pub mod upgrade;
-pub mod cancel;
-pub mod parser;
-pub mod status;
-pub mod map;
-pub mod ships;
-pub mod fleets;
-pub mod fleet;
-pub mod end_turn;
\ No newline at end of file
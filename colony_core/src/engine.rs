@@ -0,0 +1,34 @@
+//! Game rules without terminal I/O.
+//!
+//! A frontend parses player input with [`parse_command`], validates it against the current
+//! [`GameState`] with [`Command::execute`] and applies the resulting [`CommandEffect`] with
+//! [`GameState::apply_effect`]. Every state change is reported back as [`GameEvent`]s,
+//! which the frontend renders however it likes.
+
+pub mod appearance;
+pub mod clock;
+pub mod commands;
+pub mod configs;
+pub mod fleet;
+pub mod game_event;
+pub mod game_rules;
+pub mod game_state;
+pub mod map;
+pub mod options;
+pub mod pending_action;
+pub mod planet;
+pub mod planet_name_generator;
+pub mod player;
+pub mod progress;
+pub mod resources;
+pub mod rules;
+pub mod save;
+pub mod ship;
+pub mod structure;
+pub mod utils;
+
+pub use commands::command::{Command, CommandEffect, CommandError};
+pub use commands::parser::parse as parse_command;
+pub use game_event::GameEvent;
+pub use game_rules::GameRules;
+pub use game_state::{GameState, GameStateError};
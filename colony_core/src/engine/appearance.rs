@@ -0,0 +1,101 @@
+use std::collections::HashSet;
+
+/// Number of distinct player colors. Frontends map each index to a color of their own.
+pub const PLAYER_COLOR_COUNT: u8 = 8;
+
+/// How a player is told apart on every surface: map labels, status output and colored text.
+/// Assigned once when the game is created and kept in saves, so it never changes mid-game.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PlayerAppearance {
+    /// Index into the frontend's palette, below `PLAYER_COLOR_COUNT`
+    pub color_index: u8,
+    /// Short unique owner tag such as `AL`
+    pub tag: String,
+}
+
+/// Appearances for players given by name in join order: colors are handed out in turn,
+/// tags come from `generate_tags`.
+pub fn assign(names: &[&str]) -> Vec<PlayerAppearance> {
+    generate_tags(names).into_iter()
+        .zip((0..PLAYER_COLOR_COUNT).cycle())
+        .map(|(tag, color_index)| PlayerAppearance { color_index, tag })
+        .collect()
+}
+
+/// Short, distinct owner tags for `names`, in the same order: the initials of a multi-word
+/// name, otherwise its first two letters, then other letter picks and finally numbers when
+/// tags clash. Earlier names get first pick.
+pub fn generate_tags(names: &[&str]) -> Vec<String> {
+    let mut taken = HashSet::new();
+
+    names.iter().map(|name| {
+        let letters: Vec<char> = name.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_uppercase)
+            .collect();
+        let first = letters.first().copied().unwrap_or('P');
+        let initials: String = name.split_whitespace()
+            .filter_map(|word| word.chars().find(|c| c.is_alphanumeric()))
+            .flat_map(char::to_uppercase)
+            .take(3)
+            .collect();
+
+        let mut candidates = Vec::new();
+        if initials.chars().count() >= 2 {
+            candidates.push(initials);
+        }
+        candidates.push(letters.iter().take(2).collect());
+        if let Some(last) = letters.last() {
+            candidates.push(format!("{}{}", first, last));
+        }
+        candidates.push(letters.iter().take(3).collect());
+
+        let tag = candidates.into_iter()
+            .filter(|candidate| candidate.chars().count() >= 2)
+            .find(|candidate| !taken.contains(candidate))
+            .unwrap_or_else(|| (2..)
+                .map(|n| format!("{}{}", first, n))
+                .find(|candidate| !taken.contains(candidate))
+                .expect("Numbered tags never run out"));
+
+        taken.insert(tag.clone());
+        tag
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tags_prefer_initials_and_fall_back_to_numbers() {
+        let tags = generate_tags(&["Crimson Theta", "Ab", "Ab", "Ab"]);
+
+        assert_eq!(tags, ["CT", "AB", "A2", "A3"]);
+    }
+
+    #[test]
+    fn test_similar_names_get_distinct_tags() {
+        assert_eq!(generate_tags(&["Alexandria", "Alexandros"]), ["AL", "AS"]);
+        assert_eq!(generate_tags(&["Al", "Alma", "Ala", "Aa"]), ["AL", "AA", "ALA", "A2"]);
+    }
+
+    #[test]
+    fn test_awkward_names_still_get_tags() {
+        let tags = generate_tags(&["x", "!!!", "", "Élodie", "李 小龍"]);
+
+        assert_eq!(tags, ["XX", "P2", "P3", "ÉL", "李小"]);
+        let unique: HashSet<_> = tags.iter().collect();
+        assert_eq!(unique.len(), tags.len());
+    }
+
+    #[test]
+    fn test_colors_follow_join_order_and_wrap() {
+        let names: Vec<String> = (0..=PLAYER_COLOR_COUNT).map(|n| format!("Player {n}")).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+
+        let colors: Vec<_> = assign(&names).into_iter().map(|appearance| appearance.color_index).collect();
+
+        assert_eq!(colors, [0, 1, 2, 3, 4, 5, 6, 7, 0]);
+    }
+}
@@ -0,0 +1,77 @@
+//! Time as the engine measures it, only ever to report how long its own processing takes.
+//! Nothing the rules decide depends on a clock, so a seeded game plays out the same with any.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+/// A clock that never goes backwards.
+pub trait Clock: fmt::Debug + Send + Sync {
+    /// Time since a fixed point of the clock's choosing
+    fn elapsed(&self) -> Duration;
+}
+
+/// The system's monotonic clock, counting from when it was made.
+#[cfg(feature = "std-io")]
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock(std::time::Instant);
+
+#[cfg(feature = "std-io")]
+impl SystemClock {
+    pub fn new() -> Self {
+        SystemClock(std::time::Instant::now())
+    }
+}
+
+#[cfg(feature = "std-io")]
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std-io")]
+impl Clock for SystemClock {
+    fn elapsed(&self) -> Duration {
+        self.0.elapsed()
+    }
+}
+
+/// A clock that only moves when told, for tests. Each reading first moves it on by the next
+/// queued step, so the durations measured within a single call can be made up beforehand.
+/// Clones share the time.
+#[derive(Debug, Clone, Default)]
+pub struct MockClock(Arc<Mutex<MockTime>>);
+
+#[derive(Debug, Default)]
+struct MockTime {
+    now: Duration,
+    steps: VecDeque<Duration>,
+}
+
+impl MockClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).now += by;
+    }
+
+    /// Moves the clock on by each of `steps` in turn, one per reading from now on. Readings
+    /// once the steps run out leave it where it is.
+    pub fn queue_steps(&self, steps: impl IntoIterator<Item = Duration>) {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).steps.extend(steps);
+    }
+}
+
+impl Clock for MockClock {
+    fn elapsed(&self) -> Duration {
+        let mut time = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(step) = time.steps.pop_front() {
+            time.now += step;
+        }
+        time.now
+    }
+}
@@ -0,0 +1,34 @@
+pub mod command;
+pub mod advisor;
+pub mod completion;
+pub mod help;
+pub mod build;
+pub mod build_all;
+pub mod build_ship;
+pub mod upgrade;
+pub mod cancel;
+pub mod parser;
+pub mod status;
+pub mod structures;
+pub mod intel;
+pub mod inspect;
+pub mod dev;
+pub mod log;
+pub mod battles;
+pub mod forecast;
+pub mod schedule;
+pub mod defense;
+pub mod diff;
+pub mod map;
+pub mod ships;
+pub mod fleets;
+pub mod fleet;
+pub mod planet;
+pub mod ship;
+pub mod end_turn;
+pub mod save;
+pub mod export;
+pub mod set;
+pub mod options;
+pub mod alias;
+pub mod note;
\ No newline at end of file
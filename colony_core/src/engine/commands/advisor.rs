@@ -0,0 +1,19 @@
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::game_state::{GameState, Suggestion, advise};
+
+pub fn execute(game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let suggestions = advise(game_state, game_state.current_player());
+    Ok(CommandEffect::None { message: format_advice(&suggestions) })
+}
+
+/// The advisor's suggestions as a numbered list, most important first.
+pub fn format_advice(suggestions: &[Suggestion]) -> String {
+    if suggestions.is_empty() {
+        return String::from("ADVISOR\n  Nothing to suggest right now.");
+    }
+    let mut msg = String::from("ADVISOR");
+    for (number, suggestion) in (1..).zip(suggestions) {
+        msg.push_str(&format!("\n  {}. {}", number, suggestion));
+    }
+    msg
+}
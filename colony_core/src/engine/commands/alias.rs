@@ -0,0 +1,211 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::parser::{Parseable, accepted_command_words};
+use crate::engine::game_state::GameState;
+
+pub enum AliasArgs {
+    Define { name: String, expansion: String },
+    List,
+    Remove { name: String },
+}
+
+impl Parseable for AliasArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        let missing = || CommandError::MissingArguments {
+            command: String::from("alias"),
+            expected: String::from("alias <name> = <command> | alias list | alias remove <name>"),
+        };
+
+        // Spaces around '=' are optional
+        let joined = args.join(" ");
+        if let Some((name, expansion)) = joined.split_once('=') {
+            let (name, expansion) = (name.trim(), expansion.trim());
+            if name.is_empty() || expansion.is_empty() {
+                return Err(missing());
+            }
+            if name.contains(char::is_whitespace) {
+                return Err(CommandError::InvalidArgument {
+                    command: String::from("alias"),
+                    argument: name.to_string(),
+                    reason: String::from("alias names are a single word"),
+                });
+            }
+            return Ok(AliasArgs::Define { name: name.to_string(), expansion: expansion.to_string() });
+        }
+
+        match args.as_slice() {
+            ["list"] => Ok(AliasArgs::List),
+            ["remove", name] => Ok(AliasArgs::Remove { name: name.to_string() }),
+            _ => Err(missing()),
+        }
+    }
+}
+
+pub fn execute(args: AliasArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+
+    match args {
+        AliasArgs::Define { name, expansion } => {
+            let invalid = |reason: &str| CommandError::InvalidArgument {
+                command: String::from("alias"),
+                argument: name.clone(),
+                reason: reason.to_string(),
+            };
+
+            if accepted_command_words().contains(&name.as_str()) {
+                return Err(invalid("built-in commands cannot be redefined"));
+            }
+            // Expansions are never expanded again, so an alias may only stand for built-in commands
+            let first_word = expansion.split_whitespace().next().unwrap_or_default();
+            if first_word == name {
+                return Err(invalid("an alias cannot refer to itself"));
+            }
+            if player.aliases.contains_key(first_word) {
+                return Err(invalid("an alias cannot refer to another alias"));
+            }
+            if !accepted_command_words().contains(&first_word) {
+                return Err(invalid(&format!("'{}' is not a command", first_word)));
+            }
+
+            Ok(CommandEffect::DefineAlias { name, expansion })
+        }
+        AliasArgs::List => Ok(CommandEffect::None { message: format_aliases(&player.aliases) }),
+        AliasArgs::Remove { name } => {
+            if !player.aliases.contains_key(&name) {
+                return Err(CommandError::InvalidArgument {
+                    command: String::from("alias remove"),
+                    argument: name,
+                    reason: String::from("no such alias. See 'alias list'"),
+                });
+            }
+            Ok(CommandEffect::RemoveAlias { name })
+        }
+    }
+}
+
+/// Replaces a leading alias in `line` with its expansion, keeping any words typed after it.
+/// Expansion happens once: the result is not checked for aliases again.
+pub fn expand<'a>(line: &'a str, aliases: &BTreeMap<String, String>) -> Cow<'a, str> {
+    let trimmed = line.trim_start();
+    let (first_word, rest) = trimmed.split_once(char::is_whitespace).unwrap_or((trimmed, ""));
+
+    match aliases.get(first_word) {
+        // Built-in commands win should an old save carry an alias that now clashes
+        Some(expansion) if !accepted_command_words().contains(&first_word) => {
+            let rest = rest.trim();
+            if rest.is_empty() {
+                Cow::Owned(expansion.clone())
+            } else {
+                Cow::Owned(format!("{} {}", expansion, rest))
+            }
+        }
+        _ => Cow::Borrowed(line),
+    }
+}
+
+fn format_aliases(aliases: &BTreeMap<String, String>) -> String {
+    if aliases.is_empty() {
+        return String::from("No aliases. Define one with: alias <name> = <command>");
+    }
+
+    let mut msg = String::from("=== Aliases ===\n");
+    for (name, expansion) in aliases {
+        msg.push_str(&format!("  {} = {}\n", name, expansion));
+    }
+    msg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::command::Command;
+    use crate::engine::commands::parser;
+    use crate::engine::save;
+    use crate::test_support::fixture_game;
+
+    fn define(game_state: &mut GameState, line: &str) -> Result<(), CommandError> {
+        let Command::Alias(args) = parser::parse(line)? else {
+            panic!("'{}' must parse as an alias command", line);
+        };
+        let effect = execute(args, game_state)?;
+        let player_id = game_state.current_player().clone();
+        game_state.apply_effect(effect, &player_id).unwrap();
+        Ok(())
+    }
+
+    fn alice_aliases(game_state: &GameState) -> &BTreeMap<String, String> {
+        &game_state.players["alice"].aliases
+    }
+
+    #[test]
+    fn test_alias_expands_with_trailing_words() {
+        let mut state = fixture_game(1);
+        define(&mut state, "alias deffleet = fleet create Defense interceptor_1 interceptor_2").unwrap();
+        define(&mut state, "alias st=status").unwrap();
+        let aliases = alice_aliases(&state);
+
+        assert_eq!(expand("deffleet", aliases), "fleet create Defense interceptor_1 interceptor_2");
+        assert_eq!(expand("st  planet kepler", aliases), "status planet kepler");
+        assert!(matches!(expand("status turn", aliases), Cow::Borrowed("status turn")));
+        assert!(matches!(parser::parse(&expand("st turn", aliases)), Ok(Command::Status(_))));
+    }
+
+    #[test]
+    fn test_builtin_commands_cannot_be_shadowed() {
+        let mut state = fixture_game(1);
+
+        for line in ["alias build = status turn", "alias end = help", "alias alias = help"] {
+            assert!(matches!(define(&mut state, line), Err(CommandError::InvalidArgument { .. })), "{line}");
+        }
+        assert!(alice_aliases(&state).is_empty());
+    }
+
+    #[test]
+    fn test_alias_cannot_expand_into_an_alias() {
+        let mut state = fixture_game(1);
+        define(&mut state, "alias st = status").unwrap();
+
+        let own = define(&mut state, "alias loop = loop turn").unwrap_err();
+        assert!(own.to_string().contains("cannot refer to itself"), "{own}");
+        let chained = define(&mut state, "alias s = st turn").unwrap_err();
+        assert!(chained.to_string().contains("another alias"), "{chained}");
+
+        // Even a clashing table, as an edited save could hold, expands only once
+        let aliases = BTreeMap::from([(String::from("x"), String::from("x turn"))]);
+        assert_eq!(expand("x", &aliases), "x turn");
+    }
+
+    #[test]
+    fn test_remove_and_list() {
+        let mut state = fixture_game(1);
+        define(&mut state, "alias st = status").unwrap();
+        assert!(matches!(
+            execute(AliasArgs::List, &state),
+            Ok(CommandEffect::None { message }) if message.contains("st = status")
+        ));
+
+        define(&mut state, "alias remove st").unwrap();
+        assert!(alice_aliases(&state).is_empty());
+        assert!(matches!(define(&mut state, "alias remove st"), Err(CommandError::InvalidArgument { .. })));
+    }
+
+    #[test]
+    fn test_aliases_survive_save_and_load() {
+        let mut state = fixture_game(1);
+        define(&mut state, "alias st = status turn").unwrap();
+        let saved = save::encode_save(&state, "aliases", 0).unwrap();
+        let loaded = save::decode_save(&saved).unwrap();
+
+        assert_eq!(alice_aliases(&loaded), alice_aliases(&state));
+    }
+
+    #[test]
+    fn test_aliases_are_per_player() {
+        let mut state = fixture_game(1);
+        define(&mut state, "alias st = status").unwrap();
+
+        assert!(state.players["bob"].aliases.is_empty());
+    }
+}
@@ -0,0 +1,209 @@
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::parser::Parseable;
+use crate::engine::game_state::{BattleReport, BattleSide, GameState};
+
+/// Battles listed when no count is given.
+pub const DEFAULT_LISTED_BATTLES: u32 = 5;
+
+/// Most battles listed at once.
+pub const MAX_LISTED_BATTLES: u32 = 50;
+
+pub enum BattlesArgs {
+    /// The most recent battles, newest first
+    Recent { count: u32 },
+    Show { id: u32 },
+}
+
+impl Parseable for BattlesArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        let invalid = |argument: &str, reason: String| CommandError::InvalidArgument {
+            command: String::from("battles"),
+            argument: argument.to_string(),
+            reason,
+        };
+
+        match args.as_slice() {
+            [] => Ok(BattlesArgs::Recent { count: DEFAULT_LISTED_BATTLES }),
+            ["show"] => Err(CommandError::MissingArguments {
+                command: String::from("battles show"),
+                expected: String::from("battles show <id>"),
+            }),
+            ["show", id, ..] => {
+                let id = id.trim_start_matches('#').parse().ok()
+                    .filter(|id| *id > 0)
+                    .ok_or_else(|| invalid(id, String::from("battle ids are the numbers listed by 'battles'")))?;
+                Ok(BattlesArgs::Show { id })
+            }
+            [count, ..] => {
+                let count = count.parse().ok()
+                    .filter(|count| (1..=MAX_LISTED_BATTLES).contains(count))
+                    .ok_or_else(|| invalid(count, format!("the count must be a number from 1 to {}", MAX_LISTED_BATTLES)))?;
+                Ok(BattlesArgs::Recent { count })
+            }
+        }
+    }
+}
+
+/// Lists the battles the current player fought in or watched, or retells one of them.
+pub fn execute(args: BattlesArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let visible = game_state.battles_visible_to(game_state.current_player());
+
+    let message = match args {
+        BattlesArgs::Recent { count } => {
+            let mut msg = String::from("=== Battles ===\n");
+            if visible.is_empty() {
+                msg.push_str("  (no battles seen)\n");
+            }
+            for report in visible.iter().take(count as usize) {
+                msg.push_str(&format!("  {}\n", summary(report)));
+            }
+            if visible.len() > count as usize {
+                msg.push_str(&format!("  ... {} older battle(s): battles {}\n", visible.len() - count as usize, visible.len()));
+            }
+            msg.push_str("Details: battles show <id>\n");
+            msg
+        }
+        BattlesArgs::Show { id } => {
+            let report = visible.iter()
+                .find(|report| report.id == id)
+                .ok_or_else(|| CommandError::InvalidArgument {
+                    command: String::from("battles"),
+                    argument: id.to_string(),
+                    reason: String::from("no battle you fought in or saw has this id"),
+                })?;
+            narrative(report, game_state)
+        }
+    };
+
+    Ok(CommandEffect::None { message })
+}
+
+/// One line for the battle list.
+fn summary(report: &BattleReport) -> String {
+    let (attacker, defender) = (&report.attacker, &report.defender);
+    format!(
+        "#{:<3} Turn {:>3}  {}: {} attacked {}, {} won ({} lost {}, {} lost {})",
+        report.id, report.turn, report.planet_name, attacker.player_name, defender.player_name,
+        report.winner().player_name, attacker.player_name, attacker.losses.len(),
+        defender.player_name, defender.losses.len(),
+    )
+}
+
+/// The battle retold round by round from the stored report.
+fn narrative(report: &BattleReport, game_state: &GameState) -> String {
+    let (attacker, defender) = (&report.attacker, &report.defender);
+    let all_ships = |side: &BattleSide| side.composition(side.ships.iter().map(|ship| &ship.id));
+
+    let mut msg = format!("=== Battle #{}: {} at {}, turn {} ===\n", report.id, report.kind, report.planet_name, report.turn);
+    let fleet = attacker.fleet_id.as_ref().map(|fleet_id| format!(" with fleet {}", fleet_id)).unwrap_or_default();
+    msg.push_str(&format!("{} attacked{}: {}\n", attacker.player_name, fleet, all_ships(attacker)));
+    msg.push_str(&format!("{} defended: {}\n", defender.player_name, all_ships(defender)));
+
+    for (index, round) in report.rounds.iter().enumerate() {
+        msg.push_str(&format!("Round {}:\n", index + 1));
+        msg.push_str(&format!(
+            "  {} struck with {} attack ({} ship(s) with a counter bonus)\n",
+            attacker.player_name, round.attack, round.boosted_attackers,
+        ));
+        msg.push_str(&format!(
+            "  {} held with {} shield ({} ship(s) with a counter bonus)\n",
+            defender.player_name, round.defense, round.boosted_defenders,
+        ));
+    }
+
+    msg.push_str(&format!("Outcome: {} won\n", report.winner().player_name));
+    for side in [attacker, defender].into_iter().filter(|side| !side.losses.is_empty()) {
+        msg.push_str(&format!(
+            "  {} lost {} ({})\n", side.player_name, side.composition(&side.losses), side.losses.join(", "),
+        ));
+    }
+    if !report.observers.is_empty() {
+        let names: Vec<_> = report.observers.iter()
+            .map(|player_id| game_state.players.get(player_id).map_or_else(|| player_id.clone(), |player| player.name.clone()))
+            .collect();
+        msg.push_str(&format!("Observed by: {}\n", names.join(", ")));
+    }
+    msg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::parser;
+    use crate::engine::game_state::{BattleKind, BattleRound, BattleShip};
+    use crate::test_support::fixture_game;
+
+    fn side(player_id: &str, ships: &[(&str, &str)], lost: bool) -> BattleSide {
+        let ships: Vec<_> = ships.iter()
+            .map(|(id, ship_type)| BattleShip { id: id.to_string(), ship_type: ship_type.to_string() })
+            .collect();
+        BattleSide {
+            player_id: player_id.to_string(),
+            player_name: player_id[..1].to_uppercase() + &player_id[1..],
+            fleet_id: None,
+            losses: if lost { ships.iter().map(|ship| ship.id.clone()).collect() } else { Vec::new() },
+            ships,
+        }
+    }
+
+    fn report(id: u32, attacker: &str, defender: &str) -> BattleReport {
+        let mut attacker = side(attacker, &[("interceptor_1", "interceptor"), ("interceptor_2", "interceptor")], false);
+        attacker.fleet_id = Some(String::from("fleet_1"));
+        BattleReport {
+            id,
+            turn: 4,
+            kind: BattleKind::Orbital,
+            planet_id: String::from("kepler"),
+            planet_name: String::from("Kepler"),
+            attacker,
+            defender: side(defender, &[("ravager_1", "ravager")], true),
+            rounds: vec![BattleRound { attack: 30, defense: 15, boosted_attackers: 2, boosted_defenders: 0 }],
+            attacker_wins: true,
+            observers: Vec::new(),
+        }
+    }
+
+    fn battles_message(state: &GameState, input: &str) -> Result<String, CommandError> {
+        match parser::parse(input)?.execute(state)? {
+            CommandEffect::None { message } => Ok(message),
+            _ => panic!("Battles must only produce a message"),
+        }
+    }
+
+    #[test]
+    fn test_list_shows_the_players_battles_newest_first() {
+        let mut state = fixture_game(1);
+        state.battles = vec![report(1, "alice", "bob"), report(2, "bob", "carol"), report(3, "bob", "alice")];
+
+        let message = battles_message(&state, "battles").unwrap();
+
+        let listed: Vec<_> = message.lines().filter(|line| line.trim_start().starts_with('#')).collect();
+        assert_eq!(listed.len(), 2);
+        assert!(listed[0].contains("#3") && listed[0].contains("Bob attacked Alice, Bob won"));
+        assert!(listed[1].contains("#1"));
+        assert!(battles_message(&state, "battles 1").unwrap().contains("1 older battle(s)"));
+    }
+
+    #[test]
+    fn test_show_retells_the_battle_from_the_report() {
+        let mut state = fixture_game(1);
+        let mut battle = report(1, "alice", "bob");
+        battle.observers = vec![String::from("carol")];
+        state.battles = vec![battle, report(2, "bob", "carol")];
+
+        let message = battles_message(&state, "battles show 1").unwrap();
+
+        assert!(message.contains("Alice attacked with fleet fleet_1: 2 interceptor"));
+        assert!(message.contains("Round 1:\n  Alice struck with 30 attack (2 ship(s) with a counter bonus)"));
+        assert!(message.contains("Bob lost 1 ravager (ravager_1)"));
+        assert!(message.contains("Observed by: carol"));
+        assert!(matches!(battles_message(&state, "battles show 2"), Err(CommandError::InvalidArgument { .. })));
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_counts_and_ids() {
+        assert!(matches!(BattlesArgs::parse(vec!["0"]), Err(CommandError::InvalidArgument { .. })));
+        assert!(matches!(BattlesArgs::parse(vec!["show"]), Err(CommandError::MissingArguments { .. })));
+        assert!(matches!(BattlesArgs::parse(vec!["show", "#7"]), Ok(BattlesArgs::Show { id: 7 })));
+    }
+}
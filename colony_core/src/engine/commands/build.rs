@@ -0,0 +1,107 @@
+use crate::engine::commands::parser::{self, Parseable};
+use crate::engine::game_state::GameState;
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::rules::{self, Reason};
+use crate::engine::utils;
+
+pub struct BuildArgs {
+    pub planet_name: String,
+    pub structure_name: String,
+    /// Only show what the build would cost, set by `--preview` or a trailing `?`
+    pub preview: bool,
+}
+
+impl Parseable for BuildArgs {
+    fn parse(mut args: Vec<&str>) -> Result<Self, CommandError> {
+        let preview = parser::take_preview_flag(&mut args);
+        if args.len() < 2 {
+            return Err(CommandError::MissingArguments {
+                command: String::from("build"),
+                expected: String::from("build <planet_name> <structure_name> [--preview]"),
+            });
+        }
+        Ok(BuildArgs {
+            planet_name: args[0].to_string(),
+            structure_name: args[1].to_string(),
+            preview,
+        })
+    }
+}
+
+pub fn execute(args: BuildArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let (planet_id, planet) = CommandError::own_planet(&args.planet_name, game_state)?;
+
+    // Check structure type is valid
+    let definition = game_state.structure_config.find_by_name(&args.structure_name)
+        .ok_or_else(|| CommandError::UnknownStructure {
+            name: args.structure_name.clone(),
+            suggestions: game_state.structure_config.suggest_similar(&utils::name_to_id(&args.structure_name)),
+        })?;
+
+    let player = CommandError::acting_player(game_state)?;
+    rules::can_build(planet, &definition, player, game_state).map_err(|reason| match reason {
+        Reason::NotYourPlanet => CommandError::WrongPlanetOwner(args.planet_name.clone()),
+        Reason::PlanetNotOwned => CommandError::PlanetNotOwned(args.planet_name.clone()),
+        Reason::NotEnoughResources { cost, .. } => CommandError::NotEnoughResources { planet_name: planet.name.clone(), cost },
+        reason => CommandError::NotAllowed(reason),
+    })?;
+
+    if args.preview {
+        let preview = game_state.preview_structure_build(game_state.current_player(), &planet_id, &definition.id)
+            .map_err(|e| CommandError::NotAllowed(Reason::Blocked(e)))?;
+        return Ok(CommandEffect::None { message: preview.to_string() });
+    }
+
+    Ok(CommandEffect::BuildStructure { planet_id, structure_id: definition.id.clone() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::resources::Resources;
+    use crate::test_support::{self, fixture_game};
+
+    fn check(state: &GameState, planet_name: &str, structure_name: &str) -> Result<CommandEffect, CommandError> {
+        execute(BuildArgs::parse(vec![planet_name, structure_name])?, state)
+    }
+
+    #[test]
+    fn test_build_maps_rule_reasons_to_command_errors() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let bob_home = test_support::home_planet(&state, "bob");
+        let bob_home_name = state.map.planets[&bob_home].name.clone();
+
+        let result = check(&state, &bob_home_name, "power_grid");
+        assert!(matches!(result, Err(CommandError::NotYourPlanet(name)) if name == bob_home_name));
+        // A scout in orbit shows whose it is
+        state.players.get_mut("alice").unwrap().add_ship(String::from("scout"), bob_home.clone());
+        let result = check(&state, &bob_home_name, "power_grid");
+        assert!(matches!(result, Err(CommandError::WrongPlanetOwner(name)) if name == bob_home_name));
+
+        state.map.planets.get_mut(&home).unwrap().available_resources = Resources::default();
+        let home_name = state.map.planets[&home].name.clone();
+        let result = check(&state, &home_name, "power_grid");
+        assert!(matches!(result, Err(CommandError::NotEnoughResources { planet_name, .. }) if planet_name == home_name));
+
+        let result = check(&state, &home_name, "mining_complex");
+        assert!(matches!(result, Err(CommandError::NotAllowed(_))));
+    }
+
+    #[test]
+    fn test_build_preview_shows_the_build_without_queueing_it() {
+        let state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let home_name = state.map.planets[&home].name.clone();
+
+        for args in [vec![home_name.as_str(), "power_grid", "--preview"], vec![home_name.as_str(), "power_grid?"]] {
+            let parsed = BuildArgs::parse(args).unwrap();
+            assert!(parsed.preview);
+            assert_eq!(parsed.structure_name, "power_grid");
+            let Ok(CommandEffect::None { message }) = execute(parsed, &state) else {
+                panic!("Expected a preview message")
+            };
+            assert!(message.starts_with("Preview, nothing queued."), "{message}");
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use crate::engine::commands::parser::Parseable;
+use crate::engine::game_state::GameState;
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::planet::PlanetId;
+use crate::engine::rules::{self, Reason};
+use crate::engine::utils;
+
+pub struct BuildAllArgs {
+    pub structure_name: String,
+    /// Leave planets that cannot pay out of the result table, set by `--only-affordable`
+    pub only_affordable: bool,
+}
+
+impl Parseable for BuildAllArgs {
+    fn parse(mut args: Vec<&str>) -> Result<Self, CommandError> {
+        let only_affordable = args.contains(&"--only-affordable");
+        args.retain(|arg| *arg != "--only-affordable");
+        if args.is_empty() {
+            return Err(CommandError::MissingArguments {
+                command: String::from("build_all"),
+                expected: String::from("build_all <structure_name> [--only-affordable]"),
+            });
+        }
+        Ok(BuildAllArgs {
+            structure_name: args[0].to_string(),
+            only_affordable,
+        })
+    }
+}
+
+/// Checks the structure build on every planet of the current player, with the same rules as
+/// `build`. Planets that pass are queued, the others are listed with the reason.
+pub fn execute(args: BuildAllArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let definition = game_state.structure_config.find_by_name(&args.structure_name)
+        .ok_or_else(|| CommandError::UnknownStructure {
+            name: args.structure_name.clone(),
+            suggestions: game_state.structure_config.suggest_similar(&utils::name_to_id(&args.structure_name)),
+        })?;
+
+    let player = CommandError::acting_player(game_state)?;
+
+    let mut queued: Vec<PlanetId> = Vec::new();
+    let mut skipped: Vec<(PlanetId, String)> = Vec::new();
+    for planet in player.planets.iter().filter_map(|planet_id| game_state.map.planets.get(planet_id)) {
+        match rules::can_build(planet, &definition, player, game_state) {
+            Ok(_) => queued.push(planet.id.clone()),
+            Err(Reason::NotEnoughResources { .. }) if args.only_affordable => {}
+            Err(reason) => skipped.push((planet.id.clone(), reason.to_string())),
+        }
+    }
+
+    Ok(CommandEffect::BuildStructureOnPlanets {
+        structure_id: definition.id.clone(),
+        queued,
+        skipped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::game_event::GameEvent;
+    use crate::engine::resources::Resources;
+    use crate::test_support::{self, fixture_game};
+
+    /// Alice's home already has a power grid, her first colony is broke and the second
+    /// can pay for anything.
+    fn three_planet_game() -> (GameState, [PlanetId; 3]) {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let broke = test_support::grant_planet(&mut state, "alice");
+        let rich = test_support::grant_planet(&mut state, "alice");
+
+        let config = &state.structure_config;
+        state.map.planets.get_mut(&home).unwrap()
+            .complete_build_structure(String::from("power_grid"), config).unwrap();
+        state.map.planets.get_mut(&broke).unwrap().available_resources = Resources { minerals: 10, gas: 0, energy: 0 };
+        (state, [home, broke, rich])
+    }
+
+    fn run(state: &mut GameState, args: Vec<&str>) -> Vec<GameEvent> {
+        let effect = execute(BuildAllArgs::parse(args).unwrap(), state).unwrap();
+        let acting_player = state.current_player().clone();
+        state.apply_effect(effect, &acting_player).unwrap()
+    }
+
+    fn results(events: &[GameEvent]) -> Vec<(String, Option<String>)> {
+        match events.last() {
+            Some(GameEvent::StructureQueuedOnPlanets { results, .. }) => results.clone(),
+            other => panic!("Expected StructureQueuedOnPlanets, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_build_all_queues_where_possible_and_lists_the_rest() {
+        let (mut state, [home, broke, rich]) = three_planet_game();
+
+        let events = run(&mut state, vec!["power_grid"]);
+
+        let queued: Vec<_> = state.players["alice"].pending_actions.iter().map(|action| &action.planet_id).collect();
+        assert_eq!(queued, [&rich]);
+        let results = results(&events);
+        assert_eq!(results.len(), 3);
+        let reason_for = |planet_id: &PlanetId| results.iter()
+            .find(|(name, _)| name == &state.map.planets[planet_id].name)
+            .unwrap().1.clone();
+        assert_eq!(reason_for(&rich), None);
+        assert!(reason_for(&home).unwrap().contains("already exists"));
+        assert_eq!(reason_for(&broke).unwrap(), "not enough resources, short Resources { minerals: 40, gas: 0, energy: 0 }");
+    }
+
+    #[test]
+    fn test_build_all_only_affordable_leaves_out_poor_planets() {
+        let (mut state, [home, broke, _]) = three_planet_game();
+
+        let events = run(&mut state, vec!["power_grid", "--only-affordable"]);
+
+        let names: Vec<_> = results(&events).into_iter().map(|(name, _)| name).collect();
+        assert!(names.contains(&state.map.planets[&home].name));
+        assert!(!names.contains(&state.map.planets[&broke].name));
+    }
+
+    #[test]
+    fn test_build_all_respects_structure_limits_across_planets() {
+        let (mut state, _) = three_planet_game();
+
+        // The command nexus is limited to one per player, so only the first affordable planet gets it
+        let events = run(&mut state, vec!["command_nexus", "--only-affordable"]);
+
+        assert_eq!(state.players["alice"].pending_actions.len(), 1);
+        let results = results(&events);
+        assert_eq!(results.iter().filter(|(_, skipped)| skipped.is_none()).count(), 1);
+        assert!(results.iter().any(|(_, skipped)| skipped.as_deref().is_some_and(|reason| reason.contains("limited to"))));
+    }
+}
@@ -0,0 +1,169 @@
+use crate::engine::commands::parser::{self, Parseable};
+use crate::engine::game_state::{ConstructionKind, GameState, ModifierContext};
+use crate::engine::planet::PlanetId;
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::rules::Reason;
+
+const USAGE: &str = "build_ship <planet_name> <ship_type> [deliver_to <planet_name>] [--preview]";
+
+pub struct BuildShipArgs {
+    pub planet_name: String,
+    pub ship_name: String,
+    /// Planet the finished ship is carried to, set by `deliver_to <planet_name>`
+    pub deliver_to: Option<String>,
+    /// Only show what the ship would cost, set by `--preview` or a trailing `?`
+    pub preview: bool,
+}
+
+impl Parseable for BuildShipArgs {
+    fn parse(mut args: Vec<&str>) -> Result<Self, CommandError> {
+        let preview = parser::take_preview_flag(&mut args);
+        let deliver_to = match args.get(2..) {
+            None | Some([]) => None,
+            Some(["deliver_to", planet_name]) => Some(planet_name.to_string()),
+            Some(_) => {
+                return Err(CommandError::MissingArguments {
+                    command: String::from("build_ship"),
+                    expected: String::from(USAGE),
+                });
+            }
+        };
+        if args.len() < 2 {
+            return Err(CommandError::MissingArguments {
+                command: String::from("build_ship"),
+                expected: String::from(USAGE),
+            });
+        }
+        Ok(BuildShipArgs {
+            planet_name: args[0].to_string(),
+            ship_name: args[1].to_string(),
+            deliver_to,
+            preview,
+        })
+    }
+}
+
+pub fn execute(args: BuildShipArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let (planet_id, planet) = CommandError::own_planet(&args.planet_name, game_state)?;
+
+    // Check ship type is valid
+    let ship_def = game_state.ship_config.find_by_name(&args.ship_name)
+        .ok_or(CommandError::UnknownShip(args.ship_name.clone()))?;
+
+    // Check orbital_shipyard level requirement
+    let shipyard_level = planet.get_structure_level(&String::from("orbital_shipyard"));
+    if shipyard_level < ship_def.required_shipyard_level {
+        return Err(CommandError::ShipyardLevelTooLow {
+            required: ship_def.required_shipyard_level,
+            current: shipyard_level,
+        });
+    }
+
+    // Check the delivery planet is also the player's, and that ships can get there
+    let deliver_to = match &args.deliver_to {
+        Some(planet_name) => Some(delivery_planet(planet_name, &planet_id, game_state)?),
+        None => None,
+    };
+
+    // Check planet has enough resources for the cost after modifiers
+    let context = ModifierContext {
+        player_id: game_state.current_player(),
+        planet_id: &planet_id,
+        kind: ConstructionKind::ShipBuild,
+    };
+    let cost = game_state.resolve_cost(&ship_def.cost, &context);
+    if !planet.available_resources.has_enough(&cost) {
+        return Err(CommandError::NotEnoughResources {
+            planet_name: args.planet_name.clone(),
+            cost,
+        });
+    }
+
+    if args.preview {
+        let preview = game_state.preview_ship_build(game_state.current_player(), &planet_id, &ship_def.id, deliver_to.as_ref())
+            .map_err(|e| CommandError::NotAllowed(Reason::Blocked(e)))?;
+        return Ok(CommandEffect::None { message: preview.to_string() });
+    }
+
+    Ok(CommandEffect::BuildShip { planet_id, ship_id: ship_def.id.clone(), deliver_to })
+}
+
+/// Resolves the planet a ship built on `planet_id` is to be delivered to: one the player owns,
+/// reachable from the shipyard.
+fn delivery_planet(planet_name: &str, planet_id: &PlanetId, game_state: &GameState) -> Result<PlanetId, CommandError> {
+    let (delivery_id, _) = CommandError::own_planet(planet_name, game_state)?;
+    if game_state.map.graph().distance(planet_id, &delivery_id).is_none() {
+        return Err(CommandError::NotAllowed(Reason::Unreachable { from: planet_id.clone(), to: delivery_id }));
+    }
+    Ok(delivery_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::resources::Resources;
+    use crate::engine::utils;
+    use crate::test_support::{self, fixture_game};
+
+    /// Alice's home with a shipyard and a second planet of hers, by name.
+    fn shipyard_and_colony(state: &mut GameState) -> (String, String) {
+        let home = test_support::home_planet(state, "alice");
+        let colony = test_support::grant_planet(state, "alice");
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.available_resources = Resources { minerals: 1000, gas: 1000, energy: 1000 };
+        planet.complete_build_structure(String::from("orbital_shipyard"), &state.structure_config).unwrap();
+        (state.map.planets[&home].name.clone(), state.map.planets[&colony].name.clone())
+    }
+
+    fn check(state: &GameState, args: &[&str]) -> Result<CommandEffect, CommandError> {
+        execute(BuildShipArgs::parse(args.to_vec())?, state)
+    }
+
+    #[test]
+    fn test_delivery_is_an_optional_trailing_pair() {
+        let parsed = BuildShipArgs::parse(vec!["Kepler", "interceptor", "deliver_to", "Vega", "--preview"]).unwrap();
+        assert_eq!(parsed.deliver_to.as_deref(), Some("Vega"));
+        assert!(parsed.preview);
+        assert!(BuildShipArgs::parse(vec!["Kepler", "interceptor"]).unwrap().deliver_to.is_none());
+
+        for args in [vec!["Kepler", "interceptor", "deliver_to"], vec!["Kepler", "interceptor", "Vega"]] {
+            assert!(matches!(BuildShipArgs::parse(args), Err(CommandError::MissingArguments { .. })));
+        }
+    }
+
+    #[test]
+    fn test_delivery_planet_must_be_ones_own() {
+        let mut state = fixture_game(1);
+        let (home_name, colony_name) = shipyard_and_colony(&mut state);
+        let bob_home_name = state.map.planets[&test_support::home_planet(&state, "bob")].name.clone();
+        let neutral_name = state.map.planets.values().find(|planet| planet.get_owner().is_none()).unwrap().name.clone();
+
+        let to = |target: &str| check(&state, &[&home_name, "interceptor", "deliver_to", target]);
+        for target in [bob_home_name.as_str(), neutral_name.as_str(), "Nowhere"] {
+            assert!(matches!(to(target), Err(CommandError::NotYourPlanet(_))), "{target}");
+        }
+
+        let colony = utils::name_to_id(&colony_name);
+        assert!(matches!(to(&colony_name), Ok(CommandEffect::BuildShip { deliver_to: Some(planet_id), .. }) if planet_id == colony));
+    }
+
+    #[test]
+    fn test_delivery_adds_the_travel_time_to_the_build() {
+        let mut state = fixture_game(1);
+        let (home_name, colony_name) = shipyard_and_colony(&mut state);
+        let (home, colony) = (utils::name_to_id(&home_name), utils::name_to_id(&colony_name));
+        let alice = String::from("alice");
+        let ship_id = String::from("interceptor");
+        let distance = state.map.graph().distance(&home, &colony).unwrap();
+
+        let local = state.preview_ship_build(&alice, &home, &ship_id, None).unwrap();
+        let delivered = state.preview_ship_build(&alice, &home, &ship_id, Some(&colony)).unwrap();
+        assert_eq!(delivered.turns, local.turns + distance);
+        assert_eq!(delivered.cost, local.cost);
+
+        let effect = check(&state, &[&home_name, "interceptor", "deliver_to", &colony_name]).unwrap();
+        state.apply_effect(effect, &alice).unwrap();
+        let action = &state.players[&alice].pending_actions[0];
+        assert_eq!((action.cooldown_remaining, action.deliver_to.as_ref()), (delivered.turns, Some(&colony)));
+    }
+}
@@ -0,0 +1,57 @@
+use crate::engine::commands::parser::Parseable;
+use crate::engine::game_state::GameState;
+use crate::engine::commands::command::{CommandEffect, CommandError};
+
+pub struct CancelArgs {
+    pub planet_name: String,
+    /// Structure, ship or fleet id of the action to cancel; optional when the planet has only one
+    pub target_id: Option<String>,
+}
+
+impl Parseable for CancelArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::MissingArguments {
+                command: String::from("cancel"),
+                expected: String::from("cancel <planet_name> [structure_id|ship_id]"),
+            });
+        }
+        Ok(CancelArgs {
+            planet_name: args[0].to_string(),
+            target_id: args.get(1).map(|target| target.to_string()),
+        })
+    }
+}
+
+pub fn execute(args: CancelArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let (planet_id, _) = CommandError::own_planet(&args.planet_name, game_state)?;
+
+    // Check if there's a pending action on this planet
+    let player = CommandError::acting_player(game_state)?;
+
+    let targets: Vec<_> = player.pending_actions_on_planet(&planet_id)
+        .map(|action| action.target_id().to_string())
+        .collect();
+
+    let target_id = match (args.target_id, targets.as_slice()) {
+        (_, []) => return Err(CommandError::InvalidArgument {
+            command: String::from("cancel"),
+            argument: args.planet_name.clone(),
+            reason: String::from("No pending action on this planet"),
+        }),
+        (Some(target), _) if targets.contains(&target) => target,
+        (Some(target), _) => return Err(CommandError::InvalidArgument {
+            command: String::from("cancel"),
+            argument: target,
+            reason: format!("No pending action for it on this planet. Pending: {}", targets.join(", ")),
+        }),
+        (None, [only]) => only.clone(),
+        (None, _) => return Err(CommandError::InvalidArgument {
+            command: String::from("cancel"),
+            argument: args.planet_name.clone(),
+            reason: format!("Multiple pending actions, specify one of: {}", targets.join(", ")),
+        }),
+    };
+
+    Ok(CommandEffect::CancelAction { planet_id, target_id })
+}
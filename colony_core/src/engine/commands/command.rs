@@ -0,0 +1,507 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::engine::commands::advisor;
+use crate::engine::commands::build::{self, BuildArgs};
+use crate::engine::commands::build_all::{self, BuildAllArgs};
+use crate::engine::commands::build_ship::{self, BuildShipArgs};
+use crate::engine::commands::cancel::{self, CancelArgs};
+use crate::engine::commands::end_turn;
+use crate::engine::commands::export::{self, ExportArgs};
+use crate::engine::commands::fleet::{self, FleetArgs};
+use crate::engine::commands::fleets;
+use crate::engine::commands::help::{self, HelpArgs};
+use crate::engine::commands::alias::{self, AliasArgs};
+use crate::engine::commands::note::{self, NoteArgs};
+use crate::engine::commands::inspect::{self, InspectArgs};
+use crate::engine::commands::intel::{self, IntelArgs};
+use crate::engine::commands::forecast::{self, ForecastArgs};
+use crate::engine::commands::defense::{self, DefenseArgs};
+use crate::engine::commands::dev::{self, DevAction, DevArgs};
+use crate::engine::commands::diff::{self, DiffArgs};
+use crate::engine::commands::log::{self, LogArgs};
+use crate::engine::commands::battles::{self, BattlesArgs};
+use crate::engine::commands::map::{self, MapArgs};
+use crate::engine::commands::parser::{END_TURN_ALIASES, EXIT_ALIASES};
+use crate::engine::commands::planet::{self, PlanetArgs};
+use crate::engine::commands::save::{self, SaveArgs, SavesArgs};
+use crate::engine::commands::schedule;
+use crate::engine::commands::set::{self, SetArgs};
+use crate::engine::commands::options::{self as options_command, OptionsArgs};
+use crate::engine::commands::ship::{self, ShipArgs};
+use crate::engine::commands::ships;
+use crate::engine::commands::status::{self, StatusArgs};
+use crate::engine::commands::structures::{self, StructuresArgs};
+use crate::engine::commands::upgrade::{self, UpgradeArgs};
+use crate::engine::configs::ship_config::ShipId;
+use crate::engine::fleet::FleetTemplate;
+use crate::engine::game_state::{GamePhase, GameState};
+use crate::engine::options::{GameOption, SessionOption};
+use crate::engine::planet::{Allocation, Planet, PlanetId, RallyPoint};
+use crate::engine::player::{Player, PlayerId};
+use crate::engine::resources::Resources;
+use crate::engine::rules::Reason;
+use crate::engine::ship::{FleetId, ShipInstanceId};
+use crate::engine::structure::StructureId;
+use crate::engine::utils;
+
+#[derive(Debug, thiserror::Error)]
+pub enum CommandError {
+    #[error("No command was entered")]
+    NoCommandEntered,
+
+    #[error("Missing arguments for command: {command}. Expected: {expected}")]
+    MissingArguments {
+        command: String,
+        expected: String
+    },
+
+    #[error("Unknown command: {0}")]
+    UnknownCommand(String),
+
+    #[error("Command {0} is a developer tool; start the game with --dev to use it")]
+    DevOnly(String),
+
+    #[error("The game has not started yet: it has no players")]
+    GameNotStarted,
+
+    #[error("The game has ended: {0} is no longer possible. Type 'rematch' for a new game or 'exit' to leave")]
+    GameEnded(String),
+
+    #[error("Command {0} is only available once the game has ended")]
+    GameNotOver(String),
+
+    #[error("Command {0} is not available until every player has picked a starting planet")]
+    StillInSetup(String),
+
+    #[error("No charted planet matches '{0}'")]
+    UnknownPlanet(String),
+
+    /// A planet the player must own that is not theirs and out of their sensor range, or that
+    /// does not exist: the two are reported alike so neither is confirmed
+    #[error("None of your planets matches '{0}'")]
+    NotYourPlanet(String),
+
+    #[error("Player {0} does not exist")]
+    UnknownPlayer(String),
+    
+    #[error("Structure {name} does not exist{}", suggestion_hint(.suggestions))]
+    UnknownStructure {
+        name: String,
+        suggestions: Vec<StructureId>,
+    },
+
+    #[error("Planet {0} is not owned by anyone")]
+    PlanetNotOwned(String),
+
+    #[error("Planet is not owned by {0}")]
+    WrongPlanetOwner(String),
+
+    #[error("Invalid argument '{argument}' for command '{command}': {reason}")]
+    InvalidArgument {
+        command: String,
+        argument: String,
+        reason: String,
+    },
+
+    #[error("Ship type {0} does not exist")]
+    UnknownShip(String),
+
+    #[error("Ship {ship_id} is still under construction ({turns} turn(s) remaining)")]
+    ShipUnderConstruction {
+        ship_id: String,
+        turns: u32,
+    },
+
+    #[error("Shipyard level too low: requires level {required}, current level {current}")]
+    ShipyardLevelTooLow {
+        required: u16,
+        current: u16,
+    },
+
+    #[error("Planet {planet_name} does not have enough resources. Cost: {cost}")]
+    NotEnoughResources {
+        planet_name: String,
+        cost: Resources,
+    },
+
+    #[error(transparent)]
+    NotAllowed(#[from] Reason),
+
+    #[error("{kind} {id} belongs to {owner}")]
+    NotYourAsset {
+        kind: AssetKind,
+        id: String,
+        /// Name of the owning player
+        owner: String,
+        owner_id: PlayerId,
+    },
+
+    #[error("It is currently {current}'s turn{}", own_asset_hint(.asset))]
+    NotYourTurn {
+        /// Name of the player whose turn it is
+        current: String,
+        /// The issuer's own fleet or ship the command referred to, if any
+        asset: Option<(AssetKind, String)>,
+    },
+}
+
+/// Kind of player-owned asset a command referred to by id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetKind {
+    Fleet,
+    Ship,
+}
+
+impl fmt::Display for AssetKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssetKind::Fleet => write!(f, "Fleet"),
+            AssetKind::Ship => write!(f, "Ship"),
+        }
+    }
+}
+
+impl CommandError {
+    /// Error for a fleet id the current player does not have: NotYourAsset if another player
+    /// has a fleet by that id, otherwise "fleet not found".
+    pub(crate) fn fleet_not_found(command: &str, fleet_id: &FleetId, game_state: &GameState) -> Self {
+        match game_state.find_fleet_any(fleet_id) {
+            Some((owner, _)) => Self::not_your_asset(AssetKind::Fleet, fleet_id, &owner, game_state),
+            None => CommandError::InvalidArgument {
+                command: command.to_string(),
+                argument: fleet_id.clone(),
+                reason: String::from("fleet not found"),
+            },
+        }
+    }
+
+    /// Same as [`CommandError::fleet_not_found`], for ships.
+    pub(crate) fn ship_not_found(command: &str, ship_id: &ShipInstanceId, game_state: &GameState) -> Self {
+        match game_state.find_ship_any(ship_id) {
+            Some((owner, _)) => Self::not_your_asset(AssetKind::Ship, ship_id, &owner, game_state),
+            None => CommandError::InvalidArgument {
+                command: command.to_string(),
+                argument: ship_id.clone(),
+                reason: String::from("ship not found"),
+            },
+        }
+    }
+
+    /// The record of the player whose turn it is, or GameNotStarted before there are players.
+    pub(crate) fn acting_player(game_state: &GameState) -> Result<&Player, Self> {
+        let player_id = game_state.checked_current_player().map_err(|_| CommandError::GameNotStarted)?;
+        game_state.players.get(player_id).ok_or_else(|| CommandError::UnknownPlayer(player_id.clone()))
+    }
+
+    /// Looks up `planet_name` among the acting player's planets. A planet in their sensor range
+    /// that is someone else's or nobody's is reported as such; one out of range gets the same
+    /// error as a name matching no planet, so commands never tell what the player has not seen.
+    pub(crate) fn own_planet<'a>(planet_name: &str, game_state: &'a GameState) -> Result<(PlanetId, &'a Planet), Self> {
+        let player = Self::acting_player(game_state)?;
+        let planet_id = utils::name_to_id(planet_name);
+        let not_yours = || CommandError::NotYourPlanet(planet_name.to_string());
+        let planet = game_state.map.planets.get(&planet_id).ok_or_else(not_yours)?;
+        match planet.get_owner() {
+            Some(owner) if owner == &player.id => Ok((planet_id, planet)),
+            _ if game_state.fogged_view(&player.id, &planet_id).is_some() => Err(not_yours()),
+            Some(_) => Err(CommandError::WrongPlanetOwner(planet_name.to_string())),
+            None => Err(CommandError::PlanetNotOwned(planet_name.to_string())),
+        }
+    }
+
+    fn not_your_asset(kind: AssetKind, id: &str, owner: &PlayerId, game_state: &GameState) -> Self {
+        let owner_id = owner.clone();
+        let owner = game_state.players.get(owner)
+            .map_or_else(|| owner.clone(), |player| player.name.clone());
+        CommandError::NotYourAsset { kind, id: id.to_string(), owner, owner_id }
+    }
+}
+
+fn own_asset_hint(asset: &Option<(AssetKind, String)>) -> String {
+    match asset {
+        Some((kind, id)) => format!("; {} {} belongs to you, wait for your turn", kind.to_string().to_lowercase(), id),
+        None => String::from("; wait for your turn"),
+    }
+}
+
+fn suggestion_hint(suggestions: &[StructureId]) -> String {
+    if suggestions.is_empty() {
+        String::new()
+    } else {
+        format!(". Did you mean: {}?", suggestions.join(", "))
+    }
+}
+
+pub enum Command {
+    Build(BuildArgs),
+    BuildAll(BuildAllArgs),
+    BuildShip(BuildShipArgs),
+    Upgrade(UpgradeArgs),
+    Cancel(CancelArgs),
+    Status(StatusArgs),
+    Intel(IntelArgs),
+    Inspect(InspectArgs),
+    Dev(DevArgs),
+    Log(LogArgs),
+    Battles(BattlesArgs),
+    Forecast(ForecastArgs),
+    Defense(DefenseArgs),
+    Diff(DiffArgs),
+    Structures(StructuresArgs),
+    Map(MapArgs),
+    Ships,
+    Fleets,
+    Schedule,
+    Advisor,
+    Fleet(FleetArgs),
+    Planet(PlanetArgs),
+    Ship(ShipArgs),
+    Help(HelpArgs),
+    Save(SaveArgs),
+    Load(SaveArgs),
+    Saves(SavesArgs),
+    Export(ExportArgs),
+    Alias(AliasArgs),
+    Note(NoteArgs),
+    Set(SetArgs),
+    Options(OptionsArgs),
+    EndTurn,
+    Exit,
+    Rematch,
+    /// Panics, so tests can check what a crash leaves behind
+    #[cfg(test)]
+    Crash,
+}
+
+impl Command {
+    /// Executes the command for `issuer`, who may be someone other than the player whose turn
+    /// it is when players type from their own connections. Commands that act for or show the
+    /// game to the current player are refused for anyone else. When the command refers to one
+    /// of the issuer's own fleets or ships, the error says so; one belonging to a third player
+    /// is reported as theirs.
+    pub fn execute_as(self, game_state: &GameState, issuer: &PlayerId) -> Result<CommandEffect, CommandError> {
+        let current = match game_state.checked_current_player() {
+            Ok(current) if current != issuer && self.needs_players() => current,
+            _ => return self.execute(game_state),
+        };
+        let current = game_state.players.get(current).map_or_else(|| current.clone(), |player| player.name.clone());
+        // Validation looks assets up as the current player's, so an asset of the issuer's shows
+        // up as someone else's
+        match self.execute(game_state) {
+            Err(CommandError::NotYourAsset { kind, id, owner_id, .. }) if &owner_id == issuer => {
+                Err(CommandError::NotYourTurn { current, asset: Some((kind, id)) })
+            }
+            Err(error @ CommandError::NotYourAsset { .. }) => Err(error),
+            _ => Err(CommandError::NotYourTurn { current, asset: None }),
+        }
+    }
+
+    pub fn execute(self, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+        if !self.allowed_in(game_state.game_phase) {
+            let name = String::from(self.name());
+            return Err(match game_state.game_phase {
+                GamePhase::Setup => CommandError::StillInSetup(name),
+                GamePhase::Playing => CommandError::GameNotOver(name),
+                GamePhase::Finished => CommandError::GameEnded(name),
+            });
+        }
+        // Validators take the current player for granted
+        if self.needs_players() {
+            CommandError::acting_player(game_state)?;
+        }
+        match self {
+            Command::Build(args) => build::execute(args, game_state),
+            Command::BuildAll(args) => build_all::execute(args, game_state),
+            Command::BuildShip(args) => build_ship::execute(args, game_state),
+            Command::Upgrade(args) => upgrade::execute(args, game_state),
+            Command::Cancel(args) => cancel::execute(args, game_state),
+            Command::Status(args) => status::execute(args, game_state),
+            Command::Intel(args) => intel::execute(args, game_state),
+            Command::Inspect(args) => inspect::execute(args, game_state),
+            Command::Dev(args) => dev::execute(args, game_state),
+            Command::Log(args) => log::execute(args, game_state),
+            Command::Battles(args) => battles::execute(args, game_state),
+            Command::Forecast(args) => forecast::execute(args, game_state),
+            Command::Defense(args) => defense::execute(args, game_state),
+            Command::Diff(args) => diff::execute(args, game_state),
+            Command::Structures(args) => structures::execute(args, game_state),
+            Command::Map(args) => map::execute(args, game_state),
+            Command::Ships => ships::execute(game_state),
+            Command::Fleets => fleets::execute(game_state),
+            Command::Schedule => schedule::execute(game_state),
+            Command::Advisor => advisor::execute(game_state),
+            Command::Fleet(args) => fleet::execute(args, game_state),
+            Command::Planet(args) => planet::execute(args, game_state),
+            Command::Ship(args) => ship::execute(args, game_state),
+            Command::Help(args) => help::execute(args, game_state),
+            Command::Save(args) => save::execute_save(args),
+            Command::Load(args) => save::execute_load(args),
+            Command::Saves(args) => save::execute_saves(args),
+            Command::Export(args) => export::execute(args),
+            Command::Alias(args) => alias::execute(args, game_state),
+            Command::Note(args) => note::execute(args, game_state),
+            Command::Set(args) => set::execute(args),
+            Command::Options(args) => options_command::execute(args),
+            Command::EndTurn => end_turn::execute(game_state),
+            Command::Exit => Ok(CommandEffect::Exit),
+            Command::Rematch => Ok(CommandEffect::Rematch),
+            #[cfg(test)]
+            Command::Crash => panic!("Crash requested by a test"),
+        }
+    }
+
+    /// Whether the command acts for or shows the game to the current player, so it cannot run
+    /// before the game has players. Help, saves and session settings can.
+    fn needs_players(&self) -> bool {
+        !matches!(
+            self,
+            Command::Help(_)
+                | Command::Save(_)
+                | Command::Load(_)
+                | Command::Saves(_)
+                | Command::Set(_)
+                | Command::Options(_)
+                | Command::Exit
+                | Command::Rematch
+        )
+    }
+
+    /// Whether the command can be used in `phase`. Orders only go in while the game is
+    /// played and a rematch only starts once it is over; views and session commands work
+    /// throughout.
+    pub fn allowed_in(&self, phase: GamePhase) -> bool {
+        match self {
+            Command::Rematch => phase == GamePhase::Finished,
+            command => phase == GamePhase::Playing || !command.is_order(),
+        }
+    }
+
+    /// Whether the command is a move in the game, as opposed to a view, a personal setting
+    /// or a session command. Orders go to the action log.
+    pub fn is_order(&self) -> bool {
+        match self {
+            // Previews only show what the order would do
+            Command::Build(args) => !args.preview,
+            Command::BuildShip(args) => !args.preview,
+            Command::Upgrade(args) => !args.preview,
+            command => matches!(
+                command,
+                Command::BuildAll(_)
+                    | Command::Cancel(_)
+                    | Command::Fleet(_)
+                    | Command::Planet(_)
+                    | Command::Ship(_)
+                    | Command::EndTurn
+            ),
+        }
+    }
+
+    /// The command word this command is typed with; the canonical one for commands with aliases.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::Build(_) => "build",
+            Command::BuildAll(_) => "build_all",
+            Command::BuildShip(_) => "build_ship",
+            Command::Upgrade(_) => "upgrade",
+            Command::Cancel(_) => "cancel",
+            Command::Status(_) => "status",
+            Command::Intel(_) => "intel",
+            Command::Inspect(_) => "inspect",
+            Command::Dev(_) => "dev",
+            Command::Log(_) => "log",
+            Command::Battles(_) => "battles",
+            Command::Forecast(_) => "forecast",
+            Command::Defense(_) => "defense",
+            Command::Diff(_) => "diff",
+            Command::Structures(_) => "structures",
+            Command::Map(_) => "map",
+            Command::Ships => "ships",
+            Command::Fleets => "fleets",
+            Command::Schedule => "schedule",
+            Command::Advisor => "advisor",
+            Command::Fleet(_) => "fleet",
+            Command::Planet(_) => "planet",
+            Command::Ship(_) => "ship",
+            Command::Help(_) => "help",
+            Command::Save(_) => "save",
+            Command::Load(_) => "load",
+            Command::Saves(_) => "saves",
+            Command::Export(_) => "export",
+            Command::Alias(_) => "alias",
+            Command::Note(_) => "note",
+            Command::Set(_) => "set",
+            Command::Options(_) => "options",
+            Command::EndTurn => END_TURN_ALIASES[0],
+            Command::Exit => EXIT_ALIASES[0],
+            Command::Rematch => "rematch",
+            #[cfg(test)]
+            Command::Crash => "crash!",
+        }
+    }
+}
+
+pub enum CommandEffect {
+    None { message: String },
+    BuildStructure { planet_id: PlanetId, structure_id: StructureId },
+    /// Planets planned to get the structure, plus the ones left out with the reason why
+    BuildStructureOnPlanets { structure_id: StructureId, queued: Vec<PlanetId>, skipped: Vec<(PlanetId, String)> },
+    /// With `auto_repeat`, each completed level queues the next one until max level or unaffordable
+    UpgradeStructure { planet_id: PlanetId, structure_id: StructureId, auto_repeat: bool },
+    /// With `deliver_to`, the ship is carried to that planet once built, taking the route's
+    /// travel time on top of the build
+    BuildShip { planet_id: PlanetId, ship_id: ShipId, deliver_to: Option<PlanetId> },
+    CancelAction { planet_id: PlanetId, target_id: String },
+    CreateFleet { name: String, ship_ids: Vec<ShipInstanceId>, location: PlanetId },
+    AddToFleet { fleet_id: FleetId, ship_ids: Vec<ShipInstanceId> },
+    RemoveFromFleet { fleet_id: FleetId, ship_ids: Vec<ShipInstanceId> },
+    DisbandFleet { fleet_id: FleetId },
+    MergeFleets { into_fleet: FleetId, from_fleet: FleetId },
+    SplitFleet { fleet_id: FleetId, name: String, ship_ids: Vec<ShipInstanceId> },
+    SaveFleetTemplate { name: String, template: FleetTemplate },
+    /// Ships planned to be queued, plus the ones left out with the reason why
+    BuildFleetTemplate { template_name: String, planet_id: PlanetId, queued: Vec<ShipId>, skipped: Vec<(ShipId, String)> },
+    MoveFleet { fleet_id: FleetId, target_planet: PlanetId, distance: u8 },
+    /// Fleets ordered with their travel time, plus the ones left out with the reason why
+    MoveFleets { target_planet: PlanetId, moves: Vec<(FleetId, u8)>, skipped: Vec<(FleetId, String)> },
+    MoveShip { ship_id: ShipInstanceId, target_planet: PlanetId, distance: u8 },
+    BombardPlanet { fleet_id: FleetId, target_planet: PlanetId, bombardment_power: u32, target_structure: Option<StructureId> },
+    CancelBombard { fleet_id: FleetId },
+    ColonizePlanet { fleet_id: FleetId, planet_id: PlanetId },
+    SetFleetSentry { fleet_id: FleetId, sentry: bool },
+    /// Takes `cargo` aboard from the planet the fleet orbits
+    LoadCargo { fleet_id: FleetId, cargo: Resources },
+    /// Unloads the whole cargo onto the planet the fleet orbits
+    UnloadCargo { fleet_id: FleetId },
+    SetRallyPoint { planet_id: PlanetId, rally_point: Option<RallyPoint> },
+    GarrisonShips { planet_id: PlanetId, ship_ids: Vec<ShipInstanceId> },
+    UngarrisonShips { planet_id: PlanetId, ship_ids: Vec<ShipInstanceId> },
+    OverchargeShield { planet_id: PlanetId },
+    SetStructurePower { planet_id: PlanetId, structure_id: StructureId, on: bool },
+    SetAllocation { planet_id: PlanetId, allocation: Option<Allocation> },
+    LinkGates { first: PlanetId, second: PlanetId },
+    RetrofitShip { planet_id: PlanetId, ship_id: ShipInstanceId, target_type: ShipId },
+    DefineAlias { name: String, expansion: String },
+    RemoveAlias { name: String },
+    AddNote { text: String },
+    /// Removes the note numbered from 1 as listed
+    CompleteNote { number: usize },
+    /// Saved with the game, so every player sees the change
+    SetGameOption { option: GameOption },
+    /// A developer action; the game only accepts it when started with `--dev`
+    Dev(DevAction),
+    EndTurn { player_name: String },
+    /// Session-level effects handled by the caller; they never reach the game state
+    Save { name: String },
+    Load { name: String },
+    ListSaves,
+    DeleteSave { name: String },
+    /// Writes the end-of-game report, or an interim one while the game goes on
+    ExportReport { path: PathBuf },
+    ListOptions,
+    SetSessionOption { option: SessionOption },
+    /// Ends the session; handled by the caller like saving and loading
+    Exit,
+    /// Starts a new game set up like the finished one, on a fresh seed
+    Rematch,
+}
\ No newline at end of file
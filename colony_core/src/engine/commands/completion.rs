@@ -0,0 +1,351 @@
+use std::collections::BTreeSet;
+
+use crate::engine::commands::battles::MAX_LISTED_BATTLES;
+use crate::engine::commands::forecast::MAX_FORECAST_TURNS;
+use crate::engine::commands::note::MAX_TOTAL_NOTE_CHARS;
+use crate::engine::commands::parser::{END_TURN_ALIASES, EXIT_ALIASES};
+use crate::engine::configs::structure_config::STRUCTURE_CATEGORIES;
+use crate::engine::game_state::GameState;
+use crate::engine::options::MAX_AUTOSAVE_ROUNDS;
+use crate::engine::player::PlayerId;
+use crate::engine::utils;
+
+/// What a command argument position accepts, used to offer completions for it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArgKind {
+    /// A fixed word such as a command or subcommand name
+    Literal(&'static str),
+    /// One of a fixed set of words
+    OneOf(&'static [&'static str]),
+    /// A planet the viewer owns
+    OwnedPlanet,
+    /// Any planet in the system
+    Planet,
+    /// Any structure in the catalog
+    StructureId,
+    /// A structure built on the planet given by the previous argument
+    BuiltStructure,
+    /// Any ship type
+    ShipType,
+    /// One of the viewer's ships
+    ShipInstanceId,
+    /// One of the viewer's fleets
+    FleetId,
+    /// One of the viewer's fleet templates
+    Template,
+    /// One of the viewer's command aliases
+    Alias,
+    /// Another player
+    Player,
+    /// Free text such as a name; nothing to suggest
+    Text,
+    /// A whole number within an inclusive range
+    Number(u32, u32),
+    /// Turns written N, N-M or N-
+    TurnRange,
+    /// An argument that may be left out. Only trailing arguments are optional, so leaving one
+    /// out leaves out every one after it.
+    Optional(&'static ArgKind),
+}
+
+/// Argument layout of one command form. With `variadic`, the last kind repeats. Every input
+/// a signature describes parses into the command its first word names, so the signatures
+/// double as a spec for generating valid commands.
+pub struct Signature {
+    pub args: &'static [ArgKind],
+    pub variadic: bool,
+}
+
+use ArgKind::*;
+
+/// Every note holds at least one character, so no player has more notes than this
+const MAX_NOTES: u32 = MAX_TOTAL_NOTE_CHARS as u32;
+
+const fn form(args: &'static [ArgKind]) -> Signature {
+    Signature { args, variadic: false }
+}
+
+const fn variadic(args: &'static [ArgKind]) -> Signature {
+    Signature { args, variadic: true }
+}
+
+/// Every command form the parser accepts, in the order candidates are offered.
+/// Aliases of end turn and exit are added separately from the parser's alias lists.
+pub const SIGNATURES: &[Signature] = &[
+    form(&[Literal("build"), OwnedPlanet, StructureId, Optional(&Literal("--preview"))]),
+    form(&[Literal("build_all"), StructureId, Optional(&Literal("--only-affordable"))]),
+    form(&[Literal("build_ship"), OwnedPlanet, ShipType, Optional(&Literal("--preview"))]),
+    form(&[Literal("build_ship"), OwnedPlanet, ShipType, Literal("deliver_to"), OwnedPlanet, Optional(&Literal("--preview"))]),
+    form(&[Literal("upgrade"), OwnedPlanet, BuiltStructure, Optional(&Literal("--repeat")), Optional(&Literal("--preview"))]),
+    form(&[Literal("cancel"), OwnedPlanet, Optional(&Text)]),
+    form(&[Literal("status"), OneOf(&["game", "turn", "planets", "player"])]),
+    form(&[Literal("status"), Literal("planet"), Planet]),
+    form(&[Literal("status"), Literal("fleet"), FleetId]),
+    form(&[Literal("intel"), Player]),
+    form(&[Literal("inspect"), Literal("planet"), Planet]),
+    form(&[Literal("inspect"), Literal("player"), Player]),
+    form(&[Literal("inspect"), Literal("fleet"), FleetId]),
+    form(&[Literal("inspect"), Literal("invariants")]),
+    form(&[Literal("status"), Literal("perf")]),
+    form(&[Literal("dev"), Literal("give"), Planet, Number(0, u32::MAX), Number(0, u32::MAX), Number(0, u32::MAX)]),
+    form(&[Literal("dev"), Literal("complete"), Planet]),
+    form(&[Literal("dev"), Literal("reveal")]),
+    form(&[Literal("dev"), Literal("spawn-ship"), Planet, ShipType]),
+    form(&[Literal("dev"), Literal("set-turn"), Number(1, u32::MAX)]),
+    form(&[Literal("log"), Optional(&Player), Optional(&TurnRange)]),
+    form(&[Literal("battles"), Optional(&Number(1, MAX_LISTED_BATTLES))]),
+    form(&[Literal("battles"), Literal("show"), Number(1, u32::MAX)]),
+    form(&[Literal("forecast"), OwnedPlanet, Optional(&Number(1, MAX_FORECAST_TURNS))]),
+    form(&[Literal("defense"), OwnedPlanet]),
+    form(&[Literal("diff"), Number(1, u32::MAX), Number(1, u32::MAX)]),
+    form(&[Literal("structures"), Optional(&OneOf(STRUCTURE_CATEGORIES))]),
+    form(&[Literal("map"), Optional(&OneOf(&["grid", "list"]))]),
+    form(&[Literal("ships")]),
+    form(&[Literal("ship"), Literal("retrofit"), ShipInstanceId, ShipType]),
+    form(&[Literal("ship"), Literal("move"), ShipInstanceId, Planet]),
+    form(&[Literal("fleets")]),
+    form(&[Literal("schedule")]),
+    form(&[Literal("advisor")]),
+    variadic(&[Literal("fleet"), Literal("create"), Text, ShipInstanceId]),
+    variadic(&[Literal("fleet"), OneOf(&["add", "remove"]), FleetId, ShipInstanceId]),
+    form(&[Literal("fleet"), OneOf(&["disband", "cancel-bombard", "colonize", "hold", "wake", "unload"]), FleetId]),
+    form(&[Literal("fleet"), Literal("bombard"), FleetId, Optional(&StructureId)]),
+    form(&[Literal("fleet"), Literal("load"), FleetId, Number(0, u32::MAX), Number(0, u32::MAX), Number(0, u32::MAX)]),
+    form(&[Literal("fleet"), Literal("merge"), FleetId, FleetId]),
+    variadic(&[Literal("fleet"), Literal("split"), FleetId, Text, ShipInstanceId]),
+    form(&[Literal("fleet"), Literal("template"), Literal("save"), Text, FleetId]),
+    form(&[Literal("fleet"), Literal("template"), Literal("list")]),
+    form(&[Literal("fleet"), Literal("build"), Template, OwnedPlanet]),
+    form(&[Literal("fleet"), Literal("move"), FleetId, Planet]),
+    form(&[Literal("planet"), Literal("rally"), OwnedPlanet, FleetId]),
+    form(&[Literal("planet"), Literal("rally"), OwnedPlanet, Literal("none")]),
+    form(&[Literal("planet"), Literal("rally"), OwnedPlanet, Literal("new"), Text]),
+    variadic(&[Literal("planet"), OneOf(&["garrison", "ungarrison"]), OwnedPlanet, ShipInstanceId]),
+    form(&[Literal("planet"), Literal("overcharge"), OwnedPlanet]),
+    form(&[Literal("planet"), Literal("link"), OwnedPlanet, OwnedPlanet]),
+    form(&[Literal("planet"), Literal("power"), OwnedPlanet, BuiltStructure, OneOf(&["on", "off"])]),
+    form(&[Literal("planet"), Literal("allocate"), OwnedPlanet, Literal("default")]),
+    form(&[Literal("save"), Text]),
+    form(&[Literal("load"), Text]),
+    form(&[Literal("qs")]),
+    form(&[Literal("ql")]),
+    form(&[Literal("saves")]),
+    form(&[Literal("saves"), Literal("delete"), Text]),
+    form(&[Literal("export"), Literal("report"), Text]),
+    form(&[Literal("alias"), Literal("list")]),
+    form(&[Literal("alias"), Literal("remove"), Alias]),
+    variadic(&[Literal("alias"), Text, Literal("="), Text]),
+    variadic(&[Literal("note"), Literal("add"), Text]),
+    form(&[Literal("note"), Literal("list")]),
+    form(&[Literal("note"), Literal("done"), Number(1, MAX_NOTES)]),
+    form(&[Literal("set"), Literal("style"), OneOf(&["unicode", "ascii"])]),
+    form(&[Literal("options")]),
+    form(&[Literal("options"), Literal("set"), Literal("style"), OneOf(&["unicode", "ascii"])]),
+    form(&[Literal("options"), Literal("set"), OneOf(&["advisor", "confirmations"]), OneOf(&["on", "off"])]),
+    form(&[Literal("options"), Literal("set"), Literal("autosave"), Literal("off")]),
+    form(&[Literal("options"), Literal("set"), Literal("autosave"), Number(1, MAX_AUTOSAVE_ROUNDS)]),
+    form(&[Literal("help"), Optional(&OneOf(&["rules"]))]),
+    form(&[Literal("rematch")]),
+];
+
+/// Returns completions for the last token of `tokens`, the partially typed input split on
+/// whitespace. An empty last token asks for the next argument. Candidates that start with
+/// the partial token come first, then ones that merely contain it, each group sorted.
+pub fn complete(game_state: &GameState, viewer: &PlayerId, tokens: &[&str]) -> Vec<String> {
+    let (partial, typed) = match tokens.split_last() {
+        Some((partial, typed)) => (*partial, typed),
+        None => ("", &[][..]),
+    };
+
+    let mut options = BTreeSet::new();
+    if typed.is_empty() {
+        options.extend(END_TURN_ALIASES.iter().chain(EXIT_ALIASES).map(|alias| alias.to_string()));
+        if let Some(player) = game_state.players.get(viewer) {
+            options.extend(player.aliases.keys().cloned());
+        }
+    }
+    for signature in SIGNATURES {
+        if let Some(kind) = next_kind(signature, typed) {
+            options.extend(candidates(kind, game_state, viewer, typed));
+        }
+    }
+
+    rank(options, partial)
+}
+
+/// Splits raw input into tokens for `complete`, keeping an empty last token when the input
+/// ends in whitespace so the next argument is completed.
+pub fn tokenize(input: &str) -> Vec<&str> {
+    let mut tokens: Vec<_> = input.split_whitespace().collect();
+    if input.is_empty() || input.ends_with(char::is_whitespace) {
+        tokens.push("");
+    }
+    tokens
+}
+
+/// The kind expected after `typed`, if `typed` fits the signature so far.
+fn next_kind(signature: &Signature, typed: &[&str]) -> Option<ArgKind> {
+    for (position, token) in typed.iter().enumerate() {
+        let kind = kind_at(signature, position)?;
+        let fits = match kind.required() {
+            Literal(word) => *token == word,
+            OneOf(words) => words.contains(token),
+            _ => true,
+        };
+        if !fits {
+            return None;
+        }
+    }
+    kind_at(signature, typed.len())
+}
+
+fn kind_at(signature: &Signature, position: usize) -> Option<ArgKind> {
+    match signature.args.get(position) {
+        Some(kind) => Some(*kind),
+        None if signature.variadic => signature.args.last().copied(),
+        None => None,
+    }
+}
+
+impl ArgKind {
+    /// The kind of the argument when it is given, unwrapping `Optional`.
+    pub fn required(self) -> ArgKind {
+        match self {
+            Optional(kind) => kind.required(),
+            kind => kind,
+        }
+    }
+}
+
+fn candidates(kind: ArgKind, game_state: &GameState, viewer: &PlayerId, typed: &[&str]) -> Vec<String> {
+    let player = game_state.players.get(viewer);
+    match kind.required() {
+        Literal(word) => vec![word.to_string()],
+        OneOf(words) => words.iter().map(|word| word.to_string()).collect(),
+        OwnedPlanet => player.map_or_else(Vec::new, |player| player.planets.clone()),
+        Planet => game_state.map.planets.keys().cloned().collect(),
+        StructureId => game_state.structure_config.ids().cloned().collect(),
+        BuiltStructure => typed.last()
+            .and_then(|planet| game_state.map.planets.get(&utils::name_to_id(planet)))
+            .map_or_else(Vec::new, |planet| planet.get_structures().iter()
+                .filter(|(_, structure)| structure.level > 0)
+                .map(|(id, _)| id.clone())
+                .collect()),
+        ShipType => game_state.ship_config.ids().cloned().collect(),
+        ShipInstanceId => player.map_or_else(Vec::new, |player| {
+            player.ships.keys().filter(|id| !typed.contains(&id.as_str())).cloned().collect()
+        }),
+        FleetId => player.map_or_else(Vec::new, |player| player.fleets.keys().cloned().collect()),
+        Template => player.map_or_else(Vec::new, |player| player.fleet_templates.keys().cloned().collect()),
+        Alias => player.map_or_else(Vec::new, |player| player.aliases.keys().cloned().collect()),
+        Player => game_state.players.keys().filter(|id| *id != viewer).cloned().collect(),
+        Text | Number(..) | TurnRange | Optional(_) => Vec::new(),
+    }
+}
+
+/// Orders candidates matching `partial`: prefix matches before substring matches. A single
+/// character only matches as a prefix, since nearly everything contains it somewhere.
+/// Matching ignores case and treats spaces like underscores, as ids do.
+fn rank(options: BTreeSet<String>, partial: &str) -> Vec<String> {
+    let needle = utils::name_to_id(partial);
+    let allow_substring = needle.chars().count() >= 2;
+    let (prefixed, rest): (Vec<_>, Vec<_>) = options.into_iter()
+        .filter(|option| option.starts_with(&needle) || (allow_substring && option.contains(&needle)))
+        .partition(|option| option.starts_with(&needle));
+    prefixed.into_iter().chain(rest).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::parser::{self, accepted_command_words};
+    use crate::test_support::{self, fixture_game};
+
+    fn complete_input(state: &GameState, input: &str) -> Vec<String> {
+        complete(state, &String::from("alice"), &tokenize(input))
+    }
+
+    #[test]
+    fn test_signatures_cover_every_command_word() {
+        let signature_words: BTreeSet<_> = SIGNATURES.iter()
+            .filter_map(|signature| match signature.args.first() {
+                Some(Literal(word)) => Some(*word),
+                _ => None,
+            })
+            .chain(END_TURN_ALIASES.iter().copied())
+            .chain(EXIT_ALIASES.iter().copied())
+            .collect();
+        let accepted: BTreeSet<_> = accepted_command_words().into_iter().collect();
+
+        assert_eq!(signature_words, accepted);
+    }
+
+    #[test]
+    fn test_command_words_complete_from_prefix() {
+        let state = fixture_game(1);
+
+        assert_eq!(complete_input(&state, "bu"), ["build", "build_all", "build_ship"]);
+        assert_eq!(complete_input(&state, "fleet m"), ["merge", "move"]);
+        assert!(complete_input(&state, "").contains(&String::from("end_turn")));
+    }
+
+    #[test]
+    fn test_planet_then_structure_completion() {
+        let state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+
+        // Mid-token: only the owned planet, not bob's home or neutral planets
+        assert_eq!(complete_input(&state, &format!("build {}", &home[..3])), std::slice::from_ref(&home));
+
+        let structures = complete_input(&state, &format!("build {} ", home));
+        assert_eq!(structures.len(), state.structure_config.len());
+        assert_eq!(complete_input(&state, &format!("build {} min", home)), ["mining_complex"]);
+    }
+
+    #[test]
+    fn test_upgrade_offers_only_structures_on_that_planet() {
+        let state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let mut built: Vec<_> = state.map.planets[&home].get_structures().keys().cloned().collect();
+        built.sort();
+
+        assert_eq!(complete_input(&state, &format!("upgrade {} ", home)), built);
+    }
+
+    #[test]
+    fn test_substring_matches_rank_after_prefix_matches() {
+        let state = fixture_game(1);
+
+        let home = test_support::home_planet(&state, "alice");
+
+        let structures = complete_input(&state, &format!("build {} com", home));
+
+        assert_eq!(structures, ["command_nexus", "mining_complex", "storage_complex"]);
+    }
+
+    #[test]
+    fn test_variadic_ship_arguments_skip_ships_already_typed() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let alice = state.players.get_mut("alice").unwrap();
+        alice.add_ship(String::from("interceptor"), home.clone());
+        alice.add_ship(String::from("interceptor"), home);
+
+        assert_eq!(complete_input(&state, "fleet create guard interceptor_1 "), ["interceptor_2"]);
+        assert_eq!(complete_input(&state, "fleet create "), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_completed_commands_parse() {
+        let state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+
+        for input in ["he", "status tu", &format!("build {} min", home), "intel b"] {
+            let mut tokens = tokenize(input);
+            let completion = complete(&state, &String::from("alice"), &tokens)[0].clone();
+            *tokens.last_mut().unwrap() = &completion;
+
+            assert!(parser::parse(&tokens.join(" ")).is_ok(), "{:?} does not parse", tokens);
+        }
+    }
+}
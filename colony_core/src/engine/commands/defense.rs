@@ -0,0 +1,344 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::parser::Parseable;
+use crate::engine::fleet::Fleet;
+use crate::engine::game_state::GameState;
+use crate::engine::planet::{Planet, PlanetId};
+use crate::engine::player::Player;
+use crate::engine::rules;
+use crate::engine::ship::MovableUnit;
+
+/// Hops out from the planet within which visible hostile fleets count as threats.
+pub const THREAT_RADIUS_HOPS: u32 = 2;
+
+/// Rounds the shield must hold against the worst-case bombardment for the planet to count
+/// as guarded rather than exposed.
+const GUARDED_ROUNDS: u32 = 5;
+
+pub struct DefenseArgs {
+    pub planet_name: String,
+}
+
+impl Parseable for DefenseArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        let [planet_name] = args[..] else {
+            return Err(CommandError::MissingArguments {
+                command: String::from("defense"),
+                expected: String::from("defense <planet_id>"),
+            });
+        };
+        Ok(DefenseArgs { planet_name: planet_name.to_string() })
+    }
+}
+
+/// Overall verdict on how safe a planet is, from safest to most endangered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefenseRating {
+    Secure,
+    Guarded,
+    Exposed,
+    Critical,
+}
+
+impl DefenseRating {
+    /// Rates `planet` against `hostile_ships` visible nearby, assumed to land `power`
+    /// bombardment per round between them since their composition is unknown. Without
+    /// threats a shielded planet is secure and an unshielded one exposed. Otherwise the
+    /// rating follows the rounds the shield holds: forever is secure, `GUARDED_ROUNDS` or
+    /// more guarded, two or more exposed, and less critical.
+    pub fn of(planet: &Planet, hostile_ships: usize, power: u32) -> Self {
+        if hostile_ships == 0 {
+            return if planet.get_max_shield_hp() > 0 { DefenseRating::Secure } else { DefenseRating::Exposed };
+        }
+        match planet.turns_to_shield_collapse(power) {
+            None => DefenseRating::Secure,
+            Some(rounds) if rounds >= GUARDED_ROUNDS => DefenseRating::Guarded,
+            Some(rounds) if rounds >= 2 => DefenseRating::Exposed,
+            Some(_) => DefenseRating::Critical,
+        }
+    }
+}
+
+impl fmt::Display for DefenseRating {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DefenseRating::Secure => write!(f, "SECURE"),
+            DefenseRating::Guarded => write!(f, "GUARDED"),
+            DefenseRating::Exposed => write!(f, "EXPOSED"),
+            DefenseRating::Critical => write!(f, "CRITICAL"),
+        }
+    }
+}
+
+/// A hostile fleet the current player can see near the planet.
+struct Threat<'a> {
+    owner: &'a Player,
+    fleet: &'a Fleet,
+    hops: u32,
+}
+
+/// Summarizes everything defending one of the current player's planets: its shield,
+/// defensive structures, garrison and friendly fleets in orbit, the visible hostile fleets
+/// within `THREAT_RADIUS_HOPS`, and an overall rating.
+pub fn execute(args: DefenseArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let (planet_id, planet) = CommandError::own_planet(&args.planet_name, game_state)?;
+
+    let current_player_id = game_state.current_player();
+    let player = CommandError::acting_player(game_state)?;
+
+    let mut msg = format!("=== Defense: {} ({}) ===\n", planet.name, planet.id);
+
+    // Shield
+    let max_shield = planet.get_max_shield_hp();
+    if max_shield == 0 {
+        msg.push_str("Shield: none (build a defense_shield)\n");
+    } else {
+        let regen = match planet.turns_to_shield_regen() {
+            Some(turns) => format!(", back to full in {} turn(s) if not hit", turns),
+            None => String::from(", full"),
+        };
+        msg.push_str(&format!("Shield: {} / {} HP{}\n", planet.get_shield_hp(), max_shield, regen));
+    }
+
+    // Structures raising the shield
+    let mut defensive: Vec<_> = planet.get_structures().iter()
+        .filter(|(_, structure)| structure.level > 0)
+        .filter_map(|(id, structure)| structure.get_shield_regen_turns().map(|turns| (id, structure, turns)))
+        .collect();
+    defensive.sort_by(|a, b| a.0.cmp(b.0));
+    msg.push_str("\nDEFENSIVE STRUCTURES\n");
+    if defensive.is_empty() {
+        msg.push_str("  (none)\n");
+    }
+    for (structure_id, structure, regen_turns) in defensive {
+        msg.push_str(&format!(
+            "  {} Lv{}: {} HP shield, regenerates {} turn(s) after the last hit, no return fire\n",
+            structure_id, structure.level, structure.hitpoints, regen_turns
+        ));
+    }
+
+    // Garrison, the only defenders that fire back at bombarding fleets
+    let garrison_fire = game_state.garrison_fire(&planet_id);
+    let garrison = planet.get_garrison();
+    msg.push_str(&format!("\nGARRISON ({} ship(s), return fire {} per round)\n", garrison.len(), garrison_fire));
+    if garrison.is_empty() {
+        msg.push_str("  (none)\n");
+    }
+    for ship in garrison.iter().filter_map(|ship_id| player.ships.get(ship_id)) {
+        let attack = game_state.ship_config.get(&ship.ship_type).map_or(0, |ship_def| ship_def.attack);
+        msg.push_str(&format!("  - {} ({}): attack {}\n", ship.id, ship.ship_type, attack));
+    }
+
+    // Own and allied fleets orbiting the planet
+    let friendly: Vec<_> = game_state.players.values()
+        .filter(|other| game_state.are_allies(current_player_id, &other.id))
+        .flat_map(|other| other.fleets.values().map(move |fleet| (other, fleet)))
+        .filter(|(_, fleet)| fleet.location == planet_id && !fleet.is_empty())
+        .collect();
+    let (mut total_attack, mut total_shield, mut total_ships) = (0, 0, 0usize);
+    msg.push_str("\nFRIENDLY FLEETS IN ORBIT\n");
+    if friendly.is_empty() {
+        msg.push_str("  (none)\n");
+    }
+    for (owner, fleet) in &friendly {
+        let (attack, shield) = fleet_strength(owner, fleet, game_state);
+        total_attack += attack;
+        total_shield += shield;
+        total_ships += fleet.ship_count();
+        let owner_note = if owner.id == *current_player_id { String::new() } else { format!(" [{}]", owner.name) };
+        msg.push_str(&format!(
+            "  - {} ({}){}: {} ship(s), attack {}, shield {}\n",
+            fleet.name, fleet.id, owner_note, fleet.ship_count(), attack, shield
+        ));
+    }
+    if friendly.len() > 1 {
+        msg.push_str(&format!("  Total: {} ship(s), attack {}, shield {}\n", total_ships, total_attack, total_shield));
+    }
+
+    // Visible hostile fleets nearby
+    let threats = nearby_threats(&planet_id, game_state);
+    msg.push_str(&format!("\nHOSTILE FLEETS WITHIN {} HOPS\n", THREAT_RADIUS_HOPS));
+    if threats.is_empty() {
+        msg.push_str("  (none detected)\n");
+    }
+    for threat in &threats {
+        msg.push_str(&format!(
+            "  - {} ({}) of {} at {}, {} hop(s): {} ship(s), {}\n",
+            threat.fleet.name,
+            threat.fleet.id,
+            threat.owner.name,
+            game_state.map.planets.get(&threat.fleet.location).map_or("Unknown", |p| p.name.as_str()),
+            threat.hops,
+            threat.fleet.ship_count(),
+            threat_eta(threat, &planet_id, game_state),
+        ));
+    }
+
+    // Composition of hostile fleets is unknown, so assume every ship is the heaviest bomber
+    let hostile_ships: usize = threats.iter().map(|threat| threat.fleet.ship_count()).sum();
+    let heaviest_bombardment = game_state.ship_config.iter()
+        .map(|(_, ship_def)| ship_def.bombardment)
+        .max()
+        .unwrap_or(0);
+    let worst_case = (hostile_ships as u32).saturating_mul(heaviest_bombardment).saturating_sub(garrison_fire);
+    let rating = DefenseRating::of(planet, hostile_ships, worst_case);
+    msg.push_str(&format!("\nRATING: {}", rating));
+    if hostile_ships > 0 {
+        let holds = match planet.turns_to_shield_collapse(worst_case) {
+            Some(rounds) => format!("shield falls in {} round(s)", rounds),
+            None => String::from("shield holds"),
+        };
+        msg.push_str(&format!(" (worst case {} bombardment per round: {})", worst_case, holds));
+    }
+    msg.push('\n');
+
+    Ok(CommandEffect::None { message: msg })
+}
+
+/// Combined attack and shield of the ships in `owner`'s `fleet`.
+fn fleet_strength(owner: &Player, fleet: &Fleet, game_state: &GameState) -> (u32, u32) {
+    fleet.ships.iter()
+        .filter_map(|ship_id| owner.ships.get(ship_id))
+        .filter_map(|ship| Some((ship, game_state.ship_config.get(&ship.ship_type)?)))
+        .fold((0, 0), |(attack, shield), (ship, ship_def)| {
+            let ship_shield = ship_def.shield.saturating_sub(ship.supply_wear);
+            (attack.saturating_add(ship_def.attack), shield.saturating_add(ship_shield))
+        })
+}
+
+/// Fleets of players who could bombard the planet, at planets the current player can see
+/// within `THREAT_RADIUS_HOPS`, nearest first.
+fn nearby_threats<'a>(planet_id: &PlanetId, game_state: &'a GameState) -> Vec<Threat<'a>> {
+    let graph = game_state.map.graph();
+    let mut hops: BTreeMap<PlanetId, u32> = BTreeMap::from([(planet_id.clone(), 0)]);
+    let mut frontier = vec![planet_id.clone()];
+    for hop in 1..=THREAT_RADIUS_HOPS {
+        let mut next = Vec::new();
+        for from in &frontier {
+            for (neighbor, _) in graph.neighbors(from) {
+                if !hops.contains_key(neighbor) {
+                    hops.insert(neighbor.clone(), hop);
+                    next.push(neighbor.clone());
+                }
+            }
+        }
+        frontier = next;
+    }
+
+    let planet = &game_state.map.planets[planet_id];
+    let visible = game_state.visible_planets(game_state.current_player());
+    let mut threats: Vec<_> = game_state.players.values()
+        .filter(|other| rules::bombard_target(planet, other, game_state).is_ok())
+        .flat_map(|owner| owner.fleets.values().map(move |fleet| (owner, fleet)))
+        .filter(|(_, fleet)| !fleet.is_empty() && visible.contains(&fleet.location))
+        .filter_map(|(owner, fleet)| hops.get(&fleet.location).map(|hops| Threat { owner, fleet, hops: *hops }))
+        .collect();
+    threats.sort_by(|a, b| a.hops.cmp(&b.hops).then_with(|| a.fleet.id.cmp(&b.fleet.id)));
+    threats
+}
+
+/// Where a threat is heading and when: inbound with its arrival, moving elsewhere, or how
+/// far away it holds.
+fn threat_eta(threat: &Threat, planet_id: &PlanetId, game_state: &GameState) -> String {
+    let moving = threat.owner.pending_move(&MovableUnit::Fleet(threat.fleet.id.clone()))
+        .and_then(|action| action.action_type.movement().map(|(_, destination)| (destination.clone(), action.cooldown_remaining)));
+    match moving {
+        Some((destination, turns)) if &destination == planet_id => format!("inbound, arrives in {} turn(s)", turns),
+        Some((destination, turns)) => format!(
+            "moving to {} in {} turn(s)",
+            game_state.map.planets.get(&destination).map_or("Unknown", |p| p.name.as_str()),
+            turns
+        ),
+        None if threat.hops == 0 => String::from("in orbit"),
+        None => match game_state.map.graph().distance(&threat.fleet.location, planet_id) {
+            Some(turns) => format!("holding, {} turn(s) away", turns),
+            None => String::from("holding"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::parser;
+    use crate::engine::pending_action::{ActionType, PendingAction};
+    use crate::engine::resources::Resources;
+    use crate::test_support::{self, fixture_game};
+
+    fn defense_message(state: &GameState, input: &str) -> String {
+        match parser::parse(input).unwrap().execute(state).unwrap() {
+            CommandEffect::None { message } => message,
+            _ => panic!("Expected informational effect"),
+        }
+    }
+
+    /// Alice's home with a level-1 defense shield up and one garrisoned interceptor.
+    fn shielded_home(state: &mut GameState) -> PlanetId {
+        let home = test_support::home_planet(state, "alice");
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.complete_build_structure(String::from("power_grid"), &state.structure_config).unwrap();
+        planet.complete_build_structure(String::from("defense_shield"), &state.structure_config).unwrap();
+        planet.recalculate_from_structures();
+        planet.process_turn();
+        let ship_id = state.players.get_mut("alice").unwrap().add_ship(String::from("interceptor"), home.clone());
+        state.map.planets.get_mut(&home).unwrap().garrison_ship(ship_id);
+        home
+    }
+
+    #[test]
+    fn test_quiet_shielded_planet_is_secure() {
+        let mut state = fixture_game(1);
+        let home = shielded_home(&mut state);
+        let max_shield = state.map.planets[&home].get_max_shield_hp();
+        assert!(max_shield > 0);
+
+        let message = defense_message(&state, &format!("defense {}", home));
+
+        assert!(message.contains(&format!("Shield: {} / {} HP, full", max_shield, max_shield)));
+        assert!(message.contains("defense_shield Lv1"));
+        assert!(message.contains("return fire 10 per round"));
+        assert!(message.contains("interceptor_1 (interceptor): attack 10"));
+        assert!(message.contains("(none detected)"));
+        assert!(message.contains("RATING: SECURE"));
+    }
+
+    #[test]
+    fn test_visible_hostile_fleet_inbound_lowers_the_rating() {
+        let mut state = fixture_game(1);
+        let home = shielded_home(&mut state);
+        let neighbour = state.map.planets[&home].get_connections()[0].to().clone();
+        state.map.planets.get_mut(&home).unwrap().take_shield_damage(1);
+
+        let bob = state.players.get_mut("bob").unwrap();
+        let mut fleet = Fleet::new(String::from("fleet_1"), String::from("Raiders"), neighbour.clone());
+        for _ in 0..4 {
+            fleet.add_ship(bob.add_ship(String::from("ravager"), neighbour.clone()));
+        }
+        bob.fleets.insert(fleet.id.clone(), fleet);
+        bob.pending_actions.push(PendingAction::new(
+            ActionType::MoveFleet(String::from("fleet_1"), home.clone()), neighbour.clone(), 2, Resources::default(),
+        ));
+
+        // Out of sensor range the fleet goes unseen
+        let message = defense_message(&state, &format!("defense {}", home));
+        assert!(message.contains("back to full in"));
+        assert!(message.contains("(none detected)"));
+
+        state.players.get_mut("alice").unwrap().add_ship(String::from("scout"), neighbour);
+        let message = defense_message(&state, &format!("defense {}", home));
+
+        assert!(message.contains("Raiders (fleet_1) of Bob"));
+        assert!(message.contains("1 hop(s): 4 ship(s), inbound, arrives in 2 turn(s)"));
+        assert!(!message.contains("RATING: SECURE"));
+    }
+
+    #[test]
+    fn test_other_players_planets_are_rejected() {
+        let state = fixture_game(1);
+        let bob_home = test_support::home_planet(&state, "bob");
+        let result = parser::parse(&format!("defense {}", bob_home)).unwrap().execute(&state);
+        assert!(matches!(result, Err(CommandError::NotYourPlanet(_))));
+    }
+}
@@ -0,0 +1,170 @@
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::parser::Parseable;
+use crate::engine::configs::ship_config::ShipId;
+use crate::engine::game_state::GameState;
+use crate::engine::planet::PlanetId;
+use crate::engine::resources::Resources;
+use crate::engine::utils;
+
+const USAGE: &str = "dev give <planet> <minerals> <gas> <energy> | dev complete <planet> | dev reveal \
+    | dev spawn-ship <planet> <ship_type> | dev set-turn <n>";
+
+/// A state change only a game started with `--dev` accepts, to reach late-game situations
+/// without playing up to them.
+pub enum DevAction {
+    /// Adds resources to a planet's stock, up to its storage
+    Give { planet_id: PlanetId, resources: Resources },
+    /// Completes every queued action on a planet now, as the end of the round would
+    Complete { planet_id: PlanetId },
+    /// Shows every planet live to whoever looks, or stops doing so
+    Reveal,
+    /// Adds a ship of `ship_id` to the planet's owner, as a finished build would
+    SpawnShip { planet_id: PlanetId, ship_id: ShipId },
+    /// Moves the turn counter forward to `turn`
+    SetTurn { turn: u32 },
+}
+
+pub struct DevArgs {
+    pub action: DevAction,
+}
+
+impl Parseable for DevArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        let number = |command: &str, argument: &str| argument.parse::<u32>().map_err(|_| CommandError::InvalidArgument {
+            command: format!("dev {}", command),
+            argument: argument.to_string(),
+            reason: String::from("expected a whole number"),
+        });
+
+        let action = match args.as_slice() {
+            ["give", planet, minerals, gas, energy] => DevAction::Give {
+                planet_id: utils::name_to_id(planet),
+                resources: Resources {
+                    minerals: number("give", minerals)?,
+                    gas: number("give", gas)?,
+                    energy: number("give", energy)?,
+                },
+            },
+            ["complete", planet] => DevAction::Complete { planet_id: utils::name_to_id(planet) },
+            ["reveal"] => DevAction::Reveal,
+            ["spawn-ship", planet, ship_type] => DevAction::SpawnShip {
+                planet_id: utils::name_to_id(planet),
+                ship_id: utils::name_to_id(ship_type),
+            },
+            ["set-turn", turn] => DevAction::SetTurn { turn: number("set-turn", turn)? },
+            [kind, ..] if !matches!(*kind, "give" | "complete" | "reveal" | "spawn-ship" | "set-turn") => {
+                return Err(CommandError::InvalidArgument {
+                    command: String::from("dev"),
+                    argument: kind.to_string(),
+                    reason: String::from("expected give, complete, reveal, spawn-ship or set-turn"),
+                });
+            }
+            _ => {
+                return Err(CommandError::MissingArguments {
+                    command: String::from("dev"),
+                    expected: String::from(USAGE),
+                });
+            }
+        };
+        Ok(DevArgs { action })
+    }
+}
+
+/// Checks a developer action against the game. Planets are looked up past the fog of war,
+/// like `inspect` does.
+pub fn execute(args: DevArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let planet = |planet_id: &PlanetId| game_state.map.planets.get(planet_id)
+        .ok_or_else(|| CommandError::UnknownPlanet(planet_id.clone()));
+
+    match &args.action {
+        DevAction::Give { planet_id, .. } | DevAction::Complete { planet_id } => {
+            planet(planet_id)?;
+        }
+        DevAction::SpawnShip { planet_id, ship_id } => {
+            if planet(planet_id)?.get_owner().is_none() {
+                return Err(CommandError::PlanetNotOwned(planet_id.clone()));
+            }
+            if game_state.ship_config.get(ship_id).is_none() {
+                return Err(CommandError::UnknownShip(ship_id.clone()));
+            }
+        }
+        DevAction::SetTurn { turn } if *turn < game_state.turn => {
+            return Err(CommandError::InvalidArgument {
+                command: String::from("dev set-turn"),
+                argument: turn.to_string(),
+                reason: format!("the turn only moves forward; it is {} now", game_state.turn),
+            });
+        }
+        DevAction::Reveal | DevAction::SetTurn { .. } => {}
+    }
+    Ok(CommandEffect::Dev(args.action))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::command::Command;
+    use crate::engine::commands::parser;
+    use crate::engine::game_event::GameEvent;
+    use crate::engine::pending_action::ActionType;
+    use crate::test_support::{self, fixture_game, run};
+
+    #[test]
+    fn test_dev_give_fills_storage_and_is_tagged() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        state.map.planets.get_mut(&home).unwrap().available_resources = Resources::default();
+
+        let events = run(&mut state, &format!("dev give {home} 50 1000000 0")).unwrap();
+
+        let planet = &state.map.planets[&home];
+        assert_eq!(planet.available_resources.minerals, 50);
+        assert_eq!(planet.available_resources.gas, planet.storage_capacity.gas);
+        assert!(matches!(&events[..], [GameEvent::DevAction { player_name, .. }] if player_name == "Alice"), "{events:?}");
+        assert!(state.check_invariants().is_empty(), "{:?}", state.check_invariants());
+    }
+
+    #[test]
+    fn test_dev_complete_finishes_queued_builds_through_the_normal_completion() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        state.map.planets.get_mut(&home).unwrap().available_resources = Resources { minerals: 1000, gas: 1000, energy: 1000 };
+        run(&mut state, &format!("build {home} power_grid")).unwrap();
+        run(&mut state, &format!("upgrade {home} planetary_capital")).unwrap();
+
+        let events = run(&mut state, &format!("dev complete {home}")).unwrap();
+
+        assert!(matches!(events.first(), Some(GameEvent::DevAction { .. })), "{events:?}");
+        assert!(events.iter().any(|event| matches!(event, GameEvent::ConstructionCompleted { .. })), "{events:?}");
+        assert!(events.iter().any(|event| matches!(event, GameEvent::UpgradeCompleted { .. })), "{events:?}");
+        assert!(!state.players["alice"].pending_actions.iter()
+            .any(|action| matches!(action.action_type, ActionType::BuildStructure(_) | ActionType::UpgradeStructure(_))));
+        let planet = &state.map.planets[&home];
+        assert_eq!(planet.get_structures()["power_grid"].level, 1);
+        assert_eq!(planet.get_structures()["planetary_capital"].level, 2);
+        assert!(state.check_invariants().is_empty(), "{:?}", state.check_invariants());
+    }
+
+    #[test]
+    fn test_dev_spawn_ship_and_set_turn_validate_their_arguments() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+
+        run(&mut state, &format!("dev spawn-ship {home} interceptor")).unwrap();
+        assert_eq!(state.players["alice"].ships["interceptor_1"].location, home);
+        assert!(run(&mut state, &format!("dev spawn-ship {home} battlestar")).is_err());
+
+        run(&mut state, "dev set-turn 40").unwrap();
+        assert_eq!(state.turn, 40);
+        assert!(run(&mut state, "dev set-turn 39").is_err());
+    }
+
+    #[test]
+    fn test_dev_parses_subcommands_and_stays_out_of_the_action_log() {
+        let command = parser::parse("dev reveal").unwrap();
+        assert!(matches!(command, Command::Dev(_)));
+        assert!(!command.is_order());
+        assert!(matches!(parser::parse("dev teleport"), Err(CommandError::InvalidArgument { .. })));
+        assert!(matches!(parser::parse("dev"), Err(CommandError::MissingArguments { .. })));
+    }
+}
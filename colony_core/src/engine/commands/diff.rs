@@ -0,0 +1,105 @@
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::parser::Parseable;
+use crate::engine::game_state::{GameState, diff_views};
+
+pub struct DiffArgs {
+    pub from_turn: u32,
+    pub to_turn: u32,
+}
+
+impl Parseable for DiffArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        let [from_turn, to_turn] = args.as_slice() else {
+            return Err(CommandError::MissingArguments {
+                command: String::from("diff"),
+                expected: String::from("diff <turn_a> <turn_b>"),
+            });
+        };
+        let turn = |turn: &str| turn.parse::<u32>().ok()
+            .filter(|turn| *turn >= 1)
+            .ok_or_else(|| CommandError::InvalidArgument {
+                command: String::from("diff"),
+                argument: turn.to_string(),
+                reason: String::from("turns are numbers from 1"),
+            });
+        let (first, second) = (turn(from_turn)?, turn(to_turn)?);
+        // Either order compares the earlier turn to the later one
+        Ok(DiffArgs { from_turn: first.min(second), to_turn: first.max(second) })
+    }
+}
+
+/// Lists what changed between the start of two turns, as far as the current player could see
+/// at either point.
+pub fn execute(args: DiffArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let viewer = game_state.current_player();
+    let view = |turn: u32| game_state.turn_view(viewer, turn).ok_or_else(|| CommandError::InvalidArgument {
+        command: String::from("diff"),
+        argument: turn.to_string(),
+        reason: kept_turns(game_state),
+    });
+    let (before, after) = (view(args.from_turn)?, view(args.to_turn)?);
+
+    let changes = diff_views(before, after);
+    let mut message = format!("=== Changes from turn {} to turn {} ===", args.from_turn, args.to_turn);
+    if changes.is_empty() {
+        message.push_str("\nNothing changed that you could see.");
+    }
+    for change in changes {
+        message.push_str(&format!("\n  {}", change));
+    }
+    Ok(CommandEffect::None { message })
+}
+
+fn kept_turns(game_state: &GameState) -> String {
+    match (game_state.turn_history.front(), game_state.turn_history.back()) {
+        (Some(first), Some(last)) => format!(
+            "only turns {}-{} are kept; 'options set history <turns>' keeps more",
+            first.turn, last.turn,
+        ),
+        _ => String::from("no turns are kept; 'options set history <turns>' turns the history on"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::command::Command;
+    use crate::engine::commands::parser;
+    use crate::test_support::{self, fixture_game};
+
+    fn diff(state: &GameState, line: &str) -> Result<String, CommandError> {
+        match parser::parse(line)?.execute(state)? {
+            CommandEffect::None { message } => Ok(message),
+            _ => panic!("Diff only displays"),
+        }
+    }
+
+    #[test]
+    fn test_parse_orders_the_turns() {
+        assert!(matches!(DiffArgs::parse(vec!["3"]), Err(CommandError::MissingArguments { .. })));
+        assert!(matches!(DiffArgs::parse(vec!["0", "3"]), Err(CommandError::InvalidArgument { .. })));
+        assert!(matches!(parser::parse("diff 1 2"), Ok(Command::Diff(DiffArgs { from_turn: 1, to_turn: 2 }))));
+        assert!(matches!(parser::parse("diff 5 3"), Ok(Command::Diff(DiffArgs { from_turn: 3, to_turn: 5 }))));
+    }
+
+    #[test]
+    fn test_diff_lists_changes_since_an_earlier_turn() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        for line in [format!("build {home} power_grid"), String::from("end"), String::from("end")] {
+            let player_id = state.current_player().clone();
+            let effect = parser::parse(&line).unwrap().execute(&state).unwrap();
+            state.apply_effect(effect, &player_id).unwrap();
+        }
+
+        let message = diff(&state, "diff 1 2").unwrap();
+        let home_name = &state.map.planets[&home].name;
+        assert!(message.starts_with("=== Changes from turn 1 to turn 2 ==="), "{message}");
+        assert!(message.contains(&format!("  {}: power_grid built (Lv1)", home_name)), "{message}");
+
+        let Err(CommandError::InvalidArgument { reason, .. }) = diff(&state, "diff 1 7") else {
+            panic!("Turn 7 has not been played")
+        };
+        assert!(reason.contains("turns 1-2"), "{reason}");
+    }
+}
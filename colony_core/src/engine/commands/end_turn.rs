@@ -0,0 +1,10 @@
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::game_state::GameState;
+
+pub fn execute(game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+
+    Ok(CommandEffect::EndTurn {
+        player_name: player.name.clone(),
+    })
+}
@@ -0,0 +1,29 @@
+use std::path::PathBuf;
+
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::parser::Parseable;
+
+pub struct ExportArgs {
+    pub path: PathBuf,
+}
+
+impl Parseable for ExportArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        match args.as_slice() {
+            ["report", path] => Ok(ExportArgs { path: PathBuf::from(path) }),
+            [other, ..] if *other != "report" => Err(CommandError::InvalidArgument {
+                command: String::from("export"),
+                argument: other.to_string(),
+                reason: String::from("only 'report' can be exported"),
+            }),
+            _ => Err(CommandError::MissingArguments {
+                command: String::from("export"),
+                expected: String::from("export report <path>"),
+            }),
+        }
+    }
+}
+
+pub fn execute(args: ExportArgs) -> Result<CommandEffect, CommandError> {
+    Ok(CommandEffect::ExportReport { path: args.path })
+}
@@ -0,0 +1,1355 @@
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::parser::Parseable;
+use crate::engine::configs::ship_config::ShipId;
+use crate::engine::fleet::FleetTemplate;
+use crate::engine::game_state::GameState;
+use crate::engine::planet::PlanetId;
+use crate::engine::player::Player;
+use crate::engine::resources::Resources;
+use crate::engine::rules::{self, Reason};
+use crate::engine::ship::{FleetId, ShipInstanceId};
+use crate::engine::structure::StructureId;
+
+#[derive(Debug)]
+pub enum FleetAction {
+    Create { name: String, ship_ids: Vec<ShipInstanceId> },
+    Add { fleet_id: FleetId, ship_ids: Vec<ShipInstanceId> },
+    Remove { fleet_id: FleetId, ship_ids: Vec<ShipInstanceId> },
+    Disband { fleet_id: FleetId },
+    Merge { into_fleet: FleetId, from_fleet: FleetId },
+    Split { fleet_id: FleetId, name: String, ship_ids: Vec<ShipInstanceId> },
+    SaveTemplate { name: String, fleet_id: FleetId },
+    ListTemplates,
+    BuildTemplate { template: String, planet_id: PlanetId },
+    /// Fleets listed comma-separated or one after another, each ordered on its own
+    Move { fleet_ids: Vec<FleetId>, target_planet: PlanetId },
+    /// `target_structure` takes the damage passing through the shield first
+    Bombard { fleet_id: FleetId, target_structure: Option<StructureId> },
+    CancelBombard { fleet_id: FleetId },
+    Colonize { fleet_id: FleetId },
+    Hold { fleet_id: FleetId },
+    Wake { fleet_id: FleetId },
+    Load { fleet_id: FleetId, cargo: Resources },
+    Unload { fleet_id: FleetId },
+}
+
+#[derive(Debug)]
+pub struct FleetArgs {
+    pub action: FleetAction,
+}
+
+impl Parseable for FleetArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::MissingArguments {
+                command: String::from("fleet"),
+                expected: String::from("fleet <create|add|remove|disband|merge|split|template|build|move|load|unload> ..."),
+            });
+        }
+
+        let action = match args[0] {
+            "create" => {
+                if args.len() < 3 {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("fleet create"),
+                        expected: String::from("fleet create <name> <ship_id> [ship_id...]"),
+                    });
+                }
+                let name = args[1].to_string();
+                let ship_ids: Vec<ShipInstanceId> = args[2..].iter().map(|s| s.to_string()).collect();
+                FleetAction::Create { name, ship_ids }
+            }
+            "add" => {
+                if args.len() < 3 {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("fleet add"),
+                        expected: String::from("fleet add <fleet_id> <ship_id> [ship_id...]"),
+                    });
+                }
+                let fleet_id = args[1].to_string();
+                let ship_ids: Vec<ShipInstanceId> = args[2..].iter().map(|s| s.to_string()).collect();
+                FleetAction::Add { fleet_id, ship_ids }
+            }
+            "remove" => {
+                if args.len() < 3 {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("fleet remove"),
+                        expected: String::from("fleet remove <fleet_id> <ship_id> [ship_id...]"),
+                    });
+                }
+                let fleet_id = args[1].to_string();
+                let ship_ids: Vec<ShipInstanceId> = args[2..].iter().map(|s| s.to_string()).collect();
+                FleetAction::Remove { fleet_id, ship_ids }
+            }
+            "disband" => {
+                if args.len() < 2 {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("fleet disband"),
+                        expected: String::from("fleet disband <fleet_id>"),
+                    });
+                }
+                let fleet_id = args[1].to_string();
+                FleetAction::Disband { fleet_id }
+            }
+            "merge" => {
+                if args.len() < 3 {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("fleet merge"),
+                        expected: String::from("fleet merge <into_fleet> <from_fleet>"),
+                    });
+                }
+                let into_fleet = args[1].to_string();
+                let from_fleet = args[2].to_string();
+                FleetAction::Merge { into_fleet, from_fleet }
+            }
+            "split" => {
+                if args.len() < 4 {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("fleet split"),
+                        expected: String::from("fleet split <fleet_id> <new_name> <ship_id> [ship_id...]"),
+                    });
+                }
+                let fleet_id = args[1].to_string();
+                let name = args[2].to_string();
+                let ship_ids: Vec<ShipInstanceId> = args[3..].iter().map(|s| s.to_string()).collect();
+                FleetAction::Split { fleet_id, name, ship_ids }
+            }
+            "template" => match args.get(1..) {
+                Some(["save", name, fleet_id]) => FleetAction::SaveTemplate {
+                    name: name.to_string(),
+                    fleet_id: fleet_id.to_string(),
+                },
+                Some(["list"]) => FleetAction::ListTemplates,
+                _ => {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("fleet template"),
+                        expected: String::from("fleet template <save <name> <fleet_id>|list>"),
+                    });
+                }
+            },
+            "build" => {
+                if args.len() < 3 {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("fleet build"),
+                        expected: String::from("fleet build <template> <planet_id>"),
+                    });
+                }
+                let template = args[1].to_string();
+                let planet_id = args[2].to_string();
+                FleetAction::BuildTemplate { template, planet_id }
+            }
+            "move" => {
+                if args.len() < 3 {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("fleet move"),
+                        expected: String::from("fleet move <fleet_id>[,fleet_id...] <target_planet>"),
+                    });
+                }
+                let mut fleet_ids: Vec<FleetId> = Vec::new();
+                for fleet_id in args[1..args.len() - 1].iter().flat_map(|arg| arg.split(',')).filter(|id| !id.is_empty()) {
+                    if !fleet_ids.iter().any(|listed| listed == fleet_id) {
+                        fleet_ids.push(fleet_id.to_string());
+                    }
+                }
+                if fleet_ids.is_empty() {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("fleet move"),
+                        expected: String::from("fleet move <fleet_id>[,fleet_id...] <target_planet>"),
+                    });
+                }
+                let target_planet = args[args.len() - 1].to_string();
+                FleetAction::Move { fleet_ids, target_planet }
+            }
+            "bombard" => {
+                if args.len() < 2 {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("fleet bombard"),
+                        expected: String::from("fleet bombard <fleet_id> [target_structure_id]"),
+                    });
+                }
+                let fleet_id = args[1].to_string();
+                let target_structure = args.get(2).map(|structure_id| structure_id.to_string());
+                FleetAction::Bombard { fleet_id, target_structure }
+            }
+            "cancel-bombard" => {
+                if args.len() < 2 {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("fleet cancel-bombard"),
+                        expected: String::from("fleet cancel-bombard <fleet_id>"),
+                    });
+                }
+                let fleet_id = args[1].to_string();
+                FleetAction::CancelBombard { fleet_id }
+            }
+            "colonize" => {
+                if args.len() < 2 {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("fleet colonize"),
+                        expected: String::from("fleet colonize <fleet_id>"),
+                    });
+                }
+                let fleet_id = args[1].to_string();
+                FleetAction::Colonize { fleet_id }
+            }
+            action @ ("hold" | "wake") => {
+                let Some(fleet_id) = args.get(1) else {
+                    return Err(CommandError::MissingArguments {
+                        command: format!("fleet {}", action),
+                        expected: format!("fleet {} <fleet_id>", action),
+                    });
+                };
+                let fleet_id = fleet_id.to_string();
+                if action == "hold" {
+                    FleetAction::Hold { fleet_id }
+                } else {
+                    FleetAction::Wake { fleet_id }
+                }
+            }
+            "load" => {
+                let [fleet_id, minerals, gas, energy] = args[1..] else {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("fleet load"),
+                        expected: String::from("fleet load <fleet_id> <minerals> <gas> <energy>"),
+                    });
+                };
+                let amount = |amount: &str| amount.parse::<u32>().map_err(|_| CommandError::InvalidArgument {
+                    command: String::from("fleet load"),
+                    argument: amount.to_string(),
+                    reason: String::from("amounts are whole numbers from 0"),
+                });
+                let cargo = Resources { minerals: amount(minerals)?, gas: amount(gas)?, energy: amount(energy)? };
+                FleetAction::Load { fleet_id: fleet_id.to_string(), cargo }
+            }
+            "unload" => {
+                let Some(fleet_id) = args.get(1) else {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("fleet unload"),
+                        expected: String::from("fleet unload <fleet_id>"),
+                    });
+                };
+                FleetAction::Unload { fleet_id: fleet_id.to_string() }
+            }
+            _ => {
+                return Err(CommandError::InvalidArgument {
+                    command: String::from("fleet"),
+                    argument: args[0].to_string(),
+                    reason: String::from("valid actions are: create, add, remove, disband, merge, split, template, build, move, bombard, cancel-bombard, colonize, hold, wake, load, unload"),
+                });
+            }
+        };
+
+        Ok(FleetArgs { action })
+    }
+}
+
+pub fn execute(args: FleetArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    match args.action {
+        FleetAction::Create { name, ship_ids } => validate_create(&name, &ship_ids, game_state),
+        FleetAction::Add { fleet_id, ship_ids } => validate_add(&fleet_id, &ship_ids, game_state),
+        FleetAction::Remove { fleet_id, ship_ids } => validate_remove(&fleet_id, &ship_ids, game_state),
+        FleetAction::Disband { fleet_id } => validate_disband(&fleet_id, game_state),
+        FleetAction::Merge { into_fleet, from_fleet } => validate_merge(&into_fleet, &from_fleet, game_state),
+        FleetAction::Split { fleet_id, name, ship_ids } => validate_split(&fleet_id, &name, &ship_ids, game_state),
+        FleetAction::SaveTemplate { name, fleet_id } => validate_save_template(&name, &fleet_id, game_state),
+        FleetAction::ListTemplates => Ok(CommandEffect::None { message: format_templates(CommandError::acting_player(game_state)?) }),
+        FleetAction::BuildTemplate { template, planet_id } => validate_build_template(&template, &planet_id, game_state),
+        FleetAction::Move { fleet_ids, target_planet } => match &fleet_ids[..] {
+            [fleet_id] => validate_move(fleet_id, &target_planet, game_state),
+            _ => validate_move_many(&fleet_ids, &target_planet, game_state),
+        },
+        FleetAction::Bombard { fleet_id, target_structure } => validate_bombard(&fleet_id, target_structure, game_state),
+        FleetAction::CancelBombard { fleet_id } => validate_cancel_bombard(&fleet_id, game_state),
+        FleetAction::Colonize { fleet_id } => validate_colonize(&fleet_id, game_state),
+        FleetAction::Hold { fleet_id } => validate_sentry(&fleet_id, true, game_state),
+        FleetAction::Wake { fleet_id } => validate_sentry(&fleet_id, false, game_state),
+        FleetAction::Load { fleet_id, cargo } => validate_load(&fleet_id, cargo, game_state),
+        FleetAction::Unload { fleet_id } => validate_unload(&fleet_id, game_state),
+    }
+}
+
+fn validate_create(
+    name: &str,
+    ship_ids: &[ShipInstanceId],
+    game_state: &GameState,
+) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+
+    // Check all ships exist and belong to player
+    let mut location: Option<&PlanetId> = None;
+    for ship_id in ship_ids {
+        if let Some(action) = player.find_ship_under_construction(ship_id) {
+            return Err(CommandError::ShipUnderConstruction {
+                ship_id: ship_id.clone(),
+                turns: action.cooldown_remaining,
+            });
+        }
+
+        let ship = player.ships.get(ship_id)
+            .ok_or_else(|| CommandError::ship_not_found("fleet create", ship_id, game_state))?;
+
+        // Check ship is not already in a fleet
+        if let Some(existing_fleet) = &ship.fleet_id {
+            return Err(CommandError::InvalidArgument {
+                command: String::from("fleet create"),
+                argument: ship_id.clone(),
+                reason: format!("ship is already in fleet '{}'", existing_fleet),
+            });
+        }
+
+        if game_state.is_garrisoned(ship) {
+            return Err(CommandError::InvalidArgument {
+                command: String::from("fleet create"),
+                argument: ship_id.clone(),
+                reason: format!("ship is garrisoned on {} - ungarrison it first", ship.location),
+            });
+        }
+
+        if player.has_pending_ship_move(ship_id) {
+            return Err(CommandError::InvalidArgument {
+                command: String::from("fleet create"),
+                argument: ship_id.clone(),
+                reason: String::from("ship has a pending move"),
+            });
+        }
+
+        // Check all ships are at the same location
+        match location {
+            None => location = Some(&ship.location),
+            Some(loc) if loc != &ship.location => {
+                return Err(CommandError::InvalidArgument {
+                    command: String::from("fleet create"),
+                    argument: ship_id.clone(),
+                    reason: format!("ship is at different location ({})", ship.location),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    let location = location.expect("At least one ship required").clone();
+    rules::fleet_has_room(0, rules::capacity_used(ship_ids, player, game_state), game_state)
+        .map_err(|reason| rejected("fleet create", &String::from(name), reason))?;
+
+    Ok(CommandEffect::CreateFleet {
+        name: name.to_string(),
+        ship_ids: ship_ids.to_vec(),
+        location,
+    })
+}
+
+fn validate_add(
+    fleet_id: &FleetId,
+    ship_ids: &[ShipInstanceId],
+    game_state: &GameState,
+) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+
+    // Check fleet exists
+    let fleet = player.fleets.get(fleet_id)
+        .ok_or_else(|| CommandError::fleet_not_found("fleet add", fleet_id, game_state))?;
+
+    let fleet_location = &fleet.location;
+
+    // Check all ships exist, belong to player, not in fleet, and at fleet's location
+    for ship_id in ship_ids {
+        if let Some(action) = player.find_ship_under_construction(ship_id) {
+            return Err(CommandError::ShipUnderConstruction {
+                ship_id: ship_id.clone(),
+                turns: action.cooldown_remaining,
+            });
+        }
+
+        let ship = player.ships.get(ship_id)
+            .ok_or_else(|| CommandError::ship_not_found("fleet add", ship_id, game_state))?;
+
+        if let Some(existing_fleet) = &ship.fleet_id {
+            return Err(CommandError::InvalidArgument {
+                command: String::from("fleet add"),
+                argument: ship_id.clone(),
+                reason: format!("ship is already in fleet '{}'", existing_fleet),
+            });
+        }
+
+        if game_state.is_garrisoned(ship) {
+            return Err(CommandError::InvalidArgument {
+                command: String::from("fleet add"),
+                argument: ship_id.clone(),
+                reason: format!("ship is garrisoned on {} - ungarrison it first", ship.location),
+            });
+        }
+
+        if player.has_pending_ship_move(ship_id) {
+            return Err(CommandError::InvalidArgument {
+                command: String::from("fleet add"),
+                argument: ship_id.clone(),
+                reason: String::from("ship has a pending move"),
+            });
+        }
+
+        if &ship.location != fleet_location {
+            return Err(CommandError::InvalidArgument {
+                command: String::from("fleet add"),
+                argument: ship_id.clone(),
+                reason: format!(
+                    "ship is at {} but fleet is at {}",
+                    ship.location, fleet_location
+                ),
+            });
+        }
+    }
+
+    let used = rules::capacity_used(&fleet.ships, player, game_state);
+    rules::fleet_has_room(used, rules::capacity_used(ship_ids, player, game_state), game_state)
+        .map_err(|reason| rejected("fleet add", fleet_id, reason))?;
+
+    Ok(CommandEffect::AddToFleet {
+        fleet_id: fleet_id.clone(),
+        ship_ids: ship_ids.to_vec(),
+    })
+}
+
+fn validate_remove(
+    fleet_id: &FleetId,
+    ship_ids: &[ShipInstanceId],
+    game_state: &GameState,
+) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+
+    // Check fleet exists
+    let fleet = player.fleets.get(fleet_id)
+        .ok_or_else(|| CommandError::fleet_not_found("fleet remove", fleet_id, game_state))?;
+
+    // Check all ships are in this fleet
+    for ship_id in ship_ids {
+        if !fleet.ships.contains(ship_id) {
+            return Err(CommandError::InvalidArgument {
+                command: String::from("fleet remove"),
+                argument: ship_id.clone(),
+                reason: format!("ship is not in fleet '{}'", fleet_id),
+            });
+        }
+    }
+    rules::cargo_fits_without(fleet, ship_ids, player, game_state)
+        .map_err(|reason| rejected("fleet remove", fleet_id, reason))?;
+
+    Ok(CommandEffect::RemoveFromFleet {
+        fleet_id: fleet_id.clone(),
+        ship_ids: ship_ids.to_vec(),
+    })
+}
+
+fn validate_disband(fleet_id: &FleetId, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+
+    // Check fleet exists
+    let fleet = player.fleets.get(fleet_id)
+        .ok_or_else(|| CommandError::fleet_not_found("fleet disband", fleet_id, game_state))?;
+    rules::cargo_fits_without(fleet, &fleet.ships, player, game_state)
+        .map_err(|reason| rejected("fleet disband", fleet_id, reason))?;
+
+    Ok(CommandEffect::DisbandFleet {
+        fleet_id: fleet_id.clone(),
+    })
+}
+
+fn validate_merge(
+    into_fleet: &FleetId,
+    from_fleet: &FleetId,
+    game_state: &GameState,
+) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+
+    if into_fleet == from_fleet {
+        return Err(CommandError::InvalidArgument {
+            command: String::from("fleet merge"),
+            argument: from_fleet.clone(),
+            reason: String::from("cannot merge a fleet into itself"),
+        });
+    }
+
+    // Check both fleets exist and are idle
+    for fleet_id in [into_fleet, from_fleet] {
+        if !player.fleets.contains_key(fleet_id) {
+            return Err(CommandError::fleet_not_found("fleet merge", fleet_id, game_state));
+        }
+
+        if player.has_pending_fleet_move(fleet_id) {
+            return Err(CommandError::InvalidArgument {
+                command: String::from("fleet merge"),
+                argument: fleet_id.clone(),
+                reason: String::from("fleet has a pending move"),
+            });
+        }
+
+        if player.has_pending_fleet_bombardment(fleet_id) {
+            return Err(CommandError::InvalidArgument {
+                command: String::from("fleet merge"),
+                argument: fleet_id.clone(),
+                reason: String::from("fleet is bombarding - cancel bombardment first"),
+            });
+        }
+    }
+
+    // Check fleets are at the same location
+    let into_location = &player.fleets[into_fleet].location;
+    let from_location = &player.fleets[from_fleet].location;
+    if into_location != from_location {
+        return Err(CommandError::InvalidArgument {
+            command: String::from("fleet merge"),
+            argument: from_fleet.clone(),
+            reason: format!("fleet is at {} but '{}' is at {}", from_location, into_fleet, into_location),
+        });
+    }
+
+    let used = rules::capacity_used(&player.fleets[into_fleet].ships, player, game_state);
+    let adding = rules::capacity_used(&player.fleets[from_fleet].ships, player, game_state);
+    rules::fleet_has_room(used, adding, game_state)
+        .map_err(|reason| rejected("fleet merge", into_fleet, reason))?;
+
+    Ok(CommandEffect::MergeFleets {
+        into_fleet: into_fleet.clone(),
+        from_fleet: from_fleet.clone(),
+    })
+}
+
+fn validate_split(
+    fleet_id: &FleetId,
+    name: &str,
+    ship_ids: &[ShipInstanceId],
+    game_state: &GameState,
+) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+
+    // Check fleet exists
+    let fleet = player.fleets.get(fleet_id)
+        .ok_or_else(|| CommandError::fleet_not_found("fleet split", fleet_id, game_state))?;
+
+    // Ships in transit cannot be left behind
+    if player.has_pending_fleet_move(fleet_id) {
+        return Err(CommandError::InvalidArgument {
+            command: String::from("fleet split"),
+            argument: fleet_id.clone(),
+            reason: String::from("fleet has a pending move"),
+        });
+    }
+
+    // Check all ships are in this fleet and listed once
+    for (index, ship_id) in ship_ids.iter().enumerate() {
+        if !fleet.ships.contains(ship_id) {
+            return Err(CommandError::InvalidArgument {
+                command: String::from("fleet split"),
+                argument: ship_id.clone(),
+                reason: format!("ship is not in fleet '{}'", fleet_id),
+            });
+        }
+
+        if ship_ids[..index].contains(ship_id) {
+            return Err(CommandError::InvalidArgument {
+                command: String::from("fleet split"),
+                argument: ship_id.clone(),
+                reason: String::from("ship is listed more than once"),
+            });
+        }
+    }
+    // The cargo stays with the fleet being split
+    rules::cargo_fits_without(fleet, ship_ids, player, game_state)
+        .map_err(|reason| rejected("fleet split", fleet_id, reason))?;
+
+    Ok(CommandEffect::SplitFleet {
+        fleet_id: fleet_id.clone(),
+        name: name.to_string(),
+        ship_ids: ship_ids.to_vec(),
+    })
+}
+
+fn validate_save_template(
+    name: &str,
+    fleet_id: &FleetId,
+    game_state: &GameState,
+) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+
+    // Check fleet exists
+    let fleet = player.fleets.get(fleet_id)
+        .ok_or_else(|| CommandError::fleet_not_found("fleet template save", fleet_id, game_state))?;
+
+    // Check fleet is not empty
+    if fleet.is_empty() {
+        return Err(CommandError::InvalidArgument {
+            command: String::from("fleet template save"),
+            argument: fleet_id.clone(),
+            reason: String::from("fleet has no ships"),
+        });
+    }
+
+    let mut template = FleetTemplate::default();
+    for ship in fleet.ships.iter().filter_map(|ship_id| player.ships.get(ship_id)) {
+        *template.ships.entry(ship.ship_type.clone()).or_default() += 1;
+    }
+
+    Ok(CommandEffect::SaveFleetTemplate {
+        name: name.to_string(),
+        template,
+    })
+}
+
+fn format_templates(player: &Player) -> String {
+    if player.fleet_templates.is_empty() {
+        return String::from("No fleet templates. Save one with: fleet template save <name> <fleet_id>");
+    }
+
+    let mut msg = String::from("=== Fleet Templates ===\n");
+    for (name, template) in &player.fleet_templates {
+        msg.push_str(&format!("  {} ({} ship(s)): {}\n", name, template.ship_count(), template));
+    }
+    msg
+}
+
+/// Plans which ships of a template can be queued at a planet right now. Ships are taken in
+/// template order while the shipyard level, free slots and remaining resources allow.
+fn validate_build_template(
+    template_name: &str,
+    planet_id: &PlanetId,
+    game_state: &GameState,
+) -> Result<CommandEffect, CommandError> {
+    let current_player_id = game_state.current_player();
+    let player = CommandError::acting_player(game_state)?;
+
+    let template = player.fleet_templates.get(template_name)
+        .ok_or_else(|| CommandError::InvalidArgument {
+        command: String::from("fleet build"),
+        argument: template_name.to_string(),
+        reason: String::from("template not found"),
+    })?;
+
+    let (_, planet) = CommandError::own_planet(planet_id, game_state)?;
+
+    let shipyard_level = planet.get_structure_level(&String::from("orbital_shipyard"));
+    let mut free_slots = game_state.free_shipyard_slots(current_player_id, planet_id);
+    let mut remaining = planet.available_resources.clone();
+    let mut queued: Vec<ShipId> = Vec::new();
+    let mut skipped: Vec<(ShipId, String)> = Vec::new();
+
+    for (ship_type, count) in &template.ships {
+        let ship_def = game_state.ship_config.get(ship_type);
+
+        for _ in 0..*count {
+            let reason = match &ship_def {
+                None => String::from("unknown ship type"),
+                Some(def) if shipyard_level < def.required_shipyard_level => {
+                    format!("requires shipyard level {}", def.required_shipyard_level)
+                }
+                Some(_) if free_slots == 0 => String::from("no free shipyard slot"),
+                Some(def) if !remaining.has_enough(&def.cost) => String::from("not enough resources"),
+                Some(def) => {
+                    remaining -= &def.cost;
+                    free_slots -= 1;
+                    queued.push(ship_type.clone());
+                    continue;
+                }
+            };
+            skipped.push((ship_type.clone(), reason));
+        }
+    }
+
+    Ok(CommandEffect::BuildFleetTemplate {
+        template_name: template_name.to_string(),
+        planet_id: planet_id.clone(),
+        queued,
+        skipped,
+    })
+}
+
+fn validate_move(
+    fleet_id: &FleetId,
+    target_planet: &PlanetId,
+    game_state: &GameState,
+) -> Result<CommandEffect, CommandError> {
+    Ok(CommandEffect::MoveFleet {
+        fleet_id: fleet_id.clone(),
+        target_planet: target_planet.clone(),
+        distance: move_distance(fleet_id, target_planet, game_state)?,
+    })
+}
+
+/// Travel time of the fleet to `target_planet`, when it may set off for it.
+fn move_distance(fleet_id: &FleetId, target_planet: &PlanetId, game_state: &GameState) -> Result<u8, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+
+    let fleet = player.fleets.get(fleet_id)
+        .ok_or_else(|| CommandError::fleet_not_found("fleet move", fleet_id, game_state))?;
+    rules::can_move(fleet, target_planet, player, game_state)
+        .map_err(|reason| rejected("fleet move", fleet_id, reason))
+}
+
+/// Checks the move of each fleet with the same rules as a single `fleet move`. Fleets that
+/// pass are ordered, the others are listed with the reason.
+fn validate_move_many(
+    fleet_ids: &[FleetId],
+    target_planet: &PlanetId,
+    game_state: &GameState,
+) -> Result<CommandEffect, CommandError> {
+    // Every fleet would fail alike on a planet that does not exist
+    if !game_state.map.planets.contains_key(target_planet) {
+        return Err(CommandError::UnknownPlanet(target_planet.clone()));
+    }
+
+    let mut moves: Vec<(FleetId, u8)> = Vec::new();
+    let mut skipped: Vec<(FleetId, String)> = Vec::new();
+    for fleet_id in fleet_ids {
+        match move_distance(fleet_id, target_planet, game_state) {
+            Ok(distance) => moves.push((fleet_id.clone(), distance)),
+            Err(CommandError::InvalidArgument { reason, .. }) => skipped.push((fleet_id.clone(), reason)),
+            Err(error) => skipped.push((fleet_id.clone(), error.to_string())),
+        }
+    }
+
+    Ok(CommandEffect::MoveFleets {
+        target_planet: target_planet.clone(),
+        moves,
+        skipped,
+    })
+}
+
+/// Starts a bombardment. A target structure only has to exist in the catalog: what stands
+/// on an enemy planet may be out of sight, so its absence only shows when the damage lands.
+fn validate_bombard(
+    fleet_id: &FleetId,
+    target_structure: Option<StructureId>,
+    game_state: &GameState,
+) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+    if let Some(structure_id) = &target_structure
+        && game_state.structure_config.get(structure_id).is_none()
+    {
+        return Err(CommandError::UnknownStructure {
+            name: structure_id.clone(),
+            suggestions: game_state.structure_config.suggest_similar(structure_id),
+        });
+    }
+
+    let fleet = player.fleets.get(fleet_id)
+        .ok_or_else(|| CommandError::fleet_not_found("fleet bombard", fleet_id, game_state))?;
+    let planet = game_state.map.planets.get(&fleet.location)
+        .expect("Fleet location must exist");
+    let bombardment_power = rules::can_bombard(fleet, planet, player, game_state)
+        .map_err(|reason| rejected("fleet bombard", fleet_id, reason))?;
+
+    Ok(CommandEffect::BombardPlanet {
+        fleet_id: fleet_id.clone(),
+        target_planet: fleet.location.clone(),
+        bombardment_power,
+        target_structure,
+    })
+}
+
+fn validate_cancel_bombard(
+    fleet_id: &FleetId,
+    game_state: &GameState,
+) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+
+    // Check fleet exists
+    if !player.fleets.contains_key(fleet_id) {
+        return Err(CommandError::fleet_not_found("fleet cancel-bombard", fleet_id, game_state));
+    }
+
+    // Check fleet is actually bombarding
+    if !player.has_pending_fleet_bombardment(fleet_id) {
+        return Err(CommandError::InvalidArgument {
+            command: String::from("fleet cancel-bombard"),
+            argument: fleet_id.clone(),
+            reason: String::from("fleet is not bombarding"),
+        });
+    }
+
+    Ok(CommandEffect::CancelBombard {
+        fleet_id: fleet_id.clone(),
+    })
+}
+
+/// Puts a fleet on sentry (`hold`) or takes it off (`wake`). Only a fleet without a pending
+/// move can hold position.
+fn validate_sentry(fleet_id: &FleetId, sentry: bool, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+    let command = if sentry { "fleet hold" } else { "fleet wake" };
+
+    let reason = match player.fleets.get(fleet_id) {
+        None => return Err(CommandError::fleet_not_found(command, fleet_id, game_state)),
+        Some(fleet) if fleet.sentry == sentry => Some(if sentry { "fleet is already holding" } else { "fleet is not holding" }),
+        Some(_) if sentry && player.has_pending_fleet_move(fleet_id) => Some("fleet has a pending move"),
+        Some(_) => None,
+    };
+    if let Some(reason) = reason {
+        return Err(CommandError::InvalidArgument {
+            command: String::from(command),
+            argument: fleet_id.clone(),
+            reason: String::from(reason),
+        });
+    }
+
+    Ok(CommandEffect::SetFleetSentry { fleet_id: fleet_id.clone(), sentry })
+}
+
+fn validate_colonize(
+    fleet_id: &FleetId,
+    game_state: &GameState,
+) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+
+    let fleet = player.fleets.get(fleet_id)
+        .ok_or_else(|| CommandError::fleet_not_found("fleet colonize", fleet_id, game_state))?;
+    let planet = game_state.map.planets.get(&fleet.location)
+        .expect("Fleet location must exist");
+    rules::can_colonize(fleet, planet, player, game_state)
+        .map_err(|reason| rejected("fleet colonize", fleet_id, reason))?;
+
+    Ok(CommandEffect::ColonizePlanet {
+        fleet_id: fleet_id.clone(),
+        planet_id: fleet.location.clone(),
+    })
+}
+
+fn validate_load(fleet_id: &FleetId, cargo: Resources, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+
+    let fleet = player.fleets.get(fleet_id)
+        .ok_or_else(|| CommandError::fleet_not_found("fleet load", fleet_id, game_state))?;
+    let planet = game_state.map.planets.get(&fleet.location)
+        .expect("Fleet location must exist");
+    rules::can_load(fleet, &cargo, planet, player, game_state)
+        .map_err(|reason| rejected("fleet load", fleet_id, reason))?;
+
+    Ok(CommandEffect::LoadCargo { fleet_id: fleet_id.clone(), cargo })
+}
+
+fn validate_unload(fleet_id: &FleetId, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+
+    let fleet = player.fleets.get(fleet_id)
+        .ok_or_else(|| CommandError::fleet_not_found("fleet unload", fleet_id, game_state))?;
+    let planet = game_state.map.planets.get(&fleet.location)
+        .expect("Fleet location must exist");
+    rules::can_unload(fleet, planet, player)
+        .map_err(|reason| rejected("fleet unload", fleet_id, reason))?;
+
+    Ok(CommandEffect::UnloadCargo { fleet_id: fleet_id.clone() })
+}
+
+/// Turns a rule violation into the error of a fleet or ship command. Reasons about the
+/// destination name the planet as the offending argument, all others the fleet or ship.
+pub(super) fn rejected(command: &str, unit_id: &str, reason: Reason) -> CommandError {
+    let argument = match &reason {
+        Reason::UnknownPlanet(planet_id) => return CommandError::UnknownPlanet(planet_id.clone()),
+        Reason::AlreadyThere { planet_id }
+        | Reason::NoConnection { to: planet_id, .. }
+        | Reason::Unreachable { to: planet_id, .. } => planet_id.clone(),
+        _ => unit_id.to_string(),
+    };
+    CommandError::InvalidArgument {
+        command: command.to_string(),
+        argument,
+        reason: reason.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::parser::{self, Parseable};
+    use crate::engine::commands::command::AssetKind;
+    use crate::engine::fleet::Fleet;
+    use crate::engine::game_event::GameEvent;
+    use crate::engine::pending_action::{ActionType, PendingAction};
+    use crate::engine::resources::Resources;
+    use crate::test_support::{self, fixture_game, fixture_team_game};
+
+    #[test]
+    fn test_parse_bombard_command() {
+        let args = vec!["bombard", "fleet_alpha"];
+        let result = FleetArgs::parse(args);
+
+        assert!(result.is_ok());
+        let parsed = result.unwrap();
+        match parsed.action {
+            FleetAction::Bombard { fleet_id, target_structure } => {
+                assert_eq!(fleet_id, "fleet_alpha");
+                assert_eq!(target_structure, None);
+            }
+            _ => panic!("Expected Bombard action"),
+        }
+    }
+
+    #[test]
+    fn test_bombard_target_must_be_a_known_structure_type() {
+        let state = fixture_game(1);
+        let parsed = FleetArgs::parse(vec!["bombard", "fleet_1", "power_grid"]).unwrap();
+        assert!(matches!(parsed.action, FleetAction::Bombard { target_structure: Some(ref target), .. } if target == "power_grid"));
+
+        let error = parser::parse("fleet bombard fleet_1 power_grdi").unwrap().execute(&state).err().unwrap();
+        assert!(matches!(&error, CommandError::UnknownStructure { suggestions, .. } if suggestions.contains(&String::from("power_grid"))), "{error}");
+    }
+
+    #[test]
+    fn test_parse_bombard_missing_fleet_id() {
+        let args = vec!["bombard"];
+        let result = FleetArgs::parse(args);
+
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            CommandError::MissingArguments { command, expected } => {
+                assert_eq!(command, "fleet bombard");
+                assert!(expected.contains("fleet bombard <fleet_id> [target_structure_id]"));
+            }
+            _ => panic!("Expected MissingArguments error"),
+        }
+    }
+
+    #[test]
+    fn test_parse_bombard_in_help_message() {
+        let args = vec!["invalid_action"];
+        let result = FleetArgs::parse(args);
+
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            CommandError::InvalidArgument { command, argument, reason } => {
+                assert_eq!(command, "fleet");
+                assert_eq!(argument, "invalid_action");
+                assert!(reason.contains("bombard"));
+            }
+            _ => panic!("Expected InvalidArgument error"),
+        }
+    }
+
+    #[test]
+    fn test_parse_cancel_bombard_command() {
+        let args = vec!["cancel-bombard", "fleet_alpha"];
+        let result = FleetArgs::parse(args);
+
+        assert!(result.is_ok());
+        let parsed = result.unwrap();
+        match parsed.action {
+            FleetAction::CancelBombard { fleet_id } => {
+                assert_eq!(fleet_id, "fleet_alpha");
+            }
+            _ => panic!("Expected CancelBombard action"),
+        }
+    }
+
+    #[test]
+    fn test_parse_cancel_bombard_missing_fleet_id() {
+        let args = vec!["cancel-bombard"];
+        let result = FleetArgs::parse(args);
+
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            CommandError::MissingArguments { command, expected } => {
+                assert_eq!(command, "fleet cancel-bombard");
+                assert!(expected.contains("fleet cancel-bombard <fleet_id>"));
+            }
+            _ => panic!("Expected MissingArguments error"),
+        }
+    }
+
+    #[test]
+    fn test_parse_cancel_bombard_in_help_message() {
+        let args = vec!["invalid_action"];
+        let result = FleetArgs::parse(args);
+
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            CommandError::InvalidArgument { command, argument, reason } => {
+                assert_eq!(command, "fleet");
+                assert_eq!(argument, "invalid_action");
+                assert!(reason.contains("cancel-bombard"));
+            }
+            _ => panic!("Expected InvalidArgument error"),
+        }
+    }
+
+    #[test]
+    fn test_parse_colonize_command() {
+        let args = vec!["colonize", "fleet_alpha"];
+        let result = FleetArgs::parse(args);
+
+        assert!(result.is_ok());
+        let parsed = result.unwrap();
+        match parsed.action {
+            FleetAction::Colonize { fleet_id } => {
+                assert_eq!(fleet_id, "fleet_alpha");
+            }
+            _ => panic!("Expected Colonize action"),
+        }
+    }
+
+    #[test]
+    fn test_parse_colonize_missing_fleet_id() {
+        let args = vec!["colonize"];
+        let result = FleetArgs::parse(args);
+
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            CommandError::MissingArguments { command, expected } => {
+                assert_eq!(command, "fleet colonize");
+                assert!(expected.contains("fleet colonize <fleet_id>"));
+            }
+            _ => panic!("Expected MissingArguments error"),
+        }
+    }
+
+    #[test]
+    fn test_parse_colonize_in_help_message() {
+        let args = vec!["invalid_action"];
+        let result = FleetArgs::parse(args);
+
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            CommandError::InvalidArgument { command, argument, reason } => {
+                assert_eq!(command, "fleet");
+                assert_eq!(argument, "invalid_action");
+                assert!(reason.contains("colonize"));
+            }
+            _ => panic!("Expected InvalidArgument error"),
+        }
+    }
+
+    #[test]
+    fn test_parse_merge_and_split() {
+        let merge = FleetArgs::parse(vec!["merge", "fleet_1", "fleet_2"]).unwrap();
+        assert!(matches!(merge.action, FleetAction::Merge { into_fleet, from_fleet }
+            if into_fleet == "fleet_1" && from_fleet == "fleet_2"));
+
+        let split = FleetArgs::parse(vec!["split", "fleet_1", "scouts", "interceptor_1", "interceptor_2"]).unwrap();
+        assert!(matches!(split.action, FleetAction::Split { fleet_id, name, ship_ids }
+            if fleet_id == "fleet_1" && name == "scouts" && ship_ids == ["interceptor_1", "interceptor_2"]));
+
+        assert!(matches!(FleetArgs::parse(vec!["merge", "fleet_1"]), Err(CommandError::MissingArguments { .. })));
+        assert!(matches!(FleetArgs::parse(vec!["split", "fleet_1", "scouts"]), Err(CommandError::MissingArguments { .. })));
+    }
+
+    #[test]
+    fn test_parse_move_lists_fleets_comma_separated_or_repeated() {
+        let parsed = FleetArgs::parse(vec!["move", "fleet_1,fleet_3", "fleet_4", "fleet_1,", "crimson_theta"]).unwrap();
+        assert!(matches!(parsed.action, FleetAction::Move { fleet_ids, target_planet }
+            if fleet_ids == ["fleet_1", "fleet_3", "fleet_4"] && target_planet == "crimson_theta"));
+
+        assert!(matches!(FleetArgs::parse(vec!["move", ",", "crimson_theta"]), Err(CommandError::MissingArguments { .. })));
+        assert!(matches!(FleetArgs::parse(vec!["move", "fleet_1"]), Err(CommandError::MissingArguments { .. })));
+    }
+
+    /// Gives alice three interceptors at home: fleet_1 holds the first two, fleet_2 the third.
+    fn game_with_two_fleets() -> (GameState, PlanetId) {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let alice = state.players.get_mut("alice").unwrap();
+        for _ in 0..3 {
+            alice.add_ship(String::from("interceptor"), home.clone());
+        }
+        run(&mut state, vec!["create", "main", "interceptor_1", "interceptor_2"]).unwrap();
+        run(&mut state, vec!["create", "escort", "interceptor_3"]).unwrap();
+        (state, home)
+    }
+
+    /// Parses, validates and applies a fleet command for the current player.
+    fn run(state: &mut GameState, args: Vec<&str>) -> Result<Vec<GameEvent>, CommandError> {
+        let effect = execute(FleetArgs::parse(args)?, state)?;
+        let acting_player = state.current_player().clone();
+        Ok(state.apply_effect(effect, &acting_player).unwrap())
+    }
+
+    fn fleet_of(state: &GameState, ship_id: &str) -> Option<FleetId> {
+        state.players["alice"].ships[ship_id].fleet_id.clone()
+    }
+
+    #[test]
+    fn test_merge_moves_ships_and_disbands_source() {
+        let (mut state, _) = game_with_two_fleets();
+
+        run(&mut state, vec!["merge", "fleet_1", "fleet_2"]).unwrap();
+
+        let alice = &state.players["alice"];
+        assert!(!alice.fleets.contains_key("fleet_2"));
+        assert_eq!(alice.fleets["fleet_1"].ships, ["interceptor_1", "interceptor_2", "interceptor_3"]);
+        assert_eq!(fleet_of(&state, "interceptor_3").as_deref(), Some("fleet_1"));
+    }
+
+    #[test]
+    fn test_merge_rejects_fleet_with_pending_action() {
+        let (mut state, home) = game_with_two_fleets();
+        let neighbour = state.map.planets[&home].get_connections()[0].to().clone();
+        run(&mut state, vec!["move", "fleet_2", &neighbour]).unwrap();
+
+        let result = run(&mut state, vec!["merge", "fleet_1", "fleet_2"]);
+        assert!(matches!(result, Err(CommandError::InvalidArgument { reason, .. }) if reason.contains("pending move")));
+
+        let alice = state.players.get_mut("alice").unwrap();
+        alice.pending_actions.clear();
+        alice.pending_actions.push(PendingAction::new(
+            ActionType::BombardPlanet(String::from("fleet_1"), home.clone()),
+            home,
+            1,
+            Resources::default(),
+        ));
+        let result = run(&mut state, vec!["merge", "fleet_2", "fleet_1"]);
+        assert!(matches!(result, Err(CommandError::InvalidArgument { reason, .. }) if reason.contains("bombarding")));
+        assert_eq!(state.players["alice"].fleets.len(), 2);
+    }
+
+    #[test]
+    fn test_split_forms_new_fleet_at_same_location() {
+        let (mut state, home) = game_with_two_fleets();
+
+        let events = run(&mut state, vec!["split", "fleet_1", "scouts", "interceptor_2"]).unwrap();
+
+        // fleet_3 is the next free id
+        assert!(matches!(&events[..], [GameEvent::FleetSplit { fleet_id, ship_count: 1, .. }] if fleet_id == "fleet_3"));
+        let alice = &state.players["alice"];
+        assert_eq!(alice.fleets["fleet_1"].ships, ["interceptor_1"]);
+        assert_eq!(alice.fleets["fleet_3"].ships, ["interceptor_2"]);
+        assert_eq!(alice.fleets["fleet_3"].location, home);
+        assert_eq!(fleet_of(&state, "interceptor_2").as_deref(), Some("fleet_3"));
+    }
+
+    #[test]
+    fn test_split_rejects_ships_outside_fleet_and_moving_fleets() {
+        let (mut state, home) = game_with_two_fleets();
+
+        let result = run(&mut state, vec!["split", "fleet_1", "scouts", "interceptor_3"]);
+        assert!(matches!(result, Err(CommandError::InvalidArgument { argument, .. }) if argument == "interceptor_3"));
+
+        let neighbour = state.map.planets[&home].get_connections()[0].to().clone();
+        run(&mut state, vec!["move", "fleet_1", &neighbour]).unwrap();
+        let result = run(&mut state, vec!["split", "fleet_1", "scouts", "interceptor_1"]);
+        assert!(matches!(result, Err(CommandError::InvalidArgument { reason, .. }) if reason.contains("pending move")));
+    }
+
+    #[test]
+    fn test_fleet_ids_are_reused_after_disband_without_collisions() {
+        let (mut state, _) = game_with_two_fleets();
+
+        run(&mut state, vec!["disband", "fleet_1"]).unwrap();
+        run(&mut state, vec!["split", "fleet_2", "scouts", "interceptor_3"]).unwrap();
+
+        let alice = &state.players["alice"];
+        assert_eq!(alice.fleets["fleet_1"].name, "scouts");
+        assert_eq!(alice.fleets["fleet_2"].name, "escort");
+    }
+
+    #[test]
+    fn test_another_players_fleet_or_ship_is_reported_as_theirs() {
+        let (mut state, _) = game_with_two_fleets();
+        let bob_home = test_support::home_planet(&state, "bob");
+        let bob = state.players.get_mut("bob").unwrap();
+        bob.add_ship(String::from("ravager"), bob_home.clone());
+        bob.fleets.insert(String::from("fleet_7"), Fleet::new(String::from("fleet_7"), String::from("raiders"), bob_home));
+
+        let result = run(&mut state, vec!["disband", "fleet_7"]);
+        assert!(matches!(
+            result,
+            Err(CommandError::NotYourAsset { kind: AssetKind::Fleet, id, owner, owner_id })
+                if id == "fleet_7" && owner == "Bob" && owner_id == "bob"
+        ));
+
+        let result = run(&mut state, vec!["add", "fleet_1", "ravager_1"]);
+        let error = result.unwrap_err();
+        assert!(matches!(&error, CommandError::NotYourAsset { kind: AssetKind::Ship, .. }));
+        assert_eq!(error.to_string(), "Ship ravager_1 belongs to Bob");
+    }
+
+    #[test]
+    fn test_orders_out_of_turn_tell_own_assets_from_others() {
+        let mut state = fixture_team_game(1);
+        for (player_id, fleet_id) in [("bob", "fleet_7"), ("carol", "fleet_8")] {
+            let home = test_support::home_planet(&state, player_id);
+            let player = state.players.get_mut(player_id).unwrap();
+            player.fleets.insert(String::from(fleet_id), Fleet::new(String::from(fleet_id), String::from("raiders"), home));
+        }
+        let as_player = |line: &str, issuer: &str| {
+            parser::parse(line).unwrap().execute_as(&state, &String::from(issuer)).err()
+        };
+
+        let error = as_player("fleet disband fleet_7", "bob").unwrap();
+        assert!(matches!(
+            &error,
+            CommandError::NotYourTurn { current, asset: Some((AssetKind::Fleet, id)) } if current == "Alice" && id == "fleet_7"
+        ));
+        assert_eq!(error.to_string(), "It is currently Alice's turn; fleet fleet_7 belongs to you, wait for your turn");
+
+        let error = as_player("fleet disband fleet_8", "bob").unwrap();
+        assert!(matches!(&error, CommandError::NotYourAsset { owner_id, .. } if owner_id == "carol"));
+        let error = as_player("status player", "bob").unwrap();
+        assert_eq!(error.to_string(), "It is currently Alice's turn; wait for your turn");
+
+        // The current player and commands outside the game are unaffected
+        assert!(matches!(as_player("fleet disband fleet_7", "alice"), Some(CommandError::NotYourAsset { .. })));
+        assert!(as_player("help", "bob").is_none());
+    }
+
+    #[test]
+    fn test_rule_reasons_name_the_offending_argument() {
+        let (mut state, home) = game_with_two_fleets();
+
+        let result = run(&mut state, vec!["move", "fleet_1", &home]);
+        assert!(matches!(result, Err(CommandError::InvalidArgument { argument, .. }) if argument == home));
+
+        let result = run(&mut state, vec!["move", "fleet_1", "nowhere"]);
+        assert!(matches!(result, Err(CommandError::UnknownPlanet(planet_id)) if planet_id == "nowhere"));
+
+        let result = run(&mut state, vec!["colonize", "fleet_1"]);
+        assert!(matches!(
+            result,
+            Err(CommandError::InvalidArgument { argument, reason, .. }) if argument == "fleet_1" && reason == Reason::NoArk.to_string()
+        ));
+    }
+
+    #[test]
+    fn test_moving_several_fleets_orders_each_that_can_go() {
+        let (mut state, home) = game_with_two_fleets();
+        let neighbour = state.map.planets[&home].get_connections()[0].to().clone();
+        state.players.get_mut("alice").unwrap().fleets
+            .insert(String::from("fleet_3"), Fleet::new(String::from("fleet_3"), String::from("empty"), home.clone()));
+        run(&mut state, vec!["move", "fleet_1", &neighbour]).unwrap();
+
+        let events = run(&mut state, vec!["move", "fleet_1,fleet_2", "fleet_3", "fleet_9", &neighbour]).unwrap();
+
+        assert!(matches!(&events[0], GameEvent::FleetMoveOrdered { fleet_id, .. } if fleet_id == "fleet_2"));
+        let Some(GameEvent::FleetsMoveOrdered { results, .. }) = events.last() else {
+            panic!("Expected FleetsMoveOrdered, got {:?}", events);
+        };
+        let reason_for = |fleet_id: &str| results.iter().find(|(id, _)| id == fleet_id).unwrap().1.clone();
+        assert_eq!(results.len(), 4);
+        assert_eq!(reason_for("fleet_2"), None);
+        assert_eq!(reason_for("fleet_1").unwrap(), Reason::MovePending.to_string());
+        assert_eq!(reason_for("fleet_3").unwrap(), Reason::EmptyFleet.to_string());
+        assert_eq!(reason_for("fleet_9").unwrap(), "fleet not found");
+        let moving = state.players["alice"].pending_actions.iter()
+            .filter(|action| matches!(&action.action_type, ActionType::MoveFleet(_, target) if *target == neighbour))
+            .count();
+        assert_eq!(moving, 2);
+        assert!(events.last().unwrap().to_string().starts_with("1 of 4 fleet(s) ordered to"));
+
+        let result = run(&mut state, vec!["move", "fleet_2,fleet_3", "nowhere"]);
+        assert!(matches!(result, Err(CommandError::UnknownPlanet(planet_id)) if planet_id == "nowhere"));
+    }
+
+    #[test]
+    fn test_fleet_capacity_counts_ship_sizes_on_create_add_and_merge() {
+        // Interceptors take 1 capacity and ravagers 3; fleet_1 uses 2 and fleet_2 uses 1
+        let (mut state, home) = game_with_two_fleets();
+        state.rules.profile.fleet_capacity = 4;
+        for _ in 0..2 {
+            state.players.get_mut("alice").unwrap().add_ship(String::from("ravager"), home.clone());
+        }
+
+        let result = run(&mut state, vec!["create", "wing", "ravager_1", "ravager_2"]);
+        assert!(matches!(result, Err(CommandError::InvalidArgument { reason, .. }) if reason.contains("uses 0 and 6 more")));
+
+        let error = run(&mut state, vec!["add", "fleet_1", "ravager_1"]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid argument 'fleet_1' for command 'fleet add': fleet capacity is 4 - it uses 2 and 3 more does not fit"
+        );
+
+        run(&mut state, vec!["add", "fleet_2", "ravager_1"]).unwrap();
+        let alice = &state.players["alice"];
+        assert_eq!(rules::capacity_used(&alice.fleets["fleet_2"].ships, alice, &state), 4);
+
+        let result = run(&mut state, vec!["merge", "fleet_1", "fleet_2"]);
+        assert!(matches!(result, Err(CommandError::InvalidArgument { argument, reason, .. })
+            if argument == "fleet_1" && reason.contains("uses 2 and 4 more")));
+        assert_eq!(state.players["alice"].fleets.len(), 2);
+
+        let listing = match crate::engine::commands::fleets::execute(&state).unwrap() {
+            CommandEffect::None { message } => message,
+            _ => panic!("Fleets must only produce a message"),
+        };
+        assert!(listing.contains("Ships: 2 | capacity 4/4"), "{listing}");
+    }
+
+    #[test]
+    fn test_unknown_fleet_or_ship_is_not_found() {
+        let (mut state, _) = game_with_two_fleets();
+
+        let result = run(&mut state, vec!["disband", "fleet_7"]);
+        assert!(matches!(result, Err(CommandError::InvalidArgument { reason, .. }) if reason == "fleet not found"));
+
+        let result = run(&mut state, vec!["add", "fleet_1", "ravager_1"]);
+        assert!(matches!(result, Err(CommandError::InvalidArgument { reason, .. }) if reason == "ship not found"));
+    }
+
+    #[test]
+    fn test_template_save_and_list() {
+        let (mut state, _) = game_with_two_fleets();
+
+        run(&mut state, vec!["template", "save", "pair", "fleet_1"]).unwrap();
+        let events = run(&mut state, vec!["template", "list"]).unwrap();
+
+        let template = &state.players["alice"].fleet_templates["pair"];
+        assert_eq!(template.ships.get("interceptor"), Some(&2));
+        assert!(matches!(&events[..], [GameEvent::Info { message }] if message.contains("pair (2 ship(s)): 2x interceptor")));
+    }
+
+    /// Gives alice's home planet a shipyard at `level` and the given resources, and a "strike"
+    /// template of three interceptors and one ravager.
+    fn home_ready_for_strike(state: &mut GameState, level: u16, resources: Resources) -> PlanetId {
+        let home = test_support::home_planet(state, "alice");
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.complete_build_structure(String::from("orbital_shipyard"), &state.structure_config).unwrap();
+        for _ in 1..level {
+            planet.complete_upgrade_structure(&String::from("orbital_shipyard")).unwrap();
+        }
+        planet.available_resources = resources;
+
+        let template = FleetTemplate {
+            ships: [(String::from("interceptor"), 3), (String::from("ravager"), 1)].into_iter().collect(),
+        };
+        state.players.get_mut("alice").unwrap().fleet_templates.insert(String::from("strike"), template);
+        home
+    }
+
+    fn queued_ship_types(state: &GameState) -> Vec<String> {
+        state.players["alice"].pending_actions.iter()
+            .filter_map(|action| match &action.action_type {
+                ActionType::BuildShip(ship_type, _) => Some(ship_type.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_partially_affordable_template_queues_what_fits() {
+        let mut state = fixture_game(1);
+        let home = home_ready_for_strike(&mut state, 3, Resources { minerals: 250, gas: 500, energy: 0 });
+
+        let events = run(&mut state, vec!["build", "strike", &home]).unwrap();
+
+        // Two interceptors use 200 minerals, leaving too little for the rest
+        assert_eq!(queued_ship_types(&state), ["interceptor", "interceptor"]);
+        let Some(GameEvent::FleetTemplateQueued { queued, skipped, .. }) = events.last() else {
+            panic!("Expected a template report, got {:?}", events);
+        };
+        assert_eq!(*queued, 2);
+        assert_eq!(skipped, &[
+            (String::from("interceptor"), String::from("not enough resources")),
+            (String::from("ravager"), String::from("not enough resources")),
+        ]);
+        assert!(events.last().unwrap().to_string()
+            .ends_with("Not queued: 1x interceptor: not enough resources, 1x ravager: not enough resources"));
+        assert_eq!(state.map.planets[&home].available_resources.minerals, 50);
+    }
+
+    #[test]
+    fn test_template_build_respects_slots_and_shipyard_level() {
+        let mut state = fixture_game(1);
+        let home = home_ready_for_strike(&mut state, 1, Resources { minerals: 1000, gas: 1000, energy: 0 });
+
+        let events = run(&mut state, vec!["build", "strike", &home]).unwrap();
+
+        assert_eq!(queued_ship_types(&state), ["interceptor"]);
+        assert!(matches!(events.last(), Some(GameEvent::FleetTemplateQueued { queued: 1, skipped, .. }) if skipped == &[
+            (String::from("interceptor"), String::from("no free shipyard slot")),
+            (String::from("interceptor"), String::from("no free shipyard slot")),
+            (String::from("ravager"), String::from("requires shipyard level 2")),
+        ]));
+    }
+}
@@ -0,0 +1,65 @@
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::fleet::Fleet;
+use crate::engine::game_state::GameState;
+use crate::engine::player::Player;
+use crate::engine::rules;
+
+pub fn execute(game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+
+    if player.fleets.is_empty() {
+        return Ok(CommandEffect::None {
+            message: String::from("No fleets formed. Use 'fleet create <name> <ship_id>...' to create one."),
+        });
+    }
+
+    let mut msg = String::from("=== Your Fleets ===\n");
+
+    for fleet in player.fleets.values() {
+        let planet_name = game_state
+            .map
+            .planets
+            .get(&fleet.location)
+            .map(|p| p.name.as_str())
+            .unwrap_or("Unknown");
+
+        let sentry = if fleet.sentry { " [Holding]" } else { "" };
+        msg.push_str(&format!(
+            "\n{} ({}) at {} ({}){}:\n",
+            fleet.name, fleet.id, planet_name, fleet.location, sentry
+        ));
+
+        if fleet.ships.is_empty() {
+            msg.push_str("  (empty)\n");
+        } else {
+            for ship_id in &fleet.ships {
+                if let Some(ship) = player.ships.get(ship_id) {
+                    msg.push_str(&format!("  - {} ({})\n", ship.id, ship.ship_type));
+                }
+            }
+        }
+
+        msg.push_str(&format!(
+            "  Ships: {} | capacity {}/{}\n",
+            fleet.ship_count(), rules::capacity_used(&fleet.ships, player, game_state), game_state.rules_profile().fleet_capacity
+        ));
+        if let Some(cargo) = format_cargo(fleet, player, game_state) {
+            msg.push_str(&format!("  Cargo: {}
+", cargo));
+        }
+    }
+
+    msg.push_str(&format!("\nTotal fleets: {}", player.fleets.len()));
+
+    Ok(CommandEffect::None { message: msg })
+}
+
+/// The fleet's cargo against its holds, such as "120/200 (100 minerals, 20 gas, 0 energy)";
+/// None for fleets with no ship able to carry any.
+pub fn format_cargo(fleet: &Fleet, player: &Player, game_state: &GameState) -> Option<String> {
+    let capacity = rules::cargo_capacity(&fleet.ships, player, game_state);
+    (capacity > 0 || fleet.cargo.total() > 0).then(|| format!(
+        "{}/{} ({} minerals, {} gas, {} energy)",
+        fleet.cargo.total(), capacity, fleet.cargo.minerals, fleet.cargo.gas, fleet.cargo.energy
+    ))
+}
@@ -0,0 +1,137 @@
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::parser::Parseable;
+use crate::engine::commands::schedule;
+use crate::engine::game_state::GameState;
+
+/// Rounds forecast when no count is given.
+pub const DEFAULT_FORECAST_TURNS: u32 = 5;
+
+/// Longest forecast accepted, to keep the table readable.
+pub const MAX_FORECAST_TURNS: u32 = 50;
+
+pub struct ForecastArgs {
+    pub planet_name: String,
+    pub turns: u32,
+}
+
+impl Parseable for ForecastArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        let Some(planet_name) = args.first() else {
+            return Err(CommandError::MissingArguments {
+                command: String::from("forecast"),
+                expected: String::from("forecast <planet_id> [turns]"),
+            });
+        };
+
+        let turns = match args.get(1) {
+            None => DEFAULT_FORECAST_TURNS,
+            Some(turns) => turns.parse().ok()
+                .filter(|turns| (1..=MAX_FORECAST_TURNS).contains(turns))
+                .ok_or_else(|| CommandError::InvalidArgument {
+                    command: String::from("forecast"),
+                    argument: turns.to_string(),
+                    reason: format!("turns must be a number from 1 to {}", MAX_FORECAST_TURNS),
+                })?,
+        };
+
+        Ok(ForecastArgs { planet_name: planet_name.to_string(), turns })
+    }
+}
+
+/// Shows the resources an owned planet is projected to hold at the start of each of the
+/// current player's next turns, accounting for storage caps, empire bonuses and pending builds
+/// and upgrades. Either production timing lands one production before each of those turns.
+/// What the player has due at the planet within the forecast follows the table.
+pub fn execute(args: ForecastArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let (_, planet) = CommandError::own_planet(&args.planet_name, game_state)?;
+
+    let player = CommandError::acting_player(game_state)?;
+    let bonus_percent = player.empire_effects.map_or(0, |effects| effects.production_bonus_percent);
+    let forecast = planet.forecast(&player.pending_actions, &game_state.structure_config, bonus_percent, args.turns);
+
+    let mut msg = format!("=== Forecast: {} ({}) ===\n", planet.name, planet.id);
+    msg.push_str(&format!("{:<6} {:>10} {:>10} {:>10}\n", "Turn", "Minerals", "Gas", "Energy"));
+    msg.push_str(&format!(
+        "{:<6} {:>10} {:>10} {:>10}\n",
+        "now", planet.available_resources.minerals, planet.available_resources.gas, planet.available_resources.energy
+    ));
+    for (offset, resources) in (1..).zip(&forecast) {
+        msg.push_str(&format!(
+            "{:<6} {:>10} {:>10} {:>10}\n",
+            game_state.turn + offset, resources.minerals, resources.gas, resources.energy
+        ));
+    }
+    msg.push_str(&format!("Current storage: {}\n", planet.storage_capacity));
+
+    let last_turn = game_state.turn + args.turns;
+    let due: Vec<String> = game_state.completion_schedule(&player.id).range(..=last_turn)
+        .flat_map(|(turn, items)| items.iter().map(move |item| (turn, item)))
+        .filter(|(_, item)| item.planet_id() == &planet.id)
+        .map(|(turn, item)| format!("  Turn {}: {}\n", turn, schedule::describe(item, game_state)))
+        .collect();
+    if !due.is_empty() {
+        msg.push_str("Due here:\n");
+        msg.extend(due);
+    }
+
+    Ok(CommandEffect::None { message: msg })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::pending_action::{ActionType, PendingAction};
+    use crate::engine::resources::Resources;
+    use crate::test_support::{self, fixture_game};
+
+    fn forecast_message(game_state: &GameState, planet_id: &str, turns: u32) -> String {
+        let args = ForecastArgs { planet_name: planet_id.to_string(), turns };
+        match execute(args, game_state).unwrap() {
+            CommandEffect::None { message } => message,
+            _ => panic!("Forecast must be informational"),
+        }
+    }
+
+    #[test]
+    fn test_parse_defaults_and_validates_turns() {
+        assert_eq!(ForecastArgs::parse(vec!["kepler"]).unwrap().turns, DEFAULT_FORECAST_TURNS);
+        assert_eq!(ForecastArgs::parse(vec!["kepler", "3"]).unwrap().turns, 3);
+        for turns in ["0", "x", "51"] {
+            assert!(matches!(
+                ForecastArgs::parse(vec!["kepler", turns]),
+                Err(CommandError::InvalidArgument { .. })
+            ));
+        }
+        assert!(matches!(ForecastArgs::parse(vec![]), Err(CommandError::MissingArguments { .. })));
+    }
+
+    #[test]
+    fn test_table_lists_each_turn_with_pending_build() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        state.map.planets.get_mut(&home).unwrap().available_resources = Resources { minerals: 100, gas: 0, energy: 0 };
+        state.players.get_mut("alice").unwrap().pending_actions.push(PendingAction::new(
+            ActionType::BuildStructure(String::from("mining_complex")), home.clone(), 2, Resources::default(),
+        ));
+
+        let message = forecast_message(&state, &home, 3);
+        let rows: Vec<_> = message.lines()
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_digit()))
+            .map(|line| line.split_whitespace().take(2).collect::<Vec<_>>())
+            .collect();
+
+        assert_eq!(rows, vec![vec!["2", "110"], vec!["3", "160"], vec!["4", "210"]]);
+        let name = &state.map.planets[&home].name;
+        assert!(message.ends_with(&format!("Due here:\n  Turn 3: mining_complex completes on {}\n", name)), "{message}");
+        assert!(!forecast_message(&state, &home, 1).contains("Due here"));
+    }
+
+    #[test]
+    fn test_other_players_planets_are_rejected() {
+        let state = fixture_game(1);
+        let bob_home = test_support::home_planet(&state, "bob");
+        let args = ForecastArgs { planet_name: bob_home, turns: 5 };
+
+        assert!(matches!(execute(args, &state), Err(CommandError::NotYourPlanet(_))));
+    }
+}
@@ -0,0 +1,377 @@
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::parser::{END_TURN_ALIASES, EXIT_ALIASES, Parseable};
+use crate::engine::game_state::GameState;
+use crate::engine::planet::JUMP_GATE;
+use crate::engine::rules::{self, OrbitRules, Requirement, Standing};
+
+/// Commands only games started with --dev accept.
+const DEV_COMMANDS: &[&str] = &["inspect", "status perf", "dev"];
+
+/// Orders that need something before they apply, by the words they start with.
+const REQUIREMENTS: &[(&str, Requirement)] = &[
+    ("build_ship", Requirement::Structure("orbital_shipyard")),
+    ("ship retrofit", Requirement::Structure("orbital_shipyard")),
+    ("ship move", Requirement::Ships),
+    ("fleet create", Requirement::Ships),
+    ("fleet add", Requirement::Fleets),
+    ("fleet remove", Requirement::Fleets),
+    ("fleet disband", Requirement::Fleets),
+    ("fleet merge", Requirement::Fleets),
+    ("fleet split", Requirement::Fleets),
+    ("fleet template save", Requirement::Fleets),
+    ("fleet build", Requirement::Structure("orbital_shipyard")),
+    ("fleet move", Requirement::Fleets),
+    ("fleet bombard", Requirement::Fleets),
+    ("fleet cancel-bombard", Requirement::Fleets),
+    ("fleet colonize", Requirement::Ark),
+    ("fleet hold", Requirement::Fleets),
+    ("fleet wake", Requirement::Fleets),
+    ("fleet load", Requirement::Fleets),
+    ("fleet unload", Requirement::Fleets),
+    ("planet rally", Requirement::Structure("orbital_shipyard")),
+    ("planet garrison", Requirement::Ships),
+    ("planet ungarrison", Requirement::Ships),
+    ("planet overcharge", Requirement::Structure("defense_shield")),
+    ("planet link", Requirement::Structure(JUMP_GATE)),
+];
+
+/// Pages of help beyond the command reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpTopic {
+    /// What fleets may do at the planets they orbit
+    Rules,
+}
+
+#[derive(Debug, Default)]
+pub struct HelpArgs {
+    /// Whether the session accepts developer commands, filled in by the frontend
+    pub dev_mode: bool,
+    pub topic: Option<HelpTopic>,
+}
+
+impl Parseable for HelpArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        let topic = match args.as_slice() {
+            [] => None,
+            ["rules"] => Some(HelpTopic::Rules),
+            [topic, ..] => {
+                return Err(CommandError::InvalidArgument {
+                    command: String::from("help"),
+                    argument: topic.to_string(),
+                    reason: String::from("topics are: rules"),
+                });
+            }
+        };
+        Ok(HelpArgs { dev_mode: false, topic })
+    }
+}
+
+pub fn execute(args: HelpArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let message = match args.topic {
+        None => render(game_state, args.dev_mode),
+        Some(HelpTopic::Rules) => orbit_rules_text(),
+    };
+    Ok(CommandEffect::None { message })
+}
+
+/// The orbit truth table from `rules::orbit_rules`, one row per kind of planet, split by
+/// shields only where they make a difference.
+fn orbit_rules_text() -> String {
+    let mark = |allowed: bool| if allowed { "yes" } else { "-" };
+    let row = |planet: String, rules: OrbitRules| format!(
+        "  {:<28}{:<9}{:<10}{:<9}{:<10}{}",
+        planet, mark(rules.station), mark(rules.blockade), mark(rules.bombard), mark(rules.colonize), mark(rules.invade)
+    );
+
+    let mut lines = vec![
+        String::from("=== Orbit Rules ==="),
+        String::from("What a fleet may do at a planet it orbits, by who holds the planet:"),
+        String::new(),
+        format!("  {:<28}{:<9}{:<10}{:<9}{:<10}{}", "Planet", "Station", "Blockade", "Bombard", "Colonize", "Invade"),
+    ];
+    for standing in Standing::ALL {
+        let (up, down) = (rules::orbit_rules(standing, true), rules::orbit_rules(standing, false));
+        if up == down {
+            lines.push(row(standing.label().to_string(), up));
+        } else {
+            lines.push(row(format!("{}, shields up", standing.label()), up));
+            lines.push(row(format!("{}, shields down", standing.label()), down));
+        }
+    }
+    lines.extend([
+        String::new(),
+        String::from("Shields keep landings out, never fleets: a fleet may orbit any planet, and its owner sees it arrive."),
+        String::from("Blockading takes a fleet with no orders to leave, bombarding ships with bombardment power, and"),
+        String::from("colonizing or invading an ark. A planet that just changed hands cannot be taken for a few turns."),
+    ]);
+    lines.join("\n")
+}
+
+/// The command reference for whoever is playing: orders they cannot give yet say why, and
+/// developer commands are left out unless the session accepts them. Before anyone has joined,
+/// every order is listed as is.
+fn render(game_state: &GameState, dev_mode: bool) -> String {
+    let viewer = game_state.checked_current_player().ok()
+        .and_then(|player_id| game_state.players.get(player_id));
+
+    let lines: Vec<String> = help_text().lines()
+        .filter(|line| dev_mode || !DEV_COMMANDS.iter().any(|command| usage_starts_with(line, command)))
+        .map(|line| {
+            let reason = viewer.and_then(|player| REQUIREMENTS.iter()
+                .find(|(command, _)| usage_starts_with(line, command))
+                .and_then(|(_, requirement)| rules::unmet_requirement(*requirement, player, game_state)));
+            match reason {
+                Some(reason) => format!("{}  [unavailable: {}]", line, reason),
+                None => line.to_string(),
+            }
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// Whether a reference line documents `command`, the whole word or words and not a longer one.
+fn usage_starts_with(line: &str, command: &str) -> bool {
+    line.starts_with("  ")
+        && line.trim_start().strip_prefix(command).is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+}
+
+fn help_text() -> String {
+    format!(r#"=== Colony Protocol - Command Reference ===
+
+GAME COMMANDS
+  status game              Show the map, commanders, turn and rules of the game
+  status turn              Show the turn number and who has still to end their turn
+  status planets           List all planets in the system
+  status planet <id>       Show details for a specific planet
+  status fleet <id>        Show a fleet's ships and the destinations within reach
+  status player            Show your player status
+  intel <player>           Show what you have observed of another player
+  log [player] [turns]     List your orders by turn, e.g. 'log 3-5'; anyone's once the game is over
+  battles [n]              List the last n battles you fought in or saw (default 5)
+  battles show <id>        Retell a battle round by round
+  forecast <planet_id> [turns]  Project a planet's resources over the next turns (default 5)
+  schedule                 List everything you have underway by the turn it completes
+  defense <planet_id>      Sum up a planet's shield, garrison, fleets in orbit and nearby threats
+  diff <turn_a> <turn_b>   List what you saw change between the start of two recent turns
+  map                      Display the star system map ([F2] your fleets, [!] hostile ships, [B] bombardment)
+  map list                 List planets and their connections, for narrow terminals
+  structures [category]    Browse the structure catalog by category
+  advisor                  Suggest up to three next steps for your empire
+
+BUILDING
+  build <planet_id> <structure_id>    Queue structure construction
+  build_all <structure_id>            Queue a structure on every planet that can build it
+  build_all ... --only-affordable     Leave out planets that cannot pay for it
+  build_ship <planet_id> <ship_id>    Queue ship construction
+  build_ship ... deliver_to <planet_id>  Carry the finished ship to another of your planets
+  upgrade <planet_id> <structure_id>  Queue structure upgrade
+  upgrade ... --repeat                Keep queueing the next level until max level or unaffordable
+  cancel <planet_id> [target_id]      Cancel pending action on planet
+  build ... --preview                 Show cost and build time without queueing (also build_ship, upgrade, or end with '?')
+
+SHIPS & FLEETS
+  ships                               List all your ships
+  ship retrofit <ship_id> <ship_type> Refit a ship at your shipyard into another type
+  ship move <ship_id> <planet_id>     Move a ship outside any fleet to a connected planet
+  fleets                              List all your fleets
+  fleet create <name> <ship_id>...    Create fleet from ships (same location)
+  fleet add <fleet_id> <ship_id>...   Add ships to fleet
+  fleet remove <fleet_id> <ship_id>...Remove ships from fleet
+  fleet disband <fleet_id>            Disband fleet (ships become standalone)
+  fleet merge <into> <from>           Move all ships of one fleet into another
+  fleet split <fleet_id> <name> <ship_id>...  Form a new fleet from listed fleet ships
+  fleet template save <name> <fleet_id>  Save a fleet's ship composition as a template
+  fleet template list                 List your fleet templates
+  fleet build <template> <planet_id>  Queue the ships of a template on a planet
+  fleet move <fleet_id>[,...] <planet_id>  Move fleets to a connected planet, each on its own
+  fleet bombard <fleet_id> [structure_id]  Bombard the planet the fleet orbits; overflow hits the structure first
+  fleet cancel-bombard <fleet_id>     Stop an ongoing bombardment
+  fleet colonize <fleet_id>           Colonize the planet the fleet orbits, using up an ark
+  fleet hold <fleet_id>               Park a fleet: no idle warnings, wakes when hostiles approach
+  fleet wake <fleet_id>               Take a fleet off hold
+  fleet load <fleet_id> <minerals> <gas> <energy>  Load cargo at your planet the fleet orbits
+  fleet unload <fleet_id>             Unload all cargo at your planet the fleet orbits
+  planet rally <planet_id> <fleet_id> Send ships built on a planet to a fleet there
+  planet rally <planet_id> new <name> Send them to a fleet formed on first completion
+  planet rally <planet_id> none       Leave newly built ships standalone
+  planet garrison <planet_id> <ship_id>...    Station ships planet-side against invasion
+  planet ungarrison <planet_id> <ship_id>...  Release garrisoned ships so they can move
+  planet overcharge <planet_id>       Spend energy to boost shields for this turn
+  planet link <planet_a> <planet_b>   Join two planets with jump gates by a 1-turn route
+  planet power <planet_id> <structure_id> <on|off>  Power a structure down to save energy, or back on
+  planet allocate <planet_id> <minerals%> <gas%> <energy%>|default  Shift output between resources at 2:1
+
+TURN
+  {end_turn:<34}  End your turn and pass to next player
+
+SYSTEM
+  save <name>                         Save the game to a named slot
+  load <name>                         Load a named save, replacing the current game
+  qs                                  Quicksave to the default slot
+  ql                                  Load the quicksave
+  saves                               List saves, most recent first
+  saves delete <name>                 Delete a save
+  export report <path>                Write the game report to a file (.json for JSON)
+  alias <name> = <command>            Define a shortcut; words typed after it are appended
+  alias list                          List your aliases
+  alias remove <name>                 Remove an alias
+  note add <text>                     Jot down a plan only you can read; quotes are optional
+  note list                           List your notes, numbered
+  note done <number>                  Remove a note you have dealt with
+  set style <unicode|ascii>           Draw the map and output with Unicode or plain ASCII
+  options                             List settings, their current values and scopes
+  options set <key> <value>           Change a session or game setting, e.g. 'options set autosave 5'
+  inspect <planet|player|fleet> <id>  Dump raw state as JSON (games started with --dev)
+  inspect invariants                  Check the game state for inconsistencies (--dev)
+  status perf                         Time each phase of round processing, last round and all (--dev)
+  dev give <planet> <m> <g> <e>       Add minerals, gas and energy to a planet, up to storage (--dev)
+  dev complete <planet>               Complete every action queued on a planet now (--dev)
+  dev reveal                          Show or hide every planet live, fog of war aside (--dev)
+  dev spawn-ship <planet> <ship_type> Give the planet's owner a new ship there (--dev)
+  dev set-turn <n>                    Move the turn counter forward to n (--dev)
+  help                                Show this help message
+  help rules                          Show what fleets may do at the planets they orbit
+  rematch                             Once the game is over, start a new one with the same setup
+  {exit:<34}  End the game session
+
+TIPS
+  - Planet IDs are shown in parentheses, e.g. "Kepler VII (c418)"
+  - Ship IDs follow pattern: interceptor_1, ravager_2, etc.
+  - Fleet IDs follow pattern: fleet_1, fleet_2, etc.
+  - In classic rules a planet runs one structure build and one upgrade at a time, plus ship builds per shipyard level"#,
+        end_turn = END_TURN_ALIASES.join(", "),
+        exit = EXIT_ALIASES.join(", "),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::parser::{self, accepted_command_words};
+    use crate::engine::fleet::Fleet;
+    use crate::test_support::{self, fixture_game};
+
+    /// Command words documented in help: the first word(s) of each indented reference line.
+    fn documented_words() -> Vec<String> {
+        let text = help_text();
+        let reference = text.split("TIPS").next().unwrap();
+        reference.lines()
+            .filter(|line| line.starts_with("  "))
+            .flat_map(|line| {
+                let usage = line.trim_start().split("  ").next().unwrap_or("");
+                if usage.contains(", ") {
+                    usage.split(", ").map(str::to_string).collect::<Vec<_>>()
+                } else {
+                    usage.split_whitespace().next().map(str::to_string).into_iter().collect()
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_every_documented_command_parses() {
+        for word in documented_words() {
+            let result = parser::parse(&word);
+            assert!(
+                !matches!(result, Err(CommandError::UnknownCommand(_))),
+                "help documents '{}' but the parser rejects it", word
+            );
+        }
+    }
+
+    #[test]
+    fn test_help_lists_every_accepted_command() {
+        let documented = documented_words();
+        for word in accepted_command_words() {
+            assert!(documented.iter().any(|d| d == word), "parser accepts '{}' but help omits it", word);
+        }
+    }
+
+    fn help(state: &GameState, dev_mode: bool) -> String {
+        match execute(HelpArgs { dev_mode, topic: None }, state).unwrap() {
+            CommandEffect::None { message } => message,
+            _ => panic!("Help only displays"),
+        }
+    }
+
+    fn line<'a>(help: &'a str, command: &str) -> &'a str {
+        help.lines()
+            .find(|line| usage_starts_with(line, command))
+            .unwrap_or_else(|| panic!("help has no line for '{}':\n{}", command, help))
+    }
+
+    #[test]
+    fn test_fresh_player_sees_why_orders_do_not_apply_yet() {
+        let state = fixture_game(1);
+        let help = help(&state, false);
+
+        assert!(line(&help, "build_ship").ends_with("[unavailable: requires an Orbital Shipyard (orbital_shipyard)]"), "{help}");
+        assert!(line(&help, "fleet move").ends_with("[unavailable: you have no fleets yet]"), "{help}");
+        assert!(line(&help, "planet link").ends_with("[unavailable: requires a Jump Gate (jump_gate)]"), "{help}");
+        assert!(!line(&help, "build").contains("unavailable"), "{help}");
+        assert!(!line(&help, "fleets").contains("unavailable"), "{help}");
+    }
+
+    #[test]
+    fn test_developed_player_sees_orders_without_annotations() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.complete_build_structure(String::from("orbital_shipyard"), &state.structure_config).unwrap();
+        let alice = state.players.get_mut("alice").unwrap();
+        let mut fleet = Fleet::new(String::from("fleet_1"), String::from("Settlers"), home.clone());
+        fleet.add_ship(alice.add_ship(String::from("ark"), home.clone()));
+        alice.fleets.insert(fleet.id.clone(), fleet);
+
+        let help = help(&state, false);
+
+        for command in ["build_ship", "ship move", "fleet move", "fleet colonize", "planet rally"] {
+            assert!(!line(&help, command).contains("unavailable"), "{help}");
+        }
+        assert!(line(&help, "planet overcharge").contains("unavailable: requires a Defense Shield (defense_shield)"), "{help}");
+    }
+
+    #[test]
+    fn test_developer_commands_need_dev_mode() {
+        let state = fixture_game(1);
+        assert!(!help(&state, false).contains("inspect"));
+        assert!(!help(&state, false).contains("status perf"));
+        assert!(!help(&state, false).contains("dev "));
+        assert!(line(&help(&state, true), "inspect").contains("--dev"));
+        assert!(line(&help(&state, true), "dev set-turn").contains("--dev"));
+        assert!(line(&help(&state, true), "status perf").contains("--dev"));
+    }
+
+    #[test]
+    fn test_all_aliases_parse() {
+        for alias in END_TURN_ALIASES {
+            assert!(matches!(parser::parse(alias), Ok(crate::engine::commands::command::Command::EndTurn)));
+        }
+        for alias in EXIT_ALIASES {
+            assert!(matches!(parser::parse(alias), Ok(crate::engine::commands::command::Command::Exit)));
+        }
+    }
+
+    #[test]
+    fn test_help_rules_shows_the_orbit_table() {
+        let state = fixture_game(1);
+        let rules = match parser::parse("help rules").unwrap().execute(&state).unwrap() {
+            CommandEffect::None { message } => message,
+            _ => panic!("Help only displays"),
+        };
+
+        let row = |planet: &str| rules.lines()
+            .find(|line| line.trim_start().starts_with(planet))
+            .unwrap_or_else(|| panic!("no row for {}:\n{}", planet, rules))
+            .split_whitespace()
+            .rev()
+            .take(5)
+            .collect::<Vec<_>>();
+        assert_eq!(row("yours"), ["-", "-", "-", "-", "yes"]);
+        assert_eq!(row("neutral, shields down"), ["-", "yes", "-", "-", "yes"]);
+        assert_eq!(row("an opponent's, shields up"), ["-", "-", "yes", "yes", "yes"]);
+        assert_eq!(row("an opponent's, shields down"), ["yes", "-", "yes", "yes", "yes"]);
+        assert!(!rules.contains("an ally's, shields"), "{rules}");
+        assert!(parser::parse("help me").is_err());
+    }
+}
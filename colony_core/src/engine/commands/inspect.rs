@@ -0,0 +1,141 @@
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::parser::Parseable;
+use crate::engine::fleet::FleetDebugView;
+use crate::engine::game_state::GameState;
+use crate::engine::ship::FleetId;
+
+/// What `inspect` dumps.
+pub enum InspectTarget {
+    Planet(String),
+    Player(String),
+    Fleet(FleetId),
+    Invariants,
+}
+
+pub struct InspectArgs {
+    pub target: InspectTarget,
+}
+
+impl Parseable for InspectArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        let target = match args.as_slice() {
+            ["planet", id] => InspectTarget::Planet(id.to_string()),
+            ["player", id] => InspectTarget::Player(id.to_string()),
+            ["fleet", id] => InspectTarget::Fleet(id.to_string()),
+            ["invariants"] => InspectTarget::Invariants,
+            [kind, ..] if !matches!(*kind, "planet" | "player" | "fleet" | "invariants") => {
+                return Err(CommandError::InvalidArgument {
+                    command: String::from("inspect"),
+                    argument: kind.to_string(),
+                    reason: String::from("expected planet, player, fleet or invariants"),
+                });
+            }
+            _ => {
+                return Err(CommandError::MissingArguments {
+                    command: String::from("inspect"),
+                    expected: String::from("inspect <planet|player|fleet> <id> | inspect invariants"),
+                });
+            }
+        };
+        Ok(InspectArgs { target })
+    }
+}
+
+/// Dumps the unformatted state behind a planet, player or fleet as pretty JSON, or lists the
+/// consistency violations in the game state. A developer tool: it shows everything, fog of war
+/// included.
+pub fn execute(args: InspectArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let json = match args.target {
+        InspectTarget::Planet(planet_id) => {
+            let planet = game_state.map.planets.get(&planet_id)
+                .ok_or(CommandError::UnknownPlanet(planet_id))?;
+            serde_json::to_string_pretty(&planet.debug_view())
+        }
+        InspectTarget::Player(player_id) => {
+            let player = game_state.players.get(&player_id)
+                .ok_or(CommandError::UnknownPlayer(player_id))?;
+            serde_json::to_string_pretty(&player.debug_view())
+        }
+        InspectTarget::Fleet(fleet_id) => {
+            // Fleet ids are per player, so the current player's fleet wins a tie
+            let current = game_state.players.get(game_state.current_player())
+                .filter(|player| player.fleets.contains_key(&fleet_id))
+                .map(|player| player.id.clone());
+            let owner_id = current
+                .or_else(|| game_state.find_fleet_any(&fleet_id).map(|(owner, _)| owner))
+                .ok_or_else(|| CommandError::InvalidArgument {
+                    command: String::from("inspect fleet"),
+                    argument: fleet_id.clone(),
+                    reason: String::from("fleet not found"),
+                })?;
+            let owner = &game_state.players[&owner_id];
+            let fleet = &owner.fleets[&fleet_id];
+            serde_json::to_string_pretty(&FleetDebugView {
+                owner: &owner.id,
+                fleet,
+                ship_records: fleet.ships.iter().map(|ship_id| owner.ships.get(ship_id)).collect(),
+            })
+        }
+        InspectTarget::Invariants => {
+            let violations = game_state.check_invariants();
+            let mut msg = format!("=== Invariants: {} violation(s) ===\n", violations.len());
+            for violation in &violations {
+                msg.push_str(&format!("  {}\n", violation));
+            }
+            return Ok(CommandEffect::None { message: msg });
+        }
+    };
+
+    Ok(CommandEffect::None { message: json.expect("Debug views always serialize") })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::parser;
+    use crate::test_support::{self, fixture_game};
+
+    fn inspect(state: &GameState, input: &str) -> Result<String, CommandError> {
+        match parser::parse(input)?.execute(state)? {
+            CommandEffect::None { message } => Ok(message),
+            _ => panic!("Inspect must only produce a message"),
+        }
+    }
+
+    #[test]
+    fn test_planet_dump_includes_hidden_fields() {
+        let state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+
+        let dump: serde_json::Value = serde_json::from_str(&inspect(&state, &format!("inspect planet {home}")).unwrap()).unwrap();
+
+        let planet = &state.map.planets[&home];
+        assert_eq!(dump["id"], home.as_str());
+        assert_eq!(dump["owner"], "alice");
+        assert_eq!(dump["shield_regen_timer"], 0);
+        assert_eq!(dump["production_rate"]["minerals"], planet.get_production_rate().minerals);
+        assert_eq!(dump["structures"]["planetary_capital"]["level"], 1);
+        assert_eq!(dump["structures"]["planetary_capital"]["power"], "On");
+    }
+
+    #[test]
+    fn test_player_and_fleet_dumps() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "bob");
+        let bob = state.players.get_mut("bob").unwrap();
+        let ship_id = bob.add_ship(String::from("interceptor"), home.clone());
+        let mut fleet = crate::engine::fleet::Fleet::new(String::from("fleet_1"), String::from("Guard"), home);
+        fleet.add_ship(ship_id.clone());
+        bob.ships.get_mut(&ship_id).unwrap().fleet_id = Some(String::from("fleet_1"));
+        bob.fleets.insert(String::from("fleet_1"), fleet);
+
+        let player: serde_json::Value = serde_json::from_str(&inspect(&state, "inspect player bob").unwrap()).unwrap();
+        let fleet: serde_json::Value = serde_json::from_str(&inspect(&state, "inspect fleet fleet_1").unwrap()).unwrap();
+
+        assert_eq!(player["ship_id_counters"]["interceptor"], 1);
+        assert_eq!(fleet["owner"], "bob");
+        assert_eq!(fleet["ship_records"][0]["id"], ship_id.as_str());
+        assert!(inspect(&state, "inspect invariants").unwrap().starts_with("=== Invariants: 0 violation(s) ==="));
+        assert!(matches!(inspect(&state, "inspect planet nowhere"), Err(CommandError::UnknownPlanet(_))));
+    }
+}
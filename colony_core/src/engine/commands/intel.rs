@@ -0,0 +1,141 @@
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::parser::Parseable;
+use crate::engine::game_state::GameState;
+use crate::engine::utils;
+
+pub struct IntelArgs {
+    pub player_name: String,
+}
+
+impl Parseable for IntelArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::MissingArguments {
+                command: String::from("intel"),
+                expected: String::from("intel <player>"),
+            });
+        }
+        Ok(IntelArgs {
+            player_name: args.join(" "),
+        })
+    }
+}
+
+/// Shows what the current player has observed of another player's empire.
+/// Planets are only listed if explored, fleets only if currently within sensor range.
+pub fn execute(args: IntelArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let current_player_id = game_state.current_player();
+    let current_player = CommandError::acting_player(game_state)?;
+
+    let target_id = utils::name_to_id(&args.player_name);
+    let target = game_state.players.get(&target_id)
+        .ok_or_else(|| CommandError::UnknownPlayer(args.player_name.clone()))?;
+
+    if &target.id == current_player_id {
+        return Err(CommandError::InvalidArgument {
+            command: String::from("intel"),
+            argument: args.player_name.clone(),
+            reason: String::from("use 'status player' to view your own empire"),
+        });
+    }
+
+    let visible = game_state.visible_planets(current_player_id);
+
+    let mut known_planets: Vec<_> = target.planets.iter()
+        .filter(|planet_id| current_player.explored.contains(*planet_id))
+        .filter_map(|planet_id| game_state.map.planets.get(planet_id))
+        .collect();
+    known_planets.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let observed_fleets: Vec<_> = target.fleets.values()
+        .filter(|fleet| visible.contains(&fleet.location))
+        .collect();
+
+    let mut msg = format!("=== Intel: {} ===\n", target.name);
+
+    msg.push_str(&format!("\nKNOWN PLANETS ({})\n", known_planets.len()));
+    if known_planets.is_empty() {
+        msg.push_str("  (none observed)\n");
+    }
+    for planet in known_planets {
+        msg.push_str(&format!("  - {} ({})\n", planet.name, planet.id));
+    }
+
+    msg.push_str(&format!("\nFLEETS IN SENSOR RANGE ({})\n", observed_fleets.len()));
+    if observed_fleets.is_empty() {
+        msg.push_str("  (none observed)\n");
+    }
+    for fleet in observed_fleets {
+        let planet_name = game_state.map.planets.get(&fleet.location)
+            .map(|p| p.name.as_str())
+            .unwrap_or("Unknown");
+        msg.push_str(&format!(
+            "  - {} ({}) at {} ({}): {} ship(s)\n",
+            fleet.name, fleet.id, planet_name, fleet.location, fleet.ship_count()
+        ));
+    }
+
+    Ok(CommandEffect::None { message: msg })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::fleet::Fleet;
+    use crate::test_support::{self, fixture_game};
+
+    fn intel_message(game_state: &GameState, player: &str) -> String {
+        let args = IntelArgs { player_name: player.to_string() };
+        match execute(args, game_state).unwrap() {
+            CommandEffect::None { message } => message,
+            _ => panic!("Expected informational effect"),
+        }
+    }
+
+    #[test]
+    fn test_unexplored_planets_and_distant_fleets_are_hidden() {
+        let mut state = fixture_game(1);
+        let bob_home = test_support::home_planet(&state, "bob");
+
+        let bob = state.players.get_mut("bob").unwrap();
+        bob.add_ship("interceptor".to_string(), bob_home.clone());
+        let mut fleet = Fleet::new("fleet_1".to_string(), "Raiders".to_string(), bob_home.clone());
+        fleet.add_ship("interceptor_1".to_string());
+        bob.fleets.insert("fleet_1".to_string(), fleet);
+
+        let message = intel_message(&state, "Bob");
+
+        assert!(!message.contains(&bob_home));
+        assert!(!message.contains("Raiders"));
+    }
+
+    #[test]
+    fn test_explored_planet_and_fleet_in_sensor_range_are_shown() {
+        let mut state = fixture_game(1);
+        let bob_home = test_support::home_planet(&state, "bob");
+
+        let bob = state.players.get_mut("bob").unwrap();
+        bob.add_ship("interceptor".to_string(), bob_home.clone());
+        let mut fleet = Fleet::new("fleet_1".to_string(), "Raiders".to_string(), bob_home.clone());
+        fleet.add_ship("interceptor_1".to_string());
+        bob.fleets.insert("fleet_1".to_string(), fleet);
+
+        // Alice has a ship stationed at Bob's home planet
+        let alice = state.players.get_mut("alice").unwrap();
+        alice.add_ship("interceptor".to_string(), bob_home.clone());
+        alice.explored.insert(bob_home.clone());
+
+        let message = intel_message(&state, "bob");
+
+        assert!(message.contains(&bob_home));
+        assert!(message.contains("Raiders"));
+    }
+
+    #[test]
+    fn test_unknown_player_is_rejected() {
+        let state = fixture_game(1);
+        let args = IntelArgs { player_name: "Mallory".to_string() };
+
+        assert!(matches!(execute(args, &state), Err(CommandError::UnknownPlayer(_))));
+    }
+}
@@ -0,0 +1,163 @@
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::parser::Parseable;
+use crate::engine::game_state::GameState;
+use crate::engine::utils;
+
+/// Turns to list, both ends inclusive. No end means up to the current turn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TurnRange {
+    pub first: u32,
+    pub last: Option<u32>,
+}
+
+impl TurnRange {
+    pub const ALL: TurnRange = TurnRange { first: 1, last: None };
+
+    /// Parses `N`, `N-M` or `N-`.
+    fn parse(text: &str) -> Result<Self, CommandError> {
+        let invalid = |reason: &str| CommandError::InvalidArgument {
+            command: String::from("log"),
+            argument: text.to_string(),
+            reason: reason.to_string(),
+        };
+        let number = |part: &str| part.parse::<u32>().ok()
+            .filter(|turn| *turn > 0)
+            .ok_or_else(|| invalid("turns are written N, N-M or N- with turn numbers from 1"));
+
+        let range = match text.split_once('-') {
+            None => {
+                let turn = number(text)?;
+                TurnRange { first: turn, last: Some(turn) }
+            }
+            Some((first, "")) => TurnRange { first: number(first)?, last: None },
+            Some((first, last)) => TurnRange { first: number(first)?, last: Some(number(last)?) },
+        };
+        if range.last.is_some_and(|last| last < range.first) {
+            return Err(invalid("the range ends before it starts"));
+        }
+        Ok(range)
+    }
+
+    fn describe(&self) -> String {
+        match self.last {
+            _ if *self == TurnRange::ALL => String::from("all turns"),
+            Some(last) if last == self.first => format!("turn {}", last),
+            Some(last) => format!("turns {}-{}", self.first, last),
+            None => format!("turns {} onward", self.first),
+        }
+    }
+}
+
+pub struct LogArgs {
+    /// Whose log to show; the current player's when absent
+    pub player_name: Option<String>,
+    pub turns: TurnRange,
+}
+
+impl Parseable for LogArgs {
+    /// A trailing argument starting with a digit is the turn range, anything before it the player.
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        let (player_args, turns) = match args.split_last() {
+            Some((last, rest)) if last.starts_with(|c: char| c.is_ascii_digit()) => (rest, TurnRange::parse(last)?),
+            _ => (&args[..], TurnRange::ALL),
+        };
+        let player_name = (!player_args.is_empty()).then(|| player_args.join(" "));
+
+        Ok(LogArgs { player_name, turns })
+    }
+}
+
+/// Lists a player's orders with their outcome. Players always see their own; other players'
+/// orders stay hidden until the game is over, except in replays.
+pub fn execute(args: LogArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let viewer = game_state.current_player();
+    let target = match &args.player_name {
+        Some(name) => game_state.players.get(&utils::name_to_id(name))
+            .ok_or_else(|| CommandError::UnknownPlayer(name.clone()))?,
+        None => CommandError::acting_player(game_state)?,
+    };
+
+    if &target.id != viewer && !game_state.is_over() && !game_state.omniscient {
+        return Err(CommandError::InvalidArgument {
+            command: String::from("log"),
+            argument: target.name.clone(),
+            reason: String::from("other players' orders are revealed once the game is over"),
+        });
+    }
+
+    let last = args.turns.last.unwrap_or(game_state.turn);
+    let entries = game_state.player_actions(&target.id, args.turns.first, last);
+
+    let mut msg = format!("=== Action Log: {} ({}) ===\n", target.name, args.turns.describe());
+    let oldest_kept = game_state.action_log.first().map_or(game_state.turn, |entry| entry.turn);
+    if game_state.compaction.action_log_entries > 0 && args.turns.first < oldest_kept {
+        msg.push_str(&format!("  (orders before turn {} were dropped to keep the save small)\n", oldest_kept));
+    }
+    if entries.is_empty() {
+        msg.push_str("  (no orders recorded)\n");
+    }
+    for entry in entries {
+        msg.push_str(&format!("  Turn {:>3}  {} -> {}\n", entry.turn, entry.command, entry.outcome));
+    }
+
+    Ok(CommandEffect::None { message: msg })
+}
+
+/// Shown under the final report once the game is over.
+pub const GAME_OVER_HINT: &str = "\nEvery player's orders are now open: log <player> [turns]\n\
+    Keep this report with: export report <path>\n\
+    Views still work. Type 'rematch' for a new game with the same setup, or 'exit' to leave";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::parser;
+    use crate::engine::game_state::{ActionOutcome, GamePhase};
+    use crate::test_support::fixture_game;
+
+    fn log_message(state: &GameState, input: &str) -> Result<String, CommandError> {
+        match parser::parse(input)?.execute(state)? {
+            CommandEffect::None { message } => Ok(message),
+            _ => panic!("Log must only produce a message"),
+        }
+    }
+
+    #[test]
+    fn test_turn_range_forms() {
+        let parse = |input: &str| LogArgs::parse(input.split_whitespace().collect());
+
+        let args = parse("").unwrap();
+        assert_eq!((args.player_name, args.turns), (None, TurnRange::ALL));
+        let args = parse("Bob 3-").unwrap();
+        assert_eq!((args.player_name.as_deref(), args.turns), (Some("Bob"), TurnRange { first: 3, last: None }));
+        assert_eq!(parse("2").unwrap().turns, TurnRange { first: 2, last: Some(2) });
+        assert_eq!(parse("2-4").unwrap().turns, TurnRange { first: 2, last: Some(4) });
+
+        for invalid in ["4-2", "0", "2-x", "1-2-3"] {
+            assert!(matches!(parse(invalid), Err(CommandError::InvalidArgument { .. })), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_other_players_logs_open_after_game_end() {
+        let mut state = fixture_game(1);
+        for (player, turn, command) in [("alice", 1, "end_turn"), ("bob", 1, "end_turn"), ("alice", 2, "build x y")] {
+            let outcome = if command == "end_turn" {
+                ActionOutcome::Applied
+            } else {
+                ActionOutcome::Rejected { reason: String::from("Planet x does not exist") }
+            };
+            state.record_action(&player.to_string(), turn, command, outcome, &[]);
+        }
+
+        let own = log_message(&state, "log 2").unwrap();
+        assert!(own.contains("Action Log: Alice (turn 2)"), "{own}");
+        assert!(own.contains("build x y -> rejected: Planet x does not exist"), "{own}");
+        assert!(!own.contains("end_turn"), "{own}");
+
+        assert!(matches!(log_message(&state, "log bob"), Err(CommandError::InvalidArgument { reason, .. }) if reason.contains("game is over")));
+        state.game_phase = GamePhase::Finished;
+        let bob = log_message(&state, "log bob").unwrap();
+        assert!(bob.contains("Turn   1  end_turn -> ok"), "{bob}");
+    }
+}
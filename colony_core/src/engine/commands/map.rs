@@ -0,0 +1,89 @@
+
+use crate::engine::game_state::GameState;
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::parser::Parseable;
+use crate::engine::map::{Map, RenderStyle};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MapView {
+    /// ASCII art of the star system
+    Grid,
+    /// One line per planet with its connections
+    List,
+}
+
+#[derive(Debug)]
+pub struct MapArgs {
+    /// None picks a view that fits the terminal
+    pub view: Option<MapView>,
+    /// Columns of the player's terminal, filled in by the frontend when known
+    pub terminal_width: Option<usize>,
+    /// Characters the frontend can show, filled in by the frontend
+    pub style: RenderStyle,
+}
+
+impl Parseable for MapArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        let view = match args.as_slice() {
+            [] => None,
+            ["grid"] => Some(MapView::Grid),
+            ["list"] => Some(MapView::List),
+            [argument, ..] => {
+                return Err(CommandError::InvalidArgument {
+                    command: String::from("map"),
+                    argument: argument.to_string(),
+                    reason: String::from("valid views are: grid, list"),
+                });
+            }
+        };
+
+        Ok(MapArgs { view, terminal_width: None, style: RenderStyle::default() })
+    }
+}
+
+pub fn execute(args: MapArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let view = args.view.unwrap_or(match args.terminal_width {
+        Some(width) if width < Map::RENDER_WIDTH => MapView::List,
+        _ => MapView::Grid,
+    });
+
+    let presence = game_state.planet_presence(game_state.current_player());
+    let map_render = match view {
+        MapView::Grid => game_state.map.render_full(&game_state.players, &presence, Map::DEFAULT_LABEL_ID_CHARS, args.style),
+        MapView::List => game_state.map.render_list(&game_state.players, &presence),
+    };
+    Ok(CommandEffect::None { message: map_render })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::fixture_game;
+
+    fn render(state: &GameState, view: Option<MapView>, terminal_width: Option<usize>) -> String {
+        match execute(MapArgs { view, terminal_width, style: RenderStyle::Unicode }, state).unwrap() {
+            CommandEffect::None { message } => message,
+            _ => panic!("Map command must only produce a message"),
+        }
+    }
+
+    #[test]
+    fn test_parse_views() {
+        assert_eq!(MapArgs::parse(vec![]).unwrap().view, None);
+        assert_eq!(MapArgs::parse(vec!["list"]).unwrap().view, Some(MapView::List));
+        assert_eq!(MapArgs::parse(vec!["grid"]).unwrap().view, Some(MapView::Grid));
+        assert!(matches!(MapArgs::parse(vec!["tiny"]), Err(CommandError::InvalidArgument { .. })));
+    }
+
+    #[test]
+    fn test_narrow_terminal_defaults_to_list() {
+        let state = fixture_game(1);
+        let grid = render(&state, Some(MapView::Grid), None);
+        let list = render(&state, Some(MapView::List), None);
+
+        assert_eq!(render(&state, None, Some(80)), list);
+        assert_eq!(render(&state, None, Some(Map::RENDER_WIDTH)), grid);
+        assert_eq!(render(&state, None, None), grid);
+        assert_eq!(render(&state, Some(MapView::Grid), Some(40)), grid);
+    }
+}
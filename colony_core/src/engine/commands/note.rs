@@ -0,0 +1,207 @@
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::parser::Parseable;
+use crate::engine::game_state::GameState;
+
+/// Longest single note, in characters
+pub const MAX_NOTE_CHARS: usize = 200;
+/// Most characters one player may keep across all notes, so notes cannot bloat saves
+pub const MAX_TOTAL_NOTE_CHARS: usize = 2000;
+
+pub enum NoteArgs {
+    Add { text: String },
+    List,
+    /// Numbered from 1, as the listing shows them
+    Done { number: usize },
+}
+
+impl Parseable for NoteArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        let missing = || CommandError::MissingArguments {
+            command: String::from("note"),
+            expected: String::from("note add <text> | note list | note done <number>"),
+        };
+
+        match args.as_slice() {
+            ["add", text @ ..] => {
+                let text = unquote(&text.join(" ")).trim().to_string();
+                if text.is_empty() {
+                    return Err(missing());
+                }
+                Ok(NoteArgs::Add { text })
+            }
+            ["list"] => Ok(NoteArgs::List),
+            ["done", number] => number.parse::<usize>().ok()
+                .filter(|number| *number >= 1)
+                .map(|number| NoteArgs::Done { number })
+                .ok_or_else(|| CommandError::InvalidArgument {
+                    command: String::from("note done"),
+                    argument: number.to_string(),
+                    reason: String::from("notes are numbered from 1. See 'note list'"),
+                }),
+            _ => Err(missing()),
+        }
+    }
+}
+
+/// Strips one pair of matching quotes around the whole text, so `note add "hold Vega"` and
+/// `note add hold Vega` store the same note.
+fn unquote(text: &str) -> &str {
+    ['"', '\''].iter()
+        .find_map(|quote| text.strip_prefix(*quote)?.strip_suffix(*quote))
+        .unwrap_or(text)
+}
+
+pub fn execute(args: NoteArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+
+    match args {
+        NoteArgs::Add { text } => {
+            let length = text.chars().count();
+            let invalid = |reason: String| CommandError::InvalidArgument {
+                command: String::from("note add"),
+                argument: text.clone(),
+                reason,
+            };
+
+            if length > MAX_NOTE_CHARS {
+                return Err(invalid(format!("notes are at most {} characters, this one has {}", MAX_NOTE_CHARS, length)));
+            }
+            let used: usize = player.notes.iter().map(|note| note.chars().count()).sum();
+            if used + length > MAX_TOTAL_NOTE_CHARS {
+                return Err(invalid(format!(
+                    "your notes would exceed {} characters; finish some with 'note done <number>'",
+                    MAX_TOTAL_NOTE_CHARS,
+                )));
+            }
+            Ok(CommandEffect::AddNote { text })
+        }
+        NoteArgs::List => Ok(CommandEffect::None { message: format_notes(&player.notes) }),
+        NoteArgs::Done { number } => {
+            if number > player.notes.len() {
+                return Err(CommandError::InvalidArgument {
+                    command: String::from("note done"),
+                    argument: number.to_string(),
+                    reason: format!("you have {} notes. See 'note list'", player.notes.len()),
+                });
+            }
+            Ok(CommandEffect::CompleteNote { number })
+        }
+    }
+}
+
+fn format_notes(notes: &[String]) -> String {
+    if notes.is_empty() {
+        return String::from("No notes. Add one with: note add <text>");
+    }
+
+    let mut msg = String::from("=== Notes ===\n");
+    for (index, note) in notes.iter().enumerate() {
+        msg.push_str(&format!("  {}. {}\n", index + 1, note));
+    }
+    msg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::command::Command;
+    use crate::engine::commands::parser;
+    use crate::engine::game_event::GameEvent;
+    use crate::engine::save;
+    use crate::test_support::fixture_game;
+
+    fn note(game_state: &mut GameState, line: &str) -> Result<Vec<GameEvent>, CommandError> {
+        let effect = parser::parse(line)?.execute(game_state)?;
+        let player_id = game_state.current_player().clone();
+        Ok(game_state.apply_effect(effect, &player_id).unwrap())
+    }
+
+    fn list(game_state: &GameState) -> String {
+        match parser::parse("note list").unwrap().execute(game_state).unwrap() {
+            CommandEffect::None { message } => message,
+            _ => panic!("Listing notes only displays"),
+        }
+    }
+
+    #[test]
+    fn test_parse_joins_words_and_strips_quotes() {
+        let text = |line: &str| match parser::parse(line) {
+            Ok(Command::Note(NoteArgs::Add { text })) => text,
+            _ => panic!("'{}' must parse as a note", line),
+        };
+        assert_eq!(text("note add hold   Vega until turn 12"), "hold Vega until turn 12");
+        assert_eq!(text("note add \"hold Vega\""), "hold Vega");
+        assert_eq!(text("note add 'hold Vega'"), "hold Vega");
+        assert_eq!(text("note add \"hold Vega"), "\"hold Vega");
+
+        assert!(matches!(parser::parse("note add"), Err(CommandError::MissingArguments { .. })));
+        assert!(matches!(parser::parse("note add \"\""), Err(CommandError::MissingArguments { .. })));
+        assert!(matches!(parser::parse("note done 0"), Err(CommandError::InvalidArgument { .. })));
+        assert!(matches!(parser::parse("note done 2"), Ok(Command::Note(NoteArgs::Done { number: 2 }))));
+    }
+
+    #[test]
+    fn test_notes_are_added_listed_and_done() {
+        let mut state = fixture_game(1);
+        assert!(list(&state).starts_with("No notes"));
+
+        note(&mut state, "note add \"scout Vega\"").unwrap();
+        note(&mut state, "note add build a shipyard").unwrap();
+        assert_eq!(list(&state), "=== Notes ===\n  1. scout Vega\n  2. build a shipyard\n");
+
+        let events = note(&mut state, "note done 1").unwrap();
+        assert!(matches!(events.as_slice(), [GameEvent::NoteDone { number: 1, remaining: 1 }]));
+        assert_eq!(state.players["alice"].notes, vec![String::from("build a shipyard")]);
+        assert!(matches!(note(&mut state, "note done 2"), Err(CommandError::InvalidArgument { .. })));
+    }
+
+    #[test]
+    fn test_note_sizes_are_limited() {
+        let mut state = fixture_game(1);
+        let long = "x".repeat(MAX_NOTE_CHARS + 1);
+        assert!(matches!(note(&mut state, &format!("note add {long}")), Err(CommandError::InvalidArgument { .. })));
+
+        let full = "x".repeat(MAX_NOTE_CHARS);
+        for _ in 0..MAX_TOTAL_NOTE_CHARS / MAX_NOTE_CHARS {
+            note(&mut state, &format!("note add {full}")).unwrap();
+        }
+        let Err(CommandError::InvalidArgument { reason, .. }) = note(&mut state, "note add one more") else {
+            panic!("Notes past the total limit must be refused")
+        };
+        assert!(reason.contains("note done"), "{reason}");
+    }
+
+    #[test]
+    fn test_notes_are_private_and_saved() {
+        let mut state = fixture_game(1);
+        note(&mut state, "note add bob looks weak at Vega").unwrap();
+        let events = note(&mut state, "end").unwrap();
+
+        // Bob neither lists nor is told about alice's note
+        assert!(list(&state).starts_with("No notes"));
+        let Some(GameEvent::PlayerTurnStarted { notes, .. }) = events.last() else {
+            panic!("Ending the turn must start bob's")
+        };
+        assert_eq!(*notes, 0);
+
+        let saved = save::encode_save(&state, "notes", 0).unwrap();
+        let loaded = save::decode_save(&saved).unwrap();
+        assert_eq!(loaded.players["alice"].notes, vec![String::from("bob looks weak at Vega")]);
+    }
+
+    #[test]
+    fn test_turn_banner_counts_notes() {
+        let mut state = fixture_game(1);
+        note(&mut state, "note add scout Vega").unwrap();
+        note(&mut state, "note add build a shipyard").unwrap();
+        note(&mut state, "end").unwrap();
+        let events = note(&mut state, "end").unwrap();
+
+        let Some(banner @ GameEvent::PlayerTurnStarted { notes: 2, .. }) = events.last() else {
+            panic!("Alice's banner must count her two notes")
+        };
+        let banner = banner.to_string();
+        assert!(banner.contains("You have 2 notes; 'note list' shows them"), "{banner}");
+        assert!(!banner.contains("scout Vega"), "{banner}");
+    }
+}
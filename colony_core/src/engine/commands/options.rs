@@ -0,0 +1,94 @@
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::parser::Parseable;
+use crate::engine::options::{self, OptionChange};
+
+#[derive(Debug, PartialEq)]
+pub enum OptionsArgs {
+    List,
+    Set { key: String, value: String },
+}
+
+impl Parseable for OptionsArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        match args.as_slice() {
+            [] => Ok(OptionsArgs::List),
+            ["set", key, value @ ..] if !value.is_empty() => Ok(OptionsArgs::Set {
+                key: key.to_string(),
+                value: value.join(" "),
+            }),
+            _ => Err(CommandError::MissingArguments {
+                command: String::from("options"),
+                expected: String::from("options [set <key> <value>]"),
+            }),
+        }
+    }
+}
+
+/// Lists the options, or checks a new value and hands it to whoever keeps the option:
+/// the frontend for session options, the game state for game options.
+pub fn execute(args: OptionsArgs) -> Result<CommandEffect, CommandError> {
+    let (key, value) = match args {
+        OptionsArgs::List => return Ok(CommandEffect::ListOptions),
+        OptionsArgs::Set { key, value } => (key, value),
+    };
+    let Some(option) = options::find(&key) else {
+        return Err(CommandError::InvalidArgument {
+            command: String::from("options set"),
+            argument: key,
+            reason: format!("known options are: {}", options::keys().join(", ")),
+        });
+    };
+
+    match option.parse(&value) {
+        Ok(OptionChange::Session(option)) => Ok(CommandEffect::SetSessionOption { option }),
+        Ok(OptionChange::Game(option)) => Ok(CommandEffect::SetGameOption { option }),
+        Err(reason) => Err(CommandError::InvalidArgument {
+            command: format!("options set {}", key),
+            argument: value,
+            reason,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::map::RenderStyle;
+    use crate::engine::options::{GameOption, SessionOption};
+
+    fn set(line: &str) -> Result<CommandEffect, CommandError> {
+        execute(OptionsArgs::parse(line.split_whitespace().collect())?)
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(OptionsArgs::parse(vec![]).unwrap(), OptionsArgs::List);
+        assert_eq!(
+            OptionsArgs::parse(vec!["set", "autosave", "2"]).unwrap(),
+            OptionsArgs::Set { key: String::from("autosave"), value: String::from("2") },
+        );
+        assert!(matches!(OptionsArgs::parse(vec!["set", "autosave"]), Err(CommandError::MissingArguments { .. })));
+    }
+
+    #[test]
+    fn test_set_routes_each_scope() {
+        assert!(matches!(
+            set("set style ascii"),
+            Ok(CommandEffect::SetSessionOption { option: SessionOption::Style(RenderStyle::AsciiOnly) })
+        ));
+        assert!(matches!(
+            set("set autosave 5"),
+            Ok(CommandEffect::SetGameOption { option: GameOption::AutosaveRounds(5) })
+        ));
+        let Err(CommandError::InvalidArgument { reason, .. }) = set("set victory domination") else {
+            panic!("Fixed options cannot be set")
+        };
+        assert!(reason.contains("rules"), "{reason}");
+    }
+
+    #[test]
+    fn test_set_rejects_unknown_keys_and_invalid_values() {
+        assert!(matches!(set("set pager on"), Err(CommandError::InvalidArgument { argument, .. }) if argument == "pager"));
+        assert!(matches!(set("set advisor sometimes"), Err(CommandError::InvalidArgument { argument, .. }) if argument == "sometimes"));
+    }
+}
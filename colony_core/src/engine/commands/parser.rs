@@ -0,0 +1,248 @@
+use crate::engine::commands::command::{Command, CommandError};
+use crate::engine::commands::alias::AliasArgs;
+use crate::engine::commands::help::HelpArgs;
+use crate::engine::commands::note::NoteArgs;
+use crate::engine::commands::build::BuildArgs;
+use crate::engine::commands::build_all::BuildAllArgs;
+use crate::engine::commands::build_ship::BuildShipArgs;
+use crate::engine::commands::cancel::CancelArgs;
+use crate::engine::commands::export::ExportArgs;
+use crate::engine::commands::fleet::FleetArgs;
+use crate::engine::commands::forecast::ForecastArgs;
+use crate::engine::commands::defense::DefenseArgs;
+use crate::engine::commands::dev::DevArgs;
+use crate::engine::commands::diff::DiffArgs;
+use crate::engine::commands::inspect::InspectArgs;
+use crate::engine::commands::intel::IntelArgs;
+use crate::engine::commands::log::LogArgs;
+use crate::engine::commands::battles::BattlesArgs;
+use crate::engine::commands::map::MapArgs;
+use crate::engine::commands::planet::PlanetArgs;
+use crate::engine::commands::ship::ShipArgs;
+use crate::engine::commands::save::{SaveArgs, SavesArgs};
+use crate::engine::commands::set::SetArgs;
+use crate::engine::commands::options::OptionsArgs;
+use crate::engine::commands::status::StatusArgs;
+use crate::engine::commands::structures::StructuresArgs;
+use crate::engine::commands::upgrade::UpgradeArgs;
+
+/// Every accepted spelling of the end turn command. The first form is canonical.
+pub const END_TURN_ALIASES: &[&str] = &["end_turn", "end-turn", "end", "endturn"];
+
+/// Every accepted spelling of the exit command. The first form is canonical.
+pub const EXIT_ALIASES: &[&str] = &["exit", "quit", "terminate"];
+
+/// Commands that have a single spelling.
+const COMMAND_NAMES: &[&str] = &[
+    "build", "build_all", "build_ship", "upgrade", "cancel", "status", "intel", "inspect", "dev", "log", "battles", "forecast", "defense", "diff", "structures", "map", "ships", "fleets", "schedule", "advisor", "fleet", "planet", "ship", "save", "load", "qs", "ql", "saves", "export", "alias", "note", "set", "options", "rematch", "help",
+];
+
+/// Returns every command word the parser accepts.
+pub fn accepted_command_words() -> Vec<&'static str> {
+    COMMAND_NAMES.iter()
+        .chain(END_TURN_ALIASES)
+        .chain(EXIT_ALIASES)
+        .copied()
+        .collect()
+}
+
+pub trait Parseable {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> where Self: Sized;
+}
+
+/// Removes the preview modifier from `args`: either `--preview` or a `?` after the last
+/// argument, as in `build home mine?`. Returns whether it was there.
+pub fn take_preview_flag(args: &mut Vec<&str>) -> bool {
+    let mut preview = args.contains(&"--preview") || args.contains(&"?");
+    args.retain(|arg| *arg != "--preview" && *arg != "?");
+    if let Some(last) = args.last_mut()
+        && let Some(stripped) = last.strip_suffix('?')
+    {
+        *last = stripped;
+        preview = true;
+    }
+    preview
+}
+
+pub fn parse(input: &str) -> Result<Command, CommandError> {
+    let mut parts = input.split_whitespace();
+
+    let Some(command_name) = parts.next() else {
+        return Err(CommandError::NoCommandEntered);
+    };
+    let command_args: Vec<&str> = parts.collect();
+
+    match command_name {
+        "build" => Ok(Command::Build(BuildArgs::parse(command_args)?)),
+        "build_all" => Ok(Command::BuildAll(BuildAllArgs::parse(command_args)?)),
+        "build_ship" => Ok(Command::BuildShip(BuildShipArgs::parse(command_args)?)),
+        "upgrade" => Ok(Command::Upgrade(UpgradeArgs::parse(command_args)?)),
+        "cancel" => Ok(Command::Cancel(CancelArgs::parse(command_args)?)),
+        "status" => Ok(Command::Status(StatusArgs::parse(command_args)?)),
+        "intel" => Ok(Command::Intel(IntelArgs::parse(command_args)?)),
+        "inspect" => Ok(Command::Inspect(InspectArgs::parse(command_args)?)),
+        "dev" => Ok(Command::Dev(DevArgs::parse(command_args)?)),
+        "log" => Ok(Command::Log(LogArgs::parse(command_args)?)),
+        "battles" => Ok(Command::Battles(BattlesArgs::parse(command_args)?)),
+        "forecast" => Ok(Command::Forecast(ForecastArgs::parse(command_args)?)),
+        "defense" => Ok(Command::Defense(DefenseArgs::parse(command_args)?)),
+        "diff" => Ok(Command::Diff(DiffArgs::parse(command_args)?)),
+        "structures" => Ok(Command::Structures(StructuresArgs::parse(command_args)?)),
+        "map" => Ok(Command::Map(MapArgs::parse(command_args)?)),
+        "ships" => Ok(Command::Ships),
+        "fleets" => Ok(Command::Fleets),
+        "schedule" => Ok(Command::Schedule),
+        "advisor" => Ok(Command::Advisor),
+        "fleet" => Ok(Command::Fleet(FleetArgs::parse(command_args)?)),
+        "planet" => Ok(Command::Planet(PlanetArgs::parse(command_args)?)),
+        "ship" => Ok(Command::Ship(ShipArgs::parse(command_args)?)),
+        "save" => Ok(Command::Save(SaveArgs::parse(command_args)?)),
+        "load" => Ok(Command::Load(SaveArgs::parse(command_args)?)),
+        "qs" => Ok(Command::Save(SaveArgs::quick())),
+        "ql" => Ok(Command::Load(SaveArgs::quick())),
+        "saves" => Ok(Command::Saves(SavesArgs::parse(command_args)?)),
+        "export" => Ok(Command::Export(ExportArgs::parse(command_args)?)),
+        "alias" => Ok(Command::Alias(AliasArgs::parse(command_args)?)),
+        "note" => Ok(Command::Note(NoteArgs::parse(command_args)?)),
+        "set" => Ok(Command::Set(SetArgs::parse(command_args)?)),
+        "options" => Ok(Command::Options(OptionsArgs::parse(command_args)?)),
+        "help" => Ok(Command::Help(HelpArgs::parse(command_args)?)),
+        "rematch" => Ok(Command::Rematch),
+        #[cfg(test)]
+        "crash!" => Ok(Command::Crash),
+        name if END_TURN_ALIASES.contains(&name) => Ok(Command::EndTurn),
+        name if EXIT_ALIASES.contains(&name) => Ok(Command::Exit),
+        _ => Err(CommandError::UnknownCommand(command_name.to_string())),
+    }
+}
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use proptest::sample::select;
+
+    use super::*;
+    use crate::engine::commands::completion::{self, ArgKind, SIGNATURES, Signature};
+    use crate::engine::commands::fleet::FleetAction;
+
+    /// A valid token for an argument of `kind`.
+    fn argument(kind: ArgKind) -> BoxedStrategy<String> {
+        match kind.required() {
+            ArgKind::Literal(word) => Just(word.to_string()).boxed(),
+            ArgKind::OneOf(words) => select(words).prop_map(str::to_string).boxed(),
+            ArgKind::Number(min, max) => (min..=max).prop_map(|number| number.to_string()).boxed(),
+            ArgKind::TurnRange => (1u32..500, 0u32..50, 0..3)
+                .prop_map(|(first, span, form)| match form {
+                    0 => first.to_string(),
+                    1 => format!("{}-{}", first, first + span),
+                    _ => format!("{}-", first),
+                })
+                .boxed(),
+            ArgKind::Text => "[A-Za-z][A-Za-z0-9_.-]{0,15}".boxed(),
+            _ => "[a-z][a-z0-9_]{0,15}".boxed(),
+        }
+    }
+
+    /// Input matching `signature`, with any number of its optional arguments and, for variadic
+    /// forms, extra repeats of the last argument.
+    fn command_line(signature: &'static Signature) -> impl Strategy<Value = String> {
+        let required = signature.args.iter().filter(|kind| !matches!(kind, ArgKind::Optional(_))).count();
+        let repeats = if signature.variadic { 0..8usize } else { 0..1 };
+        (required..=signature.args.len(), repeats)
+            .prop_flat_map(move |(given, repeats)| {
+                let last = signature.args[signature.args.len() - 1];
+                signature.args[..given].iter().copied()
+                    .chain(std::iter::repeat_n(last, repeats))
+                    .map(argument)
+                    .collect::<Vec<_>>()
+            })
+            .prop_map(|tokens| tokens.join(" "))
+    }
+
+    fn expected_command(signature: &Signature) -> &'static str {
+        match signature.args[0] {
+            ArgKind::Literal("qs") => "save",
+            ArgKind::Literal("ql") => "load",
+            ArgKind::Literal(word) => word,
+            kind => panic!("Signature starts with {:?} rather than a command word", kind),
+        }
+    }
+
+    fn separator() -> impl Strategy<Value = &'static str> {
+        select(&[" ", "  ", "\t", "\n", "\u{3000}", " \u{a0}"][..])
+    }
+
+    /// Tokens nothing in the parser expects: arbitrary text, stray quotes, modifiers and '='.
+    fn junk_token() -> impl Strategy<Value = String> {
+        prop_oneof![
+            "\\PC{0,12}",
+            "[\"'a-z]{0,8}",
+            select(&["?", "--preview", "--repeat", "=", "\"", "'", "-", "0", "4294967296"][..]).prop_map(str::to_string),
+        ]
+    }
+
+    #[test]
+    fn test_whitespace_only_input_is_no_command() {
+        assert!(matches!(parse(" \t "), Err(CommandError::NoCommandEntered)));
+        assert!(matches!(parse(""), Err(CommandError::NoCommandEntered)));
+    }
+
+    #[test]
+    fn test_every_alias_parses_to_its_command() {
+        for alias in END_TURN_ALIASES {
+            assert_eq!(parse(alias).unwrap().name(), "end_turn");
+        }
+        for alias in EXIT_ALIASES {
+            assert_eq!(parse(alias).unwrap().name(), "exit");
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_parser_never_panics_on_arbitrary_tokens(
+            command in prop_oneof![select(accepted_command_words()).prop_map(str::to_string), junk_token()],
+            tokens in prop::collection::vec((separator(), junk_token()), 0..40),
+        ) {
+            let input: String = tokens.iter()
+                .fold(command, |input, (separator, token)| format!("{}{}{}", input, separator, token));
+            let _ = parse(&input);
+        }
+
+        #[test]
+        fn test_signature_commands_parse_into_their_command(
+            (index, input) in (0..SIGNATURES.len()).prop_flat_map(|index| (Just(index), command_line(&SIGNATURES[index]))),
+        ) {
+            let expected = expected_command(&SIGNATURES[index]);
+            match parse(&input) {
+                Ok(command) => prop_assert_eq!(command.name(), expected, "{}", input),
+                Err(error) => prop_assert!(false, "{} does not parse: {}", input, error),
+            }
+        }
+
+        #[test]
+        fn test_long_ship_lists_round_trip(
+            name in "[a-z][a-z0-9_]{0,15}",
+            ship_ids in prop::collection::vec("[a-z]{1,10}_[0-9]{1,4}", 1..500),
+        ) {
+            let mut args = vec!["create", name.as_str()];
+            args.extend(ship_ids.iter().map(String::as_str));
+
+            let fleet = FleetArgs::parse(args).unwrap();
+
+            let FleetAction::Create { name: parsed_name, ship_ids: parsed_ids } = fleet.action else {
+                panic!("Expected fleet create, got {:?}", fleet.action)
+            };
+            prop_assert_eq!(parsed_name, name);
+            prop_assert_eq!(parsed_ids, ship_ids);
+        }
+
+        #[test]
+        fn test_tokens_never_hold_whitespace(input in "[\"' a-z\t\u{3000}]{0,40}") {
+            let tokens = completion::tokenize(&input);
+
+            let (last, rest) = tokens.split_last().expect("tokenize always returns a token to complete");
+            prop_assert!(rest.iter().all(|token| !token.is_empty()));
+            prop_assert!(tokens.iter().all(|token| !token.contains(char::is_whitespace)));
+            prop_assert_eq!(last.is_empty(), input.is_empty() || input.ends_with(char::is_whitespace));
+        }
+    }
+}
@@ -0,0 +1,639 @@
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::fleet::rejected;
+use crate::engine::commands::parser::Parseable;
+use crate::engine::game_state::GameState;
+use crate::engine::planet::{Allocation, OVERCHARGE_ENERGY_COST, Planet, PlanetId, RallyPoint};
+use crate::engine::pending_action::ActionType;
+use crate::engine::resources::Resources;
+use crate::engine::rules;
+use crate::engine::ship::ShipInstanceId;
+use crate::engine::structure::{Power, StructureId};
+
+#[derive(Debug)]
+pub enum PlanetAction {
+    /// None clears the rally point
+    Rally { planet_id: PlanetId, rally_point: Option<RallyPoint> },
+    Garrison { planet_id: PlanetId, ship_ids: Vec<ShipInstanceId> },
+    Ungarrison { planet_id: PlanetId, ship_ids: Vec<ShipInstanceId> },
+    Overcharge { planet_id: PlanetId },
+    Link { first: PlanetId, second: PlanetId },
+    Power { planet_id: PlanetId, structure_id: StructureId, on: bool },
+    /// None goes back to the structures' own split
+    Allocate { planet_id: PlanetId, allocation: Option<Allocation> },
+}
+
+#[derive(Debug)]
+pub struct PlanetArgs {
+    pub action: PlanetAction,
+}
+
+impl Parseable for PlanetArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::MissingArguments {
+                command: String::from("planet"),
+                expected: String::from("planet <rally|garrison|ungarrison|overcharge|link|power|allocate> ..."),
+            });
+        }
+
+        let action = match args[0] {
+            "rally" => {
+                let rally_point = match args.get(2..) {
+                    Some(["none"]) => None,
+                    Some(["new", name]) => Some(RallyPoint::NewFleet(name.to_string())),
+                    Some([fleet_id]) if *fleet_id != "new" => Some(RallyPoint::Fleet(fleet_id.to_string())),
+                    _ => {
+                        return Err(CommandError::MissingArguments {
+                            command: String::from("planet rally"),
+                            expected: String::from("planet rally <planet_id> <fleet_id|new <name>|none>"),
+                        });
+                    }
+                };
+                PlanetAction::Rally { planet_id: args[1].to_string(), rally_point }
+            }
+            action @ ("garrison" | "ungarrison") => {
+                if args.len() < 3 {
+                    return Err(CommandError::MissingArguments {
+                        command: format!("planet {}", action),
+                        expected: format!("planet {} <planet_id> <ship_id>...", action),
+                    });
+                }
+                let planet_id = args[1].to_string();
+                let ship_ids = args[2..].iter().map(|s| s.to_string()).collect();
+                if action == "garrison" {
+                    PlanetAction::Garrison { planet_id, ship_ids }
+                } else {
+                    PlanetAction::Ungarrison { planet_id, ship_ids }
+                }
+            }
+            "overcharge" => {
+                let Some(planet_id) = args.get(1) else {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("planet overcharge"),
+                        expected: String::from("planet overcharge <planet_id>"),
+                    });
+                };
+                PlanetAction::Overcharge { planet_id: planet_id.to_string() }
+            }
+            "link" => {
+                let [first, second] = args[1..] else {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("planet link"),
+                        expected: String::from("planet link <planet_a> <planet_b>"),
+                    });
+                };
+                PlanetAction::Link { first: first.to_string(), second: second.to_string() }
+            }
+            "power" => {
+                let [planet_id, structure_id, setting] = args[1..] else {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("planet power"),
+                        expected: String::from("planet power <planet_id> <structure_id> <on|off>"),
+                    });
+                };
+                let on = match setting {
+                    "on" => true,
+                    "off" => false,
+                    _ => {
+                        return Err(CommandError::InvalidArgument {
+                            command: String::from("planet power"),
+                            argument: setting.to_string(),
+                            reason: String::from("expected on or off"),
+                        });
+                    }
+                };
+                PlanetAction::Power { planet_id: planet_id.to_string(), structure_id: structure_id.to_string(), on }
+            }
+            "allocate" => {
+                let allocation = match args.get(1..) {
+                    Some([_, "default"]) => None,
+                    Some([_, minerals, gas, energy]) => Some(parse_allocation(minerals, gas, energy)?),
+                    _ => {
+                        return Err(CommandError::MissingArguments {
+                            command: String::from("planet allocate"),
+                            expected: String::from("planet allocate <planet_id> <minerals%> <gas%> <energy%>|default"),
+                        });
+                    }
+                };
+                PlanetAction::Allocate { planet_id: args[1].to_string(), allocation }
+            }
+            _ => {
+                return Err(CommandError::InvalidArgument {
+                    command: String::from("planet"),
+                    argument: args[0].to_string(),
+                    reason: String::from("valid actions are: rally, garrison, ungarrison, overcharge, link, power, allocate"),
+                });
+            }
+        };
+
+        Ok(PlanetArgs { action })
+    }
+}
+
+/// Reads the three shares of an allocation, each a whole percent with or without the sign.
+fn parse_allocation(minerals: &str, gas: &str, energy: &str) -> Result<Allocation, CommandError> {
+    let share = |share: &str| share.strip_suffix('%').unwrap_or(share).parse::<u32>()
+        .ok()
+        .filter(|percent| *percent <= 100)
+        .ok_or_else(|| CommandError::InvalidArgument {
+            command: String::from("planet allocate"),
+            argument: share.to_string(),
+            reason: String::from("shares are whole percents from 0 to 100"),
+        });
+    let allocation = Allocation { minerals: share(minerals)?, gas: share(gas)?, energy: share(energy)? };
+
+    let sum: u32 = allocation.shares().iter().sum();
+    if sum != 100 {
+        return Err(CommandError::InvalidArgument {
+            command: String::from("planet allocate"),
+            argument: format!("{} {} {}", minerals, gas, energy),
+            reason: format!("shares must add up to 100%, not {}%", sum),
+        });
+    }
+    Ok(allocation)
+}
+
+pub fn execute(args: PlanetArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    match args.action {
+        PlanetAction::Rally { planet_id, rally_point } => validate_rally(&planet_id, rally_point, game_state),
+        PlanetAction::Garrison { planet_id, ship_ids } => validate_garrison(&planet_id, &ship_ids, game_state),
+        PlanetAction::Ungarrison { planet_id, ship_ids } => validate_ungarrison(&planet_id, &ship_ids, game_state),
+        PlanetAction::Overcharge { planet_id } => validate_overcharge(&planet_id, game_state),
+        PlanetAction::Link { first, second } => validate_link(&first, &second, game_state),
+        PlanetAction::Power { planet_id, structure_id, on } => validate_power(&planet_id, &structure_id, on, game_state),
+        PlanetAction::Allocate { planet_id, allocation } => {
+            owned_planet(&planet_id, game_state)?;
+            Ok(CommandEffect::SetAllocation { planet_id, allocation })
+        }
+    }
+}
+
+/// Checks the planet exists and belongs to the current player.
+fn owned_planet<'a>(planet_id: &PlanetId, game_state: &'a GameState) -> Result<&'a Planet, CommandError> {
+    CommandError::own_planet(planet_id, game_state).map(|(_, planet)| planet)
+}
+
+fn validate_garrison(
+    planet_id: &PlanetId,
+    ship_ids: &[ShipInstanceId],
+    game_state: &GameState,
+) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+    let planet = owned_planet(planet_id, game_state)?;
+
+    for ship_id in ship_ids {
+        if let Some(action) = player.find_ship_under_construction(ship_id) {
+            return Err(CommandError::ShipUnderConstruction {
+                ship_id: ship_id.clone(),
+                turns: action.cooldown_remaining,
+            });
+        }
+
+        let ship = player.ships.get(ship_id)
+            .ok_or_else(|| CommandError::ship_not_found("planet garrison", ship_id, game_state))?;
+
+        let reason = if &ship.location != planet_id {
+            Some(format!("ship is at {}, not {}", ship.location, planet_id))
+        } else if planet.is_garrisoned(ship_id) {
+            Some(String::from("ship is already garrisoned"))
+        } else if ship.fleet_id.as_ref().is_some_and(|fleet_id| player.has_pending_fleet_move(fleet_id)) {
+            Some(String::from("ship's fleet has a pending move"))
+        } else if player.has_pending_ship_move(ship_id) {
+            Some(String::from("ship has a pending move"))
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            return Err(CommandError::InvalidArgument {
+                command: String::from("planet garrison"),
+                argument: ship_id.clone(),
+                reason,
+            });
+        }
+    }
+
+    // Garrisoned ships leave their fleets, which must still hold their cargo without them
+    for fleet in player.fleets.values() {
+        rules::cargo_fits_without(fleet, ship_ids, player, game_state)
+            .map_err(|reason| rejected("planet garrison", &fleet.id, reason))?;
+    }
+
+    Ok(CommandEffect::GarrisonShips {
+        planet_id: planet_id.clone(),
+        ship_ids: ship_ids.to_vec(),
+    })
+}
+
+fn validate_ungarrison(
+    planet_id: &PlanetId,
+    ship_ids: &[ShipInstanceId],
+    game_state: &GameState,
+) -> Result<CommandEffect, CommandError> {
+    let planet = owned_planet(planet_id, game_state)?;
+
+    if let Some(ship_id) = ship_ids.iter().find(|ship_id| !planet.is_garrisoned(ship_id)) {
+        return Err(CommandError::InvalidArgument {
+            command: String::from("planet ungarrison"),
+            argument: ship_id.clone(),
+            reason: format!("ship is not garrisoned on {}", planet_id),
+        });
+    }
+
+    Ok(CommandEffect::UngarrisonShips {
+        planet_id: planet_id.clone(),
+        ship_ids: ship_ids.to_vec(),
+    })
+}
+
+fn validate_rally(
+    planet_id: &PlanetId,
+    rally_point: Option<RallyPoint>,
+    game_state: &GameState,
+) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+
+    owned_planet(planet_id, game_state)?;
+
+    // An existing fleet must be orbiting the planet and staying there
+    if let Some(RallyPoint::Fleet(fleet_id)) = &rally_point {
+        let fleet = player.fleets.get(fleet_id)
+            .ok_or_else(|| CommandError::fleet_not_found("planet rally", fleet_id, game_state))?;
+
+        if &fleet.location != planet_id {
+            return Err(CommandError::InvalidArgument {
+                command: String::from("planet rally"),
+                argument: fleet_id.clone(),
+                reason: format!("fleet is at {}, not {}", fleet.location, planet_id),
+            });
+        }
+
+        if player.has_pending_fleet_move(fleet_id) {
+            return Err(CommandError::InvalidArgument {
+                command: String::from("planet rally"),
+                argument: fleet_id.clone(),
+                reason: String::from("fleet has a pending move"),
+            });
+        }
+    }
+
+    Ok(CommandEffect::SetRallyPoint {
+        planet_id: planet_id.clone(),
+        rally_point,
+    })
+}
+
+fn validate_overcharge(planet_id: &PlanetId, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let planet = owned_planet(planet_id, game_state)?;
+
+    let reason = if planet.get_max_shield_hp() == 0 {
+        Some(String::from("planet has no defense shield"))
+    } else if planet.get_shield_overcharge() > 0 {
+        Some(String::from("shields are already overcharged this turn"))
+    } else {
+        None
+    };
+    if let Some(reason) = reason {
+        return Err(CommandError::InvalidArgument {
+            command: String::from("planet overcharge"),
+            argument: planet_id.clone(),
+            reason,
+        });
+    }
+
+    let cost = Resources { energy: OVERCHARGE_ENERGY_COST, ..Resources::default() };
+    if !planet.available_resources.has_enough(&cost) {
+        return Err(CommandError::NotEnoughResources {
+            planet_name: planet.name.clone(),
+            cost,
+        });
+    }
+
+    Ok(CommandEffect::OverchargeShield { planet_id: planet_id.clone() })
+}
+
+/// A built structure on one of the player's planets can be powered on or off, except the
+/// planetary capital, and the defense shield while the planet is being bombarded.
+fn validate_power(
+    planet_id: &PlanetId,
+    structure_id: &StructureId,
+    on: bool,
+    game_state: &GameState,
+) -> Result<CommandEffect, CommandError> {
+    let planet = owned_planet(planet_id, game_state)?;
+    let structure = planet.get_structures().get(structure_id)
+        .filter(|structure| structure.level > 0)
+        .ok_or_else(|| CommandError::InvalidArgument {
+            command: String::from("planet power"),
+            argument: structure_id.clone(),
+            reason: format!("no structure with that id is built on {}", planet.name),
+        })?;
+
+    let under_bombardment = game_state.players.values()
+        .flat_map(|player| &player.pending_actions)
+        .any(|action| matches!(&action.action_type, ActionType::BombardPlanet(_, target) if target == planet_id));
+    let reason = if structure_id == "planetary_capital" {
+        Some(String::from("the planetary capital cannot be powered down"))
+    } else if !on && structure_id == "defense_shield" && under_bombardment {
+        Some(String::from("the defense shield cannot be powered down while the planet is bombarded"))
+    } else if !on && structure.power == Power::Off {
+        Some(String::from("structure is already powered down"))
+    } else if on && structure.power == Power::On {
+        Some(String::from("structure is already powered on"))
+    } else {
+        None
+    };
+    if let Some(reason) = reason {
+        return Err(CommandError::InvalidArgument {
+            command: String::from("planet power"),
+            argument: structure_id.clone(),
+            reason,
+        });
+    }
+
+    Ok(CommandEffect::SetStructurePower { planet_id: planet_id.clone(), structure_id: structure_id.clone(), on })
+}
+
+/// Two of the player's planets with operational, unlinked jump gates can be linked, unless
+/// they are already neighbors. Each gate links to a single partner.
+fn validate_link(first: &PlanetId, second: &PlanetId, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let first_planet = owned_planet(first, game_state)?;
+    let second_planet = owned_planet(second, game_state)?;
+
+    let invalid = |planet_id: &PlanetId, reason: String| CommandError::InvalidArgument {
+        command: String::from("planet link"),
+        argument: planet_id.clone(),
+        reason,
+    };
+
+    if first == second {
+        return Err(invalid(second, String::from("a planet cannot be linked to itself")));
+    }
+    for planet in [first_planet, second_planet] {
+        if !planet.has_operational_gate() {
+            return Err(invalid(&planet.id, String::from("planet has no operational jump gate")));
+        }
+        if let Some(partner) = planet.gate_partner() {
+            return Err(invalid(&planet.id, format!("jump gate is already linked to {}", partner)));
+        }
+    }
+    if game_state.map.graph().are_adjacent(first, second) {
+        return Err(invalid(second, format!("{} and {} are already connected", first, second)));
+    }
+
+    Ok(CommandEffect::LinkGates { first: first.clone(), second: second.clone() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::game_event::GameEvent;
+    use crate::engine::pending_action::PendingAction;
+    use crate::test_support::{self, end_round, fixture_game, run};
+
+    #[test]
+    fn test_parse_rally_targets() {
+        let parse = |args: Vec<&str>| match PlanetArgs::parse(args).map(|args| args.action) {
+            Ok(PlanetAction::Rally { rally_point, .. }) => Ok(rally_point),
+            Ok(action) => panic!("Expected a rally action, got {:?}", action),
+            Err(e) => Err(e),
+        };
+
+        assert_eq!(parse(vec!["rally", "c418", "fleet_1"]).unwrap(), Some(RallyPoint::Fleet(String::from("fleet_1"))));
+        assert_eq!(parse(vec!["rally", "c418", "new", "home_guard"]).unwrap(), Some(RallyPoint::NewFleet(String::from("home_guard"))));
+        assert_eq!(parse(vec!["rally", "c418", "none"]).unwrap(), None);
+        assert!(matches!(parse(vec!["rally", "c418"]), Err(CommandError::MissingArguments { .. })));
+        assert!(matches!(parse(vec!["rally", "c418", "new"]), Err(CommandError::MissingArguments { .. })));
+    }
+
+    #[test]
+    fn test_allocate_checks_shares_and_resets_to_default() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+
+        for (shares, reason) in [("60 30 20", "must add up to 100%, not 110%"), ("150 0 0", "whole percents from 0 to 100")] {
+            let error = run(&mut state, &format!("planet allocate {} {}", home, shares)).unwrap_err();
+            assert!(error.to_string().contains(reason), "{shares}: {error}");
+        }
+        let bob_home = test_support::home_planet(&state, "bob");
+        assert!(matches!(run(&mut state, &format!("planet allocate {} 100 0 0", bob_home)), Err(CommandError::NotYourPlanet(_))));
+
+        let events = run(&mut state, &format!("planet allocate {} 60% 20% 20%", home)).unwrap();
+        assert!(matches!(&events[..], [GameEvent::AllocationSet { allocation: Some(_), .. }]));
+        assert_eq!(state.map.planets[&home].get_allocation(), Some(Allocation { minerals: 60, gas: 20, energy: 20 }));
+
+        let events = run(&mut state, &format!("planet allocate {} default", home)).unwrap();
+        assert!(events[0].to_string().contains("back to the default allocation"), "{}", events[0]);
+        assert_eq!(state.map.planets[&home].get_allocation(), None);
+    }
+
+    /// Queues an interceptor at `planet_id` for alice and plays out the round.
+    fn build_interceptor(state: &mut GameState, planet_id: &str) -> Vec<GameEvent> {
+        run(state, &format!("build_ship {} interceptor", planet_id)).unwrap();
+        run(state, "end_turn").unwrap();
+        state.apply_effect(CommandEffect::EndTurn { player_name: String::from("Bob") }, &String::from("bob")).unwrap()
+    }
+
+    /// Gives alice's home planet a level 1 shipyard and plenty of resources.
+    fn home_with_shipyard(state: &mut GameState) -> PlanetId {
+        let home = test_support::home_planet(state, "alice");
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.complete_build_structure(String::from("power_grid"), &state.structure_config).unwrap();
+        planet.complete_build_structure(String::from("orbital_shipyard"), &state.structure_config).unwrap();
+        planet.recalculate_from_structures();
+        planet.available_resources = planet.storage_capacity.clone();
+        home
+    }
+
+    #[test]
+    fn test_new_fleet_rally_forms_fleet_then_keeps_joining_it() {
+        let mut state = fixture_game(1);
+        let home = home_with_shipyard(&mut state);
+        run(&mut state, &format!("planet rally {} new home_guard", home)).unwrap();
+
+        let events = build_interceptor(&mut state, &home);
+        assert!(events.iter().any(|e| matches!(e, GameEvent::ShipBuilt { fleet_name: Some(name), .. } if name == "home_guard")));
+        build_interceptor(&mut state, &home);
+
+        let alice = &state.players["alice"];
+        assert_eq!(alice.fleets["fleet_1"].ships, ["interceptor_1", "interceptor_2"]);
+        assert_eq!(alice.ships["interceptor_2"].fleet_id.as_deref(), Some("fleet_1"));
+        assert_eq!(state.map.planets[&home].get_rally_point(), Some(&RallyPoint::Fleet(String::from("fleet_1"))));
+    }
+
+    #[test]
+    fn test_clearing_rally_leaves_ships_standalone() {
+        let mut state = fixture_game(1);
+        let home = home_with_shipyard(&mut state);
+        run(&mut state, &format!("planet rally {} new home_guard", home)).unwrap();
+        run(&mut state, &format!("planet rally {} none", home)).unwrap();
+
+        build_interceptor(&mut state, &home);
+
+        assert!(state.players["alice"].fleets.is_empty());
+        assert_eq!(state.players["alice"].ships["interceptor_1"].fleet_id, None);
+    }
+
+    #[test]
+    fn test_rally_requires_fleet_at_planet() {
+        let mut state = fixture_game(1);
+        let home = home_with_shipyard(&mut state);
+        let neighbour = state.map.planets[&home].get_connections()[0].to().clone();
+        state.players.get_mut("alice").unwrap().add_ship(String::from("interceptor"), neighbour.clone());
+        run(&mut state, "fleet create away interceptor_1").unwrap();
+
+        let result = run(&mut state, &format!("planet rally {} fleet_1", home));
+
+        assert!(matches!(result, Err(CommandError::InvalidArgument { reason, .. }) if reason.contains(&neighbour)));
+    }
+
+    #[test]
+    fn test_departing_fleet_breaks_rally() {
+        let mut state = fixture_game(1);
+        let home = home_with_shipyard(&mut state);
+        let neighbour = state.map.planets[&home].get_connections()[0].to().clone();
+        state.players.get_mut("alice").unwrap().add_ship(String::from("interceptor"), home.clone());
+        run(&mut state, "fleet create guard interceptor_1").unwrap();
+        run(&mut state, &format!("planet rally {} fleet_1", home)).unwrap();
+
+        let events = run(&mut state, &format!("fleet move fleet_1 {}", neighbour)).unwrap();
+
+        assert!(events.iter().any(|e| matches!(e, GameEvent::RallyPointBroken { .. })));
+        assert_eq!(state.map.planets[&home].get_rally_point(), None);
+    }
+
+    #[test]
+    fn test_full_rally_fleet_breaks_rally() {
+        let mut state = fixture_game(1);
+        state.rules.profile.fleet_capacity = 1;
+        let home = home_with_shipyard(&mut state);
+        run(&mut state, &format!("planet rally {} new home_guard", home)).unwrap();
+        build_interceptor(&mut state, &home);
+
+        let events = build_interceptor(&mut state, &home);
+
+        assert!(events.iter().any(|e| matches!(e, GameEvent::RallyPointBroken { reason, .. } if reason == "is full (capacity 1/1)")));
+        assert_eq!(state.players["alice"].fleets["fleet_1"].ships, ["interceptor_1"]);
+        assert_eq!(state.players["alice"].ships["interceptor_2"].fleet_id, None);
+    }
+
+    #[test]
+    fn test_garrison_takes_ships_out_of_fleets_until_released() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        state.players.get_mut("alice").unwrap().add_ship(String::from("interceptor"), home.clone());
+        run(&mut state, "fleet create guard interceptor_1").unwrap();
+
+        run(&mut state, &format!("planet garrison {} interceptor_1", home)).unwrap();
+
+        assert_eq!(state.players["alice"].ships["interceptor_1"].fleet_id, None);
+        assert!(state.players["alice"].fleets["fleet_1"].ships.is_empty());
+        let detail = match crate::engine::commands::parser::parse(&format!("status planet {}", home)).unwrap().execute(&state).unwrap() {
+            CommandEffect::None { message } => message,
+            _ => panic!("Status must only produce a message"),
+        };
+        assert!(detail.contains("GARRISON\n  interceptor_1: Interceptor (interceptor)"), "{detail}");
+
+        let result = run(&mut state, "fleet add fleet_1 interceptor_1");
+        assert!(matches!(result, Err(CommandError::InvalidArgument { reason, .. }) if reason.contains("garrisoned")));
+
+        run(&mut state, &format!("planet ungarrison {} interceptor_1", home)).unwrap();
+        run(&mut state, "fleet add fleet_1 interceptor_1").unwrap();
+        assert!(state.map.planets[&home].get_garrison().is_empty());
+    }
+
+    #[test]
+    fn test_garrison_rejects_ships_elsewhere_or_not_garrisoned() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let neighbour = state.map.planets[&home].get_connections()[0].to().clone();
+        let alice = state.players.get_mut("alice").unwrap();
+        alice.add_ship(String::from("interceptor"), neighbour.clone());
+        alice.add_ship(String::from("interceptor"), home.clone());
+
+        let away = run(&mut state, &format!("planet garrison {} interceptor_1", home));
+        assert!(matches!(away, Err(CommandError::InvalidArgument { reason, .. }) if reason.contains(&neighbour)));
+
+        let free = run(&mut state, &format!("planet ungarrison {} interceptor_2", home));
+        assert!(matches!(free, Err(CommandError::InvalidArgument { argument, .. }) if argument == "interceptor_2"));
+
+        let missing = PlanetArgs::parse(vec!["garrison", &home]);
+        assert!(matches!(missing, Err(CommandError::MissingArguments { .. })));
+    }
+
+    #[test]
+    fn test_overcharge_spends_energy_once_per_turn() {
+        let mut state = fixture_game(1);
+        let home = home_with_shipyard(&mut state);
+        let without_shield = run(&mut state, &format!("planet overcharge {}", home));
+        assert!(matches!(without_shield, Err(CommandError::InvalidArgument { reason, .. }) if reason.contains("no defense shield")));
+
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.complete_build_structure(String::from("defense_shield"), &state.structure_config).unwrap();
+        let energy_before = planet.available_resources.energy;
+
+        let events = run(&mut state, &format!("planet overcharge {}", home)).unwrap();
+        assert!(matches!(events[..], [GameEvent::ShieldOvercharged { added: 50, energy_spent: OVERCHARGE_ENERGY_COST, .. }]));
+        assert_eq!(state.map.planets[&home].available_resources.energy, energy_before - OVERCHARGE_ENERGY_COST);
+
+        let again = run(&mut state, &format!("planet overcharge {}", home));
+        assert!(matches!(again, Err(CommandError::InvalidArgument { reason, .. }) if reason.contains("already overcharged")));
+
+        end_round(&mut state);
+        assert_eq!(state.map.planets[&home].get_shield_overcharge(), 0);
+        run(&mut state, &format!("planet overcharge {}", home)).unwrap();
+    }
+
+    #[test]
+    fn test_overcharge_needs_energy() {
+        let mut state = fixture_game(1);
+        let home = home_with_shipyard(&mut state);
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.complete_build_structure(String::from("defense_shield"), &state.structure_config).unwrap();
+        planet.available_resources.energy = OVERCHARGE_ENERGY_COST - 1;
+
+        let result = run(&mut state, &format!("planet overcharge {}", home));
+
+        assert!(matches!(result, Err(CommandError::NotEnoughResources { .. })));
+    }
+
+    #[test]
+    fn test_power_refuses_the_capital_and_the_shield_under_bombardment() {
+        let mut state = fixture_game(1);
+        let home = home_with_shipyard(&mut state);
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.complete_build_structure(String::from("defense_shield"), &state.structure_config).unwrap();
+        state.players.get_mut("bob").unwrap().pending_actions.push(PendingAction::new(
+            ActionType::BombardPlanet(String::from("fleet_1"), home.clone()),
+            home.clone(),
+            0,
+            Resources::default(),
+        ));
+
+        let capital = run(&mut state, &format!("planet power {} planetary_capital off", home));
+        let shield = run(&mut state, &format!("planet power {} defense_shield off", home));
+
+        assert!(matches!(capital, Err(CommandError::InvalidArgument { reason, .. }) if reason.contains("capital")));
+        assert!(matches!(shield, Err(CommandError::InvalidArgument { reason, .. }) if reason.contains("bombarded")));
+        assert!(state.map.planets[&home].get_structures()["defense_shield"].is_powered());
+    }
+
+    #[test]
+    fn test_powered_down_structure_stays_off_through_production() {
+        let mut state = fixture_game(1);
+        let home = home_with_shipyard(&mut state);
+
+        let events = run(&mut state, &format!("planet power {} orbital_shipyard off", home)).unwrap();
+        assert!(matches!(&events[..], [GameEvent::StructurePowerChanged { on: false, energy: 5, .. }]));
+        assert!(matches!(
+            run(&mut state, &format!("planet power {} orbital_shipyard off", home)),
+            Err(CommandError::InvalidArgument { .. })
+        ));
+
+        run(&mut state, "end_turn").unwrap();
+        state.apply_effect(CommandEffect::EndTurn { player_name: String::from("Bob") }, &String::from("bob")).unwrap();
+
+        let shipyard = &state.map.planets[&home].get_structures()["orbital_shipyard"];
+        assert_eq!(shipyard.power, Power::Off);
+        assert_eq!(shipyard.energy_consumption(), 0);
+    }
+}
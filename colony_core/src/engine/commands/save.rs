@@ -0,0 +1,179 @@
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::parser::Parseable;
+use crate::engine::save::{QUICKSAVE_SLOT, SaveEntry};
+
+pub struct SaveArgs {
+    pub name: String,
+}
+
+impl SaveArgs {
+    /// Arguments for `qs` and `ql`, which always use the quicksave slot.
+    pub fn quick() -> Self {
+        SaveArgs { name: QUICKSAVE_SLOT.to_string() }
+    }
+}
+
+impl Parseable for SaveArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        match args.as_slice() {
+            [name] => Ok(SaveArgs { name: name.to_string() }),
+            _ => Err(CommandError::MissingArguments {
+                command: String::from("save"),
+                expected: String::from("save|load <name>"),
+            }),
+        }
+    }
+}
+
+pub fn execute_save(args: SaveArgs) -> Result<CommandEffect, CommandError> {
+    Ok(CommandEffect::Save { name: args.name })
+}
+
+pub fn execute_load(args: SaveArgs) -> Result<CommandEffect, CommandError> {
+    Ok(CommandEffect::Load { name: args.name })
+}
+
+pub enum SavesAction {
+    List,
+    Delete { name: String },
+}
+
+pub struct SavesArgs {
+    pub action: SavesAction,
+}
+
+impl Parseable for SavesArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        let action = match args.as_slice() {
+            [] => SavesAction::List,
+            ["delete", name] => SavesAction::Delete { name: name.to_string() },
+            ["delete", ..] => {
+                return Err(CommandError::MissingArguments {
+                    command: String::from("saves delete"),
+                    expected: String::from("saves delete <name>"),
+                });
+            }
+            [other, ..] => {
+                return Err(CommandError::InvalidArgument {
+                    command: String::from("saves"),
+                    argument: other.to_string(),
+                    reason: String::from("expected 'delete' or no argument"),
+                });
+            }
+        };
+        Ok(SavesArgs { action })
+    }
+}
+
+pub fn execute_saves(args: SavesArgs) -> Result<CommandEffect, CommandError> {
+    Ok(match args.action {
+        SavesAction::List => CommandEffect::ListSaves,
+        SavesAction::Delete { name } => CommandEffect::DeleteSave { name },
+    })
+}
+
+/// Formats the save directory listing; `now` is used to show how long ago each save was made.
+pub fn format_save_list(entries: &[SaveEntry], now: u64) -> String {
+    let mut msg = format!("=== Saves ({}) ===\n", entries.len());
+    if entries.is_empty() {
+        msg.push_str("  (no saves)\n");
+    }
+
+    for entry in entries {
+        match &entry.header {
+            Ok(header) => {
+                msg.push_str(&format!(
+                    "  {:<16} turn {:<4} {:<28} {:<9} {}\n",
+                    header.name,
+                    header.turn,
+                    header.player_names.join(", "),
+                    format_age(now.saturating_sub(entry.timestamp)),
+                    format_size(entry.size),
+                ));
+                if let Some(summary) = &header.summary {
+                    msg.push_str(&format!(
+                        "  {:<16} {} map ({} planets), {} rules\n",
+                        "", summary.map_size, summary.planets, summary.rules,
+                    ));
+                }
+                if let Some(sizes) = &header.sizes {
+                    msg.push_str(&format!(
+                        "  {:<16} log {}, battles {}, intel {}, history {}\n",
+                        "",
+                        format_size(sizes.action_log),
+                        format_size(sizes.battles),
+                        format_size(sizes.intel),
+                        format_size(sizes.turn_history),
+                    ));
+                }
+            }
+            Err(reason) => msg.push_str(&format!("  {:<16} [unreadable: {}]\n", entry.file_name, reason)),
+        }
+    }
+
+    msg
+}
+
+fn format_age(seconds: u64) -> String {
+    match seconds {
+        0..60 => String::from("just now"),
+        60..3600 => format!("{}m ago", seconds / 60),
+        3600..86400 => format!("{}h ago", seconds / 3600),
+        _ => format!("{}d ago", seconds / 86400),
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    match bytes {
+        0..1024 => format!("{} B", bytes),
+        1024..1_048_576 => format!("{} KB", bytes / 1024),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::save::{SaveHeader, SaveSizes};
+
+    #[test]
+    fn test_format_save_list_flags_unreadable_files() {
+        let entries = vec![
+            SaveEntry {
+                file_name: String::from("campaign.json"),
+                header: Ok(SaveHeader {
+                    name: String::from("campaign"),
+                    turn: 12,
+                    player_names: vec![String::from("Alice"), String::from("Bob")],
+                    saved_at: 1_000,
+                    sizes: Some(SaveSizes { action_log: 3_000, battles: 800, intel: 0, turn_history: 2_500_000 }),
+                    summary: None,
+                }),
+                size: 40_960,
+                timestamp: 1_000,
+            },
+            SaveEntry {
+                file_name: String::from("broken.json"),
+                size: 12,
+                header: Err(String::from("bad json")),
+                timestamp: 500,
+            },
+        ];
+
+        let text = format_save_list(&entries, 1_000 + 7_200);
+
+        assert!(text.contains("campaign"));
+        assert!(text.contains("turn 12"));
+        assert!(text.contains("Alice, Bob"));
+        assert!(text.contains("2h ago"));
+        assert!(text.contains("40 KB"));
+        assert!(text.contains("log 2 KB, battles 800 B, intel 0 B, history 2.4 MB"));
+        assert!(text.contains("broken.json") && text.contains("[unreadable: bad json]"));
+    }
+
+    #[test]
+    fn test_saves_delete_requires_name() {
+        assert!(matches!(SavesArgs::parse(vec!["delete"]), Err(CommandError::MissingArguments { .. })));
+        assert!(matches!(SavesArgs::parse(vec!["purge"]), Err(CommandError::InvalidArgument { .. })));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::game_state::{GameState, ScheduledItem};
+
+/// Lists everything the current player has underway, merged across planets and fleets and
+/// grouped by the turn it is done by.
+pub fn execute(game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+    let schedule = game_state.completion_schedule(&player.id);
+
+    if schedule.is_empty() {
+        return Ok(CommandEffect::None {
+            message: String::from("Nothing underway. Queue builds with 'build', 'build_ship' or 'upgrade', or send fleets with 'fleet move'."),
+        });
+    }
+
+    let mut msg = String::from("=== Schedule ===\n");
+    for (turn, items) in &schedule {
+        let items: Vec<String> = items.iter().map(|item| describe(item, game_state)).collect();
+        msg.push_str(&format!("Turn {}: {}.\n", turn, items.join("; ")));
+    }
+    Ok(CommandEffect::None { message: msg })
+}
+
+/// One scheduled item as a clause, such as "power_plant completes on Kepler VII".
+pub fn describe(item: &ScheduledItem, game_state: &GameState) -> String {
+    let planet_name = game_state.map.planets.get(item.planet_id())
+        .map_or(item.planet_id().as_str(), |planet| planet.name.as_str());
+    match item {
+        ScheduledItem::Structure { structure_id, level: 1, .. } => format!("{} completes on {}", structure_id, planet_name),
+        ScheduledItem::Structure { structure_id, level, .. } => {
+            format!("{} Lv{} completes on {}", structure_id, level, planet_name)
+        }
+        ScheduledItem::Ship { ship_id, .. } => format!("{} completes on {}", ship_id, planet_name),
+        ScheduledItem::Retrofit { ship_id, ship_type, .. } => {
+            format!("{} refit into {} completes on {}", ship_id, ship_type, planet_name)
+        }
+        ScheduledItem::FleetArrival { fleet_id, .. } => format!("{} arrives at {}", fleet_id, planet_name),
+        ScheduledItem::ShipArrival { ship_id, .. } => format!("{} arrives at {}", ship_id, planet_name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::parser;
+    use crate::engine::pending_action::{ActionType, PendingAction};
+    use crate::engine::resources::Resources;
+    use crate::test_support::{self, fixture_game};
+
+    fn schedule_message(state: &GameState) -> String {
+        match parser::parse("schedule").unwrap().execute(state).unwrap() {
+            CommandEffect::None { message } => message,
+            _ => panic!("Schedule only displays"),
+        }
+    }
+
+    #[test]
+    fn test_schedule_merges_planets_into_one_timeline() {
+        let mut state = fixture_game(1);
+        assert!(schedule_message(&state).starts_with("Nothing underway"));
+
+        let home = test_support::home_planet(&state, "alice");
+        let colony = test_support::grant_planet(&mut state, "alice");
+        let queue = [
+            (ActionType::BuildStructure(String::from("power_grid")), &home, 2),
+            (ActionType::BuildShip(String::from("scout"), String::from("scout_4")), &colony, 2),
+            (ActionType::MoveFleet(String::from("fleet_2"), colony.clone()), &home, 3),
+        ];
+        for (action_type, planet_id, cooldown) in queue {
+            state.players.get_mut("alice").unwrap().pending_actions.push(
+                PendingAction::new(action_type, planet_id.clone(), cooldown, Resources::default()),
+            );
+        }
+
+        let (home_name, colony_name) = (&state.map.planets[&home].name, &state.map.planets[&colony].name);
+        assert_eq!(schedule_message(&state), format!(
+            "=== Schedule ===\n\
+             Turn 3: power_grid completes on {home_name}; scout_4 completes on {colony_name}.\n\
+             Turn 4: fleet_2 arrives at {colony_name}.\n"
+        ));
+    }
+
+    #[test]
+    fn test_describe_names_levels_refits_and_arrivals() {
+        let state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let name = state.map.planets[&home].name.clone();
+        let describe = |item: ScheduledItem| describe(&item, &state);
+
+        let upgrade = ScheduledItem::Structure { structure_id: String::from("mining_complex"), level: 3, planet_id: home.clone() };
+        assert_eq!(describe(upgrade), format!("mining_complex Lv3 completes on {}", name));
+        let refit = ScheduledItem::Retrofit { ship_id: String::from("scout_2"), ship_type: String::from("ravager"), planet_id: home.clone() };
+        assert_eq!(describe(refit), format!("scout_2 refit into ravager completes on {}", name));
+        let arrival = ScheduledItem::FleetArrival { fleet_id: String::from("fleet_2"), destination: home.clone() };
+        assert_eq!(describe(arrival), format!("fleet_2 arrives at {}", name));
+        let lone = ScheduledItem::ShipArrival { ship_id: String::from("scout_1"), destination: home };
+        assert_eq!(describe(lone), format!("scout_1 arrives at {}", name));
+    }
+}
@@ -0,0 +1,47 @@
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::parser::Parseable;
+use crate::engine::map::RenderStyle;
+use crate::engine::options::SessionOption;
+
+/// Session settings; they belong to the frontend, not the saved game.
+#[derive(Debug, PartialEq)]
+pub enum SetArgs {
+    Style(RenderStyle),
+}
+
+impl Parseable for SetArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        match args.as_slice() {
+            ["style", "unicode"] => Ok(SetArgs::Style(RenderStyle::Unicode)),
+            ["style", "ascii"] => Ok(SetArgs::Style(RenderStyle::AsciiOnly)),
+            ["style", style] => Err(CommandError::InvalidArgument {
+                command: String::from("set style"),
+                argument: style.to_string(),
+                reason: String::from("valid styles are: unicode, ascii"),
+            }),
+            _ => Err(CommandError::MissingArguments {
+                command: String::from("set"),
+                expected: String::from("set style <unicode|ascii>"),
+            }),
+        }
+    }
+}
+
+pub fn execute(args: SetArgs) -> Result<CommandEffect, CommandError> {
+    match args {
+        SetArgs::Style(style) => Ok(CommandEffect::SetSessionOption { option: SessionOption::Style(style) }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_style() {
+        assert_eq!(SetArgs::parse(vec!["style", "ascii"]).unwrap(), SetArgs::Style(RenderStyle::AsciiOnly));
+        assert_eq!(SetArgs::parse(vec!["style", "unicode"]).unwrap(), SetArgs::Style(RenderStyle::Unicode));
+        assert!(matches!(SetArgs::parse(vec!["style", "fancy"]), Err(CommandError::InvalidArgument { .. })));
+        assert!(matches!(SetArgs::parse(vec!["style"]), Err(CommandError::MissingArguments { .. })));
+    }
+}
@@ -0,0 +1,330 @@
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::fleet::rejected;
+use crate::engine::commands::parser::Parseable;
+use crate::engine::configs::ship_config::ShipId;
+use crate::engine::game_state::GameState;
+use crate::engine::planet::PlanetId;
+use crate::engine::rules;
+use crate::engine::ship::ShipInstanceId;
+use crate::engine::utils;
+
+#[derive(Debug)]
+pub enum ShipAction {
+    Retrofit { ship_id: ShipInstanceId, target_type: ShipId },
+    Move { ship_id: ShipInstanceId, target_planet: PlanetId },
+}
+
+#[derive(Debug)]
+pub struct ShipArgs {
+    pub action: ShipAction,
+}
+
+impl Parseable for ShipArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::MissingArguments {
+                command: String::from("ship"),
+                expected: String::from("ship <retrofit|move> ..."),
+            });
+        }
+
+        let action = match args[0] {
+            "retrofit" => {
+                let [ship_id, target_type] = args[1..] else {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("ship retrofit"),
+                        expected: String::from("ship retrofit <ship_id> <target_ship_type>"),
+                    });
+                };
+                ShipAction::Retrofit { ship_id: ship_id.to_string(), target_type: utils::name_to_id(target_type) }
+            }
+            "move" => {
+                let [ship_id, target_planet] = args[1..] else {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("ship move"),
+                        expected: String::from("ship move <ship_id> <target_planet>"),
+                    });
+                };
+                ShipAction::Move { ship_id: ship_id.to_string(), target_planet: target_planet.to_string() }
+            }
+            _ => {
+                return Err(CommandError::InvalidArgument {
+                    command: String::from("ship"),
+                    argument: args[0].to_string(),
+                    reason: String::from("valid actions are: retrofit, move"),
+                });
+            }
+        };
+
+        Ok(ShipArgs { action })
+    }
+}
+
+pub fn execute(args: ShipArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    match args.action {
+        ShipAction::Retrofit { ship_id, target_type } => validate_retrofit(&ship_id, &target_type, game_state),
+        ShipAction::Move { ship_id, target_planet } => validate_move(&ship_id, &target_planet, game_state),
+    }
+}
+
+/// A ship outside any fleet can travel on its own, under the same rules as a fleet move.
+fn validate_move(
+    ship_id: &ShipInstanceId,
+    target_planet: &PlanetId,
+    game_state: &GameState,
+) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+
+    if let Some(action) = player.find_ship_under_construction(ship_id) {
+        return Err(CommandError::ShipUnderConstruction {
+            ship_id: ship_id.clone(),
+            turns: action.cooldown_remaining,
+        });
+    }
+
+    let ship = player.ships.get(ship_id)
+        .ok_or_else(|| CommandError::ship_not_found("ship move", ship_id, game_state))?;
+    let distance = rules::can_move_ship(ship, target_planet, player, game_state)
+        .map_err(|reason| rejected("ship move", ship_id, reason))?;
+
+    Ok(CommandEffect::MoveShip {
+        ship_id: ship_id.clone(),
+        target_planet: target_planet.clone(),
+        distance,
+    })
+}
+
+/// A ship can be refitted into another type at one of its owner's planets, provided the
+/// shipyard there could build the target type. The cost is what the target costs beyond
+/// the current type.
+fn validate_retrofit(
+    ship_id: &ShipInstanceId,
+    target_type: &ShipId,
+    game_state: &GameState,
+) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+
+    if let Some(action) = player.find_ship_under_construction(ship_id) {
+        return Err(CommandError::ShipUnderConstruction {
+            ship_id: ship_id.clone(),
+            turns: action.cooldown_remaining,
+        });
+    }
+
+    let invalid = |reason: String| CommandError::InvalidArgument {
+        command: String::from("ship retrofit"),
+        argument: ship_id.clone(),
+        reason,
+    };
+
+    let ship = player.ships.get(ship_id)
+        .ok_or_else(|| CommandError::ship_not_found("ship retrofit", ship_id, game_state))?;
+    let target_def = game_state.ship_config.get(target_type)
+        .ok_or_else(|| CommandError::UnknownShip(target_type.clone()))?;
+    let planet = game_state.map.planets.get(&ship.location)
+        .expect("Ships are always at a known planet");
+
+    let reason = if &ship.ship_type == target_type {
+        Some(format!("ship is already a {}", target_type))
+    } else if planet.get_owner().as_ref() != Some(game_state.current_player()) {
+        Some(format!("ship must be at one of your planets, it is at {}", ship.location))
+    } else if let Some(action) = player.find_ship_in_retrofit(ship_id) {
+        Some(format!("ship is already being retrofitted, {} turn(s) remaining", action.cooldown_remaining))
+    } else if ship.fleet_id.as_ref().is_some_and(|fleet_id| player.has_pending_fleet_move(fleet_id)) {
+        Some(String::from("ship's fleet has a pending move"))
+    } else if player.has_pending_ship_move(ship_id) {
+        Some(String::from("ship has a pending move"))
+    } else {
+        None
+    };
+    if let Some(reason) = reason {
+        return Err(invalid(reason));
+    }
+
+    let shipyard_level = planet.get_structure_level(&String::from("orbital_shipyard"));
+    if shipyard_level < target_def.required_shipyard_level {
+        return Err(CommandError::ShipyardLevelTooLow {
+            required: target_def.required_shipyard_level,
+            current: shipyard_level,
+        });
+    }
+
+    let cost = game_state.retrofit_cost(&ship.ship_type, target_type);
+    if !planet.available_resources.has_enough(&cost) {
+        return Err(CommandError::NotEnoughResources {
+            planet_name: planet.name.clone(),
+            cost,
+        });
+    }
+
+    Ok(CommandEffect::RetrofitShip {
+        planet_id: ship.location.clone(),
+        ship_id: ship_id.clone(),
+        target_type: target_type.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::game_event::GameEvent;
+    use crate::engine::planet::PlanetId;
+    use crate::engine::resources::Resources;
+    use crate::test_support::{self, end_round, fixture_game, run};
+
+    /// Gives alice's home planet a shipyard of `level` and full storage.
+    fn home_with_shipyard(state: &mut GameState, level: u16) -> PlanetId {
+        let home = test_support::home_planet(state, "alice");
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.complete_build_structure(String::from("power_grid"), &state.structure_config).unwrap();
+        planet.complete_build_structure(String::from("orbital_shipyard"), &state.structure_config).unwrap();
+        for _ in 1..level {
+            planet.complete_upgrade_structure(&String::from("orbital_shipyard")).unwrap();
+        }
+        planet.recalculate_from_structures();
+        planet.available_resources = planet.storage_capacity.clone();
+        home
+    }
+
+    #[test]
+    fn test_retrofit_keeps_fleeted_ship_and_updates_fleet_stats() {
+        let mut state = fixture_game(1);
+        let home = home_with_shipyard(&mut state, 2);
+        let alice = state.players.get_mut("alice").unwrap();
+        alice.add_ship(String::from("interceptor"), home.clone());
+        alice.add_ship(String::from("interceptor"), home.clone());
+        run(&mut state, "fleet create strike interceptor_1 interceptor_2").unwrap();
+        let fleet_id = String::from("fleet_1");
+        assert_eq!(state.calculate_fleet_bombardment(&String::from("alice"), &fleet_id), 0);
+        let minerals_before = state.map.planets[&home].available_resources.minerals;
+
+        let events = run(&mut state, "ship retrofit interceptor_1 ravager").unwrap();
+
+        // A ravager costs 200/100 against the interceptor's 100/50
+        let difference = Resources { minerals: 100, gas: 50, energy: 0 };
+        assert!(matches!(&events[..], [GameEvent::ShipRetrofitQueued { cost, turns: 2, .. }] if *cost == difference));
+        assert_eq!(state.map.planets[&home].available_resources.minerals, minerals_before - 100);
+
+        end_round(&mut state);
+        assert_eq!(state.players["alice"].ships["interceptor_1"].ship_type, "interceptor");
+        let events = end_round(&mut state);
+
+        assert!(events.iter().any(|event| matches!(event, GameEvent::ShipRetrofitted { ship_instance_id, .. } if ship_instance_id == "interceptor_1")));
+        let alice = &state.players["alice"];
+        assert_eq!(alice.ships["interceptor_1"].ship_type, "ravager");
+        assert_eq!(alice.ships["interceptor_1"].fleet_id.as_deref(), Some("fleet_1"));
+        assert_eq!(alice.fleets[&fleet_id].ships, ["interceptor_1", "interceptor_2"]);
+        assert_eq!(state.calculate_fleet_bombardment(&String::from("alice"), &fleet_id), 25);
+    }
+
+    #[test]
+    fn test_retrofit_to_cheaper_type_is_free() {
+        let mut state = fixture_game(1);
+        let home = home_with_shipyard(&mut state, 2);
+        state.players.get_mut("alice").unwrap().add_ship(String::from("ravager"), home.clone());
+        let resources_before = state.map.planets[&home].available_resources.clone();
+
+        let events = run(&mut state, "ship retrofit ravager_1 scout").unwrap();
+
+        assert!(matches!(&events[..], [GameEvent::ShipRetrofitQueued { cost, .. }] if *cost == Resources::default()));
+        assert_eq!(state.map.planets[&home].available_resources, resources_before);
+    }
+
+    #[test]
+    fn test_retrofit_requires_owned_planet_and_shipyard_level() {
+        let mut state = fixture_game(1);
+        let home = home_with_shipyard(&mut state, 1);
+        let neighbour = state.map.planets[&home].get_connections()[0].to().clone();
+        let alice = state.players.get_mut("alice").unwrap();
+        alice.add_ship(String::from("interceptor"), home.clone());
+        alice.add_ship(String::from("interceptor"), neighbour);
+
+        let too_low = run(&mut state, "ship retrofit interceptor_1 ravager");
+        assert!(matches!(too_low, Err(CommandError::ShipyardLevelTooLow { required: 2, current: 1 })));
+
+        let away = run(&mut state, "ship retrofit interceptor_2 scout");
+        assert!(matches!(away, Err(CommandError::InvalidArgument { reason, .. }) if reason.contains("your planets")));
+
+        let same = run(&mut state, "ship retrofit interceptor_1 interceptor");
+        assert!(matches!(same, Err(CommandError::InvalidArgument { reason, .. }) if reason.contains("already")));
+
+        run(&mut state, "ship retrofit interceptor_1 scout").unwrap();
+        let twice = run(&mut state, "ship retrofit interceptor_1 scout");
+        assert!(matches!(twice, Err(CommandError::InvalidArgument { reason, .. }) if reason.contains("being retrofitted")));
+    }
+
+    /// Ends rounds until `ship_id` arrives somewhere, returning the arrival's events.
+    fn travel(state: &mut GameState, ship_id: &str) -> Vec<GameEvent> {
+        for _ in 0..10 {
+            let events = end_round(state);
+            if events.iter().any(|event| matches!(event, GameEvent::ShipArrived { ship_instance_id, .. } if ship_instance_id == ship_id)) {
+                return events;
+            }
+        }
+        panic!("{} never arrived", ship_id);
+    }
+
+    /// An unowned neighbour of `planet_id` other than `except`.
+    fn unowned_neighbour(state: &GameState, planet_id: &PlanetId, except: &PlanetId) -> PlanetId {
+        state.map.planets[planet_id].get_connections().iter()
+            .map(|connection| connection.to().clone())
+            .find(|id| id != except && state.map.planets[id].get_owner().is_none())
+            .expect("Fixture map has unowned planets two hops from home")
+    }
+
+    #[test]
+    fn test_lone_ark_moves_two_hops_and_colonizes_on_arrival() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let first = unowned_neighbour(&state, &home, &home);
+        let second = unowned_neighbour(&state, &first, &home);
+        state.players.get_mut("alice").unwrap().add_ship(String::from("ark"), home.clone());
+
+        let events = run(&mut state, &format!("ship move ark_1 {}", first)).unwrap();
+        assert!(matches!(&events[..], [GameEvent::ShipMoveOrdered { ship_instance_id, .. }] if ship_instance_id == "ark_1"));
+        let again = run(&mut state, &format!("ship move ark_1 {}", first));
+        assert!(matches!(again, Err(CommandError::InvalidArgument { reason, .. }) if reason.contains("pending move")));
+        let CommandEffect::None { message } = crate::engine::commands::ships::execute(&state).unwrap() else {
+            panic!("Expected informational effect");
+        };
+        assert!(message.contains("in transit to"));
+
+        travel(&mut state, "ark_1");
+        assert_eq!(state.players["alice"].ships["ark_1"].location, first);
+        run(&mut state, &format!("ship move ark_1 {}", second)).unwrap();
+        travel(&mut state, "ark_1");
+        assert_eq!(state.players["alice"].ships["ark_1"].location, second);
+        assert!(state.players["alice"].explored.contains(&second));
+
+        run(&mut state, "fleet create settlers ark_1").unwrap();
+        let events = run(&mut state, "fleet colonize fleet_1").unwrap();
+        assert!(events.iter().any(|event| matches!(event, GameEvent::PlanetColonized { .. })));
+        assert_eq!(state.map.planets[&second].get_owner().as_deref(), Some("alice"));
+    }
+
+    #[test]
+    fn test_ship_move_rejects_fleeted_garrisoned_and_distant_ships() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let neighbour = state.map.planets[&home].get_connections()[0].to().clone();
+        let bob_home = test_support::home_planet(&state, "bob");
+        let alice = state.players.get_mut("alice").unwrap();
+        for _ in 0..3 {
+            alice.add_ship(String::from("interceptor"), home.clone());
+        }
+        run(&mut state, "fleet create strike interceptor_1").unwrap();
+        run(&mut state, &format!("planet garrison {} interceptor_2", home)).unwrap();
+
+        let fleeted = run(&mut state, &format!("ship move interceptor_1 {}", neighbour));
+        assert!(matches!(fleeted, Err(CommandError::InvalidArgument { reason, .. }) if reason.contains("fleet_1")));
+        let garrisoned = run(&mut state, &format!("ship move interceptor_2 {}", neighbour));
+        assert!(matches!(garrisoned, Err(CommandError::InvalidArgument { reason, .. }) if reason.contains("garrison")));
+        let distant = run(&mut state, &format!("ship move interceptor_3 {}", bob_home));
+        assert!(matches!(distant, Err(CommandError::InvalidArgument { argument, .. }) if argument == bob_home));
+
+        // Once under way the ship cannot join a fleet
+        run(&mut state, &format!("ship move interceptor_3 {}", neighbour)).unwrap();
+        let join = run(&mut state, "fleet add fleet_1 interceptor_3");
+        assert!(matches!(join, Err(CommandError::InvalidArgument { reason, .. }) if reason.contains("pending move")));
+    }
+}
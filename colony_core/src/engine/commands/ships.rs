@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::game_state::GameState;
+use crate::engine::pending_action::ActionType;
+use crate::engine::planet::PlanetId;
+use crate::engine::ship::MovableUnit;
+
+pub fn execute(game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+
+    let mut under_construction: Vec<_> = player.pending_actions.iter()
+        .filter_map(|action| match &action.action_type {
+            ActionType::BuildShip(ship_type, ship_id) => Some((ship_id, ship_type, action)),
+            _ => None,
+        })
+        .collect();
+    under_construction.sort_by(|a, b| a.0.cmp(b.0));
+
+    if player.ships.is_empty() && under_construction.is_empty() {
+        return Ok(CommandEffect::None {
+            message: String::from("No ships in your fleet."),
+        });
+    }
+
+    // Group ships by location, ordered by planet id
+    let mut ships_by_location: BTreeMap<&PlanetId, Vec<&crate::engine::ship::Ship>> = BTreeMap::new();
+    for ship in player.ships.values() {
+        ships_by_location
+            .entry(&ship.location)
+            .or_default()
+            .push(ship);
+    }
+
+    let mut msg = String::from("=== Your Ships ===\n");
+
+    for (planet_id, mut ships) in ships_by_location {
+        ships.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let planet_name = game_state
+            .map
+            .planets
+            .get(planet_id)
+            .map(|p| p.name.as_str())
+            .unwrap_or("Unknown");
+
+        msg.push_str(&format!("\n{} ({}):\n", planet_name, planet_id));
+
+        for ship in ships {
+            let fleet_info = match &ship.fleet_id {
+                Some(fleet_id) => format!(" [Fleet: {}]", fleet_id),
+                None if game_state.is_garrisoned(ship) => String::from(" [Garrison]"),
+                None => String::new(),
+            };
+            let transit_info = player.pending_move(&MovableUnit::Ship(ship.id.clone()))
+                .and_then(|action| action.action_type.movement().map(|(_, destination)| (destination, action)))
+                .map(|(destination, action)| {
+                    let destination_name = game_state.map.planets.get(destination)
+                        .map_or(destination.as_str(), |p| p.name.as_str());
+                    format!(" [in transit to {}, {} turn(s)]", destination_name, action.cooldown_remaining)
+                })
+                .unwrap_or_default();
+            msg.push_str(&format!("  - {} ({}){}{}\n", ship.id, ship.ship_type, fleet_info, transit_info));
+        }
+    }
+
+    if !under_construction.is_empty() {
+        msg.push_str("\nUnder construction:\n");
+        for (ship_id, ship_type, action) in under_construction {
+            let planet_name = game_state
+                .map
+                .planets
+                .get(&action.planet_id)
+                .map(|p| p.name.as_str())
+                .unwrap_or("Unknown");
+            msg.push_str(&format!(
+                "  - {} ({}) at {} ({}), {} turn(s)\n",
+                ship_id, ship_type, planet_name, action.planet_id, action.cooldown_remaining
+            ));
+        }
+    }
+
+    msg.push_str(&format!("\nTotal ships: {}", player.ships.len()));
+
+    Ok(CommandEffect::None { message: msg })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::game_state::GameState;
+    use crate::test_support::{self, fixture_game};
+
+    fn queue_interceptor(state: &mut GameState, planet_id: &PlanetId) {
+        let effect = CommandEffect::BuildShip {
+            planet_id: planet_id.clone(),
+            ship_id: "interceptor".to_string(),
+            deliver_to: None,
+        };
+        state.apply_effect(effect, &"alice".to_string()).unwrap();
+    }
+
+    #[test]
+    fn test_listing_shows_ships_under_construction() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        queue_interceptor(&mut state, &home);
+
+        let CommandEffect::None { message } = execute(&state).unwrap() else {
+            panic!("Expected informational effect");
+        };
+
+        assert!(message.contains("Under construction:"));
+        assert!(message.contains("interceptor_1 (interceptor)"));
+        assert!(message.contains("1 turn(s)"));
+    }
+
+    #[test]
+    fn test_cancelled_build_does_not_reuse_ship_id() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+
+        queue_interceptor(&mut state, &home);
+        state.apply_effect(
+            CommandEffect::CancelAction { planet_id: home.clone(), target_id: "interceptor_1".to_string() },
+            &"alice".to_string(),
+        ).unwrap();
+        queue_interceptor(&mut state, &home);
+
+        let alice = &state.players["alice"];
+        assert!(alice.find_ship_under_construction(&"interceptor_1".to_string()).is_none());
+        assert!(alice.find_ship_under_construction(&"interceptor_2".to_string()).is_some());
+    }
+}
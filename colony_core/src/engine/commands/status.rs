@@ -0,0 +1,1011 @@
+use crate::engine::commands::parser::Parseable;
+use crate::engine::game_state::GameState;
+use std::collections::{HashSet, VecDeque};
+
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::fleets;
+use crate::engine::configs::ship_config::fmt_ship;
+use crate::engine::configs::structure_config::fmt_structure;
+use crate::engine::fleet::Fleet;
+use crate::engine::game_rules::MAX_UNREST;
+use crate::engine::game_state::{RoundTimings, ShieldEstimate};
+use crate::engine::map::Fogged;
+use crate::engine::pending_action::ActionType;
+use crate::engine::player::{Player, PlayerId};
+use crate::engine::planet::{Planet, PlanetId};
+use crate::engine::rules;
+use crate::engine::ship::FleetId;
+use crate::engine::structure::{Power, StructureId, StructureState};
+
+/// How many jumps away from a fleet `status fleet` still lists destinations.
+const DESTINATION_MAX_HOPS: usize = 4;
+
+pub enum StatusTarget {
+    Game,
+    Turn,
+    Planets,
+    Planet { id: PlanetId },
+    Fleet { id: FleetId },
+    Player,
+    /// Processing timings, for developers
+    Perf,
+}
+
+pub struct StatusArgs {
+    pub target: StatusTarget,
+    /// Seconds since the Unix epoch, set by frontends with a clock to show how long the game has run
+    pub now: Option<u64>,
+}
+
+impl Parseable for StatusArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        if args.is_empty() {
+            return Err(CommandError::MissingArguments {
+                command: String::from("status"),
+                expected: String::from("status <game|turn|planets|planet <id>|fleet <id>|player>"),
+            });
+        }
+
+        let target = match args[0] {
+            "game" => StatusTarget::Game,
+            "turn" => StatusTarget::Turn,
+            "planets" => StatusTarget::Planets,
+            "planet" => {
+                if args.len() < 2 {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("status"),
+                        expected: String::from("status planet <planet_id>"),
+                    });
+                }
+                StatusTarget::Planet { id: args[1].to_string() }
+            }
+            "fleet" => {
+                if args.len() < 2 {
+                    return Err(CommandError::MissingArguments {
+                        command: String::from("status"),
+                        expected: String::from("status fleet <fleet_id>"),
+                    });
+                }
+                StatusTarget::Fleet { id: args[1].to_string() }
+            }
+            "player" => StatusTarget::Player,
+            "perf" => StatusTarget::Perf,
+            _ => return Err(CommandError::InvalidArgument {
+                command: String::from("status"),
+                argument: args[0].to_string(),
+                reason: String::from("valid targets are: game, turn, planets, planet <id>, fleet <id>, player"),
+            }),
+        };
+
+        Ok(StatusArgs { target, now: None })
+    }
+}
+
+pub fn execute(args: StatusArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let message = match args.target {
+        StatusTarget::Game => format_game(game_state, args.now),
+        StatusTarget::Turn => format_turn(game_state),
+        StatusTarget::Planets => format_planets_list(game_state),
+        StatusTarget::Planet { id } => format_planet_detail(&id, game_state)?,
+        StatusTarget::Fleet { id } => format_fleet_detail(&id, game_state)?,
+        StatusTarget::Player => format_player_status(game_state),
+        StatusTarget::Perf => format_perf(&game_state.timings),
+    };
+
+    Ok(CommandEffect::None { message })
+}
+
+fn format_turn(game_state: &GameState) -> String {
+    let mut msg = format!("Current turn: {} ({} speed)", game_state.turn, game_state.rules.speed);
+    let waiting = game_state.player_names(game_state.players_waiting());
+    if let Some(playing) = waiting.first() {
+        let acted = game_state.player_names(game_state.players_acted());
+        let names = |names: &[String]| if names.is_empty() { String::from("nobody yet") } else { names.join(", ") };
+        msg.push_str(&format!("\nNow playing: {}", playing));
+        msg.push_str(&format!("\nActed this round: {}", names(&acted)));
+        msg.push_str(&format!("\nStill to act: {}", names(&waiting)));
+    }
+    let turns_left = game_state.protection_turns_left();
+    if turns_left > 0 {
+        msg.push_str(&format!(
+            "\nProtected expansion: hostilities may begin on turn {} ({} turn(s) left)",
+            game_state.turn + turns_left, turns_left,
+        ));
+    }
+    msg
+}
+
+fn format_game(game_state: &GameState, now: Option<u64>) -> String {
+    let summary = game_state.game_summary();
+    let mut msg = String::from("=== Game ===\n");
+    msg.push_str(&format!("Map: {} ({} planets)\n", summary.map_size, summary.planets));
+    msg.push_str(&format!(
+        "Commanders: {} ({} human, {} AI)\n",
+        summary.players.len(), summary.humans(), summary.ai_players(),
+    ));
+    for player in &summary.players {
+        let ai = if player.ai { " [AI]" } else { "" };
+        msg.push_str(&format!("  {}{} - {} planet(s)\n", player.name, ai, player.planets));
+    }
+    msg.push_str(&format!("Turn: {}\n", summary.turn));
+    msg.push_str(&format!("Rules: {}\n", summary.rules));
+    match summary.seed {
+        Some(seed) => msg.push_str(&format!("Seed: {}\n", seed)),
+        None => msg.push_str("Seed: hidden until the game is over ('options set seed on' shows it)\n"),
+    }
+    if let (Some(started_at), Some(now)) = (summary.started_at, now) {
+        msg.push_str(&format!("Running for: {}\n", format_elapsed(now.saturating_sub(started_at))));
+    }
+    msg
+}
+
+/// A duration as hours and minutes, such as `2h 05m`, or just minutes under an hour.
+fn format_elapsed(seconds: u64) -> String {
+    let minutes = seconds / 60;
+    match minutes / 60 {
+        0 => format!("{}m", minutes),
+        hours => format!("{}h {:02}m", hours, minutes % 60),
+    }
+}
+
+/// Time spent in each processing phase in the last round and in all rounds so far, with what
+/// was processed.
+fn format_perf(timings: &RoundTimings) -> String {
+    if timings.rounds == 0 {
+        return String::from("No rounds timed yet; rounds are timed while the game runs with a clock.");
+    }
+    let ms = |micros: u64| format!("{}.{:02} ms", micros / 1000, micros % 1000 / 10);
+    let (last, all) = (&timings.last_round, &timings.cumulative);
+
+    let mut msg = format!("=== Round Timings ({} round(s)) ===\n", timings.rounds);
+    msg.push_str(&format!("  {:<12} {:>12} {:>12}\n", "phase", "last round", "all rounds"));
+    for (phase, micros) in &all.micros {
+        let last_micros = last.micros.get(phase).copied().unwrap_or(0);
+        msg.push_str(&format!("  {:<12} {:>12} {:>12}\n", phase.to_string(), ms(last_micros), ms(*micros)));
+    }
+    msg.push_str(&format!("  {:<12} {:>12} {:>12}\n", "total", ms(last.total_micros()), ms(all.total_micros())));
+    msg.push_str(&format!("Planets processed: {} last round, {} in all\n", last.planets_processed, all.planets_processed));
+    msg.push_str(&format!("Battles resolved: {} last round, {} in all\n", last.battles_resolved, all.battles_resolved));
+    msg
+}
+
+fn format_planets_list(game_state: &GameState) -> String {
+    let presence = game_state.planet_presence(game_state.current_player());
+
+    let mut msg = String::from("=== Planets ===\n");
+    for planet in game_state.map.planets.values() {
+        let owner = match presence.get(&planet.id).and_then(|presence| presence.fogged.as_ref()) {
+            None => owner_name(planet.get_owner().as_ref(), game_state),
+            Some(Fogged::LastSeen { turn, owner }) => format!("{} (as of turn {})", owner_name(owner.as_ref(), game_state), turn),
+            Some(Fogged::Unexplored) => String::from("unexplored"),
+        };
+        msg.push_str(&format!("{} ({}) - {}\n", planet.name, planet.id, owner));
+    }
+    msg
+}
+
+/// Owner's name with their tag, e.g. `Alice [AL]`, so it matches the map labels.
+fn owner_name(owner: Option<&PlayerId>, game_state: &GameState) -> String {
+    let Some(owner) = owner else {
+        return String::from("uncolonized");
+    };
+    match (game_state.players.get(owner), game_state.appearance(owner)) {
+        (Some(player), Some(appearance)) => format!("{} [{}]", player.name, appearance.tag),
+        _ => owner.clone(),
+    }
+}
+
+fn format_planet_detail(planet_id: &str, game_state: &GameState) -> Result<String, CommandError> {
+    let planet = game_state.map.planets.get(planet_id)
+        .ok_or_else(|| CommandError::UnknownPlanet(planet_id.to_string()))?;
+
+    if let Some(fogged) = game_state.fogged_view(game_state.current_player(), &planet.id) {
+        return Ok(format_fogged_planet(planet, &fogged, game_state));
+    }
+
+    let owner = owner_name(planet.get_owner().as_ref(), game_state);
+
+    let mut msg = format!("=== {} ({}) ===\n", planet.name, planet.id);
+    msg.push_str(&format!("Owner: {}\n", owner));
+    if planet.is_key_planet() {
+        msg.push_str("Key planet: counts towards a domination victory\n");
+    }
+    if let Some(turn) = planet.get_colonized_turn() {
+        msg.push_str(&format!("Colonized: turn {}\n", turn));
+    }
+    if planet.get_consolidation_turns() > 0 {
+        msg.push_str(&format!(
+            "Consolidating: cannot be colonized or invaded for {} more turn(s)\n",
+            planet.get_consolidation_turns()
+        ));
+    }
+    if let Some(ruin) = planet.get_ruin() {
+        msg.push_str(&format!("Ruins: {}, claimed by colonizing\n", ruin.describe(&game_state.structure_config)));
+    }
+    if planet.get_unrest() > 0 || planet.is_on_strike() {
+        msg.push_str(&format_unrest(planet, game_state));
+    }
+    if game_state.is_blockaded(&planet.id) {
+        msg.push_str("Blockaded: hostile fleets in orbit stir up unrest every turn\n");
+    }
+
+    // Resources
+    msg.push_str("\nRESOURCES\n");
+    msg.push_str(&format!("  Available: {}\n", planet.available_resources));
+    msg.push_str(&format!("  Capacity:  {}\n", planet.storage_capacity));
+    msg.push_str(&format!("  Production: {} per turn\n", planet.get_production_rate()));
+    if let Some(allocation) = planet.get_allocation() {
+        msg.push_str(&format!(
+            "  Allocation: {}, yielding {} per turn\n",
+            allocation,
+            planet.allocated_production(game_state.rules.allocation_shift_percent)
+        ));
+    }
+    let energy_use = planet.get_energy_consumption();
+    msg.push_str(&format!(
+        "  Energy use: {} per turn (balance {:+})\n",
+        energy_use,
+        i64::from(planet.get_production_rate().energy) - i64::from(energy_use)
+    ));
+
+    // Shields (if planet has defense shield structure)
+    let max_shield = planet.get_max_shield_hp();
+    if max_shield > 0 {
+        let overcharge = planet.get_shield_overcharge();
+        let current_shield = planet.get_shield_hp() - overcharge;
+        let shield_status = if current_shield == 0 {
+            String::from("DESTROYED")
+        } else if current_shield < max_shield {
+            String::from("DAMAGED")
+        } else {
+            String::from("FULL")
+        };
+        msg.push_str("\nSHIELDS\n");
+        msg.push_str(&format!("  {} / {} HP ({})\n", current_shield, max_shield, shield_status));
+        if overcharge > 0 {
+            msg.push_str(&format!("  Overcharged: +{} HP until the end of the turn\n", overcharge));
+        }
+    }
+
+    let viewer = CommandError::acting_player(game_state)?;
+    if let Some(bombardment) = format_bombardment(planet, viewer, game_state) {
+        msg.push_str(&bombardment);
+    }
+
+    // Garrison (ships stationed planet-side by the owner)
+    let garrison = planet.get_garrison();
+    if !garrison.is_empty() {
+        let owner_ships = planet.get_owner().as_ref()
+            .and_then(|owner| game_state.players.get(owner))
+            .map(|player| &player.ships);
+        msg.push_str("\nGARRISON\n");
+        for ship_id in garrison {
+            match owner_ships.and_then(|ships| ships.get(ship_id)) {
+                Some(ship) => msg.push_str(&format!("  {}: {}\n", ship_id, fmt_ship(&game_state.ship_config, &ship.ship_type))),
+                None => msg.push_str(&format!("  {}\n", ship_id)),
+            }
+        }
+    }
+
+    // Structures
+    let structures = planet.get_structures();
+    if structures.is_empty() {
+        msg.push_str("\nSTRUCTURES\n  (none)\n");
+    } else {
+        msg.push_str("\nSTRUCTURES\n");
+        let mut structure_list: Vec<_> = structures.iter().collect();
+        structure_list.sort_by_key(|(id, _)| id.as_str());
+
+        for (id, structure) in structure_list {
+            let state_info = match &structure.state {
+                crate::engine::structure::StructureState::UnderConstruction => String::from(" (UNDER CONSTRUCTION)"),
+                crate::engine::structure::StructureState::Operational => String::new(),
+                crate::engine::structure::StructureState::Upgrading { turns_remaining, target_level } => {
+                    format!(" (upgrading to Lv{}, {} turns)", target_level, turns_remaining)
+                }
+                crate::engine::structure::StructureState::Damaged => String::from(" (DAMAGED)"),
+                crate::engine::structure::StructureState::Unsupported { prerequisite, required_level } => {
+                    format!(
+                        " (UNSUPPORTED: needs {} Lv{}, producing nothing)",
+                        fmt_structure(&game_state.structure_config, prerequisite), required_level
+                    )
+                }
+            };
+            let power_info = match structure.power {
+                Power::On if structure.energy_consumption() > 0 => {
+                    format!(", {} energy", structure.energy_consumption())
+                }
+                Power::On => String::new(),
+                Power::Off => String::from(" (POWERED DOWN)"),
+                Power::Starved => String::from(" (SHUT DOWN: energy deficit)"),
+            };
+            msg.push_str(&format!(
+                "  {} ({}): Lv{}/{}{}{}\n",
+                structure.name, id, structure.level, structure.max_level, state_info, power_info
+            ));
+        }
+    }
+
+    // Available structures (if owned by current player)
+    let current_player_id = game_state.current_player();
+    if planet.get_owner().as_ref() == Some(current_player_id) {
+        let buildable = planet.get_buildable_structures(&game_state.structure_config, game_state.rules.prerequisites_under_construction);
+
+        if !buildable.can_build_now.is_empty() || !buildable.locked.is_empty() {
+            msg.push_str("\nAVAILABLE STRUCTURES\n");
+
+            // Show structures that can be built now
+            if !buildable.can_build_now.is_empty() {
+                msg.push_str("  Can Build Now:\n");
+                for structure_info in buildable.can_build_now {
+                    let affordability = if structure_info.can_afford {
+                        format!("Cost: {}", structure_info.cost)
+                    } else {
+                        format!("Cost: {} (insufficient resources)", structure_info.cost)
+                    };
+                    msg.push_str(&format!("    {} ({}) - {}\n", structure_info.name, structure_info.id, affordability));
+                }
+            }
+
+            // Show locked structures
+            if !buildable.locked.is_empty() {
+                msg.push_str("  Locked:\n");
+                for (id, name, reason) in buildable.locked {
+                    msg.push_str(&format!("    {} ({}) - {}\n", name, id, reason));
+                }
+            }
+        }
+    }
+
+    // Pending actions (if owned by current player)
+    let planet_id = planet.id.clone();
+    let pending_actions: Vec<_> = game_state.players.get(current_player_id)
+        .filter(|_| planet.get_owner().as_ref() == Some(current_player_id))
+        .map(|player| player.pending_actions_on_planet(&planet_id).collect())
+        .unwrap_or_default();
+
+    let held_actions: Vec<_> = game_state.players.get(current_player_id)
+        .filter(|_| planet.get_owner().as_ref() == Some(current_player_id))
+        .map(|player| player.held_actions_on_planet(&planet_id).collect())
+        .unwrap_or_default();
+
+    if !pending_actions.is_empty() || !held_actions.is_empty() {
+        msg.push_str("\nPENDING ACTIONS\n");
+    }
+    for held in &held_actions {
+        msg.push_str(&format!(
+            "  Building {}: construction halted: awaiting {} Lv{} (refunded in {} turns)\n",
+            fmt_structure(&game_state.structure_config, &held.action.target_id().to_string()),
+            fmt_structure(&game_state.structure_config, &held.awaiting), held.required_level, held.turns_left
+        ));
+    }
+    let structure = |id: &String| fmt_structure(&game_state.structure_config, id);
+    let ship = |id: &String| fmt_ship(&game_state.ship_config, id);
+    for action in &pending_actions {
+        let action_desc = match &action.action_type {
+            crate::engine::pending_action::ActionType::BuildStructure(id) => format!("Building {}", structure(id)),
+            crate::engine::pending_action::ActionType::UpgradeStructure(id) if action.auto_repeat => {
+                format!("Upgrading {} (repeating)", structure(id))
+            }
+            crate::engine::pending_action::ActionType::UpgradeStructure(id) => format!("Upgrading {}", structure(id)),
+            crate::engine::pending_action::ActionType::BuildShip(ship_type, id) => match &action.deliver_to {
+                Some(destination) => format!("Building ship {} as {} for delivery to {}", ship(ship_type), id, destination),
+                None => format!("Building ship {} as {}", ship(ship_type), id),
+            },
+            crate::engine::pending_action::ActionType::RetrofitShip(ship_type, id) => {
+                format!("Retrofitting {} into {}", id, ship(ship_type))
+            }
+            crate::engine::pending_action::ActionType::MoveFleet(fleet_id, destination) => {
+                format!("Fleet {} moving to {}", fleet_id, destination)
+            }
+            crate::engine::pending_action::ActionType::MoveShip(ship_id, destination) => {
+                format!("Ship {} moving to {}", ship_id, destination)
+            }
+            crate::engine::pending_action::ActionType::BombardPlanet(fleet_id, target) => match &action.target_structure {
+                Some(structure_id) => format!("Fleet {} bombarding {}, aiming for {}", fleet_id, target, structure_id),
+                None => format!("Fleet {} bombarding {}", fleet_id, target),
+            },
+        };
+        msg.push_str(&format!("  {} ({} turns remaining)\n", action_desc, action.cooldown_remaining));
+    }
+
+    // Project storage up to the last construction action on this planet
+    let last_completion = pending_actions.iter()
+        .filter(|action| !matches!(
+            action.action_type,
+            crate::engine::pending_action::ActionType::MoveFleet(..)
+                | crate::engine::pending_action::ActionType::MoveShip(..)
+                | crate::engine::pending_action::ActionType::BombardPlanet(..)
+        ))
+        .map(|action| action.cooldown_remaining)
+        .max();
+    if let Some(turns) = last_completion {
+        msg.push_str(&format!("  Resources at completion: {}\n", planet.project_resources(turns)));
+        for warning in planet.storage_warnings(turns) {
+            msg.push_str(&format!("  warning: {}\n", warning));
+        }
+    }
+
+    msg.push_str(&format_connections(planet, game_state));
+    Ok(msg)
+}
+
+/// Detail view of a planet outside the current player's sensor range: only the summary
+/// recorded when it was last observed.
+fn format_unrest(planet: &Planet, game_state: &GameState) -> String {
+    let mut line = format!("Unrest: {}/{}", planet.get_unrest(), MAX_UNREST);
+    let penalty_percent = game_state.rules.unrest.production_penalty_percent(planet.get_unrest());
+    if penalty_percent > 0 {
+        line.push_str(&format!(", production -{}%", penalty_percent));
+    }
+    if planet.is_on_strike() {
+        line.push_str(", workers on strike: no production next turn");
+    }
+    line.push('\n');
+    line
+}
+
+fn format_fogged_planet(planet: &Planet, fogged: &Fogged, game_state: &GameState) -> String {
+    let mut msg = format!("=== {} ({}) ===\n", planet.name, planet.id);
+    if planet.is_key_planet() {
+        msg.push_str("Key planet: counts towards a domination victory\n");
+    }
+    let intel = game_state.players.get(game_state.current_player())
+        .and_then(|player| player.planet_intel.get(&planet.id));
+
+    match (fogged, intel) {
+        (Fogged::LastSeen { turn, .. }, Some(intel)) => {
+            msg.push_str(&format!("Out of sensor range - last seen on turn {}\n", turn));
+            msg.push_str(&format!("Owner: {} (as of turn {})\n", owner_name(intel.owner_at_time.as_ref(), game_state), turn));
+            msg.push_str(&format!("Shields: {} HP (as of turn {})\n", intel.shield_seen, turn));
+            msg.push_str(&format!("Structures: {} (as of turn {})\n", intel.structure_count, turn));
+            if let Some(ruin) = &intel.ruin_seen {
+                msg.push_str(&format!("Ruins: {} (as of turn {})\n", ruin.describe(&game_state.structure_config), turn));
+            }
+        }
+        _ => msg.push_str("Unexplored - no intel on this planet\n"),
+    }
+
+    msg.push_str(&format_connections(planet, game_state));
+    msg
+}
+
+fn format_connections(planet: &Planet, game_state: &GameState) -> String {
+    let mut msg = String::from("\nCONNECTIONS\n");
+    for (neighbor, distance) in game_state.map.graph().neighbors(&planet.id) {
+        let dest_name = game_state.map.planets.get(neighbor)
+            .map(|p| p.name.as_str())
+            .unwrap_or("Unknown");
+        msg.push_str(&format!("  {} ({}) - {} turn(s)\n", dest_name, neighbor, distance));
+    }
+    msg
+}
+
+/// A fleet's ships, followed by the planets within reach sorted by travel time and what the
+/// fleet could do there.
+fn format_fleet_detail(fleet_id: &FleetId, game_state: &GameState) -> Result<String, CommandError> {
+    let player = CommandError::acting_player(game_state)?;
+    let fleet = player.fleets.get(fleet_id)
+        .ok_or_else(|| CommandError::fleet_not_found("status fleet", fleet_id, game_state))?;
+    let location_name = game_state.map.planets.get(&fleet.location)
+        .map_or("Unknown", |planet| planet.name.as_str());
+
+    let mut msg = format!("=== {} ({}) ===\n", fleet.name, fleet.id);
+    msg.push_str(&format!("Location: {} ({})\n", location_name, fleet.location));
+    if let Some(supply) = game_state.fleet_supply(&player.id, &fleet.id) {
+        msg.push_str(&format!("SUPPLY: {}\n", supply));
+    }
+    if let Some(cargo) = fleets::format_cargo(fleet, player, game_state) {
+        msg.push_str(&format!("CARGO: {}\n", cargo));
+    }
+
+    msg.push_str("\nSHIPS\n");
+    if fleet.ships.is_empty() {
+        msg.push_str("  (empty)\n");
+    }
+    for ship in fleet.ships.iter().filter_map(|ship_id| player.ships.get(ship_id)) {
+        if ship.supply_wear > 0 {
+            msg.push_str(&format!(
+                "  - {}: {}, shield -{} from attrition\n",
+                ship.id, fmt_ship(&game_state.ship_config, &ship.ship_type), ship.supply_wear
+            ));
+        } else {
+            msg.push_str(&format!("  - {}: {}\n", ship.id, fmt_ship(&game_state.ship_config, &ship.ship_type)));
+        }
+    }
+
+    let target = player.pending_actions.iter().find_map(|action| match &action.action_type {
+        ActionType::BombardPlanet(bombarding, planet_id) if bombarding == &fleet.id => game_state.map.planets.get(planet_id),
+        _ => None,
+    });
+    if let Some(bombardment) = target.and_then(|planet| format_bombardment(planet, player, game_state)) {
+        msg.push_str(&bombardment);
+    }
+
+    let graph = game_state.map.graph();
+    let mut destinations: Vec<(u32, &Planet)> = planets_within_hops(&fleet.location, DESTINATION_MAX_HOPS, game_state)
+        .into_iter()
+        .filter_map(|planet_id| Some((graph.distance(&fleet.location, &planet_id)?, game_state.map.planets.get(&planet_id)?)))
+        .collect();
+    destinations.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.name.cmp(&b.1.name)));
+
+    msg.push_str("\nDESTINATIONS\n");
+    if destinations.is_empty() {
+        msg.push_str("  (none)\n");
+    }
+    let width = destinations.iter().map(|(_, planet)| planet.name.chars().count()).max().unwrap_or(0);
+    for (turns, planet) in destinations {
+        let (owner, outlook) = destination_outlook(planet, fleet, player, game_state);
+        msg.push_str(&format!("  {:<width$}  {:>2} turn(s)  {}", planet.name, turns, owner));
+        if let Some(outlook) = outlook {
+            msg.push_str(&format!(" - {}", outlook));
+        }
+        msg.push('\n');
+    }
+    Ok(msg)
+}
+
+/// Shield state of a planet under bombardment, shown only to its owner and to players
+/// bombarding it. The owner sees every fleet firing on it, a bombarding player only their own
+/// fleets, and the exact shield only with a sensor ship among them. Only the bombarding player
+/// sees which structure each of their fleets aims for.
+fn format_bombardment(planet: &Planet, viewer: &Player, game_state: &GameState) -> Option<String> {
+    let defending = planet.get_owner().as_ref() == Some(&viewer.id);
+    let bombarding: Vec<(&Player, &Fleet, Option<&StructureId>)> = game_state.players_order.iter()
+        .filter_map(|player_id| game_state.players.get(player_id))
+        .filter(|player| defending || player.id == viewer.id)
+        .flat_map(|player| player.pending_actions.iter().filter_map(move |action| match &action.action_type {
+            ActionType::BombardPlanet(fleet_id, target) if target == &planet.id => {
+                player.fleets.get(fleet_id).map(|fleet| (player, fleet, action.target_structure.as_ref()))
+            }
+            _ => None,
+        }))
+        .collect();
+    if bombarding.is_empty() {
+        return None;
+    }
+
+    let power = bombarding.iter()
+        .map(|(player, fleet, _)| game_state.effective_bombardment(&player.id, &fleet.id, &planet.id))
+        .fold(0, u32::saturating_add);
+    let has_sensor = bombarding.iter().any(|(player, fleet, _)| fleet.ships.iter()
+        .filter_map(|ship_id| player.ships.get(ship_id))
+        .filter_map(|ship| game_state.ship_config.get(&ship.ship_type))
+        .any(|definition| definition.sensor));
+    let max_shield = planet.get_max_shield_hp();
+
+    let mut msg = String::from("\nBOMBARDMENT\n");
+    let collapse = if defending || has_sensor {
+        msg.push_str(&format!("  Shield: {} / {} HP\n", planet.get_shield_hp(), max_shield));
+        match planet.turns_to_shield_collapse(power) {
+            Some(turns) => format!("in {} turn(s)", turns),
+            None => String::from("not at this power"),
+        }
+    } else {
+        // Without a sensor ship the attacker only knows the band the shield is in
+        let estimate = ShieldEstimate::of(planet.get_shield_hp(), max_shield);
+        let (low, high) = estimate.hp_range(max_shield);
+        let low = low.max(1);
+        msg.push_str(&format!("  Shield: {}-{} / {} HP ({}, no sensor ship)\n", low, high, max_shield, estimate));
+        match (planet.project_shield_collapse(low, 0, power), planet.project_shield_collapse(high, 0, power)) {
+            (Some(soonest), Some(latest)) if soonest == latest => format!("in {} turn(s)", soonest),
+            (Some(soonest), Some(latest)) => format!("in {}-{} turn(s)", soonest, latest),
+            (Some(soonest), None) => format!("in {} turn(s) at best", soonest),
+            _ => String::from("not at this power"),
+        }
+    };
+
+    let fleets: Vec<String> = bombarding.iter()
+        .map(|(player, fleet, _)| format!("{} ({})", fleet.name, player.name))
+        .collect();
+    msg.push_str(&format!("  Incoming: {} HP per turn from {}\n", power, fleets.join(", ")));
+    for (_, fleet, target) in bombarding.iter().filter(|(player, _, _)| player.id == viewer.id) {
+        if let Some(structure_id) = target {
+            msg.push_str(&format!(
+                "  {} aims for: {}\n", fleet.name, fmt_structure(&game_state.structure_config, structure_id)
+            ));
+        }
+    }
+
+    let mut damaged: Vec<&str> = planet.get_structures().values()
+        .filter(|structure| matches!(structure.state, StructureState::Damaged))
+        .map(|structure| structure.name.as_str())
+        .collect();
+    damaged.sort();
+    if damaged.is_empty() {
+        msg.push_str("  Structures damaged: none\n");
+    } else {
+        msg.push_str(&format!("  Structures damaged: {}\n", damaged.join(", ")));
+    }
+    msg.push_str(&format!("  Shield collapse: {}\n", collapse));
+    Some(msg)
+}
+
+/// Planets at most `max_hops` connections away from `start`, not counting `start` itself.
+fn planets_within_hops(start: &PlanetId, max_hops: usize, game_state: &GameState) -> Vec<PlanetId> {
+    let graph = game_state.map.graph();
+    let mut seen = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([(start.clone(), 0)]);
+    let mut reached = Vec::new();
+
+    while let Some((planet_id, hops)) = queue.pop_front() {
+        if hops == max_hops {
+            continue;
+        }
+        for (neighbor, _) in graph.neighbors(&planet_id) {
+            if seen.insert(neighbor.clone()) {
+                reached.push(neighbor.clone());
+                queue.push_back((neighbor.clone(), hops + 1));
+            }
+        }
+    }
+    reached
+}
+
+/// Owner of a destination and what `fleet` could do there, judged with the colonize and
+/// bombard rules. Planets out of sensor range only show the last known owner.
+fn destination_outlook(planet: &Planet, fleet: &Fleet, player: &Player, game_state: &GameState) -> (String, Option<&'static str>) {
+    match game_state.fogged_view(&player.id, &planet.id) {
+        Some(Fogged::LastSeen { turn, owner }) => {
+            return (format!("{} (as of turn {})", owner_name(owner.as_ref(), game_state), turn), None);
+        }
+        Some(Fogged::Unexplored) => return (String::from("unexplored"), None),
+        None => {}
+    }
+
+    let owner = owner_name(planet.get_owner().as_ref(), game_state);
+    let can_colonize = rules::colonize_target(planet, player, game_state).is_ok();
+    let can_bombard = rules::bombard_target(planet, player, game_state).is_ok();
+    let has_ark = rules::fleet_has_ark(fleet, player);
+    let outlook = match planet.get_owner() {
+        None if can_colonize && has_ark => Some("colonizable"),
+        None if can_colonize => Some("colonizable with an ark"),
+        Some(owner_id) if owner_id != &player.id && planet.get_consolidation_turns() > 0 => Some("consolidating, cannot be taken yet"),
+        Some(_) if can_colonize && has_ark => Some("open, shields down - can invade"),
+        Some(_) if can_colonize => Some("open, shields down"),
+        Some(_) if can_bombard && game_state.calculate_fleet_bombardment(&player.id, &fleet.id) > 0 => Some("bombardable"),
+        Some(_) if can_bombard => Some("shielded, needs bombers"),
+        _ => None,
+    };
+    (owner, outlook)
+}
+
+fn format_player_status(game_state: &GameState) -> String {
+    let current_player_id = game_state.current_player();
+    let player = game_state.players.get(current_player_id)
+        .expect("Current player not found in players map");
+
+    let mut msg = format!("=== {} ===\n", player.name);
+    msg.push_str(&format!("Planets owned: {}\n", player.planets.len()));
+    for planet_id in &player.planets {
+        if let Some(planet) = game_state.map.planets.get(planet_id) {
+            msg.push_str(&format!("  - {} ({})\n", planet.name, planet.id));
+        }
+    }
+
+    let summary = game_state.player_resource_summary(current_player_id)
+        .expect("Current player not found in players map");
+
+    msg.push_str("\nTOTALS\n");
+    msg.push_str(&format!("  Available:  {}\n", summary.available));
+    msg.push_str(&format!("  Storage:    {}\n", summary.storage));
+    msg.push_str(&format!("  Production: {} per turn\n", summary.production));
+    msg.push_str(&format!("  Energy balance: {:+} per turn\n", summary.energy_balance));
+    msg.push_str(&format!("  Idle planets: {}/{}\n", summary.idle_planets, player.planets.len()));
+
+    let effects = game_state.empire_effects(current_player_id);
+    msg.push_str("\nEMPIRE EFFECTS\n");
+    if effects.is_empty() {
+        msg.push_str("  (none)\n");
+    }
+    if effects.production_bonus_percent > 0 {
+        msg.push_str(&format!("  Production: +{}%\n", effects.production_bonus_percent));
+    }
+    if effects.ship_build_time_reduction_percent > 0 {
+        msg.push_str(&format!("  Ship build time: -{}%\n", effects.ship_build_time_reduction_percent));
+    }
+
+    msg.push_str("\nMILITARY\n");
+    msg.push_str(&format!("  Fleets: {}\n", summary.fleet_count));
+    if summary.ships_by_type.is_empty() {
+        msg.push_str("  Ships: (none)\n");
+    } else {
+        msg.push_str("  Ships:\n");
+        for (ship_type, count) in &summary.ships_by_type {
+            msg.push_str(&format!("    {}: {}\n", fmt_ship(&game_state.ship_config, ship_type), count));
+        }
+    }
+
+    if let Some(team) = player.team {
+        msg.push_str(&format_team_status(team, current_player_id, game_state));
+    }
+
+    let key_planets = game_state.map.key_planets().len();
+    if game_state.rules.victory.allows_domination() && key_planets > 0 {
+        let streak = game_state.domination_streaks.get(current_player_id).copied().unwrap_or(0);
+        msg.push_str("\nDOMINATION\n");
+        msg.push_str(&format!(
+            "  Key planets held: {}/{} (a majority is {})\n",
+            game_state.key_planets_held(current_player_id), key_planets, key_planets / 2 + 1
+        ));
+        msg.push_str(&format!("  Turns held: {}/{}\n", streak, game_state.rules.domination_turns));
+    }
+
+    msg
+}
+
+/// Teammates and the totals of the whole team, for `status player` in team games.
+fn format_team_status(team: u8, player_id: &PlayerId, game_state: &GameState) -> String {
+    let mut msg = format!("\nTEAM {}\n", team);
+    let teammates = game_state.teammates(player_id);
+    if teammates.is_empty() {
+        msg.push_str("  Teammates: (none)\n");
+    }
+    for teammate in &teammates {
+        let planets = game_state.players.get(teammate).map_or(0, |player| player.planets.len());
+        let standing = if planets == 0 { String::from("eliminated") } else { format!("{} planet(s)", planets) };
+        msg.push_str(&format!("  {} - {}\n", owner_name(Some(teammate), game_state), standing));
+    }
+
+    let team_summary = game_state.team_resource_summary(player_id)
+        .expect("Current player not found in players map");
+    let team_planets: usize = game_state.side_of(player_id).iter()
+        .filter_map(|member| game_state.players.get(member))
+        .map(|member| member.planets.len())
+        .sum();
+    let team_ships: usize = team_summary.ships_by_type.values().sum();
+    msg.push_str("  Combined:\n");
+    msg.push_str(&format!("    Planets: {}\n", team_planets));
+    msg.push_str(&format!("    Available:  {}\n", team_summary.available));
+    msg.push_str(&format!("    Production: {} per turn\n", team_summary.production));
+    msg.push_str(&format!("    Energy balance: {:+} per turn\n", team_summary.energy_balance));
+    msg.push_str(&format!("    Fleets: {} | Ships: {}\n", team_summary.fleet_count, team_ships));
+    msg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::game_rules::UnrestRules;
+    use crate::engine::game_state::GamePhase;
+    use crate::engine::pending_action::PendingAction;
+    use crate::engine::resources::Resources;
+    use crate::test_support::{self, fixture_game};
+
+    /// Alice's fleet_1 at home, with scouts making the two nearest neutral planets visible.
+    /// Returns the state with those two planets, nearest first.
+    fn fleet_at_home(ship_types: &[&str]) -> (GameState, [PlanetId; 2]) {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let mut neutral: Vec<_> = planets_within_hops(&home, DESTINATION_MAX_HOPS, &state).into_iter()
+            .filter(|planet_id| state.map.planets[planet_id].get_owner().is_none())
+            .map(|planet_id| (state.map.graph().distance(&home, &planet_id).unwrap(), planet_id))
+            .collect();
+        neutral.sort();
+        let [(_, near), (_, far), ..] = &neutral[..] else {
+            panic!("Fixture home needs two neutral planets within reach");
+        };
+
+        let alice = state.players.get_mut("alice").unwrap();
+        let mut fleet = Fleet::new(String::from("fleet_1"), String::from("Vanguard"), home.clone());
+        for ship_type in ship_types {
+            fleet.add_ship(alice.add_ship(ship_type.to_string(), home.clone()));
+        }
+        alice.fleets.insert(fleet.id.clone(), fleet);
+        for planet_id in [near, far] {
+            alice.add_ship(String::from("scout"), planet_id.clone());
+        }
+        (state, [near.clone(), far.clone()])
+    }
+
+    fn destination_line<'a>(output: &'a str, state: &GameState, planet_id: &PlanetId) -> &'a str {
+        let name = &state.map.planets[planet_id].name;
+        output.lines()
+            .skip_while(|line| *line != "DESTINATIONS")
+            .find(|line| line.trim_start().starts_with(name.as_str()))
+            .unwrap_or_else(|| panic!("{} missing from:\n{}", name, output))
+    }
+
+    #[test]
+    fn test_game_status_summarises_the_game_and_hides_the_seed() {
+        let mut state = fixture_game(1).with_seed(1234);
+        state.started_at = Some(10_000);
+
+        let output = format_game(&state, Some(10_000 + 2 * 3600 + 5 * 60));
+
+        assert!(output.starts_with(&format!("=== Game ===\nMap: {} ({} planets)\nCommanders: 2 (2 human, 0 AI)\n", state.map.size, state.map.planets.len())), "{output}");
+        assert!(output.contains("  Alice - 1 planet(s)\n") && output.contains("  Bob - 1 planet(s)\n"), "{output}");
+        assert!(output.contains("Rules: classic\n") && output.contains("Seed: hidden"), "{output}");
+        assert!(!output.contains("1234"), "{output}");
+        assert!(output.ends_with("Running for: 2h 05m\n"), "{output}");
+
+        state.game_phase = GamePhase::Finished;
+        assert!(format_game(&state, None).ends_with("Seed: 1234\n"));
+    }
+
+    #[test]
+    fn test_turn_status_counts_down_the_protected_expansion() {
+        let mut state = fixture_game(1);
+        state.rules.protected_turns = 5;
+        state.turn = 5;
+        let output = format_turn(&state);
+        assert!(output.ends_with("\nProtected expansion: hostilities may begin on turn 6 (1 turn(s) left)"), "{output}");
+
+        state.turn = 6;
+        assert!(!format_turn(&state).contains("Protected expansion"));
+    }
+
+    #[test]
+    fn test_turn_status_shows_who_acted_and_who_is_left_mid_round() {
+        let mut state = test_support::fixture_team_game(1);
+        assert_eq!(
+            format_turn(&state),
+            "Current turn: 1 (normal speed)\nNow playing: Alice\nActed this round: nobody yet\nStill to act: Alice, Bob, Carol, Dave",
+        );
+
+        for player_name in ["Alice", "Bob"] {
+            let player_id = state.current_player().clone();
+            state.apply_effect(CommandEffect::EndTurn { player_name: player_name.to_string() }, &player_id).unwrap();
+        }
+
+        assert_eq!(
+            format_turn(&state),
+            "Current turn: 1 (normal speed)\nNow playing: Carol\nActed this round: Alice, Bob\nStill to act: Carol, Dave",
+        );
+    }
+
+    #[test]
+    fn test_fleet_status_lists_destinations_by_travel_time() {
+        let (state, [near, far]) = fleet_at_home(&["ark"]);
+
+        let output = format_fleet_detail(&String::from("fleet_1"), &state).unwrap();
+
+        assert!(output.starts_with("=== Vanguard (fleet_1) ===\n"), "{output}");
+        assert!(output.contains("SUPPLY: supplied (0 hops)\n"), "{output}");
+        assert!(output.contains("SHIPS\n  - ark_1: Ark (ark)\n"), "{output}");
+        let destinations: Vec<u32> = output.lines()
+            .skip_while(|line| *line != "DESTINATIONS")
+            .skip(1)
+            .map(|line| line.split("turn(s)").next().unwrap().split_whitespace().last().unwrap().parse().unwrap())
+            .collect();
+        assert!(!destinations.is_empty() && destinations.is_sorted(), "{output}");
+        assert!(destination_line(&output, &state, &near).ends_with("uncolonized - colonizable"), "{output}");
+        assert!(destination_line(&output, &state, &far).ends_with("uncolonized - colonizable"), "{output}");
+    }
+
+    #[test]
+    fn test_fleet_status_judges_enemy_planets_by_shields_and_ships() {
+        let (mut state, [near, far]) = fleet_at_home(&["ravager"]);
+        let config = &state.structure_config;
+        for planet_id in [&near, &far] {
+            let planet = state.map.planets.get_mut(planet_id).unwrap();
+            planet.set_owner(String::from("bob"));
+            planet.colonize(config, 1).unwrap();
+        }
+        let shielded = state.map.planets.get_mut(&near).unwrap();
+        for structure_id in ["power_grid", "defense_shield"] {
+            shielded.complete_build_structure(String::from(structure_id), config).unwrap();
+        }
+        shielded.overcharge_shield();
+
+        let output = format_fleet_detail(&String::from("fleet_1"), &state).unwrap();
+
+        assert!(destination_line(&output, &state, &near).ends_with("Bob [BO] - bombardable"), "{output}");
+        assert!(destination_line(&output, &state, &far).ends_with("Bob [BO] - open, shields down"), "{output}");
+        // Planets nobody of alice's side can see give nothing away
+        assert!(output.contains("unexplored"), "{output}");
+    }
+
+    #[test]
+    fn test_fleet_status_shows_stretched_supply_and_worn_shields() {
+        let (mut state, _) = fleet_at_home(&["ravager"]);
+        let home = test_support::home_planet(&state, "alice");
+        let range = state.rules_profile().supply_range;
+        let (far, _) = state.map.graph().hops(&home).into_iter()
+            .filter(|(_, hops)| *hops == range + 1)
+            .min()
+            .expect("Fixture map must reach past the supply range");
+        let alice = state.players.get_mut("alice").unwrap();
+        alice.fleets.get_mut("fleet_1").unwrap().location = far;
+        alice.ships.get_mut("ravager_1").unwrap().supply_wear = 5;
+
+        let output = format_fleet_detail(&String::from("fleet_1"), &state).unwrap();
+
+        assert!(output.contains(&format!("SUPPLY: stretched ({} hops)\n", range + 1)), "{output}");
+        assert!(output.contains("  - ravager_1: Ravager (ravager), shield -5 from attrition\n"), "{output}");
+    }
+
+    /// Alice's fleet_1 of `ship_types` bombarding bob's home, whose 100 HP shield took a 25 HP hit.
+    fn siege(ship_types: &[&str]) -> (GameState, PlanetId) {
+        let mut state = fixture_game(1);
+        let bob_home = test_support::home_planet(&state, "bob");
+        let planet = state.map.planets.get_mut(&bob_home).unwrap();
+        for structure_id in ["power_grid", "defense_shield"] {
+            planet.complete_build_structure(String::from(structure_id), &state.structure_config).unwrap();
+        }
+        planet.process_turn();
+        planet.take_shield_damage(25);
+
+        let alice = state.players.get_mut("alice").unwrap();
+        let mut fleet = Fleet::new(String::from("fleet_1"), String::from("Siege"), bob_home.clone());
+        for ship_type in ship_types {
+            fleet.add_ship(alice.add_ship(ship_type.to_string(), bob_home.clone()));
+        }
+        alice.fleets.insert(fleet.id.clone(), fleet);
+        alice.pending_actions.push(PendingAction::new(
+            ActionType::BombardPlanet(String::from("fleet_1"), bob_home.clone()),
+            bob_home.clone(),
+            u32::MAX,
+            Resources::default(),
+        ));
+        (state, bob_home)
+    }
+
+    fn bombardment_section(output: &str) -> Vec<&str> {
+        output.lines()
+            .skip_while(|line| *line != "BOMBARDMENT")
+            .skip(1)
+            .take_while(|line| !line.is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn test_bombardment_status_for_attacker_without_sensor_shows_a_range() {
+        let (state, bob_home) = siege(&["ravager"]);
+
+        let output = format_fleet_detail(&String::from("fleet_1"), &state).unwrap();
+
+        assert_eq!(bombardment_section(&output), [
+            "  Shield: 67-100 / 100 HP (holding strong, no sensor ship)",
+            "  Incoming: 25 HP per turn from Siege (Alice)",
+            "  Structures damaged: none",
+            "  Shield collapse: in 3-4 turn(s)",
+        ], "{output}");
+        let planet_view = format_planet_detail(&bob_home, &state).unwrap();
+        assert_eq!(bombardment_section(&planet_view), bombardment_section(&output));
+        let home_view = format_planet_detail(&test_support::home_planet(&state, "alice"), &state).unwrap();
+        assert!(!home_view.contains("BOMBARDMENT"), "{home_view}");
+    }
+
+    #[test]
+    fn test_bombardment_status_is_exact_with_a_sensor_ship_and_for_the_defender() {
+        let (mut state, bob_home) = siege(&["ravager", "scout"]);
+
+        let output = format_fleet_detail(&String::from("fleet_1"), &state).unwrap();
+        assert_eq!(bombardment_section(&output)[0], "  Shield: 75 / 100 HP", "{output}");
+        assert_eq!(bombardment_section(&output)[3], "  Shield collapse: in 3 turn(s)", "{output}");
+
+        state.players_order.rotate_left(1);
+        let defender_view = format_planet_detail(&bob_home, &state).unwrap();
+        assert_eq!(bombardment_section(&defender_view), [
+            "  Shield: 75 / 100 HP",
+            "  Incoming: 25 HP per turn from Siege (Alice)",
+            "  Structures damaged: none",
+            "  Shield collapse: in 3 turn(s)",
+        ], "{defender_view}");
+    }
+
+    #[test]
+    fn test_planet_status_shows_unrest_and_blockade() {
+        let (mut state, bob_home) = siege(&["ravager"]);
+        let planet = state.map.planets.get_mut(&bob_home).unwrap();
+        planet.set_unrest(50);
+        planet.update_unrest(true, &UnrestRules { strike_threshold: 60, ..UnrestRules::default() });
+
+        let output = format_planet_detail(&bob_home, &state).unwrap();
+        assert!(output.contains("Unrest: 65/100, production -45%, workers on strike: no production next turn\n"), "{output}");
+        assert!(output.contains("Blockaded: hostile fleets in orbit"), "{output}");
+        let home_view = format_planet_detail(&test_support::home_planet(&state, "alice"), &state).unwrap();
+        assert!(!home_view.contains("Unrest") && !home_view.contains("Blockaded"), "{home_view}");
+    }
+
+    #[test]
+    fn test_fleet_status_for_someone_elses_fleet() {
+        let (mut state, _) = fleet_at_home(&["ark"]);
+        state.players_order.rotate_left(1);
+
+        let result = format_fleet_detail(&String::from("fleet_1"), &state);
+
+        assert!(matches!(result, Err(CommandError::NotYourAsset { owner, .. }) if owner == "Alice"));
+    }
+}
@@ -0,0 +1,116 @@
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::parser::Parseable;
+use crate::engine::configs::structure_config::{STRUCTURE_CATEGORIES, StructureLimit};
+use crate::engine::game_state::GameState;
+
+pub struct StructuresArgs {
+    /// Only list structures of this category
+    pub category: Option<String>,
+}
+
+impl Parseable for StructuresArgs {
+    fn parse(args: Vec<&str>) -> Result<Self, CommandError> {
+        Ok(StructuresArgs {
+            category: args.first().map(|category| category.to_lowercase()),
+        })
+    }
+}
+
+/// Lists every structure definition grouped by category.
+pub fn execute(args: StructuresArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    if let Some(category) = &args.category
+        && !STRUCTURE_CATEGORIES.contains(&category.as_str())
+    {
+        return Err(CommandError::InvalidArgument {
+            command: String::from("structures"),
+            argument: category.clone(),
+            reason: format!("known categories: {}", STRUCTURE_CATEGORIES.join(", ")),
+        });
+    }
+
+    let mut msg = String::from("=== Structure Catalog ===\n");
+
+    for category in STRUCTURE_CATEGORIES {
+        if args.category.as_deref().is_some_and(|filter| filter != *category) {
+            continue;
+        }
+
+        let definitions: Vec<_> = game_state.structure_config.iter()
+            .map(|(_, definition)| definition)
+            .filter(|definition| definition.category == *category)
+            .collect();
+        if definitions.is_empty() {
+            if args.category.is_some() {
+                msg.push_str(&format!("\n{}\n  (none)\n", category.to_uppercase()));
+            }
+            continue;
+        }
+
+        msg.push_str(&format!("\n{}\n", category.to_uppercase()));
+        for definition in definitions {
+            let cost = definition.costs.first().cloned().unwrap_or_default();
+            let build_time = definition.upgrade_time.first().copied().unwrap_or(0);
+            let limit = match definition.limit() {
+                StructureLimit::PerPlanet => String::new(),
+                limit => format!(" - Limit: {}", limit),
+            };
+            let effect = definition.global_effect
+                .map(|effect| format!(" - Empire: {}", effect))
+                .unwrap_or_default();
+            msg.push_str(&format!(
+                "  {:<20} {:<20} Max Lv{} - Cost: {} - {} turn(s){}{}\n",
+                definition.id, definition.name, definition.max_level, cost, build_time, limit, effect
+            ));
+        }
+    }
+
+    Ok(CommandEffect::None { message: msg })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::fixture_game;
+
+    fn catalog(category: Option<&str>) -> Result<String, CommandError> {
+        let args = StructuresArgs { category: category.map(str::to_string) };
+        match execute(args, &fixture_game(1))? {
+            CommandEffect::None { message } => Ok(message),
+            _ => panic!("Catalog must be informational"),
+        }
+    }
+
+    #[test]
+    fn test_catalog_groups_by_category() {
+        let text = catalog(None).unwrap();
+
+        let production = text.find("PRODUCTION").unwrap();
+        let military = text.find("MILITARY").unwrap();
+        let general = text.find("GENERAL").unwrap();
+        assert!(production < military && military < general);
+        assert!(text[military..general].contains("orbital_shipyard"));
+        assert!(text[general..].contains("planetary_capital"));
+    }
+
+    #[test]
+    fn test_catalog_filters_by_category() {
+        let text = catalog(Some("military")).unwrap();
+
+        assert!(text.contains("defense_shield") && text.contains("orbital_shipyard"));
+        assert!(!text.contains("power_grid"));
+    }
+
+    #[test]
+    fn test_catalog_shows_structure_limits() {
+        let text = catalog(Some("special")).unwrap();
+
+        assert!(text.contains("Limit: 1 per game"));
+        assert!(text.contains("Limit: 1 per player"));
+        assert!(text.contains("Empire: +20% production per level"));
+    }
+
+    #[test]
+    fn test_catalog_rejects_unknown_category() {
+        assert!(matches!(catalog(Some("wonders")), Err(CommandError::InvalidArgument { .. })));
+    }
+}
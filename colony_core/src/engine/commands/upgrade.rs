@@ -0,0 +1,56 @@
+use crate::engine::commands::parser::{self, Parseable};
+use crate::engine::game_state::GameState;
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::rules::Reason;
+use crate::engine::utils;
+
+pub struct UpgradeArgs {
+    pub planet_name: String,
+    pub structure_name: String,
+    /// Keep upgrading level after level, set by `--repeat`
+    pub repeat: bool,
+    /// Only show what the upgrade would cost, set by `--preview` or a trailing `?`
+    pub preview: bool,
+}
+
+impl Parseable for UpgradeArgs {
+    fn parse(mut args: Vec<&str>) -> Result<Self, CommandError> {
+        let preview = parser::take_preview_flag(&mut args);
+        let repeat = args.contains(&"--repeat");
+        args.retain(|arg| *arg != "--repeat");
+        if args.len() < 2 {
+            return Err(CommandError::MissingArguments {
+                command: String::from("upgrade"),
+                expected: String::from("upgrade <planet_name> <structure_name> [--repeat] [--preview]"),
+            });
+        }
+        Ok(UpgradeArgs {
+            planet_name: args[0].to_string(),
+            structure_name: args[1].to_string(),
+            repeat,
+            preview,
+        })
+    }
+}
+
+pub fn execute(args: UpgradeArgs, game_state: &GameState) -> Result<CommandEffect, CommandError> {
+    let (planet_id, planet) = CommandError::own_planet(&args.planet_name, game_state)?;
+
+    // Check structure exists on planet
+    let structure_id = utils::name_to_id(&args.structure_name);
+    if !planet.get_structures().contains_key(&structure_id) {
+        return Err(CommandError::InvalidArgument {
+            command: String::from("upgrade"),
+            argument: args.structure_name.clone(),
+            reason: format!("Structure '{}' does not exist on planet '{}'", args.structure_name, args.planet_name),
+        });
+    }
+
+    if args.preview {
+        let preview = game_state.preview_structure_upgrade(game_state.current_player(), &planet_id, &structure_id)
+            .map_err(|e| CommandError::NotAllowed(Reason::Blocked(e)))?;
+        return Ok(CommandEffect::None { message: preview.to_string() });
+    }
+
+    Ok(CommandEffect::UpgradeStructure { planet_id, structure_id, auto_repeat: args.repeat })
+}
@@ -0,0 +1,195 @@
+// Configuration modules
+
+pub mod structure_config;
+pub mod ship_config;
+pub mod planet_names;
+pub mod player_names;
+#[cfg(feature = "embedded-data")]
+pub mod embedded;
+#[cfg(feature = "std-io")]
+pub mod validation;
+
+#[cfg(feature = "std-io")]
+use std::fmt;
+#[cfg(feature = "std-io")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "std-io")]
+use std::sync::OnceLock;
+
+pub use planet_names::{PlanetNamesConfigError, PlanetNameParts};
+
+/// Upper bound for any numeric stat, cost or amount in the ship and structure configs.
+/// Keeps sums over whole fleets and empires far away from `u32::MAX`.
+pub const MAX_CONFIG_VALUE: u32 = 1_000_000;
+
+/// Environment variable naming the directory the data files are read from.
+pub const DATA_DIR_ENV: &str = "COLONY_DATA_DIR";
+
+/// Data files every game needs, relative to the data directory.
+pub const DATA_FILES: [&str; 4] = ["structure.json", "ships.json", "planet_names.json", "player_names.json"];
+
+/// A problem with one entry of a config file, or with the whole file when `entry_id` is None.
+#[derive(Debug)]
+pub struct EntryError<E> {
+    pub entry_id: Option<String>,
+    pub error: E,
+}
+
+/// Parses a JSON array of config entries one entry at a time, so a malformed entry does not
+/// hide problems with the ones after it. Entries that fail to parse are reported by their
+/// `id` field when they have one.
+pub(crate) fn parse_entries<T, E>(json: &str) -> (Vec<T>, Vec<EntryError<E>>)
+where
+    T: serde::de::DeserializeOwned,
+    E: From<serde_json::Error>,
+{
+    let values: Vec<serde_json::Value> = match serde_json::from_str(json) {
+        Ok(values) => values,
+        Err(error) => return (Vec::new(), vec![EntryError { entry_id: None, error: error.into() }]),
+    };
+
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+    for value in values {
+        let entry_id = value.get("id").and_then(serde_json::Value::as_str).map(str::to_string);
+        match serde_json::from_value(value) {
+            Ok(entry) => entries.push(entry),
+            Err(error) => errors.push(EntryError { entry_id, error: error.into() }),
+        }
+    }
+    (entries, errors)
+}
+
+#[cfg(feature = "std-io")]
+static DATA_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Overrides the data directory for the rest of the process, taking precedence over
+/// `COLONY_DATA_DIR`. Only the first call has an effect.
+#[cfg(feature = "std-io")]
+pub fn set_data_dir(path: impl Into<PathBuf>) {
+    let _ = DATA_DIR_OVERRIDE.set(path.into());
+}
+
+/// Directory the data files are read from: the `--data-dir` override, else
+/// `COLONY_DATA_DIR`, else `data` relative to the working directory.
+#[cfg(feature = "std-io")]
+pub fn data_dir() -> PathBuf {
+    if let Some(path) = DATA_DIR_OVERRIDE.get() {
+        return path.clone();
+    }
+    match std::env::var_os(DATA_DIR_ENV) {
+        Some(path) if !path.is_empty() => PathBuf::from(path),
+        _ => PathBuf::from("data"),
+    }
+}
+
+/// Path of one of the [`DATA_FILES`] inside the data directory.
+#[cfg(feature = "std-io")]
+pub fn data_file(file_name: &str) -> PathBuf {
+    data_dir().join(file_name)
+}
+
+/// A config file that could not be read, with enough context to find out why.
+#[cfg(feature = "std-io")]
+#[derive(Debug)]
+pub struct DataFileError {
+    /// Absolute path that was attempted
+    pub path: PathBuf,
+    pub working_dir: Option<PathBuf>,
+    pub source: std::io::Error,
+}
+
+#[cfg(feature = "std-io")]
+impl DataFileError {
+    fn new(path: &Path, source: std::io::Error) -> Self {
+        let working_dir = std::env::current_dir().ok();
+        let path = match &working_dir {
+            Some(dir) if path.is_relative() => dir.join(path),
+            _ => path.to_path_buf(),
+        };
+        DataFileError { path, working_dir, source }
+    }
+}
+
+#[cfg(feature = "std-io")]
+impl fmt::Display for DataFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to read config file {}: {}", self.path.display(), self.source)?;
+        if let Some(dir) = &self.working_dir {
+            write!(f, " (working directory: {})", dir.display())?;
+        }
+        write!(f, ". Run from the repository root, or point {} or --data-dir at the data directory", DATA_DIR_ENV)
+    }
+}
+
+#[cfg(feature = "std-io")]
+impl std::error::Error for DataFileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Reads a config file, attaching the attempted path to any error.
+#[cfg(feature = "std-io")]
+pub fn read_config_file(path: impl AsRef<Path>) -> Result<String, DataFileError> {
+    let path = path.as_ref();
+    std::fs::read_to_string(path).map_err(|source| DataFileError::new(path, source))
+}
+
+/// Every one of the [`DATA_FILES`] that cannot be read from the data directory, so a
+/// frontend can report them all at once before starting a game.
+#[cfg(feature = "std-io")]
+pub fn missing_data_files() -> Vec<DataFileError> {
+    DATA_FILES.iter()
+        .filter_map(|file_name| {
+            let path = data_file(file_name);
+            std::fs::metadata(&path).err().map(|source| DataFileError::new(&path, source))
+        })
+        .collect()
+}
+
+#[cfg(all(test, feature = "std-io"))]
+mod tests {
+    use super::*;
+
+    fn read_data_file(file_name: &str) -> String {
+        let path = format!("{}/../data/{}", env!("CARGO_MANIFEST_DIR"), file_name);
+        std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("Failed to read {}: {}", path, e))
+    }
+
+    /// Smoke test for the shipped data files: they must parse and pass validation.
+    /// Deliberately asserts no balance numbers so data tweaks don't break the suite.
+    #[test]
+    fn test_shipped_data_files_are_valid() {
+        structure_config::StructureConfig::load_from_string(&read_data_file("structure.json"))
+            .expect("data/structure.json must be valid");
+        ship_config::ShipConfig::load_from_string(&read_data_file("ships.json"))
+            .expect("data/ships.json must be valid");
+
+        let name_parts = PlanetNameParts::load_from_string(&read_data_file("planet_names.json"))
+            .expect("data/planet_names.json must be valid");
+        assert!(!name_parts.prefixes.is_empty() && !name_parts.suffixes.is_empty());
+
+        player_names::generate_random_names_from_string(&read_data_file("player_names.json"), 1)
+            .expect("data/player_names.json must be valid");
+    }
+
+    #[test]
+    fn test_unreadable_config_file_names_the_absolute_path() {
+        let error = read_config_file("no_such_dir/ships.json").unwrap_err();
+        let working_dir = std::env::current_dir().unwrap();
+
+        assert_eq!(error.path, working_dir.join("no_such_dir/ships.json"));
+        let message = error.to_string();
+        assert!(message.contains(&working_dir.join("no_such_dir/ships.json").display().to_string()), "{message}");
+        assert!(message.contains(&format!("working directory: {}", working_dir.display())), "{message}");
+        assert!(message.contains(DATA_DIR_ENV) && message.contains("--data-dir"), "{message}");
+    }
+
+    #[test]
+    fn test_config_errors_carry_the_path() {
+        let error = PlanetNameParts::load_from_path("no_such_dir/planet_names.json").unwrap_err();
+        assert!(matches!(&error, PlanetNamesConfigError::FileReadError(error) if error.path.ends_with("no_such_dir/planet_names.json")));
+        assert!(error.to_string().contains("no_such_dir/planet_names.json"), "{error}");
+    }
+}
@@ -0,0 +1,32 @@
+// The shipped data files, compiled in for embedders that cannot read the data directory
+
+use crate::engine::configs::planet_names::{PlanetNameParts, PlanetNamesConfigError};
+use crate::engine::configs::ship_config::{ShipConfig, ShipConfigError};
+use crate::engine::configs::structure_config::{StructureConfig, StructureConfigError};
+
+pub const STRUCTURES: &str = include_str!("../../../../data/structure.json");
+pub const SHIPS: &str = include_str!("../../../../data/ships.json");
+pub const PLANET_NAMES: &str = include_str!("../../../../data/planet_names.json");
+/// Player names JSON, for [`super::player_names::choose_names_from_string`]
+pub const PLAYER_NAMES: &str = include_str!("../../../../data/player_names.json");
+
+impl StructureConfig {
+    /// The structures from the shipped `data/structure.json`.
+    pub fn embedded() -> Result<Self, StructureConfigError> {
+        Self::load_from_string(STRUCTURES)
+    }
+}
+
+impl ShipConfig {
+    /// The ships from the shipped `data/ships.json`.
+    pub fn embedded() -> Result<Self, ShipConfigError> {
+        Self::load_from_string(SHIPS)
+    }
+}
+
+impl PlanetNameParts {
+    /// The name parts from the shipped `data/planet_names.json`.
+    pub fn embedded() -> Result<Self, PlanetNamesConfigError> {
+        Self::load_from_string(PLANET_NAMES)
+    }
+}
@@ -0,0 +1,47 @@
+// Planet name generation configuration
+
+#[cfg(feature = "std-io")]
+use std::path::Path;
+
+use serde::Deserialize;
+
+#[cfg(feature = "std-io")]
+use crate::engine::configs::{self, DataFileError};
+
+#[cfg(feature = "std-io")]
+pub(crate) const PLANET_NAMES_CONFIG_FILE: &str = "planet_names.json";
+
+#[derive(thiserror::Error, Debug)]
+pub enum PlanetNamesConfigError {
+    #[error(transparent)]
+    #[cfg(feature = "std-io")]
+    FileReadError(#[from] DataFileError),
+
+    #[error("Failed to parse JSON: {0}")]
+    JsonParseError(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PlanetNameParts {
+    pub prefixes: Vec<String>,
+    pub suffixes: Vec<String>,
+}
+
+impl PlanetNameParts {
+    #[cfg(feature = "std-io")]
+    pub fn load() -> Result<Self, PlanetNamesConfigError> {
+        Self::load_from_path(configs::data_file(PLANET_NAMES_CONFIG_FILE))
+    }
+
+    /// Loads an alternative name pack with the same format as `data/planet_names.json`.
+    #[cfg(feature = "std-io")]
+    pub fn load_from_path(path: impl AsRef<Path>) -> Result<Self, PlanetNamesConfigError> {
+        let json_content = configs::read_config_file(path)?;
+        Self::load_from_string(&json_content)
+    }
+
+    pub fn load_from_string(json: &str) -> Result<Self, PlanetNamesConfigError> {
+        let name_parts: PlanetNameParts = serde_json::from_str(json)?;
+        Ok(name_parts)
+    }
+}
@@ -1,14 +1,21 @@
 use serde::Deserialize;
-use std::fs;
+use rand::Rng;
 use rand::prelude::IndexedRandom;
 
+#[cfg(feature = "std-io")]
+use crate::engine::configs::{self, DataFileError};
+
+#[cfg(feature = "std-io")]
+const PLAYER_NAMES_CONFIG_FILE: &str = "player_names.json";
+
 #[derive(Debug, thiserror::Error)]
 pub enum PlayerNameConfigError {
     #[error("Not enough names in configuration. Need {needed}, but only {available} available")]
     InsufficientNames { needed: usize, available: usize },
     
-    #[error("Failed to read config file: {0}")]
-    FileReadError(#[from] std::io::Error),
+    #[error(transparent)]
+    #[cfg(feature = "std-io")]
+    FileReadError(#[from] DataFileError),
 
     #[error("Failed to parse JSON: {0}")]
     JsonParseError(#[from] serde_json::Error),
@@ -33,12 +40,24 @@ struct PlayerNamesData {
 /// - The config file cannot be read
 /// - The JSON is malformed
 /// - There aren't enough names in the config for the requested count
+#[cfg(feature = "std-io")]
 pub fn generate_random_names(count: usize) -> Result<Vec<String>, PlayerNameConfigError> {
-    let data = fs::read_to_string("data/player_names.json")?;
+    let data = configs::read_config_file(configs::data_file(PLAYER_NAMES_CONFIG_FILE))?;
     generate_random_names_from_string(&data, count)
 }
 
+#[cfg(feature = "std-io")]
 pub fn generate_random_names_from_string(json: &str, count: usize) -> Result<Vec<String>, PlayerNameConfigError> {
+    choose_names_from_string(json, count, &mut rand::rng())
+}
+
+/// Picks `count` unique names from player names JSON, drawing from `rng` so a seeded rng
+/// always picks the same names.
+pub fn choose_names_from_string(
+    json: &str,
+    count: usize,
+    rng: &mut impl Rng,
+) -> Result<Vec<String>, PlayerNameConfigError> {
     let player_data: PlayerNamesData = serde_json::from_str(json)?;
 
     if player_data.names.len() < count {
@@ -48,16 +67,15 @@ pub fn generate_random_names_from_string(json: &str, count: usize) -> Result<Vec
         });
     }
 
-    let mut rng = rand::rng();
     Ok(
         player_data.names
-            .choose_multiple(&mut rng, count)
+            .choose_multiple(rng, count)
             .cloned()
             .collect()
     )
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std-io"))]
 mod tests {
     use super::*;
 
@@ -0,0 +1,375 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use thiserror::Error;
+
+use crate::engine::configs::{self, EntryError, MAX_CONFIG_VALUE};
+#[cfg(feature = "std-io")]
+use crate::engine::configs::DataFileError;
+use crate::engine::resources::Resources;
+use crate::engine::utils;
+
+pub type ShipId = String;
+
+#[cfg(feature = "std-io")]
+pub(crate) const SHIP_CONFIG_FILE: &str = "ships.json";
+
+#[derive(Debug, Error)]
+pub enum ShipConfigError {
+    #[error(transparent)]
+    #[cfg(feature = "std-io")]
+    FileReadError(#[from] DataFileError),
+
+    #[error("Failed to parse JSON: {0}")]
+    JsonParseError(#[from] serde_json::Error),
+
+    #[error("Ship '{ship_name}' has invalid counter reference: '{counter_id}'")]
+    InvalidCounterReference {
+        ship_name: String,
+        counter_id: ShipId,
+    },
+
+    #[error("Ship '{ship_name}' has size 0; every ship takes at least 1 fleet capacity")]
+    ZeroSize { ship_name: String },
+
+    #[error("Ship '{ship_name}': {field_name} is {value} but may be at most {}", MAX_CONFIG_VALUE)]
+    ValueTooLarge {
+        ship_name: String,
+        field_name: String,
+        value: u32,
+    },
+}
+
+impl ShipConfigError {
+    /// The field of the ship definition the error is about, if it is about one.
+    pub fn field(&self) -> Option<&str> {
+        match self {
+            ShipConfigError::InvalidCounterReference { .. } => Some("counters"),
+            ShipConfigError::ZeroSize { .. } => Some("size"),
+            ShipConfigError::ValueTooLarge { field_name, .. } => Some(field_name),
+            #[cfg(feature = "std-io")]
+            ShipConfigError::FileReadError(_) => None,
+            ShipConfigError::JsonParseError(_) => None,
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct ShipDefinition {
+    pub id: ShipId,
+    pub name: String,
+    #[allow(dead_code)]
+    pub description: String,
+    pub attack: u32,
+    pub shield: u32,
+    pub bombardment: u32,
+    pub cost: Resources,
+    pub build_time: u32,
+    pub counters: Vec<ShipId>,
+    pub required_shipyard_level: u16,
+    /// Reveals the exact size of enemy fleets it detects
+    #[serde(default)]
+    pub sensor: bool,
+    /// Fleet capacity the ship takes up; capital ships take more than fighters
+    #[serde(default = "default_ship_size")]
+    pub size: u8,
+    /// Resources the ship can carry, counted together across minerals, gas and energy
+    #[serde(default)]
+    pub cargo_capacity: u32,
+}
+
+fn default_ship_size() -> u8 {
+    1
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ShipConfig {
+    ships: HashMap<ShipId, Arc<ShipDefinition>>,
+}
+
+impl ShipConfig {
+    #[cfg(feature = "std-io")]
+    pub fn load() -> Result<Self, ShipConfigError> {
+        let json_content = configs::read_config_file(configs::data_file(SHIP_CONFIG_FILE))?;
+        Self::load_from_string(&json_content)
+    }
+
+    pub fn load_from_string(json: &str) -> Result<Self, ShipConfigError> {
+        Self::load_collecting_errors(json)
+            .map_err(|errors| errors.into_iter().next().expect("Failed loads report an error").error)
+    }
+
+    /// Loads ship definitions like `load_from_string`, but checks every definition and reports
+    /// every problem found instead of stopping at the first.
+    pub fn load_collecting_errors(json: &str) -> Result<Self, Vec<EntryError<ShipConfigError>>> {
+        let (definitions, mut errors) = configs::parse_entries::<ShipDefinition, _>(json);
+
+        let mut ships: HashMap<ShipId, Arc<ShipDefinition>> = HashMap::new();
+        for ship in definitions {
+            let mut entry_errors = Vec::new();
+            Self::validate_values(&ship, &mut entry_errors);
+            errors.extend(entry_errors.into_iter()
+                .map(|error| EntryError { entry_id: Some(ship.id.clone()), error }));
+
+            let ship_id = ship.id.clone();
+            let arc_def = Arc::new(ship);
+            ships.insert(ship_id, arc_def);
+        }
+
+        // Validate counter references after all ships are loaded
+        let config = ShipConfig { ships };
+        config.validate_counters(&mut errors);
+
+        if errors.is_empty() {
+            Ok(config)
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn get(&self, id: &ShipId) -> Option<Arc<ShipDefinition>> {
+        self.ships.get(id).cloned()
+    }
+
+    /// Looks up a ship type the way a player names it: by id or display name, in any case and
+    /// with spaces for underscores.
+    pub fn find_by_name(&self, name: &str) -> Option<Arc<ShipDefinition>> {
+        let id = utils::name_to_id(name);
+        self.get(&id).or_else(|| self.iter()
+            .find(|(_, definition)| utils::name_to_id(&definition.name) == id)
+            .map(|(_, definition)| Arc::clone(definition)))
+    }
+
+    /// Returns an iterator over all ship definitions, in id order.
+    pub fn iter(&self) -> impl Iterator<Item = (&ShipId, &Arc<ShipDefinition>)> {
+        let mut entries: Vec<_> = self.ships.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        entries.into_iter()
+    }
+
+    /// Returns every ship type id, in order.
+    pub fn ids(&self) -> impl Iterator<Item = &ShipId> {
+        self.iter().map(|(id, _)| id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.ships.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ships.is_empty()
+    }
+
+    /// Fleet capacity a ship of `ship_type` takes up; 1 for unknown types.
+    pub fn size_of(&self, ship_type: &str) -> usize {
+        self.ships.get(ship_type).map_or(1, |definition| usize::from(definition.size))
+    }
+
+    /// Resources a ship of `ship_type` can carry; 0 for unknown types.
+    pub fn cargo_of(&self, ship_type: &str) -> u32 {
+        self.ships.get(ship_type).map_or(0, |definition| definition.cargo_capacity)
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.ships.contains_key(id)
+    }
+
+    fn validate_values(ship: &ShipDefinition, errors: &mut Vec<ShipConfigError>) {
+        if ship.size == 0 {
+            errors.push(ShipConfigError::ZeroSize { ship_name: ship.name.clone() });
+        }
+
+        let values = [
+            ("attack", ship.attack),
+            ("shield", ship.shield),
+            ("bombardment", ship.bombardment),
+            ("cost", ship.cost.max_amount()),
+            ("build_time", ship.build_time),
+            ("cargo_capacity", ship.cargo_capacity),
+        ];
+
+        for (field_name, value) in values {
+            if value > MAX_CONFIG_VALUE {
+                errors.push(ShipConfigError::ValueTooLarge {
+                    ship_name: ship.name.clone(),
+                    field_name: field_name.to_string(),
+                    value,
+                });
+            }
+        }
+    }
+
+    fn validate_counters(&self, errors: &mut Vec<EntryError<ShipConfigError>>) {
+        for (ship_id, ship) in self.iter() {
+            for counter_id in &ship.counters {
+                if !self.contains(counter_id) {
+                    errors.push(EntryError {
+                        entry_id: Some(ship_id.clone()),
+                        error: ShipConfigError::InvalidCounterReference {
+                            ship_name: ship.name.clone(),
+                            counter_id: counter_id.clone(),
+                        },
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// A ship type as player-facing text names it, e.g. "Interceptor (interceptor)"; the bare id
+/// for a type the config does not define.
+pub fn fmt_ship(ships: &ShipConfig, id: &ShipId) -> String {
+    match ships.get(id) {
+        Some(definition) => format!("{} ({})", definition.name, id),
+        None => id.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_valid_config() {
+        let json = r#"[
+            {
+                "id": "interceptor",
+                "name": "Interceptor",
+                "description": "Fast fighter for fleet combat",
+                "attack": 10,
+                "shield": 5,
+                "bombardment": 0,
+                "cost": {"minerals": 100, "gas": 50, "energy": 0},
+                "build_time": 2,
+                "counters": ["interceptor", "ravager"],
+                "required_shipyard_level": 1
+            },
+            {
+                "id": "ravager",
+                "name": "Ravager",
+                "description": "Heavy bomber for planetary bombardment",
+                "attack": 5,
+                "shield": 15,
+                "bombardment": 25,
+                "cost": {"minerals": 200, "gas": 100, "energy": 0},
+                "build_time": 3,
+                "counters": [],
+                "required_shipyard_level": 2
+            }
+        ]"#;
+
+        let result = ShipConfig::load_from_string(json);
+        assert!(result.is_ok());
+
+        let config = result.unwrap();
+        assert!(config.get(&"interceptor".to_string()).is_some());
+        assert!(config.get(&"ravager".to_string()).is_some());
+    }
+
+    #[test]
+    fn test_invalid_counter_reference() {
+        let json = r#"[
+            {
+                "id": "interceptor",
+                "name": "Interceptor",
+                "description": "Fast fighter",
+                "attack": 10,
+                "shield": 5,
+                "bombardment": 0,
+                "cost": {"minerals": 100, "gas": 50, "energy": 0},
+                "build_time": 2,
+                "counters": ["nonexistent_ship"],
+                "required_shipyard_level": 1
+            }
+        ]"#;
+
+        let result = ShipConfig::load_from_string(json);
+        assert!(result.is_err());
+
+        match result.unwrap_err() {
+            ShipConfigError::InvalidCounterReference { ship_name, counter_id } => {
+                assert_eq!(ship_name, "Interceptor");
+                assert_eq!(counter_id, "nonexistent_ship");
+            }
+            err => panic!("Expected InvalidCounterReference, got {:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_stat_above_cap_is_rejected() {
+        let json = r#"[
+            {
+                "id": "doomsday",
+                "name": "Doomsday",
+                "description": "Modded ship",
+                "attack": 10,
+                "shield": 5,
+                "bombardment": 4000000000,
+                "cost": {"minerals": 100, "gas": 50, "energy": 0},
+                "build_time": 2,
+                "counters": [],
+                "required_shipyard_level": 1
+            }
+        ]"#;
+
+        match ShipConfig::load_from_string(json).unwrap_err() {
+            ShipConfigError::ValueTooLarge { ship_name, field_name, value } => {
+                assert_eq!(ship_name, "Doomsday");
+                assert_eq!(field_name, "bombardment");
+                assert_eq!(value, 4_000_000_000);
+            }
+            err => panic!("Expected ValueTooLarge, got {:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_size_defaults_to_one_and_zero_is_rejected() {
+        let json = |size: &str| format!(r#"[
+            {{
+                "id": "corvette",
+                "name": "Corvette",
+                "description": "Modded ship",
+                "attack": 10,
+                "shield": 5,
+                "bombardment": 0,
+                "cost": {{"minerals": 100, "gas": 50, "energy": 0}},
+                "build_time": 2,
+                "counters": [],
+                "required_shipyard_level": 1{}
+            }}
+        ]"#, size);
+
+        assert_eq!(ShipConfig::load_from_string(&json("")).unwrap().size_of("corvette"), 1);
+        assert_eq!(ShipConfig::load_from_string(&json(r#", "size": 4"#)).unwrap().size_of("corvette"), 4);
+        assert!(matches!(
+            ShipConfig::load_from_string(&json(r#", "size": 0"#)),
+            Err(ShipConfigError::ZeroSize { ship_name }) if ship_name == "Corvette"
+        ));
+    }
+
+    #[test]
+    fn test_invalid_json() {
+        let json = r#"{ this is not valid json }"#;
+
+        let result = ShipConfig::load_from_string(json);
+        assert!(result.is_err());
+
+        match result.unwrap_err() {
+            ShipConfigError::JsonParseError(_) => {}
+            err => panic!("Expected JsonParseError, got {:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_iteration_is_sorted_and_names_are_normalized() {
+        let config = crate::test_support::fixture_ship_config();
+
+        let ids: Vec<_> = config.ids().map(String::as_str).collect();
+        assert_eq!(ids, ["ark", "interceptor", "ravager", "scout"]);
+        assert_eq!(config.len(), 4);
+        assert!(config.contains("scout"));
+        let found = config.find_by_name("Interceptor").unwrap();
+        assert_eq!(found.id, "interceptor");
+        assert!(config.find_by_name("dreadnought").is_none());
+    }
+}
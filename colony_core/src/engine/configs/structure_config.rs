@@ -0,0 +1,811 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use thiserror::Error;
+
+use crate::engine::configs;
+#[cfg(feature = "std-io")]
+use crate::engine::configs::DataFileError;
+use crate::engine::configs::{EntryError, MAX_CONFIG_VALUE};
+use crate::engine::resources::Resources;
+use crate::engine::structure::StructureId;
+use crate::engine::utils;
+
+#[cfg(feature = "std-io")]
+pub(crate) const STRUCTURE_CONFIG_FILE: &str = "structure.json";
+
+/// Known structure categories, in catalog display order
+pub const STRUCTURE_CATEGORIES: &[&str] = &["production", "storage", "military", "special", "general"];
+
+pub const DEFAULT_STRUCTURE_CATEGORY: &str = "general";
+
+fn default_category() -> String {
+    DEFAULT_STRUCTURE_CATEGORY.to_string()
+}
+
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Error)]
+pub enum StructureConfigError {
+    #[error("Structure '{structure_name}': {field_name} has {actual} items but max_level is {expected}")]
+    SizeMismatchError {
+        structure_name: String,
+        field_name: String,
+        expected: usize,
+        actual: usize
+    },
+
+    #[error("Structure '{structure_name}': unknown category '{category}' (expected one of: {})", STRUCTURE_CATEGORIES.join(", "))]
+    UnknownCategory {
+        structure_name: String,
+        category: String,
+    },
+
+    #[error("Structure '{structure_name}': limit must allow at least one instance")]
+    InvalidLimit { structure_name: String },
+
+    #[error("Structure '{structure_name}': global effect must have a non-zero percentage")]
+    InvalidGlobalEffect { structure_name: String },
+
+    #[error("Structure '{structure_name}': {field_name} is {value} but may be at most {}", MAX_CONFIG_VALUE)]
+    ValueTooLarge {
+        structure_name: String,
+        field_name: String,
+        value: u32,
+    },
+
+    #[error(transparent)]
+    #[cfg(feature = "std-io")]
+    FileReadError(#[from] DataFileError),
+
+    #[error("Failed to parse JSON: {0}")]
+    JsonParseError(#[from] serde_json::Error),
+}
+
+impl StructureConfigError {
+    /// The field of the structure definition the error is about, if it is about one.
+    pub fn field(&self) -> Option<&str> {
+        match self {
+            StructureConfigError::SizeMismatchError { field_name, .. }
+            | StructureConfigError::ValueTooLarge { field_name, .. } => Some(field_name),
+            StructureConfigError::UnknownCategory { .. } => Some("category"),
+            StructureConfigError::InvalidLimit { .. } => Some("limit"),
+            StructureConfigError::InvalidGlobalEffect { .. } => Some("global_effect"),
+            #[cfg(feature = "std-io")]
+            StructureConfigError::FileReadError(_) => None,
+            StructureConfigError::JsonParseError(_) => None,
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct Prerequisity {
+    pub structure_id: StructureId,
+    pub required_levels: Vec<u32>
+}
+
+impl Prerequisity {
+    pub fn validate(&self, structure_name: &str, max_level: usize) -> Result<(), StructureConfigError> {
+        let required_levels_len = self.required_levels.len();
+        if required_levels_len > max_level {
+            return Err(
+                StructureConfigError::SizeMismatchError {
+                    structure_name: structure_name.to_string(),
+                    field_name: "prerequisity.required_levels".to_string(),
+                    expected: max_level, 
+                    actual: required_levels_len 
+                }
+            );
+        }
+        Ok(())
+    }
+}
+
+/// How many instances of a structure may exist.
+#[allow(clippy::enum_variant_names)]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructureLimit {
+    /// One per planet, the behaviour of every structure without a limit
+    PerPlanet,
+    PerPlayer(u32),
+    PerGame(u32),
+}
+
+impl fmt::Display for StructureLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StructureLimit::PerPlanet => write!(f, "1 per planet"),
+            StructureLimit::PerPlayer(max) => write!(f, "{} per player", max),
+            StructureLimit::PerGame(max) => write!(f, "{} per game", max),
+        }
+    }
+}
+
+/// An empire-wide modifier granted while the structure is operational. Percentages are per level.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlobalEffect {
+    /// Extra production on every owned planet
+    ProductionBonus(u32),
+    /// Shorter build time for every ship
+    ShipBuildTimeReduction(u32),
+}
+
+impl GlobalEffect {
+    fn percent(&self) -> u32 {
+        match self {
+            GlobalEffect::ProductionBonus(percent) | GlobalEffect::ShipBuildTimeReduction(percent) => *percent,
+        }
+    }
+}
+
+impl fmt::Display for GlobalEffect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GlobalEffect::ProductionBonus(percent) => write!(f, "+{}% production per level", percent),
+            GlobalEffect::ShipBuildTimeReduction(percent) => write!(f, "-{}% ship build time per level", percent),
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct StructureDefinition {
+    pub id: StructureId,
+    pub name: String,
+    /// Catalog grouping, one of `STRUCTURE_CATEGORIES`
+    #[serde(default = "default_category")]
+    pub category: String,
+    #[allow(dead_code)]
+    pub description: String,
+    pub max_level: u16,
+    pub costs: Vec<Resources>,
+    pub upgrade_time: Vec<u32>,
+    pub energy_consumption: Vec<u32>,
+    pub hitpoints: Vec<u32>,
+    pub production: Vec<Resources>,
+    pub storage_capacity: Vec<Resources>,
+    pub prerequisites: Vec<Prerequisity>,
+    /// Turns without attack required for shield regeneration (only for defense_shield)
+    pub shield_regen_turns: Option<u32>,
+    #[serde(default)]
+    pub limit: Option<StructureLimit>,
+    #[serde(default)]
+    pub global_effect: Option<GlobalEffect>,
+    /// Unrest removed from the planet each round per level, as a morale structure does
+    #[serde(default)]
+    pub unrest_relief: Option<u32>,
+}
+
+impl StructureDefinition {
+    pub fn limit(&self) -> StructureLimit {
+        self.limit.unwrap_or(StructureLimit::PerPlanet)
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StructureConfig {
+    structures: HashMap<StructureId, Arc<StructureDefinition>>
+}
+
+impl StructureConfig {
+    #[cfg(feature = "std-io")]
+    pub fn load() -> Result<Self, StructureConfigError> {
+        let json_content = configs::read_config_file(configs::data_file(STRUCTURE_CONFIG_FILE))?;
+        Self::load_from_string(&json_content)
+    }
+
+    pub fn load_from_string(json: &str) -> Result<Self, StructureConfigError> {
+        Self::load_collecting_errors(json)
+            .map_err(|errors| errors.into_iter().next().expect("Failed loads report an error").error)
+    }
+
+    /// Loads structure definitions like `load_from_string`, but checks every definition and
+    /// reports every problem found instead of stopping at the first.
+    pub fn load_collecting_errors(json: &str) -> Result<Self, Vec<EntryError<StructureConfigError>>> {
+        let (definitions, mut errors) = configs::parse_entries::<StructureDefinition, _>(json);
+
+        let mut structures: HashMap<StructureId, Arc<StructureDefinition>> = HashMap::new();
+        for structure in definitions {
+            // Validation of structure definitions
+            let mut entry_errors = Vec::new();
+            StructureConfig::validate_arrays(&structure, &mut entry_errors);
+            StructureConfig::validate_prerequisities(&structure, &mut entry_errors);
+            StructureConfig::validate_category(&structure, &mut entry_errors);
+            StructureConfig::validate_limit(&structure, &mut entry_errors);
+            StructureConfig::validate_global_effect(&structure, &mut entry_errors);
+            StructureConfig::validate_values(&structure, &mut entry_errors);
+            errors.extend(entry_errors.into_iter()
+                .map(|error| EntryError { entry_id: Some(structure.id.clone()), error }));
+
+            let structure_id = structure.id.clone();
+            let arc_def = Arc::new(structure);
+            structures.insert(structure_id, arc_def);
+        }
+
+        if errors.is_empty() {
+            Ok(StructureConfig { structures })
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn get(&self, id: &StructureId) -> Option<Arc<StructureDefinition>> {
+        self.structures.get(id).cloned()
+    }
+
+    /// Looks up a structure the way a player names it: by id or display name, in any case and
+    /// with spaces for underscores.
+    pub fn find_by_name(&self, name: &str) -> Option<Arc<StructureDefinition>> {
+        let id = utils::name_to_id(name);
+        self.get(&id).or_else(|| self.iter()
+            .find(|(_, definition)| utils::name_to_id(&definition.name) == id)
+            .map(|(_, definition)| Arc::clone(definition)))
+    }
+
+    /// Returns an iterator over all structure definitions, in id order.
+    pub fn iter(&self) -> impl Iterator<Item = (&StructureId, &Arc<StructureDefinition>)> {
+        let mut entries: Vec<_> = self.structures.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        entries.into_iter()
+    }
+
+    /// Returns every structure id, in order.
+    pub fn ids(&self) -> impl Iterator<Item = &StructureId> {
+        self.iter().map(|(id, _)| id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.structures.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.structures.is_empty()
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.structures.contains_key(id)
+    }
+
+    /// Suggests structures for a mistyped id: every structure in the category of the closest
+    /// match, closest first. Returns nothing when no id is reasonably close.
+    pub fn suggest_similar(&self, typed_id: &str) -> Vec<StructureId> {
+        let Some((closest, distance)) = self.structures.values()
+            .map(|definition| (definition, utils::edit_distance(typed_id, &definition.id)))
+            .min_by(|(a, a_distance), (b, b_distance)| a_distance.cmp(b_distance).then_with(|| a.id.cmp(&b.id)))
+        else {
+            return Vec::new();
+        };
+        if distance > typed_id.chars().count().max(closest.id.len()) / 2 {
+            return Vec::new();
+        }
+
+        let mut same_category: Vec<_> = self.structures.values()
+            .filter(|definition| definition.category == closest.category)
+            .map(|definition| (utils::edit_distance(typed_id, &definition.id), definition.id.clone()))
+            .collect();
+        same_category.sort();
+        same_category.into_iter().map(|(_, id)| id).collect()
+    }
+
+    fn validate_arrays(definition: &StructureDefinition, errors: &mut Vec<StructureConfigError>) {
+        let max_level = definition.max_level as usize;
+
+        let sizes_to_check = [
+            ("costs", definition.costs.len()),
+            ("production", definition.production.len()),
+            ("storage_capacity", definition.storage_capacity.len()),
+            ("hitpoints", definition.hitpoints.len()),
+            ("upgrade_time", definition.upgrade_time.len()),
+            ("energy_consumption", definition.energy_consumption.len()),
+        ];
+
+        for (field_name, size) in sizes_to_check {
+            if size > max_level {
+                errors.push(StructureConfigError::SizeMismatchError {
+                    structure_name: definition.name.clone(),
+                    field_name: field_name.to_string(),
+                    expected: max_level,
+                    actual: size,
+                });
+            }
+        }
+    }
+
+    fn validate_values(definition: &StructureDefinition, errors: &mut Vec<StructureConfigError>) {
+        let largest = |amounts: &[Resources]| amounts.iter().map(Resources::max_amount).max().unwrap_or(0);
+        let largest_u32 = |values: &[u32]| values.iter().copied().max().unwrap_or(0);
+
+        let values = [
+            ("costs", largest(&definition.costs)),
+            ("production", largest(&definition.production)),
+            ("storage_capacity", largest(&definition.storage_capacity)),
+            ("hitpoints", largest_u32(&definition.hitpoints)),
+            ("upgrade_time", largest_u32(&definition.upgrade_time)),
+            ("energy_consumption", largest_u32(&definition.energy_consumption)),
+            ("shield_regen_turns", definition.shield_regen_turns.unwrap_or(0)),
+            ("global_effect", definition.global_effect.map_or(0, |effect| effect.percent())),
+            ("unrest_relief", definition.unrest_relief.unwrap_or(0)),
+        ];
+
+        for (field_name, value) in values {
+            if value > MAX_CONFIG_VALUE {
+                errors.push(StructureConfigError::ValueTooLarge {
+                    structure_name: definition.name.clone(),
+                    field_name: field_name.to_string(),
+                    value,
+                });
+            }
+        }
+    }
+
+    fn validate_category(definition: &StructureDefinition, errors: &mut Vec<StructureConfigError>) {
+        if !STRUCTURE_CATEGORIES.contains(&definition.category.as_str()) {
+            errors.push(StructureConfigError::UnknownCategory {
+                structure_name: definition.name.clone(),
+                category: definition.category.clone(),
+            });
+        }
+    }
+
+    fn validate_limit(definition: &StructureDefinition, errors: &mut Vec<StructureConfigError>) {
+        if let Some(StructureLimit::PerPlayer(0) | StructureLimit::PerGame(0)) = definition.limit {
+            errors.push(StructureConfigError::InvalidLimit { structure_name: definition.name.clone() });
+        }
+    }
+
+    fn validate_global_effect(definition: &StructureDefinition, errors: &mut Vec<StructureConfigError>) {
+        if definition.global_effect.is_some_and(|effect| effect.percent() == 0) {
+            errors.push(StructureConfigError::InvalidGlobalEffect { structure_name: definition.name.clone() });
+        }
+    }
+
+    fn validate_prerequisities(definition: &StructureDefinition, errors: &mut Vec<StructureConfigError>) {
+        for prerequisity in &definition.prerequisites {
+            if let Err(error) = prerequisity.validate(definition.name.as_str(), definition.max_level as usize) {
+                errors.push(error);
+            }
+        }
+    }
+}
+
+/// A structure as player-facing text names it: the display name with the id players type in
+/// parentheses, e.g. "Orbital Shipyard (orbital_shipyard)". Falls back to the bare id for a
+/// structure the config does not define.
+pub fn fmt_structure(structures: &StructureConfig, id: &StructureId) -> String {
+    match structures.get(id) {
+        Some(definition) => format!("{} ({})", definition.name, id),
+        None => id.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_valid_config() {
+        let json = r#"[
+            {
+                "id": "metal_mine",
+                "name": "Metal Mine",
+                "description": "Produces metal",
+                "max_level": 3,
+                "costs": [
+                    {"minerals": 100, "gas": 50, "energy": 0},
+                    {"minerals": 200, "gas": 100, "energy": 0},
+                    {"minerals": 400, "gas": 200, "energy": 0}
+                ],
+                "upgrade_time": [100, 200, 400],
+                "energy_consumption": [10, 20, 40],
+                "hitpoints": [1000, 2000, 4000],
+                "production": [
+                    {"minerals": 10, "gas": 0, "energy": 0},
+                    {"minerals": 20, "gas": 0, "energy": 0},
+                    {"minerals": 40, "gas": 0, "energy": 0}
+                ],
+                "storage_capacity": [
+                    {"minerals": 0, "gas": 0, "energy": 0},
+                    {"minerals": 0, "gas": 0, "energy": 0},
+                    {"minerals": 0, "gas": 0, "energy": 0}
+                ],
+                "prerequisites": []
+            }
+        ]"#;
+
+        let result = StructureConfig::load_from_string(json);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_costs_array_too_large() {
+        let json = r#"[
+            {
+                "id": "metal_mine",
+                "name": "Metal Mine",
+                "description": "Produces metal",
+                "max_level": 2,
+                "costs": [
+                    {"minerals": 100, "gas": 50, "energy": 0},
+                    {"minerals": 200, "gas": 100, "energy": 0},
+                    {"minerals": 400, "gas": 200, "energy": 0}
+                ],
+                "upgrade_time": [100, 200],
+                "energy_consumption": [10, 20],
+                "hitpoints": [1000, 2000],
+                "production": [
+                    {"minerals": 10, "gas": 0, "energy": 0},
+                    {"minerals": 20, "gas": 0, "energy": 0}
+                ],
+                "storage_capacity": [
+                    {"minerals": 0, "gas": 0, "energy": 0},
+                    {"minerals": 0, "gas": 0, "energy": 0}
+                ],
+                "prerequisites": []
+            }
+        ]"#;
+
+        let result = StructureConfig::load_from_string(json);
+        assert!(result.is_err());
+
+        let err = result.unwrap_err();
+        match err {
+            StructureConfigError::SizeMismatchError {
+                structure_name, field_name, expected, actual
+            } => {
+                assert_eq!(structure_name, "Metal Mine");
+                assert_eq!(field_name, "costs");
+                assert_eq!(expected, 2);
+                assert_eq!(actual, 3);
+            },
+            _ => panic!("Expected SizeMismatchError, got {:?}", err)
+        }
+    }
+
+    #[test]
+    fn test_production_array_too_large() {
+        let json = r#"[
+            {
+                "id": "gas_extractor",
+                "name": "Gas Extractor",
+                "description": "Produces gas",
+                "max_level": 1,
+                "costs": [
+                    {"minerals": 100, "gas": 50, "energy": 0}
+                ],
+                "upgrade_time": [100],
+                "energy_consumption": [10],
+                "hitpoints": [1000],
+                "production": [
+                    {"minerals": 0, "gas": 10, "energy": 0},
+                    {"minerals": 0, "gas": 20, "energy": 0}
+                ],
+                "storage_capacity": [
+                    {"minerals": 0, "gas": 0, "energy": 0}
+                ],
+                "prerequisites": []
+            }
+        ]"#;
+
+        let result = StructureConfig::load_from_string(json);
+        assert!(result.is_err());
+
+        let err = result.unwrap_err();
+        match err {
+            StructureConfigError::SizeMismatchError { structure_name, field_name, expected, actual } => {
+                assert_eq!(structure_name, "Gas Extractor");
+                assert_eq!(field_name, "production");
+                assert_eq!(expected, 1);
+                assert_eq!(actual, 2);
+            },
+            _ => panic!("Expected SizeMismatchError, got {:?}", err)
+        }
+    }
+
+    #[test]
+    fn test_prerequisite_required_levels_too_large() {
+        let json = r#"[
+            {
+                "id": "fusion_reactor",
+                "name": "Fusion Reactor",
+                "description": "Advanced energy source",
+                "max_level": 2,
+                "costs": [
+                    {"minerals": 1000, "gas": 500, "energy": 100},
+                    {"minerals": 2000, "gas": 1000, "energy": 200}
+                ],
+                "upgrade_time": [500, 1000],
+                "energy_consumption": [0, 0],
+                "hitpoints": [5000, 10000],
+                "production": [
+                    {"minerals": 0, "gas": 0, "energy": 0},
+                    {"minerals": 0, "gas": 0, "energy": 0}
+                ],
+                "storage_capacity": [
+                    {"minerals": 0, "gas": 0, "energy": 0},
+                    {"minerals": 0, "gas": 0, "energy": 0}
+                ],
+                "prerequisites": [
+                    {
+                        "structure_id": "energy_plant",
+                        "required_levels": [5, 10, 15]
+                    }
+                ]
+            }
+        ]"#;
+
+        let result = StructureConfig::load_from_string(json);
+        assert!(result.is_err());
+
+        let err = result.unwrap_err();
+        match err {
+            StructureConfigError::SizeMismatchError { structure_name, field_name, expected, actual } => {
+                assert_eq!(structure_name, "Fusion Reactor");
+                assert_eq!(field_name, "prerequisity.required_levels");
+                assert_eq!(expected, 2);
+                assert_eq!(actual, 3);
+            },
+            _ => panic!("Expected SizeMismatchError, got {:?}", err)
+        }
+    }
+
+    #[test]
+    fn test_empty_arrays_are_valid() {
+        let json = r#"[
+            {
+                "id": "storage",
+                "name": "Storage",
+                "description": "Stores resources",
+                "max_level": 5,
+                "costs": [],
+                "upgrade_time": [],
+                "energy_consumption": [],
+                "hitpoints": [],
+                "production": [],
+                "storage_capacity": [],
+                "prerequisites": []
+            }
+        ]"#;
+
+        let result = StructureConfig::load_from_string(json);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_invalid_json() {
+        let json = r#"{ this is not valid json }"#;
+
+        let result = StructureConfig::load_from_string(json);
+        assert!(result.is_err());
+
+        match result.unwrap_err() {
+            StructureConfigError::JsonParseError(_serde_json) => {},
+            err => panic!("Expected JsonParseError, got {:?}", err)
+        }
+    }
+
+    #[test]
+    fn test_upgrade_time_array_too_large() {
+        let json = r#"[
+            {
+                "id": "test_structure",
+                "name": "Test Structure",
+                "description": "Test",
+                "max_level": 2,
+                "costs": [
+                    {"minerals": 100, "gas": 50, "energy": 0},
+                    {"minerals": 200, "gas": 100, "energy": 0}
+                ],
+                "upgrade_time": [100, 200, 300],
+                "energy_consumption": [10, 20],
+                "hitpoints": [1000, 2000],
+                "production": [
+                    {"minerals": 10, "gas": 0, "energy": 0},
+                    {"minerals": 20, "gas": 0, "energy": 0}
+                ],
+                "storage_capacity": [
+                    {"minerals": 0, "gas": 0, "energy": 0},
+                    {"minerals": 0, "gas": 0, "energy": 0}
+                ],
+                "prerequisites": []
+            }
+        ]"#;
+
+        let result = StructureConfig::load_from_string(json);
+        assert!(result.is_err());
+
+        let err = result.unwrap_err();
+        match err {
+            StructureConfigError::SizeMismatchError {
+                structure_name, field_name, expected, actual
+            } => {
+                assert_eq!(structure_name, "Test Structure");
+                assert_eq!(field_name, "upgrade_time");
+                assert_eq!(expected, 2);
+                assert_eq!(actual, 3);
+            },
+            _ => panic!("Expected SizeMismatchError for upgrade_time, got {:?}", err)
+        }
+    }
+
+    #[test]
+    fn test_energy_consumption_array_too_large() {
+        let json = r#"[
+            {
+                "id": "test_structure",
+                "name": "Test Structure",
+                "description": "Test",
+                "max_level": 1,
+                "costs": [
+                    {"minerals": 100, "gas": 50, "energy": 0}
+                ],
+                "upgrade_time": [100],
+                "energy_consumption": [10, 20],
+                "hitpoints": [1000],
+                "production": [
+                    {"minerals": 10, "gas": 0, "energy": 0}
+                ],
+                "storage_capacity": [
+                    {"minerals": 0, "gas": 0, "energy": 0}
+                ],
+                "prerequisites": []
+            }
+        ]"#;
+
+        let result = StructureConfig::load_from_string(json);
+        assert!(result.is_err());
+
+        let err = result.unwrap_err();
+        match err {
+            StructureConfigError::SizeMismatchError {
+                structure_name, field_name, expected, actual
+            } => {
+                assert_eq!(structure_name, "Test Structure");
+                assert_eq!(field_name, "energy_consumption");
+                assert_eq!(expected, 1);
+                assert_eq!(actual, 2);
+            },
+            _ => panic!("Expected SizeMismatchError for energy_consumption, got {:?}", err)
+        }
+    }
+
+    fn category_json(category: Option<&str>) -> String {
+        let category = category.map(|c| format!(r#""category": "{}","#, c)).unwrap_or_default();
+        single_structure_json(&category)
+    }
+
+    fn limit_json(limit: &str) -> String {
+        single_structure_json(&format!(r#""limit": {},"#, limit))
+    }
+
+    /// A minimal one-level structure definition with `extra_fields` spliced in.
+    fn single_structure_json(extra_fields: &str) -> String {
+        format!(r#"[
+            {{
+                "id": "metal_mine",
+                "name": "Metal Mine",
+                {extra_fields}
+                "description": "Produces metal",
+                "max_level": 1,
+                "costs": [{{"minerals": 100, "gas": 0, "energy": 0}}],
+                "upgrade_time": [1],
+                "energy_consumption": [0],
+                "hitpoints": [100],
+                "production": [{{"minerals": 10, "gas": 0, "energy": 0}}],
+                "storage_capacity": [{{"minerals": 0, "gas": 0, "energy": 0}}],
+                "prerequisites": []
+            }}
+        ]"#)
+    }
+
+    #[test]
+    fn test_category_defaults_to_general() {
+        let config = StructureConfig::load_from_string(&category_json(None)).unwrap();
+
+        let definition = config.get(&"metal_mine".to_string()).unwrap();
+        assert_eq!(definition.category, DEFAULT_STRUCTURE_CATEGORY);
+    }
+
+    #[test]
+    fn test_known_category_is_accepted() {
+        let config = StructureConfig::load_from_string(&category_json(Some("production"))).unwrap();
+
+        assert_eq!(config.get(&"metal_mine".to_string()).unwrap().category, "production");
+    }
+
+    #[test]
+    fn test_unknown_category_is_rejected() {
+        let result = StructureConfig::load_from_string(&category_json(Some("wonders")));
+
+        match result {
+            Err(StructureConfigError::UnknownCategory { structure_name, category }) => {
+                assert_eq!(structure_name, "Metal Mine");
+                assert_eq!(category, "wonders");
+            }
+            other => panic!("Expected UnknownCategory error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_suggestions_come_from_category_of_closest_match() {
+        let config = crate::test_support::fixture_structure_config();
+
+        assert_eq!(config.suggest_similar("orbital_shipyrd"), vec!["orbital_shipyard", "defense_shield"]);
+        assert!(config.suggest_similar("xyz").is_empty());
+    }
+
+    #[test]
+    fn test_limit_parsing() {
+        let mine = "metal_mine".to_string();
+
+        let default = StructureConfig::load_from_string(&category_json(None)).unwrap();
+        assert_eq!(default.get(&mine).unwrap().limit(), StructureLimit::PerPlanet);
+
+        let per_player = StructureConfig::load_from_string(&limit_json(r#"{"PerPlayer": 2}"#)).unwrap();
+        assert_eq!(per_player.get(&mine).unwrap().limit(), StructureLimit::PerPlayer(2));
+
+        let per_game = StructureConfig::load_from_string(&limit_json(r#"{"PerGame": 1}"#)).unwrap();
+        assert_eq!(per_game.get(&mine).unwrap().limit(), StructureLimit::PerGame(1));
+    }
+
+    #[test]
+    fn test_global_effect_parsing() {
+        let mine = "metal_mine".to_string();
+
+        let without = StructureConfig::load_from_string(&category_json(None)).unwrap();
+        assert_eq!(without.get(&mine).unwrap().global_effect, None);
+
+        let json = single_structure_json(r#""global_effect": {"ProductionBonus": 5},"#);
+        let with = StructureConfig::load_from_string(&json).unwrap();
+        assert_eq!(with.get(&mine).unwrap().global_effect, Some(GlobalEffect::ProductionBonus(5)));
+
+        let json = single_structure_json(r#""global_effect": {"ShipBuildTimeReduction": 0},"#);
+        let result = StructureConfig::load_from_string(&json);
+        assert!(matches!(result, Err(StructureConfigError::InvalidGlobalEffect { .. })));
+    }
+
+    #[test]
+    fn test_values_above_cap_are_rejected() {
+        let json = single_structure_json("").replace(r#""hitpoints": [100]"#, r#""hitpoints": [1000001]"#);
+        let result = StructureConfig::load_from_string(&json);
+        assert!(matches!(result, Err(StructureConfigError::ValueTooLarge { field_name, value: 1_000_001, .. })
+            if field_name == "hitpoints"));
+
+        let json = single_structure_json("").replace(r#""minerals": 10,"#, r#""minerals": 4294967295,"#);
+        let result = StructureConfig::load_from_string(&json);
+        assert!(matches!(result, Err(StructureConfigError::ValueTooLarge { field_name, .. }) if field_name == "production"));
+
+        let at_cap = single_structure_json("").replace(r#""hitpoints": [100]"#, r#""hitpoints": [1000000]"#);
+        assert!(StructureConfig::load_from_string(&at_cap).is_ok());
+    }
+
+    #[test]
+    fn test_zero_limit_is_rejected() {
+        let result = StructureConfig::load_from_string(&limit_json(r#"{"PerGame": 0}"#));
+
+        assert!(matches!(result, Err(StructureConfigError::InvalidLimit { .. })));
+    }
+
+    #[test]
+    fn test_iteration_is_sorted_and_repeatable() {
+        let config = crate::test_support::fixture_structure_config();
+
+        let ids: Vec<_> = config.ids().cloned().collect();
+        let mut sorted = ids.clone();
+        sorted.sort();
+        assert_eq!(ids, sorted);
+        assert_eq!(ids, config.ids().cloned().collect::<Vec<_>>());
+        assert_eq!(ids.len(), config.len());
+        assert!(config.contains("power_grid") && !config.contains("Power Grid"));
+    }
+
+    #[test]
+    fn test_find_by_name_normalizes_ids_and_display_names() {
+        let json = single_structure_json("").replace(r#""name": "Metal Mine""#, r#""name": "Ore Extractor""#);
+        let config = StructureConfig::load_from_string(&json).unwrap();
+
+        for typed in ["metal_mine", "Metal Mine", "METAL_MINE", "ore extractor", "Ore Extractor"] {
+            let found = config.find_by_name(typed).map(|definition| definition.id.clone());
+            assert_eq!(found.as_deref(), Some("metal_mine"), "{typed}");
+        }
+        assert!(config.find_by_name("no such structure").is_none());
+    }
+}
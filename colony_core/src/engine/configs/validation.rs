@@ -0,0 +1,550 @@
+// Checks a set of data files the way a game loads them, reporting every problem at once
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use crate::engine::configs::planet_names::PlanetNameParts;
+use crate::engine::configs::player_names;
+use crate::engine::configs::ship_config::ShipConfig;
+use crate::engine::configs::structure_config::{StructureConfig, StructureDefinition};
+use crate::engine::configs::{self, DATA_FILES, EntryError};
+use crate::engine::game_rules::GameSpeed;
+use crate::engine::planet::PLANETARY_CAPITAL;
+use crate::engine::planet_name_generator::PlanetNameGenerator;
+use crate::engine::resources::{RESOURCE_NAMES, Resources};
+use crate::engine::structure::StructureId;
+
+/// The structure whose level decides which ships a planet can build
+const SHIPYARD_ID: &str = "orbital_shipyard";
+
+/// The ship that founds colonies
+const COLONIZER_ID: &str = "ark";
+
+/// Fewest player names a name pack must offer: enough for a two-player game
+const MIN_PLAYER_NAMES: usize = 2;
+
+/// Where each data file is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataFilePaths {
+    pub structures: PathBuf,
+    pub ships: PathBuf,
+    pub planet_names: PathBuf,
+    pub player_names: PathBuf,
+}
+
+impl DataFilePaths {
+    /// The [`DATA_FILES`] inside `dir`.
+    pub fn in_dir(dir: impl AsRef<Path>) -> Self {
+        let [structures, ships, planet_names, player_names] = DATA_FILES.map(|file_name| dir.as_ref().join(file_name));
+        DataFilePaths { structures, ships, planet_names, player_names }
+    }
+}
+
+/// One problem found in a data file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    pub file: PathBuf,
+    /// Id of the entry at fault; None when the problem is with the file as a whole
+    pub entry: Option<String>,
+    pub field: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.file.display())?;
+        if let Some(entry) = &self.entry {
+            write!(f, ", entry '{}'", entry)?;
+        }
+        if let Some(field) = &self.field {
+            write!(f, ", field '{}'", field)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Every problem found across the data files, in file order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    fn add(&mut self, file: &Path, entry: Option<String>, field: Option<&str>, message: impl ToString) {
+        self.issues.push(ValidationIssue {
+            file: file.to_path_buf(),
+            entry,
+            field: field.map(str::to_string),
+            message: message.to_string(),
+        });
+    }
+
+    /// Records every error of a collecting loader, returning the config when there were none.
+    fn collect<T, E: fmt::Display>(
+        &mut self,
+        file: &Path,
+        loaded: Result<T, Vec<EntryError<E>>>,
+        field_of: fn(&E) -> Option<&str>,
+    ) -> Option<T> {
+        match loaded {
+            Ok(config) => Some(config),
+            Err(errors) => {
+                for EntryError { entry_id, error } in errors {
+                    self.add(file, entry_id, field_of(&error), &error);
+                }
+                None
+            }
+        }
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_valid() {
+            return write!(f, "All data files are valid.");
+        }
+        write!(f, "{} problem(s) found in the data files:", self.issues.len())?;
+        for issue in &self.issues {
+            write!(f, "\n  {}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs every config loader over the data files at `paths`, plus the checks between
+/// structures and ships, and reports every problem instead of stopping at the first.
+pub fn validate_data_files(paths: &DataFilePaths) -> ValidationReport {
+    let mut report = ValidationReport::default();
+
+    let structures = read(&paths.structures, &mut report)
+        .and_then(|json| report.collect(
+            &paths.structures,
+            StructureConfig::load_collecting_errors(&json),
+            |error| error.field(),
+        ));
+    let ships = read(&paths.ships, &mut report)
+        .and_then(|json| report.collect(
+            &paths.ships,
+            ShipConfig::load_collecting_errors(&json),
+            |error| error.field(),
+        ));
+
+    if let Some(json) = read(&paths.planet_names, &mut report) {
+        let generator = PlanetNameParts::load_from_string(&json)
+            .map_err(|error| error.to_string())
+            .and_then(|name_parts| PlanetNameGenerator::from_parts(name_parts).map_err(|error| error.to_string()));
+        if let Err(message) = generator {
+            report.add(&paths.planet_names, None, None, message);
+        }
+    }
+
+    if let Some(json) = read(&paths.player_names, &mut report) {
+        let mut rng = StdRng::seed_from_u64(0);
+        if let Err(error) = player_names::choose_names_from_string(&json, MIN_PLAYER_NAMES, &mut rng) {
+            report.add(&paths.player_names, None, None, error);
+        }
+    }
+
+    if let (Some(structures), Some(ships)) = (structures, ships) {
+        cross_validate(&structures, &ships, paths, &mut report);
+        check_reachability(&structures, &ships, paths, &mut report);
+    }
+    report
+}
+
+/// Checks references between the structure and ship configs, which each loader alone cannot see.
+fn cross_validate(structures: &StructureConfig, ships: &ShipConfig, paths: &DataFilePaths, report: &mut ValidationReport) {
+    for (structure_id, definition) in structures.iter() {
+        for prerequisite in &definition.prerequisites {
+            if !structures.contains(&prerequisite.structure_id) {
+                report.add(
+                    &paths.structures,
+                    Some(structure_id.clone()),
+                    Some("prerequisites"),
+                    format!("Structure '{}' requires unknown structure '{}'", definition.name, prerequisite.structure_id),
+                );
+            }
+        }
+    }
+
+    let shipyard_levels = structures.get(&SHIPYARD_ID.to_string()).map_or(0, |shipyard| shipyard.max_level);
+    for (ship_id, definition) in ships.iter() {
+        if definition.required_shipyard_level > shipyard_levels {
+            report.add(
+                &paths.ships,
+                Some(ship_id.clone()),
+                Some("required_shipyard_level"),
+                format!(
+                    "Ship '{}' needs {} level {}, but it only goes up to level {}",
+                    definition.name, SHIPYARD_ID, definition.required_shipyard_level, shipyard_levels,
+                ),
+            );
+        }
+    }
+}
+
+/// Checks a fresh colony can work its way up to producing every resource and building arks,
+/// so a game cannot open soft-locked. Reports the chain of requirements that breaks when not.
+fn check_reachability(structures: &StructureConfig, ships: &ShipConfig, paths: &DataFilePaths, report: &mut ValidationReport) {
+    let Some(reach) = Reachability::new(structures) else {
+        report.add(&paths.structures, None, None, format!("No '{}': starting planets cannot be settled", PLANETARY_CAPITAL));
+        return;
+    };
+
+    for (resource, name) in RESOURCE_NAMES.into_iter().enumerate() {
+        if reach.produces(resource) {
+            continue;
+        }
+        match reach.next_producer(resource) {
+            Some((producer, level)) => report.add(
+                &paths.structures,
+                Some(producer.clone()),
+                None,
+                format!("Nothing producing {} can ever be built: {}", name, reach.explain_structure(producer, level, &mut Vec::new())),
+            ),
+            None => report.add(&paths.structures, None, None, format!("Nothing produces {}", name)),
+        }
+    }
+
+    let Some(colonizer) = ships.get(&COLONIZER_ID.to_string()) else {
+        report.add(&paths.ships, None, None, format!("No '{}': colonies can never be founded", COLONIZER_ID));
+        return;
+    };
+    let shipyard_level = reach.level(SHIPYARD_ID);
+    let chain = if shipyard_level < colonizer.required_shipyard_level {
+        let needs = format!("{} needs {} Lv{}", COLONIZER_ID, SHIPYARD_ID, colonizer.required_shipyard_level);
+        let shipyard_chain = structures.contains(SHIPYARD_ID)
+            .then(|| reach.explain_structure(&SHIPYARD_ID.to_string(), shipyard_level + 1, &mut Vec::new()));
+        Some(match shipyard_chain {
+            Some(chain) => format!("{} -> {}", needs, chain),
+            None => needs,
+        })
+    } else {
+        reach.afford(&colonizer.cost)
+            .map(|blocker| reach.explain(COLONIZER_ID.to_string(), blocker, &mut Vec::new()))
+    };
+    if let Some(chain) = chain {
+        report.add(
+            &paths.ships,
+            Some(COLONIZER_ID.to_string()),
+            None,
+            format!("Colonies can never be founded, as no {} can be built: {}", COLONIZER_ID, chain),
+        );
+    }
+}
+
+/// What stops a structure level or ship from being built on a planet that has reached
+/// everything it can.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Blocker {
+    Prerequisite { structure_id: StructureId, level: u16 },
+    /// More of a resource than the starting stock, with nothing reachable producing it
+    Unproduced { resource: usize, amount: u32 },
+    /// More of a resource than every reachable store together holds
+    Unstorable { resource: usize, amount: u32, capacity: u32 },
+}
+
+/// The highest level of every structure a fresh colony can work its way up to, ignoring how
+/// long anything takes. A cost is taken as payable when each resource in it is produced by a
+/// reached structure or covered by the starting stock, and fits the reached storage.
+struct Reachability<'a> {
+    structures: &'a StructureConfig,
+    levels: HashMap<StructureId, u16>,
+    /// What a starting planet is stocked with at the least generous speed
+    stock: [u32; 3],
+}
+
+impl<'a> Reachability<'a> {
+    /// Starts from the capital at level 1 with full stores and builds whatever becomes
+    /// possible until nothing more does. None without a capital to start from.
+    fn new(structures: &'a StructureConfig) -> Option<Self> {
+        let capital = structures.get(&PLANETARY_CAPITAL.to_string())?;
+        let stock = capital.storage_capacity.first().cloned().unwrap_or_default()
+            .percentage(GameSpeed::Epic.starting_resources_percent())
+            .amounts();
+        let mut reach = Reachability {
+            structures,
+            levels: HashMap::from([(PLANETARY_CAPITAL.to_string(), 1)]),
+            stock,
+        };
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (structure_id, definition) in structures.iter() {
+                let next = reach.level(structure_id) + 1;
+                if next <= definition.max_level && reach.blocker(definition, next).is_none() {
+                    reach.levels.insert(structure_id.clone(), next);
+                    changed = true;
+                }
+            }
+        }
+        Some(reach)
+    }
+
+    fn level(&self, structure_id: &str) -> u16 {
+        self.levels.get(structure_id).copied().unwrap_or(0)
+    }
+
+    /// Reached structures with their definitions and levels.
+    fn reached(&self) -> impl Iterator<Item = (Arc<StructureDefinition>, usize)> + '_ {
+        self.levels.iter()
+            .filter_map(|(structure_id, level)| Some((self.structures.get(structure_id)?, *level as usize)))
+    }
+
+    fn produces(&self, resource: usize) -> bool {
+        self.reached().any(|(definition, level)| {
+            definition.production.iter().take(level).any(|production| production.amounts()[resource] > 0)
+        })
+    }
+
+    fn storage(&self, resource: usize) -> u32 {
+        self.reached()
+            .filter_map(|(definition, level)| definition.storage_capacity.get(level - 1).map(|capacity| capacity.amounts()[resource]))
+            .fold(0, u32::saturating_add)
+    }
+
+    /// The first structure, by id, with a level producing `resource` that is not reached yet,
+    /// and the lowest level of it not reached.
+    fn next_producer(&self, resource: usize) -> Option<(&'a StructureId, u16)> {
+        self.structures.iter()
+            .find(|(structure_id, definition)| {
+                definition.production.iter().skip(self.level(structure_id) as usize)
+                    .any(|production| production.amounts()[resource] > 0)
+            })
+            .map(|(structure_id, _)| (structure_id, self.level(structure_id) + 1))
+    }
+
+    fn blocker(&self, definition: &StructureDefinition, level: u16) -> Option<Blocker> {
+        let level_idx = (level - 1) as usize;
+        for prerequisite in &definition.prerequisites {
+            if let Some(&required) = prerequisite.required_levels.get(level_idx)
+                && (self.level(&prerequisite.structure_id) as u32) < required
+            {
+                return Some(Blocker::Prerequisite { structure_id: prerequisite.structure_id.clone(), level: required as u16 });
+            }
+        }
+        definition.costs.get(level_idx).and_then(|cost| self.afford(cost))
+    }
+
+    fn afford(&self, cost: &Resources) -> Option<Blocker> {
+        cost.amounts().into_iter().enumerate()
+            .filter(|(_, amount)| *amount > 0)
+            .find_map(|(resource, amount)| {
+                let capacity = self.storage(resource);
+                if amount > capacity {
+                    Some(Blocker::Unstorable { resource, amount, capacity })
+                } else if amount > self.stock[resource] && !self.produces(resource) {
+                    Some(Blocker::Unproduced { resource, amount })
+                } else {
+                    None
+                }
+            })
+    }
+
+    /// Why `level` of a structure cannot be reached, as links such as "orbital_shipyard Lv1
+    /// needs gas_refinery Lv1" joined with arrows, following each requirement back to where
+    /// it breaks. `seen` holds the structures already on the chain, so a circle ends it.
+    fn explain_structure(&self, structure_id: &StructureId, level: u16, seen: &mut Vec<StructureId>) -> String {
+        let subject = format!("{} Lv{}", structure_id, level);
+        let blocker = self.structures.get(structure_id)
+            .and_then(|definition| self.blocker(&definition, level));
+        match blocker {
+            Some(blocker) => {
+                seen.push(structure_id.clone());
+                self.explain(subject, blocker, seen)
+            }
+            None => subject,
+        }
+    }
+
+    fn explain(&self, subject: String, blocker: Blocker, seen: &mut Vec<StructureId>) -> String {
+        let (link, next) = match blocker {
+            Blocker::Prerequisite { structure_id, .. } if !self.structures.contains(&structure_id) => {
+                return format!("{} needs unknown structure {}", subject, structure_id);
+            }
+            Blocker::Prerequisite { structure_id, level } => {
+                let next = self.level(&structure_id) + 1;
+                (format!("{} needs {} Lv{}", subject, structure_id, level), (structure_id, next))
+            }
+            Blocker::Unstorable { resource, amount, capacity } => {
+                return format!(
+                    "{} costs {} {}, more than the {} every reachable store holds together",
+                    subject, amount, RESOURCE_NAMES[resource], capacity,
+                );
+            }
+            Blocker::Unproduced { resource, amount } => {
+                let name = RESOURCE_NAMES[resource];
+                let link = format!("{} costs {} {}, more than the starting {}", subject, amount, name, self.stock[resource]);
+                match self.next_producer(resource) {
+                    Some((producer, level)) => (format!("{}, and only {} produces it", link, producer), (producer.clone(), level)),
+                    None => return format!("{}, and nothing produces it", link),
+                }
+            }
+        };
+
+        let (structure_id, level) = next;
+        if seen.contains(&structure_id) {
+            return format!("{}, closing the circle", link);
+        }
+        format!("{} -> {}", link, self.explain_structure(&structure_id, level, seen))
+    }
+}
+
+fn read(path: &Path, report: &mut ValidationReport) -> Option<String> {
+    configs::read_config_file(path)
+        .map_err(|error| report.add(path, None, None, format!("Cannot be read: {}", error.source)))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use serde_json::Value;
+
+    use super::*;
+    use crate::test_support::{self, PLANET_NAMES_FIXTURE, SHIPS_FIXTURE, STRUCTURES_FIXTURE};
+
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir = test_support::temp_dir(name);
+        let paths = DataFilePaths::in_dir(&dir);
+        fs::write(&paths.structures, STRUCTURES_FIXTURE).unwrap();
+        fs::write(&paths.ships, SHIPS_FIXTURE).unwrap();
+        fs::write(&paths.planet_names, PLANET_NAMES_FIXTURE).unwrap();
+        fs::write(&paths.player_names, include_str!("../../../tests/fixtures/player_names.json")).unwrap();
+        dir
+    }
+
+    /// Replaces `from` with `to` in one of the data files, which must contain it.
+    fn corrupt(path: &Path, from: &str, to: &str) {
+        let json = fs::read_to_string(path).unwrap();
+        assert!(json.contains(from), "{} does not contain {}", path.display(), from);
+        fs::write(path, json.replacen(from, to, 1)).unwrap();
+    }
+
+    /// Edits the structures of the data files at `paths` as JSON.
+    fn edit_structures(paths: &DataFilePaths, edit: impl Fn(&str, &mut Value)) {
+        let mut structures: Vec<Value> = serde_json::from_str(&fs::read_to_string(&paths.structures).unwrap()).unwrap();
+        for structure in &mut structures {
+            let structure_id = structure["id"].as_str().unwrap().to_string();
+            edit(&structure_id, structure);
+        }
+        fs::write(&paths.structures, serde_json::to_string(&structures).unwrap()).unwrap();
+    }
+
+    fn messages<'a>(report: &'a ValidationReport, file: &Path) -> Vec<&'a str> {
+        report.issues.iter().filter(|issue| issue.file == file).map(|issue| issue.message.as_str()).collect()
+    }
+
+    #[test]
+    fn test_shipped_and_fixture_data_are_valid() {
+        let shipped = DataFilePaths::in_dir(format!("{}/../data", env!("CARGO_MANIFEST_DIR")));
+        let report = validate_data_files(&shipped);
+        assert!(report.is_valid(), "{report}");
+
+        let report = validate_data_files(&DataFilePaths::in_dir(fixture_dir("validate_fixtures")));
+        assert!(report.is_valid(), "{report}");
+    }
+
+    #[test]
+    fn test_report_attributes_every_broken_file() {
+        let paths = DataFilePaths::in_dir(fixture_dir("validate_broken"));
+        corrupt(&paths.structures, r#""category": "production""#, r#""category": "mystery""#);
+        corrupt(&paths.structures, r#""hitpoints": ["#, r#""hitpoints": [9999999, "#);
+        corrupt(&paths.ships, r#""counters": ["#, r#""counters": ["no_such_ship", "#);
+        corrupt(&paths.planet_names, r#""suffixes""#, r#""sufixes""#);
+
+        let report = validate_data_files(&paths);
+        let in_file = |path: &Path| report.issues.iter().filter(|issue| issue.file == path).collect::<Vec<_>>();
+
+        // Both structure problems are reported, not just the first
+        let structures = in_file(&paths.structures);
+        assert!(structures.iter().any(|issue| issue.field.as_deref() == Some("category") && issue.entry.is_some()), "{report}");
+        assert!(structures.iter().any(|issue| issue.field.as_deref() == Some("hitpoints") && issue.message.contains("9999999")), "{report}");
+
+        let ships = in_file(&paths.ships);
+        assert_eq!(ships.len(), 1, "{report}");
+        assert_eq!(ships[0].field.as_deref(), Some("counters"));
+        assert!(ships[0].entry.is_some() && ships[0].message.contains("no_such_ship"), "{report}");
+
+        let planet_names = in_file(&paths.planet_names);
+        assert_eq!(planet_names.len(), 1, "{report}");
+        assert!(planet_names[0].message.contains("suffixes"), "{report}");
+
+        assert!(in_file(&paths.player_names).is_empty(), "{report}");
+    }
+
+    #[test]
+    fn test_cross_validation_catches_unknown_references() {
+        let paths = DataFilePaths::in_dir(fixture_dir("validate_cross"));
+        corrupt(&paths.structures, r#""structure_id": ""#, r#""structure_id": "no_such_"#);
+        corrupt(&paths.ships, r#""required_shipyard_level": 1"#, r#""required_shipyard_level": 99"#);
+        fs::remove_file(&paths.player_names).unwrap();
+
+        let report = validate_data_files(&paths);
+        let fields: Vec<_> = report.issues.iter().map(|issue| (&issue.file, issue.field.as_deref())).collect();
+        assert!(fields.contains(&(&paths.structures, Some("prerequisites"))), "{report}");
+        assert!(fields.contains(&(&paths.ships, Some("required_shipyard_level"))), "{report}");
+        assert!(fields.contains(&(&paths.player_names, None)), "{report}");
+    }
+
+    #[test]
+    fn test_soft_locked_opening_is_rejected_with_the_broken_chain() {
+        let dir = fixture_dir("validate_soft_lock");
+        let paths = DataFilePaths::in_dir(&dir);
+        let shipped = DataFilePaths::in_dir(format!("{}/../data", env!("CARGO_MANIFEST_DIR")));
+        fs::copy(&shipped.structures, &paths.structures).unwrap();
+        fs::copy(&shipped.ships, &paths.ships).unwrap();
+
+        // Gas only comes from the refinery, which needs the shipyard, which needs the refinery
+        edit_structures(&paths, |structure_id, structure| match structure_id {
+            PLANETARY_CAPITAL => {
+                for production in structure["production"].as_array_mut().unwrap() {
+                    production["gas"] = Value::from(0);
+                }
+            }
+            "gas_refinery" => structure["prerequisites"] = serde_json::json!([{ "structure_id": SHIPYARD_ID, "required_levels": [1] }]),
+            _ => {}
+        });
+
+        let report = validate_data_files(&paths);
+        assert_eq!(messages(&report, &paths.structures), [
+            "Nothing producing gas can ever be built: gas_refinery Lv1 needs orbital_shipyard Lv1 -> \
+             orbital_shipyard Lv1 needs gas_refinery Lv1, closing the circle",
+        ], "{report}");
+        assert_eq!(messages(&report, &paths.ships), [
+            "Colonies can never be founded, as no ark can be built: ark needs orbital_shipyard Lv3 -> \
+             orbital_shipyard Lv1 needs gas_refinery Lv1 -> gas_refinery Lv1 needs orbital_shipyard Lv1, closing the circle",
+        ], "{report}");
+    }
+
+    #[test]
+    fn test_costs_beyond_the_starting_stock_need_a_producer() {
+        let paths = DataFilePaths::in_dir(fixture_dir("validate_unproduced"));
+        edit_structures(&paths, |structure_id, structure| {
+            if structure_id == PLANETARY_CAPITAL {
+                for production in structure["production"].as_array_mut().unwrap() {
+                    production["gas"] = Value::from(0);
+                }
+            }
+        });
+
+        // The starting 125 gas pays for the first two shipyard levels, but not the third arks need
+        let report = validate_data_files(&paths);
+        assert_eq!(messages(&report, &paths.structures), ["Nothing produces gas"], "{report}");
+        assert_eq!(messages(&report, &paths.ships), [
+            "Colonies can never be founded, as no ark can be built: ark needs orbital_shipyard Lv3 -> \
+             orbital_shipyard Lv3 costs 200 gas, more than the starting 125, and nothing produces it",
+        ], "{report}");
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::engine::configs::ship_config::ShipId;
+use crate::engine::planet::PlanetId;
+use crate::engine::player::PlayerId;
+use crate::engine::resources::Resources;
+use crate::engine::ship::{FleetId, Ship, ShipInstanceId};
+
+/// A saved fleet composition: how many ships of each type it holds.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FleetTemplate {
+    pub ships: BTreeMap<ShipId, u32>,
+}
+
+impl FleetTemplate {
+    pub fn ship_count(&self) -> u32 {
+        self.ships.values().fold(0, |total, count| total.saturating_add(*count))
+    }
+}
+
+impl fmt::Display for FleetTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<_> = self.ships.iter()
+            .map(|(ship_type, count)| format!("{}x {}", count, ship_type))
+            .collect();
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Fleet {
+    pub id: FleetId,
+    pub name: String,
+    pub ships: Vec<ShipInstanceId>,
+    pub location: PlanetId,
+    /// Parked on purpose: left out of idle warnings until hostiles come near
+    #[serde(default)]
+    pub sentry: bool,
+    /// Planet where hostiles were seen arriving when the sentry last woke, reported to the
+    /// owner until the next round is processed
+    #[serde(default)]
+    pub woken_by: Option<String>,
+    /// Resources loaded aboard, lost with the ships carrying them
+    #[serde(default)]
+    pub cargo: Resources,
+}
+
+impl Fleet {
+    pub fn new(id: FleetId, name: String, location: PlanetId) -> Self {
+        Self {
+            id,
+            name,
+            ships: Vec::new(),
+            location,
+            sentry: false,
+            woken_by: None,
+            cargo: Resources::default(),
+        }
+    }
+
+    pub fn add_ship(&mut self, ship_id: ShipInstanceId) {
+        if !self.ships.contains(&ship_id) {
+            self.ships.push(ship_id);
+        }
+    }
+
+    pub fn remove_ship(&mut self, ship_id: &ShipInstanceId) -> bool {
+        if let Some(pos) = self.ships.iter().position(|s| s == ship_id) {
+            self.ships.remove(pos);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ships.is_empty()
+    }
+
+    pub fn ship_count(&self) -> usize {
+        self.ships.len()
+    }
+}
+
+/// Raw state of a fleet for the `inspect` debug command, with the owner's record of each of
+/// its ships; a ship the owner has no record of shows as null.
+#[derive(serde::Serialize)]
+pub struct FleetDebugView<'a> {
+    pub owner: &'a PlayerId,
+    #[serde(flatten)]
+    pub fleet: &'a Fleet,
+    pub ship_records: Vec<Option<&'a Ship>>,
+}
@@ -0,0 +1,940 @@
+use std::fmt;
+
+use crate::engine::configs::ship_config::{self, ShipConfig, ShipId};
+use crate::engine::configs::structure_config::{self, StructureConfig};
+use crate::engine::fleet::FleetTemplate;
+use crate::engine::game_state::{Alert, FleetSighting, ShieldEstimate};
+use crate::engine::planet::{Allocation, RallyPoint, StorageAdvice, StorageWarning, StructureHit};
+use crate::engine::player::PlayerId;
+use crate::engine::resources::Resources;
+use crate::engine::ship::{FleetId, ShipInstanceId};
+use crate::engine::structure::StructureId;
+
+/// How a fleet or ship arrival without combat was resolved.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum ArrivalKind {
+    Friendly,
+    UndefendedEnemy,
+    Neutral,
+}
+
+/// How the winning side won the game.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum VictoryKind {
+    #[default]
+    Elimination,
+    /// Held `held` of the `total` key planets for `turns` consecutive full turns
+    Domination { held: usize, total: usize, turns: u32 },
+}
+
+impl fmt::Display for VictoryKind {
+    /// What the winners did, e.g. "conquered the entire system".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VictoryKind::Elimination => write!(f, "conquered the entire system"),
+            VictoryKind::Domination { held, total, turns } => write!(
+                f, "held {} of {} key planets for {} turns", held, total, turns
+            ),
+        }
+    }
+}
+
+/// Outcome of applying a command effect or processing a turn.
+/// Events carry the data needed to render output; `Display` renders the CLI text.
+/// Serialized with the variant name in a `type` field, the form external tools read.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type")]
+pub enum GameEvent {
+    Info { message: String },
+    StructureQueued {
+        structure_id: StructureId,
+        cost: Resources,
+        turns: u32,
+        warnings: Vec<StorageWarning>,
+        /// Production that will outgrow storage once the structure is done; never blocks the build
+        #[serde(default)]
+        advice: Vec<StorageAdvice>,
+    },
+    UpgradeQueued { structure_id: StructureId, cost: Resources, turns: u32, warnings: Vec<StorageWarning> },
+    ShipQueued {
+        ship_id: ShipId,
+        ship_instance_id: ShipInstanceId,
+        cost: Resources,
+        turns: u32,
+        warnings: Vec<StorageWarning>,
+        /// Planet the ship is carried to once built, when not the shipyard's own
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        delivery_planet: Option<String>,
+    },
+    ShipRetrofitQueued {
+        ship_instance_id: ShipInstanceId,
+        from_type: ShipId,
+        to_type: ShipId,
+        cost: Resources,
+        turns: u32,
+        warnings: Vec<StorageWarning>,
+    },
+    ActionCancelled {
+        planet_name: String,
+        refunded: Resources,
+        penalty: Resources,
+        penalty_percent: u32,
+        wasted: Option<Resources>,
+    },
+    FleetCreated { fleet_id: FleetId, name: String, ship_count: usize },
+    ShipsAddedToFleet { fleet_name: String, ship_count: usize },
+    ShipsRemovedFromFleet { fleet_name: String, ship_count: usize },
+    FleetDisbanded { fleet_name: String, ship_count: usize },
+    FleetsMerged { into_name: String, from_name: String, ship_count: usize },
+    FleetSplit { fleet_id: FleetId, name: String, source_name: String, ship_count: usize },
+    FleetTemplateSaved { name: String, template: FleetTemplate, replaced: bool },
+    /// `skipped` lists each ship left out of the queue with the reason
+    /// Outcome of `build_all` per planet, by planet name: None when queued, else the reason it was skipped
+    StructureQueuedOnPlanets { structure_name: String, results: Vec<(String, Option<String>)> },
+    FleetTemplateQueued { template_name: String, planet_name: String, queued: usize, skipped: Vec<(ShipId, String)> },
+    RallyPointSet { planet_name: String, rally_point: Option<RallyPoint> },
+    ShipsGarrisoned { planet_name: String, ship_count: usize },
+    ShipsUngarrisoned { planet_name: String, ship_count: usize },
+    /// `production` is the planet's base output per turn under the new allocation
+    AllocationSet { planet_name: String, allocation: Option<Allocation>, production: Resources },
+    ShieldOvercharged { planet_name: String, added: u32, shield_hp: u32, energy_spent: u32 },
+    /// `energy` is what the structure draws while running
+    StructurePowerChanged { planet_name: String, structure_name: String, on: bool, energy: u32 },
+    /// An energy deficit shut structures down, or spare energy restarted ones shut down earlier
+    PowerRationed { player_id: PlayerId, planet_name: String, shut_down: Vec<String>, restarted: Vec<String> },
+    /// Unrest on the planet reached the strike threshold; its next production yields nothing
+    WorkersStrike { player_id: PlayerId, planet_name: String, unrest: u32 },
+    /// `hops` is `None` when the fleet has no owned planet to reach at all
+    FleetAttrition { player_id: PlayerId, fleet_name: String, hops: Option<u32>, ships_worn: usize, shield_lost: u32 },
+    FleetResupplied { player_id: PlayerId, fleet_name: String, shield_restored: u32 },
+    GatesLinked { first_name: String, second_name: String, distance: u8 },
+    GateLinkCollapsed { first_name: String, second_name: String, reason: String },
+    AliasDefined { name: String, expansion: String, replaced: bool },
+    AliasRemoved { name: String },
+    /// Note numbers and counts only: the text stays out of shared output such as event logs
+    NoteAdded { number: usize },
+    NoteDone { number: usize, remaining: usize },
+    /// A game option changed for everyone; `value` is as the options listing shows it
+    GameOptionChanged { player_name: String, key: String, value: String },
+    RallyPointBroken { player_id: PlayerId, planet_name: String, fleet_id: FleetId, reason: String },
+    FleetMoveOrdered {
+        fleet_id: FleetId,
+        fleet_name: String,
+        source_name: String,
+        target_name: String,
+        turns: u8,
+    },
+    /// Outcome of a multi-fleet `fleet move` per fleet: None when ordered, else the reason it was skipped
+    FleetsMoveOrdered { target_name: String, results: Vec<(FleetId, Option<String>)> },
+    ShipMoveOrdered {
+        ship_instance_id: ShipInstanceId,
+        ship_type: ShipId,
+        source_name: String,
+        target_name: String,
+        turns: u8,
+    },
+    BombardmentStarted {
+        fleet_id: FleetId,
+        fleet_name: String,
+        target_name: String,
+        bombardment_power: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        target_structure: Option<StructureId>,
+    },
+    BombardmentCancelled { fleet_id: FleetId },
+    FleetSentrySet { fleet_id: FleetId, sentry: bool },
+    /// `carried` is the fleet's whole cargo after loading, out of `capacity`
+    CargoLoaded { fleet_id: FleetId, planet_name: String, cargo: Resources, carried: u32, capacity: u32 },
+    /// `wasted` is what the planet had no storage left for
+    CargoUnloaded { player_id: PlayerId, fleet_id: FleetId, planet_name: String, stored: Resources, wasted: Resources },
+    /// Cargo gone with the ships that carried it
+    CargoLost { player_id: PlayerId, fleet_name: String, lost: Resources },
+    /// `ark_bonus` is what the ark used up founding the colony left in its storage
+    PlanetColonized { fleet_id: FleetId, planet_name: String, player_name: String, ark_bonus: Resources },
+    RuinClaimed { planet_name: String, description: String },
+    ColonizationFailed { planet_name: String, reason: String },
+    /// The cost of colonizing a neutral planet, taken from the colonizer's nearest planet
+    ColonizationPaid { planet_name: String, origin_name: String, cost: Resources },
+    /// The cost of a failed colonization, returned to the planet that paid it
+    ColonizationRefunded { planet_name: String, origin_name: String, refunded: Resources },
+    TurnEnded { player_name: String },
+    /// `player_name` changed the game with a developer command, not by playing
+    DevAction { player_name: String, description: String },
+    /// The round of `turn` goes on; `player_names` still have to end their turn, in turn order
+    WaitingOn { turn: u32, player_names: Vec<String> },
+    /// Header emitted before the events produced by end-of-round processing
+    RoundProcessing { turn: u32 },
+    /// `player_id` is the bombarding player
+    ShieldsDamaged {
+        player_id: PlayerId,
+        fleet_id: FleetId,
+        planet_name: String,
+        shields_before: u32,
+        shields_after: u32,
+    },
+    /// Credited to the player whose hit brought the shield down; `overflow` passed through
+    ShieldsDestroyed { player_id: PlayerId, fleet_id: FleetId, planet_name: String, shields_before: u32, overflow: u32 },
+    /// A bombardment landing after another hit brought the shields down this round
+    BombardmentOverflow { player_id: PlayerId, fleet_id: FleetId, planet_name: String, damage: u32 },
+    /// Where the damage passing through the shield landed, in order
+    StructuresBombarded { player_id: PlayerId, fleet_id: FleetId, planet_name: String, hits: Vec<StructureHit> },
+    /// The structure a bombardment aimed at is not on the planet, so its damage fell by the usual order
+    BombardmentTargetMissing { player_id: PlayerId, fleet_id: FleetId, planet_name: String, structure_id: StructureId },
+    /// Told to the owner of a planet whose structure bombardment tore down
+    StructureDestroyed { player_id: PlayerId, planet_name: String, structure_id: StructureId, attacker_name: String },
+    ConstructionCompleted { player_id: PlayerId, structure_id: StructureId, planet_name: String },
+    ConstructionFailed { player_id: PlayerId, structure_id: StructureId, planet_name: String, reason: String },
+    /// A finished structure build waits for a prerequisite lost during construction
+    ConstructionHalted {
+        player_id: PlayerId,
+        structure_id: StructureId,
+        planet_name: String,
+        awaiting: StructureId,
+        required_level: u32,
+        turns: u32,
+    },
+    /// A finished build that could not be placed; its reserved resources go back to the planet
+    ConstructionRejected {
+        player_id: PlayerId,
+        structure_id: StructureId,
+        planet_name: String,
+        reason: String,
+        refunded: Resources,
+    },
+    /// A pending action was dropped because the planet or fleet it depends on is no longer the player's.
+    /// `refund` names the planet still theirs that got back part of what it reserved, if any.
+    ActionOrphaned {
+        player_id: PlayerId,
+        action: String,
+        reason: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        refund: Option<(String, Resources)>,
+    },
+    UpgradeCompleted { player_id: PlayerId, structure_id: StructureId, planet_name: String },
+    UpgradeFailed { player_id: PlayerId, structure_id: StructureId, planet_name: String, reason: String },
+    /// A repeating upgrade completed and queued the structure's next level
+    UpgradeRepeated { player_id: PlayerId, structure_id: StructureId, planet_name: String, cost: Resources, turns: u32 },
+    /// A repeating upgrade completed but could not queue the next level
+    UpgradeRepeatStopped { player_id: PlayerId, structure_id: StructureId, planet_name: String, reason: String },
+    /// `fleet_name` is the fleet the planet's rally point sent the ship to
+    ShipBuilt {
+        player_id: PlayerId,
+        ship_instance_id: ShipInstanceId,
+        ship_type: ShipId,
+        planet_name: String,
+        fleet_name: Option<String>,
+    },
+    ShipRetrofitted {
+        player_id: PlayerId,
+        ship_instance_id: ShipInstanceId,
+        from_type: ShipId,
+        to_type: ShipId,
+        planet_name: String,
+    },
+    /// The ship left the shipyard or was lost before its retrofit finished
+    ShipRetrofitFailed { player_id: PlayerId, ship_instance_id: ShipInstanceId, planet_name: String, reason: String },
+    FleetArrived { player_id: PlayerId, fleet_id: FleetId, planet_name: String, kind: ArrivalKind },
+    ShipArrived { player_id: PlayerId, ship_instance_id: ShipInstanceId, planet_name: String, kind: ArrivalKind },
+    Battle {
+        /// The attacking fleet, or the ship when a lone ship attacked
+        fleet_id: FleetId,
+        planet_name: String,
+        attacker_name: String,
+        defender_name: String,
+        attacker_strength: u32,
+        defender_strength: u32,
+        attacker_wins: bool,
+        ships_destroyed: usize,
+    },
+    /// A colonizing fleet fighting the garrison of an enemy planet; the loser's ships are destroyed
+    GarrisonAssault {
+        fleet_id: FleetId,
+        planet_name: String,
+        attacker_name: String,
+        defender_name: String,
+        attacker_strength: u32,
+        defender_strength: u32,
+        attacker_wins: bool,
+        ships_destroyed: usize,
+    },
+    /// `player_id` is the bombarding player; `intercepted` is the damage the garrison's fire absorbed
+    BombardmentIntercepted { player_id: PlayerId, fleet_id: FleetId, planet_name: String, intercepted: u32 },
+    /// `player_names` lists the winning side: a single player, or a team in turn order
+    Victory {
+        player_names: Vec<String>,
+        turn: u32,
+        #[serde(default)]
+        kind: VictoryKind,
+    },
+    /// `player_name` has held a majority of the key planets long enough to win by domination
+    /// in `turns_left` more full turns
+    DominationWarning { player_name: String, held: usize, total: usize, turns_left: u32 },
+    /// Processing the round of `turn` took longer than the `slow_round` option allows
+    SlowRound { turn: u32, elapsed_ms: u64, threshold_ms: u32 },
+    /// The engine was misused in a way release builds carry on past; worth reporting as a bug
+    EngineWarning { message: String },
+    /// `protection_turns_left` counts this turn and those after it in which the protected
+    /// expansion still holds
+    TurnBegan {
+        turn: u32,
+        #[serde(default)]
+        protection_turns_left: u32,
+    },
+    /// `attacker_name` bombarded a planet `observer_id` has in sensor range
+    BombardmentObserved { observer_id: PlayerId, attacker_name: String, planet_name: String, shields: ShieldEstimate },
+    /// A planet `observer_id` has in sensor range lost its shields to `attacker_name`'s bombardment
+    ShieldsDownObserved { observer_id: PlayerId, attacker_name: String, planet_name: String, own_planet: bool },
+    /// Another player's fleet arrived where `observer_name` could see it
+    FleetDetected { observer_id: PlayerId, observer_name: String, sighting: FleetSighting },
+    /// `alerts` are conditions on the player's planets, most urgent first, and `sightings`
+    /// the fleets this player spotted during the last round processing. `notes` counts the
+    /// player's notes without revealing them to whoever else is at the screen.
+    PlayerTurnStarted {
+        player_name: String,
+        alerts: Vec<Alert>,
+        sightings: Vec<FleetSighting>,
+        #[serde(default)]
+        notes: usize,
+    },
+}
+
+/// Who should see a piece of output when players do not share a screen.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutputTarget {
+    /// Whoever holds the turn when the output is emitted
+    CurrentPlayer,
+    Player(PlayerId),
+    Broadcast,
+}
+
+impl GameEvent {
+    /// Command results go to the player who issued them, round results to the player they
+    /// concern, and turn and round banners to everyone.
+    pub fn target(&self) -> OutputTarget {
+        match self {
+            GameEvent::RallyPointBroken { player_id, .. }
+            | GameEvent::PowerRationed { player_id, .. }
+            | GameEvent::WorkersStrike { player_id, .. }
+            | GameEvent::FleetAttrition { player_id, .. }
+            | GameEvent::FleetResupplied { player_id, .. }
+            | GameEvent::CargoUnloaded { player_id, .. }
+            | GameEvent::CargoLost { player_id, .. }
+            | GameEvent::ShieldsDamaged { player_id, .. }
+            | GameEvent::ShieldsDestroyed { player_id, .. }
+            | GameEvent::BombardmentOverflow { player_id, .. }
+            | GameEvent::StructuresBombarded { player_id, .. }
+            | GameEvent::BombardmentTargetMissing { player_id, .. }
+            | GameEvent::StructureDestroyed { player_id, .. }
+            | GameEvent::BombardmentIntercepted { player_id, .. }
+            | GameEvent::ConstructionCompleted { player_id, .. }
+            | GameEvent::ConstructionFailed { player_id, .. }
+            | GameEvent::ConstructionHalted { player_id, .. }
+            | GameEvent::ConstructionRejected { player_id, .. }
+            | GameEvent::ActionOrphaned { player_id, .. }
+            | GameEvent::UpgradeCompleted { player_id, .. }
+            | GameEvent::UpgradeFailed { player_id, .. }
+            | GameEvent::UpgradeRepeated { player_id, .. }
+            | GameEvent::UpgradeRepeatStopped { player_id, .. }
+            | GameEvent::ShipBuilt { player_id, .. }
+            | GameEvent::ShipRetrofitted { player_id, .. }
+            | GameEvent::ShipRetrofitFailed { player_id, .. }
+            | GameEvent::FleetArrived { player_id, .. }
+            | GameEvent::ShipArrived { player_id, .. } => OutputTarget::Player(player_id.clone()),
+            GameEvent::FleetDetected { observer_id, .. }
+            | GameEvent::BombardmentObserved { observer_id, .. }
+            | GameEvent::ShieldsDownObserved { observer_id, .. } => OutputTarget::Player(observer_id.clone()),
+            GameEvent::TurnEnded { .. }
+            | GameEvent::DevAction { .. }
+            | GameEvent::WaitingOn { .. }
+            | GameEvent::RoundProcessing { .. }
+            | GameEvent::Battle { .. }
+            | GameEvent::GarrisonAssault { .. }
+            | GameEvent::GateLinkCollapsed { .. }
+            | GameEvent::GameOptionChanged { .. }
+            | GameEvent::Victory { .. }
+            | GameEvent::DominationWarning { .. }
+            | GameEvent::SlowRound { .. }
+            | GameEvent::EngineWarning { .. }
+            | GameEvent::TurnBegan { .. } => OutputTarget::Broadcast,
+            // Emitted after the turn has passed, so this is the player whose sightings it lists
+            GameEvent::PlayerTurnStarted { .. } => OutputTarget::CurrentPlayer,
+            _ => OutputTarget::CurrentPlayer,
+        }
+    }
+}
+
+/// How event text names structures and ship types: by display name and id when the configs are
+/// at hand, by bare id otherwise.
+#[derive(Debug, Clone, Copy, Default)]
+struct Names<'a> {
+    structures: Option<&'a StructureConfig>,
+    ships: Option<&'a ShipConfig>,
+}
+
+impl Names<'_> {
+    fn structure(&self, id: &StructureId) -> String {
+        self.structures.map_or_else(|| id.clone(), |structures| structure_config::fmt_structure(structures, id))
+    }
+
+    fn ship(&self, id: &ShipId) -> String {
+        self.ships.map_or_else(|| id.clone(), |ships| ship_config::fmt_ship(ships, id))
+    }
+}
+
+/// An event rendered for players, naming structures and ship types as the configs define them.
+pub struct NamedEvent<'a> {
+    event: &'a GameEvent,
+    names: Names<'a>,
+}
+
+impl GameEvent {
+    /// The event as players read it: `Display` with structures and ship types written as
+    /// "Display Name (id)".
+    pub fn named<'a>(&'a self, structures: &'a StructureConfig, ships: &'a ShipConfig) -> NamedEvent<'a> {
+        NamedEvent { event: self, names: Names { structures: Some(structures), ships: Some(ships) } }
+    }
+}
+
+impl fmt::Display for NamedEvent<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.event.write(f, self.names)
+    }
+}
+
+/// Renders with bare ids, for output that has no configs to hand.
+impl fmt::Display for GameEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, Names::default())
+    }
+}
+
+impl GameEvent {
+    fn write(&self, f: &mut fmt::Formatter<'_>, names: Names<'_>) -> fmt::Result {
+        match self {
+            GameEvent::Info { message } => write!(f, "{}", message),
+            GameEvent::StructureQueued { structure_id, cost, turns, warnings, advice } => {
+                write!(
+                    f, "Construction of {} queued. Resources spent: {}. Turns to complete: {}",
+                    names.structure(structure_id), cost, turns
+                )?;
+                write_storage_warnings(f, warnings)?;
+                for advice in advice {
+                    write!(f, "\n  advisory: {}", advice)?;
+                }
+                Ok(())
+            }
+            GameEvent::UpgradeQueued { structure_id, cost, turns, warnings } => {
+                write!(
+                    f, "Upgrade of {} queued. Resources spent: {}. Turns to complete: {}",
+                    names.structure(structure_id), cost, turns
+                )?;
+                write_storage_warnings(f, warnings)
+            }
+            GameEvent::ShipQueued { ship_id, ship_instance_id, cost, turns, warnings, delivery_planet } => {
+                write!(
+                    f, "Ship construction queued: {} as {}. Resources spent: {}. Turns to complete: {}",
+                    names.ship(ship_id), ship_instance_id, cost, turns
+                )?;
+                if let Some(delivery_planet) = delivery_planet {
+                    write!(f, ", delivery to {} included", delivery_planet)?;
+                }
+                write_storage_warnings(f, warnings)
+            }
+            GameEvent::ShipRetrofitQueued { ship_instance_id, from_type, to_type, cost, turns, warnings } => {
+                write!(
+                    f, "Retrofit queued: {} from {} to {}. Resources spent: {}. Turns to complete: {}",
+                    ship_instance_id, names.ship(from_type), names.ship(to_type), cost, turns
+                )?;
+                write_storage_warnings(f, warnings)
+            }
+            GameEvent::ActionCancelled { planet_name, refunded, penalty, penalty_percent, wasted } => {
+                write!(
+                    f, "Action cancelled on planet {}. Resources refunded: {}. Cancellation penalty ({}%): {}",
+                    planet_name, refunded, penalty_percent, penalty
+                )?;
+                match wasted {
+                    Some(wasted) => write!(
+                        f, ". Wasted (storage full, penalty applied first): {}", wasted
+                    ),
+                    None => Ok(()),
+                }
+            }
+            GameEvent::FleetCreated { fleet_id, name, ship_count } => write!(
+                f, "Fleet '{}' ({}) created with {} ship(s)", name, fleet_id, ship_count
+            ),
+            GameEvent::ShipsAddedToFleet { fleet_name, ship_count } => write!(
+                f, "Added {} ship(s) to fleet '{}'", ship_count, fleet_name
+            ),
+            GameEvent::ShipsRemovedFromFleet { fleet_name, ship_count } => write!(
+                f, "Removed {} ship(s) from fleet '{}'", ship_count, fleet_name
+            ),
+            GameEvent::FleetDisbanded { fleet_name, ship_count } => write!(
+                f, "Fleet '{}' disbanded. {} ship(s) are now standalone.", fleet_name, ship_count
+            ),
+            GameEvent::FleetsMerged { into_name, from_name, ship_count } => write!(
+                f, "Fleet '{}' merged into '{}' with {} ship(s).", from_name, into_name, ship_count
+            ),
+            GameEvent::FleetSplit { fleet_id, name, source_name, ship_count } => write!(
+                f, "Fleet '{}' ({}) split from '{}' with {} ship(s)", name, fleet_id, source_name, ship_count
+            ),
+            GameEvent::FleetTemplateSaved { name, template, replaced } => write!(
+                f, "Fleet template '{}' {}: {}", name, if *replaced { "updated" } else { "saved" }, template
+            ),
+            GameEvent::AliasDefined { name, expansion, replaced } => write!(
+                f, "Alias '{}' {}: {}", name, if *replaced { "updated" } else { "defined" }, expansion
+            ),
+            GameEvent::AliasRemoved { name } => write!(f, "Alias '{}' removed", name),
+            GameEvent::NoteAdded { number } => write!(f, "Note {} saved. 'note list' shows your notes", number),
+            GameEvent::NoteDone { number, remaining } => write!(f, "Note {} done, {} left", number, remaining),
+            GameEvent::GameOptionChanged { player_name, key, value } => write!(
+                f, "{} set game option '{}' to {}", player_name, key, value
+            ),
+            GameEvent::StructureQueuedOnPlanets { structure_name, results } => {
+                let queued = results.iter().filter(|(_, skipped)| skipped.is_none()).count();
+                write!(f, "{}: queued on {} of {} planet(s)", structure_name, queued, results.len())?;
+                let width = results.iter().map(|(planet_name, _)| planet_name.chars().count()).max().unwrap_or(0);
+                for (planet_name, skipped) in results {
+                    match skipped {
+                        None => write!(f, "\n  {:<width$}  queued", planet_name)?,
+                        Some(reason) => write!(f, "\n  {:<width$}  skipped: {}", planet_name, reason)?,
+                    }
+                }
+                Ok(())
+            }
+            GameEvent::FleetTemplateQueued { template_name, planet_name, queued, skipped } => {
+                write!(f, "Template '{}' on {}: {} ship(s) queued", template_name, planet_name, queued)?;
+                if skipped.is_empty() {
+                    return Ok(());
+                }
+                // Group identical shortfalls, e.g. "2x interceptor (not enough resources)"
+                let mut groups: Vec<(&(ShipId, String), usize)> = Vec::new();
+                for entry in skipped {
+                    match groups.last_mut() {
+                        Some((last, count)) if *last == entry => *count += 1,
+                        _ => groups.push((entry, 1)),
+                    }
+                }
+                let groups: Vec<_> = groups.iter()
+                    .map(|((ship_type, reason), count)| format!("{}x {}: {}", count, names.ship(ship_type), reason))
+                    .collect();
+                write!(f, ". Not queued: {}", groups.join(", "))
+            }
+            GameEvent::RallyPointSet { planet_name, rally_point } => match rally_point {
+                Some(rally_point) => write!(f, "Ships built on {} will join {}", planet_name, rally_point),
+                None => write!(f, "Ships built on {} will remain standalone", planet_name),
+            },
+            GameEvent::ShipsGarrisoned { planet_name, ship_count } => write!(
+                f, "{} ship(s) garrisoned on {}. They defend against invasion and cannot move until ungarrisoned.",
+                ship_count, planet_name
+            ),
+            GameEvent::ShipsUngarrisoned { planet_name, ship_count } => write!(
+                f, "{} ship(s) left the garrison on {} and are now standalone.", ship_count, planet_name
+            ),
+            GameEvent::AllocationSet { planet_name, allocation: Some(allocation), production } => write!(
+                f, "{} now splits its output {}: {} per turn before bonuses", planet_name, allocation, production
+            ),
+            GameEvent::AllocationSet { planet_name, allocation: None, production } => write!(
+                f, "{} is back to the default allocation: {} per turn before bonuses", planet_name, production
+            ),
+            GameEvent::ShieldOvercharged { planet_name, added, shield_hp, energy_spent } => write!(
+                f, "Shields on {} overcharged: +{} HP ({} HP total) until the end of the turn. Energy spent: {}",
+                planet_name, added, shield_hp, energy_spent
+            ),
+            GameEvent::StructurePowerChanged { planet_name, structure_name, on: true, energy } => write!(
+                f, "{} on {} powered on. It draws {} energy per turn again.",
+                structure_name, planet_name, energy
+            ),
+            GameEvent::StructurePowerChanged { planet_name, structure_name, on: false, energy } => write!(
+                f, "{} on {} powered down: no production and {} energy per turn saved. Storage is kept.",
+                structure_name, planet_name, energy
+            ),
+            GameEvent::PowerRationed { planet_name, shut_down, restarted, .. } => {
+                write!(f, "Energy on {}:", planet_name)?;
+                if !shut_down.is_empty() {
+                    write!(f, " deficit shut down {}.", shut_down.join(", "))?;
+                }
+                if !restarted.is_empty() {
+                    write!(f, " restarted {}.", restarted.join(", "))?;
+                }
+                Ok(())
+            }
+            GameEvent::WorkersStrike { planet_name, unrest, .. } => write!(
+                f, "Unrest on {} reached {}: the workers strike and the planet produces nothing next turn. \
+                A morale center calms unrest.",
+                planet_name, unrest
+            ),
+            GameEvent::FleetAttrition { fleet_name, hops, ships_worn, shield_lost, .. } => write!(
+                f, "Fleet {} is out of supply ({}): {} ship(s) lost {} shield in total. \
+                Bring it within range of one of your planets to restore it.",
+                fleet_name,
+                hops.map_or(String::from("no route home"), |hops| format!("{} hops from your nearest planet", hops)),
+                ships_worn, shield_lost
+            ),
+            GameEvent::FleetResupplied { fleet_name, shield_restored, .. } => write!(
+                f, "Fleet {} is back in supply: {} shield restored.",
+                fleet_name, shield_restored
+            ),
+            GameEvent::GatesLinked { first_name, second_name, distance } => write!(
+                f, "Jump gates on {} and {} linked: the planets are now {} turn(s) apart.",
+                first_name, second_name, distance
+            ),
+            GameEvent::GateLinkCollapsed { first_name, second_name, reason } => write!(
+                f, "Jump gate link between {} and {} collapsed: {}.", first_name, second_name, reason
+            ),
+            GameEvent::RallyPointBroken { planet_name, fleet_id, reason, .. } => write!(
+                f, "Rally point on {} cleared: fleet {} {}. New ships will remain standalone.",
+                planet_name, fleet_id, reason
+            ),
+            GameEvent::FleetMoveOrdered { fleet_id, fleet_name, source_name, target_name, turns } => write!(
+                f, "Fleet '{}' ({}) ordered to move from {} to {}. Arrival in {} turn(s).",
+                fleet_name, fleet_id, source_name, target_name, turns
+            ),
+            GameEvent::FleetsMoveOrdered { target_name, results } => {
+                let ordered = results.iter().filter(|(_, skipped)| skipped.is_none()).count();
+                write!(f, "{} of {} fleet(s) ordered to {}", ordered, results.len(), target_name)?;
+                let width = results.iter().map(|(fleet_id, _)| fleet_id.chars().count()).max().unwrap_or(0);
+                for (fleet_id, skipped) in results {
+                    match skipped {
+                        None => write!(f, "\n  {:<width$}  moving", fleet_id)?,
+                        Some(reason) => write!(f, "\n  {:<width$}  skipped: {}", fleet_id, reason)?,
+                    }
+                }
+                Ok(())
+            }
+            GameEvent::ShipMoveOrdered { ship_instance_id, ship_type, source_name, target_name, turns } => write!(
+                f, "Ship {}, {}, ordered to move from {} to {}. Arrival in {} turn(s).",
+                ship_instance_id, names.ship(ship_type), source_name, target_name, turns
+            ),
+            GameEvent::BombardmentStarted { fleet_id, fleet_name, target_name, bombardment_power, target_structure } => {
+                write!(
+                    f, "Fleet '{}' ({}) begins bombarding {} with {} bombardment power.",
+                    fleet_name, fleet_id, target_name, bombardment_power
+                )?;
+                if let Some(structure_id) = target_structure {
+                    write!(f, " Once the shields are down it aims for {}.", names.structure(structure_id))?;
+                }
+                Ok(())
+            }
+            GameEvent::BombardmentCancelled { fleet_id } => write!(
+                f, "Fleet '{}' bombardment cancelled.", fleet_id
+            ),
+            GameEvent::FleetSentrySet { fleet_id, sentry: true } => write!(
+                f, "Fleet '{}' is holding position and will wake if hostiles approach.", fleet_id
+            ),
+            GameEvent::FleetSentrySet { fleet_id, sentry: false } => write!(f, "Fleet '{}' is no longer holding.", fleet_id),
+            GameEvent::CargoLoaded { fleet_id, planet_name, cargo, carried, capacity } => write!(
+                f, "Fleet '{}' loaded {} at {}. Cargo: {}/{}.", fleet_id, cargo, planet_name, carried, capacity
+            ),
+            GameEvent::CargoUnloaded { fleet_id, planet_name, stored, wasted, .. } => {
+                write!(f, "Fleet '{}' unloaded {} at {}.", fleet_id, stored, planet_name)?;
+                if *wasted != Resources::default() {
+                    write!(f, " Storage was full: {} went to waste.", wasted)?;
+                }
+                Ok(())
+            }
+            GameEvent::CargoLost { fleet_name, lost, .. } => write!(
+                f, "Fleet {} lost {} of cargo with its ships.", fleet_name, lost
+            ),
+            GameEvent::PlanetColonized { fleet_id, planet_name, player_name, ark_bonus } => {
+                write!(f, "Fleet '{}' has colonized {}! Planet now belongs to {}.", fleet_id, planet_name, player_name)?;
+                if *ark_bonus != Resources::default() {
+                    write!(f, " Its ark was broken up, adding {} to the colony's stores.", ark_bonus)?;
+                }
+                Ok(())
+            }
+            GameEvent::RuinClaimed { planet_name, description } => write!(
+                f, "The colonists of {} salvaged the ruins: {}.", planet_name, description
+            ),
+            GameEvent::ColonizationFailed { planet_name, reason } => write!(
+                f, "Failed to colonize {}: {}", planet_name, reason
+            ),
+            GameEvent::ColonizationPaid { planet_name, origin_name, cost } => write!(
+                f, "{} supplied {} to settle {}.", origin_name, cost, planet_name
+            ),
+            GameEvent::ColonizationRefunded { planet_name, origin_name, refunded } => write!(
+                f, "{} returned to {} after the landing on {} failed.", refunded, origin_name, planet_name
+            ),
+            GameEvent::TurnEnded { player_name } => write!(f, "{} ends their turn.", player_name),
+            GameEvent::DevAction { player_name, description } => write!(f, "[dev] {} {}", player_name, description),
+            GameEvent::WaitingOn { turn, player_names } => {
+                write!(f, "Turn {}: waiting on {}", turn, player_names.join(", "))
+            }
+            GameEvent::RoundProcessing { turn } => write!(f, "\n=== Turn {} Processing ===", turn),
+            GameEvent::ShieldsDamaged { fleet_id, planet_name, shields_before, shields_after, .. } => write!(
+                f, "Fleet {} bombards {}. Shields damaged: {} → {} HP.",
+                fleet_id, planet_name, shields_before, shields_after
+            ),
+            GameEvent::ShieldsDestroyed { fleet_id, planet_name, shields_before, overflow, .. } => {
+                write!(
+                    f, "Fleet {} bombards {}. Shields down on {} ({} → 0 HP)! Planet open to colonization or invasion.",
+                    fleet_id, planet_name, planet_name, shields_before
+                )?;
+                if *overflow > 0 {
+                    write!(f, " {} damage passed through.", overflow)?;
+                }
+                Ok(())
+            }
+            GameEvent::BombardmentOverflow { fleet_id, planet_name, damage, .. } => write!(
+                f, "Fleet {} bombards {}. Shields were already down: {} damage passed through. Bombardment complete.",
+                fleet_id, planet_name, damage
+            ),
+            GameEvent::StructuresBombarded { fleet_id, planet_name, hits, .. } => {
+                let hits: Vec<String> = hits.iter()
+                    .map(|hit| match hit.destroyed {
+                        true => format!("{} destroyed", names.structure(&hit.structure_id)),
+                        false => format!("{} -{} HP", names.structure(&hit.structure_id), hit.damage),
+                    })
+                    .collect();
+                write!(f, "Fleet {} strikes structures on {}: {}.", fleet_id, planet_name, hits.join(", "))
+            }
+            GameEvent::BombardmentTargetMissing { fleet_id, planet_name, structure_id, .. } => write!(
+                f, "Fleet {} aimed for {} on {}, but no such installation was detected. The damage fell in the usual order.",
+                fleet_id, structure_id, planet_name
+            ),
+            GameEvent::StructureDestroyed { planet_name, structure_id, attacker_name, .. } => write!(
+                f, "{}'s bombardment destroyed {} on {}.", attacker_name, names.structure(structure_id), planet_name
+            ),
+            GameEvent::ConstructionCompleted { structure_id, planet_name, .. } => write!(
+                f, "Construction completed: {} on planet {}", names.structure(structure_id), planet_name
+            ),
+            GameEvent::ConstructionFailed { structure_id, planet_name, reason, .. } => write!(
+                f, "Construction failed for {} on planet {}: {}", names.structure(structure_id), planet_name, reason
+            ),
+            GameEvent::ConstructionHalted { structure_id, planet_name, awaiting, required_level, turns, .. } => write!(
+                f, "{} on planet {}: construction halted: awaiting {} Lv{}. It completes once that is in place, \
+                    or is refunded after {} turn(s)",
+                names.structure(structure_id), planet_name, names.structure(awaiting), required_level, turns
+            ),
+            GameEvent::ConstructionRejected { structure_id, planet_name, reason, refunded, .. } => write!(
+                f, "Construction of {} on planet {} was abandoned: {}. Resources refunded: {}",
+                names.structure(structure_id), planet_name, reason, refunded
+            ),
+            GameEvent::ActionOrphaned { action, reason, refund, .. } => {
+                write!(f, "Cancelled the {}: {}", action, reason)?;
+                match refund {
+                    Some((planet_name, refunded)) => write!(f, ". Resources refunded to {}: {}", planet_name, refunded),
+                    None => Ok(()),
+                }
+            }
+            GameEvent::UpgradeCompleted { structure_id, planet_name, .. } => write!(
+                f, "Upgrade completed: {} on planet {}", names.structure(structure_id), planet_name
+            ),
+            GameEvent::UpgradeFailed { structure_id, planet_name, reason, .. } => write!(
+                f, "Upgrade failed for {} on planet {}: {}", names.structure(structure_id), planet_name, reason
+            ),
+            GameEvent::UpgradeRepeated { structure_id, planet_name, cost, turns, .. } => write!(
+                f, "Next upgrade of {} on planet {} queued automatically. Cost: {}, Turns: {}",
+                names.structure(structure_id), planet_name, cost, turns
+            ),
+            GameEvent::UpgradeRepeatStopped { structure_id, planet_name, reason, .. } => write!(
+                f, "Repeated upgrades of {} on planet {} stopped: {}", names.structure(structure_id), planet_name, reason
+            ),
+            GameEvent::ShipBuilt { ship_instance_id, ship_type, planet_name, fleet_name, .. } => {
+                write!(f, "Ship built: {}, {}, at planet {}", ship_instance_id, names.ship(ship_type), planet_name)?;
+                match fleet_name {
+                    Some(fleet_name) => write!(f, ", joined fleet '{}'", fleet_name),
+                    None => Ok(()),
+                }
+            }
+            GameEvent::ShipRetrofitted { ship_instance_id, from_type, to_type, planet_name, .. } => write!(
+                f, "Ship retrofitted: {} is now {}, was {}, at planet {}",
+                ship_instance_id, names.ship(to_type), names.ship(from_type), planet_name
+            ),
+            GameEvent::ShipRetrofitFailed { ship_instance_id, planet_name, reason, .. } => write!(
+                f, "Retrofit of {} at planet {} failed: {}", ship_instance_id, planet_name, reason
+            ),
+            GameEvent::FleetArrived { fleet_id, planet_name, kind, .. } => match kind {
+                ArrivalKind::Friendly => write!(
+                    f, "Fleet {} arrived at {} (friendly territory)", fleet_id, planet_name
+                ),
+                ArrivalKind::UndefendedEnemy => write!(
+                    f, "Fleet {} arrived at undefended enemy planet {}. Use bombardment to weaken defenses, then colonize.",
+                    fleet_id, planet_name
+                ),
+                ArrivalKind::Neutral => write!(
+                    f, "Fleet {} arrived at neutral planet {}. Use colonize command to claim it.",
+                    fleet_id, planet_name
+                ),
+            },
+            GameEvent::ShipArrived { ship_instance_id, planet_name, kind, .. } => match kind {
+                ArrivalKind::Friendly => write!(
+                    f, "Ship {} arrived at {} (friendly territory)", ship_instance_id, planet_name
+                ),
+                ArrivalKind::UndefendedEnemy => write!(
+                    f, "Ship {} arrived at undefended enemy planet {}", ship_instance_id, planet_name
+                ),
+                ArrivalKind::Neutral => write!(
+                    f, "Ship {} arrived at neutral planet {}. Form a fleet there to colonize it.",
+                    ship_instance_id, planet_name
+                ),
+            },
+            GameEvent::Battle {
+                fleet_id,
+                planet_name,
+                attacker_name,
+                defender_name,
+                attacker_strength,
+                defender_strength,
+                attacker_wins,
+                ships_destroyed,
+            } => {
+                writeln!(f, "⚔ BATTLE at {}! {} vs {}", planet_name, attacker_name, defender_name)?;
+                writeln!(f, "  Attack: {} | Defense: {}", attacker_strength, defender_strength)?;
+                if *attacker_wins {
+                    writeln!(f, "  Victory! Attacker wins!")?;
+                    writeln!(f, "  {} defending ship(s) destroyed", ships_destroyed)?;
+                    write!(
+                        f, "  {} now orbits {}. Use bombardment to weaken defenses, then colonize.",
+                        fleet_id, planet_name
+                    )
+                } else {
+                    writeln!(f, "  Defeat! Defender wins!")?;
+                    write!(f, "  {} attacking ship(s) destroyed", ships_destroyed)
+                }
+            }
+            GameEvent::GarrisonAssault {
+                planet_name,
+                attacker_name,
+                defender_name,
+                attacker_strength,
+                defender_strength,
+                attacker_wins,
+                ships_destroyed,
+                ..
+            } => {
+                writeln!(f, "⚔ LANDING on {}! {} vs {}'s garrison", planet_name, attacker_name, defender_name)?;
+                writeln!(f, "  Attack: {} | Defense: {}", attacker_strength, defender_strength)?;
+                if *attacker_wins {
+                    write!(f, "  Garrison overwhelmed! {} garrisoned ship(s) destroyed", ships_destroyed)
+                } else {
+                    write!(f, "  Landing repelled! {} attacking ship(s) destroyed", ships_destroyed)
+                }
+            }
+            GameEvent::BombardmentIntercepted { fleet_id, planet_name, intercepted, .. } => write!(
+                f, "Garrison fire over {} absorbs {} of fleet {}'s bombardment.",
+                planet_name, intercepted, fleet_id
+            ),
+            GameEvent::Victory { player_names, turn, kind } => {
+                let winners = player_names.join(" & ");
+                let (have, win) = if player_names.len() > 1 { ("have", "win") } else { ("has", "wins") };
+                write!(
+                    f, "\n🎉 VICTORY! {} {} {}!\nGame Over - {} {} on Turn {}",
+                    winners, have, kind, winners, win, turn
+                )
+            }
+            GameEvent::DominationWarning { player_name, held, total, turns_left } => write!(
+                f, "⚠ {} holds {} of {} key planets and wins by domination in {} turn(s) unless stopped",
+                player_name, held, total, turns_left
+            ),
+            GameEvent::SlowRound { turn, elapsed_ms, threshold_ms } => write!(
+                f, "⚠ Processing turn {} took {} ms, over the {} ms the slow_round option allows",
+                turn, elapsed_ms, threshold_ms
+            ),
+            GameEvent::EngineWarning { message } => write!(f, "⚠ Engine warning: {}", message),
+            GameEvent::TurnBegan { turn, protection_turns_left } => {
+                write!(f, "\n=== Turn {} Begins ===", turn)?;
+                if *protection_turns_left > 0 {
+                    write!(
+                        f, "\n🛡 Protected expansion: hostilities may begin on turn {} ({} turn(s) left)",
+                        turn + protection_turns_left, protection_turns_left
+                    )?;
+                }
+                Ok(())
+            }
+            GameEvent::BombardmentObserved { attacker_name, planet_name, shields, .. } => write!(
+                f, "📡 {} is bombarding {}; its shields are {}", attacker_name, planet_name, shields
+            ),
+            GameEvent::ShieldsDownObserved { attacker_name, planet_name, own_planet: true, .. } => write!(
+                f, "🚨 Shields down on your planet {}! {} can now land there.", planet_name, attacker_name
+            ),
+            GameEvent::ShieldsDownObserved { attacker_name, planet_name, own_planet: false, .. } => write!(
+                f, "📡 Shields down on {} after {}'s bombardment. Planet open to colonization or invasion.",
+                planet_name, attacker_name
+            ),
+            GameEvent::FleetDetected { observer_name, sighting, .. } => write!(
+                f, "📡 {} detected {}", observer_name, sighting
+            ),
+            GameEvent::PlayerTurnStarted { player_name, alerts, sightings, notes } => {
+                write!(f, "{}'s turn.", player_name)?;
+                if *notes > 0 {
+                    write!(f, " You have {} note{}; 'note list' shows them.", notes, if *notes == 1 { "" } else { "s" })?;
+                }
+                for alert in alerts {
+                    write!(f, "\n  ⚠ {}", alert)?;
+                }
+                if !sightings.is_empty() {
+                    write!(f, "\nFleets sighted last round:")?;
+                    for sighting in sightings {
+                        write!(f, "\n  - {}", sighting)?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn write_storage_warnings(f: &mut fmt::Formatter<'_>, warnings: &[StorageWarning]) -> fmt::Result {
+    for warning in warnings {
+        write!(f, "\n  warning: {}; consider a storage complex", warning)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{fixture_ship_config, fixture_structure_config};
+
+    #[test]
+    fn test_named_events_give_display_names_with_ids() {
+        let (structures, ships) = (fixture_structure_config(), fixture_ship_config());
+        let named = |event: &GameEvent| event.named(&structures, &ships).to_string();
+        let player_id = String::from("alice");
+        let planet_name = String::from("Kepler VII");
+
+        let completed = GameEvent::ConstructionCompleted {
+            player_id: player_id.clone(), structure_id: String::from("orbital_shipyard"), planet_name: planet_name.clone(),
+        };
+        assert_eq!(named(&completed), "Construction completed: Orbital Shipyard (orbital_shipyard) on planet Kepler VII");
+        assert_eq!(completed.to_string(), "Construction completed: orbital_shipyard on planet Kepler VII");
+
+        let built = GameEvent::ShipBuilt {
+            player_id: player_id.clone(),
+            ship_instance_id: String::from("interceptor_3"),
+            ship_type: String::from("interceptor"),
+            planet_name: planet_name.clone(),
+            fleet_name: None,
+        };
+        assert_eq!(named(&built), "Ship built: interceptor_3, Interceptor (interceptor), at planet Kepler VII");
+
+        let halted = GameEvent::ConstructionHalted {
+            player_id,
+            structure_id: String::from("mining_complex"),
+            planet_name,
+            awaiting: String::from("storage_complex"),
+            required_level: 1,
+            turns: 3,
+        };
+        assert!(named(&halted).starts_with(
+            "Mining Complex (mining_complex) on planet Kepler VII: construction halted: awaiting Storage Complex (storage_complex) Lv1."
+        ));
+    }
+
+    #[test]
+    fn test_undefined_ids_fall_back_to_the_raw_id() {
+        let (structures, ships) = (fixture_structure_config(), fixture_ship_config());
+        assert_eq!(structure_config::fmt_structure(&structures, &String::from("warp_core")), "warp_core");
+        assert_eq!(ship_config::fmt_ship(&ships, &String::from("dreadnought")), "dreadnought");
+
+        let queued = GameEvent::ShipQueued {
+            ship_id: String::from("dreadnought"),
+            ship_instance_id: String::from("dreadnought_1"),
+            cost: Resources::default(),
+            turns: 2,
+            warnings: Vec::new(),
+            delivery_planet: None,
+        };
+        assert!(queued.named(&structures, &ships).to_string().starts_with("Ship construction queued: dreadnought as dreadnought_1."));
+    }
+}
@@ -0,0 +1,553 @@
+use std::fmt;
+
+use crate::engine::resources::Resources;
+
+pub const DEFAULT_CANCELLATION_PENALTY_PERCENT: u32 = 25;
+
+/// Most factions, human and AI together, in one game.
+pub const MAX_FACTIONS: usize = 8;
+
+/// Most actions of one kind a rules profile lets a planet run at once.
+pub const MAX_CONSTRUCTION_SLOTS: u32 = 4;
+
+fn one_slot() -> u32 {
+    1
+}
+
+pub const DEFAULT_RUIN_CHANCE_PERCENT: u32 = 15;
+
+fn default_ruin_chance_percent() -> u32 {
+    DEFAULT_RUIN_CHANCE_PERCENT
+}
+
+pub const DEFAULT_KEY_PLANETS: u32 = 5;
+
+fn default_key_planets() -> u32 {
+    DEFAULT_KEY_PLANETS
+}
+
+pub const DEFAULT_DOMINATION_TURNS: u32 = 5;
+
+fn default_domination_turns() -> u32 {
+    DEFAULT_DOMINATION_TURNS
+}
+
+pub const DEFAULT_CONSOLIDATION_TURNS: u32 = 2;
+
+fn default_consolidation_turns() -> u32 {
+    DEFAULT_CONSOLIDATION_TURNS
+}
+
+/// Opening turns the protected expansion rule keeps players from attacking each other when
+/// it is turned on
+pub const DEFAULT_PROTECTED_TURNS: u32 = 5;
+
+/// Most of a planet's output its allocation can shift between resources
+pub const DEFAULT_ALLOCATION_SHIFT_PERCENT: u32 = 20;
+
+fn default_allocation_shift_percent() -> u32 {
+    DEFAULT_ALLOCATION_SHIFT_PERCENT
+}
+
+pub const DEFAULT_FLEET_CAPACITY: usize = 20;
+
+fn default_fleet_capacity() -> usize {
+    DEFAULT_FLEET_CAPACITY
+}
+
+/// What colonizing a neutral planet next to one of the player's own costs
+pub const DEFAULT_COLONIZATION_BASE_COST: Resources = Resources { minerals: 50, gas: 0, energy: 0 };
+
+/// What each further connection between a neutral planet and the player's nearest planet adds
+pub const DEFAULT_COLONIZATION_COST_PER_HOP: Resources = Resources { minerals: 50, gas: 25, energy: 0 };
+
+/// Credited to a new colony when its ark is broken up
+pub const DEFAULT_ARK_COLONY_BONUS: Resources = Resources { minerals: 100, gas: 50, energy: 0 };
+
+fn default_ark_colony_bonus() -> Resources {
+    DEFAULT_ARK_COLONY_BONUS
+}
+
+/// Hops from its owner's nearest planet a fleet can go without losing supply
+pub const DEFAULT_SUPPLY_RANGE: u32 = 3;
+
+fn default_supply_range() -> u32 {
+    DEFAULT_SUPPLY_RANGE
+}
+
+/// Chance each ship of an unsupplied fleet wears down in a round
+pub const DEFAULT_ATTRITION_CHANCE_PERCENT: u32 = 25;
+
+fn default_attrition_chance_percent() -> u32 {
+    DEFAULT_ATTRITION_CHANCE_PERCENT
+}
+
+/// Shield a ship loses each time attrition wears it down
+pub const DEFAULT_ATTRITION_SHIELD_LOSS: u32 = 5;
+
+fn default_attrition_shield_loss() -> u32 {
+    DEFAULT_ATTRITION_SHIELD_LOSS
+}
+
+fn default_colonization_base_cost() -> Resources {
+    DEFAULT_COLONIZATION_BASE_COST
+}
+
+fn default_colonization_cost_per_hop() -> Resources {
+    DEFAULT_COLONIZATION_COST_PER_HOP
+}
+
+/// Most unrest a planet can build up.
+pub const MAX_UNREST: u32 = 100;
+
+pub const DEFAULT_UNREST_RISE: u32 = 15;
+pub const DEFAULT_UNREST_DECAY: u32 = 5;
+pub const DEFAULT_UNREST_PENALTY_THRESHOLD: u32 = 20;
+pub const DEFAULT_STRIKE_THRESHOLD: u32 = 60;
+
+/// How bombardment and blockade stir up a planet and how it calms down again. Unrest runs
+/// from 0 to `MAX_UNREST`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct UnrestRules {
+    /// Gained each round the planet is bombarded or blockaded
+    pub rise_per_round: u32,
+    /// Lost each round nobody troubles the planet
+    pub decay_per_round: u32,
+    /// Unrest above which production falls by one percent per point
+    pub penalty_threshold: u32,
+    /// Reaching this sends the workers on strike: the planet produces nothing for a turn
+    pub strike_threshold: u32,
+}
+
+impl UnrestRules {
+    /// Share of its production a planet with `unrest` loses.
+    pub fn production_penalty_percent(&self, unrest: u32) -> u32 {
+        unrest.saturating_sub(self.penalty_threshold).min(100)
+    }
+}
+
+impl Default for UnrestRules {
+    fn default() -> Self {
+        UnrestRules {
+            rise_per_round: DEFAULT_UNREST_RISE,
+            decay_per_round: DEFAULT_UNREST_DECAY,
+            penalty_threshold: DEFAULT_UNREST_PENALTY_THRESHOLD,
+            strike_threshold: DEFAULT_STRIKE_THRESHOLD,
+        }
+    }
+}
+
+pub const DEFAULT_ACTION_LOG_TURNS: u32 = 200;
+pub const DEFAULT_BATTLES_KEPT: usize = 500;
+pub const DEFAULT_INTEL_PER_PLAYER: usize = 300;
+
+/// How much of the game's past a state keeps. Compaction at every round rollover drops the
+/// oldest entries beyond these caps, never anything from the round just played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RetentionRules {
+    /// Turns of orders kept in the action log, the round just played included
+    pub action_log_turns: u32,
+    /// Battle reports kept, newest first
+    pub battles: usize,
+    /// Planets each player keeps intel on, most recently seen first
+    pub intel_per_player: usize,
+}
+
+impl Default for RetentionRules {
+    fn default() -> Self {
+        RetentionRules {
+            action_log_turns: DEFAULT_ACTION_LOG_TURNS,
+            battles: DEFAULT_BATTLES_KEPT,
+            intel_per_player: DEFAULT_INTEL_PER_PLAYER,
+        }
+    }
+}
+
+/// When planets produce their resources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum ProductionTiming {
+    /// At the start of each player's turn, for that player's planets only, from the second
+    /// turn on. Every player waits the same number of turns for their production.
+    #[default]
+    TurnStart,
+    /// For every planet at once when the round ends, right after the last player's turn
+    RoundEnd,
+}
+
+/// Saves from before production timing could be chosen produced at the end of the round.
+fn saved_production_timing() -> ProductionTiming {
+    ProductionTiming::RoundEnd
+}
+
+impl fmt::Display for ProductionTiming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProductionTiming::TurnStart => write!(f, "turn start"),
+            ProductionTiming::RoundEnd => write!(f, "round end"),
+        }
+    }
+}
+
+/// How a game can be won.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum VictoryCondition {
+    /// Own every planet, or as a team leave every opponent without planets
+    #[default]
+    Elimination,
+    /// Hold a majority of the key planets for a number of consecutive full turns
+    Domination,
+    /// Whichever of the two comes first
+    Both,
+}
+
+impl VictoryCondition {
+    pub fn allows_elimination(self) -> bool {
+        matches!(self, VictoryCondition::Elimination | VictoryCondition::Both)
+    }
+
+    pub fn allows_domination(self) -> bool {
+        matches!(self, VictoryCondition::Domination | VictoryCondition::Both)
+    }
+}
+
+impl fmt::Display for VictoryCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VictoryCondition::Elimination => write!(f, "elimination"),
+            VictoryCondition::Domination => write!(f, "domination"),
+            VictoryCondition::Both => write!(f, "elimination or domination"),
+        }
+    }
+}
+
+/// Pace of the game: scales how long building, upgrading and ship construction take, and
+/// how well stocked starting planets are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum GameSpeed {
+    /// Half the build times, rounded up, and double the starting resources
+    Quick,
+    #[default]
+    Normal,
+    /// Double the build times and half the starting resources
+    Epic,
+}
+
+impl GameSpeed {
+    /// Turns an action configured to take `base_turns` takes at this speed, at least one.
+    pub fn scale_turns(self, base_turns: u32) -> u32 {
+        match self {
+            GameSpeed::Quick => base_turns.div_ceil(2).max(1),
+            GameSpeed::Normal => base_turns,
+            GameSpeed::Epic => base_turns.saturating_mul(2).max(1),
+        }
+    }
+
+    /// Build time at this speed as a percentage of the configured one. Rounded half up with at
+    /// least one turn, as the modifier pipeline does, this gives the same turns as `scale_turns`.
+    pub fn build_time_percent(self) -> u32 {
+        match self {
+            GameSpeed::Quick => 50,
+            GameSpeed::Normal => 100,
+            GameSpeed::Epic => 200,
+        }
+    }
+
+    /// Share of the usual starting resources a settled starting planet gets.
+    pub fn starting_resources_percent(self) -> u32 {
+        match self {
+            GameSpeed::Quick => 200,
+            GameSpeed::Normal => 100,
+            GameSpeed::Epic => 50,
+        }
+    }
+}
+
+impl fmt::Display for GameSpeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameSpeed::Quick => write!(f, "quick"),
+            GameSpeed::Normal => write!(f, "normal"),
+            GameSpeed::Epic => write!(f, "epic"),
+        }
+    }
+}
+
+/// Named sets of pacing rules to start a game from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RulesPreset {
+    /// One structure build and one upgrade per planet at a time
+    #[default]
+    Classic,
+    /// Two structure builds and two upgrades per planet, and two ships per shipyard level
+    Standard,
+    /// Any other combination of knobs
+    Custom,
+}
+
+impl fmt::Display for RulesPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RulesPreset::Classic => write!(f, "classic"),
+            RulesPreset::Standard => write!(f, "standard"),
+            RulesPreset::Custom => write!(f, "custom"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RulesProfileError {
+    #[error("A planet must run between 1 and {MAX_CONSTRUCTION_SLOTS} {kind} at a time, got {slots}")]
+    InvalidSlots { kind: &'static str, slots: u32 },
+
+    #[error("Cancellation penalty must be between 0 and 100 percent, got {0}")]
+    InvalidCancellationPenalty(u32),
+
+    #[error("Fleet capacity must be at least 1.")]
+    InvalidFleetCapacity,
+
+    #[error("Attrition chance must be between 0 and 100 percent, got {0}")]
+    InvalidAttritionChance(u32),
+}
+
+/// The pacing rules: how many actions a planet runs at once, what cancelling costs and how
+/// much a fleet holds. Which preset a profile matches follows from its knobs, so a profile
+/// with any knob changed is a custom one.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RulesProfile {
+    /// Structures a planet can build at the same time
+    #[serde(default = "one_slot")]
+    pub build_slots: u32,
+    /// Structures a planet can upgrade at the same time
+    #[serde(default = "one_slot")]
+    pub upgrade_slots: u32,
+    /// Ships a planet can build or retrofit at the same time per shipyard level
+    #[serde(default = "one_slot")]
+    pub ships_per_shipyard_level: u32,
+    /// Share of reserved resources lost when a pending action is cancelled (0-100)
+    pub cancellation_penalty_percent: u32,
+    /// Total ship size a single fleet can hold
+    #[serde(default = "default_fleet_capacity")]
+    pub fleet_capacity: usize,
+    /// Paid to colonize a neutral planet one connection from the nearest planet the player owns
+    #[serde(default = "default_colonization_base_cost")]
+    pub colonization_base_cost: Resources,
+    /// Added to the colonization cost for every connection beyond the first
+    #[serde(default = "default_colonization_cost_per_hop")]
+    pub colonization_cost_per_hop: Resources,
+    /// Added to a new colony's storage, above its capacity, from the ark consumed founding it
+    #[serde(default = "default_ark_colony_bonus")]
+    pub ark_colony_bonus: Resources,
+    /// Hops from the owner's nearest planet a fleet stays supplied within
+    #[serde(default = "default_supply_range")]
+    pub supply_range: u32,
+    /// Chance each ship of a fleet out of supply loses shield in a round (0-100)
+    #[serde(default = "default_attrition_chance_percent")]
+    pub attrition_chance_percent: u32,
+    /// Shield lost per hit of attrition, never below zero
+    #[serde(default = "default_attrition_shield_loss")]
+    pub attrition_shield_loss: u32,
+}
+
+impl RulesProfile {
+    /// The rules the game has always been played with.
+    pub fn classic() -> Self {
+        RulesProfile {
+            build_slots: 1,
+            upgrade_slots: 1,
+            ships_per_shipyard_level: 1,
+            cancellation_penalty_percent: DEFAULT_CANCELLATION_PENALTY_PERCENT,
+            fleet_capacity: DEFAULT_FLEET_CAPACITY,
+            colonization_base_cost: DEFAULT_COLONIZATION_BASE_COST,
+            colonization_cost_per_hop: DEFAULT_COLONIZATION_COST_PER_HOP,
+            ark_colony_bonus: DEFAULT_ARK_COLONY_BONUS,
+            supply_range: DEFAULT_SUPPLY_RANGE,
+            attrition_chance_percent: DEFAULT_ATTRITION_CHANCE_PERCENT,
+            attrition_shield_loss: DEFAULT_ATTRITION_SHIELD_LOSS,
+        }
+    }
+
+    pub fn standard() -> Self {
+        RulesProfile {
+            build_slots: 2,
+            upgrade_slots: 2,
+            ships_per_shipyard_level: 2,
+            ..Self::classic()
+        }
+    }
+
+    /// The profile a preset starts from; a custom one starts out as the classic rules.
+    pub fn from_preset(preset: RulesPreset) -> Self {
+        match preset {
+            RulesPreset::Standard => Self::standard(),
+            RulesPreset::Classic | RulesPreset::Custom => Self::classic(),
+        }
+    }
+
+    pub fn preset(&self) -> RulesPreset {
+        if *self == Self::classic() {
+            RulesPreset::Classic
+        } else if *self == Self::standard() {
+            RulesPreset::Standard
+        } else {
+            RulesPreset::Custom
+        }
+    }
+
+    /// What colonizing a neutral planet `hops` connections from the player's nearest planet
+    /// costs: the base cost, plus the per-hop cost for every connection beyond the first.
+    pub fn colonization_cost(&self, hops: u32) -> Resources {
+        let extra = hops.saturating_sub(1);
+        let per_hop = &self.colonization_cost_per_hop;
+        self.colonization_base_cost.clone() + Resources {
+            minerals: per_hop.minerals.saturating_mul(extra),
+            gas: per_hop.gas.saturating_mul(extra),
+            energy: per_hop.energy.saturating_mul(extra),
+        }
+    }
+
+    /// Checks every knob is within its range.
+    pub fn validate(&self) -> Result<(), RulesProfileError> {
+        let slots = [
+            ("structure builds", self.build_slots),
+            ("structure upgrades", self.upgrade_slots),
+            ("ship builds per shipyard level", self.ships_per_shipyard_level),
+        ];
+        for (kind, slots) in slots {
+            if !(1..=MAX_CONSTRUCTION_SLOTS).contains(&slots) {
+                return Err(RulesProfileError::InvalidSlots { kind, slots });
+            }
+        }
+        if self.cancellation_penalty_percent > 100 {
+            return Err(RulesProfileError::InvalidCancellationPenalty(self.cancellation_penalty_percent));
+        }
+        if self.fleet_capacity == 0 {
+            return Err(RulesProfileError::InvalidFleetCapacity);
+        }
+        if self.attrition_chance_percent > 100 {
+            return Err(RulesProfileError::InvalidAttritionChance(self.attrition_chance_percent));
+        }
+        Ok(())
+    }
+}
+
+impl Default for RulesProfile {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+/// Tunable gameplay rules carried into the game state.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GameRules {
+    /// Kept flat in saves, where its knobs sat before there were profiles
+    #[serde(flatten)]
+    pub profile: RulesProfile,
+    /// Chance for each neutral planet to hold ruins when the map is generated (0-100)
+    #[serde(default = "default_ruin_chance_percent")]
+    pub ruin_chance_percent: u32,
+    #[serde(default)]
+    pub speed: GameSpeed,
+    #[serde(default)]
+    pub victory: VictoryCondition,
+    /// Key planets marked at map generation when domination can win the game
+    #[serde(default = "default_key_planets")]
+    pub key_planets: u32,
+    /// Consecutive full turns a majority of the key planets must be held to win by domination
+    #[serde(default = "default_domination_turns")]
+    pub domination_turns: u32,
+    /// Full turns a planet that changed hands cannot be colonized or invaded again
+    #[serde(default = "default_consolidation_turns")]
+    pub consolidation_turns: u32,
+    #[serde(default = "saved_production_timing")]
+    pub production_timing: ProductionTiming,
+    /// Share of a planet's output (0-100) its allocation can move between resources
+    #[serde(default = "default_allocation_shift_percent")]
+    pub allocation_shift_percent: u32,
+    #[serde(default)]
+    pub unrest: UnrestRules,
+    #[serde(default)]
+    pub retention: RetentionRules,
+    /// Whether a structure under construction counts as level 1 toward the prerequisites of
+    /// builds queued after it. A dependent that finishes first waits for it to be completed.
+    #[serde(default)]
+    pub prerequisites_under_construction: bool,
+    /// Opening turns in which players may colonize but not bombard, blockade or invade each
+    /// other; 0 turns the protected expansion off
+    #[serde(default)]
+    pub protected_turns: u32,
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        GameRules {
+            profile: RulesProfile::classic(),
+            ruin_chance_percent: DEFAULT_RUIN_CHANCE_PERCENT,
+            speed: GameSpeed::Normal,
+            victory: VictoryCondition::Elimination,
+            key_planets: DEFAULT_KEY_PLANETS,
+            domination_turns: DEFAULT_DOMINATION_TURNS,
+            consolidation_turns: DEFAULT_CONSOLIDATION_TURNS,
+            production_timing: ProductionTiming::TurnStart,
+            allocation_shift_percent: DEFAULT_ALLOCATION_SHIFT_PERCENT,
+            unrest: UnrestRules::default(),
+            retention: RetentionRules::default(),
+            prerequisites_under_construction: false,
+            protected_turns: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_speed_scales_turns_rounding_up_to_at_least_one() {
+        let scaled: Vec<_> = [0, 1, 3, 4].iter().map(|turns| GameSpeed::Quick.scale_turns(*turns)).collect();
+        assert_eq!(scaled, [1, 1, 2, 2]);
+        assert_eq!(GameSpeed::Normal.scale_turns(3), 3);
+        assert_eq!(GameSpeed::Epic.scale_turns(3), 6);
+        assert_eq!(GameSpeed::Epic.scale_turns(u32::MAX), u32::MAX);
+    }
+
+    #[test]
+    fn test_profile_preset_follows_its_knobs() {
+        assert_eq!(RulesProfile::default().preset(), RulesPreset::Classic);
+        assert_eq!(RulesProfile::from_preset(RulesPreset::Standard).preset(), RulesPreset::Standard);
+        let custom = RulesProfile { fleet_capacity: 12, ..RulesProfile::standard() };
+        assert_eq!(custom.preset(), RulesPreset::Custom);
+        assert_eq!(custom.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_profile_validation_rejects_out_of_range_knobs() {
+        let no_builds = RulesProfile { build_slots: 0, ..RulesProfile::classic() };
+        assert!(matches!(no_builds.validate(), Err(RulesProfileError::InvalidSlots { slots: 0, .. })));
+        let too_many_ships = RulesProfile { ships_per_shipyard_level: MAX_CONSTRUCTION_SLOTS + 1, ..RulesProfile::classic() };
+        assert!(matches!(too_many_ships.validate(), Err(RulesProfileError::InvalidSlots { .. })));
+        let penalty = RulesProfile { cancellation_penalty_percent: 101, ..RulesProfile::classic() };
+        assert_eq!(penalty.validate(), Err(RulesProfileError::InvalidCancellationPenalty(101)));
+        let empty_fleets = RulesProfile { fleet_capacity: 0, ..RulesProfile::classic() };
+        assert_eq!(empty_fleets.validate(), Err(RulesProfileError::InvalidFleetCapacity));
+        let attrition = RulesProfile { attrition_chance_percent: 101, ..RulesProfile::classic() };
+        assert_eq!(attrition.validate(), Err(RulesProfileError::InvalidAttritionChance(101)));
+    }
+
+    #[test]
+    fn test_unrest_penalty_starts_past_the_threshold() {
+        let unrest = UnrestRules::default();
+        assert_eq!(unrest.production_penalty_percent(DEFAULT_UNREST_PENALTY_THRESHOLD), 0);
+        assert_eq!(unrest.production_penalty_percent(DEFAULT_UNREST_PENALTY_THRESHOLD + 15), 15);
+        let harsh = UnrestRules { penalty_threshold: 0, ..unrest };
+        assert_eq!(harsh.production_penalty_percent(250), 100);
+    }
+
+    #[test]
+    fn test_rules_saved_before_profiles_load_as_classic() {
+        let saved = serde_json::json!({ "cancellation_penalty_percent": 25, "ruin_chance_percent": 15 });
+        let rules: GameRules = serde_json::from_value(saved).unwrap();
+        assert_eq!(rules.profile.preset(), RulesPreset::Classic);
+        let roundtrip: GameRules = serde_json::from_value(serde_json::to_value(&rules).unwrap()).unwrap();
+        assert_eq!(roundtrip, rules);
+    }
+}
@@ -0,0 +1,2332 @@
+mod action_log;
+mod advisor;
+mod alerts;
+mod battles;
+mod cargo;
+mod combat;
+mod detection;
+mod dev;
+mod digest;
+mod domination;
+mod empire_effects;
+mod gates;
+mod intel;
+mod invariants;
+mod modifiers;
+mod orphans;
+mod phase;
+mod preview;
+mod rally;
+mod retention;
+mod schedule;
+mod summary;
+mod supply;
+mod teams;
+mod timings;
+mod turn_processing;
+mod unrest;
+mod view;
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::collections::btree_map::Entry;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use thiserror::Error;
+
+use crate::engine::appearance::PlayerAppearance;
+use crate::engine::clock::Clock;
+use crate::engine::commands::command::CommandEffect;
+use crate::engine::fleet::Fleet;
+use crate::engine::game_rules::{GameRules, RulesProfile};
+use crate::engine::game_event::GameEvent;
+use crate::engine::map::{Map, MapError, PlanetPresence};
+use crate::engine::options::GameOptions;
+use crate::engine::pending_action::{ActionType, PendingAction};
+use crate::engine::resources::Resources;
+use crate::engine::rules;
+
+use super::configs::structure_config::{StructureConfig, StructureConfigError, StructureLimit};
+use super::configs::ship_config::{ShipConfig, ShipConfigError, ShipId};
+use super::planet::{
+    BuildInfo, ConnectionKind, GATE_LINK_DISTANCE, OVERCHARGE_ENERGY_COST, Planet, PlanetError, PlanetId,
+};
+use super::player::{PlayerId, Player};
+use super::ship::{FleetId, MovableUnit, Ship, ShipInstanceId};
+use super::structure::StructureId;
+
+pub use action_log::{ActionLogEntry, ActionOutcome, Highlight};
+pub use advisor::{MAX_SUGGESTIONS, Suggestion, advise};
+pub use alerts::{Alert, scan_alerts};
+pub use battles::{BattleKind, BattleReport, BattleRound, BattleShip, BattleSide};
+pub use detection::{FleetSighting, FleetSizeClass, ObservedSize, ShieldEstimate};
+pub use empire_effects::EmpireEffects;
+pub use intel::PlanetIntel;
+pub use modifiers::{
+    Adjustment, ConstructionKind, Contribution, MIN_BUILD_TURNS, ModifierContext, ModifierSource, ModifierTarget, Modifiers,
+};
+pub use phase::{GamePhase, ProcessingPhase, TurnPhaseContext};
+pub use preview::ConstructionPreview;
+pub use retention::Compaction;
+pub use schedule::ScheduledItem;
+pub use summary::{GameSummary, PlayerEntry};
+pub use supply::Supply;
+pub use timings::{PhaseTimings, RoundTimings};
+pub use view::{FleetView, GameView, PlanetView, ShipView, TurnViews, ViewChange, diff_views};
+
+use orphans::Lost;
+
+#[derive(Debug, Error)]
+pub enum GameStateError {
+    #[error("Player {0} is already present")]
+    PlayerAlreadyExists(String),
+
+    #[error("Planet {0} is already present")]
+    PlanetAlreadyExists(String),
+
+    #[error("Player {0} does not exist")]
+    UnknownPlayer(PlayerId),
+
+    #[error("The game has not started: it has no players yet")]
+    NotStarted,
+
+    #[error("Planet {0} does not exist")]
+    UnknownPlanet(PlanetId),
+
+    #[error("All {slots} {kind} slot(s) on planet {planet_id} are in use")]
+    ConstructionSlotOccupied { planet_id: PlanetId, kind: &'static str, slots: u32 },
+
+    #[error("Structure {0} already has a pending action")]
+    StructureBusy(StructureId),
+
+    #[error("All {slots} shipyard slot(s) on planet {planet_id} are in use")]
+    ShipyardSlotsFull { planet_id: PlanetId, slots: usize },
+
+    #[error("{structure_name} is limited to {limit}{}", existing_hint(.existing))]
+    StructureLimitReached {
+        structure_name: String,
+        limit: StructureLimit,
+        /// The acting player's own instances, built or queued
+        existing: Vec<String>,
+    },
+
+    #[error(transparent)]
+    PlanetError(#[from] PlanetError),
+
+    #[error(transparent)]
+    StructureConfigError(#[from] StructureConfigError),
+
+    #[error(transparent)]
+    ShipConfigError(#[from] ShipConfigError),
+
+    #[error(transparent)]
+    MapError(#[from] MapError),
+}
+
+fn existing_hint(existing: &[String]) -> String {
+    if existing.is_empty() {
+        String::new()
+    } else {
+        format!("; yours: {}", existing.join(", "))
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct GameState {
+    pub players: BTreeMap<PlayerId, Player>,
+    pub players_order: VecDeque<PlayerId>,
+    pub map: Map,
+    /// Round number: goes up once every player in the rotation has ended their turn, so all
+    /// players give their orders for the same turn
+    pub turn: u32,
+    /// Players who have ended their turn this round. The round ends once everyone still in
+    /// `players_order` has, however the rotation changed along the way.
+    #[serde(default)]
+    pub acted_this_round: HashSet<PlayerId>,
+    pub structure_config: StructureConfig,
+    pub ship_config: ShipConfig,
+    pub rules: GameRules,
+    /// Settings players can change while the game goes on, unlike the rules
+    #[serde(default)]
+    pub options: GameOptions,
+    /// Every player's view at the start of each of the last turns, for `diff`
+    #[serde(default)]
+    pub turn_history: VecDeque<TurnViews>,
+    /// Every player's orders in the order they were given, for reviewing the game afterwards
+    #[serde(default)]
+    pub action_log: Vec<ActionLogEntry>,
+    /// Whether players are still picking starting planets, giving orders, or done; once a side
+    /// has won nothing is hidden in the logs anymore
+    #[serde(default)]
+    pub game_phase: GamePhase,
+    /// Consecutive full turns each player has ended holding a majority of the key planets
+    #[serde(default)]
+    pub domination_streaks: HashMap<PlayerId, u32>,
+    /// Every battle fought so far, in order
+    #[serde(default)]
+    pub battles: Vec<BattleReport>,
+    /// Old entries dropped so far to keep long games small, see `compact`
+    #[serde(default)]
+    pub compaction: Compaction,
+    /// Chance during play, such as supply attrition, is drawn from this; new games take the
+    /// seed they were set up from
+    #[serde(default)]
+    pub seed: u64,
+    /// Seconds since the Unix epoch when the game was created; None for games made without a
+    /// clock, such as tests and simulations, and saves from before it was recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<u64>,
+    /// How long processing took, see `set_clock`
+    #[serde(default, skip_serializing_if = "RoundTimings::is_empty")]
+    pub timings: RoundTimings,
+    /// Set while a replay is viewed and once the game is over: views show every planet live,
+    /// whoever's turn it is. Sensor range still decides everything the rules do, such as intel
+    /// and detection.
+    #[serde(skip)]
+    pub omniscient: bool,
+    /// Whose behalf the state acts on; saves are only made between orders
+    #[serde(skip)]
+    phase: TurnPhaseContext,
+    /// Times processing; None leaves `timings` untouched
+    #[serde(skip)]
+    clock: Option<Arc<dyn Clock>>,
+    /// When the processing phase under way started, by `clock`
+    #[serde(skip)]
+    phase_started: Option<Duration>,
+    /// Engine misuse release builds carried on past, see `check_current_player_use`;
+    /// `apply_effect` hands them to the frontend as events
+    #[serde(skip)]
+    engine_warnings: RefCell<Vec<String>>,
+}
+
+impl GameState {
+    pub fn new(
+        players: BTreeMap<PlayerId, Player>,
+        players_order: VecDeque<PlayerId>,
+        map: Map,
+        structure_config: StructureConfig,
+        ship_config: ShipConfig,
+        rules: GameRules,
+    ) -> Result<Self, GameStateError> {
+        let mut game_state = GameState {
+            players,
+            players_order,
+            map,
+            turn: 1,
+            acted_this_round: HashSet::new(),
+            structure_config,
+            ship_config,
+            rules,
+            options: GameOptions::default(),
+            turn_history: VecDeque::new(),
+            action_log: Vec::new(),
+            game_phase: GamePhase::Playing,
+            domination_streaks: HashMap::new(),
+            battles: Vec::new(),
+            compaction: Compaction::default(),
+            seed: 0,
+            started_at: None,
+            timings: RoundTimings::default(),
+            omniscient: false,
+            phase: TurnPhaseContext::Orders,
+            clock: None,
+            phase_started: None,
+            engine_warnings: RefCell::new(Vec::new()),
+        };
+        game_state.refresh_intel();
+        game_state.record_turn_views();
+        Ok(game_state)
+    }
+
+    /// Sets the seed chance during play is drawn from.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Random numbers for processing the current round. The same seed, turn and orders always
+    /// give the same outcomes, so replays play out as the game did.
+    pub(super) fn round_rng(&self) -> StdRng {
+        StdRng::seed_from_u64(self.seed ^ u64::from(self.turn).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+    }
+
+    /// The pacing rules every action is checked and processed against.
+    pub fn rules_profile(&self) -> &RulesProfile {
+        &self.rules.profile
+    }
+
+    /// Player whose turn it is. Only meaningful while orders are given: processing code must
+    /// pass player ids explicitly, see [`TurnPhaseContext`]. Panics before the game has any
+    /// players; code that can run that early, such as command validation, checks with
+    /// [`GameState::checked_current_player`] first.
+    pub fn current_player(&self) -> &PlayerId {
+        self.checked_current_player()
+            .expect("Game has no players - invalid state")
+    }
+
+    /// Player whose turn it is, or NotStarted while the game has no players.
+    pub fn checked_current_player(&self) -> Result<&PlayerId, GameStateError> {
+        self.check_current_player_use();
+        self.players_order.front().ok_or(GameStateError::NotStarted)
+    }
+
+    /// Players who have ended their turn this round, in the order they did.
+    pub fn players_acted(&self) -> Vec<&PlayerId> {
+        self.players_order.iter().filter(|player_id| self.acted_this_round.contains(*player_id)).collect()
+    }
+
+    /// Players still to end their turn this round, in turn order: the first is the one playing now.
+    pub fn players_waiting(&self) -> Vec<&PlayerId> {
+        self.players_order.iter().filter(|player_id| !self.acted_this_round.contains(*player_id)).collect()
+    }
+
+    /// Names of `player_ids`, in the same order.
+    pub fn player_names<'a>(&self, player_ids: impl IntoIterator<Item = &'a PlayerId>) -> Vec<String> {
+        player_ids.into_iter()
+            .map(|player_id| self.players.get(player_id).expect("Player in rotation must exist").name.clone())
+            .collect()
+    }
+
+    /// Finds a fleet by id among all players, in turn order. Fleet ids are only unique per
+    /// player, so the first owner found is returned.
+    pub fn find_fleet_any(&self, fleet_id: &FleetId) -> Option<(PlayerId, &Fleet)> {
+        self.players_order.iter()
+            .filter_map(|player_id| self.players.get(player_id))
+            .find_map(|player| player.fleets.get(fleet_id).map(|fleet| (player.id.clone(), fleet)))
+    }
+
+    /// Finds a ship by id among all players, in turn order. Like fleet ids, ship ids are only
+    /// unique per player.
+    pub fn find_ship_any(&self, ship_id: &ShipInstanceId) -> Option<(PlayerId, &Ship)> {
+        self.players_order.iter()
+            .filter_map(|player_id| self.players.get(player_id))
+            .find_map(|player| player.ships.get(ship_id).map(|ship| (player.id.clone(), ship)))
+    }
+
+    pub fn add_player(&mut self, player: Player) -> Result<(), GameStateError> {
+        match self.players.entry(player.id.clone()) {
+            Entry::Vacant(e) => {
+                e.insert(player);
+                Ok(())
+            }
+            Entry::Occupied(_) => {
+                Err(GameStateError::PlayerAlreadyExists(player.name))
+            }
+        }
+    }
+
+    pub fn add_planet(&mut self, planet: Planet) -> Result<(), GameStateError> {
+        match self.map.planets.entry(planet.id.clone()) {
+            Entry::Vacant(e) => {
+                e.insert(planet);
+                Ok(())
+            }
+            Entry::Occupied(_) => {
+                Err(GameStateError::PlanetAlreadyExists(planet.name))
+            }
+        }
+    }
+
+    /// Calculates the total bombardment power of a fleet by summing all ships' bombardment stats.
+    /// Returns 0 if the fleet doesn't exist or has no ships.
+    pub fn calculate_fleet_bombardment(&self, player_id: &PlayerId, fleet_id: &FleetId) -> u32 {
+        let player = match self.players.get(player_id) {
+            Some(p) => p,
+            None => return 0,
+        };
+
+        let fleet = match player.fleets.get(fleet_id) {
+            Some(f) => f,
+            None => return 0,
+        };
+
+        // Sum bombardment value from all ships in the fleet, saturating for huge fleets
+        fleet.ships.iter()
+            .filter_map(|ship_id| {
+                player.ships.get(ship_id)
+                    .and_then(|ship| self.ship_config.get(&ship.ship_type))
+                    .map(|ship_def| ship_def.bombardment)
+            })
+            .fold(0, u32::saturating_add)
+    }
+
+    /// Whether the ship is stationed in its planet's garrison rather than free to join fleets.
+    pub fn is_garrisoned(&self, ship: &Ship) -> bool {
+        self.map.planets.get(&ship.location)
+            .is_some_and(|planet| planet.is_garrisoned(&ship.id))
+    }
+
+    /// How `player_id` is shown on every surface; None for unknown players.
+    pub fn appearance(&self, player_id: &PlayerId) -> Option<&PlayerAppearance> {
+        self.players.get(player_id).map(|player| &player.appearance)
+    }
+
+    /// Returns the planets currently in a player's sensor range: planets they or their
+    /// teammates own and planets where any of their ships are stationed.
+    pub fn visible_planets(&self, player_id: &PlayerId) -> HashSet<PlanetId> {
+        self.side_of(player_id).iter()
+            .filter_map(|member| self.players.get(member))
+            .flat_map(|player| player.planets.iter()
+                .cloned()
+                .chain(player.ships.values().map(|ship| ship.location.clone())))
+            .collect()
+    }
+
+    /// Returns the military presence `viewer` can see on each planet, for map markers.
+    /// Hostile ships and other players' bombardments only show on visible planets, and
+    /// planets out of sensor range carry the viewer's last intel on them.
+    /// Visible planets without any presence are left out.
+    pub fn planet_presence(&self, viewer: &PlayerId) -> HashMap<PlanetId, PlanetPresence> {
+        let visible = if self.omniscient {
+            self.map.planets.keys().cloned().collect()
+        } else {
+            self.visible_planets(viewer)
+        };
+        let mut presence: HashMap<PlanetId, PlanetPresence> = HashMap::new();
+
+        for planet_id in self.map.planets.keys().filter(|planet_id| !visible.contains(*planet_id)) {
+            presence.entry(planet_id.clone()).or_default().fogged = self.fogged_view(viewer, planet_id);
+        }
+
+        for player in self.players.values() {
+            if &player.id == viewer {
+                for fleet in player.fleets.values() {
+                    presence.entry(fleet.location.clone()).or_default().own_fleets += 1;
+                }
+            } else if !self.are_allies(viewer, &player.id) {
+                for ship in player.ships.values().filter(|ship| visible.contains(&ship.location)) {
+                    presence.entry(ship.location.clone()).or_default().hostile_ships = true;
+                }
+            }
+
+            let bombarded = player.pending_actions.iter().filter_map(|action| match &action.action_type {
+                ActionType::BombardPlanet(_, target) => Some(target),
+                _ => None,
+            });
+            for target in bombarded {
+                if &player.id == viewer || visible.contains(target) {
+                    presence.entry(target.clone()).or_default().bombarded = true;
+                }
+            }
+        }
+
+        presence
+    }
+
+    /// Returns the planets where `structure_id` is built, sorted by id. Only walks owned
+    /// planets, restricted to `owner`'s planets when given.
+    pub fn structure_locations(&self, structure_id: &StructureId, owner: Option<&PlayerId>) -> Vec<PlanetId> {
+        let mut locations: Vec<_> = self.players.values()
+            .filter(|player| owner.is_none_or(|owner| &player.id == owner))
+            .flat_map(|player| player.planets.iter())
+            .filter(|planet_id| self.map.planets.get(*planet_id)
+                .is_some_and(|planet| planet.has_built(structure_id)))
+            .cloned()
+            .collect();
+        locations.sort();
+        locations
+    }
+
+    /// Checks a per-player or per-game structure limit before `player_id` gets another instance.
+    /// Built instances always count; with `include_queued` the player's own queued builds count
+    /// too. Other players' queued builds are unknown here, so completion rechecks the limit.
+    fn check_structure_limit(
+        &self,
+        player_id: &PlayerId,
+        structure_id: &StructureId,
+        include_queued: bool,
+    ) -> Result<(), GameStateError> {
+        let Some(definition) = self.structure_config.get(structure_id) else {
+            return Ok(());
+        };
+        let limit = definition.limit();
+        let (max, scope) = match limit {
+            StructureLimit::PerPlanet => return Ok(()),
+            StructureLimit::PerPlayer(max) => (max, Some(player_id)),
+            StructureLimit::PerGame(max) => (max, None),
+        };
+
+        let built = self.structure_locations(structure_id, scope);
+        let queued: Vec<_> = self.players.get(player_id)
+            .filter(|_| include_queued)
+            .map(|player| player.pending_actions.iter()
+                .filter(|action| matches!(&action.action_type, ActionType::BuildStructure(id) if id == structure_id))
+                .map(|action| action.planet_id.clone())
+                .collect())
+            .unwrap_or_default();
+
+        if built.len() + queued.len() < max as usize {
+            return Ok(());
+        }
+
+        let own_planets = self.players.get(player_id).map(|player| &player.planets);
+        let existing = built.iter()
+            .filter(|planet_id| own_planets.is_some_and(|planets| planets.contains(planet_id)))
+            .map(|planet_id| self.planet_name(planet_id))
+            .chain(queued.iter().map(|planet_id| format!("{} (queued)", self.planet_name(planet_id))))
+            .collect();
+
+        Err(GameStateError::StructureLimitReached {
+            structure_name: definition.name.clone(),
+            limit,
+            existing,
+        })
+    }
+
+    /// Hands `planet_id` to `new_owner` and updates both players' planet lists. The planet then
+    /// consolidates: it cannot change hands again for the configured number of turns. Returns
+    /// the cancellations of the previous owner's constructions on the planet.
+    pub fn set_planet_owner(&mut self, planet_id: &PlanetId, new_owner: &PlayerId) -> Vec<GameEvent> {
+        let Some(planet) = self.map.planets.get_mut(planet_id) else {
+            return Vec::new();
+        };
+        let previous_owner = planet.get_owner().clone();
+        planet.set_owner(new_owner.clone());
+        planet.set_consolidation_turns(self.rules.consolidation_turns);
+        // The new owner's workforce starts out on the structures' own split
+        planet.set_allocation(None);
+
+        // A conquered planet no longer counts for the player who lost it
+        if let Some(previous) = previous_owner.as_ref().and_then(|owner| self.players.get_mut(owner)) {
+            previous.planets.retain(|owned| owned != planet_id);
+            previous.empire_effects = None;
+        }
+
+        if let Some(player) = self.players.get_mut(new_owner) {
+            player.explored.insert(planet_id.clone());
+            if !player.planets.contains(planet_id) {
+                player.planets.push(planet_id.clone());
+            }
+            player.empire_effects = None;
+        }
+
+        match previous_owner.filter(|previous| previous != new_owner) {
+            Some(previous) => self.cancel_orphaned_actions(&previous, Lost::Planet(planet_id)),
+            None => Vec::new(),
+        }
+    }
+
+    /// Takes what colonizing `planet_id` costs from the player's nearest planet, see
+    /// [`rules::colonization_cost`]. Returns the paying planet and the amount taken; None when
+    /// the colony is free.
+    fn pay_for_colonization(&mut self, player_id: &PlayerId, planet_id: &PlanetId) -> Option<(PlanetId, Resources)> {
+        let planet = self.map.planets.get(planet_id)?;
+        let player = self.players.get(player_id)?;
+        let rules::ColonizationCost { origin, cost, .. } = rules::colonization_cost(planet, player, self)?;
+        if cost == Resources::default() {
+            return None;
+        }
+        let origin_planet = self.map.planets.get_mut(&origin).expect("Origin is one of the player's planets");
+        let paid = cost.capped_at(&origin_planet.available_resources);
+        origin_planet.available_resources -= &paid;
+        Some((origin, paid))
+    }
+
+    /// Takes a player out of the turn rotation, such as one who dropped out of the game. The
+    /// round then ends once the remaining players have acted, whether or not the removed
+    /// player had.
+    pub fn remove_from_rotation(&mut self, player_id: &PlayerId) {
+        self.players_order.retain(|id| id != player_id);
+        self.acted_this_round.remove(player_id);
+    }
+
+    /// Returns the display name of a planet, falling back to its id.
+    fn planet_name(&self, planet_id: &PlanetId) -> String {
+        self.map.planets.get(planet_id)
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| planet_id.clone())
+    }
+
+    /// Queues `unit` to travel from where it stands to `target_planet` over `distance` turns.
+    /// Returns the source and target names for the order event.
+    fn order_move(
+        &mut self,
+        player_id: &PlayerId,
+        unit: MovableUnit,
+        target_planet: PlanetId,
+        distance: u8,
+    ) -> (String, String) {
+        let player = &self.players[player_id];
+        let source_planet = match &unit {
+            MovableUnit::Fleet(fleet_id) => player.fleets.get(fleet_id)
+                .expect("Fleet must exist (validated by command)")
+                .location.clone(),
+            MovableUnit::Ship(ship_id) => player.ships.get(ship_id)
+                .expect("Ship must exist (validated by command)")
+                .location.clone(),
+        };
+        let source_name = self.planet_name(&source_planet);
+        let target_name = self.planet_name(&target_planet);
+
+        // No resource cost for movement
+        let action_type = match unit {
+            MovableUnit::Fleet(fleet_id) => ActionType::MoveFleet(fleet_id, target_planet),
+            MovableUnit::Ship(ship_id) => ActionType::MoveShip(ship_id, target_planet),
+        };
+        let pending_action = PendingAction::new(action_type, source_planet, distance as u32, Resources::default());
+        self.players.get_mut(player_id)
+            .expect("Acting player must exist")
+            .pending_actions.push(pending_action);
+
+        (source_name, target_name)
+    }
+
+    /// Validates the next level of `structure_id`, reserves its cost on the planet and queues the
+    /// upgrade for `player_id`. Returns the cost and scaled duration, and the storage the planet
+    /// may fill up meanwhile.
+    fn queue_upgrade(
+        &mut self,
+        player_id: &PlayerId,
+        planet_id: &PlanetId,
+        structure_id: &StructureId,
+        auto_repeat: bool,
+    ) -> Result<ConstructionPreview, GameStateError> {
+        let preview = self.preview_structure_upgrade(player_id, planet_id, structure_id)?;
+        let build_info = BuildInfo { cost: preview.cost.clone(), turns: preview.turns };
+        self.reserve_and_queue(player_id, planet_id, ActionType::UpgradeStructure(structure_id.clone()), build_info)?
+            .auto_repeat = auto_repeat;
+
+        Ok(preview)
+    }
+
+    /// Queues `action_type` on `planet_id` for `build_info.turns`, taking `build_info.cost` from
+    /// the planet's stock. Every construction that pays up front goes through here: the queue
+    /// conflict check and the affordability check run before anything changes, a structure build
+    /// puts up its construction site, and the action reserves exactly what left the stock - so a
+    /// cancel or an orphaned action refunds what was paid. Returns the queued action.
+    fn reserve_and_queue(
+        &mut self,
+        player_id: &PlayerId,
+        planet_id: &PlanetId,
+        action_type: ActionType,
+        build_info: BuildInfo,
+    ) -> Result<&mut PendingAction, GameStateError> {
+        self.check_action_conflicts(player_id, planet_id, &action_type)?;
+
+        let planet = self.map.planets.get_mut(planet_id)
+            .ok_or_else(|| GameStateError::UnknownPlanet(planet_id.clone()))?;
+        if !planet.available_resources.has_enough(&build_info.cost) {
+            return Err(PlanetError::NotEnoughResources { name: planet.name.clone(), cost: build_info.cost }.into());
+        }
+        if let ActionType::BuildStructure(structure_id) = &action_type {
+            planet.start_construction(structure_id, &self.structure_config)?;
+        }
+        planet.available_resources -= &build_info.cost;
+
+        let player = self.players.get_mut(player_id)
+            .expect("Acting player must exist in game state");
+        player.pending_actions.push(PendingAction::new(action_type, planet_id.clone(), build_info.turns, build_info.cost));
+        Ok(player.pending_actions.last_mut().expect("Action was just queued"))
+    }
+
+    /// Runs every check a new structure build on `planet_id` must pass - queue conflicts,
+    /// structure limits, prerequisites and resources - without changing anything. Returns the
+    /// cost and the build time at the game's speed.
+    pub fn check_structure_build(
+        &self,
+        player_id: &PlayerId,
+        planet_id: &PlanetId,
+        structure_id: &StructureId,
+    ) -> Result<BuildInfo, GameStateError> {
+        self.check_action_conflicts(player_id, planet_id, &ActionType::BuildStructure(structure_id.clone()))?;
+        self.check_structure_limit(player_id, structure_id, true)?;
+
+        let planet = self.map.planets.get(planet_id)
+            .ok_or_else(|| GameStateError::UnknownPlanet(planet_id.clone()))?;
+        let build_info = planet.validate_build_structure(
+            structure_id, &self.structure_config, self.rules.prerequisites_under_construction,
+        )?;
+        let context = ModifierContext { player_id, planet_id, kind: ConstructionKind::StructureBuild };
+        self.resolve_construction(planet_id, build_info, &context)
+    }
+
+    /// Checks whether `action_type` can be queued on a planet alongside the player's other actions.
+    /// A planet runs as many structure builds, upgrades and ship builds at a time as the rules
+    /// profile gives it slots for, and never two actions on the same structure.
+    fn check_action_conflicts(
+        &self,
+        player_id: &PlayerId,
+        planet_id: &PlanetId,
+        action_type: &ActionType,
+    ) -> Result<(), GameStateError> {
+        let player = self.players.get(player_id)
+            .ok_or_else(|| GameStateError::UnknownPlayer(player_id.clone()))?;
+        // A held build occupies its structure, but not the planet's build slot: the
+        // prerequisite it waits for may need that slot to be rebuilt
+        let held: Vec<_> = player.held_actions_on_planet(planet_id)
+            .map(|held| &held.action.action_type)
+            .collect();
+        let existing: Vec<_> = player.pending_actions_on_planet(planet_id)
+            .map(|action| &action.action_type)
+            .collect();
+
+        match action_type {
+            ActionType::BuildStructure(structure_id) | ActionType::UpgradeStructure(structure_id) => {
+                let targets_same_structure = existing.iter().chain(&held).any(|existing| matches!(existing,
+                    ActionType::BuildStructure(id) | ActionType::UpgradeStructure(id) if id == structure_id));
+                if targets_same_structure {
+                    return Err(GameStateError::StructureBusy(structure_id.clone()));
+                }
+
+                let profile = self.rules_profile();
+                let (in_progress, slots, kind) = match action_type {
+                    ActionType::BuildStructure(_) => (
+                        existing.iter().filter(|a| matches!(a, ActionType::BuildStructure(_))).count(),
+                        profile.build_slots,
+                        "structure build",
+                    ),
+                    _ => (
+                        existing.iter().filter(|a| matches!(a, ActionType::UpgradeStructure(_))).count(),
+                        profile.upgrade_slots,
+                        "structure upgrade",
+                    ),
+                };
+                if in_progress >= slots as usize {
+                    return Err(GameStateError::ConstructionSlotOccupied { planet_id: planet_id.clone(), kind, slots });
+                }
+            }
+            ActionType::BuildShip(_, _) | ActionType::RetrofitShip(_, _) => {
+                if self.free_shipyard_slots(player_id, planet_id) == 0 {
+                    let slots = self.shipyard_slots(planet_id);
+                    return Err(GameStateError::ShipyardSlotsFull { planet_id: planet_id.clone(), slots });
+                }
+            }
+            ActionType::MoveFleet(_, _) | ActionType::MoveShip(_, _) | ActionType::BombardPlanet(_, _) => {}
+        }
+
+        Ok(())
+    }
+
+    /// Returns how many ships a planet can build at once: the profile's ships per shipyard
+    /// level, counting a planet without a shipyard as level one.
+    pub fn shipyard_slots(&self, planet_id: &PlanetId) -> usize {
+        let level = self.map.planets.get(planet_id)
+            .map(|planet| planet.get_structure_level(&String::from("orbital_shipyard")))
+            .unwrap_or(0)
+            .max(1);
+        level as usize * self.rules_profile().ships_per_shipyard_level as usize
+    }
+
+    /// Returns the shipyard slots on a planet not taken by the player's queued ship builds and retrofits.
+    pub fn free_shipyard_slots(&self, player_id: &PlayerId, planet_id: &PlanetId) -> usize {
+        let ships_in_progress = self.players.get(player_id)
+            .map(|player| player.pending_actions_on_planet(planet_id)
+                .filter(|action| matches!(action.action_type, ActionType::BuildShip(_, _) | ActionType::RetrofitShip(_, _)))
+                .count())
+            .unwrap_or(0);
+        self.shipyard_slots(planet_id).saturating_sub(ships_in_progress)
+    }
+
+    /// What refitting a `from` ship into a `to` ship costs: the difference in build cost,
+    /// never below zero for any resource.
+    pub fn retrofit_cost(&self, from: &ShipId, to: &ShipId) -> Resources {
+        let cost_of = |ship_type: &ShipId| self.ship_config.get(ship_type)
+            .map(|ship_def| ship_def.cost.clone())
+            .unwrap_or_default();
+        cost_of(to) - cost_of(from)
+    }
+
+    /// Applies a validated command effect on behalf of `acting_player`.
+    /// This is the pure state transition: no I/O happens here, callers render the returned events.
+    pub fn apply_effect(
+        &mut self,
+        command_effect: CommandEffect,
+        acting_player: &PlayerId,
+    ) -> Result<Vec<GameEvent>, GameStateError> {
+        if !self.players.contains_key(acting_player) {
+            return Err(GameStateError::UnknownPlayer(acting_player.clone()));
+        }
+
+        let mut events = Vec::new();
+        let turn = self.turn;
+
+        match command_effect {
+            CommandEffect::BuildStructure { planet_id, structure_id } => {
+                let preview = self.preview_structure_build(acting_player, &planet_id, &structure_id)?;
+                let build_info = BuildInfo { cost: preview.cost.clone(), turns: preview.turns };
+                self.reserve_and_queue(
+                    acting_player, &planet_id, ActionType::BuildStructure(structure_id.clone()), build_info
+                )?;
+
+                events.push(GameEvent::StructureQueued {
+                    structure_id,
+                    cost: preview.cost,
+                    turns: preview.turns,
+                    warnings: preview.warnings,
+                    advice: preview.advice,
+                });
+            },
+            CommandEffect::UpgradeStructure { planet_id, structure_id, auto_repeat } => {
+                let preview = self.queue_upgrade(acting_player, &planet_id, &structure_id, auto_repeat)?;
+
+                events.push(GameEvent::UpgradeQueued {
+                    structure_id,
+                    cost: preview.cost,
+                    turns: preview.turns,
+                    warnings: preview.warnings,
+                });
+            },
+            CommandEffect::BuildShip { planet_id, ship_id, deliver_to } => {
+                let preview = self.preview_ship_build(acting_player, &planet_id, &ship_id, deliver_to.as_ref())?;
+                let build_time = preview.turns;
+                let warnings = preview.warnings;
+
+                // Reserve the instance id now so the ship can be referred to while under construction
+                let ship_instance_id = self.players.get_mut(acting_player)
+                    .expect("Acting player must exist in game state")
+                    .generate_ship_id(&ship_id);
+
+                let build_info = BuildInfo { cost: preview.cost.clone(), turns: build_time };
+                self.reserve_and_queue(
+                    acting_player,
+                    &planet_id,
+                    ActionType::BuildShip(ship_id.clone(), ship_instance_id.clone()),
+                    build_info,
+                )?.deliver_to = deliver_to.clone();
+
+                events.push(GameEvent::ShipQueued {
+                    ship_id,
+                    ship_instance_id,
+                    cost: preview.cost,
+                    turns: build_time,
+                    warnings,
+                    delivery_planet: deliver_to.map(|planet_id| self.planet_name(&planet_id)),
+                });
+            },
+            CommandEffect::RetrofitShip { planet_id, ship_id, target_type } => {
+                let from_type = self.players[acting_player].ships.get(&ship_id)
+                    .expect("Ship must exist (validated by command)")
+                    .ship_type.clone();
+                let ship_def = self.ship_config.get(&target_type)
+                    .expect("Ship must exist (validated by command)");
+                let context = ModifierContext {
+                    player_id: acting_player,
+                    planet_id: &planet_id,
+                    kind: ConstructionKind::ShipRetrofit,
+                };
+                let turns = self.resolve_build_time(ship_def.build_time, &context);
+                let cost = self.retrofit_cost(&from_type, &target_type);
+
+                self.reserve_and_queue(
+                    acting_player,
+                    &planet_id,
+                    ActionType::RetrofitShip(target_type.clone(), ship_id.clone()),
+                    BuildInfo { cost: cost.clone(), turns },
+                )?;
+                let warnings = self.map.planets[&planet_id].storage_warnings(turns);
+
+                events.push(GameEvent::ShipRetrofitQueued {
+                    ship_instance_id: ship_id,
+                    from_type,
+                    to_type: target_type,
+                    cost,
+                    turns,
+                    warnings,
+                });
+            },
+            CommandEffect::CancelAction { planet_id, target_id } => {
+                // Remove pending action and get the reserved resources
+                let player = self.players.get_mut(acting_player)
+                    .expect("Acting player must exist in game state");
+                let action = player.remove_pending_action(&planet_id, &target_id)
+                    .expect("Pending action must exist (validated by command)");
+
+                // Cancelling costs a share of the reserved resources so queue-then-cancel
+                // cannot be used to park resources above storage caps
+                let penalty = action.reserved_resources
+                    .percentage(self.rules_profile().cancellation_penalty_percent);
+                let refund = action.reserved_resources - penalty.clone();
+
+                // Get planet and calculate available space
+                let planet = self.map.planets.get_mut(&planet_id)
+                    .expect("Planet must exist (validated by command)");
+                if let ActionType::BuildStructure(structure_id) = &action.action_type {
+                    planet.clear_construction_site(structure_id);
+                }
+                let space_available = planet.storage_capacity.clone() - planet.available_resources.clone();
+
+                // Refund resources with overflow handling
+                if !space_available.has_enough(&refund) {
+                    // Partial refund - add what fits, waste the rest
+                    let refunded = refund.capped_at(&space_available);
+                    let wasted = refund - refunded.clone();
+                    planet.available_resources += &refunded;
+
+                    events.push(GameEvent::ActionCancelled {
+                        planet_name: planet.name.clone(),
+                        refunded,
+                        penalty,
+                        penalty_percent: self.rules_profile().cancellation_penalty_percent,
+                        wasted: Some(wasted),
+                    });
+                } else {
+                    planet.available_resources += &refund;
+
+                    events.push(GameEvent::ActionCancelled {
+                        planet_name: planet.name.clone(),
+                        refunded: refund,
+                        penalty,
+                        penalty_percent: self.rules_profile().cancellation_penalty_percent,
+                        wasted: None,
+                    });
+                }
+            },
+            CommandEffect::CreateFleet { name, ship_ids, location } => {
+                let player = self.players.get_mut(acting_player)
+                    .expect("Acting player must exist");
+
+                let fleet_id = player.next_fleet_id();
+                let mut fleet = Fleet::new(fleet_id.clone(), name.clone(), location);
+
+                // Add ships to fleet and update ship's fleet_id
+                for ship_id in &ship_ids {
+                    fleet.add_ship(ship_id.clone());
+                    if let Some(ship) = player.ships.get_mut(ship_id) {
+                        ship.fleet_id = Some(fleet_id.clone());
+                    }
+                }
+
+                player.fleets.insert(fleet_id.clone(), fleet);
+
+                events.push(GameEvent::FleetCreated {
+                    fleet_id,
+                    name,
+                    ship_count: ship_ids.len(),
+                });
+            }
+            CommandEffect::AddToFleet { fleet_id, ship_ids } => {
+                let player = self.players.get_mut(acting_player)
+                    .expect("Acting player must exist");
+
+                for ship_id in &ship_ids {
+                    if let Some(ship) = player.ships.get_mut(ship_id) {
+                        ship.fleet_id = Some(fleet_id.clone());
+                    }
+                }
+
+                if let Some(fleet) = player.fleets.get_mut(&fleet_id) {
+                    for ship_id in &ship_ids {
+                        fleet.add_ship(ship_id.clone());
+                    }
+                    events.push(GameEvent::ShipsAddedToFleet {
+                        fleet_name: fleet.name.clone(),
+                        ship_count: ship_ids.len(),
+                    });
+                }
+            }
+            CommandEffect::RemoveFromFleet { fleet_id, ship_ids } => {
+                let player = self.players.get_mut(acting_player)
+                    .expect("Acting player must exist");
+
+                for ship_id in &ship_ids {
+                    if let Some(ship) = player.ships.get_mut(ship_id) {
+                        ship.fleet_id = None;
+                    }
+                }
+
+                if let Some(fleet) = player.fleets.get_mut(&fleet_id) {
+                    for ship_id in &ship_ids {
+                        fleet.remove_ship(ship_id);
+                    }
+                    events.push(GameEvent::ShipsRemovedFromFleet {
+                        fleet_name: fleet.name.clone(),
+                        ship_count: ship_ids.len(),
+                    });
+                }
+            }
+            CommandEffect::DisbandFleet { fleet_id } => {
+                let player = self.players.get_mut(acting_player)
+                    .expect("Acting player must exist");
+
+                let fleet = player.fleets.remove(&fleet_id);
+                let fleet_name = fleet.as_ref().map(|f| f.name.clone()).unwrap_or_default();
+                let ship_ids = fleet.map(|f| f.ships).unwrap_or_default();
+
+                // Clear fleet_id from all ships in the fleet
+                for ship_id in &ship_ids {
+                    if let Some(ship) = player.ships.get_mut(ship_id) {
+                        ship.fleet_id = None;
+                    }
+                }
+
+                events.push(GameEvent::FleetDisbanded {
+                    fleet_name,
+                    ship_count: ship_ids.len(),
+                });
+                events.extend(self.cancel_orphaned_actions(acting_player, Lost::Fleet(&fleet_id)));
+                events.extend(self.break_rally_points(acting_player, &fleet_id, "was disbanded"));
+            }
+            CommandEffect::MergeFleets { into_fleet, from_fleet } => {
+                let player = self.players.get_mut(acting_player)
+                    .expect("Acting player must exist");
+
+                let source = player.fleets.remove(&from_fleet)
+                    .expect("Fleet must exist (validated by command)");
+                let target = player.fleets.get_mut(&into_fleet)
+                    .expect("Fleet must exist (validated by command)");
+
+                for ship_id in &source.ships {
+                    target.add_ship(ship_id.clone());
+                    if let Some(ship) = player.ships.get_mut(ship_id) {
+                        ship.fleet_id = Some(into_fleet.clone());
+                    }
+                }
+                target.cargo += &source.cargo;
+
+                events.push(GameEvent::FleetsMerged {
+                    into_name: target.name.clone(),
+                    from_name: source.name,
+                    ship_count: source.ships.len(),
+                });
+                events.extend(self.cancel_orphaned_actions(acting_player, Lost::Fleet(&from_fleet)));
+                events.extend(self.break_rally_points(acting_player, &from_fleet, "was merged into another fleet"));
+            }
+            CommandEffect::SplitFleet { fleet_id, name, ship_ids } => {
+                let player = self.players.get_mut(acting_player)
+                    .expect("Acting player must exist");
+
+                let new_fleet_id = player.next_fleet_id();
+                let source = player.fleets.get_mut(&fleet_id)
+                    .expect("Fleet must exist (validated by command)");
+                let source_name = source.name.clone();
+                let mut fleet = Fleet::new(new_fleet_id.clone(), name.clone(), source.location.clone());
+
+                for ship_id in &ship_ids {
+                    source.remove_ship(ship_id);
+                    fleet.add_ship(ship_id.clone());
+                    if let Some(ship) = player.ships.get_mut(ship_id) {
+                        ship.fleet_id = Some(new_fleet_id.clone());
+                    }
+                }
+
+                player.fleets.insert(new_fleet_id.clone(), fleet);
+
+                events.push(GameEvent::FleetSplit {
+                    fleet_id: new_fleet_id,
+                    name,
+                    source_name,
+                    ship_count: ship_ids.len(),
+                });
+            }
+            CommandEffect::MoveFleet { fleet_id, target_planet, distance } => {
+                let fleet_name = self.players[acting_player].fleets.get(&fleet_id)
+                    .expect("Fleet must exist (validated by command)")
+                    .name.clone();
+                let (source_name, target_name) =
+                    self.order_move(acting_player, MovableUnit::Fleet(fleet_id.clone()), target_planet, distance);
+
+                let player = self.players.get_mut(acting_player)
+                    .expect("Acting player must exist");
+                // A fleet sent away is no longer holding position
+                if let Some(fleet) = player.fleets.get_mut(&fleet_id) {
+                    fleet.sentry = false;
+                }
+
+                events.push(GameEvent::FleetMoveOrdered {
+                    fleet_id: fleet_id.clone(),
+                    fleet_name,
+                    source_name,
+                    target_name,
+                    turns: distance,
+                });
+                events.extend(self.break_rally_points(acting_player, &fleet_id, "departed"));
+            }
+            CommandEffect::MoveFleets { target_planet, moves, skipped } => {
+                // Each fleet is ordered on its own; one failing leaves the others on their way
+                let mut results = Vec::new();
+                for (fleet_id, distance) in moves {
+                    let effect = CommandEffect::MoveFleet { fleet_id: fleet_id.clone(), target_planet: target_planet.clone(), distance };
+                    match self.apply_effect(effect, acting_player) {
+                        Ok(ordered_events) => {
+                            events.extend(ordered_events);
+                            results.push((fleet_id, None));
+                        }
+                        Err(error) => results.push((fleet_id, Some(error.to_string()))),
+                    }
+                }
+                results.extend(skipped.into_iter().map(|(fleet_id, reason)| (fleet_id, Some(reason))));
+
+                events.push(GameEvent::FleetsMoveOrdered {
+                    target_name: self.planet_name(&target_planet),
+                    results,
+                });
+            }
+            CommandEffect::MoveShip { ship_id, target_planet, distance } => {
+                let ship_type = self.players[acting_player].ships.get(&ship_id)
+                    .expect("Ship must exist (validated by command)")
+                    .ship_type.clone();
+                let (source_name, target_name) =
+                    self.order_move(acting_player, MovableUnit::Ship(ship_id.clone()), target_planet, distance);
+
+                events.push(GameEvent::ShipMoveOrdered {
+                    ship_instance_id: ship_id,
+                    ship_type,
+                    source_name,
+                    target_name,
+                    turns: distance,
+                });
+            }
+            CommandEffect::BombardPlanet { fleet_id, target_planet, bombardment_power, target_structure } => {
+                let player = self.players.get(acting_player)
+                    .expect("Acting player must exist");
+
+                let fleet_name = player.fleets.get(&fleet_id)
+                    .expect("Fleet must exist (validated by command)")
+                    .name.clone();
+                let target_name = self.planet_name(&target_planet);
+
+                // Create pending action (no resource cost for bombardment, per-turn action)
+                let mut pending_action = PendingAction::new(
+                    ActionType::BombardPlanet(fleet_id.clone(), target_planet.clone()),
+                    target_planet,
+                    u32::MAX, // Bombardment continues indefinitely until shields are down
+                    Resources::default(),
+                );
+                pending_action.target_structure = target_structure.clone();
+
+                let player = self.players.get_mut(acting_player)
+                    .expect("Acting player must exist");
+                player.pending_actions.push(pending_action);
+
+                events.push(GameEvent::BombardmentStarted {
+                    fleet_id,
+                    fleet_name,
+                    target_name,
+                    bombardment_power,
+                    target_structure,
+                });
+            }
+            CommandEffect::SetFleetSentry { fleet_id, sentry } => {
+                let fleet = self.players.get_mut(acting_player)
+                    .expect("Acting player must exist")
+                    .fleets.get_mut(&fleet_id)
+                    .expect("Fleet must exist (validated by command)");
+                fleet.sentry = sentry;
+                fleet.woken_by = None;
+
+                events.push(GameEvent::FleetSentrySet { fleet_id, sentry });
+            }
+            CommandEffect::LoadCargo { fleet_id, cargo } => {
+                events.push(self.load_cargo(acting_player, &fleet_id, cargo));
+            }
+            CommandEffect::UnloadCargo { fleet_id } => {
+                let location = self.players[acting_player].fleets.get(&fleet_id)
+                    .expect("Fleet must exist (validated by command)")
+                    .location.clone();
+                let cargo = self.take_cargo(acting_player, &fleet_id);
+                events.push(self.unload_cargo(acting_player, &fleet_id, &location, cargo));
+            }
+            CommandEffect::CancelBombard { fleet_id } => {
+                let player = self.players.get_mut(acting_player)
+                    .expect("Acting player must exist");
+
+                player.pending_actions.retain(|action| {
+                    !matches!(&action.action_type,
+                        ActionType::BombardPlanet(fid, _)
+                        if fid == &fleet_id)
+                });
+
+                events.push(GameEvent::BombardmentCancelled { fleet_id });
+            }
+            CommandEffect::ColonizePlanet { fleet_id, planet_id } => {
+                let planet_name = self.planet_name(&planet_id);
+
+                // A neutral planet is paid for up front, and the payment returned if no colony comes of it
+                let payment = self.pay_for_colonization(acting_player, &planet_id);
+                if let Some((origin_id, cost)) = &payment {
+                    events.push(GameEvent::ColonizationPaid {
+                        planet_name: planet_name.clone(),
+                        origin_name: self.planet_name(origin_id),
+                        cost: cost.clone(),
+                    });
+                }
+
+                // Landing on an enemy planet means fighting through its garrison first
+                let assault = self.assault_garrison(acting_player, &fleet_id, &planet_id);
+                let repelled = matches!(assault.first(), Some(GameEvent::GarrisonAssault { attacker_wins: false, .. }));
+                events.extend(assault);
+                let ark = self.players[acting_player].fleets.get(&fleet_id)
+                    .and_then(|fleet| rules::fleet_ark(fleet, &self.players[acting_player]))
+                    .cloned();
+                let ark_lost = ark.is_none();
+
+                if ark_lost && !repelled {
+                    events.push(GameEvent::ColonizationFailed {
+                        planet_name: planet_name.clone(),
+                        reason: String::from("the fleet no longer carries an ark"),
+                    });
+                }
+                let colonized = !repelled && !ark_lost && {
+                    let planet = self.map.planets.get_mut(&planet_id)
+                        .expect("Planet must exist (validated by command)");
+
+                    match planet.colonize(&self.structure_config, self.turn) {
+                        Ok(ruin) => {
+                            // The ark is broken up to stock the colony, on top of its filled storage
+                            let ark_bonus = self.rules.profile.ark_colony_bonus.clone();
+                            planet.available_resources += &ark_bonus;
+                            events.extend(self.set_planet_owner(&planet_id, acting_player));
+
+                            events.push(GameEvent::PlanetColonized {
+                                fleet_id: fleet_id.clone(),
+                                planet_name: planet_name.clone(),
+                                player_name: self.players[acting_player].name.clone(),
+                                ark_bonus,
+                            });
+                            if let Some(ruin) = ruin {
+                                events.push(GameEvent::RuinClaimed {
+                                    planet_name: planet_name.clone(),
+                                    description: ruin.describe(&self.structure_config),
+                                });
+                            }
+                            // The colony takes the fleet's cargo before the ark carrying it is gone
+                            let cargo = self.take_cargo(acting_player, &fleet_id);
+                            if cargo != Resources::default() {
+                                events.push(self.unload_cargo(acting_player, &fleet_id, &planet_id, cargo));
+                            }
+                            events.extend(self.destroy_ships(&Some(acting_player.clone()), ark.as_slice()));
+                            true
+                        }
+                        Err(e) => {
+                            events.push(GameEvent::ColonizationFailed {
+                                planet_name: planet_name.clone(),
+                                reason: e.to_string(),
+                            });
+                            false
+                        }
+                    }
+                };
+
+                if let Some((origin_id, cost)) = payment
+                    && !colonized
+                {
+                    let refunded = self.map.planets.get_mut(&origin_id)
+                        .expect("Paying planet must exist")
+                        .refund(&cost);
+                    events.push(GameEvent::ColonizationRefunded {
+                        planet_name,
+                        origin_name: self.planet_name(&origin_id),
+                        refunded,
+                    });
+                }
+            }
+            CommandEffect::SaveFleetTemplate { name, template } => {
+                let player = self.players.get_mut(acting_player)
+                    .expect("Acting player must exist");
+                let replaced = player.fleet_templates.insert(name.clone(), template.clone()).is_some();
+
+                events.push(GameEvent::FleetTemplateSaved { name, template, replaced });
+            }
+            CommandEffect::BuildStructureOnPlanets { structure_id, queued, skipped } => {
+                // Each planet is queued on its own; one failing leaves the others in place
+                let mut results = Vec::new();
+                for planet_id in queued {
+                    let effect = CommandEffect::BuildStructure { planet_id: planet_id.clone(), structure_id: structure_id.clone() };
+                    match self.apply_effect(effect, acting_player) {
+                        Ok(queued_events) => {
+                            events.extend(queued_events);
+                            results.push((self.planet_name(&planet_id), None));
+                        }
+                        Err(error) => results.push((self.planet_name(&planet_id), Some(error.to_string()))),
+                    }
+                }
+                results.extend(skipped.into_iter().map(|(planet_id, reason)| (self.planet_name(&planet_id), Some(reason))));
+                results.sort_by(|a, b| a.0.cmp(&b.0));
+
+                events.push(GameEvent::StructureQueuedOnPlanets {
+                    structure_name: self.structure_config.get(&structure_id)
+                        .map_or(structure_id.clone(), |definition| definition.name.clone()),
+                    results,
+                });
+            }
+            CommandEffect::BuildFleetTemplate { template_name, planet_id, queued, skipped } => {
+                for ship_id in &queued {
+                    events.extend(self.apply_effect(
+                        CommandEffect::BuildShip { planet_id: planet_id.clone(), ship_id: ship_id.clone(), deliver_to: None },
+                        acting_player,
+                    )?);
+                }
+
+                events.push(GameEvent::FleetTemplateQueued {
+                    template_name,
+                    planet_name: self.planet_name(&planet_id),
+                    queued: queued.len(),
+                    skipped,
+                });
+            }
+            CommandEffect::SetRallyPoint { planet_id, rally_point } => {
+                let planet = self.map.planets.get_mut(&planet_id)
+                    .expect("Planet must exist (validated by command)");
+                planet.set_rally_point(rally_point.clone());
+
+                events.push(GameEvent::RallyPointSet {
+                    planet_name: planet.name.clone(),
+                    rally_point,
+                });
+            }
+            CommandEffect::GarrisonShips { planet_id, ship_ids } => {
+                let player = self.players.get_mut(acting_player)
+                    .expect("Acting player must exist");
+                let planet = self.map.planets.get_mut(&planet_id)
+                    .expect("Planet must exist (validated by command)");
+
+                // Garrisoned ships leave their fleets so they stay behind when the fleet moves
+                for ship_id in &ship_ids {
+                    if let Some(ship) = player.ships.get_mut(ship_id)
+                        && let Some(fleet_id) = ship.fleet_id.take()
+                        && let Some(fleet) = player.fleets.get_mut(&fleet_id)
+                    {
+                        fleet.remove_ship(ship_id);
+                    }
+                    planet.garrison_ship(ship_id.clone());
+                }
+
+                events.push(GameEvent::ShipsGarrisoned {
+                    planet_name: planet.name.clone(),
+                    ship_count: ship_ids.len(),
+                });
+            }
+            CommandEffect::UngarrisonShips { planet_id, ship_ids } => {
+                let planet = self.map.planets.get_mut(&planet_id)
+                    .expect("Planet must exist (validated by command)");
+                for ship_id in &ship_ids {
+                    planet.ungarrison_ship(ship_id);
+                }
+
+                events.push(GameEvent::ShipsUngarrisoned {
+                    planet_name: planet.name.clone(),
+                    ship_count: ship_ids.len(),
+                });
+            }
+            CommandEffect::OverchargeShield { planet_id } => {
+                let planet = self.map.planets.get_mut(&planet_id)
+                    .expect("Planet must exist (validated by command)");
+                planet.available_resources.energy -= OVERCHARGE_ENERGY_COST;
+                let added = planet.overcharge_shield();
+
+                events.push(GameEvent::ShieldOvercharged {
+                    planet_name: planet.name.clone(),
+                    added,
+                    shield_hp: planet.get_shield_hp(),
+                    energy_spent: OVERCHARGE_ENERGY_COST,
+                });
+            }
+            CommandEffect::SetStructurePower { planet_id, structure_id, on } => {
+                let planet = self.map.planets.get_mut(&planet_id)
+                    .expect("Planet must exist (validated by command)");
+                planet.set_structure_power(&structure_id, on)?;
+                let structure = &planet.get_structures()[&structure_id];
+
+                events.push(GameEvent::StructurePowerChanged {
+                    planet_name: planet.name.clone(),
+                    structure_name: structure.name.clone(),
+                    on,
+                    energy: structure.rated_energy_consumption(),
+                });
+                self.invalidate_empire_effects(acting_player);
+            }
+            CommandEffect::SetAllocation { planet_id, allocation } => {
+                let planet = self.map.planets.get_mut(&planet_id)
+                    .expect("Planet must exist (validated by command)");
+                planet.set_allocation(allocation);
+
+                events.push(GameEvent::AllocationSet {
+                    planet_name: planet.name.clone(),
+                    allocation,
+                    production: planet.allocated_production(self.rules.allocation_shift_percent),
+                });
+            }
+            CommandEffect::LinkGates { first, second } => {
+                self.map.add_edge(&first, &second, GATE_LINK_DISTANCE, ConnectionKind::Gate)?;
+
+                events.push(GameEvent::GatesLinked {
+                    first_name: self.planet_name(&first),
+                    second_name: self.planet_name(&second),
+                    distance: GATE_LINK_DISTANCE,
+                });
+            }
+            CommandEffect::DefineAlias { name, expansion } => {
+                let player = self.players.get_mut(acting_player)
+                    .expect("Acting player must exist");
+                let replaced = player.aliases.insert(name.clone(), expansion.clone()).is_some();
+
+                events.push(GameEvent::AliasDefined { name, expansion, replaced });
+            }
+            CommandEffect::RemoveAlias { name } => {
+                let player = self.players.get_mut(acting_player)
+                    .expect("Acting player must exist");
+                player.aliases.remove(&name);
+
+                events.push(GameEvent::AliasRemoved { name });
+            }
+            CommandEffect::AddNote { text } => {
+                let player = self.players.get_mut(acting_player)
+                    .expect("Acting player must exist");
+                player.notes.push(text);
+
+                events.push(GameEvent::NoteAdded { number: player.notes.len() });
+            }
+            CommandEffect::CompleteNote { number } => {
+                let player = self.players.get_mut(acting_player)
+                    .expect("Acting player must exist");
+                player.notes.remove(number - 1);
+
+                events.push(GameEvent::NoteDone { number, remaining: player.notes.len() });
+            }
+            CommandEffect::SetGameOption { option } => {
+                self.options.apply(option);
+                self.trim_turn_history();
+                let player_name = self.players.get(acting_player)
+                    .expect("Acting player must exist")
+                    .name.clone();
+
+                events.push(GameEvent::GameOptionChanged { player_name, key: option.key().to_string(), value: option.value() });
+            }
+            CommandEffect::Dev(action) => {
+                events.extend(self.apply_dev_action(action, acting_player));
+            }
+            CommandEffect::EndTurn { player_name } => {
+                events.push(GameEvent::TurnEnded { player_name });
+                events.extend(self.end_turn());
+            }
+            CommandEffect::Save { .. }
+            | CommandEffect::Load { .. }
+            | CommandEffect::ListSaves
+            | CommandEffect::DeleteSave { .. }
+            | CommandEffect::ExportReport { .. }
+            | CommandEffect::ListOptions
+            | CommandEffect::SetSessionOption { .. }
+            | CommandEffect::Exit
+            | CommandEffect::Rematch => {},
+            CommandEffect::None { message } => {
+                events.push(GameEvent::Info { message });
+            }
+        }
+
+        // A conquest can leave a gate link between planets of different owners
+        events.extend(self.collapse_broken_gate_links());
+        self.refresh_intel();
+        if self.turn != turn {
+            self.record_turn_views();
+        }
+        events.extend(self.engine_warnings.take().into_iter().map(|message| GameEvent::EngineWarning { message }));
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::game_event::ArrivalKind;
+    use crate::engine::game_rules::GameSpeed;
+    use crate::engine::map::{Fogged, RenderStyle};
+    use crate::engine::planet::Ruin;
+    use crate::test_support::{self, end_round, fixture_game};
+
+    /// Fixture game with an extra "titan" ship whose stats sit at the config cap.
+    fn game_with_titans() -> GameState {
+        let mut state = fixture_game(1);
+        let titan = format!(r#"[{{
+            "id": "titan", "name": "Titan", "description": "Modded capital ship",
+            "attack": {max}, "shield": {max}, "bombardment": {max},
+            "cost": {{"minerals": {max}, "gas": {max}, "energy": {max}}},
+            "build_time": {max}, "counters": ["titan"], "required_shipyard_level": 1
+        }}]"#, max = crate::engine::configs::MAX_CONFIG_VALUE);
+        state.ship_config = ShipConfig::load_from_string(&titan).unwrap();
+        state
+    }
+
+    /// Gives `player_id` a fleet of `count` titans at `location`.
+    fn titan_fleet(state: &mut GameState, player_id: &str, count: usize, location: &PlanetId) -> FleetId {
+        let player = state.players.get_mut(player_id).unwrap();
+        let fleet_id = player.next_fleet_id();
+        let mut fleet = Fleet::new(fleet_id.clone(), String::from("Armada"), location.clone());
+        for _ in 0..count {
+            let ship_id = player.add_ship(String::from("titan"), location.clone());
+            player.ships.get_mut(&ship_id).unwrap().fleet_id = Some(fleet_id.clone());
+            fleet.add_ship(ship_id);
+        }
+        player.fleets.insert(fleet_id.clone(), fleet);
+        fleet_id
+    }
+
+    /// Bob has two fleets at home, where alice has a ship and is bombarding; alice also has a
+    /// ship on a planet bob cannot see. Returns (bob_home, hidden_planet).
+    fn game_with_presence() -> (GameState, PlanetId, PlanetId) {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "bob");
+        let hidden = state.map.planets.keys()
+            .filter(|id| state.map.planets[*id].get_owner().is_none())
+            .min()
+            .unwrap()
+            .clone();
+
+        let bob = state.players.get_mut("bob").unwrap();
+        for name in ["Home Guard", "Reserve"] {
+            let fleet_id = bob.next_fleet_id();
+            bob.fleets.insert(fleet_id.clone(), Fleet::new(fleet_id, String::from(name), home.clone()));
+        }
+
+        let alice = state.players.get_mut("alice").unwrap();
+        alice.add_ship(String::from("interceptor"), home.clone());
+        alice.add_ship(String::from("interceptor"), hidden.clone());
+        alice.pending_actions.push(PendingAction::new(
+            ActionType::BombardPlanet(String::from("fleet_1"), home.clone()),
+            home.clone(),
+            0,
+            Resources::default(),
+        ));
+
+        (state, home, hidden)
+    }
+
+    #[test]
+    fn test_planet_presence_respects_visibility() {
+        let (state, home, hidden) = game_with_presence();
+
+        let presence = state.planet_presence(&String::from("bob"));
+
+        assert_eq!(presence[&home], PlanetPresence { own_fleets: 2, hostile_ships: true, bombarded: true, fogged: None });
+        // Alice's ship there stays hidden; bob only knows he has never seen the planet
+        assert_eq!(presence[&hidden], PlanetPresence { fogged: Some(Fogged::Unexplored), ..Default::default() });
+    }
+
+    #[test]
+    fn test_map_labels_show_presence_markers() {
+        let (state, home, _) = game_with_presence();
+
+        let presence = state.planet_presence(&String::from("bob"));
+        let render = state.map.render_full(&state.players, &presence, Map::DEFAULT_LABEL_ID_CHARS, RenderStyle::Unicode);
+
+        assert!(render.contains(&format!("{} (BO) [F2][!][B]", home)), "{}", render);
+        assert_eq!(render.matches("[F").count(), 1);
+    }
+
+    #[test]
+    fn test_huge_fleet_bombardment_saturates_instead_of_wrapping() {
+        let mut state = game_with_titans();
+        let bob_home = test_support::home_planet(&state, "bob");
+        let fleet_id = titan_fleet(&mut state, "alice", 5000, &bob_home);
+
+        let power = state.calculate_fleet_bombardment(&String::from("alice"), &fleet_id);
+        let overflow = state.map.planets.get_mut(&bob_home).unwrap().take_shield_damage(power);
+
+        assert_eq!(power, u32::MAX);
+        assert_eq!(overflow, u32::MAX);
+    }
+
+    #[test]
+    fn test_battle_between_huge_fleets_saturates_strengths() {
+        let mut state = game_with_titans();
+        let bob_home = test_support::home_planet(&state, "bob");
+        let origin = state.map.planets[&bob_home].get_connections()[0].to().clone();
+        let attacker = titan_fleet(&mut state, "alice", 5000, &origin);
+        titan_fleet(&mut state, "bob", 5000, &bob_home);
+
+        let events = state.process_arrival(&String::from("alice"), &MovableUnit::Fleet(attacker.clone()), &bob_home);
+
+        assert!(events.iter().any(|event| matches!(event,
+            GameEvent::Battle { attacker_strength: u32::MAX, defender_strength: u32::MAX, .. })));
+    }
+
+    #[test]
+    fn test_build_ship_deducts_resources_and_queues_action() {
+        let mut state = fixture_game(1);
+        let alice = "alice".to_string();
+        let home = test_support::home_planet(&state, "alice");
+        let minerals_before = state.map.planets[&home].available_resources.minerals;
+
+        let events = state.apply_effect(
+            CommandEffect::BuildShip { planet_id: home.clone(), ship_id: "interceptor".to_string(), deliver_to: None },
+            &alice,
+        ).unwrap();
+
+        assert!(matches!(events.as_slice(), [GameEvent::ShipQueued { .. }]));
+        let ship_cost = state.ship_config.get(&"interceptor".to_string()).unwrap().cost.minerals;
+        assert_eq!(state.map.planets[&home].available_resources.minerals, minerals_before - ship_cost);
+        assert!(state.players[&alice].has_pending_action_on_planet(&home));
+    }
+
+    #[test]
+    fn test_second_action_on_planet_is_rejected() {
+        let mut state = fixture_game(1);
+        let alice = "alice".to_string();
+        let home = test_support::home_planet(&state, "alice");
+        let effect = || CommandEffect::BuildShip {
+            planet_id: home.clone(),
+            ship_id: "interceptor".to_string(),
+            deliver_to: None,
+        };
+
+        state.apply_effect(effect(), &alice).unwrap();
+        let result = state.apply_effect(effect(), &alice);
+
+        assert!(matches!(result, Err(GameStateError::ShipyardSlotsFull { slots: 1, .. })));
+    }
+
+    fn queue(state: &mut GameState, effect: CommandEffect) -> Result<Vec<GameEvent>, GameStateError> {
+        state.apply_effect(effect, &"alice".to_string())
+    }
+
+    /// Gives alice's home planet a level 1 power grid and enough resources for several actions.
+    fn home_with_power_grid(state: &mut GameState) -> PlanetId {
+        let home = test_support::home_planet(state, "alice");
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.complete_build_structure(String::from("power_grid"), &state.structure_config).unwrap();
+        planet.recalculate_from_structures();
+        planet.available_resources = Resources { minerals: 500, gas: 250, energy: 100 };
+        home
+    }
+
+    #[test]
+    fn test_build_and_upgrade_run_concurrently() {
+        let mut state = fixture_game(1);
+        let home = home_with_power_grid(&mut state);
+
+        queue(&mut state, CommandEffect::UpgradeStructure {
+            planet_id: home.clone(),
+            structure_id: String::from("planetary_capital"),
+            auto_repeat: false,
+        }).unwrap();
+        queue(&mut state, CommandEffect::BuildStructure {
+            planet_id: home.clone(),
+            structure_id: String::from("orbital_shipyard"),
+        }).unwrap();
+        queue(&mut state, CommandEffect::BuildShip {
+            planet_id: home.clone(),
+            ship_id: String::from("interceptor"),
+            deliver_to: None,
+        }).unwrap();
+
+        assert_eq!(state.players["alice"].pending_actions_on_planet(&home).count(), 3);
+    }
+
+    #[test]
+    fn test_every_queued_action_reserves_exactly_what_it_deducted() {
+        let mut state = fixture_game(1);
+        let home = home_with_power_grid(&mut state);
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.complete_build_structure(String::from("orbital_shipyard"), &state.structure_config).unwrap();
+        planet.complete_upgrade_structure(&String::from("orbital_shipyard")).unwrap();
+        planet.recalculate_from_structures();
+        planet.available_resources = planet.storage_capacity.clone();
+        state.players.get_mut("alice").unwrap().add_ship(String::from("interceptor"), home.clone());
+
+        let queue_and_check = |state: &mut GameState, effect: CommandEffect| {
+            let before = state.map.planets[&home].available_resources.clone();
+            queue(state, effect).unwrap();
+            let deducted = before - state.map.planets[&home].available_resources.clone();
+            let action = state.players["alice"].pending_actions.last().unwrap();
+            assert_eq!(action.reserved_resources, deducted, "{:?}", action.action_type);
+            assert_ne!(deducted, Resources::default(), "{:?}", action.action_type);
+            assert!(state.check_invariants().is_empty(), "{:?}", state.check_invariants());
+        };
+        queue_and_check(&mut state, CommandEffect::UpgradeStructure {
+            planet_id: home.clone(), structure_id: String::from("planetary_capital"), auto_repeat: true,
+        });
+        queue_and_check(&mut state, CommandEffect::BuildStructure {
+            planet_id: home.clone(), structure_id: String::from("morale_center"),
+        });
+        queue_and_check(&mut state, CommandEffect::BuildShip {
+            planet_id: home.clone(), ship_id: String::from("interceptor"), deliver_to: None,
+        });
+        queue_and_check(&mut state, CommandEffect::RetrofitShip {
+            planet_id: home.clone(), ship_id: String::from("interceptor_1"), target_type: String::from("ravager"),
+        });
+    }
+
+    #[test]
+    fn test_rejected_action_leaves_stock_and_queue_untouched() {
+        let mut state = fixture_game(1);
+        let home = home_with_power_grid(&mut state);
+        state.map.planets.get_mut(&home).unwrap().available_resources = Resources::default();
+        state.players.get_mut("alice").unwrap().add_ship(String::from("interceptor"), home.clone());
+
+        let result = queue(&mut state, CommandEffect::RetrofitShip {
+            planet_id: home.clone(), ship_id: String::from("interceptor_1"), target_type: String::from("ravager"),
+        });
+
+        assert!(matches!(result, Err(GameStateError::PlanetError(PlanetError::NotEnoughResources { .. }))));
+        assert!(state.players["alice"].pending_actions.is_empty());
+        assert_eq!(state.map.planets[&home].available_resources, Resources::default());
+    }
+
+    #[test]
+    fn test_game_speed_scales_pending_action_cooldowns() {
+        let cooldowns = |speed: GameSpeed| {
+            let mut state = fixture_game(1);
+            state.rules.speed = speed;
+            let home = home_with_power_grid(&mut state);
+            for effect in [
+                CommandEffect::UpgradeStructure {
+                    planet_id: home.clone(), structure_id: String::from("planetary_capital"), auto_repeat: false,
+                },
+                CommandEffect::BuildStructure { planet_id: home.clone(), structure_id: String::from("orbital_shipyard") },
+                CommandEffect::BuildShip { planet_id: home.clone(), ship_id: String::from("interceptor"), deliver_to: None },
+            ] {
+                queue(&mut state, effect).unwrap();
+            }
+            state.players["alice"].pending_actions.iter().map(|action| action.cooldown_remaining).collect::<Vec<_>>()
+        };
+
+        let normal = cooldowns(GameSpeed::Normal);
+        let scaled = |speed: GameSpeed| normal.iter().map(|turns| speed.scale_turns(*turns)).collect::<Vec<_>>();
+        assert_eq!(cooldowns(GameSpeed::Quick), scaled(GameSpeed::Quick));
+        assert_eq!(cooldowns(GameSpeed::Epic), scaled(GameSpeed::Epic));
+        assert!(normal.iter().zip(cooldowns(GameSpeed::Epic)).all(|(normal, epic)| epic == 2 * normal));
+    }
+
+    #[test]
+    fn test_second_build_on_planet_is_rejected() {
+        let mut state = fixture_game(1);
+        let home = home_with_power_grid(&mut state);
+
+        queue(&mut state, CommandEffect::BuildStructure {
+            planet_id: home.clone(),
+            structure_id: String::from("orbital_shipyard"),
+        }).unwrap();
+        let result = queue(&mut state, CommandEffect::BuildStructure {
+            planet_id: home.clone(),
+            structure_id: String::from("defense_shield"),
+        });
+
+        assert!(matches!(result, Err(GameStateError::ConstructionSlotOccupied { kind: "structure build", .. })));
+    }
+
+    #[test]
+    fn test_action_slots_follow_the_rules_profile() {
+        let custom = RulesProfile { build_slots: 3, upgrade_slots: 1, ships_per_shipyard_level: 4, ..RulesProfile::classic() };
+        for profile in [RulesProfile::classic(), RulesProfile::standard(), custom] {
+            let mut state = fixture_game(1);
+            let alice = String::from("alice");
+            let home = test_support::home_planet(&state, "alice");
+            state.rules.profile = profile.clone();
+            // Without a shipyard the planet counts as shipyard level one
+            assert_eq!(state.shipyard_slots(&home), profile.ships_per_shipyard_level as usize);
+
+            type NumberedAction = fn(u32) -> ActionType;
+            let kinds: [(u32, NumberedAction); 3] = [
+                (profile.build_slots, |i| ActionType::BuildStructure(format!("structure_{i}"))),
+                (profile.upgrade_slots, |i| ActionType::UpgradeStructure(format!("upgraded_{i}"))),
+                (profile.ships_per_shipyard_level, |i| ActionType::BuildShip(String::from("scout"), format!("scout_{i}"))),
+            ];
+            for (slots, action) in kinds {
+                for i in 0..slots {
+                    state.check_action_conflicts(&alice, &home, &action(i)).unwrap();
+                    state.players.get_mut(&alice).unwrap().pending_actions
+                        .push(PendingAction::new(action(i), home.clone(), 1, Resources::default()));
+                }
+                let result = state.check_action_conflicts(&alice, &home, &action(slots));
+                assert!(
+                    matches!(result, Err(GameStateError::ConstructionSlotOccupied { .. } | GameStateError::ShipyardSlotsFull { .. })),
+                    "{:?} allowed more than {} of {:?}", profile.preset(), slots, action(0)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_build_and_upgrade_of_same_structure_conflict() {
+        let mut state = fixture_game(1);
+        let home = home_with_power_grid(&mut state);
+        state.players.get_mut("alice").unwrap().pending_actions.push(PendingAction::new(
+            ActionType::BuildStructure(String::from("power_grid")),
+            home.clone(),
+            1,
+            Resources::default(),
+        ));
+
+        let result = queue(&mut state, CommandEffect::UpgradeStructure {
+            planet_id: home,
+            structure_id: String::from("power_grid"),
+            auto_repeat: false,
+        });
+
+        assert!(matches!(result, Err(GameStateError::StructureBusy(id)) if id == "power_grid"));
+    }
+
+    fn build_structure(planet_id: &PlanetId, structure_id: &str) -> CommandEffect {
+        CommandEffect::BuildStructure { planet_id: planet_id.clone(), structure_id: structure_id.to_string() }
+    }
+
+    #[test]
+    fn test_per_planet_limit_allows_one_on_each_planet() {
+        let mut state = fixture_game(1);
+        let home = home_with_power_grid(&mut state);
+        let colony = test_support::grant_planet(&mut state, "alice");
+
+        assert!(queue(&mut state, build_structure(&home, "power_grid")).is_err());
+        queue(&mut state, build_structure(&colony, "power_grid")).unwrap();
+    }
+
+    #[test]
+    fn test_per_player_limit_counts_built_and_queued() {
+        let mut state = fixture_game(1);
+        let home = home_with_power_grid(&mut state);
+        let colony = test_support::grant_planet(&mut state, "alice");
+        state.map.planets.get_mut(&colony).unwrap().available_resources = Resources { minerals: 500, gas: 0, energy: 0 };
+
+        queue(&mut state, build_structure(&home, "command_nexus")).unwrap();
+        let result = queue(&mut state, build_structure(&colony, "command_nexus"));
+
+        assert!(matches!(&result, Err(GameStateError::StructureLimitReached { existing, .. })
+            if existing.len() == 1 && existing[0].ends_with("(queued)")));
+
+        // Another player's instance does not count towards alice's limit
+        let bob_home = test_support::home_planet(&state, "bob");
+        state.apply_effect(build_structure(&bob_home, "command_nexus"), &"bob".to_string()).unwrap();
+    }
+
+    #[test]
+    fn test_per_game_limit_names_existing_instance() {
+        let mut state = fixture_game(1);
+        let home = home_with_power_grid(&mut state);
+        let colony = test_support::grant_planet(&mut state, "alice");
+        state.map.planets.get_mut(&home).unwrap()
+            .complete_build_structure(String::from("galactic_senate"), &state.structure_config).unwrap();
+        let home_name = state.map.planets[&home].name.clone();
+
+        let result = queue(&mut state, build_structure(&colony, "galactic_senate"));
+        let Err(e) = result else { panic!("Second senate must be rejected") };
+        assert!(e.to_string().contains(&home_name));
+
+        let bob_home = test_support::home_planet(&state, "bob");
+        let result = state.apply_effect(build_structure(&bob_home, "galactic_senate"), &"bob".to_string());
+        assert!(matches!(&result, Err(GameStateError::StructureLimitReached { existing, .. }) if existing.is_empty()));
+    }
+
+    #[test]
+    fn test_simultaneous_per_game_builds_refund_the_later_one() {
+        let mut state = fixture_game(1);
+        let alice = "alice".to_string();
+        let bob = "bob".to_string();
+        let alice_home = test_support::home_planet(&state, "alice");
+        let bob_home = test_support::home_planet(&state, "bob");
+
+        state.apply_effect(build_structure(&alice_home, "galactic_senate"), &alice).unwrap();
+        state.apply_effect(build_structure(&bob_home, "galactic_senate"), &bob).unwrap();
+        state.apply_effect(CommandEffect::EndTurn { player_name: "Alice".to_string() }, &alice).unwrap();
+        let events = state.apply_effect(CommandEffect::EndTurn { player_name: "Bob".to_string() }, &bob).unwrap();
+
+        let completed = events.iter()
+            .filter(|e| matches!(e, GameEvent::ConstructionCompleted { structure_id, .. } if structure_id == "galactic_senate"))
+            .count();
+        assert_eq!(completed, 1);
+        assert!(events.iter().any(|e| matches!(e, GameEvent::ConstructionRejected { refunded, .. }
+            if refunded.minerals == 100)));
+        assert_eq!(state.structure_locations(&"galactic_senate".to_string(), None).len(), 1);
+    }
+
+    #[test]
+    fn test_queue_then_cancel_loses_resources() {
+        let mut state = fixture_game(1);
+        let alice = "alice".to_string();
+        let home = test_support::home_planet(&state, "alice");
+        let before = state.map.planets[&home].available_resources.clone();
+
+        queue(&mut state, CommandEffect::BuildShip {
+            planet_id: home.clone(),
+            ship_id: "interceptor".to_string(),
+            deliver_to: None,
+        }).unwrap();
+        let events = state.apply_effect(
+            CommandEffect::CancelAction { planet_id: home.clone(), target_id: "interceptor_1".to_string() },
+            &alice,
+        ).unwrap();
+
+        // Interceptor costs 100/50/0 with the default 25% penalty
+        assert!(matches!(&events[..], [GameEvent::ActionCancelled { penalty, .. }]
+            if *penalty == Resources { minerals: 25, gas: 12, energy: 0 }));
+        let after = &state.map.planets[&home].available_resources;
+        assert!(after.minerals < before.minerals);
+        assert!(after.gas < before.gas);
+    }
+
+    #[test]
+    fn test_cancel_refund_is_capped_by_storage() {
+        let mut state = fixture_game(1);
+        let alice = "alice".to_string();
+        let home = test_support::home_planet(&state, "alice");
+
+        queue(&mut state, CommandEffect::BuildShip {
+            planet_id: home.clone(),
+            ship_id: "interceptor".to_string(),
+            deliver_to: None,
+        }).unwrap();
+        // Storage refilled while the build was pending
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.available_resources = planet.storage_capacity.clone();
+        let full = planet.available_resources.clone();
+
+        let events = state.apply_effect(
+            CommandEffect::CancelAction { planet_id: home.clone(), target_id: "interceptor_1".to_string() },
+            &alice,
+        ).unwrap();
+
+        assert!(matches!(&events[..], [GameEvent::ActionCancelled { wasted: Some(_), .. }]));
+        assert_eq!(state.map.planets[&home].available_resources, full);
+    }
+
+    #[test]
+    fn test_queued_structure_stands_under_construction_until_cancelled() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let storage = state.map.planets[&home].storage_capacity.clone();
+
+        queue(&mut state, build_structure(&home, "power_grid")).unwrap();
+
+        let planet = &state.map.planets[&home];
+        let site = &planet.get_structures()["power_grid"];
+        assert!(site.is_under_construction());
+        assert!(!planet.has_built(&String::from("power_grid")));
+        assert_eq!(planet.storage_capacity, storage);
+        let status = planet_status(&state, &home);
+        assert!(status.contains("Power Grid (power_grid): Lv0/2 (UNDER CONSTRUCTION)"), "{status}");
+        assert!(state.check_invariants().is_empty(), "{:?}", state.check_invariants());
+
+        state.apply_effect(
+            CommandEffect::CancelAction { planet_id: home.clone(), target_id: String::from("power_grid") },
+            &String::from("alice"),
+        ).unwrap();
+
+        assert!(!state.map.planets[&home].get_structures().contains_key("power_grid"));
+        assert!(state.check_invariants().is_empty(), "{:?}", state.check_invariants());
+    }
+
+    #[test]
+    fn test_dependents_queue_on_prerequisites_under_construction_when_the_rules_allow() {
+        let mut state = fixture_game(1);
+        state.rules.profile = RulesProfile::standard();
+        let home = test_support::home_planet(&state, "alice");
+        state.map.planets.get_mut(&home).unwrap().available_resources = Resources { minerals: 500, gas: 250, energy: 100 };
+        queue(&mut state, build_structure(&home, "power_grid")).unwrap();
+
+        let result = queue(&mut state, build_structure(&home, "defense_shield"));
+        assert!(matches!(result, Err(GameStateError::PlanetError(PlanetError::PrerequisitesNotMet { .. }))));
+
+        state.rules.prerequisites_under_construction = true;
+        queue(&mut state, build_structure(&home, "defense_shield")).unwrap();
+        let events = end_round(&mut state);
+
+        let completed: Vec<_> = events.iter()
+            .filter_map(|e| match e {
+                GameEvent::ConstructionCompleted { structure_id, .. } => Some(structure_id.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(completed, ["power_grid", "defense_shield"]);
+        assert!(state.map.planets[&home].has_built(&String::from("defense_shield")));
+    }
+
+    #[test]
+    fn test_full_round_completes_ship_build() {
+        let mut state = fixture_game(1);
+        let alice = "alice".to_string();
+        let bob = "bob".to_string();
+        let home = test_support::home_planet(&state, "alice");
+
+        state.apply_effect(
+            CommandEffect::BuildShip { planet_id: home, ship_id: "interceptor".to_string(), deliver_to: None },
+            &alice,
+        ).unwrap();
+        state.apply_effect(CommandEffect::EndTurn { player_name: "Alice".to_string() }, &alice).unwrap();
+        let events = state.apply_effect(CommandEffect::EndTurn { player_name: "Bob".to_string() }, &bob).unwrap();
+
+        assert!(events.iter().any(|e| matches!(e, GameEvent::ShipBuilt { .. })));
+        assert!(events.contains(&GameEvent::TurnBegan { turn: 2, protection_turns_left: 0 }));
+        assert_eq!(state.turn, 2);
+        assert!(state.players[&alice].ships.contains_key("interceptor_1"));
+    }
+
+    fn queue_repeating_capital_upgrade(state: &mut GameState) -> PlanetId {
+        let home = test_support::home_planet(state, "alice");
+        queue(state, CommandEffect::UpgradeStructure {
+            planet_id: home.clone(),
+            structure_id: String::from("planetary_capital"),
+            auto_repeat: true,
+        }).unwrap();
+        home
+    }
+
+    fn capital_level(state: &GameState, planet_id: &PlanetId) -> u16 {
+        state.map.planets[planet_id].get_structures()["planetary_capital"].level
+    }
+
+    #[test]
+    fn test_repeating_upgrade_reaches_max_level() {
+        let mut state = fixture_game(1);
+        let home = queue_repeating_capital_upgrade(&mut state);
+
+        let events = end_round(&mut state);
+        assert_eq!(capital_level(&state, &home), 2);
+        assert!(events.iter().any(|event| matches!(event, GameEvent::UpgradeRepeated { turns: 2, .. })), "{events:?}");
+
+        end_round(&mut state);
+        let events = end_round(&mut state);
+        assert_eq!(capital_level(&state, &home), 3);
+        let stopped = events.iter()
+            .find(|event| matches!(event, GameEvent::UpgradeRepeatStopped { .. }))
+            .expect("Repetition must stop at max level");
+        assert!(stopped.to_string().contains("has reached maximum level"), "{stopped}");
+        assert!(!state.players["alice"].has_pending_action_on_planet(&home));
+    }
+
+    #[test]
+    fn test_repeating_upgrade_stops_when_unaffordable() {
+        let mut state = fixture_game(1);
+        let home = queue_repeating_capital_upgrade(&mut state);
+        state.map.planets.get_mut(&home).unwrap().available_resources = Resources::default();
+
+        let events = end_round(&mut state);
+
+        assert_eq!(capital_level(&state, &home), 2);
+        let stopped = events.iter()
+            .find(|event| matches!(event, GameEvent::UpgradeRepeatStopped { .. }))
+            .expect("Repetition must stop when the next level is unaffordable");
+        assert!(stopped.to_string().contains("not enough resources. Resources needed: Resources { minerals: 200"), "{stopped}");
+        assert!(!state.players["alice"].has_pending_action_on_planet(&home));
+    }
+
+    #[test]
+    fn test_unknown_acting_player_is_rejected() {
+        let mut state = fixture_game(1);
+
+        let result = state.apply_effect(
+            CommandEffect::None { message: String::new() },
+            &"mallory".to_string(),
+        );
+
+        assert!(matches!(result, Err(GameStateError::UnknownPlayer(_))));
+    }
+
+    #[test]
+    fn test_fixture_game_is_reproducible() {
+        let first = fixture_game(3);
+        let second = fixture_game(3);
+
+        assert!(first.map.planets.keys().eq(second.map.planets.keys()));
+        assert_eq!(
+            test_support::home_planet(&first, "alice"),
+            test_support::home_planet(&second, "alice")
+        );
+    }
+
+    /// Gives `player_id` a fleet of fixture ships at `location`.
+    fn fixture_fleet(state: &mut GameState, player_id: &str, ship_types: &[&str], location: &PlanetId) -> FleetId {
+        let player = state.players.get_mut(player_id).unwrap();
+        let fleet_id = player.next_fleet_id();
+        let mut fleet = Fleet::new(fleet_id.clone(), String::from("Landing"), location.clone());
+        for ship_type in ship_types {
+            let ship_id = player.add_ship(ship_type.to_string(), location.clone());
+            player.ships.get_mut(&ship_id).unwrap().fleet_id = Some(fleet_id.clone());
+            fleet.add_ship(ship_id);
+        }
+        player.fleets.insert(fleet_id.clone(), fleet);
+        fleet_id
+    }
+
+    /// Alice's home with a garrison of the given fixture ships.
+    fn garrisoned_home(ship_types: &[&str]) -> (GameState, PlanetId) {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let alice = state.players.get_mut("alice").unwrap();
+        let ship_ids = ship_types.iter()
+            .map(|ship_type| alice.add_ship(ship_type.to_string(), home.clone()))
+            .collect();
+        state.apply_effect(CommandEffect::GarrisonShips { planet_id: home.clone(), ship_ids }, &String::from("alice")).unwrap();
+        (state, home)
+    }
+
+    #[test]
+    fn test_garrison_stays_out_of_orbital_combat() {
+        let (mut state, home) = garrisoned_home(&["interceptor"]);
+        let origin = state.map.planets[&home].get_connections()[0].to().clone();
+        let fleet_id = fixture_fleet(&mut state, "bob", &["ravager"], &origin);
+
+        let events = state.process_arrival(&String::from("bob"), &MovableUnit::Fleet(fleet_id.clone()), &home);
+
+        assert!(matches!(&events[..], [GameEvent::FleetArrived { kind: ArrivalKind::UndefendedEnemy, .. }]));
+        assert!(state.map.planets[&home].is_garrisoned(&String::from("interceptor_1")));
+    }
+
+    #[test]
+    fn test_garrison_repels_weak_landing() {
+        let (mut state, home) = garrisoned_home(&["ravager", "ravager"]);
+        let fleet_id = fixture_fleet(&mut state, "bob", &["ark", "interceptor"], &home);
+
+        let events = state.apply_effect(CommandEffect::ColonizePlanet { fleet_id, planet_id: home.clone() }, &String::from("bob")).unwrap();
+
+        assert!(matches!(&events[..], [GameEvent::GarrisonAssault {
+            attacker_strength: 15, defender_strength: 30, attacker_wins: false, ships_destroyed: 2, ..
+        }]));
+        assert_eq!(state.map.planets[&home].get_owner().as_deref(), Some("alice"));
+        assert!(state.players["bob"].ships.is_empty());
+        assert!(state.players["bob"].fleets.is_empty());
+    }
+
+    #[test]
+    fn test_landing_through_garrison_destroys_it_and_colonizes() {
+        let (mut state, home) = garrisoned_home(&["scout"]);
+        let fleet_id = fixture_fleet(&mut state, "bob", &["ark", "interceptor"], &home);
+
+        let events = state.apply_effect(CommandEffect::ColonizePlanet { fleet_id, planet_id: home.clone() }, &String::from("bob")).unwrap();
+
+        assert!(matches!(events[0], GameEvent::GarrisonAssault { attacker_wins: true, ships_destroyed: 1, .. }));
+        assert!(matches!(events[1], GameEvent::PlanetColonized { .. }));
+        assert_eq!(state.map.planets[&home].get_owner().as_deref(), Some("bob"));
+        assert!(state.map.planets[&home].get_garrison().is_empty());
+        assert!(state.players["alice"].ships.is_empty());
+    }
+
+    #[test]
+    fn test_captured_planet_cannot_be_retaken_until_consolidated() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let landing = fixture_fleet(&mut state, "bob", &["ark", "interceptor"], &home);
+        state.apply_effect(CommandEffect::ColonizePlanet { fleet_id: landing, planet_id: home.clone() }, &String::from("bob")).unwrap();
+        assert_eq!(state.map.planets[&home].get_consolidation_turns(), 2);
+
+        // Alice's ark was in orbit all along, but has to wait out the consolidation
+        let fleet_id = fixture_fleet(&mut state, "alice", &["ark", "interceptor"], &home);
+        let counter_attack = format!("fleet colonize {}", fleet_id);
+        let attempt = |state: &GameState| crate::engine::commands::parser::parse(&counter_attack).unwrap().execute(state);
+        for round in 0..2 {
+            let result = attempt(&state);
+            assert!(
+                matches!(&result, Err(crate::engine::commands::command::CommandError::InvalidArgument { reason, .. }) if reason.contains("consolidating")),
+                "round {round}: {:?}", result.map(|_| ())
+            );
+            state.apply_effect(CommandEffect::EndTurn { player_name: String::from("Alice") }, &String::from("alice")).unwrap();
+            state.apply_effect(CommandEffect::EndTurn { player_name: String::from("Bob") }, &String::from("bob")).unwrap();
+        }
+
+        assert!(matches!(attempt(&state), Ok(CommandEffect::ColonizePlanet { .. })));
+    }
+
+    #[test]
+    fn test_garrison_fire_absorbs_bombardment() {
+        let (mut state, home) = garrisoned_home(&["interceptor", "ark"]);
+        let fleet_id = fixture_fleet(&mut state, "bob", &["ravager"], &home);
+        state.players.get_mut("bob").unwrap().pending_actions.push(PendingAction::new(
+            ActionType::BombardPlanet(fleet_id, home.clone()),
+            home.clone(),
+            u32::MAX,
+            Resources::default(),
+        ));
+        assert_eq!(state.garrison_fire(&home), 10);
+
+        state.apply_effect(CommandEffect::EndTurn { player_name: String::from("Alice") }, &String::from("alice")).unwrap();
+        let events = state.apply_effect(CommandEffect::EndTurn { player_name: String::from("Bob") }, &String::from("bob")).unwrap();
+
+        assert!(events.iter().any(|event| matches!(event, GameEvent::BombardmentIntercepted { intercepted: 10, .. })));
+    }
+
+    /// The neutral planet with the lowest id, holding `ruin`.
+    fn ruined_planet(state: &mut GameState, ruin: Ruin) -> PlanetId {
+        let planet_id = state.map.planets.values()
+            .filter(|planet| planet.get_owner().is_none())
+            .map(|planet| planet.id.clone())
+            .min()
+            .unwrap();
+        state.map.planets.get_mut(&planet_id).unwrap().set_ruin(Some(ruin));
+        planet_id
+    }
+
+    fn planet_status(state: &GameState, planet_id: &PlanetId) -> String {
+        match crate::engine::commands::parser::parse(&format!("status planet {}", planet_id)).unwrap().execute(state) {
+            Ok(CommandEffect::None { message }) => message,
+            _ => panic!("Status must only produce a message"),
+        }
+    }
+
+    #[test]
+    fn test_colonizing_keeps_ruined_structure() {
+        let mut state = fixture_game(1);
+        let planet_id = ruined_planet(&mut state, Ruin::Structure(String::from("storage_complex")));
+        let fleet_id = fixture_fleet(&mut state, "alice", &["ark"], &planet_id);
+
+        let events = state.apply_effect(CommandEffect::ColonizePlanet { fleet_id, planet_id: planet_id.clone() }, &String::from("alice")).unwrap();
+
+        assert!(matches!(
+            &events[..],
+            [GameEvent::ColonizationPaid { .. }, GameEvent::PlanetColonized { .. }, GameEvent::RuinClaimed { description, .. }]
+                if description == "abandoned Storage Complex (Lv1)"
+        ));
+        let planet = &state.map.planets[&planet_id];
+        assert_eq!(planet.get_structure_level(&String::from("storage_complex")), 1);
+        assert_eq!(planet.get_ruin(), None);
+        let capital_storage = state.structure_config.get(&String::from("planetary_capital")).unwrap().storage_capacity[0].clone();
+        let warehouse_storage = state.structure_config.get(&String::from("storage_complex")).unwrap().storage_capacity[0].clone();
+        assert_eq!(planet.storage_capacity, capital_storage + warehouse_storage);
+        let ark_bonus = state.rules_profile().ark_colony_bonus.clone();
+        assert_eq!(planet.available_resources, planet.storage_capacity.clone() + ark_bonus);
+    }
+
+    /// Neutral planets nearest to and furthest from alice's home, with their hop counts.
+    fn near_and_far_neutral(state: &GameState) -> ((PlanetId, u32), (PlanetId, u32)) {
+        let hops = state.map.graph().hops(&test_support::home_planet(state, "alice"));
+        let mut neutral: Vec<_> = hops.into_iter()
+            .filter(|(planet_id, _)| state.map.planets[planet_id].get_owner().is_none())
+            .map(|(planet_id, hops)| (hops, planet_id))
+            .collect();
+        neutral.sort();
+        let (near_hops, near) = neutral.first().unwrap().clone();
+        let (far_hops, far) = neutral.last().unwrap().clone();
+        ((near, near_hops), (far, far_hops))
+    }
+
+    #[test]
+    fn test_colonization_cost_grows_with_hops_from_the_nearest_planet() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let ((near, near_hops), (far, far_hops)) = near_and_far_neutral(&state);
+        assert_eq!(near_hops, 1);
+        assert!(far_hops > 1, "{far_hops}");
+
+        let cost = |state: &GameState, planet_id: &PlanetId| {
+            rules::colonization_cost(&state.map.planets[planet_id], &state.players["alice"], state).unwrap()
+        };
+        let profile = state.rules_profile().clone();
+        assert_eq!(cost(&state, &near).cost, profile.colonization_base_cost);
+        let far_cost = cost(&state, &far);
+        assert_eq!(far_cost.origin, home);
+        assert_eq!(far_cost.cost, profile.colonization_cost(far_hops));
+        assert!(far_cost.cost.minerals > profile.colonization_base_cost.minerals);
+
+        // The nearest planet pays when the colony is founded
+        let before = state.map.planets[&home].available_resources.clone();
+        let fleet_id = fixture_fleet(&mut state, "alice", &["ark"], &far);
+        state.apply_effect(CommandEffect::ColonizePlanet { fleet_id, planet_id: far.clone() }, &String::from("alice")).unwrap();
+        assert_eq!(state.map.planets[&far].get_owner().as_deref(), Some("alice"));
+        assert_eq!(state.map.planets[&home].available_resources, before - far_cost.cost);
+    }
+
+    #[test]
+    fn test_unaffordable_colonization_is_rejected_with_its_cost() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let (_, (far, far_hops)) = near_and_far_neutral(&state);
+        let fleet_id = fixture_fleet(&mut state, "alice", &["ark"], &far);
+        state.map.planets.get_mut(&home).unwrap().available_resources = Resources::default();
+
+        let command = crate::engine::commands::parser::parse(&format!("fleet colonize {}", fleet_id)).unwrap();
+        let Err(crate::engine::CommandError::InvalidArgument { reason, .. }) = command.execute(&state) else {
+            panic!("An empty home cannot pay for a colony")
+        };
+        let cost = state.rules_profile().colonization_cost(far_hops);
+        assert!(reason.contains(&format!("costs {}", cost)), "{reason}");
+        assert!(reason.contains(&format!("{} hop(s) away", far_hops)), "{reason}");
+    }
+
+    #[test]
+    fn test_colonization_cost_is_refunded_when_the_ark_is_lost() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let ((near, _), _) = near_and_far_neutral(&state);
+        let fleet_id = fixture_fleet(&mut state, "alice", &["ark", "scout"], &near);
+        let before = state.map.planets[&home].available_resources.clone();
+
+        // The ark is destroyed after the order is given but before the colonists land
+        let alice = state.players.get_mut("alice").unwrap();
+        let ark = alice.fleets[&fleet_id].ships[0].clone();
+        alice.fleets.get_mut(&fleet_id).unwrap().ships.retain(|ship_id| *ship_id != ark);
+        alice.ships.remove(&ark);
+        let events = state.apply_effect(CommandEffect::ColonizePlanet { fleet_id, planet_id: near.clone() }, &String::from("alice")).unwrap();
+
+        assert!(matches!(
+            &events[..],
+            [GameEvent::ColonizationPaid { .. }, GameEvent::ColonizationFailed { .. }, GameEvent::ColonizationRefunded { refunded, .. }]
+                if *refunded == state.rules_profile().colonization_base_cost
+        ));
+        assert_eq!(state.map.planets[&near].get_owner(), &None);
+        assert_eq!(state.map.planets[&home].available_resources, before);
+    }
+
+    #[test]
+    fn test_colonizing_consumes_the_lowest_id_ark_and_credits_its_bonus() {
+        let mut state = fixture_game(1);
+        let planet_id = ruined_planet(&mut state, Ruin::Cache(Resources::default()));
+        let fleet_id = fixture_fleet(&mut state, "alice", &["scout", "ark", "ark"], &planet_id);
+        let arks: Vec<_> = state.players["alice"].fleets[&fleet_id].ships[1..].to_vec();
+        let first_ark = arks.iter().min().unwrap().clone();
+        state.rules.profile.ark_colony_bonus = Resources { minerals: 70, gas: 30, energy: 5 };
+
+        let events = state.apply_effect(CommandEffect::ColonizePlanet { fleet_id: fleet_id.clone(), planet_id: planet_id.clone() }, &String::from("alice")).unwrap();
+
+        let alice = &state.players["alice"];
+        assert!(!alice.ships.contains_key(&first_ark));
+        let remaining: Vec<_> = alice.fleets[&fleet_id].ships.iter().filter(|ship_id| arks.contains(ship_id)).collect();
+        assert_eq!(remaining.len(), 1);
+        assert_ne!(*remaining[0], first_ark);
+        assert!(state.check_invariants().is_empty(), "{:?}", state.check_invariants());
+
+        let planet = &state.map.planets[&planet_id];
+        let bonus = Resources { minerals: 70, gas: 30, energy: 5 };
+        assert_eq!(planet.available_resources, planet.storage_capacity.clone() + bonus.clone());
+        let colonized = events.iter().find(|event| matches!(event, GameEvent::PlanetColonized { .. })).unwrap();
+        assert!(matches!(colonized, GameEvent::PlanetColonized { ark_bonus, .. } if *ark_bonus == bonus));
+        assert!(colonized.to_string().ends_with(&format!("adding {} to the colony's stores.", bonus)), "{colonized}");
+    }
+
+    #[test]
+    fn test_colonizing_with_a_lone_ark_disbands_its_fleet() {
+        let mut state = fixture_game(1);
+        let planet_id = ruined_planet(&mut state, Ruin::Cache(Resources::default()));
+        let fleet_id = fixture_fleet(&mut state, "alice", &["ark"], &planet_id);
+        let ships_before = state.players["alice"].ships.len();
+
+        state.apply_effect(CommandEffect::ColonizePlanet { fleet_id: fleet_id.clone(), planet_id }, &String::from("alice")).unwrap();
+
+        let alice = &state.players["alice"];
+        assert_eq!(alice.ships.len(), ships_before - 1);
+        assert!(!alice.fleets.contains_key(&fleet_id));
+        assert!(state.check_invariants().is_empty(), "{:?}", state.check_invariants());
+    }
+
+    #[test]
+    fn test_colonizing_credits_ruin_cache_above_storage() {
+        let mut state = fixture_game(1);
+        let cache = Resources { minerals: 300, gas: 100, energy: 0 };
+        let planet_id = ruined_planet(&mut state, Ruin::Cache(cache.clone()));
+        let fleet_id = fixture_fleet(&mut state, "alice", &["ark"], &planet_id);
+
+        state.apply_effect(CommandEffect::ColonizePlanet { fleet_id, planet_id: planet_id.clone() }, &String::from("alice")).unwrap();
+
+        let planet = &state.map.planets[&planet_id];
+        let stocked = planet.storage_capacity.clone() + cache + state.rules_profile().ark_colony_bonus.clone();
+        assert_eq!(planet.available_resources, stocked);
+        assert_eq!(planet.get_structures().len(), 1);
+
+        // Production cannot add to a full store, but does not throw the cache away either
+        state.apply_effect(CommandEffect::EndTurn { player_name: String::from("Alice") }, &String::from("alice")).unwrap();
+        state.apply_effect(CommandEffect::EndTurn { player_name: String::from("Bob") }, &String::from("bob")).unwrap();
+        assert_eq!(state.map.planets[&planet_id].available_resources, stocked);
+    }
+
+    #[test]
+    fn test_ruins_show_in_status_only_once_scouted() {
+        let mut state = fixture_game(1);
+        let visible = state.visible_planets(&String::from("alice"));
+        let planet_id = state.map.planets.keys()
+            .filter(|id| !visible.contains(*id))
+            .min()
+            .unwrap()
+            .clone();
+        state.map.planets.get_mut(&planet_id).unwrap()
+            .set_ruin(Some(Ruin::Cache(Resources { minerals: 200, gas: 50, energy: 0 })));
+        assert!(!planet_status(&state, &planet_id).contains("Ruins"));
+
+        state.players.get_mut("alice").unwrap().add_ship(String::from("scout"), planet_id.clone());
+        state.refresh_intel();
+        let live = planet_status(&state, &planet_id);
+        assert!(live.contains("Ruins: resource cache (Resources { minerals: 200"), "{live}");
+
+        state.players.get_mut("alice").unwrap().ships.clear();
+        let fogged = planet_status(&state, &planet_id);
+        assert!(fogged.contains("Ruins: resource cache (Resources { minerals: 200"), "{fogged}");
+        assert!(fogged.contains("(as of turn 1)"), "{fogged}");
+    }
+}
@@ -0,0 +1,269 @@
+use std::fmt;
+
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::game_event::{GameEvent, VictoryKind};
+use crate::engine::player::PlayerId;
+
+use super::GameState;
+
+/// How many battles the end-of-game highlights keep, the bloodiest ones
+const HIGHLIGHTED_BATTLES: usize = 3;
+
+/// One order a player gave: the command line after alias expansion and how it turned out.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ActionLogEntry {
+    pub turn: u32,
+    pub player_id: PlayerId,
+    pub command: String,
+    pub outcome: ActionOutcome,
+    /// Notable moments the order led to, including the round processing an `end_turn` ran
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<Highlight>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ActionOutcome {
+    Applied,
+    Rejected { reason: String },
+}
+
+impl fmt::Display for ActionOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ActionOutcome::Applied => write!(f, "ok"),
+            ActionOutcome::Rejected { reason } => write!(f, "rejected: {}", reason),
+        }
+    }
+}
+
+/// A moment worth replaying once the game is over.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Highlight {
+    Colonized { player_name: String, planet_name: String },
+    Eliminated { player_name: String },
+    Battle {
+        planet_name: String,
+        attacker_name: String,
+        defender_name: String,
+        attacker_wins: bool,
+        ships_destroyed: usize,
+    },
+    Victory { player_names: Vec<String>, kind: VictoryKind },
+}
+
+impl Highlight {
+    /// The highlight an event amounts to, if any.
+    pub fn from_event(event: &GameEvent) -> Option<Highlight> {
+        match event {
+            GameEvent::PlanetColonized { planet_name, player_name, .. } => Some(Highlight::Colonized {
+                player_name: player_name.clone(),
+                planet_name: planet_name.clone(),
+            }),
+            GameEvent::Battle { planet_name, attacker_name, defender_name, attacker_wins, ships_destroyed, .. }
+            | GameEvent::GarrisonAssault { planet_name, attacker_name, defender_name, attacker_wins, ships_destroyed, .. } => {
+                Some(Highlight::Battle {
+                    planet_name: planet_name.clone(),
+                    attacker_name: attacker_name.clone(),
+                    defender_name: defender_name.clone(),
+                    attacker_wins: *attacker_wins,
+                    ships_destroyed: *ships_destroyed,
+                })
+            }
+            GameEvent::Victory { player_names, kind, .. } => Some(Highlight::Victory {
+                player_names: player_names.clone(),
+                kind: kind.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Highlight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Highlight::Colonized { player_name, planet_name } => write!(f, "{} colonized {}", player_name, planet_name),
+            Highlight::Eliminated { player_name } => write!(f, "{} was eliminated", player_name),
+            Highlight::Battle { planet_name, attacker_name, defender_name, attacker_wins, ships_destroyed } => {
+                let result = if *attacker_wins { "defeated" } else { "was repelled by" };
+                write!(
+                    f, "Battle of {}: {} {} {}, {} ship(s) destroyed",
+                    planet_name, attacker_name, result, defender_name, ships_destroyed
+                )
+            }
+            Highlight::Victory { player_names, kind } => write!(f, "{} won: {}", player_names.join(" & "), kind),
+        }
+    }
+}
+
+impl GameState {
+    /// Applies the outcome of a command line given by `acting_player`: its effect when it passed
+    /// validation, or the reason it did not. Orders are logged in the action log either way.
+    /// Sessions, replays and the JSON API all apply commands through here, so a replay rebuilds
+    /// the same game.
+    pub fn apply_command(
+        &mut self,
+        acting_player: &PlayerId,
+        line: &str,
+        is_order: bool,
+        result: Result<CommandEffect, CommandError>,
+    ) -> Result<Vec<GameEvent>, String> {
+        let turn = self.turn;
+        let outcome = result
+            .map(|effect| (!matches!(effect, CommandEffect::None { .. }), effect))
+            .map_err(|e| e.to_string())
+            .and_then(|(mutates, effect)| {
+                let events = self.apply_effect(effect, acting_player).map_err(|e| e.to_string())?;
+                Ok((mutates, events))
+            });
+
+        match outcome {
+            Ok((mutates, events)) => {
+                if is_order && mutates {
+                    self.record_action(acting_player, turn, line, ActionOutcome::Applied, &events);
+                }
+                Ok(events)
+            }
+            Err(reason) => {
+                if is_order {
+                    self.record_action(acting_player, turn, line, ActionOutcome::Rejected { reason: reason.clone() }, &[]);
+                }
+                Err(reason)
+            }
+        }
+    }
+
+    /// Appends an order to the action log. `events` are what applying it produced; the
+    /// highlights among them, and any player it left without planets, are kept with the entry.
+    pub fn record_action(
+        &mut self,
+        player_id: &PlayerId,
+        turn: u32,
+        command: &str,
+        outcome: ActionOutcome,
+        events: &[GameEvent],
+    ) {
+        let mut highlights: Vec<_> = events.iter().filter_map(Highlight::from_event).collect();
+
+        let mut eliminated: Vec<_> = self.players.values()
+            .filter(|player| player.planets.is_empty())
+            .map(|player| player.name.clone())
+            .filter(|name| !self.was_eliminated(name))
+            .collect();
+        eliminated.sort();
+        highlights.extend(eliminated.into_iter().map(|player_name| Highlight::Eliminated { player_name }));
+
+        self.action_log.push(ActionLogEntry {
+            turn,
+            player_id: player_id.clone(),
+            command: command.split_whitespace().collect::<Vec<_>>().join(" "),
+            outcome,
+            highlights,
+        });
+    }
+
+    fn was_eliminated(&self, player_name: &str) -> bool {
+        self.action_log.iter()
+            .flat_map(|entry| &entry.highlights)
+            .any(|highlight| matches!(highlight, Highlight::Eliminated { player_name: name } if name == player_name))
+    }
+
+    /// `player_id`'s orders from turns `first` to `last` inclusive, oldest first.
+    pub fn player_actions(&self, player_id: &PlayerId, first: u32, last: u32) -> Vec<&ActionLogEntry> {
+        self.action_log.iter()
+            .filter(|entry| &entry.player_id == player_id && (first..=last).contains(&entry.turn))
+            .collect()
+    }
+
+    /// Every colonization and elimination plus the bloodiest battles, in the order they happened,
+    /// each with the turn it happened on.
+    pub fn highlights(&self) -> Vec<(u32, &Highlight)> {
+        let all: Vec<_> = self.action_log.iter()
+            .flat_map(|entry| entry.highlights.iter().map(move |highlight| (entry.turn, highlight)))
+            .collect();
+
+        let mut battles: Vec<_> = all.iter().enumerate()
+            .filter_map(|(index, (_, highlight))| match highlight {
+                Highlight::Battle { ships_destroyed, .. } => Some((index, *ships_destroyed)),
+                _ => None,
+            })
+            .collect();
+        // Stable, so among equally bloody battles the earlier ones are kept
+        battles.sort_by_key(|(_, ships_destroyed)| std::cmp::Reverse(*ships_destroyed));
+        let kept: Vec<_> = battles.iter().take(HIGHLIGHTED_BATTLES).map(|(index, _)| *index).collect();
+
+        all.into_iter().enumerate()
+            .filter(|(index, (_, highlight))| !matches!(highlight, Highlight::Battle { .. }) || kept.contains(index))
+            .map(|(_, entry)| entry)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::command::CommandEffect;
+    use crate::engine::fleet::Fleet;
+    use crate::test_support::{self, fixture_game};
+
+    fn battle(planet_name: &str, ships_destroyed: usize) -> GameEvent {
+        GameEvent::Battle {
+            fleet_id: String::from("fleet_1"),
+            planet_name: planet_name.to_string(),
+            attacker_name: String::from("Alice"),
+            defender_name: String::from("Bob"),
+            attacker_strength: 10,
+            defender_strength: 5,
+            attacker_wins: true,
+            ships_destroyed,
+        }
+    }
+
+    #[test]
+    fn test_highlights_keep_bloodiest_battles_in_order() {
+        let mut state = fixture_game(1);
+        let alice = String::from("alice");
+        let events = [battle("A", 1), battle("B", 5), battle("C", 2), battle("D", 4)];
+
+        for (turn, event) in (1..).zip(events) {
+            state.record_action(&alice, turn, "end_turn", ActionOutcome::Applied, &[event]);
+        }
+
+        let planets: Vec<_> = state.highlights().into_iter()
+            .map(|(turn, highlight)| match highlight {
+                Highlight::Battle { planet_name, .. } => (turn, planet_name.as_str()),
+                other => panic!("Unexpected highlight {other}"),
+            })
+            .collect();
+        assert_eq!(planets, [(2, "B"), (3, "C"), (4, "D")]);
+    }
+
+    #[test]
+    fn test_invasion_records_colonization_and_elimination_once() {
+        let mut state = fixture_game(1);
+        let bob_home = test_support::home_planet(&state, "bob");
+        let alice = String::from("alice");
+
+        let player = state.players.get_mut("alice").unwrap();
+        let fleet_id = player.next_fleet_id();
+        let mut fleet = Fleet::new(fleet_id.clone(), String::from("Landing"), bob_home.clone());
+        for ship_type in ["ark", "interceptor"] {
+            let ship_id = player.add_ship(ship_type.to_string(), bob_home.clone());
+            player.ships.get_mut(&ship_id).unwrap().fleet_id = Some(fleet_id.clone());
+            fleet.add_ship(ship_id);
+        }
+        player.fleets.insert(fleet_id.clone(), fleet);
+        let effect = CommandEffect::ColonizePlanet { fleet_id, planet_id: bob_home.clone() };
+        let events = state.apply_effect(effect, &alice).unwrap();
+
+        state.record_action(&alice, 1, "fleet  colonize fleet_1", ActionOutcome::Applied, &events);
+        state.record_action(&alice, 1, "end_turn", ActionOutcome::Applied, &[]);
+
+        let entry = &state.action_log[0];
+        assert_eq!(entry.command, "fleet colonize fleet_1");
+        assert_eq!(entry.highlights, [
+            Highlight::Colonized { player_name: String::from("Alice"), planet_name: state.planet_name(&bob_home) },
+            Highlight::Eliminated { player_name: String::from("Bob") },
+        ]);
+        assert!(state.action_log[1].highlights.is_empty());
+    }
+}
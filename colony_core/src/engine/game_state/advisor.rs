@@ -0,0 +1,313 @@
+use std::fmt;
+
+use crate::engine::pending_action::ActionType;
+use crate::engine::planet::Planet;
+use crate::engine::player::{Player, PlayerId};
+use crate::engine::rules;
+use crate::engine::structure::StructureId;
+
+use super::GameState;
+use super::alerts::{Alert, scan_alerts};
+
+/// Most suggestions the advisor makes at once.
+pub const MAX_SUGGESTIONS: usize = 3;
+
+/// A next step the advisor recommends, and the situation that calls for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub situation: String,
+    pub advice: String,
+}
+
+impl fmt::Display for Suggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.situation, self.advice)
+    }
+}
+
+/// A rule looks at the player's state and their alerts and suggests at most one next step.
+type Rule = fn(&GameState, &Player, &[Alert]) -> Option<Suggestion>;
+
+/// Rules from most to least important; the advisor keeps the first suggestions made.
+const RULES: [Rule; 5] = [
+    defend_exposed_planet,
+    fix_energy_deficit,
+    colonize_neighbor,
+    start_construction,
+    spend_full_storage,
+];
+
+/// Up to [`MAX_SUGGESTIONS`] next steps for `player_id`, most important first. The same state
+/// always gets the same suggestions.
+pub fn advise(game_state: &GameState, player_id: &PlayerId) -> Vec<Suggestion> {
+    let Some(player) = game_state.players.get(player_id) else {
+        return Vec::new();
+    };
+    let alerts = scan_alerts(game_state, player_id);
+    RULES.iter()
+        .filter_map(|rule| rule(game_state, player, &alerts))
+        .take(MAX_SUGGESTIONS)
+        .collect()
+}
+
+/// The player's planet called `name`, as alerts name planets.
+fn owned_planet<'a>(game_state: &'a GameState, player: &Player, name: &str) -> Option<&'a Planet> {
+    player.planets.iter()
+        .filter_map(|planet_id| game_state.map.planets.get(planet_id))
+        .find(|planet| planet.name == name)
+}
+
+/// Whether `player` could start building `structure_id` on `planet` right now.
+fn can_build(game_state: &GameState, player: &Player, planet: &Planet, structure_id: &str) -> bool {
+    game_state.structure_config.get(&StructureId::from(structure_id))
+        .is_some_and(|definition| rules::can_build(planet, &definition, player, game_state).is_ok())
+}
+
+/// Hostile ships next to a planet without a shield: build one while it can still be built.
+fn defend_exposed_planet(game_state: &GameState, player: &Player, alerts: &[Alert]) -> Option<Suggestion> {
+    alerts.iter().find_map(|alert| {
+        let Alert::UnshieldedNearHostiles { planet_name, hostile_planet_name } = alert else {
+            return None;
+        };
+        let planet = owned_planet(game_state, player, planet_name)?;
+        can_build(game_state, player, planet, "defense_shield").then(|| Suggestion {
+            situation: format!("Hostile ships are at {}, and {} has no shield", hostile_planet_name, planet_name),
+            advice: format!("build a defense_shield on {}", planet_name),
+        })
+    })
+}
+
+/// Structures drawing more energy than a planet makes: build or upgrade what produces the most.
+fn fix_energy_deficit(game_state: &GameState, player: &Player, alerts: &[Alert]) -> Option<Suggestion> {
+    let mut producers: Vec<_> = game_state.structure_config.iter()
+        .map(|(_, definition)| definition)
+        .filter(|definition| definition.production.first().is_some_and(|production| production.energy > 0))
+        .collect();
+    producers.sort_by(|a, b| b.production[0].energy.cmp(&a.production[0].energy).then_with(|| a.id.cmp(&b.id)));
+
+    alerts.iter().find_map(|alert| {
+        let Alert::EnergyDeficit { planet_name, balance } = alert else {
+            return None;
+        };
+        let planet = owned_planet(game_state, player, planet_name)?;
+        let advice = producers.iter().find_map(|definition| {
+            if planet.get_structures().contains_key(&definition.id) {
+                planet.validate_upgrade_structure(&definition.id, &game_state.structure_config).ok()
+                    .map(|_| format!("upgrade the {} on {}", definition.id, planet_name))
+            } else {
+                can_build(game_state, player, planet, &definition.id)
+                    .then(|| format!("build a {} on {}", definition.id, planet_name))
+            }
+        })?;
+        Some(Suggestion { situation: format!("Your energy balance on {} is {}", planet_name, balance), advice })
+    })
+}
+
+/// A fleet carrying an ark with a neutral planet next to it, or under it, that it could take.
+fn colonize_neighbor(game_state: &GameState, player: &Player, _alerts: &[Alert]) -> Option<Suggestion> {
+    let graph = game_state.map.graph();
+    let fleets: Vec<_> = player.fleets.values()
+        .filter(|fleet| rules::fleet_has_ark(fleet, player))
+        .filter(|fleet| !player.has_pending_fleet_move(&fleet.id))
+        .collect();
+
+    let open = |planet: &Planet| planet.get_owner().is_none() && rules::colonize_target(planet, player, game_state).is_ok();
+
+    fleets.into_iter().find_map(|fleet| {
+        if let Some(planet) = game_state.map.planets.get(&fleet.location).filter(|planet| open(planet)) {
+            return Some(Suggestion {
+                situation: format!("Fleet {} has an ark at the neutral planet {}", fleet.name, planet.name),
+                advice: format!("colonize it with 'fleet colonize {}'", fleet.id),
+            });
+        }
+
+        let (planet, distance) = graph.neighbors(&fleet.location)
+            .filter_map(|(planet_id, distance)| game_state.map.planets.get(planet_id).map(|planet| (planet, distance)))
+            .filter(|(planet, _)| open(planet))
+            .min_by(|(a, a_distance), (b, b_distance)| a_distance.cmp(b_distance).then_with(|| a.name.cmp(&b.name)))?;
+        Some(Suggestion {
+            situation: format!(
+                "You have an ark in fleet {} and a neutral neighbor {} {} turn(s) away", fleet.name, planet.name, distance
+            ),
+            advice: format!("consider colonizing it, starting with 'fleet move {} {}'", fleet.id, planet.id),
+        })
+    })
+}
+
+/// A planet with no structure under construction that can afford one now: the cheapest.
+fn start_construction(game_state: &GameState, player: &Player, _alerts: &[Alert]) -> Option<Suggestion> {
+    player.planets.iter()
+        .filter_map(|planet_id| game_state.map.planets.get(planet_id))
+        .filter(|planet| !player.pending_actions_on_planet(&planet.id).any(|action| matches!(
+            action.action_type,
+            ActionType::BuildStructure(_)
+        )))
+        .find_map(|planet| {
+            let buildable = planet.get_buildable_structures(
+                &game_state.structure_config, game_state.rules.prerequisites_under_construction,
+            );
+            let mut affordable: Vec<_> = buildable.can_build_now
+                .into_iter()
+                .filter(|info| info.can_afford && can_build(game_state, player, planet, &info.id))
+                .collect();
+            affordable.sort_by_key(|info| (info.cost.minerals + info.cost.gas + info.cost.energy, info.id.clone()));
+            let cheapest = affordable.into_iter().next()?;
+            Some(Suggestion {
+                situation: format!("Nothing is under construction on {}", planet.name),
+                advice: format!("build a {} for {}", cheapest.id, cheapest.cost),
+            })
+        })
+}
+
+/// Storage about to overflow: production is wasted unless it is spent or storage grows.
+fn spend_full_storage(game_state: &GameState, player: &Player, alerts: &[Alert]) -> Option<Suggestion> {
+    alerts.iter().find_map(|alert| {
+        let Alert::StorageNearlyFull { planet_name, resource, percent } = alert else {
+            return None;
+        };
+        let planet = owned_planet(game_state, player, planet_name)?;
+        let storage = StructureId::from("storage_complex");
+        let advice = if planet.get_structures().contains_key(&storage) {
+            format!("spend it or upgrade the storage_complex on {}", planet_name)
+        } else {
+            format!("spend it or build a storage_complex on {}", planet_name)
+        };
+        Some(Suggestion {
+            situation: format!("{} storage on {} is {}% full", resource, planet_name, percent),
+            advice,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::fleet::Fleet;
+    use crate::engine::resources::Resources;
+    use crate::test_support::{self, fixture_game};
+
+    fn alice(state: &GameState) -> &Player {
+        &state.players["alice"]
+    }
+
+    fn run(rule: Rule, state: &GameState) -> Option<Suggestion> {
+        let alerts = scan_alerts(state, &String::from("alice"));
+        rule(state, alice(state), &alerts)
+    }
+
+    fn rich_home(state: &mut GameState) -> String {
+        let home = test_support::home_planet(state, "alice");
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.available_resources = Resources { minerals: 500, gas: 500, energy: 500 };
+        home
+    }
+
+    /// A fleet of `ship_types` for alice, orbiting `location`.
+    fn fleet_at(state: &mut GameState, location: &str, ship_types: &[&str]) {
+        let alice = state.players.get_mut("alice").unwrap();
+        let mut fleet = Fleet::new(String::from("fleet_1"), String::from("Pioneer"), location.to_string());
+        for ship_type in ship_types {
+            fleet.add_ship(alice.add_ship(ship_type.to_string(), location.to_string()));
+        }
+        alice.fleets.insert(fleet.id.clone(), fleet);
+    }
+
+    #[test]
+    fn test_defend_exposed_planet() {
+        let mut state = fixture_game(1);
+        let home = rich_home(&mut state);
+        state.map.planets.get_mut(&home).unwrap()
+            .complete_build_structure(String::from("power_grid"), &test_support::fixture_structure_config()).unwrap();
+        assert_eq!(run(defend_exposed_planet, &state), None);
+
+        let bob = state.players.get_mut("bob").unwrap();
+        let ship = bob.add_ship(String::from("interceptor"), home.clone());
+        let mut fleet = Fleet::new(String::from("fleet_1"), String::from("Raiders"), home.clone());
+        fleet.add_ship(ship);
+        bob.fleets.insert(fleet.id.clone(), fleet);
+
+        let suggestion = run(defend_exposed_planet, &state).expect("An unshielded planet under threat needs a shield");
+        assert_eq!(suggestion.advice, format!("build a defense_shield on {}", state.map.planets[&home].name));
+    }
+
+    #[test]
+    fn test_fix_energy_deficit() {
+        let mut state = fixture_game(1);
+        let home = rich_home(&mut state);
+        assert_eq!(run(fix_energy_deficit, &state), None);
+
+        let config = test_support::fixture_structure_config();
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        let (storage, mining) = (String::from("storage_complex"), String::from("mining_complex"));
+        planet.complete_upgrade_structure(&String::from("planetary_capital")).unwrap();
+        planet.complete_build_structure(storage.clone(), &config).unwrap();
+        planet.complete_upgrade_structure(&storage).unwrap();
+        planet.complete_build_structure(mining.clone(), &config).unwrap();
+        planet.complete_upgrade_structure(&mining).unwrap();
+        planet.recalculate_from_structures();
+
+        let suggestion = run(fix_energy_deficit, &state).expect("A deficit calls for energy");
+        let name = &state.map.planets[&home].name;
+        assert_eq!(suggestion.to_string(), format!("Your energy balance on {name} is -8: build a power_grid on {name}"));
+    }
+
+    #[test]
+    fn test_colonize_neighbor() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        fleet_at(&mut state, &home, &["interceptor"]);
+        assert_eq!(run(colonize_neighbor, &state), None);
+
+        state.players.get_mut("alice").unwrap().fleets.clear();
+        fleet_at(&mut state, &home, &["ark"]);
+
+        let suggestion = run(colonize_neighbor, &state).expect("An ark next to neutral planets should colonize");
+        assert!(suggestion.situation.starts_with("You have an ark in fleet Pioneer and a neutral neighbor"));
+        assert!(suggestion.advice.starts_with("consider colonizing it, starting with 'fleet move fleet_1 "));
+    }
+
+    #[test]
+    fn test_start_construction() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        state.map.planets.get_mut(&home).unwrap().available_resources = Resources::default();
+        assert_eq!(run(start_construction, &state), None);
+
+        rich_home(&mut state);
+
+        let suggestion = run(start_construction, &state).expect("Spare resources should be put to work");
+        assert_eq!(suggestion.situation, format!("Nothing is under construction on {}", state.map.planets[&home].name));
+    }
+
+    #[test]
+    fn test_spend_full_storage() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        state.map.planets.get_mut(&home).unwrap().available_resources = Resources::default();
+        assert_eq!(run(spend_full_storage, &state), None);
+
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.available_resources = planet.storage_capacity.clone();
+
+        let suggestion = run(spend_full_storage, &state).expect("Full storage should be spent");
+        assert!(suggestion.situation.ends_with("100% full"));
+        assert!(suggestion.advice.starts_with("spend it or build a storage_complex"));
+    }
+
+    #[test]
+    fn test_advise_keeps_the_most_important_suggestions() {
+        let mut state = fixture_game(1);
+        let home = rich_home(&mut state);
+        fleet_at(&mut state, &home, &["ark"]);
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.available_resources = planet.storage_capacity.clone();
+
+        let suggestions = advise(&state, &String::from("alice"));
+
+        assert_eq!(suggestions.len(), MAX_SUGGESTIONS);
+        assert!(suggestions[0].situation.starts_with("You have an ark"));
+        assert!(suggestions[1].situation.starts_with("Nothing is under construction"));
+        assert!(suggestions[2].situation.contains("% full"));
+        assert_eq!(advise(&state, &String::from("alice")), suggestions);
+    }
+}
@@ -0,0 +1,302 @@
+use std::fmt;
+
+use crate::engine::player::PlayerId;
+use crate::engine::resources::{self, ResourceName};
+use crate::engine::structure::StructureState;
+
+use super::GameState;
+
+/// Storage fill level, in percent, from which a still-producing resource is reported.
+pub const STORAGE_ALERT_PERCENT: u32 = 95;
+
+/// A condition on one of a player's planets that needs attention at the start of their turn.
+/// Variants are listed from most to least urgent, so sorting alerts puts the urgent ones first.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+pub enum Alert {
+    /// A fleet on sentry woke because hostiles arrived at or next to its planet
+    SentryWoken { planet_name: String, fleet_name: String, hostile_planet_name: String },
+    /// Hostile ships are at or next to a planet without a defense shield
+    UnshieldedNearHostiles { planet_name: String, hostile_planet_name: String },
+    /// Structures on the planet consume more energy than it produces
+    EnergyDeficit { planet_name: String, balance: i64 },
+    /// Unrest is high enough to cut the planet's production
+    Unrest { planet_name: String, unrest: u32, penalty_percent: u32 },
+    StructureDamaged { planet_name: String, structure_name: String },
+    /// A resource is close to its storage cap while still being produced
+    StorageNearlyFull {
+        planet_name: String,
+        #[serde(deserialize_with = "resources::deserialize_resource_name")]
+        resource: ResourceName,
+        percent: u32,
+    },
+    /// A fleet with ships has no orders and is not on sentry
+    IdleFleet { planet_name: String, fleet_name: String },
+}
+
+impl fmt::Display for Alert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Alert::SentryWoken { planet_name, fleet_name, hostile_planet_name } => write!(
+                f, "{}: sentry fleet {} woke, hostile fleet arriving at {}", planet_name, fleet_name, hostile_planet_name
+            ),
+            Alert::UnshieldedNearHostiles { planet_name, hostile_planet_name } => write!(
+                f, "{}: no defense shield and hostile ships detected at {}", planet_name, hostile_planet_name
+            ),
+            Alert::EnergyDeficit { planet_name, balance } => write!(
+                f, "{}: energy balance {} per turn", planet_name, balance
+            ),
+            Alert::Unrest { planet_name, unrest, penalty_percent } => write!(
+                f, "{}: unrest {} is cutting production by {}%", planet_name, unrest, penalty_percent
+            ),
+            Alert::StructureDamaged { planet_name, structure_name } => write!(
+                f, "{}: {} is damaged", planet_name, structure_name
+            ),
+            Alert::StorageNearlyFull { planet_name, resource, percent } => write!(
+                f, "{}: {} storage {}% full, production will be wasted", planet_name, resource, percent
+            ),
+            Alert::IdleFleet { planet_name, fleet_name } => write!(
+                f, "{}: fleet {} has no orders (use 'fleet hold' to park it)", planet_name, fleet_name
+            ),
+        }
+    }
+}
+
+/// Scans `player_id`'s planets and fleets for alert conditions, most urgent first.
+/// Hostile ships only count where the player can currently see them.
+pub fn scan_alerts(game_state: &GameState, player_id: &PlayerId) -> Vec<Alert> {
+    let Some(player) = game_state.players.get(player_id) else {
+        return Vec::new();
+    };
+    let presence = game_state.planet_presence(player_id);
+    let hostile_at = |planet_id: &String| presence.get(planet_id).is_some_and(|presence| presence.hostile_ships);
+
+    let graph = game_state.map.graph();
+
+    let mut alerts = Vec::new();
+    for planet in player.planets.iter().filter_map(|id| game_state.map.planets.get(id)) {
+        if planet.get_structure_level(&String::from("defense_shield")) == 0 {
+            let mut nearby = std::iter::once(&planet.id)
+                .chain(graph.neighbors(&planet.id).map(|(neighbor, _)| neighbor))
+                .filter(|planet_id| hostile_at(planet_id))
+                .filter_map(|planet_id| game_state.map.planets.get(planet_id))
+                .map(|planet| planet.name.clone())
+                .collect::<Vec<_>>();
+            nearby.sort();
+            if let Some(hostile_planet_name) = nearby.into_iter().next() {
+                alerts.push(Alert::UnshieldedNearHostiles { planet_name: planet.name.clone(), hostile_planet_name });
+            }
+        }
+
+        let balance = i64::from(planet.get_production_rate().energy) - i64::from(planet.get_energy_consumption());
+        if balance < 0 {
+            alerts.push(Alert::EnergyDeficit { planet_name: planet.name.clone(), balance });
+        }
+
+        let penalty_percent = game_state.rules.unrest.production_penalty_percent(planet.get_unrest());
+        if penalty_percent > 0 {
+            alerts.push(Alert::Unrest { planet_name: planet.name.clone(), unrest: planet.get_unrest(), penalty_percent });
+        }
+
+        for structure in planet.get_structures().values() {
+            if matches!(structure.state, StructureState::Damaged) {
+                alerts.push(Alert::StructureDamaged {
+                    planet_name: planet.name.clone(),
+                    structure_name: structure.name.clone(),
+                });
+            }
+        }
+
+        let available = &planet.available_resources;
+        let production = planet.get_production_rate();
+        let capacity = &planet.storage_capacity;
+        let resources = [
+            ("minerals", available.minerals, production.minerals, capacity.minerals),
+            ("gas", available.gas, production.gas, capacity.gas),
+            ("energy", available.energy, production.energy, capacity.energy),
+        ];
+        for (resource, available, rate, capacity) in resources {
+            if rate == 0 || capacity == 0 {
+                continue;
+            }
+            let percent = u32::try_from(u64::from(available) * 100 / u64::from(capacity)).unwrap_or(u32::MAX);
+            if percent >= STORAGE_ALERT_PERCENT {
+                alerts.push(Alert::StorageNearlyFull { planet_name: planet.name.clone(), resource, percent });
+            }
+        }
+    }
+
+    for fleet in player.fleets.values() {
+        let planet_name = game_state.planet_name(&fleet.location);
+        if let Some(hostile_planet_name) = &fleet.woken_by {
+            alerts.push(Alert::SentryWoken {
+                planet_name,
+                fleet_name: fleet.name.clone(),
+                hostile_planet_name: hostile_planet_name.clone(),
+            });
+        } else if !fleet.sentry
+            && !fleet.is_empty()
+            && !player.has_pending_fleet_move(&fleet.id)
+            && !player.has_pending_fleet_bombardment(&fleet.id)
+        {
+            alerts.push(Alert::IdleFleet { planet_name, fleet_name: fleet.name.clone() });
+        }
+    }
+
+    alerts.sort();
+    alerts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::command::CommandEffect;
+    use crate::engine::fleet::Fleet;
+    use crate::engine::game_event::GameEvent;
+    use crate::engine::resources::Resources;
+    use crate::test_support::{self, fixture_game};
+
+    /// Alice's home with storage well below the alert level, so only the condition under test fires.
+    fn quiet_game() -> (GameState, String) {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        state.map.planets.get_mut(&home).unwrap().available_resources = Resources::default();
+        (state, home)
+    }
+
+    fn alice_alerts(state: &GameState) -> Vec<Alert> {
+        scan_alerts(state, &String::from("alice"))
+    }
+
+    #[test]
+    fn test_quiet_planet_has_no_alerts() {
+        let (state, _) = quiet_game();
+
+        assert_eq!(alice_alerts(&state), vec![]);
+    }
+
+    #[test]
+    fn test_storage_near_capacity_with_production() {
+        let (mut state, home) = quiet_game();
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        // Capital Lv1 stores 500/250/100, so gas at 80% is fine
+        planet.available_resources = Resources { minerals: 475, gas: 200, energy: 100 };
+        let planet_name = planet.name.clone();
+
+        assert_eq!(alice_alerts(&state), vec![
+            Alert::StorageNearlyFull { planet_name: planet_name.clone(), resource: "energy", percent: 100 },
+            Alert::StorageNearlyFull { planet_name, resource: "minerals", percent: 95 },
+        ]);
+    }
+
+    #[test]
+    fn test_negative_energy_balance() {
+        let (mut state, home) = quiet_game();
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        // Capital Lv2 makes 15 energy; storage and mining at Lv2 consume 8 + 15
+        planet.complete_upgrade_structure(&String::from("planetary_capital")).unwrap();
+        for structure_id in ["storage_complex", "mining_complex"] {
+            planet.complete_build_structure(String::from(structure_id), &state.structure_config).unwrap();
+            planet.complete_upgrade_structure(&String::from(structure_id)).unwrap();
+        }
+        planet.recalculate_from_structures();
+        let planet_name = planet.name.clone();
+
+        assert_eq!(alice_alerts(&state), vec![Alert::EnergyDeficit { planet_name, balance: -8 }]);
+    }
+
+    #[test]
+    fn test_unrest_past_the_penalty_threshold() {
+        let (mut state, home) = quiet_game();
+        let threshold = state.rules.unrest.penalty_threshold;
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.set_unrest(threshold);
+        assert_eq!(alice_alerts(&state), vec![]);
+
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.set_unrest(threshold + 10);
+        let planet_name = planet.name.clone();
+        assert_eq!(alice_alerts(&state), vec![Alert::Unrest { planet_name, unrest: threshold + 10, penalty_percent: 10 }]);
+    }
+
+    #[test]
+    fn test_damaged_structure() {
+        let (mut state, home) = quiet_game();
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.get_structure_mut(&String::from("planetary_capital")).unwrap().state = StructureState::Damaged;
+        let planet_name = planet.name.clone();
+
+        assert_eq!(alice_alerts(&state), vec![
+            Alert::StructureDamaged { planet_name, structure_name: String::from("Planetary Capital") },
+        ]);
+    }
+
+    #[test]
+    fn test_unshielded_planet_next_to_detected_hostiles() {
+        let (mut state, home) = quiet_game();
+        let neighbour = state.map.planets[&home].get_connections()[0].to().clone();
+        state.players.get_mut("bob").unwrap().add_ship(String::from("interceptor"), neighbour.clone());
+
+        // Hostiles out of sensor range go unnoticed
+        assert_eq!(alice_alerts(&state), vec![]);
+
+        state.players.get_mut("alice").unwrap().add_ship(String::from("scout"), neighbour.clone());
+        let alerts = alice_alerts(&state);
+        assert_eq!(alerts, vec![Alert::UnshieldedNearHostiles {
+            planet_name: state.map.planets[&home].name.clone(),
+            hostile_planet_name: state.map.planets[&neighbour].name.clone(),
+        }]);
+
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        for structure_id in ["power_grid", "defense_shield"] {
+            planet.complete_build_structure(String::from(structure_id), &state.structure_config).unwrap();
+        }
+        planet.recalculate_from_structures();
+        assert_eq!(alice_alerts(&state), vec![]);
+    }
+
+    #[test]
+    fn test_held_fleet_is_not_reported_idle() {
+        let (mut state, home) = quiet_game();
+        let alice = state.players.get_mut("alice").unwrap();
+        let ship_id = alice.add_ship(String::from("interceptor"), home.clone());
+        let mut fleet = Fleet::new(String::from("fleet_1"), String::from("Picket"), home.clone());
+        fleet.add_ship(ship_id);
+        alice.fleets.insert(fleet.id.clone(), fleet);
+
+        assert_eq!(alice_alerts(&state), vec![Alert::IdleFleet {
+            planet_name: state.map.planets[&home].name.clone(),
+            fleet_name: String::from("Picket"),
+        }]);
+
+        let effect = CommandEffect::SetFleetSentry { fleet_id: String::from("fleet_1"), sentry: true };
+        state.apply_effect(effect, &String::from("alice")).unwrap();
+        assert_eq!(alice_alerts(&state), vec![]);
+    }
+
+    #[test]
+    fn test_alerts_lead_the_turn_banner() {
+        let (mut state, home) = quiet_game();
+        let neighbour = state.map.planets[&home].get_connections()[0].to().clone();
+        state.players.get_mut("alice").unwrap().add_ship(String::from("scout"), neighbour.clone());
+        state.players.get_mut("bob").unwrap().add_ship(String::from("interceptor"), neighbour);
+        state.map.planets.get_mut(&home).unwrap().available_resources.minerals = 500;
+
+        let mut events = Vec::new();
+        for _ in 0..2 {
+            let player_id = state.current_player().clone();
+            let player_name = state.players[&player_id].name.clone();
+            events = state.apply_effect(CommandEffect::EndTurn { player_name }, &player_id).unwrap();
+        }
+
+        let Some(GameEvent::PlayerTurnStarted { alerts, .. }) = events.last() else {
+            panic!("A round must end with the next player's banner");
+        };
+        assert!(matches!(alerts[0], Alert::UnshieldedNearHostiles { .. }), "{alerts:?}");
+        assert!(matches!(alerts[1], Alert::StorageNearlyFull { resource: "minerals", .. }), "{alerts:?}");
+
+        let banner = events.last().unwrap().to_string();
+        let lines: Vec<_> = banner.lines().collect();
+        assert_eq!(lines[0], "Alice's turn.");
+        assert!(lines[1].contains("no defense shield"), "{banner}");
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::engine::configs::ship_config::ShipId;
+use crate::engine::planet::PlanetId;
+use crate::engine::player::PlayerId;
+use crate::engine::ship::{FleetId, ShipInstanceId};
+
+use super::GameState;
+
+/// Where a battle was fought.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum BattleKind {
+    /// An arriving fleet against the ships in orbit
+    Orbital,
+    /// A colonizing fleet against the planet's garrison
+    GarrisonAssault,
+}
+
+impl fmt::Display for BattleKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BattleKind::Orbital => write!(f, "orbital battle"),
+            BattleKind::GarrisonAssault => write!(f, "garrison assault"),
+        }
+    }
+}
+
+/// A ship that took part in a battle, with the type it fought as.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BattleShip {
+    pub id: ShipInstanceId,
+    pub ship_type: ShipId,
+}
+
+/// One side of a battle as it stood when the fighting started.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BattleSide {
+    pub player_id: PlayerId,
+    pub player_name: String,
+    /// The attacking fleet; defenders fight with whatever is at the planet
+    pub fleet_id: Option<FleetId>,
+    pub ships: Vec<BattleShip>,
+    /// Ships of this side destroyed in the battle
+    pub losses: Vec<ShipInstanceId>,
+}
+
+impl BattleSide {
+    /// Counts by type of the side's ships among `ship_ids`, such as "2 interceptor, 1 ravager".
+    pub fn composition<'a>(&self, ship_ids: impl IntoIterator<Item = &'a ShipInstanceId>) -> String {
+        let ship_ids: Vec<_> = ship_ids.into_iter().collect();
+        let mut counts: BTreeMap<&ShipId, usize> = BTreeMap::new();
+        for ship in self.ships.iter().filter(|ship| ship_ids.contains(&&ship.id)) {
+            *counts.entry(&ship.ship_type).or_default() += 1;
+        }
+        if counts.is_empty() {
+            return String::from("no ships");
+        }
+        counts.iter()
+            .map(|(ship_type, count)| format!("{} {}", count, ship_type))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// One exchange of fire. Strengths include counter bonuses; `*_boosted` count the ships that
+/// got one.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BattleRound {
+    pub attack: u32,
+    pub defense: u32,
+    pub boosted_attackers: usize,
+    pub boosted_defenders: usize,
+}
+
+/// Everything needed to retell a battle after its events have scrolled away.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BattleReport {
+    /// Numbered from 1 in the order battles were fought
+    pub id: u32,
+    pub turn: u32,
+    pub kind: BattleKind,
+    pub planet_id: PlanetId,
+    pub planet_name: String,
+    pub attacker: BattleSide,
+    pub defender: BattleSide,
+    pub rounds: Vec<BattleRound>,
+    pub attacker_wins: bool,
+    /// Players who had the planet in sensor range without fighting
+    pub observers: Vec<PlayerId>,
+}
+
+impl BattleReport {
+    /// Whether `player_id` fought in or watched the battle.
+    pub fn is_visible_to(&self, player_id: &PlayerId) -> bool {
+        &self.attacker.player_id == player_id || &self.defender.player_id == player_id || self.observers.contains(player_id)
+    }
+
+    pub fn winner(&self) -> &BattleSide {
+        if self.attacker_wins { &self.attacker } else { &self.defender }
+    }
+}
+
+impl GameState {
+    /// Stores a battle under the next id, counting the reports compaction dropped.
+    pub(super) fn record_battle(&mut self, mut report: BattleReport) {
+        report.id = (self.compaction.battles + self.battles.len()) as u32 + 1;
+        self.battles.push(report);
+        self.count_processed(0, 1);
+    }
+
+    /// Battles `viewer` fought in or watched, most recent first. Replays show every battle.
+    pub fn battles_visible_to(&self, viewer: &PlayerId) -> Vec<&BattleReport> {
+        self.battles.iter()
+            .rev()
+            .filter(|report| self.omniscient || report.is_visible_to(viewer))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::commands::command::CommandEffect;
+    use crate::engine::game_state::GameState;
+    use crate::engine::planet::PlanetId;
+    use crate::engine::ship::MovableUnit;
+    use crate::test_support::{self, fixture_game};
+
+    /// Alice's `attackers` arrive at Bob's home, where `defenders` orbit. Returns the ship ids
+    /// both players had before the battle.
+    fn fight(state: &mut GameState, attackers: &[&str], defenders: &[&str]) -> (Vec<String>, Vec<String>, PlanetId) {
+        let (alice, bob) = (String::from("alice"), String::from("bob"));
+        let bob_home = test_support::home_planet(state, "bob");
+        let mut ship_ids = Vec::new();
+        for ship_type in attackers {
+            ship_ids.push(state.players.get_mut(&alice).unwrap().add_ship(ship_type.to_string(), bob_home.clone()));
+        }
+        for ship_type in defenders {
+            state.players.get_mut(&bob).unwrap().add_ship(ship_type.to_string(), bob_home.clone());
+        }
+        let first_ship = ship_ids[0].clone();
+        let create = CommandEffect::CreateFleet { name: String::from("strike"), ship_ids, location: bob_home.clone() };
+        state.apply_effect(create, &alice).unwrap();
+        let fleet_id = state.players[&alice].ships[&first_ship].fleet_id.clone().unwrap();
+
+        let before = |state: &GameState, player_id: &str| state.players[player_id].ships.keys().cloned().collect::<Vec<_>>();
+        let (alice_before, bob_before) = (before(state, "alice"), before(state, "bob"));
+        state.process_arrival(&alice, &MovableUnit::Fleet(fleet_id.clone()), &bob_home);
+        (alice_before, bob_before, bob_home)
+    }
+
+    fn destroyed(before: &[String], state: &GameState, player_id: &str) -> Vec<String> {
+        before.iter().filter(|ship_id| !state.players[player_id].ships.contains_key(*ship_id)).cloned().collect()
+    }
+
+    #[test]
+    fn test_stored_losses_match_the_ships_removed() {
+        for (attackers, defenders) in [(&["interceptor"; 3][..], &["ravager"][..]), (&["scout"][..], &["ravager"; 2][..])] {
+            let mut state = fixture_game(1);
+            let (alice_before, bob_before, bob_home) = fight(&mut state, attackers, defenders);
+
+            let report = state.battles.last().expect("The battle must be recorded");
+            let mut attacker_losses = report.attacker.losses.clone();
+            let mut defender_losses = report.defender.losses.clone();
+            attacker_losses.sort();
+            defender_losses.sort();
+            assert_eq!(attacker_losses, destroyed(&alice_before, &state, "alice"));
+            assert_eq!(defender_losses, destroyed(&bob_before, &state, "bob"));
+            assert_eq!(report.planet_id, bob_home);
+            assert_eq!(report.attacker.ships.len(), attackers.len());
+            let losers = if report.attacker_wins { defenders } else { attackers };
+            assert_eq!(attacker_losses.len() + defender_losses.len(), losers.len());
+        }
+    }
+
+    #[test]
+    fn test_battles_are_numbered_and_visible_to_participants_only() {
+        let mut state = fixture_game(1);
+        fight(&mut state, &["interceptor"; 3], &["ravager"]);
+        fight(&mut state, &["scout"], &["ravager"]);
+
+        let ids: Vec<_> = state.battles.iter().map(|report| report.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+        assert_eq!(state.battles_visible_to(&String::from("alice")).first().map(|report| report.id), Some(2));
+        assert_eq!(state.battles_visible_to(&String::from("bob")).len(), 2);
+        assert!(state.battles_visible_to(&String::from("carol")).is_empty());
+
+        state.omniscient = true;
+        assert_eq!(state.battles_visible_to(&String::from("carol")).len(), 2);
+    }
+}
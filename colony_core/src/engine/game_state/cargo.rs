@@ -0,0 +1,188 @@
+use crate::engine::game_event::GameEvent;
+use crate::engine::planet::PlanetId;
+use crate::engine::player::PlayerId;
+use crate::engine::resources::Resources;
+use crate::engine::rules;
+use crate::engine::ship::FleetId;
+
+use super::GameState;
+
+impl GameState {
+    /// Moves `cargo` from the planet the fleet orbits into its holds.
+    pub(super) fn load_cargo(&mut self, player_id: &PlayerId, fleet_id: &FleetId, cargo: Resources) -> GameEvent {
+        let player = &self.players[player_id];
+        let capacity = rules::cargo_capacity(&player.fleets[fleet_id].ships, player, self);
+
+        let fleet = self.players.get_mut(player_id)
+            .and_then(|player| player.fleets.get_mut(fleet_id))
+            .expect("Fleet must exist (validated by command)");
+        let planet = self.map.planets.get_mut(&fleet.location)
+            .expect("Fleet location must exist");
+        planet.available_resources -= &cargo;
+        fleet.cargo += &cargo;
+
+        GameEvent::CargoLoaded {
+            fleet_id: fleet_id.clone(),
+            planet_name: planet.name.clone(),
+            cargo,
+            carried: fleet.cargo.total(),
+            capacity,
+        }
+    }
+
+    /// Stores `cargo` on `planet_id`, up to its storage capacity, and reports what went to waste.
+    pub(super) fn unload_cargo(&mut self, player_id: &PlayerId, fleet_id: &FleetId, planet_id: &PlanetId, cargo: Resources) -> GameEvent {
+        let planet = self.map.planets.get_mut(planet_id)
+            .expect("Planet must exist");
+        let stored = planet.refund(&cargo);
+        let wasted = cargo - stored.clone();
+
+        GameEvent::CargoUnloaded {
+            player_id: player_id.clone(),
+            fleet_id: fleet_id.clone(),
+            planet_name: planet.name.clone(),
+            stored,
+            wasted,
+        }
+    }
+
+    /// Takes the whole cargo of a fleet off it. Empty for unknown fleets.
+    pub(super) fn take_cargo(&mut self, player_id: &PlayerId, fleet_id: &FleetId) -> Resources {
+        self.players.get_mut(player_id)
+            .and_then(|player| player.fleets.get_mut(fleet_id))
+            .map(|fleet| std::mem::take(&mut fleet.cargo))
+            .unwrap_or_default()
+    }
+
+    /// Trims the cargo of a fleet to what its ships can still carry after some of them were
+    /// lost or refitted, and returns the part that no longer fits. Minerals are kept first,
+    /// then gas, then energy.
+    pub(super) fn take_cargo_overflow(&mut self, player_id: &PlayerId, fleet_id: &FleetId) -> Resources {
+        let Some(player) = self.players.get(player_id) else {
+            return Resources::default();
+        };
+        let Some(fleet) = player.fleets.get(fleet_id) else {
+            return Resources::default();
+        };
+        let kept = fleet.cargo.limited_to_total(rules::cargo_capacity(&fleet.ships, player, self));
+
+        let fleet = self.players.get_mut(player_id)
+            .and_then(|player| player.fleets.get_mut(fleet_id))
+            .expect("Fleet was found above");
+        std::mem::replace(&mut fleet.cargo, kept.clone()) - kept
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::commands::command::CommandEffect;
+    use crate::engine::commands::parser;
+    use crate::engine::fleet::Fleet;
+    use crate::engine::game_event::GameEvent;
+    use crate::engine::game_state::GameState;
+    use crate::engine::resources::Resources;
+    use crate::test_support::{self, fixture_game};
+
+    /// Alice's fleet_1 at her home planet with two arks, 200 cargo between them.
+    fn freighters(state: &mut GameState) -> Vec<String> {
+        let home = test_support::home_planet(state, "alice");
+        let alice = state.players.get_mut("alice").unwrap();
+        let mut fleet = Fleet::new(String::from("fleet_1"), String::from("Convoy"), home.clone());
+        let arks: Vec<String> = (0..2).map(|_| alice.add_ship(String::from("ark"), home.clone())).collect();
+        for ark in &arks {
+            fleet.add_ship(ark.clone());
+            alice.ships.get_mut(ark).unwrap().fleet_id = Some(fleet.id.clone());
+        }
+        alice.fleets.insert(fleet.id.clone(), fleet);
+        arks
+    }
+
+    fn order(state: &mut GameState, line: &str) -> Result<Vec<GameEvent>, String> {
+        let effect = parser::parse(line).unwrap().execute(state).map_err(|error| error.to_string())?;
+        Ok(state.apply_effect(effect, &String::from("alice")).unwrap())
+    }
+
+    #[test]
+    fn test_loading_is_bounded_by_capacity_and_planet_stock() {
+        let mut state = fixture_game(1);
+        freighters(&mut state);
+        let home = test_support::home_planet(&state, "alice");
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.available_resources = Resources { minerals: 150, gas: 40, energy: 500 };
+
+        let error = order(&mut state, "fleet load fleet_1 150 40 20").unwrap_err();
+        assert!(error.contains("cargo holds take 200 - 0 aboard and 210 more does not fit"), "{error}");
+        let error = order(&mut state, "fleet load fleet_1 100 50 0").unwrap_err();
+        assert!(error.contains("not enough resources"), "{error}");
+
+        let events = order(&mut state, "fleet load fleet_1 150 40 0").unwrap();
+        assert!(matches!(&events[..], [GameEvent::CargoLoaded { carried: 190, capacity: 200, .. }]));
+        assert_eq!(state.map.planets[&home].available_resources, Resources { minerals: 0, gas: 0, energy: 500 });
+        assert_eq!(state.players["alice"].fleets["fleet_1"].cargo, Resources { minerals: 150, gas: 40, energy: 0 });
+
+        let error = order(&mut state, "fleet load fleet_1 0 0 11").unwrap_err();
+        assert!(error.contains("190 aboard and 11 more does not fit"), "{error}");
+        order(&mut state, "fleet load fleet_1 0 0 10").unwrap();
+        assert_eq!(state.players["alice"].fleets["fleet_1"].cargo.total(), 200);
+    }
+
+    #[test]
+    fn test_unloading_stores_what_fits_and_reports_the_waste() {
+        let mut state = fixture_game(1);
+        freighters(&mut state);
+        let home = test_support::home_planet(&state, "alice");
+        assert!(order(&mut state, "fleet unload fleet_1").unwrap_err().contains("fleet carries no cargo"));
+
+        state.players.get_mut("alice").unwrap().fleets.get_mut("fleet_1").unwrap().cargo =
+            Resources { minerals: 120, gas: 30, energy: 0 };
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        let capacity = planet.storage_capacity.clone();
+        planet.available_resources = Resources { minerals: capacity.minerals - 100, ..capacity.clone() };
+
+        let events = order(&mut state, "fleet unload fleet_1").unwrap();
+        let [GameEvent::CargoUnloaded { stored, wasted, .. }] = &events[..] else {
+            panic!("Expected one unload, got {:?}", events);
+        };
+        assert_eq!(*stored, Resources { minerals: 100, gas: 0, energy: 0 });
+        assert_eq!(*wasted, Resources { minerals: 20, gas: 30, energy: 0 });
+        assert_eq!(state.map.planets[&home].available_resources, capacity);
+        assert_eq!(state.players["alice"].fleets["fleet_1"].cargo, Resources::default());
+    }
+
+    #[test]
+    fn test_cargo_goes_down_with_the_ships_carrying_it() {
+        let mut state = fixture_game(1);
+        let arks = freighters(&mut state);
+        let alice = String::from("alice");
+        state.players.get_mut("alice").unwrap().fleets.get_mut("fleet_1").unwrap().cargo =
+            Resources { minerals: 80, gas: 50, energy: 30 };
+
+        // One ark lost leaves room for 100, kept minerals first
+        let events = state.destroy_ships(&Some(alice.clone()), &arks[..1]);
+        assert!(matches!(&events[..], [GameEvent::CargoLost { lost, .. }] if *lost == Resources { minerals: 0, gas: 30, energy: 30 }));
+        assert_eq!(state.players["alice"].fleets["fleet_1"].cargo, Resources { minerals: 80, gas: 20, energy: 0 });
+
+        // The fleet gone, nothing of its cargo is left anywhere
+        let events = state.destroy_ships(&Some(alice), &arks[1..]);
+        assert!(matches!(&events[..], [GameEvent::CargoLost { lost, .. }, ..] if lost.total() == 100));
+        assert!(state.players["alice"].fleets.is_empty());
+        assert!(state.check_invariants().is_empty());
+    }
+
+    #[test]
+    fn test_ships_needed_for_the_cargo_cannot_leave_the_fleet() {
+        let mut state = fixture_game(1);
+        let arks = freighters(&mut state);
+        state.players.get_mut("alice").unwrap().fleets.get_mut("fleet_1").unwrap().cargo =
+            Resources { minerals: 150, gas: 0, energy: 0 };
+
+        for line in [format!("fleet remove fleet_1 {}", arks[0]), String::from("fleet disband fleet_1"), format!("fleet split fleet_1 Escort {}", arks[1])] {
+            let error = order(&mut state, &line).unwrap_err();
+            assert!(error.contains("fleet carries 150 cargo and only"), "{line}: {error}");
+        }
+
+        let effect = CommandEffect::UnloadCargo { fleet_id: String::from("fleet_1") };
+        state.apply_effect(effect, &String::from("alice")).unwrap();
+        order(&mut state, "fleet disband fleet_1").unwrap();
+    }
+}
@@ -0,0 +1,536 @@
+use crate::engine::configs::ship_config::ShipId;
+use crate::engine::game_event::{ArrivalKind, GameEvent};
+use crate::engine::planet::PlanetId;
+use crate::engine::player::PlayerId;
+use crate::engine::resources::Resources;
+use crate::engine::ship::{FleetId, MovableUnit, ShipInstanceId};
+
+use super::GameState;
+use super::battles::{BattleKind, BattleReport, BattleRound, BattleShip, BattleSide};
+use super::orphans::Lost;
+
+/// Counter bonus multiplier for ships attacking their counter-type
+const COUNTER_BONUS_MULTIPLIER: f32 = 1.5;
+
+struct CombatResult {
+    attacker_wins: bool,
+    attacker_strength: u32,
+    defender_strength: u32,
+}
+
+impl GameState {
+    /// Lands a finished move of `unit`: observers spot it first, while combat cannot have
+    /// shrunk it yet, then the arrival and any battle are resolved.
+    pub(super) fn complete_move(&mut self, player_id: &PlayerId, unit: &MovableUnit, destination: &PlanetId) -> Vec<GameEvent> {
+        let mut events = self.report_arrival(player_id, unit, destination);
+        events.extend(self.process_arrival(player_id, unit, destination));
+        events
+    }
+
+    /// Processes a fleet or lone ship arriving at a destination planet.
+    /// Handles combat resolution and conquest.
+    /// Returns events describing what happened.
+    pub(super) fn process_arrival(
+        &mut self,
+        attacker_id: &PlayerId,
+        unit: &MovableUnit,
+        destination: &PlanetId,
+    ) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+
+        // Get destination planet info
+        let planet_owner = self.map.planets
+            .get(destination)
+            .and_then(|p| p.get_owner().clone());
+        let planet_name = self.planet_name(destination);
+
+        // Check if this is a friendly arrival (own or allied planet) or potential combat
+        let needs_combat = !planet_owner.as_ref().is_some_and(|owner| self.are_allies(owner, attacker_id));
+
+        if !needs_combat {
+            // Friendly arrival - just move the unit
+            self.move_unit_to_planet(attacker_id, unit, destination);
+            events.push(arrival_event(attacker_id, unit, planet_name, ArrivalKind::Friendly));
+            return events;
+        }
+
+        // Combat needed - get defending ships at the planet
+        let defender_id = planet_owner;
+        let defending_ship_ids = self.get_defending_ships(destination, &defender_id);
+
+        if defending_ship_ids.is_empty() {
+            // Undefended planet - move the unit there
+            self.move_unit_to_planet(attacker_id, unit, destination);
+
+            let kind = if defender_id.is_some() {
+                ArrivalKind::UndefendedEnemy
+            } else {
+                ArrivalKind::Neutral
+            };
+            events.push(arrival_event(attacker_id, unit, planet_name, kind));
+            return events;
+        }
+
+        // Defended planet - resolve combat
+        let attacking_ship_ids = self.unit_ships(attacker_id, unit);
+        let combat_result = self.resolve_combat(
+            attacker_id,
+            &attacking_ship_ids,
+            &defender_id,
+            &defending_ship_ids,
+        );
+        if let Some(defender) = &defender_id {
+            let fleet_id = match unit {
+                MovableUnit::Fleet(fleet_id) => Some(fleet_id.clone()),
+                MovableUnit::Ship(_) => None,
+            };
+            self.report_battle(
+                BattleKind::Orbital, attacker_id, fleet_id, &attacking_ship_ids, defender, &defending_ship_ids,
+                destination, &combat_result,
+            );
+        }
+
+        let attacker_name = self.players.get(attacker_id)
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| String::from("Unknown"));
+        let defender_name = defender_id.as_ref()
+            .and_then(|id| self.players.get(id))
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| String::from("Unknown"));
+
+        let (ships_destroyed, cancelled) = if combat_result.attacker_wins {
+            // Destroy defending ships
+            let cancelled = self.destroy_ships(&defender_id, &defending_ship_ids);
+
+            // Move the attacking unit to planet
+            self.move_unit_to_planet(attacker_id, unit, destination);
+
+            (defending_ship_ids.len(), cancelled)
+        } else {
+            // Destroy the attacking ships; an emptied fleet is disbanded along with them
+            let cancelled = self.destroy_ships(&Some(attacker_id.clone()), &attacking_ship_ids);
+
+            (attacking_ship_ids.len(), cancelled)
+        };
+
+        events.push(GameEvent::Battle {
+            fleet_id: unit.id().to_string(),
+            planet_name,
+            attacker_name,
+            defender_name,
+            attacker_strength: combat_result.attacker_strength,
+            defender_strength: combat_result.defender_strength,
+            attacker_wins: combat_result.attacker_wins,
+            ships_destroyed,
+        });
+        events.extend(cancelled);
+
+        events
+    }
+
+    /// Fights the garrison of the planet `fleet_id` is landing on, when it belongs to another
+    /// player and has one. The loser's ships are destroyed. Returns the `GarrisonAssault` event
+    /// followed by any actions the loss cancelled, or nothing when there is no fight.
+    pub(super) fn assault_garrison(
+        &mut self,
+        attacker_id: &PlayerId,
+        fleet_id: &FleetId,
+        planet_id: &PlanetId,
+    ) -> Vec<GameEvent> {
+        let Some(planet) = self.map.planets.get(planet_id) else {
+            return Vec::new();
+        };
+        let Some(defender_id) = planet.get_owner().clone().filter(|owner| !self.are_allies(owner, attacker_id)) else {
+            return Vec::new();
+        };
+        let garrison = planet.get_garrison().to_vec();
+        if garrison.is_empty() {
+            return Vec::new();
+        }
+
+        let attacker_fleet = self.unit_ships(attacker_id, &MovableUnit::Fleet(fleet_id.clone()));
+        let combat_result = self.resolve_combat(attacker_id, &attacker_fleet, &Some(defender_id.clone()), &garrison);
+        self.report_battle(
+            BattleKind::GarrisonAssault, attacker_id, Some(fleet_id.clone()), &attacker_fleet, &defender_id, &garrison,
+            planet_id, &combat_result,
+        );
+
+        let (ships_destroyed, cancelled) = if combat_result.attacker_wins {
+            let cancelled = self.destroy_ships(&Some(defender_id.clone()), &garrison);
+            (garrison.len(), cancelled)
+        } else {
+            let cancelled = self.destroy_ships(&Some(attacker_id.clone()), &attacker_fleet);
+            (attacker_fleet.len(), cancelled)
+        };
+
+        let mut events = vec![GameEvent::GarrisonAssault {
+            fleet_id: fleet_id.clone(),
+            planet_name: self.planet_name(planet_id),
+            attacker_name: self.players.get(attacker_id).map(|p| p.name.clone()).unwrap_or_default(),
+            defender_name: self.players.get(&defender_id).map(|p| p.name.clone()).unwrap_or_default(),
+            attacker_strength: combat_result.attacker_strength,
+            defender_strength: combat_result.defender_strength,
+            attacker_wins: combat_result.attacker_wins,
+            ships_destroyed,
+        }];
+        events.extend(cancelled);
+        events
+    }
+
+    /// Records a battle `combat_result` is about to settle, while the ships of both sides still
+    /// exist. The losing side loses every ship it fought with.
+    #[allow(clippy::too_many_arguments)]
+    fn report_battle(
+        &mut self,
+        kind: BattleKind,
+        attacker_id: &PlayerId,
+        fleet_id: Option<FleetId>,
+        attacking_ship_ids: &[ShipInstanceId],
+        defender_id: &PlayerId,
+        defending_ship_ids: &[ShipInstanceId],
+        planet_id: &PlanetId,
+        combat_result: &CombatResult,
+    ) {
+        let boosted_attackers = self.battle_ships(attacker_id, attacking_ship_ids).iter()
+            .filter(|ship| self.has_counter_advantage(&ship.ship_type, defending_ship_ids))
+            .count();
+        let boosted_defenders = self.battle_ships(defender_id, defending_ship_ids).iter()
+            .filter(|ship| self.has_counter_advantage_against_attackers(&ship.ship_type, attacker_id, attacking_ship_ids))
+            .count();
+
+        let side = |player_id: &PlayerId, fleet_id: Option<FleetId>, ship_ids: &[ShipInstanceId], lost: bool| BattleSide {
+            player_id: player_id.clone(),
+            player_name: self.players.get(player_id).map(|player| player.name.clone()).unwrap_or_default(),
+            fleet_id,
+            ships: self.battle_ships(player_id, ship_ids),
+            losses: if lost { ship_ids.to_vec() } else { Vec::new() },
+        };
+        let report = BattleReport {
+            id: 0,
+            turn: self.turn,
+            kind,
+            planet_id: planet_id.clone(),
+            planet_name: self.planet_name(planet_id),
+            attacker: side(attacker_id, fleet_id, attacking_ship_ids, !combat_result.attacker_wins),
+            defender: side(defender_id, None, defending_ship_ids, combat_result.attacker_wins),
+            rounds: vec![BattleRound {
+                attack: combat_result.attacker_strength,
+                defense: combat_result.defender_strength,
+                boosted_attackers,
+                boosted_defenders,
+            }],
+            attacker_wins: combat_result.attacker_wins,
+            observers: self.bombardment_observers(attacker_id, planet_id).into_iter()
+                .filter(|observer| observer != defender_id)
+                .collect(),
+        };
+        self.record_battle(report);
+    }
+
+    /// The ships `unit` of `player_id` consists of: a fleet's members, or the lone ship itself.
+    pub(super) fn unit_ships(&self, player_id: &PlayerId, unit: &MovableUnit) -> Vec<ShipInstanceId> {
+        let Some(player) = self.players.get(player_id) else {
+            return Vec::new();
+        };
+        match unit {
+            MovableUnit::Fleet(fleet_id) => player.fleets.get(fleet_id)
+                .map(|fleet| fleet.ships.clone())
+                .unwrap_or_default(),
+            MovableUnit::Ship(ship_id) => player.ships.get(ship_id)
+                .map(|ship| vec![ship.id.clone()])
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The ships among `ship_ids` that `player_id` owns, with their types.
+    fn battle_ships(&self, player_id: &PlayerId, ship_ids: &[ShipInstanceId]) -> Vec<BattleShip> {
+        let Some(player) = self.players.get(player_id) else {
+            return Vec::new();
+        };
+        ship_ids.iter()
+            .filter_map(|ship_id| player.ships.get(ship_id))
+            .map(|ship| BattleShip { id: ship.id.clone(), ship_type: ship.ship_type.clone() })
+            .collect()
+    }
+
+    /// Bombardment power `fleet_id` lands on `planet_id` each turn once the garrison has fired back.
+    pub fn effective_bombardment(&self, player_id: &PlayerId, fleet_id: &FleetId, planet_id: &PlanetId) -> u32 {
+        let power = self.calculate_fleet_bombardment(player_id, fleet_id);
+        power - self.garrison_fire(planet_id).min(power)
+    }
+
+    /// Total attack of the ships garrisoned on a planet, which fires back at bombarding fleets.
+    pub fn garrison_fire(&self, planet_id: &PlanetId) -> u32 {
+        let Some(planet) = self.map.planets.get(planet_id) else {
+            return 0;
+        };
+        let Some(owner) = planet.get_owner().as_ref().and_then(|owner| self.players.get(owner)) else {
+            return 0;
+        };
+
+        planet.get_garrison().iter()
+            .filter_map(|ship_id| owner.ships.get(ship_id))
+            .filter_map(|ship| self.ship_config.get(&ship.ship_type))
+            .map(|ship_def| ship_def.attack)
+            .fold(0, u32::saturating_add)
+    }
+
+    /// Gets all ships defending a planet in orbit (ships belonging to planet owner at that
+    /// location). Garrisoned ships stay planet-side and do not take part.
+    fn get_defending_ships(
+        &self,
+        planet_id: &PlanetId,
+        owner_id: &Option<PlayerId>,
+    ) -> Vec<ShipInstanceId> {
+        let Some(owner) = owner_id else {
+            return Vec::new();
+        };
+
+        let Some(player) = self.players.get(owner) else {
+            return Vec::new();
+        };
+
+        let garrison = self.map.planets.get(planet_id)
+            .map(|planet| planet.get_garrison())
+            .unwrap_or_default();
+
+        player.ships
+            .values()
+            .filter(|ship| &ship.location == planet_id && !garrison.contains(&ship.id))
+            .map(|ship| ship.id.clone())
+            .collect()
+    }
+
+    /// Moves a fleet or lone ship to a new planet location.
+    fn move_unit_to_planet(
+        &mut self,
+        player_id: &PlayerId,
+        unit: &MovableUnit,
+        destination: &PlanetId,
+    ) {
+        let ship_ids = self.unit_ships(player_id, unit);
+        let Some(player) = self.players.get_mut(player_id) else {
+            return;
+        };
+        if ship_ids.is_empty() {
+            return;
+        }
+
+        if let MovableUnit::Fleet(fleet_id) = unit
+            && let Some(fleet) = player.fleets.get_mut(fleet_id)
+        {
+            fleet.location = destination.clone();
+        }
+        player.explored.insert(destination.clone());
+
+        // Update all ship locations
+        for ship_id in &ship_ids {
+            if let Some(ship) = player.ships.get_mut(ship_id) {
+                ship.location = destination.clone();
+            }
+        }
+    }
+
+    /// Destroys a list of ships belonging to a player. Fleets left empty are disbanded; returns
+    /// the cargo lost with the ships, and the cancellations of the disbanded fleets' remaining
+    /// moves and bombardments and of the destroyed ships' own moves.
+    pub(super) fn destroy_ships(&mut self, player_id: &Option<PlayerId>, ship_ids: &[ShipInstanceId]) -> Vec<GameEvent> {
+        let Some(owner_id) = player_id else {
+            return Vec::new();
+        };
+
+        let Some(player) = self.players.get_mut(owner_id) else {
+            return Vec::new();
+        };
+
+        let mut shrunk: Vec<FleetId> = Vec::new();
+        for ship_id in ship_ids {
+            // Remove ship from player and from any fleet or garrison
+            let Some(ship) = player.ships.remove(ship_id) else {
+                continue;
+            };
+            if let Some(fleet_id) = &ship.fleet_id
+                && let Some(fleet) = player.fleets.get_mut(fleet_id)
+            {
+                fleet.remove_ship(ship_id);
+                if !shrunk.contains(fleet_id) {
+                    shrunk.push(fleet_id.clone());
+                }
+            }
+            if let Some(planet) = self.map.planets.get_mut(&ship.location) {
+                planet.ungarrison_ship(ship_id);
+            }
+        }
+
+        // Cargo beyond what the surviving ships carry goes down with the rest
+        let mut events: Vec<GameEvent> = Vec::new();
+        for fleet_id in &shrunk {
+            let lost = self.take_cargo_overflow(owner_id, fleet_id);
+            if lost != Resources::default() {
+                let fleet_name = self.players[owner_id].fleets[fleet_id].name.clone();
+                events.push(GameEvent::CargoLost { player_id: owner_id.clone(), fleet_name, lost });
+            }
+        }
+
+        // Clean up empty fleets
+        let player = self.players.get_mut(owner_id)
+            .expect("Player was found above");
+        let emptied: Vec<_> = player.fleets.values()
+            .filter(|fleet| fleet.is_empty())
+            .map(|fleet| fleet.id.clone())
+            .collect();
+        player.fleets.retain(|_, fleet| !fleet.is_empty());
+
+        events.extend(emptied.iter()
+            .flat_map(|fleet_id| self.cancel_orphaned_actions(owner_id, Lost::Fleet(fleet_id))));
+        for ship_id in ship_ids {
+            events.extend(self.cancel_orphaned_actions(owner_id, Lost::Ship(ship_id)));
+        }
+        events
+    }
+
+    /// Resolves combat between attacking ships and defending ships.
+    fn resolve_combat(
+        &self,
+        attacker_id: &PlayerId,
+        attacking_ship_ids: &[ShipInstanceId],
+        defender_id: &Option<PlayerId>,
+        defending_ship_ids: &[ShipInstanceId],
+    ) -> CombatResult {
+        let attacker_strength = self.calculate_attack(attacker_id, attacking_ship_ids, defending_ship_ids);
+        let defender_strength = self.calculate_defense(defender_id, defending_ship_ids, attacker_id, attacking_ship_ids);
+
+        CombatResult {
+            attacker_wins: attacker_strength > defender_strength,
+            attacker_strength,
+            defender_strength,
+        }
+    }
+
+    /// Calculates total attack strength of attacking ships with counter bonuses.
+    fn calculate_attack(
+        &self,
+        player_id: &PlayerId,
+        attacker_ship_ids: &[ShipInstanceId],
+        defender_ship_ids: &[ShipInstanceId],
+    ) -> u32 {
+        let Some(player) = self.players.get(player_id) else {
+            return 0;
+        };
+
+        let mut total_attack: u32 = 0;
+
+        for ship_id in attacker_ship_ids {
+            if let Some(ship) = player.ships.get(ship_id)
+                && let Some(ship_def) = self.ship_config.get(&ship.ship_type)
+            {
+                let mut attack = ship_def.attack;
+
+                // Apply counter bonuses
+                if self.has_counter_advantage(&ship.ship_type, defender_ship_ids) {
+                    attack = (attack as f32 * COUNTER_BONUS_MULTIPLIER) as u32;
+                }
+
+                total_attack = total_attack.saturating_add(attack);
+            }
+        }
+
+        total_attack
+    }
+
+    /// Calculates total defense strength of defending ships with counter bonuses.
+    pub(super) fn calculate_defense(
+        &self,
+        defender_id: &Option<PlayerId>,
+        defender_ship_ids: &[ShipInstanceId],
+        attacker_id: &PlayerId,
+        attacker_ship_ids: &[ShipInstanceId],
+    ) -> u32 {
+        let Some(owner_id) = defender_id else {
+            return 0;
+        };
+
+        let Some(player) = self.players.get(owner_id) else {
+            return 0;
+        };
+
+        let mut total_defense: u32 = 0;
+
+        for ship_id in defender_ship_ids {
+            if let Some(ship) = player.ships.get(ship_id)
+                && let Some(ship_def) = self.ship_config.get(&ship.ship_type)
+            {
+                let mut defense = ship_def.shield.saturating_sub(ship.supply_wear);
+
+                // Apply counter bonuses
+                if self.has_counter_advantage_against_attackers(&ship.ship_type, attacker_id, attacker_ship_ids) {
+                    defense = (defense as f32 * COUNTER_BONUS_MULTIPLIER) as u32;
+                }
+
+                total_defense = total_defense.saturating_add(defense);
+            }
+        }
+
+        total_defense
+    }
+
+    /// Checks if a ship type has counter advantage against any of the defender ships.
+    fn has_counter_advantage(&self, ship_type: &ShipId, defender_ship_ids: &[ShipInstanceId]) -> bool {
+        let Some(ship_def) = self.ship_config.get(ship_type) else {
+            return false;
+        };
+
+        // Get all defender ship types
+        for ship_id in defender_ship_ids {
+            for player in self.players.values() {
+                if let Some(defender_ship) = player.ships.get(ship_id)
+                    && ship_def.counters.contains(&defender_ship.ship_type)
+                {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Checks if a ship type has counter advantage against any of the attacking ships.
+    fn has_counter_advantage_against_attackers(
+        &self,
+        ship_type: &ShipId,
+        attacker_id: &PlayerId,
+        attacker_ship_ids: &[ShipInstanceId],
+    ) -> bool {
+        let Some(ship_def) = self.ship_config.get(ship_type) else {
+            return false;
+        };
+
+        let Some(player) = self.players.get(attacker_id) else {
+            return false;
+        };
+
+        // Check if this ship counters any attacking ship
+        attacker_ship_ids.iter().any(|ship_id| {
+            player.ships.get(ship_id)
+                .is_some_and(|attacker_ship| ship_def.counters.contains(&attacker_ship.ship_type))
+        })
+    }
+}
+
+/// The no-combat arrival event for `unit`.
+fn arrival_event(player_id: &PlayerId, unit: &MovableUnit, planet_name: String, kind: ArrivalKind) -> GameEvent {
+    match unit {
+        MovableUnit::Fleet(fleet_id) => GameEvent::FleetArrived {
+            player_id: player_id.clone(),
+            fleet_id: fleet_id.clone(),
+            planet_name,
+            kind,
+        },
+        MovableUnit::Ship(ship_id) => GameEvent::ShipArrived {
+            player_id: player_id.clone(),
+            ship_instance_id: ship_id.clone(),
+            planet_name,
+            kind,
+        },
+    }
+}
@@ -0,0 +1,485 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::engine::game_event::GameEvent;
+use crate::engine::planet::PlanetId;
+use crate::engine::player::PlayerId;
+use crate::engine::ship::MovableUnit;
+
+use super::GameState;
+
+/// Rough size of a detected fleet, reported when no sensor ship is watching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum FleetSizeClass {
+    Lone,
+    Small,
+    Medium,
+    Large,
+}
+
+impl FleetSizeClass {
+    pub fn of(ship_count: usize) -> Self {
+        match ship_count {
+            0..=1 => FleetSizeClass::Lone,
+            2..=4 => FleetSizeClass::Small,
+            5..=9 => FleetSizeClass::Medium,
+            _ => FleetSizeClass::Large,
+        }
+    }
+}
+
+/// What an observer learns about the size of a fleet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ObservedSize {
+    Class(FleetSizeClass),
+    Exact(usize),
+}
+
+impl fmt::Display for ObservedSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObservedSize::Class(FleetSizeClass::Lone) => write!(f, "a lone ship"),
+            ObservedSize::Class(FleetSizeClass::Small) => write!(f, "a small fleet (2-4 ships)"),
+            ObservedSize::Class(FleetSizeClass::Medium) => write!(f, "a medium fleet (5-9 ships)"),
+            ObservedSize::Class(FleetSizeClass::Large) => write!(f, "a large fleet (10+ ships)"),
+            ObservedSize::Exact(count) => write!(f, "a fleet of exactly {} ship(s)", count),
+        }
+    }
+}
+
+/// Rough state of a bombarded planet's shield, as seen by anyone but the bombarder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ShieldEstimate {
+    Strong,
+    Weakened,
+    Failing,
+}
+
+impl ShieldEstimate {
+    /// Above two thirds of the maximum is strong, above one third weakened.
+    pub fn of(shield_hp: u32, max_shield_hp: u32) -> Self {
+        let percent = u64::from(shield_hp) * 100 / u64::from(max_shield_hp.max(1));
+        match percent {
+            67.. => ShieldEstimate::Strong,
+            34..=66 => ShieldEstimate::Weakened,
+            _ => ShieldEstimate::Failing,
+        }
+    }
+
+    /// Lowest and highest shield HP the estimate stands for on a shield of `max_shield_hp`.
+    pub fn hp_range(self, max_shield_hp: u32) -> (u32, u32) {
+        // Smallest HP at `percent` of the maximum, rounded the way `of` does
+        let at_least = |percent: u64| (u64::from(max_shield_hp) * percent).div_ceil(100) as u32;
+        match self {
+            ShieldEstimate::Strong => (at_least(67), max_shield_hp),
+            ShieldEstimate::Weakened => (at_least(34), at_least(67).saturating_sub(1)),
+            ShieldEstimate::Failing => (0, at_least(34).saturating_sub(1)),
+        }
+    }
+}
+
+impl fmt::Display for ShieldEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShieldEstimate::Strong => write!(f, "holding strong"),
+            ShieldEstimate::Weakened => write!(f, "weakened"),
+            ShieldEstimate::Failing => write!(f, "close to failing"),
+        }
+    }
+}
+
+/// Another player's fleet seen arriving at a planet. Composition is never revealed.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FleetSighting {
+    pub owner_name: String,
+    pub planet_name: String,
+    pub size: ObservedSize,
+}
+
+impl fmt::Display for FleetSighting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} of {} arriving at {}", self.size, self.owner_name, self.planet_name)
+    }
+}
+
+impl GameState {
+    /// Decides who notices `mover`'s fleet of `ship_count` ships arriving at `destination`.
+    /// Any player outside the mover's team who owns the destination or a planet adjacent to
+    /// it, or has ships at one of those planets, sees it. Only a sensor ship among those ships
+    /// reveals the exact size. Observers are returned in turn order.
+    pub fn detect_fleet_arrival(
+        &self,
+        mover: &PlayerId,
+        ship_count: usize,
+        destination: &PlanetId,
+    ) -> Vec<(PlayerId, FleetSighting)> {
+        let Some(planet) = self.map.planets.get(destination) else {
+            return Vec::new();
+        };
+        let watched: HashSet<&PlanetId> = std::iter::once(destination)
+            .chain(self.map.graph().neighbors(destination).map(|(neighbor, _)| neighbor))
+            .collect();
+        let owner_name = self.players.get(mover)
+            .map(|player| player.name.clone())
+            .unwrap_or_else(|| mover.clone());
+
+        self.players_order.iter()
+            .filter(|player_id| !self.are_allies(mover, player_id))
+            .filter_map(|player_id| self.players.get(player_id))
+            .filter_map(|player| {
+                let owns_watched = player.planets.iter().any(|planet_id| watched.contains(planet_id));
+                let mut watching_ships = player.ships.values()
+                    .filter(|ship| watched.contains(&ship.location))
+                    .peekable();
+                let has_ships = watching_ships.peek().is_some();
+                let has_sensor = watching_ships.any(|ship| self.ship_config.get(&ship.ship_type)
+                    .is_some_and(|definition| definition.sensor));
+
+                if !owns_watched && !has_ships {
+                    return None;
+                }
+
+                let size = if has_sensor {
+                    ObservedSize::Exact(ship_count)
+                } else {
+                    ObservedSize::Class(FleetSizeClass::of(ship_count))
+                };
+                Some((player.id.clone(), FleetSighting {
+                    owner_name: owner_name.clone(),
+                    planet_name: planet.name.clone(),
+                    size,
+                }))
+            })
+            .collect()
+    }
+
+    /// Players other than `attacker` with `planet_id` in sensor range, in turn order. Owners
+    /// always see their own planets.
+    pub fn bombardment_observers(&self, attacker: &PlayerId, planet_id: &PlanetId) -> Vec<PlayerId> {
+        self.players_order.iter()
+            .filter(|player_id| *player_id != attacker)
+            .filter(|player_id| self.visible_planets(player_id).contains(planet_id))
+            .cloned()
+            .collect()
+    }
+
+    /// Records sightings of a fleet or lone ship about to arrive at `destination` for every
+    /// observer, wakes their sentry fleets at or next to it, and returns the matching events.
+    /// Called before combat so observers see the full fleet.
+    pub(super) fn report_arrival(
+        &mut self,
+        mover: &PlayerId,
+        unit: &MovableUnit,
+        destination: &PlanetId,
+    ) -> Vec<GameEvent> {
+        let ship_count = self.unit_ships(mover, unit).len();
+        if ship_count == 0 {
+            return Vec::new();
+        }
+
+        let watched: Vec<PlanetId> = self.map.graph().neighbors(destination)
+            .map(|(neighbor, _)| neighbor.clone())
+            .collect();
+
+        let mut events = Vec::new();
+        for (observer_id, sighting) in self.detect_fleet_arrival(mover, ship_count, destination) {
+            let observer = self.players.get_mut(&observer_id)
+                .expect("Observer comes from the players map");
+            observer.recent_sightings.push(sighting.clone());
+
+            // Fleets on sentry nearby stop holding and raise the alarm for their owner
+            let sentries = observer.fleets.values_mut()
+                .filter(|fleet| fleet.sentry)
+                .filter(|fleet| &fleet.location == destination || watched.contains(&fleet.location));
+            for fleet in sentries {
+                fleet.sentry = false;
+                fleet.woken_by = Some(sighting.planet_name.clone());
+            }
+
+            events.push(GameEvent::FleetDetected {
+                observer_id,
+                observer_name: observer.name.clone(),
+                sighting,
+            });
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::command::CommandEffect;
+    use crate::engine::fleet::Fleet;
+    use crate::engine::game_state::{Alert, scan_alerts};
+    use crate::engine::pending_action::{ActionType, PendingAction};
+    use crate::engine::resources::Resources;
+    use crate::engine::game_event::OutputTarget;
+    use crate::test_support::{self, fixture_game};
+
+    /// A planet neither fixture player owns, next to bob's home, and one further away from
+    /// both homes. Returns (adjacent_to_bob, far_away).
+    fn watched_and_unwatched(state: &GameState) -> (PlanetId, PlanetId) {
+        let bob_home = test_support::home_planet(state, "bob");
+        let near_bob: HashSet<_> = state.map.planets[&bob_home].get_connections().iter()
+            .map(|connection| connection.to().clone())
+            .collect();
+        let owned: Vec<_> = state.players.values().flat_map(|player| player.planets.clone()).collect();
+        let is_unowned = |id: &PlanetId| !owned.contains(id);
+
+        let mut adjacent: Vec<_> = near_bob.iter().filter(|id| is_unowned(id)).cloned().collect();
+        adjacent.sort();
+        let far: Vec<_> = state.map.planets.values()
+            .filter(|planet| is_unowned(&planet.id) && !near_bob.contains(&planet.id))
+            .filter(|planet| planet.get_connections().iter().all(|connection| is_unowned(connection.to())))
+            .map(|planet| planet.id.clone())
+            .collect();
+        (adjacent[0].clone(), far[0].clone())
+    }
+
+    fn bob_sightings(state: &GameState, destination: &PlanetId, ship_count: usize) -> Vec<FleetSighting> {
+        state.detect_fleet_arrival(&String::from("alice"), ship_count, destination)
+            .into_iter()
+            .filter(|(observer, _)| observer == "bob")
+            .map(|(_, sighting)| sighting)
+            .collect()
+    }
+
+    #[test]
+    fn test_owner_of_destination_detects_arrival() {
+        let state = fixture_game(1);
+        let bob_home = test_support::home_planet(&state, "bob");
+
+        let sightings = bob_sightings(&state, &bob_home, 3);
+
+        assert_eq!(sightings.len(), 1);
+        assert_eq!(sightings[0].size, ObservedSize::Class(FleetSizeClass::Small));
+        assert_eq!(sightings[0].owner_name, "Alice");
+    }
+
+    #[test]
+    fn test_owner_of_adjacent_planet_detects_arrival() {
+        let state = fixture_game(1);
+        let (adjacent, far) = watched_and_unwatched(&state);
+
+        assert_eq!(bob_sightings(&state, &adjacent, 1).len(), 1);
+        assert!(bob_sightings(&state, &far, 1).is_empty());
+    }
+
+    #[test]
+    fn test_ships_at_destination_or_adjacent_planet_detect_arrival() {
+        let mut state = fixture_game(1);
+        let (_, far) = watched_and_unwatched(&state);
+        let next_to_far = state.map.planets[&far].get_connections()[0].to().clone();
+
+        state.players.get_mut("bob").unwrap().add_ship(String::from("interceptor"), next_to_far);
+        let sightings = bob_sightings(&state, &far, 7);
+
+        assert_eq!(sightings.len(), 1);
+        assert_eq!(sightings[0].size, ObservedSize::Class(FleetSizeClass::Medium));
+    }
+
+    #[test]
+    fn test_sensor_ship_reveals_exact_count() {
+        let mut state = fixture_game(1);
+        let (_, far) = watched_and_unwatched(&state);
+
+        state.players.get_mut("bob").unwrap().add_ship(String::from("scout"), far.clone());
+        let sightings = bob_sightings(&state, &far, 12);
+
+        assert_eq!(sightings[0].size, ObservedSize::Exact(12));
+    }
+
+    #[test]
+    fn test_mover_never_detects_itself() {
+        let state = fixture_game(1);
+        let alice_home = test_support::home_planet(&state, "alice");
+
+        let observers: Vec<_> = state.detect_fleet_arrival(&String::from("alice"), 2, &alice_home)
+            .into_iter()
+            .map(|(observer, _)| observer)
+            .collect();
+
+        assert!(!observers.contains(&String::from("alice")));
+    }
+
+    #[test]
+    fn test_fleet_move_reports_sighting_to_observer() {
+        let mut state = fixture_game(1);
+        let (adjacent, _) = watched_and_unwatched(&state);
+        let origin = state.map.planets[&adjacent].get_connections()[0].to().clone();
+        state.players.get_mut("alice").unwrap().add_ship(String::from("interceptor"), origin);
+        for input in ["fleet create raiders interceptor_1", &format!("fleet move fleet_1 {}", adjacent)] {
+            let effect = crate::engine::commands::parser::parse(input).unwrap().execute(&state).unwrap();
+            state.apply_effect(effect, &String::from("alice")).unwrap();
+        }
+
+        let mut events = Vec::new();
+        for _ in 0..10 {
+            let player_id = state.current_player().clone();
+            let player_name = state.players[&player_id].name.clone();
+            events = state.apply_effect(CommandEffect::EndTurn { player_name }, &player_id).unwrap();
+            if events.iter().any(|e| matches!(e, GameEvent::FleetDetected { .. })) {
+                break;
+            }
+        }
+
+        assert!(events.iter().any(|e| matches!(e, GameEvent::FleetDetected { observer_name, .. } if observer_name == "Bob")));
+        assert_eq!(state.players["bob"].recent_sightings.len(), 1);
+        assert!(state.players["alice"].recent_sightings.is_empty());
+    }
+
+    #[test]
+    fn test_fleets_may_orbit_a_shielded_enemy_planet_but_are_seen_arriving() {
+        let mut state = fixture_game(1);
+        let bob_home = test_support::home_planet(&state, "bob");
+        let planet = state.map.planets.get_mut(&bob_home).unwrap();
+        for structure_id in ["power_grid", "defense_shield"] {
+            planet.complete_build_structure(String::from(structure_id), &state.structure_config).unwrap();
+        }
+        planet.overcharge_shield();
+        let (alice, fleet_id) = (String::from("alice"), String::from("fleet_1"));
+        let player = state.players.get_mut("alice").unwrap();
+        let mut fleet = Fleet::new(fleet_id.clone(), String::from("Pickets"), bob_home.clone());
+        fleet.add_ship(player.add_ship(String::from("interceptor"), bob_home.clone()));
+        player.fleets.insert(fleet_id.clone(), fleet);
+
+        let events = state.complete_move(&alice, &MovableUnit::Fleet(fleet_id.clone()), &bob_home);
+
+        assert!(state.map.planets[&bob_home].get_shield_hp() > 0);
+        assert_eq!(state.players["alice"].fleets[&fleet_id].location, bob_home);
+        assert!(events.iter().any(|e| matches!(e, GameEvent::FleetDetected { observer_name, .. } if observer_name == "Bob")));
+        assert!(state.is_blockaded(&bob_home));
+    }
+
+    #[test]
+    fn test_hostile_arrival_wakes_nearby_sentry_with_alert() {
+        let mut state = fixture_game(1);
+        let (adjacent, far) = watched_and_unwatched(&state);
+        let bob_home = test_support::home_planet(&state, "bob");
+        let bob = state.players.get_mut("bob").unwrap();
+        for (fleet_id, location) in [("fleet_1", &bob_home), ("fleet_2", &far)] {
+            let ship_id = bob.add_ship(String::from("interceptor"), location.clone());
+            let mut fleet = Fleet::new(fleet_id.to_string(), String::from("Picket"), location.clone());
+            fleet.add_ship(ship_id);
+            fleet.sentry = true;
+            bob.fleets.insert(fleet.id.clone(), fleet);
+        }
+        let alice = state.players.get_mut("alice").unwrap();
+        let ship_id = alice.add_ship(String::from("interceptor"), adjacent.clone());
+        let mut raiders = Fleet::new(String::from("fleet_1"), String::from("Raiders"), adjacent.clone());
+        raiders.add_ship(ship_id);
+        alice.fleets.insert(raiders.id.clone(), raiders);
+
+        state.report_arrival(&String::from("alice"), &MovableUnit::Fleet(String::from("fleet_1")), &adjacent);
+
+        let bob = &state.players["bob"];
+        assert!(!bob.fleets["fleet_1"].sentry);
+        assert!(bob.fleets["fleet_2"].sentry);
+        let alerts = scan_alerts(&state, &String::from("bob"));
+        assert!(matches!(&alerts[0], Alert::SentryWoken { hostile_planet_name, .. }
+            if *hostile_planet_name == state.map.planets[&adjacent].name), "{alerts:?}");
+    }
+
+    #[test]
+    fn test_size_classes() {
+        let classes: Vec<_> = [1, 2, 4, 5, 9, 10, 50].into_iter().map(FleetSizeClass::of).collect();
+
+        assert_eq!(classes, [
+            FleetSizeClass::Lone,
+            FleetSizeClass::Small,
+            FleetSizeClass::Small,
+            FleetSizeClass::Medium,
+            FleetSizeClass::Medium,
+            FleetSizeClass::Large,
+            FleetSizeClass::Large,
+        ]);
+    }
+
+    /// Alice bombards bob's overcharged home (50 HP) with `ravagers` ravagers while carol has
+    /// a scout in orbit and dave sees nothing; nobody is allied. Returns the round's events.
+    fn bombard_bob_home(ravagers: usize) -> Vec<GameEvent> {
+        let mut state = test_support::fixture_team_game(1);
+        for player in state.players.values_mut() {
+            player.team = None;
+        }
+        let bob_home = test_support::home_planet(&state, "bob");
+        let planet = state.map.planets.get_mut(&bob_home).unwrap();
+        for structure_id in ["power_grid", "defense_shield"] {
+            planet.complete_build_structure(String::from(structure_id), &state.structure_config).unwrap();
+        }
+        planet.overcharge_shield();
+
+        let alice = state.players.get_mut("alice").unwrap();
+        let mut fleet = Fleet::new(String::from("fleet_1"), String::from("Siege"), bob_home.clone());
+        for _ in 0..ravagers {
+            fleet.add_ship(alice.add_ship(String::from("ravager"), bob_home.clone()));
+        }
+        alice.fleets.insert(fleet.id.clone(), fleet);
+        alice.pending_actions.push(PendingAction::new(
+            ActionType::BombardPlanet(String::from("fleet_1"), bob_home.clone()),
+            bob_home.clone(),
+            u32::MAX,
+            Resources::default(),
+        ));
+        state.players.get_mut("carol").unwrap().add_ship(String::from("scout"), bob_home);
+
+        let mut events = Vec::new();
+        for player_id in test_support::FIXTURE_TEAM_PLAYERS {
+            let player_name = state.players[player_id].name.clone();
+            events = state.apply_effect(CommandEffect::EndTurn { player_name }, &player_id.to_string()).unwrap();
+        }
+        events
+    }
+
+    fn events_for<'a>(events: &'a [GameEvent], player_id: &str) -> Vec<&'a GameEvent> {
+        events.iter()
+            .filter(|event| matches!(event,
+                GameEvent::ShieldsDamaged { .. }
+                | GameEvent::ShieldsDestroyed { .. }
+                | GameEvent::BombardmentObserved { .. }
+                | GameEvent::ShieldsDownObserved { .. }))
+            .filter(|event| event.target() == OutputTarget::Player(player_id.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_bombardment_progress_is_exact_for_attacker_and_coarse_for_observers() {
+        let events = bombard_bob_home(1);
+
+        assert!(matches!(events_for(&events, "alice")[..], [GameEvent::ShieldsDamaged { shields_before: 50, shields_after: 25, .. }]));
+        for observer in ["bob", "carol"] {
+            assert!(matches!(events_for(&events, observer)[..],
+                [GameEvent::BombardmentObserved { shields: ShieldEstimate::Failing, .. }]), "{observer}");
+        }
+        assert!(events_for(&events, "dave").is_empty());
+    }
+
+    #[test]
+    fn test_shields_down_reaches_attacker_defender_and_watchers() {
+        let events = bombard_bob_home(2);
+
+        let [attacker] = events_for(&events, "alice")[..] else { panic!("{events:?}") };
+        assert!(attacker.to_string().contains("open to colonization or invasion"), "{attacker}");
+        let [defender] = events_for(&events, "bob")[..] else { panic!("{events:?}") };
+        assert!(matches!(defender, GameEvent::ShieldsDownObserved { own_planet: true, .. }));
+        assert!(defender.to_string().contains("Shields down on your planet"), "{defender}");
+        assert!(matches!(events_for(&events, "carol")[..],
+            [GameEvent::ShieldsDownObserved { own_planet: false, attacker_name, .. }] if attacker_name == "Alice"));
+        assert!(events_for(&events, "dave").is_empty());
+    }
+
+    #[test]
+    fn test_shield_estimate_bands() {
+        let estimates: Vec<_> = [100, 67, 66, 34, 33, 0].into_iter().map(|hp| ShieldEstimate::of(hp, 100)).collect();
+
+        assert_eq!(estimates, [
+            ShieldEstimate::Strong,
+            ShieldEstimate::Strong,
+            ShieldEstimate::Weakened,
+            ShieldEstimate::Weakened,
+            ShieldEstimate::Failing,
+            ShieldEstimate::Failing,
+        ]);
+    }
+}
@@ -0,0 +1,73 @@
+use crate::engine::commands::dev::DevAction;
+use crate::engine::game_event::GameEvent;
+use crate::engine::pending_action::ActionType;
+use crate::engine::player::PlayerId;
+
+use super::GameState;
+
+impl GameState {
+    /// Applies a developer action through the same paths play uses, so the game stays
+    /// consistent. The first event always tags the change as a developer's, which keeps
+    /// event logs and replays honest about how the game got where it is.
+    pub(super) fn apply_dev_action(
+        &mut self,
+        action: DevAction,
+        acting_player: &PlayerId,
+    ) -> Vec<GameEvent> {
+        let mut effects = Vec::new();
+        let description = match action {
+            DevAction::Give { planet_id, resources } => {
+                let planet = self.map.planets.get_mut(&planet_id)
+                    .expect("Planet must exist (validated by command)");
+                let given = planet.refund(&resources);
+                format!("added {} to {}", given, planet.name)
+            }
+            DevAction::Complete { planet_id } => {
+                let planet_name = self.planet_name(&planet_id);
+                // Bombardments end with the shields, not on a timer
+                let queued_here = |action_type: &ActionType, at: &_| {
+                    at == &planet_id && !matches!(action_type, ActionType::BombardPlanet(..))
+                };
+                let mut completed = 0;
+                for player_id in self.players_order.clone() {
+                    let player = self.players.get_mut(&player_id).expect("Player in rotation must exist");
+                    let (mut due, kept) = std::mem::take(&mut player.pending_actions).into_iter()
+                        .partition::<Vec<_>, _>(|action| queued_here(&action.action_type, &action.planet_id));
+                    player.pending_actions = kept;
+                    due.sort_by_key(|action| action.action_type.completion_stage());
+                    completed += due.len();
+                    for action in due {
+                        effects.extend(self.complete_action(&player_id, action));
+                    }
+                }
+                format!("completed {} queued action(s) on {}", completed, planet_name)
+            }
+            DevAction::Reveal => {
+                self.omniscient = !self.omniscient;
+                String::from(if self.omniscient { "revealed the whole map" } else { "restored the fog of war" })
+            }
+            DevAction::SpawnShip { planet_id, ship_id } => {
+                let planet = self.map.planets.get(&planet_id)
+                    .expect("Planet must exist (validated by command)");
+                let planet_name = planet.name.clone();
+                let owner_id = planet.get_owner().clone()
+                    .expect("Planet must be owned (validated by command)");
+                let owner = self.players.get_mut(&owner_id)
+                    .expect("Planet owner must exist");
+                let ship_instance_id = owner.add_ship(ship_id, planet_id);
+                format!("spawned {} for {} at {}", ship_instance_id, owner.name, planet_name)
+            }
+            DevAction::SetTurn { turn } => {
+                self.turn = turn;
+                format!("set the turn to {}", turn)
+            }
+        };
+
+        let player_name = self.players.get(acting_player)
+            .expect("Acting player must exist")
+            .name.clone();
+        let mut events = vec![GameEvent::DevAction { player_name, description }];
+        events.extend(effects);
+        events
+    }
+}
@@ -0,0 +1,57 @@
+use serde_json::Value;
+
+use super::GameState;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+impl GameState {
+    /// Fingerprint of everything that is saved, equal for equal states however their maps
+    /// happen to be ordered in memory. Two runs of the same seeded game agree turn by turn.
+    pub fn digest(&self) -> u64 {
+        let mut value = serde_json::to_value(self).expect("Game state must serialize");
+        // How long processing took differs from run to run of the same game
+        if let Some(fields) = value.as_object_mut() {
+            fields.remove("timings");
+        }
+        // Objects come out with sorted keys; sets come out in hash order and need sorting
+        sort_array(value.get_mut("acted_this_round"));
+        if let Some(Value::Object(players)) = value.get_mut("players") {
+            for player in players.values_mut() {
+                sort_array(player.get_mut("explored"));
+            }
+        }
+        fnv1a(value.to_string().as_bytes())
+    }
+}
+
+fn sort_array(value: Option<&mut Value>) {
+    if let Some(Value::Array(items)) = value {
+        items.sort_by_key(|item| item.to_string());
+    }
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::fixture_game;
+
+    #[test]
+    fn test_digest_tracks_state_not_map_order() {
+        let mut state = fixture_game(1);
+        let digest = state.digest();
+
+        // Rebuilding the sets reorders them in memory without changing the game
+        state.acted_this_round = state.acted_this_round.drain().collect();
+        for player in state.players.values_mut() {
+            player.explored = player.explored.drain().collect();
+        }
+        assert_eq!(state.digest(), digest);
+
+        state.turn += 1;
+        assert_ne!(state.digest(), digest);
+    }
+}
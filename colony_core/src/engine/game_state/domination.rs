@@ -0,0 +1,133 @@
+use crate::engine::game_event::{GameEvent, VictoryKind};
+use crate::engine::player::PlayerId;
+
+use super::GameState;
+
+impl GameState {
+    /// Number of key planets `player_id` owns.
+    pub fn key_planets_held(&self, player_id: &PlayerId) -> usize {
+        self.map.key_planets().iter()
+            .filter(|planet| planet.get_owner().as_ref() == Some(player_id))
+            .count()
+    }
+
+    /// Counts the full turn that just ended towards each player's domination streak: a player
+    /// holding a majority of the key planets extends theirs, everyone else starts over. Returns
+    /// warnings for players two turns away from winning, and the side of a player whose streak
+    /// reached the configured length together with how they won.
+    pub(super) fn update_domination(&mut self) -> (Vec<GameEvent>, Option<(Vec<PlayerId>, VictoryKind)>) {
+        let total = self.map.key_planets().len();
+        let required = self.rules.domination_turns.max(1);
+        let mut warnings = Vec::new();
+        let mut dominating = None;
+
+        let player_ids: Vec<_> = self.players_order.iter().cloned().collect();
+        for player_id in player_ids {
+            let held = self.key_planets_held(&player_id);
+            if total == 0 || held * 2 <= total {
+                self.domination_streaks.remove(&player_id);
+                continue;
+            }
+
+            let streak = self.domination_streaks.entry(player_id.clone()).or_default();
+            *streak += 1;
+            let streak = *streak;
+            if streak >= required {
+                let kind = VictoryKind::Domination { held, total, turns: streak };
+                dominating.get_or_insert_with(|| (self.side_of(&player_id), kind));
+            } else if required - streak == 2 {
+                warnings.push(GameEvent::DominationWarning {
+                    player_name: self.players[&player_id].name.clone(),
+                    held,
+                    total,
+                    turns_left: 2,
+                });
+            }
+        }
+
+        (warnings, dominating)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::commands::command::CommandEffect;
+    use crate::engine::fleet::Fleet;
+    use crate::engine::game_event::{GameEvent, VictoryKind};
+    use crate::engine::game_rules::VictoryCondition;
+    use crate::engine::game_state::GameState;
+    use crate::engine::planet::PlanetId;
+    use crate::test_support::{self, end_round, fixture_game};
+
+    /// Lands an ark with an escort on `planet_id` and colonizes it for `player_id`.
+    fn invade(state: &mut GameState, player_id: &str, planet_id: &PlanetId) {
+        let player = state.players.get_mut(player_id).unwrap();
+        let fleet_id = player.next_fleet_id();
+        let mut fleet = Fleet::new(fleet_id.clone(), String::from("Landing"), planet_id.clone());
+        for ship_type in ["ark", "interceptor"] {
+            let ship_id = player.add_ship(ship_type.to_string(), planet_id.clone());
+            player.ships.get_mut(&ship_id).unwrap().fleet_id = Some(fleet_id.clone());
+            fleet.add_ship(ship_id);
+        }
+        player.fleets.insert(fleet_id.clone(), fleet);
+        let effect = CommandEffect::ColonizePlanet { fleet_id, planet_id: planet_id.clone() };
+        state.apply_effect(effect, &player_id.to_string()).unwrap();
+    }
+
+    fn warnings(events: &[GameEvent]) -> Vec<(String, u32)> {
+        events.iter()
+            .filter_map(|event| match event {
+                GameEvent::DominationWarning { player_name, turns_left, .. } => Some((player_name.clone(), *turns_left)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_domination_streak_resets_when_control_flips() {
+        let mut state = fixture_game(1);
+        state.rules.victory = VictoryCondition::Domination;
+        state.rules.domination_turns = 3;
+        let first = test_support::grant_planet(&mut state, "alice");
+        let second = test_support::grant_planet(&mut state, "alice");
+        let third = test_support::grant_planet(&mut state, "bob");
+        for planet_id in [&first, &second, &third] {
+            state.map.planets.get_mut(planet_id).unwrap().set_key_planet(true);
+        }
+
+        let events = end_round(&mut state);
+        assert_eq!(warnings(&events), [(String::from("Alice"), 2)]);
+        end_round(&mut state);
+        assert_eq!(state.domination_streaks.get("alice"), Some(&2));
+
+        // Bob takes one back just before alice's third turn and breaks her streak
+        invade(&mut state, "bob", &first);
+        let events = end_round(&mut state);
+        assert!(!events.iter().any(|event| matches!(event, GameEvent::Victory { .. })));
+        assert_eq!(state.domination_streaks.get("alice"), None);
+        assert_eq!(state.domination_streaks.get("bob"), Some(&1));
+        assert_eq!(warnings(&events), [(String::from("Bob"), 2)]);
+
+        end_round(&mut state);
+        let events = end_round(&mut state);
+        let victory = events.iter().find_map(|event| match event {
+            GameEvent::Victory { player_names, kind, .. } => Some((player_names.clone(), kind.clone())),
+            _ => None,
+        });
+        assert_eq!(victory, Some((vec![String::from("Bob")], VictoryKind::Domination { held: 2, total: 3, turns: 3 })));
+        assert!(state.is_over());
+    }
+
+    #[test]
+    fn test_key_planets_do_not_count_under_elimination_rules() {
+        let mut state = fixture_game(1);
+        state.rules.domination_turns = 1;
+        let planet_id = test_support::grant_planet(&mut state, "alice");
+        state.map.planets.get_mut(&planet_id).unwrap().set_key_planet(true);
+
+        let events = end_round(&mut state);
+
+        assert!(!events.iter().any(|event| matches!(event, GameEvent::Victory { .. })));
+        assert!(state.domination_streaks.is_empty());
+    }
+}
@@ -0,0 +1,229 @@
+use crate::engine::configs::structure_config::GlobalEffect;
+use crate::engine::player::PlayerId;
+use crate::engine::structure::StructureState;
+
+use super::GameState;
+
+/// Sum of the global effects of a player's operational, powered structures.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EmpireEffects {
+    pub production_bonus_percent: u32,
+    /// Capped at 100; ships always take at least one turn
+    pub ship_build_time_reduction_percent: u32,
+}
+
+impl EmpireEffects {
+    fn add(&mut self, effect: GlobalEffect, level: u16) {
+        match effect {
+            GlobalEffect::ProductionBonus(percent) => {
+                self.production_bonus_percent = self.production_bonus_percent
+                    .saturating_add(percent.saturating_mul(u32::from(level)));
+            }
+            GlobalEffect::ShipBuildTimeReduction(percent) => {
+                self.ship_build_time_reduction_percent =
+                    self.ship_build_time_reduction_percent
+                        .saturating_add(percent.saturating_mul(u32::from(level)))
+                        .min(100);
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == EmpireEffects::default()
+    }
+
+    /// Returns the reduced build time, rounded to the nearest turn and never below one.
+    pub fn ship_build_time(&self, base_turns: u32) -> u32 {
+        let remaining_percent = 100 - self.ship_build_time_reduction_percent;
+        let reduced = (u64::from(base_turns) * u64::from(remaining_percent) + 50) / 100;
+        u32::try_from(reduced).unwrap_or(u32::MAX).max(1)
+    }
+}
+
+impl GameState {
+    /// Returns a player's empire effects, from the cache when it is fresh.
+    /// Unknown players have no effects.
+    pub fn empire_effects(&self, player_id: &PlayerId) -> EmpireEffects {
+        self.players.get(player_id)
+            .and_then(|player| player.empire_effects)
+            .unwrap_or_else(|| self.compute_empire_effects(player_id))
+    }
+
+    /// Recomputes the cached empire effects of every player whose cache was invalidated.
+    pub(super) fn refresh_empire_effects(&mut self) {
+        let stale: Vec<_> = self.players.values()
+            .filter(|player| player.empire_effects.is_none())
+            .map(|player| player.id.clone())
+            .collect();
+
+        for player_id in stale {
+            let effects = self.compute_empire_effects(&player_id);
+            if let Some(player) = self.players.get_mut(&player_id) {
+                player.empire_effects = Some(effects);
+            }
+        }
+    }
+
+    /// Marks a player's empire effects as stale after their structures or planets changed.
+    pub(super) fn invalidate_empire_effects(&mut self, player_id: &PlayerId) {
+        if let Some(player) = self.players.get_mut(player_id) {
+            player.empire_effects = None;
+        }
+    }
+
+    fn compute_empire_effects(&self, player_id: &PlayerId) -> EmpireEffects {
+        let mut effects = EmpireEffects::default();
+        let Some(player) = self.players.get(player_id) else {
+            return effects;
+        };
+
+        let structures = player.planets.iter()
+            .filter_map(|planet_id| self.map.planets.get(planet_id))
+            .flat_map(|planet| planet.get_structures().values())
+            .filter(|structure| matches!(structure.state, StructureState::Operational) && structure.is_powered());
+
+        for structure in structures {
+            if let Some(effect) = structure.global_effect() {
+                effects.add(effect, structure.level);
+            }
+        }
+
+        effects
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::command::CommandEffect;
+    use crate::engine::game_event::GameEvent;
+    use crate::engine::resources::Resources;
+    use crate::test_support::{self, end_round, fixture_game};
+
+    /// Builds a structure at `level` on the planet, bypassing the pending action queue.
+    fn add_structure(state: &mut GameState, planet_id: &str, structure_id: &str, level: u16) {
+        let planet = state.map.planets.get_mut(planet_id).unwrap();
+        planet.complete_build_structure(structure_id.to_string(), &state.structure_config).unwrap();
+        for _ in 1..level {
+            planet.complete_upgrade_structure(&structure_id.to_string()).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_two_instances_stack() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let colony = test_support::grant_planet(&mut state, "alice");
+        add_structure(&mut state, &home, "trade_hub", 1);
+        add_structure(&mut state, &colony, "trade_hub", 2);
+
+        // Trade hub grants +20% production per level
+        let effects = state.empire_effects(&"alice".to_string());
+
+        assert_eq!(effects, EmpireEffects { production_bonus_percent: 60, ship_build_time_reduction_percent: 0 });
+        assert!(state.empire_effects(&"bob".to_string()).is_empty());
+    }
+
+    #[test]
+    fn test_cache_is_refreshed_only_after_invalidation() {
+        let mut state = fixture_game(1);
+        let alice = "alice".to_string();
+        let home = test_support::home_planet(&state, "alice");
+        state.refresh_empire_effects();
+
+        add_structure(&mut state, &home, "trade_hub", 1);
+        assert!(state.empire_effects(&alice).is_empty());
+
+        state.invalidate_empire_effects(&alice);
+        state.refresh_empire_effects();
+        assert_eq!(state.players[&alice].empire_effects.unwrap().production_bonus_percent, 20);
+    }
+
+    #[test]
+    fn test_stacked_production_bonus_applies_to_round_production() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let colony = test_support::grant_planet(&mut state, "alice");
+        add_structure(&mut state, &home, "trade_hub", 1);
+        add_structure(&mut state, &colony, "trade_hub", 1);
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.available_resources = Resources::default();
+        let base = planet.get_production_rate().clone();
+
+        end_round(&mut state);
+
+        let expected = base.clone() + base.percentage(40);
+        assert_eq!(state.map.planets[&home].available_resources, expected);
+    }
+
+    #[test]
+    fn test_completed_structure_takes_effect_in_the_same_round() {
+        let mut state = fixture_game(1);
+        let alice = "alice".to_string();
+        let home = test_support::home_planet(&state, "alice");
+        state.refresh_empire_effects();
+        state.apply_effect(
+            CommandEffect::BuildStructure { planet_id: home.clone(), structure_id: "trade_hub".to_string() },
+            &alice,
+        ).unwrap();
+
+        end_round(&mut state);
+
+        assert_eq!(state.players[&alice].empire_effects.unwrap().production_bonus_percent, 20);
+    }
+
+    #[test]
+    fn test_powering_off_a_trade_hub_removes_its_bonus() {
+        let mut state = fixture_game(1);
+        let alice = "alice".to_string();
+        let home = test_support::home_planet(&state, "alice");
+        add_structure(&mut state, &home, "trade_hub", 1);
+        state.refresh_empire_effects();
+        assert_eq!(state.empire_effects(&alice).production_bonus_percent, 20);
+
+        state.apply_effect(
+            CommandEffect::SetStructurePower { planet_id: home.clone(), structure_id: "trade_hub".to_string(), on: false },
+            &alice,
+        ).unwrap();
+
+        assert!(state.empire_effects(&alice).is_empty());
+    }
+
+    #[test]
+    fn test_stacked_academies_shorten_ship_builds() {
+        let mut state = fixture_game(1);
+        let alice = "alice".to_string();
+        let home = test_support::home_planet(&state, "alice");
+        let colony = test_support::grant_planet(&mut state, "alice");
+        add_structure(&mut state, &home, "fleet_academy", 1);
+        add_structure(&mut state, &colony, "fleet_academy", 1);
+
+        // Two academies at -25% each halve the ravager's two turn build
+        let events = state.apply_effect(
+            CommandEffect::BuildShip { planet_id: home, ship_id: "ravager".to_string(), deliver_to: None },
+            &alice,
+        ).unwrap();
+
+        assert!(matches!(&events[..], [GameEvent::ShipQueued { turns: 1, .. }]));
+    }
+
+    #[test]
+    fn test_ship_build_time_never_drops_below_one_turn() {
+        let effects = EmpireEffects { production_bonus_percent: 0, ship_build_time_reduction_percent: 100 };
+
+        assert_eq!(effects.ship_build_time(4), 1);
+        assert_eq!(EmpireEffects::default().ship_build_time(4), 4);
+    }
+
+    #[test]
+    fn test_extreme_effects_saturate() {
+        let mut effects = EmpireEffects::default();
+        effects.add(GlobalEffect::ProductionBonus(u32::MAX), u16::MAX);
+        effects.add(GlobalEffect::ShipBuildTimeReduction(u32::MAX), u16::MAX);
+
+        assert_eq!(effects.production_bonus_percent, u32::MAX);
+        assert_eq!(effects.ship_build_time_reduction_percent, 100);
+        assert_eq!(EmpireEffects::default().ship_build_time(u32::MAX), u32::MAX);
+        assert_eq!(Resources { minerals: u32::MAX, gas: 10, energy: 0 }.percentage(u32::MAX).minerals, u32::MAX);
+    }
+}
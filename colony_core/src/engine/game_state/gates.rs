@@ -0,0 +1,176 @@
+use crate::engine::game_event::GameEvent;
+use crate::engine::planet::{Planet, PlanetId};
+
+use super::GameState;
+
+impl GameState {
+    /// Removes every gate link whose planets no longer both hold an operational jump gate
+    /// under the same owner. Returns an event per collapsed link.
+    pub(super) fn collapse_broken_gate_links(&mut self) -> Vec<GameEvent> {
+        let links: Vec<(PlanetId, PlanetId)> = self.map.planets.values()
+            .filter_map(|planet| planet.gate_partner().map(|partner| (planet.id.clone(), partner.clone())))
+            .filter(|(planet_id, partner)| planet_id < partner)
+            .collect();
+
+        let mut events = Vec::new();
+        for (first, second) in links {
+            let (Some(first_planet), Some(second_planet)) = (self.map.planets.get(&first), self.map.planets.get(&second)) else {
+                continue;
+            };
+            let Some(reason) = Self::broken_link_reason(first_planet, second_planet) else {
+                continue;
+            };
+
+            self.map.remove_edge(&first, &second);
+            events.push(GameEvent::GateLinkCollapsed {
+                first_name: self.planet_name(&first),
+                second_name: self.planet_name(&second),
+                reason,
+            });
+        }
+        events
+    }
+
+    fn broken_link_reason(first: &Planet, second: &Planet) -> Option<String> {
+        if let Some(planet) = [first, second].into_iter().find(|planet| !planet.has_operational_gate()) {
+            return Some(format!("the jump gate on {} is down", planet.name));
+        }
+        if first.get_owner().is_none() || first.get_owner() != second.get_owner() {
+            return Some(String::from("the planets no longer share an owner"));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::command::{CommandEffect, CommandError};
+    use crate::engine::commands::parser;
+    use crate::engine::fleet::Fleet;
+    use crate::engine::map::{Map, RenderStyle};
+    use crate::engine::planet::{ConnectionKind, JUMP_GATE};
+    use crate::engine::structure::StructureState;
+    use crate::test_support::{self, end_round, fixture_game, run};
+
+    fn build_gate(state: &mut GameState, planet_id: &PlanetId) {
+        state.map.planets.get_mut(planet_id).unwrap()
+            .complete_build_structure(String::from(JUMP_GATE), &state.structure_config)
+            .unwrap();
+    }
+
+    /// Alice's home and a neutral planet that is not its neighbor, colonized for her, both
+    /// with a jump gate.
+    fn gated_pair(state: &mut GameState) -> (PlanetId, PlanetId) {
+        let home = test_support::home_planet(state, "alice");
+        let graph = state.map.graph();
+        let far = state.map.planets.values()
+            .filter(|planet| planet.get_owner().is_none())
+            .find(|planet| planet.id != home && !graph.are_adjacent(&home, &planet.id))
+            .map(|planet| planet.id.clone())
+            .expect("Fixture map must have a neutral planet beyond the home neighbors");
+
+        let planet = state.map.planets.get_mut(&far).unwrap();
+        planet.set_owner(String::from("alice"));
+        planet.colonize(&state.structure_config, state.turn).unwrap();
+        state.players.get_mut("alice").unwrap().planets.push(far.clone());
+
+        build_gate(state, &home);
+        build_gate(state, &far);
+        (home, far)
+    }
+
+    fn rendered(state: &GameState, style: RenderStyle) -> String {
+        let presence = state.planet_presence(&String::from("alice"));
+        state.map.render_full(&state.players, &presence, Map::DEFAULT_LABEL_ID_CHARS, style)
+    }
+
+    #[test]
+    fn test_linking_gates_shortens_the_path() {
+        let mut state = fixture_game(1);
+        let (home, far) = gated_pair(&mut state);
+        assert!(!rendered(&state, RenderStyle::Unicode).contains('·'));
+
+        let events = run(&mut state, &format!("planet link {} {}", home, far)).unwrap();
+
+        assert!(matches!(&events[..], [GameEvent::GatesLinked { distance: 1, .. }]));
+        let graph = state.map.graph();
+        assert_eq!(graph.distance(&home, &far), Some(1));
+        assert_eq!(graph.check_consistency(), Ok(()));
+        assert_eq!(state.map.planets[&home].gate_partner(), Some(&far));
+        assert_eq!(state.map.planets[&far].gate_partner(), Some(&home));
+
+        assert!(rendered(&state, RenderStyle::Unicode).contains('·'));
+        let ascii = rendered(&state, RenderStyle::AsciiOnly);
+        assert!(ascii.contains('.') && ascii.is_ascii());
+        let list = state.map.render_list(&state.players, &state.planet_presence(&String::from("alice")));
+        assert!(list.contains(&format!("-> {} (1, gate)", state.map.planets[&far].name)), "{list}");
+    }
+
+    #[test]
+    fn test_link_needs_two_free_gates_on_distant_planets() {
+        let mut state = fixture_game(1);
+        let (home, far) = gated_pair(&mut state);
+        let neighbor = state.map.planets[&home].get_connections()[0].to().clone();
+        let reason = |state: &GameState, input: &str| match parser::parse(input).unwrap().execute(state) {
+            Err(CommandError::InvalidArgument { reason, .. }) => reason,
+            Err(error) => panic!("Expected an invalid argument, got {}", error),
+            Ok(_) => panic!("Expected {input} to be rejected"),
+        };
+
+        assert!(reason(&state, &format!("planet link {} {}", home, home)).contains("itself"));
+
+        let planet = state.map.planets.get_mut(&neighbor).unwrap();
+        planet.set_owner(String::from("alice"));
+        planet.colonize(&state.structure_config, state.turn).unwrap();
+        assert!(reason(&state, &format!("planet link {} {}", home, neighbor)).contains("no operational jump gate"));
+        build_gate(&mut state, &neighbor);
+        assert!(reason(&state, &format!("planet link {} {}", home, neighbor)).contains("already connected"));
+
+        run(&mut state, &format!("planet link {} {}", home, far)).unwrap();
+        assert!(reason(&state, &format!("planet link {} {}", neighbor, far)).contains("already linked"));
+    }
+
+    #[test]
+    fn test_downed_gate_collapses_the_link() {
+        let mut state = fixture_game(1);
+        let (home, far) = gated_pair(&mut state);
+        let distance_before = state.map.graph().distance(&home, &far);
+        run(&mut state, &format!("planet link {} {}", home, far)).unwrap();
+
+        state.map.planets.get_mut(&far).unwrap()
+            .get_structure_mut(&String::from(JUMP_GATE)).unwrap()
+            .state = StructureState::Damaged;
+        let events = end_round(&mut state);
+
+        assert!(events.iter().any(|event| matches!(event, GameEvent::GateLinkCollapsed { reason, .. } if reason.contains("is down"))));
+        assert_eq!(state.map.graph().distance(&home, &far), distance_before);
+        assert!(state.map.planets.values().flat_map(|planet| planet.get_connections()).all(|connection| connection.kind() == ConnectionKind::Lane));
+        assert_eq!(state.map.planets[&home].gate_partner(), None);
+    }
+
+    #[test]
+    fn test_conquered_gate_planet_collapses_the_link() {
+        let mut state = fixture_game(1);
+        let (home, far) = gated_pair(&mut state);
+        run(&mut state, &format!("planet link {} {}", home, far)).unwrap();
+
+        let bob = state.players.get_mut("bob").unwrap();
+        let fleet_id = bob.next_fleet_id();
+        let mut fleet = Fleet::new(fleet_id.clone(), String::from("Landing"), far.clone());
+        for ship_type in ["ark", "interceptor"] {
+            let ship_id = bob.add_ship(ship_type.to_string(), far.clone());
+            bob.ships.get_mut(&ship_id).unwrap().fleet_id = Some(fleet_id.clone());
+            fleet.add_ship(ship_id);
+        }
+        bob.fleets.insert(fleet_id.clone(), fleet);
+        let events = state.apply_effect(
+            CommandEffect::ColonizePlanet { fleet_id, planet_id: far.clone() },
+            &String::from("bob"),
+        ).unwrap();
+
+        assert!(events.iter().any(|event| matches!(event, GameEvent::GateLinkCollapsed { reason, .. } if reason.contains("owner"))));
+        assert!(!state.map.graph().are_adjacent(&home, &far));
+        assert_eq!(state.map.graph().check_consistency(), Ok(()));
+    }
+}
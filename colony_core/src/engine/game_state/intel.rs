@@ -0,0 +1,171 @@
+use crate::engine::map::Fogged;
+use crate::engine::planet::{PlanetId, Ruin};
+use crate::engine::player::PlayerId;
+
+use super::GameState;
+
+/// Summary of a planet as a player last observed it. Kept small on purpose: no structure
+/// or resource details, just what a passing fleet would report.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PlanetIntel {
+    pub last_seen_turn: u32,
+    pub owner_at_time: Option<PlayerId>,
+    pub shield_seen: u32,
+    pub structure_count: usize,
+    #[serde(default)]
+    pub ruin_seen: Option<Ruin>,
+}
+
+impl GameState {
+    /// Records what every player currently sees, replacing older intel on those planets.
+    pub(crate) fn refresh_intel(&mut self) {
+        let player_ids: Vec<_> = self.players.keys().cloned().collect();
+        for player_id in player_ids {
+            let visible = self.visible_planets(&player_id);
+            let observed: Vec<_> = visible.into_iter()
+                .filter_map(|planet_id| self.map.planets.get(&planet_id))
+                .map(|planet| (planet.id.clone(), PlanetIntel {
+                    last_seen_turn: self.turn,
+                    owner_at_time: planet.get_owner().clone(),
+                    shield_seen: planet.get_shield_hp(),
+                    structure_count: planet.get_structures().len(),
+                    ruin_seen: planet.get_ruin().cloned(),
+                }))
+                .collect();
+
+            let player = self.players.get_mut(&player_id)
+                .expect("Player ids come from the players map");
+            player.planet_intel.extend(observed);
+        }
+    }
+
+    /// How a planet outside `viewer`'s sensor range appears to them; None when it is in range
+    /// and live data can be shown, as it always is while a replay is viewed.
+    pub fn fogged_view(&self, viewer: &PlayerId, planet_id: &PlanetId) -> Option<Fogged> {
+        if self.omniscient || self.visible_planets(viewer).contains(planet_id) {
+            return None;
+        }
+        let intel = self.players.get(viewer)
+            .and_then(|player| player.planet_intel.get(planet_id));
+        Some(match intel {
+            Some(intel) => Fogged::LastSeen { turn: intel.last_seen_turn, owner: intel.owner_at_time.clone() },
+            None => Fogged::Unexplored,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::command::CommandEffect;
+    use crate::engine::commands::parser;
+    use crate::engine::commands::status::{self, StatusArgs, StatusTarget};
+    use crate::engine::save;
+    use crate::test_support::{self, fixture_game};
+
+    fn end_turn(state: &mut GameState) {
+        let player_id = state.current_player().clone();
+        let player_name = state.players[&player_id].name.clone();
+        state.apply_effect(CommandEffect::EndTurn { player_name }, &player_id).unwrap();
+    }
+
+    fn status(state: &GameState, target: StatusTarget) -> String {
+        match status::execute(StatusArgs { target, now: None }, state).unwrap() {
+            CommandEffect::None { message } => message,
+            _ => panic!("Status must only produce a message"),
+        }
+    }
+
+    /// Alice scouts bob's home on turn 1, her scout is lost, then bob builds a power grid there.
+    fn scouted_then_lost() -> (GameState, PlanetId, usize) {
+        let mut state = fixture_game(1);
+        let bob_home = test_support::home_planet(&state, "bob");
+        state.players.get_mut("alice").unwrap().add_ship(String::from("scout"), bob_home.clone());
+        state.refresh_intel();
+        let seen_structures = state.map.planets[&bob_home].get_structures().len();
+
+        state.players.get_mut("alice").unwrap().ships.clear();
+        end_turn(&mut state);
+        let bob = String::from("bob");
+        state.apply_effect(
+            CommandEffect::BuildStructure { planet_id: bob_home.clone(), structure_id: String::from("power_grid") },
+            &bob,
+        ).unwrap();
+        end_turn(&mut state);
+
+        assert_eq!(state.turn, 2);
+        assert_eq!(state.map.planets[&bob_home].get_structures().len(), seen_structures + 1);
+        (state, bob_home, seen_structures)
+    }
+
+    #[test]
+    fn test_stale_intel_is_shown_after_losing_sight() {
+        let (state, bob_home, seen_structures) = scouted_then_lost();
+        assert_eq!(state.current_player(), "alice");
+
+        let detail = status(&state, StatusTarget::Planet { id: bob_home.clone() });
+        assert!(detail.contains("last seen on turn 1"), "{detail}");
+        assert!(detail.contains(&format!("Structures: {} (as of turn 1)", seen_structures)), "{detail}");
+        assert!(!detail.contains("RESOURCES"), "{detail}");
+
+        let planets = status(&state, StatusTarget::Planets);
+        assert!(planets.contains(&format!("({}) - Bob [BO] (as of turn 1)", bob_home)), "{planets}");
+
+        let list = state.map.render_list(&state.players, &state.planet_presence(&String::from("alice")));
+        assert!(list.contains(&format!("{} (Bob) (as of turn 1)", bob_home)), "{list}");
+    }
+
+    #[test]
+    fn test_unexplored_planets_show_no_owner() {
+        let state = fixture_game(1);
+        let bob_home = test_support::home_planet(&state, "bob");
+
+        let detail = status(&state, StatusTarget::Planet { id: bob_home.clone() });
+        assert!(detail.contains("Unexplored"), "{detail}");
+
+        let planets = status(&state, StatusTarget::Planets);
+        assert!(planets.contains(&format!("({}) - unexplored", bob_home)), "{planets}");
+    }
+
+    #[test]
+    fn test_commands_cannot_tell_unseen_planets_from_no_planet_at_all() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        state.map.planets.get_mut(&home).unwrap()
+            .complete_build_structure(String::from("orbital_shipyard"), &state.structure_config).unwrap();
+        let bob_home = test_support::home_planet(&state, "bob");
+        let neutral = state.map.planets.values()
+            .find(|planet| planet.get_owner().is_none() && state.fogged_view(&String::from("alice"), &planet.id).is_some())
+            .unwrap().id.clone();
+        let commands = [
+            "build {} power_grid", "build_ship {} interceptor", "upgrade {} planetary_capital", "cancel {}",
+            "defense {}", "forecast {}", "planet allocate {} 100 0 0", "planet rally {} fleet_1",
+        ];
+        let error = |line: String, name: &str| {
+            let Err(error) = parser::parse(&line).unwrap().execute(&state) else {
+                panic!("{line} must be rejected")
+            };
+            error.to_string().replace(name, "<planet>")
+        };
+
+        for command in commands.into_iter().chain([&*format!("build_ship {} interceptor deliver_to {{}}", home)]) {
+            let missing = error(command.replace("{}", "nowhere"), "nowhere");
+            assert_eq!(missing, "None of your planets matches '<planet>'", "{command}");
+            for planet_id in [&bob_home, &neutral] {
+                assert_eq!(error(command.replace("{}", planet_id), planet_id), missing, "{command}");
+            }
+        }
+        let status = parser::parse("status planet nowhere").unwrap().execute(&state);
+        assert!(matches!(status, Err(error) if error.to_string() == "No charted planet matches 'nowhere'"));
+    }
+
+    #[test]
+    fn test_intel_survives_save_and_load() {
+        let (state, bob_home, _) = scouted_then_lost();
+        let saved = save::encode_save(&state, "planet_intel", 0).unwrap();
+        let loaded = save::decode_save(&saved).unwrap();
+
+        assert_eq!(loaded.players["alice"].planet_intel[&bob_home], state.players["alice"].planet_intel[&bob_home]);
+        assert_eq!(loaded.players["alice"].planet_intel[&bob_home].last_seen_turn, 1);
+    }
+}
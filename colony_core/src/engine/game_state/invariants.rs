@@ -0,0 +1,143 @@
+use std::collections::BTreeSet;
+
+use crate::engine::pending_action::ActionType;
+use crate::engine::planet::PlanetId;
+use crate::engine::structure::StructureId;
+
+use super::GameState;
+
+impl GameState {
+    /// Checks that the cross-references the game keeps in several places agree: map
+    /// connections, planet ownership, fleet membership, garrisons, structures under
+    /// construction and the turn rotation.
+    /// Returns one line per violation, empty when the state is consistent.
+    pub fn check_invariants(&self) -> Vec<String> {
+        let mut violations: Vec<String> = self.map.graph().check_consistency()
+            .err()
+            .unwrap_or_default()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        for player_id in &self.players_order {
+            if !self.players.contains_key(player_id) {
+                violations.push(format!("Player {} is in the rotation but does not exist", player_id));
+            }
+        }
+        for player_id in &self.acted_this_round {
+            if !self.players_order.contains(player_id) {
+                violations.push(format!("Player {} acted this round but is not in the rotation", player_id));
+            }
+        }
+
+        for planet in self.map.planets.values() {
+            if let Some(owner) = planet.get_owner()
+                && !self.players.get(owner).is_some_and(|player| player.planets.contains(&planet.id))
+            {
+                violations.push(format!("Planet {} is owned by {} but missing from their planets", planet.id, owner));
+            }
+            if planet.get_shield_hp() > planet.get_max_shield_hp() + planet.get_shield_overcharge() {
+                violations.push(format!(
+                    "Planet {} has {} shield HP above its maximum of {}",
+                    planet.id, planet.get_shield_hp(), planet.get_max_shield_hp()
+                ));
+            }
+            for ship_id in planet.get_garrison() {
+                let ship = planet.get_owner().as_ref()
+                    .and_then(|owner| self.players.get(owner))
+                    .and_then(|owner| owner.ships.get(ship_id));
+                match ship {
+                    None => violations.push(format!("Garrison of {} lists unknown ship {}", planet.id, ship_id)),
+                    Some(ship) if ship.fleet_id.is_some() || ship.location != planet.id => violations.push(
+                        format!("Garrisoned ship {} on {} is in a fleet or elsewhere", ship_id, planet.id)
+                    ),
+                    Some(_) => {}
+                }
+            }
+        }
+
+        // Every structure under construction stands for a queued or held build of its owner
+        let mut construction_sites: BTreeSet<(&PlanetId, &StructureId)> = self.map.planets.values()
+            .flat_map(|planet| planet.get_structures().iter()
+                .filter(|(_, structure)| structure.is_under_construction())
+                .map(|(structure_id, _)| (&planet.id, structure_id)))
+            .collect();
+        for player in self.players.values() {
+            let builds = player.pending_actions.iter().chain(player.held_actions.iter().map(|held| &held.action));
+            for action in builds {
+                if let ActionType::BuildStructure(structure_id) = &action.action_type
+                    && !construction_sites.remove(&(&action.planet_id, structure_id))
+                {
+                    violations.push(format!(
+                        "Build of {} on {} by {} has no structure under construction", structure_id, action.planet_id, player.id
+                    ));
+                }
+            }
+        }
+        for (planet_id, structure_id) in construction_sites {
+            violations.push(format!("Structure {} on {} is under construction without a build", structure_id, planet_id));
+        }
+
+        for player in self.players.values() {
+            for planet_id in &player.planets {
+                let owner = self.map.planets.get(planet_id).and_then(|planet| planet.get_owner().as_ref());
+                if owner != Some(&player.id) {
+                    violations.push(format!("{} lists planet {} it does not own", player.id, planet_id));
+                }
+            }
+
+            for ship in player.ships.values() {
+                if let Some(fleet_id) = &ship.fleet_id
+                    && !player.fleets.get(fleet_id).is_some_and(|fleet| fleet.ships.contains(&ship.id))
+                {
+                    violations.push(format!("Ship {} of {} points to fleet {} which does not list it", ship.id, player.id, fleet_id));
+                }
+            }
+
+            for fleet in player.fleets.values() {
+                for ship_id in &fleet.ships {
+                    match player.ships.get(ship_id) {
+                        None => violations.push(format!("Fleet {} of {} lists unknown ship {}", fleet.id, player.id, ship_id)),
+                        Some(ship) if ship.fleet_id.as_ref() != Some(&fleet.id) => violations.push(
+                            format!("Fleet {} of {} lists ship {} which belongs elsewhere", fleet.id, player.id, ship_id)
+                        ),
+                        Some(ship) if ship.location != fleet.location => violations.push(format!(
+                            "Ship {} is at {} but its fleet {} of {} is at {}",
+                            ship_id, ship.location, fleet.id, player.id, fleet.location
+                        )),
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::fleet::Fleet;
+    use crate::test_support::{self, fixture_game};
+
+    #[test]
+    fn test_fixture_game_is_consistent() {
+        assert!(fixture_game(1).check_invariants().is_empty());
+    }
+
+    #[test]
+    fn test_broken_fleet_membership_is_reported() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let alice = state.players.get_mut("alice").unwrap();
+        let ship_id = alice.add_ship(String::from("interceptor"), home.clone());
+        let mut fleet = Fleet::new(String::from("fleet_1"), String::from("Guard"), home);
+        fleet.add_ship(ship_id.clone());
+        alice.fleets.insert(String::from("fleet_1"), fleet);
+
+        assert_eq!(
+            state.check_invariants(),
+            [format!("Fleet fleet_1 of alice lists ship {} which belongs elsewhere", ship_id)]
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use crate::engine::planet::PlanetId;
+use crate::engine::player::PlayerId;
+use crate::engine::resources::Resources;
+
+use super::GameState;
+
+/// Fewest turns any construction takes once every modifier is applied.
+pub const MIN_BUILD_TURNS: u32 = 1;
+
+/// Kind of construction a modifier is asked about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstructionKind {
+    StructureBuild,
+    StructureUpgrade,
+    ShipBuild,
+    ShipRetrofit,
+}
+
+impl ConstructionKind {
+    fn is_ship(self) -> bool {
+        matches!(self, ConstructionKind::ShipBuild | ConstructionKind::ShipRetrofit)
+    }
+}
+
+/// Who is building what, and where.
+#[derive(Debug, Clone, Copy)]
+pub struct ModifierContext<'a> {
+    pub player_id: &'a PlayerId,
+    pub planet_id: &'a PlanetId,
+    pub kind: ConstructionKind,
+}
+
+/// Where a contribution comes from. Within a step, contributions apply in this order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ModifierSource {
+    GameSpeed,
+    EmpireEffects,
+}
+
+/// What a contribution changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModifierTarget {
+    BuildTime,
+    Cost,
+}
+
+/// How a contribution changes its target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Adjustment {
+    /// Scales the value to this percentage, rounded half up
+    Percent(u32),
+    /// Adds to the value, or subtracts when negative. For costs, it applies to every resource
+    /// the base cost asks for, so it never makes a free resource cost something.
+    Flat(i64),
+}
+
+/// One source's change to a build time or cost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contribution {
+    pub source: ModifierSource,
+    pub target: ModifierTarget,
+    pub adjustment: Adjustment,
+}
+
+/// Every contribution that applies to one construction. Resolving a value applies them in a
+/// fixed order, whatever order they were added in:
+///
+/// 1. percentages, in [`ModifierSource`] order, each rounded half up;
+/// 2. flat amounts, in [`ModifierSource`] order;
+/// 3. clamps: build times take at least [`MIN_BUILD_TURNS`], costs never go below zero.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Modifiers {
+    contributions: Vec<Contribution>,
+}
+
+impl Modifiers {
+    pub fn add(&mut self, source: ModifierSource, target: ModifierTarget, adjustment: Adjustment) {
+        self.contributions.push(Contribution { source, target, adjustment });
+    }
+
+    pub fn contributions(&self) -> &[Contribution] {
+        &self.contributions
+    }
+
+    /// Turns a construction configured to take `base_turns` takes.
+    pub fn build_time(&self, base_turns: u32) -> u32 {
+        let turns = self.resolve(ModifierTarget::BuildTime, u64::from(base_turns));
+        u32::try_from(turns).unwrap_or(u32::MAX).max(MIN_BUILD_TURNS)
+    }
+
+    /// What a construction configured to cost `base` costs.
+    pub fn cost(&self, base: &Resources) -> Resources {
+        let resolve = |amount: u32| match amount {
+            0 => 0,
+            amount => u32::try_from(self.resolve(ModifierTarget::Cost, u64::from(amount))).unwrap_or(u32::MAX),
+        };
+        Resources {
+            minerals: resolve(base.minerals),
+            gas: resolve(base.gas),
+            energy: resolve(base.energy),
+        }
+    }
+
+    fn resolve(&self, target: ModifierTarget, base: u64) -> u64 {
+        let mut steps: Vec<_> = self.contributions.iter()
+            .filter(|contribution| contribution.target == target)
+            .collect();
+        steps.sort_by_key(|contribution| (matches!(contribution.adjustment, Adjustment::Flat(_)), contribution.source));
+
+        steps.into_iter().fold(base, |value, contribution| match contribution.adjustment {
+            Adjustment::Percent(percent) => (value.saturating_mul(u64::from(percent)) + 50) / 100,
+            Adjustment::Flat(amount) if amount < 0 => value.saturating_sub(amount.unsigned_abs()),
+            Adjustment::Flat(amount) => value.saturating_add(amount.unsigned_abs()),
+        })
+    }
+}
+
+impl GameState {
+    /// Collects the contributions of every modifier source to a construction.
+    pub fn modifiers(&self, context: &ModifierContext) -> Modifiers {
+        let mut modifiers = Modifiers::default();
+
+        let speed_percent = self.rules.speed.build_time_percent();
+        if speed_percent != 100 {
+            modifiers.add(ModifierSource::GameSpeed, ModifierTarget::BuildTime, Adjustment::Percent(speed_percent));
+        }
+
+        let reduction = self.empire_effects(context.player_id).ship_build_time_reduction_percent;
+        if context.kind.is_ship() && reduction > 0 {
+            modifiers.add(ModifierSource::EmpireEffects, ModifierTarget::BuildTime, Adjustment::Percent(100 - reduction));
+        }
+
+        modifiers
+    }
+
+    /// Turns a construction configured to take `base_turns` takes with every modifier applied.
+    pub fn resolve_build_time(&self, base_turns: u32, context: &ModifierContext) -> u32 {
+        self.modifiers(context).build_time(base_turns)
+    }
+
+    /// What a construction configured to cost `base` costs with every modifier applied.
+    pub fn resolve_cost(&self, base: &Resources, context: &ModifierContext) -> Resources {
+        self.modifiers(context).cost(base)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::game_rules::GameSpeed;
+    use crate::engine::game_state::EmpireEffects;
+    use crate::test_support::{self, fixture_game};
+
+    use Adjustment::{Flat, Percent};
+    use ModifierSource::{EmpireEffects as Empire, GameSpeed as Speed};
+    use ModifierTarget::{BuildTime, Cost};
+
+    fn modifiers(contributions: &[(ModifierSource, ModifierTarget, Adjustment)]) -> Modifiers {
+        let mut modifiers = Modifiers::default();
+        for (source, target, adjustment) in contributions {
+            modifiers.add(*source, *target, *adjustment);
+        }
+        modifiers
+    }
+
+    fn resources(minerals: u32, gas: u32, energy: u32) -> Resources {
+        Resources { minerals, gas, energy }
+    }
+
+    #[test]
+    fn test_no_contributions_leave_values_alone() {
+        let none = Modifiers::default();
+
+        assert_eq!(none.build_time(3), 3);
+        assert_eq!(none.cost(&resources(100, 50, 0)), resources(100, 50, 0));
+    }
+
+    #[test]
+    fn test_percentages_round_half_up_one_step_at_a_time() {
+        let half = modifiers(&[(Speed, BuildTime, Percent(50))]);
+        let halves = [1, 2, 3, 4, 5].map(|turns| half.build_time(turns));
+        assert_eq!(halves, [1, 1, 2, 2, 3]);
+
+        // 3 -> 2 at half speed, then 75% of 2 rounds back up to 2; one combined step would give 1
+        let stacked = modifiers(&[(Speed, BuildTime, Percent(50)), (Empire, BuildTime, Percent(75))]);
+        assert_eq!(stacked.build_time(3), 2);
+    }
+
+    #[test]
+    fn test_percentages_apply_before_flat_amounts_whatever_the_insertion_order() {
+        let flat_first = modifiers(&[(Empire, BuildTime, Flat(2)), (Speed, BuildTime, Percent(200))]);
+        let percent_first = modifiers(&[(Speed, BuildTime, Percent(200)), (Empire, BuildTime, Flat(2))]);
+
+        // (4 * 200%) + 2, not (4 + 2) * 200%
+        assert_eq!(flat_first.build_time(4), 10);
+        assert_eq!(percent_first.build_time(4), 10);
+    }
+
+    #[test]
+    fn test_percentages_apply_in_source_order() {
+        // Rounding makes the order visible: speed first gives 3 -> 2 -> 2, empire first 3 -> 2 -> 1
+        let contributions = [(Empire, BuildTime, Percent(75)), (Speed, BuildTime, Percent(50))];
+
+        assert_eq!(modifiers(&contributions).build_time(3), 2);
+    }
+
+    #[test]
+    fn test_build_times_clamp_to_the_minimum() {
+        let rush = modifiers(&[(Empire, BuildTime, Percent(0))]);
+        let cut = modifiers(&[(Speed, BuildTime, Flat(-10))]);
+
+        assert_eq!(rush.build_time(8), MIN_BUILD_TURNS);
+        assert_eq!(cut.build_time(3), MIN_BUILD_TURNS);
+        assert_eq!(Modifiers::default().build_time(0), MIN_BUILD_TURNS);
+    }
+
+    #[test]
+    fn test_costs_clamp_at_zero_and_keep_free_resources_free() {
+        let discount = modifiers(&[(Empire, Cost, Flat(-80)), (Speed, Cost, Percent(50))]);
+        let surcharge = modifiers(&[(Speed, Cost, Flat(25))]);
+
+        // 50% first: 200 -> 100 -> 20, 100 -> 50 -> 0
+        assert_eq!(discount.cost(&resources(200, 100, 0)), resources(20, 0, 0));
+        assert_eq!(surcharge.cost(&resources(200, 0, 0)), resources(225, 0, 0));
+    }
+
+    #[test]
+    fn test_targets_do_not_leak() {
+        let cheaper = modifiers(&[(Speed, Cost, Percent(50))]);
+        let slower = modifiers(&[(Speed, BuildTime, Percent(200))]);
+
+        assert_eq!(cheaper.build_time(4), 4);
+        assert_eq!(slower.cost(&resources(100, 0, 0)), resources(100, 0, 0));
+    }
+
+    #[test]
+    fn test_huge_values_saturate() {
+        let double = modifiers(&[(Speed, BuildTime, Percent(200)), (Empire, BuildTime, Flat(i64::MAX))]);
+
+        assert_eq!(double.build_time(u32::MAX), u32::MAX);
+        assert_eq!(double.cost(&resources(u32::MAX, 0, 0)), resources(u32::MAX, 0, 0));
+    }
+
+    #[test]
+    fn test_game_state_sources_match_speed_and_empire_effects() {
+        let mut state = fixture_game(1);
+        let alice = String::from("alice");
+        let home = test_support::home_planet(&state, "alice");
+        let context = |kind| ModifierContext { player_id: &alice, planet_id: &home, kind };
+        assert!(state.modifiers(&context(ConstructionKind::ShipBuild)).contributions().is_empty());
+
+        state.rules.speed = GameSpeed::Quick;
+        state.players.get_mut(&alice).unwrap().empire_effects =
+            Some(EmpireEffects { production_bonus_percent: 0, ship_build_time_reduction_percent: 25 });
+
+        for base in 0..12 {
+            let expected = EmpireEffects { production_bonus_percent: 0, ship_build_time_reduction_percent: 25 }
+                .ship_build_time(GameSpeed::Quick.scale_turns(base));
+            assert_eq!(state.resolve_build_time(base, &context(ConstructionKind::ShipBuild)), expected);
+            assert_eq!(state.resolve_build_time(base, &context(ConstructionKind::ShipRetrofit)), expected);
+            assert_eq!(
+                state.resolve_build_time(base, &context(ConstructionKind::StructureUpgrade)),
+                GameSpeed::Quick.scale_turns(base),
+            );
+        }
+        assert_eq!(
+            state.resolve_cost(&resources(100, 50, 0), &context(ConstructionKind::StructureBuild)),
+            resources(100, 50, 0),
+        );
+    }
+}
@@ -0,0 +1,299 @@
+use crate::engine::game_event::GameEvent;
+use crate::engine::pending_action::{ActionType, PendingAction};
+use crate::engine::planet::PlanetId;
+use crate::engine::player::PlayerId;
+use crate::engine::resources::Resources;
+use crate::engine::ship::{FleetId, ShipInstanceId};
+
+use super::GameState;
+
+/// Something a player's pending actions can depend on and the player can lose.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum Lost<'a> {
+    /// The planet changed hands; constructions on it, and ships on their way to it, can no
+    /// longer finish for the player
+    Planet(&'a PlanetId),
+    /// The fleet was disbanded, merged away or destroyed; its moves and bombardments go nowhere
+    Fleet(&'a FleetId),
+    /// The ship was destroyed; its own move goes nowhere
+    Ship(&'a ShipInstanceId),
+}
+
+impl Lost<'_> {
+    fn orphans(&self, action: &PendingAction) -> bool {
+        match self {
+            Lost::Planet(planet_id) => {
+                construction_planet(action) == Some(*planet_id) || action.deliver_to.as_ref() == Some(*planet_id)
+            }
+            Lost::Fleet(fleet_id) => action_fleet(action) == Some(*fleet_id),
+            Lost::Ship(ship_id) => moving_ship(action) == Some(*ship_id),
+        }
+    }
+}
+
+/// Planet a construction happens on. Moves and bombardments depend on their fleet or ship instead.
+fn construction_planet(action: &PendingAction) -> Option<&PlanetId> {
+    match action.action_type {
+        ActionType::BuildStructure(_)
+        | ActionType::UpgradeStructure(_)
+        | ActionType::BuildShip(_, _)
+        | ActionType::RetrofitShip(_, _) => Some(&action.planet_id),
+        ActionType::MoveFleet(_, _) | ActionType::MoveShip(_, _) | ActionType::BombardPlanet(_, _) => None,
+    }
+}
+
+fn action_fleet(action: &PendingAction) -> Option<&FleetId> {
+    match &action.action_type {
+        ActionType::MoveFleet(fleet_id, _) | ActionType::BombardPlanet(fleet_id, _) => Some(fleet_id),
+        _ => None,
+    }
+}
+
+fn moving_ship(action: &PendingAction) -> Option<&ShipInstanceId> {
+    match &action.action_type {
+        ActionType::MoveShip(ship_id, _) => Some(ship_id),
+        _ => None,
+    }
+}
+
+impl GameState {
+    /// Cancels every pending and held action of `player_id` that depends on something they
+    /// lost, with an event for each, and takes down the structures they had under construction.
+    /// Resources reserved for construction were paid from the lost planet's stock and are gone,
+    /// and moves and bombardments reserve none. Only a delivered ship build, one of whose two
+    /// planets is still the player's, is refunded there as if it had been cancelled.
+    pub(super) fn cancel_orphaned_actions(&mut self, player_id: &PlayerId, lost: Lost) -> Vec<GameEvent> {
+        let reason = match lost {
+            Lost::Planet(planet_id) => format!("planet {} is no longer yours", self.planet_name(planet_id)),
+            Lost::Fleet(fleet_id) => format!("fleet {} no longer exists", fleet_id),
+            Lost::Ship(ship_id) => format!("ship {} no longer exists", ship_id),
+        };
+        let Some(player) = self.players.get_mut(player_id) else {
+            return Vec::new();
+        };
+
+        let mut cancelled = Vec::new();
+        player.pending_actions.retain(|action| {
+            let orphaned = lost.orphans(action);
+            if orphaned {
+                cancelled.push(action.clone());
+            }
+            !orphaned
+        });
+        player.held_actions.retain(|held| {
+            let orphaned = lost.orphans(&held.action);
+            if orphaned {
+                cancelled.push(held.action.clone());
+            }
+            !orphaned
+        });
+
+        for action in &cancelled {
+            if let ActionType::BuildStructure(structure_id) = &action.action_type
+                && let Some(planet) = self.map.planets.get_mut(&action.planet_id)
+            {
+                planet.clear_construction_site(structure_id);
+            }
+        }
+
+        cancelled.iter()
+            .map(|action| GameEvent::ActionOrphaned {
+                player_id: player_id.clone(),
+                action: self.describe_action(action),
+                reason: reason.clone(),
+                refund: self.refund_delivery(player_id, action),
+            })
+            .collect()
+    }
+
+    /// Refunds a cancelled delivered ship build, less the cancellation penalty and what does not
+    /// fit in storage, to whichever of its two planets `player_id` still owns. Returns that
+    /// planet's name and the resources it got back.
+    fn refund_delivery(&mut self, player_id: &PlayerId, action: &PendingAction) -> Option<(String, Resources)> {
+        let delivery = action.deliver_to.as_ref()?;
+        let penalty_percent = self.rules_profile().cancellation_penalty_percent;
+        let kept = [&action.planet_id, delivery].into_iter()
+            .find(|planet_id| self.map.planets.get(*planet_id).is_some_and(|planet| planet.get_owner().as_ref() == Some(player_id)))?;
+        let planet = self.map.planets.get_mut(kept)?;
+
+        let refund = action.reserved_resources.clone() - action.reserved_resources.percentage(penalty_percent);
+        let space_available = planet.storage_capacity.clone() - planet.available_resources.clone();
+        let refunded = refund.capped_at(&space_available);
+        planet.available_resources += &refunded;
+        Some((planet.name.clone(), refunded))
+    }
+
+    /// Why a completed action of `player_id` can no longer be carried out, if it cannot. A
+    /// guard for completion: cleanup on ownership changes and disbands should already have
+    /// cancelled such actions.
+    pub(super) fn orphan_reason(&self, player_id: &PlayerId, action: &PendingAction) -> Option<String> {
+        for planet_id in construction_planet(action).into_iter().chain(action.deliver_to.as_ref()) {
+            let Some(planet) = self.map.planets.get(planet_id) else {
+                return Some(format!("planet {} no longer exists", planet_id));
+            };
+            if planet.get_owner().as_ref() != Some(player_id) {
+                return Some(format!("planet {} is no longer yours", planet.name));
+            }
+        }
+        if let Some(fleet_id) = action_fleet(action)
+            && !self.players.get(player_id).is_some_and(|player| player.fleets.contains_key(fleet_id))
+        {
+            return Some(format!("fleet {} no longer exists", fleet_id));
+        }
+        if let Some(ship_id) = moving_ship(action)
+            && !self.players.get(player_id).is_some_and(|player| player.ships.contains_key(ship_id))
+        {
+            return Some(format!("ship {} no longer exists", ship_id));
+        }
+        None
+    }
+
+    /// Short description of an action for players, such as "build of power_grid on Kepler".
+    pub(super) fn describe_action(&self, action: &PendingAction) -> String {
+        let planet_name = self.planet_name(&action.planet_id);
+        match &action.action_type {
+            ActionType::BuildStructure(structure_id) => format!("build of {} on {}", structure_id, planet_name),
+            ActionType::UpgradeStructure(structure_id) => format!("upgrade of {} on {}", structure_id, planet_name),
+            ActionType::BuildShip(ship_type, ship_id) => match &action.deliver_to {
+                Some(destination) => format!(
+                    "build of {} ({}) on {} for delivery to {}", ship_id, ship_type, planet_name, self.planet_name(destination)
+                ),
+                None => format!("build of {} ({}) on {}", ship_id, ship_type, planet_name),
+            },
+            ActionType::RetrofitShip(ship_type, ship_id) => {
+                format!("retrofit of {} to {} on {}", ship_id, ship_type, planet_name)
+            }
+            ActionType::MoveFleet(fleet_id, destination) => {
+                format!("move of fleet {} to {}", fleet_id, self.planet_name(destination))
+            }
+            ActionType::MoveShip(ship_id, destination) => {
+                format!("move of ship {} to {}", ship_id, self.planet_name(destination))
+            }
+            ActionType::BombardPlanet(fleet_id, target) => {
+                format!("bombardment of {} by fleet {}", self.planet_name(target), fleet_id)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::commands::command::CommandEffect;
+    use crate::engine::game_event::GameEvent;
+    use crate::engine::pending_action::{ActionType, PendingAction};
+    use crate::engine::resources::Resources;
+    use crate::test_support::{self, end_round, fixture_game};
+
+    fn cancellations(events: &[GameEvent]) -> Vec<(&str, &str)> {
+        events.iter()
+            .filter_map(|event| match event {
+                GameEvent::ActionOrphaned { action, reason, .. } => Some((action.as_str(), reason.as_str())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_capture_during_construction_cancels_the_losers_builds() {
+        let mut state = fixture_game(1);
+        let (alice, bob) = (String::from("alice"), String::from("bob"));
+        let bob_home = test_support::home_planet(&state, "bob");
+        let planet = state.map.planets.get_mut(&bob_home).unwrap();
+        planet.available_resources = Resources { minerals: 5000, gas: 5000, energy: 5000 };
+        planet.complete_build_structure(String::from("orbital_shipyard"), &test_support::fixture_structure_config())
+            .unwrap();
+        let build = CommandEffect::BuildStructure { planet_id: bob_home.clone(), structure_id: String::from("power_grid") };
+        state.apply_effect(build, &bob).unwrap();
+        let ship = CommandEffect::BuildShip { planet_id: bob_home.clone(), ship_id: String::from("interceptor"), deliver_to: None };
+        state.apply_effect(ship, &bob).unwrap();
+
+        let events = state.set_planet_owner(&bob_home, &alice);
+
+        assert!(state.players[&bob].pending_actions.is_empty());
+        let cancelled = cancellations(&events);
+        assert_eq!(cancelled.len(), 2);
+        assert!(cancelled[0].0.starts_with("build of power_grid on "));
+        assert!(cancelled.iter().all(|(_, reason)| reason.ends_with("is no longer yours")));
+        assert!(events.iter().all(|event| matches!(event, GameEvent::ActionOrphaned { player_id, .. } if *player_id == bob)));
+    }
+
+    #[test]
+    fn test_losing_either_end_of_a_delivery_refunds_the_other() {
+        for lose_shipyard in [false, true] {
+            let mut state = fixture_game(1);
+            let (alice, bob) = (String::from("alice"), String::from("bob"));
+            let home = test_support::home_planet(&state, "alice");
+            let colony = test_support::grant_planet(&mut state, "alice");
+            let planet = state.map.planets.get_mut(&home).unwrap();
+            planet.complete_build_structure(String::from("orbital_shipyard"), &test_support::fixture_structure_config())
+                .unwrap();
+            let ship = CommandEffect::BuildShip {
+                planet_id: home.clone(),
+                ship_id: String::from("interceptor"),
+                deliver_to: Some(colony.clone()),
+            };
+            state.apply_effect(ship, &alice).unwrap();
+            let (lost, kept) = if lose_shipyard { (&home, &colony) } else { (&colony, &home) };
+            // Room in storage for the whole refund
+            state.map.planets.get_mut(kept).unwrap().available_resources = Resources::default();
+
+            let events = state.set_planet_owner(lost, &bob);
+
+            assert!(state.players[&alice].pending_actions.is_empty());
+            let [GameEvent::ActionOrphaned { action, refund: Some((planet_name, refunded)), .. }] = &events[..] else {
+                panic!("Expected the delivery to be cancelled with a refund: {events:?}")
+            };
+            assert!(action.contains("for delivery to"), "{action}");
+            assert_eq!(planet_name, &state.map.planets[kept].name);
+            // The fixture interceptor costs 100 minerals and 50 gas, less the 25% penalty rounded down
+            assert_eq!(*refunded, Resources { minerals: 75, gas: 38, energy: 0 });
+            assert_eq!(&state.map.planets[kept].available_resources, refunded);
+        }
+    }
+
+    #[test]
+    fn test_disband_during_pending_move_cancels_the_move() {
+        let mut state = fixture_game(1);
+        let alice = String::from("alice");
+        let home = test_support::home_planet(&state, "alice");
+        let neighbor = state.map.planets[&home].get_connections()[0].to().clone();
+        let ship_id = state.players.get_mut(&alice).unwrap().add_ship(String::from("interceptor"), home.clone());
+        let create = CommandEffect::CreateFleet { name: String::from("scouts"), ship_ids: vec![ship_id], location: home };
+        state.apply_effect(create, &alice).unwrap();
+        let fleet_id = state.players[&alice].fleets.keys().next().unwrap().clone();
+        let move_order = CommandEffect::MoveFleet { fleet_id: fleet_id.clone(), target_planet: neighbor, distance: 1 };
+        state.apply_effect(move_order, &alice).unwrap();
+
+        let events = state.apply_effect(CommandEffect::DisbandFleet { fleet_id: fleet_id.clone() }, &alice).unwrap();
+
+        assert!(state.players[&alice].pending_actions.is_empty());
+        let cancelled = cancellations(&events);
+        assert_eq!(cancelled.len(), 1);
+        assert!(cancelled[0].0.starts_with("move of fleet"));
+        assert_eq!(cancelled[0].1, format!("fleet {} no longer exists", fleet_id));
+    }
+
+    #[test]
+    fn test_completion_skips_actions_that_slipped_through() {
+        let mut state = fixture_game(1);
+        let alice = String::from("alice");
+        let home = test_support::home_planet(&state, "alice");
+        let bob_home = test_support::home_planet(&state, "bob");
+        let player = state.players.get_mut(&alice).unwrap();
+        let ship_id = player.generate_ship_id(&String::from("interceptor"));
+        player.pending_actions.push(PendingAction::new(
+            ActionType::BuildShip(String::from("interceptor"), ship_id), bob_home, 1, Resources::default(),
+        ));
+        player.pending_actions.push(PendingAction::new(
+            ActionType::MoveFleet(String::from("ghost"), home.clone()), home, 1, Resources::default(),
+        ));
+
+        let events = end_round(&mut state);
+
+        assert!(state.players[&alice].ships.is_empty());
+        let reasons: Vec<_> = cancellations(&events).into_iter().map(|(_, reason)| reason).collect();
+        assert_eq!(reasons.len(), 2);
+        assert!(reasons[0].ends_with("is no longer yours"));
+        assert_eq!(reasons[1], "fleet ghost no longer exists");
+    }
+}
@@ -0,0 +1,177 @@
+use std::fmt;
+
+use crate::engine::player::PlayerId;
+
+use super::GameState;
+
+/// Where the game as a whole stands: players pick their starting planets, give orders, and
+/// once a side has won only look back on the game.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GamePhase {
+    /// The starting planet draft is under way
+    Setup,
+    #[default]
+    Playing,
+    /// A side has won; every view shows the whole galaxy and no more orders are taken
+    Finished,
+}
+
+/// Step of end-of-round processing, or of turn-start production.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessingPhase {
+    Intel,
+    Bombardments,
+    Unrest,
+    Completions,
+    GateLinks,
+    Supply,
+    Production,
+    Victory,
+}
+
+impl fmt::Display for ProcessingPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ProcessingPhase::Intel => "intel",
+            ProcessingPhase::Bombardments => "bombardment",
+            ProcessingPhase::Unrest => "unrest",
+            ProcessingPhase::Completions => "completion",
+            ProcessingPhase::GateLinks => "gate link",
+            ProcessingPhase::Supply => "supply",
+            ProcessingPhase::Production => "production",
+            ProcessingPhase::Victory => "victory",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Who the game state is acting for. While a player gives orders, they are the current player;
+/// processing spans every player, so it names the player it acts for, if any, explicitly.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum TurnPhaseContext {
+    #[default]
+    Orders,
+    Processing { phase: ProcessingPhase, acting_player: Option<PlayerId> },
+}
+
+impl TurnPhaseContext {
+    /// Player whose share of a processing phase is being resolved.
+    pub fn acting_player(&self) -> Option<&PlayerId> {
+        match self {
+            TurnPhaseContext::Orders => None,
+            TurnPhaseContext::Processing { acting_player, .. } => acting_player.as_ref(),
+        }
+    }
+}
+
+impl GameState {
+    pub fn phase(&self) -> &TurnPhaseContext {
+        &self.phase
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.game_phase == GamePhase::Finished
+    }
+
+    /// Ends the game. Nothing is hidden from anyone from here on.
+    pub(super) fn finish(&mut self) {
+        self.game_phase = GamePhase::Finished;
+        self.omniscient = true;
+    }
+
+    /// Starts a processing phase acting for no player in particular.
+    pub(super) fn begin_phase(&mut self, phase: ProcessingPhase) {
+        self.clock_phase_change();
+        self.phase = TurnPhaseContext::Processing { phase, acting_player: None };
+    }
+
+    /// Resolves `player_id`'s share of the current processing phase from here on.
+    pub(super) fn act_for(&mut self, player_id: &PlayerId) {
+        if let TurnPhaseContext::Processing { acting_player, .. } = &mut self.phase {
+            *acting_player = Some(player_id.clone());
+        }
+    }
+
+    /// Hands control back to the player whose turn it is.
+    pub(super) fn end_processing(&mut self) {
+        self.clock_phase_change();
+        self.phase = TurnPhaseContext::Orders;
+    }
+
+    /// Flags a call to `current_player` while processing, where whoever is first in the
+    /// rotation has nothing to do with what is resolved. Debug builds panic so the caller gets
+    /// fixed; release builds carry on and report it through an `EngineWarning` event.
+    pub(super) fn check_current_player_use(&self) {
+        if let TurnPhaseContext::Processing { phase, .. } = &self.phase {
+            let message = format!(
+                "current_player() called during {} processing, which spans every player; pass a player id instead",
+                phase,
+            );
+            if cfg!(debug_assertions) {
+                panic!("{}", message);
+            }
+            self.engine_warnings.borrow_mut().push(message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::command::CommandEffect;
+    #[cfg(not(debug_assertions))]
+    use crate::engine::game_event::GameEvent;
+    use crate::test_support::fixture_game;
+
+    #[test]
+    fn test_processing_hands_control_back_to_the_next_player() {
+        let mut state = fixture_game(1);
+        let end = |name: &str| CommandEffect::EndTurn { player_name: name.to_string() };
+
+        state.apply_effect(end("Alice"), &String::from("alice")).unwrap();
+        state.apply_effect(end("Bob"), &String::from("bob")).unwrap();
+
+        assert_eq!(state.phase(), &TurnPhaseContext::Orders);
+        assert_eq!(state.current_player(), "alice");
+    }
+
+    #[test]
+    fn test_acting_player_is_scoped_to_the_phase() {
+        let mut state = fixture_game(1);
+        state.begin_phase(ProcessingPhase::Completions);
+        state.act_for(&String::from("bob"));
+        assert_eq!(state.phase().acting_player().map(String::as_str), Some("bob"));
+
+        state.begin_phase(ProcessingPhase::GateLinks);
+        assert_eq!(state.phase().acting_player(), None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "current_player() called during completion processing")]
+    fn test_current_player_during_processing_panics_in_debug_builds() {
+        let mut state = fixture_game(1);
+        state.begin_phase(ProcessingPhase::Completions);
+        state.act_for(&String::from("bob"));
+
+        state.current_player();
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn test_current_player_during_processing_reaches_the_frontend_in_release_builds() {
+        let mut state = fixture_game(1);
+        state.begin_phase(ProcessingPhase::Completions);
+        state.current_player();
+        state.end_processing();
+
+        let events = state.apply_effect(CommandEffect::None { message: String::new() }, &String::from("alice")).unwrap();
+
+        assert!(events.iter().any(|event| matches!(event, GameEvent::EngineWarning { message }
+            if message.contains("completion processing"))), "{events:?}");
+        let events = state.apply_effect(CommandEffect::None { message: String::new() }, &String::from("alice")).unwrap();
+        assert!(!events.iter().any(|event| matches!(event, GameEvent::EngineWarning { .. })));
+    }
+}
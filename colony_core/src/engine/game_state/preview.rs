@@ -0,0 +1,208 @@
+use std::fmt;
+
+use crate::engine::configs::ship_config::ShipId;
+use crate::engine::pending_action::{ActionType, PendingAction};
+use crate::engine::planet::{BuildInfo, PlanetError, PlanetId, StorageAdvice, StorageWarning};
+use crate::engine::player::PlayerId;
+use crate::engine::resources::Resources;
+use crate::engine::structure::StructureId;
+
+use super::{ConstructionKind, GameState, GameStateError, ModifierContext};
+
+/// What queueing a construction costs and brings, worked out without changing anything.
+/// Queueing it for real uses the same numbers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstructionPreview {
+    pub cost: Resources,
+    /// Build time at the game's speed
+    pub turns: u32,
+    /// Turn by which the construction is done
+    pub ready_on_turn: u32,
+    /// The planet's resources once the cost is paid
+    pub resources_after: Resources,
+    pub warnings: Vec<StorageWarning>,
+    /// Only structure builds get storage advice
+    pub advice: Vec<StorageAdvice>,
+}
+
+impl fmt::Display for ConstructionPreview {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f, "Preview, nothing queued. Cost: {}. Turns to complete: {} (ready by turn {}). Resources left: {}",
+            self.cost, self.turns, self.ready_on_turn, self.resources_after
+        )?;
+        for warning in &self.warnings {
+            write!(f, "\n  warning: {}; consider a storage complex", warning)?;
+        }
+        for advice in &self.advice {
+            write!(f, "\n  advisory: {}", advice)?;
+        }
+        Ok(())
+    }
+}
+
+impl GameState {
+    /// Previews building `structure_id` on `planet_id`, running every check the build must pass.
+    pub fn preview_structure_build(
+        &self,
+        player_id: &PlayerId,
+        planet_id: &PlanetId,
+        structure_id: &StructureId,
+    ) -> Result<ConstructionPreview, GameStateError> {
+        let build_info = self.check_structure_build(player_id, planet_id, structure_id)?;
+        let planet = &self.map.planets[planet_id];
+        let player = &self.players[player_id];
+
+        // Advice looks at the planet with the new build queued alongside the others
+        let mut pending = player.pending_actions.clone();
+        pending.push(PendingAction::new(
+            ActionType::BuildStructure(structure_id.clone()),
+            planet_id.clone(),
+            build_info.turns,
+            build_info.cost.clone(),
+        ));
+        let bonus_percent = player.empire_effects.map_or(0, |effects| effects.production_bonus_percent);
+        let advice = planet.storage_advice(
+            structure_id, &build_info.cost, &pending, &self.structure_config, bonus_percent, build_info.turns,
+        );
+
+        Ok(self.construction_preview(planet_id, build_info.cost, build_info.turns, advice))
+    }
+
+    /// Previews upgrading `structure_id` on `planet_id` to its next level.
+    pub fn preview_structure_upgrade(
+        &self,
+        player_id: &PlayerId,
+        planet_id: &PlanetId,
+        structure_id: &StructureId,
+    ) -> Result<ConstructionPreview, GameStateError> {
+        self.check_action_conflicts(player_id, planet_id, &ActionType::UpgradeStructure(structure_id.clone()))?;
+        let planet = self.map.planets.get(planet_id)
+            .ok_or_else(|| GameStateError::UnknownPlanet(planet_id.clone()))?;
+        let upgrade_info = planet.validate_upgrade_structure(structure_id, &self.structure_config)?;
+        let context = ModifierContext { player_id, planet_id, kind: ConstructionKind::StructureUpgrade };
+        let upgrade_info = self.resolve_construction(planet_id, upgrade_info, &context)?;
+
+        Ok(self.construction_preview(planet_id, upgrade_info.cost, upgrade_info.turns, Vec::new()))
+    }
+
+    /// Previews building a `ship_id` ship on `planet_id`. A ship delivered to another planet
+    /// takes the quickest route's travel time on top of its build time.
+    pub fn preview_ship_build(
+        &self,
+        player_id: &PlayerId,
+        planet_id: &PlanetId,
+        ship_id: &ShipId,
+        deliver_to: Option<&PlanetId>,
+    ) -> Result<ConstructionPreview, GameStateError> {
+        self.check_action_conflicts(player_id, planet_id, &ActionType::BuildShip(ship_id.clone(), String::new()))?;
+        if !self.map.planets.contains_key(planet_id) {
+            return Err(GameStateError::UnknownPlanet(planet_id.clone()));
+        }
+        let ship_def = self.ship_config.get(ship_id)
+            .expect("Ship must exist (validated by command)");
+        let base = BuildInfo { cost: ship_def.cost.clone(), turns: ship_def.build_time };
+        let context = ModifierContext { player_id, planet_id, kind: ConstructionKind::ShipBuild };
+        let build_info = self.resolve_construction(planet_id, base, &context)?;
+        let transit = match deliver_to {
+            Some(destination) => self.map.graph().distance(planet_id, destination)
+                .ok_or_else(|| GameStateError::UnknownPlanet(destination.clone()))?,
+            None => 0,
+        };
+
+        Ok(self.construction_preview(planet_id, build_info.cost, build_info.turns + transit, Vec::new()))
+    }
+
+    /// Applies the modifier pipeline to a construction's configured cost and time, and checks the
+    /// planet can pay the resolved cost.
+    pub(super) fn resolve_construction(
+        &self,
+        planet_id: &PlanetId,
+        base: BuildInfo,
+        context: &ModifierContext,
+    ) -> Result<BuildInfo, GameStateError> {
+        let modifiers = self.modifiers(context);
+        let cost = modifiers.cost(&base.cost);
+        let planet = &self.map.planets[planet_id];
+        if !planet.available_resources.has_enough(&cost) {
+            return Err(PlanetError::NotEnoughResources { name: planet.name.clone(), cost }.into());
+        }
+        Ok(BuildInfo { cost, turns: modifiers.build_time(base.turns) })
+    }
+
+    fn construction_preview(
+        &self,
+        planet_id: &PlanetId,
+        cost: Resources,
+        turns: u32,
+        advice: Vec<StorageAdvice>,
+    ) -> ConstructionPreview {
+        let planet = &self.map.planets[planet_id];
+        ConstructionPreview {
+            warnings: planet.storage_warnings_after(&cost, turns),
+            resources_after: planet.available_resources.clone() - cost.clone(),
+            ready_on_turn: self.turn + turns,
+            cost,
+            turns,
+            advice,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::commands::command::CommandEffect;
+    use crate::engine::game_event::GameEvent;
+    use crate::engine::game_state::GameState;
+    use crate::test_support::{self, fixture_game};
+
+    fn snapshot(state: &GameState) -> serde_json::Value {
+        serde_json::to_value(state).unwrap()
+    }
+
+    #[test]
+    fn test_preview_matches_the_queued_build_and_changes_nothing() {
+        let mut state = fixture_game(1);
+        let alice = String::from("alice");
+        let home = test_support::home_planet(&state, "alice");
+        let structure_id = String::from("power_grid");
+        let before = snapshot(&state);
+
+        let preview = state.preview_structure_build(&alice, &home, &structure_id).unwrap();
+
+        assert_eq!(snapshot(&state), before);
+        assert_eq!(preview.resources_after, state.map.planets[&home].available_resources.clone() - preview.cost.clone());
+        let effect = CommandEffect::BuildStructure { planet_id: home.clone(), structure_id };
+        let events = state.apply_effect(effect, &alice).unwrap();
+        let GameEvent::StructureQueued { cost, turns, warnings, advice, .. } = &events[0] else {
+            panic!("Expected the build to be queued, got {:?}", events)
+        };
+        assert_eq!((cost, *turns, warnings, advice), (&preview.cost, preview.turns, &preview.warnings, &preview.advice));
+        assert_eq!(state.map.planets[&home].available_resources, preview.resources_after);
+        assert_eq!(state.players[&alice].pending_actions[0].cooldown_remaining, preview.turns);
+    }
+
+    #[test]
+    fn test_upgrade_and_ship_previews_match_the_queued_actions() {
+        let mut state = fixture_game(1);
+        let alice = String::from("alice");
+        let home = test_support::home_planet(&state, "alice");
+        let capital = String::from("planetary_capital");
+        let ship_id = String::from("interceptor");
+        state.map.planets.get_mut(&home).unwrap()
+            .complete_build_structure(String::from("orbital_shipyard"), &test_support::fixture_structure_config()).unwrap();
+        let before = snapshot(&state);
+
+        let upgrade = state.preview_structure_upgrade(&alice, &home, &capital).unwrap();
+        let ship = state.preview_ship_build(&alice, &home, &ship_id, None).unwrap();
+
+        assert_eq!(snapshot(&state), before);
+        let effect = CommandEffect::UpgradeStructure { planet_id: home.clone(), structure_id: capital, auto_repeat: false };
+        let events = state.apply_effect(effect, &alice).unwrap();
+        assert!(matches!(&events[0], GameEvent::UpgradeQueued { cost, turns, warnings, .. }
+            if (cost, *turns, warnings) == (&upgrade.cost, upgrade.turns, &upgrade.warnings)));
+        let events = state.apply_effect(CommandEffect::BuildShip { planet_id: home.clone(), ship_id, deliver_to: None }, &alice).unwrap();
+        assert!(matches!(&events[0], GameEvent::ShipQueued { cost, turns, .. }
+            if (cost, *turns) == (&ship.cost, ship.turns)));
+    }
+}
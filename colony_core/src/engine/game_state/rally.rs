@@ -0,0 +1,98 @@
+use crate::engine::fleet::Fleet;
+use crate::engine::game_event::GameEvent;
+use crate::engine::planet::{PlanetId, RallyPoint};
+use crate::engine::player::PlayerId;
+use crate::engine::ship::{FleetId, ShipInstanceId};
+
+use super::GameState;
+
+impl GameState {
+    /// Sends a ship finished on `planet_id` to the planet's rally fleet, forming that fleet first
+    /// if the rally point asks for a new one. Returns the name of the fleet joined, or a notice
+    /// when the rally fleet is gone.
+    pub(super) fn rally_new_ship(
+        &mut self,
+        player_id: &PlayerId,
+        planet_id: &PlanetId,
+        ship_id: &ShipInstanceId,
+    ) -> (Option<String>, Option<GameEvent>) {
+        let capacity = self.rules_profile().fleet_capacity;
+        let Some(planet) = self.map.planets.get_mut(planet_id) else {
+            return (None, None);
+        };
+        let Some(rally_point) = planet.get_rally_point().cloned() else {
+            return (None, None);
+        };
+        let player = self.players.get_mut(player_id)
+            .expect("Player must exist");
+
+        let fleet_id = match rally_point {
+            RallyPoint::Fleet(fleet_id) => {
+                // Fleets can be destroyed in combat without departing
+                if player.fleets.get(&fleet_id).is_none_or(|fleet| &fleet.location != planet_id) {
+                    planet.set_rally_point(None);
+                    return (None, Some(GameEvent::RallyPointBroken {
+                        player_id: player_id.clone(),
+                        planet_name: planet.name.clone(),
+                        fleet_id,
+                        reason: String::from("no longer orbits the planet"),
+                    }));
+                }
+                fleet_id
+            }
+            RallyPoint::NewFleet(name) => {
+                let fleet_id = player.next_fleet_id();
+                player.fleets.insert(fleet_id.clone(), Fleet::new(fleet_id.clone(), name, planet_id.clone()));
+                planet.set_rally_point(Some(RallyPoint::Fleet(fleet_id.clone())));
+                fleet_id
+            }
+        };
+
+        let fleet = player.fleets.get_mut(&fleet_id)
+            .expect("Rally fleet was checked or created above");
+        let size_of = |ship_id: &ShipInstanceId| {
+            player.ships.get(ship_id).map_or(1, |ship| self.ship_config.size_of(&ship.ship_type))
+        };
+        let used: usize = fleet.ships.iter().map(size_of).sum();
+        if used + size_of(ship_id) > capacity {
+            planet.set_rally_point(None);
+            return (None, Some(GameEvent::RallyPointBroken {
+                player_id: player_id.clone(),
+                planet_name: planet.name.clone(),
+                fleet_id,
+                reason: format!("is full (capacity {}/{})", used, capacity),
+            }));
+        }
+        fleet.add_ship(ship_id.clone());
+        if let Some(ship) = player.ships.get_mut(ship_id) {
+            ship.fleet_id = Some(fleet_id);
+        }
+
+        (Some(fleet.name.clone()), None)
+    }
+
+    /// Clears the rally points on `player_id`'s planets that send ships to `fleet_id`.
+    /// `reason` completes the sentence "fleet <id> ...".
+    pub(super) fn break_rally_points(&mut self, player_id: &PlayerId, fleet_id: &FleetId, reason: &str) -> Vec<GameEvent> {
+        let Some(player) = self.players.get(player_id) else {
+            return Vec::new();
+        };
+
+        let mut events = Vec::new();
+        for planet_id in &player.planets {
+            let Some(planet) = self.map.planets.get_mut(planet_id) else {
+                continue;
+            };
+            if planet.get_rally_point() == Some(&RallyPoint::Fleet(fleet_id.clone())) {
+                planet.set_rally_point(None);
+                events.push(GameEvent::RallyPointBroken {
+                    player_id: player_id.clone(),
+                    planet_name: planet.name.clone(),
+                    fleet_id: fleet_id.clone(),
+                    reason: reason.to_string(),
+                });
+            }
+        }
+        events
+    }
+}
@@ -0,0 +1,201 @@
+use std::cmp::Reverse;
+
+use super::GameState;
+
+/// Entries compaction dropped, counted per collection. On the game state it adds up every
+/// compaction so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub struct Compaction {
+    pub action_log_entries: usize,
+    pub battles: usize,
+    pub intel_entries: usize,
+    /// The last turn anything was dropped on
+    pub last_turn: Option<u32>,
+}
+
+impl Compaction {
+    pub fn is_empty(&self) -> bool {
+        self.action_log_entries == 0 && self.battles == 0 && self.intel_entries == 0
+    }
+}
+
+impl GameState {
+    /// Drops the oldest action log turns, battle reports and intel beyond the caps in
+    /// `rules.retention`, and the turn history beyond `options.history_turns`. Nothing from the
+    /// current turn is dropped, whatever the caps. Returns what was dropped and adds it to
+    /// `compaction`.
+    pub fn compact(&mut self) -> Compaction {
+        let retention = self.rules.retention;
+        let turn = self.turn;
+        let mut dropped = Compaction::default();
+
+        let logged = self.action_log.len();
+        self.action_log.retain(|entry| entry.turn >= turn || entry.turn + retention.action_log_turns > turn);
+        dropped.action_log_entries = logged - self.action_log.len();
+
+        // Battles are recorded in the order they were fought, so the oldest come first
+        let excess = self.battles.len().saturating_sub(retention.battles);
+        let old_battles = self.battles.iter().take(excess).take_while(|report| report.turn < turn).count();
+        self.battles.drain(..old_battles);
+        dropped.battles = old_battles;
+
+        for player in self.players.values_mut() {
+            if player.planet_intel.len() <= retention.intel_per_player {
+                continue;
+            }
+            let mut seen: Vec<_> = player.planet_intel.iter()
+                .map(|(planet_id, intel)| (Reverse(intel.last_seen_turn), planet_id.clone()))
+                .collect();
+            seen.sort();
+            for (Reverse(last_seen_turn), planet_id) in seen.into_iter().skip(retention.intel_per_player) {
+                if last_seen_turn < turn {
+                    player.planet_intel.remove(&planet_id);
+                    dropped.intel_entries += 1;
+                }
+            }
+        }
+
+        self.trim_turn_history();
+
+        if !dropped.is_empty() {
+            dropped.last_turn = Some(turn);
+            self.compaction.action_log_entries += dropped.action_log_entries;
+            self.compaction.battles += dropped.battles;
+            self.compaction.intel_entries += dropped.intel_entries;
+            self.compaction.last_turn = Some(turn);
+        }
+        dropped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::command::CommandEffect;
+    use crate::engine::game_rules::RetentionRules;
+    use crate::engine::game_state::{ActionLogEntry, ActionOutcome, BattleKind, BattleReport, BattleSide, PlanetIntel};
+    use crate::test_support::{self, fixture_game};
+
+    fn log_entry(turn: u32) -> ActionLogEntry {
+        ActionLogEntry {
+            turn,
+            player_id: String::from("alice"),
+            command: String::from("status"),
+            outcome: ActionOutcome::Applied,
+            highlights: Vec::new(),
+        }
+    }
+
+    fn intel(last_seen_turn: u32) -> PlanetIntel {
+        PlanetIntel { last_seen_turn, owner_at_time: None, shield_seen: 0, structure_count: 0, ruin_seen: None }
+    }
+
+    fn battle(state: &GameState, turn: u32) -> BattleReport {
+        let side = |player_id: &str| BattleSide {
+            player_id: player_id.to_string(),
+            player_name: state.players[player_id].name.clone(),
+            fleet_id: None,
+            ships: Vec::new(),
+            losses: Vec::new(),
+        };
+        let planet_id = test_support::home_planet(state, "bob");
+        BattleReport {
+            id: 0,
+            turn,
+            kind: BattleKind::Orbital,
+            planet_name: state.map.planets[&planet_id].name.clone(),
+            planet_id,
+            attacker: side("alice"),
+            defender: side("bob"),
+            rounds: Vec::new(),
+            attacker_wins: false,
+            observers: Vec::new(),
+        }
+    }
+
+    fn small_caps(state: &mut GameState) {
+        state.rules.retention = RetentionRules { action_log_turns: 3, battles: 2, intel_per_player: 2 };
+    }
+
+    #[test]
+    fn test_compaction_keeps_exactly_the_configured_amounts() {
+        let mut state = fixture_game(1);
+        small_caps(&mut state);
+        state.turn = 10;
+        state.action_log = (1..=10).flat_map(|turn| [log_entry(turn), log_entry(turn)]).collect();
+        for turn in [3, 6, 9, 10] {
+            let report = battle(&state, turn);
+            state.record_battle(report);
+        }
+        let alice = state.players.get_mut("alice").unwrap();
+        alice.planet_intel.clear();
+        for (index, seen) in [4, 9, 2, 7].into_iter().enumerate() {
+            alice.planet_intel.insert(format!("planet_{index}"), intel(seen));
+        }
+
+        let dropped = state.compact();
+
+        assert_eq!(dropped, Compaction { action_log_entries: 14, battles: 2, intel_entries: 2, last_turn: Some(10) });
+        let turns: Vec<u32> = state.action_log.iter().map(|entry| entry.turn).collect();
+        assert_eq!(turns, vec![8, 8, 9, 9, 10, 10]);
+        assert_eq!(state.battles.iter().map(|report| (report.id, report.turn)).collect::<Vec<_>>(), vec![(3, 9), (4, 10)]);
+        let mut kept: Vec<_> = state.players["alice"].planet_intel.keys().cloned().collect();
+        kept.sort();
+        assert_eq!(kept, vec![String::from("planet_1"), String::from("planet_3")]);
+        assert_eq!(state.compaction, dropped);
+
+        // Nothing more to drop, so the record stays as it was
+        assert!(state.compact().is_empty());
+        assert_eq!(state.compaction, dropped);
+    }
+
+    #[test]
+    fn test_compaction_never_drops_the_current_turn() {
+        let mut state = fixture_game(1);
+        state.rules.retention = RetentionRules { action_log_turns: 0, battles: 0, intel_per_player: 0 };
+        state.turn = 5;
+        state.action_log = vec![log_entry(4), log_entry(5), log_entry(5)];
+        let alice = state.players.get_mut("alice").unwrap();
+        alice.planet_intel.clear();
+        alice.planet_intel.insert(String::from("old"), intel(4));
+        alice.planet_intel.insert(String::from("fresh"), intel(5));
+
+        let dropped = state.compact();
+
+        assert_eq!(dropped.action_log_entries, 1);
+        assert_eq!(state.action_log, vec![log_entry(5), log_entry(5)]);
+        assert_eq!(state.players["alice"].planet_intel.keys().collect::<Vec<_>>(), vec!["fresh"]);
+    }
+
+    #[test]
+    fn test_rounds_compact_battles_and_keep_numbering_them() {
+        let mut state = fixture_game(1);
+        small_caps(&mut state);
+        for turn in 1..=4 {
+            let report = battle(&state, turn);
+            state.record_battle(report);
+        }
+        state.turn = 5;
+        for _ in 0..3 {
+            let report = battle(&state, 5);
+            state.record_battle(report);
+        }
+
+        // Three battles this turn are kept against a cap of two
+        state.compact();
+        assert_eq!(state.battles.iter().map(|report| report.turn).collect::<Vec<_>>(), vec![5, 5, 5]);
+        assert_eq!(state.compaction.battles, 4);
+
+        // Rounds compact as they end, once turn 5 is over, and new battles carry on the numbering
+        for _ in 0..2 * state.players_order.len() {
+            let player_id = state.current_player().clone();
+            let player_name = state.players[&player_id].name.clone();
+            state.apply_effect(CommandEffect::EndTurn { player_name }, &player_id).unwrap();
+        }
+        assert_eq!(state.turn, 7);
+        assert_eq!(state.battles.iter().map(|report| report.id).collect::<Vec<_>>(), vec![6, 7]);
+        let report = battle(&state, 7);
+        state.record_battle(report);
+        assert_eq!(state.battles.last().unwrap().id, 8);
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::BTreeMap;
+
+use crate::engine::configs::ship_config::ShipId;
+use crate::engine::pending_action::ActionType;
+use crate::engine::planet::PlanetId;
+use crate::engine::player::PlayerId;
+use crate::engine::ship::{FleetId, ShipInstanceId};
+use crate::engine::structure::StructureId;
+
+use super::GameState;
+
+/// Something a player has underway that finishes on a known turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduledItem {
+    /// A structure built or upgraded to `level`
+    Structure { structure_id: StructureId, level: u16, planet_id: PlanetId },
+    Ship { ship_id: ShipInstanceId, ship_type: ShipId, planet_id: PlanetId },
+    Retrofit { ship_id: ShipInstanceId, ship_type: ShipId, planet_id: PlanetId },
+    FleetArrival { fleet_id: FleetId, destination: PlanetId },
+    /// A ship travelling outside any fleet
+    ShipArrival { ship_id: ShipInstanceId, destination: PlanetId },
+}
+
+impl ScheduledItem {
+    /// The planet the item finishes at: where it is built, or where the move ends.
+    pub fn planet_id(&self) -> &PlanetId {
+        match self {
+            ScheduledItem::Structure { planet_id, .. }
+            | ScheduledItem::Ship { planet_id, .. }
+            | ScheduledItem::Retrofit { planet_id, .. } => planet_id,
+            ScheduledItem::FleetArrival { destination, .. } | ScheduledItem::ShipArrival { destination, .. } => destination,
+        }
+    }
+}
+
+impl GameState {
+    /// Everything `player_id` has in flight, keyed by the turn it is done by: the turn the
+    /// player first sees the structure standing, the ship launched or the fleet in orbit.
+    /// Items finishing on the same turn keep the order they were queued in. Bombardments go on
+    /// until called off and builds held for a lost prerequisite have no date, so neither is
+    /// listed. Empty for unknown players.
+    pub fn completion_schedule(&self, player_id: &PlayerId) -> BTreeMap<u32, Vec<ScheduledItem>> {
+        let mut schedule: BTreeMap<u32, Vec<ScheduledItem>> = BTreeMap::new();
+        let Some(player) = self.players.get(player_id) else {
+            return schedule;
+        };
+
+        // Upgrades queued one after another on a structure each add a level
+        let mut levels: BTreeMap<(&PlanetId, &StructureId), u16> = BTreeMap::new();
+        let mut actions: Vec<_> = player.pending_actions.iter().collect();
+        actions.sort_by_key(|action| action.cooldown_remaining.max(1));
+
+        for action in actions {
+            let planet_id = action.planet_id.clone();
+            let item = match &action.action_type {
+                ActionType::BuildStructure(structure_id) | ActionType::UpgradeStructure(structure_id) => {
+                    let level = levels.entry((&action.planet_id, structure_id)).or_insert_with(|| {
+                        self.map.planets.get(&action.planet_id)
+                            .map_or(0, |planet| planet.get_structure_level(structure_id))
+                    });
+                    *level += 1;
+                    ScheduledItem::Structure { structure_id: structure_id.clone(), level: *level, planet_id }
+                }
+                ActionType::BuildShip(ship_type, ship_id) => {
+                    ScheduledItem::Ship { ship_id: ship_id.clone(), ship_type: ship_type.clone(), planet_id }
+                }
+                ActionType::RetrofitShip(ship_type, ship_id) => {
+                    ScheduledItem::Retrofit { ship_id: ship_id.clone(), ship_type: ship_type.clone(), planet_id }
+                }
+                ActionType::MoveFleet(fleet_id, destination) => {
+                    ScheduledItem::FleetArrival { fleet_id: fleet_id.clone(), destination: destination.clone() }
+                }
+                ActionType::MoveShip(ship_id, destination) => {
+                    ScheduledItem::ShipArrival { ship_id: ship_id.clone(), destination: destination.clone() }
+                }
+                ActionType::BombardPlanet(..) => continue,
+            };
+            // A cooldown of 0 or 1 completes in the coming round
+            let turn = self.turn + action.cooldown_remaining.max(1);
+            schedule.entry(turn).or_default().push(item);
+        }
+        schedule
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::parser;
+    use crate::engine::fleet::Fleet;
+    use crate::engine::pending_action::PendingAction;
+    use crate::engine::resources::Resources;
+    use crate::test_support::{self, fixture_game};
+
+    fn queue(state: &mut GameState, action_type: ActionType, planet_id: &PlanetId, cooldown: u32) {
+        state.players.get_mut("alice").unwrap().pending_actions.push(
+            PendingAction::new(action_type, planet_id.clone(), cooldown, Resources::default()),
+        );
+    }
+
+    fn order(state: &mut GameState, line: &str) {
+        let player_id = state.current_player().clone();
+        let effect = parser::parse(line).unwrap().execute(state).unwrap();
+        state.apply_effect(effect, &player_id).unwrap();
+    }
+
+    #[test]
+    fn test_schedule_groups_items_by_completion_turn() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let bob_home = test_support::home_planet(&state, "bob");
+        let mining = String::from("mining_complex");
+        let level = state.map.planets[&home].get_structure_level(&mining);
+        queue(&mut state, ActionType::UpgradeStructure(mining.clone()), &home, 2);
+        queue(&mut state, ActionType::BuildShip(String::from("scout"), String::from("scout_1")), &home, 0);
+        queue(&mut state, ActionType::BuildStructure(String::from("power_grid")), &home, 1);
+        queue(&mut state, ActionType::BombardPlanet(String::from("fleet_1"), bob_home.clone()), &bob_home, u32::MAX);
+
+        let schedule = state.completion_schedule(&String::from("alice"));
+
+        assert_eq!(schedule.keys().copied().collect::<Vec<_>>(), vec![state.turn + 1, state.turn + 2]);
+        assert_eq!(schedule[&(state.turn + 1)], vec![
+            ScheduledItem::Ship { ship_id: String::from("scout_1"), ship_type: String::from("scout"), planet_id: home.clone() },
+            ScheduledItem::Structure { structure_id: String::from("power_grid"), level: 1, planet_id: home.clone() },
+        ]);
+        assert_eq!(schedule[&(state.turn + 2)], vec![
+            ScheduledItem::Structure { structure_id: mining, level: level + 1, planet_id: home },
+        ]);
+        assert!(state.completion_schedule(&String::from("nobody")).is_empty());
+    }
+
+    #[test]
+    fn test_fleet_arrival_counts_every_turn_of_a_long_connection() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let graph = state.map.graph();
+        let (distance, destination) = graph.neighbors(&home)
+            .map(|(planet_id, distance)| (u32::from(distance), planet_id.clone()))
+            .max()
+            .expect("Fixture home must have neighbours");
+        assert!(distance > 1, "Fixture home needs a connection longer than a turn");
+
+        let alice = state.players.get_mut("alice").unwrap();
+        let fleet_id = alice.next_fleet_id();
+        let mut fleet = Fleet::new(fleet_id.clone(), String::from("Vanguard"), home.clone());
+        fleet.add_ship(alice.add_ship(String::from("scout"), home.clone()));
+        alice.fleets.insert(fleet_id.clone(), fleet);
+        order(&mut state, &format!("fleet move {} {}", fleet_id, destination));
+
+        let arrival = ScheduledItem::FleetArrival { fleet_id: fleet_id.clone(), destination: destination.clone() };
+        let eta = state.turn + distance;
+        assert_eq!(state.completion_schedule(&String::from("alice")), BTreeMap::from([(eta, vec![arrival.clone()])]));
+
+        // The arrival turn holds as the rounds pass, and the fleet is in orbit on it
+        while state.turn < eta {
+            order(&mut state, "end");
+            order(&mut state, "end");
+            if state.turn < eta {
+                assert_eq!(state.completion_schedule(&String::from("alice"))[&eta], vec![arrival.clone()]);
+            }
+        }
+        assert!(state.completion_schedule(&String::from("alice")).is_empty());
+        assert_eq!(state.players["alice"].fleets[&fleet_id].location, destination);
+    }
+
+    #[test]
+    fn test_queued_upgrades_stack_levels() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let mining = String::from("mining_complex");
+        let level = state.map.planets[&home].get_structure_level(&mining);
+        queue(&mut state, ActionType::UpgradeStructure(mining.clone()), &home, 3);
+        queue(&mut state, ActionType::UpgradeStructure(mining.clone()), &home, 6);
+
+        let levels: Vec<u16> = state.completion_schedule(&String::from("alice")).into_values()
+            .flatten()
+            .map(|item| match item {
+                ScheduledItem::Structure { level, .. } => level,
+                other => panic!("Only upgrades were queued, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(levels, vec![level + 1, level + 2]);
+    }
+}
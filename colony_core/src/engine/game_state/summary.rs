@@ -0,0 +1,219 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::configs::ship_config::ShipId;
+use crate::engine::map::MapSize;
+use crate::engine::player::PlayerId;
+use crate::engine::resources::Resources;
+
+use super::GameState;
+
+/// Totals of a player's economy and military across all owned planets.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PlayerResourceSummary {
+    pub available: Resources,
+    pub storage: Resources,
+    pub production: Resources,
+    /// Energy produced minus energy consumed per turn; negative when structures drain more than is generated
+    pub energy_balance: i64,
+    pub ships_by_type: BTreeMap<ShipId, usize>,
+    pub fleet_count: usize,
+    /// Owned planets without a pending action
+    pub idle_planets: usize,
+}
+
+/// The game at a glance, for `status game` and save listings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameSummary {
+    pub map_size: MapSize,
+    pub planets: usize,
+    /// Every commander still in the game, in turn order
+    pub players: Vec<PlayerEntry>,
+    pub turn: u32,
+    /// Name of the rules preset, `custom` when the knobs match none
+    pub rules: String,
+    /// Hidden until the game is over, unless its players chose to show it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Seconds since the Unix epoch when the game was created, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<u64>,
+}
+
+/// One commander of a [`GameSummary`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerEntry {
+    pub name: String,
+    pub ai: bool,
+    pub planets: usize,
+}
+
+impl GameSummary {
+    pub fn humans(&self) -> usize {
+        self.players.iter().filter(|player| !player.ai).count()
+    }
+
+    pub fn ai_players(&self) -> usize {
+        self.players.len() - self.humans()
+    }
+}
+
+impl GameState {
+    /// Map, commanders, turn and rules of the game, as anyone watching may see them.
+    pub fn game_summary(&self) -> GameSummary {
+        GameSummary {
+            map_size: self.map.size,
+            planets: self.map.planets.len(),
+            players: self.players_order.iter()
+                .filter_map(|id| self.players.get(id))
+                .map(|player| PlayerEntry { name: player.name.clone(), ai: player.ai, planets: player.planets.len() })
+                .collect(),
+            turn: self.turn,
+            rules: self.rules_profile().preset().to_string(),
+            seed: (self.is_over() || self.options.reveal_seed).then_some(self.seed),
+            started_at: self.started_at,
+        }
+    }
+
+    /// Aggregates resources, production, ships and fleets of a player.
+    /// Returns None if the player does not exist.
+    pub fn player_resource_summary(&self, player_id: &PlayerId) -> Option<PlayerResourceSummary> {
+        let player = self.players.get(player_id)?;
+        let mut summary = PlayerResourceSummary::default();
+        let mut energy_consumption: i64 = 0;
+
+        for planet in player.planets.iter().filter_map(|id| self.map.planets.get(id)) {
+            summary.available += &planet.available_resources;
+            summary.storage += &planet.storage_capacity;
+            summary.production += planet.get_production_rate();
+            energy_consumption += i64::from(planet.get_energy_consumption());
+
+            if !player.has_pending_action_on_planet(&planet.id) {
+                summary.idle_planets += 1;
+            }
+        }
+
+        summary.energy_balance = i64::from(summary.production.energy) - energy_consumption;
+
+        for ship in player.ships.values() {
+            *summary.ships_by_type.entry(ship.ship_type.clone()).or_default() += 1;
+        }
+        summary.fleet_count = player.fleets.len();
+
+        Some(summary)
+    }
+
+    /// Combined totals of `player_id` and their teammates. Returns None if the player does not exist.
+    pub fn team_resource_summary(&self, player_id: &PlayerId) -> Option<PlayerResourceSummary> {
+        let mut team = self.player_resource_summary(player_id)?;
+        for member in self.teammates(player_id) {
+            let Some(summary) = self.player_resource_summary(&member) else {
+                continue;
+            };
+            team.available += &summary.available;
+            team.storage += &summary.storage;
+            team.production += &summary.production;
+            team.energy_balance += summary.energy_balance;
+            for (ship_type, count) in summary.ships_by_type {
+                *team.ships_by_type.entry(ship_type).or_default() += count;
+            }
+            team.fleet_count += summary.fleet_count;
+            team.idle_planets += summary.idle_planets;
+        }
+        Some(team)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::fleet::Fleet;
+    use crate::engine::game_state::GamePhase;
+    use crate::engine::pending_action::{ActionType, PendingAction};
+    use crate::engine::resources::Resources;
+    use crate::test_support::{self, fixture_game};
+
+    #[test]
+    fn test_summary_sums_two_planets() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+
+        // Colonize a second, neutral planet for alice
+        let second = state.map.planets.values()
+            .find(|p| p.get_owner().is_none())
+            .map(|p| p.id.clone())
+            .expect("Fixture map must have a neutral planet");
+        let planet = state.map.planets.get_mut(&second).unwrap();
+        planet.set_owner("alice".to_string());
+        planet.colonize(&state.structure_config, state.turn).unwrap();
+
+        let alice = state.players.get_mut("alice").unwrap();
+        alice.planets.push(second.clone());
+        alice.add_ship("interceptor".to_string(), home.clone());
+        alice.add_ship("interceptor".to_string(), home.clone());
+        let ravager = alice.add_ship("ravager".to_string(), home.clone());
+        let mut fleet = Fleet::new("fleet_1".to_string(), "Strike".to_string(), home.clone());
+        fleet.add_ship(ravager);
+        alice.fleets.insert("fleet_1".to_string(), fleet);
+        alice.pending_actions.push(PendingAction::new(
+            ActionType::BuildShip("interceptor".to_string(), "interceptor_3".to_string()),
+            second,
+            1,
+            Resources::default(),
+        ));
+
+        let summary = state.player_resource_summary(&"alice".to_string()).unwrap();
+
+        // Each colonized planet has a level 1 capital with full storage
+        assert_eq!(summary.available, Resources { minerals: 1000, gas: 500, energy: 200 });
+        assert_eq!(summary.storage, Resources { minerals: 1000, gas: 500, energy: 200 });
+        assert_eq!(summary.production, Resources { minerals: 20, gas: 10, energy: 20 });
+        assert_eq!(summary.energy_balance, 20);
+        assert_eq!(summary.ships_by_type.get("interceptor"), Some(&2));
+        assert_eq!(summary.ships_by_type.get("ravager"), Some(&1));
+        assert_eq!(summary.fleet_count, 1);
+        assert_eq!(summary.idle_planets, 1);
+    }
+
+    #[test]
+    fn test_summary_for_unknown_player_is_none() {
+        let state = fixture_game(1);
+
+        assert!(state.player_resource_summary(&"mallory".to_string()).is_none());
+    }
+
+    #[test]
+    fn test_game_summary_describes_the_fixture_game() {
+        let mut state = fixture_game(7);
+        test_support::grant_planet(&mut state, "bob");
+        state.players.get_mut("bob").unwrap().ai = true;
+        state.started_at = Some(1_700_000_000);
+
+        let summary = state.game_summary();
+
+        let order: Vec<&str> = state.players_order.iter().map(|id| state.players[id].name.as_str()).collect();
+        assert_eq!(summary.players.iter().map(|player| player.name.as_str()).collect::<Vec<_>>(), order);
+        let bob = summary.players.iter().find(|player| player.name == "Bob").unwrap();
+        assert_eq!(*bob, PlayerEntry { name: String::from("Bob"), ai: true, planets: 2 });
+        assert_eq!((summary.humans(), summary.ai_players()), (1, 1));
+        assert_eq!(summary.map_size, state.map.size);
+        assert_eq!(summary.planets, state.map.planets.len());
+        assert_eq!(summary.turn, 1);
+        assert_eq!(summary.rules, "classic");
+        assert_eq!(summary.started_at, Some(1_700_000_000));
+    }
+
+    #[test]
+    fn test_seed_stays_hidden_until_the_game_ends_or_players_show_it() {
+        let mut state = fixture_game(7).with_seed(99);
+        assert_eq!(state.game_summary().seed, None);
+
+        state.options.reveal_seed = true;
+        assert_eq!(state.game_summary().seed, Some(99));
+
+        state.options.reveal_seed = false;
+        state.game_phase = GamePhase::Finished;
+        assert_eq!(state.game_summary().seed, Some(99));
+    }
+}
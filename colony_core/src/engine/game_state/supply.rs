@@ -0,0 +1,227 @@
+use std::fmt;
+
+use rand::Rng;
+
+use crate::engine::game_event::GameEvent;
+use crate::engine::player::PlayerId;
+use crate::engine::ship::FleetId;
+
+use super::GameState;
+
+/// How well a fleet's owner can keep it supplied where it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Supply {
+    /// Within the supply range of one of the owner's planets
+    Supplied { hops: u32 },
+    /// Further than the supply range from every planet of the owner
+    Stretched { hops: u32 },
+    /// No planet of the owner connects to the fleet's location
+    CutOff,
+}
+
+impl Supply {
+    pub fn is_supplied(&self) -> bool {
+        matches!(self, Supply::Supplied { .. })
+    }
+}
+
+impl fmt::Display for Supply {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hops = |hops: u32| if hops == 1 { String::from("1 hop") } else { format!("{} hops", hops) };
+        match self {
+            Supply::Supplied { hops: count } => write!(f, "supplied ({})", hops(*count)),
+            Supply::Stretched { hops: count } => write!(f, "stretched ({})", hops(*count)),
+            Supply::CutOff => write!(f, "cut off (no route to your planets)"),
+        }
+    }
+}
+
+impl GameState {
+    /// Whether `fleet_id` is within the supply range of its owner's nearest planet, counted
+    /// in hops over the planet graph. `None` when the player or fleet does not exist.
+    pub fn fleet_supply(&self, player_id: &PlayerId, fleet_id: &FleetId) -> Option<Supply> {
+        let player = self.players.get(player_id)?;
+        let fleet = player.fleets.get(fleet_id)?;
+        let hops = self.map.graph().hops(&fleet.location);
+        let nearest = player.planets.iter().filter_map(|planet_id| hops.get(planet_id).copied()).min();
+        Some(match nearest {
+            Some(hops) if hops <= self.rules_profile().supply_range => Supply::Supplied { hops },
+            Some(hops) => Supply::Stretched { hops },
+            None => Supply::CutOff,
+        })
+    }
+
+    /// Wears down the shields of ships in fleets out of supply, each ship by chance, and
+    /// restores every ship of a fleet back in supply at once.
+    pub(super) fn process_supply(&mut self) -> Vec<GameEvent> {
+        let supplies: Vec<(PlayerId, FleetId, Supply)> = self.players.values()
+            .flat_map(|player| player.fleets.values()
+                .filter(|fleet| !fleet.is_empty())
+                .map(|fleet| (player.id.clone(), fleet.id.clone())))
+            .filter_map(|(player_id, fleet_id)| {
+                let supply = self.fleet_supply(&player_id, &fleet_id)?;
+                Some((player_id, fleet_id, supply))
+            })
+            .collect();
+
+        let chance = self.rules_profile().attrition_chance_percent;
+        let shield_loss = self.rules_profile().attrition_shield_loss;
+        let mut rng = self.round_rng();
+        let mut events = Vec::new();
+        for (player_id, fleet_id, supply) in supplies {
+            let Some(player) = self.players.get_mut(&player_id) else {
+                continue;
+            };
+            let fleet_name = player.fleets[&fleet_id].name.clone();
+            let ships = player.fleets[&fleet_id].ships.clone();
+
+            if supply.is_supplied() {
+                let mut shield_restored = 0;
+                for ship_id in &ships {
+                    if let Some(ship) = player.ships.get_mut(ship_id) {
+                        shield_restored += std::mem::take(&mut ship.supply_wear);
+                    }
+                }
+                if shield_restored > 0 {
+                    events.push(GameEvent::FleetResupplied { player_id, fleet_name, shield_restored });
+                }
+                continue;
+            }
+
+            let (mut ships_worn, mut shield_lost) = (0, 0);
+            for ship_id in &ships {
+                let Some(ship) = player.ships.get_mut(ship_id) else {
+                    continue;
+                };
+                // Every ship rolls, worn out or not, so one ship's state never shifts another's luck
+                if rng.random_range(0..100) >= chance {
+                    continue;
+                }
+                let shield = self.ship_config.get(&ship.ship_type).map_or(0, |ship_def| ship_def.shield);
+                let worn = ship.supply_wear.saturating_add(shield_loss).min(shield);
+                if worn > ship.supply_wear {
+                    ships_worn += 1;
+                    shield_lost += worn - ship.supply_wear;
+                    ship.supply_wear = worn;
+                }
+            }
+            if ships_worn > 0 {
+                let hops = match supply {
+                    Supply::Stretched { hops } => Some(hops),
+                    _ => None,
+                };
+                events.push(GameEvent::FleetAttrition { player_id, fleet_name, hops, ships_worn, shield_lost });
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::fleet::Fleet;
+    use crate::engine::game_rules::DEFAULT_SUPPLY_RANGE;
+    use crate::engine::planet::PlanetId;
+    use crate::test_support::{self, end_round, fixture_game};
+
+    fn park_fleet(state: &mut GameState, player_id: &str, ravagers: usize, planet_id: &PlanetId) -> FleetId {
+        let player = state.players.get_mut(player_id).unwrap();
+        let fleet_id = player.next_fleet_id();
+        let mut fleet = Fleet::new(fleet_id.clone(), String::from("Raiders"), planet_id.clone());
+        for _ in 0..ravagers {
+            let ship_id = player.add_ship(String::from("ravager"), planet_id.clone());
+            player.ships.get_mut(&ship_id).unwrap().fleet_id = Some(fleet_id.clone());
+            fleet.add_ship(ship_id);
+        }
+        player.fleets.insert(fleet_id.clone(), fleet);
+        fleet_id
+    }
+
+    /// A planet the given number of hops from alice's home, if the fixture map has one.
+    fn planet_at_hops(state: &GameState, wanted: u32) -> PlanetId {
+        let hops = state.map.graph().hops(&test_support::home_planet(state, "alice"));
+        hops.into_iter()
+            .filter(|(_, hops)| *hops == wanted)
+            .map(|(planet_id, _)| planet_id)
+            .min()
+            .expect("The fixture map must reach that far")
+    }
+
+    fn total_wear(state: &GameState, fleet_id: &FleetId) -> u32 {
+        let alice = &state.players["alice"];
+        alice.fleets[fleet_id].ships.iter().map(|ship_id| alice.ships[ship_id].supply_wear).sum()
+    }
+
+    #[test]
+    fn test_supply_follows_hops_from_the_nearest_planet() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let near = park_fleet(&mut state, "alice", 1, &home);
+        let (edge_planet, far_planet) = (planet_at_hops(&state, DEFAULT_SUPPLY_RANGE), planet_at_hops(&state, DEFAULT_SUPPLY_RANGE + 1));
+        let edge = park_fleet(&mut state, "alice", 1, &edge_planet);
+        let far = park_fleet(&mut state, "alice", 1, &far_planet);
+
+        let alice = String::from("alice");
+        assert_eq!(state.fleet_supply(&alice, &near), Some(Supply::Supplied { hops: 0 }));
+        assert_eq!(state.fleet_supply(&alice, &edge), Some(Supply::Supplied { hops: DEFAULT_SUPPLY_RANGE }));
+        assert_eq!(state.fleet_supply(&alice, &far), Some(Supply::Stretched { hops: DEFAULT_SUPPLY_RANGE + 1 }));
+        assert_eq!(Supply::Stretched { hops: 3 }.to_string(), "stretched (3 hops)");
+        assert_eq!(Supply::Supplied { hops: 1 }.to_string(), "supplied (1 hop)");
+    }
+
+    #[test]
+    fn test_stretched_fleets_wear_down_and_recover_once_supplied() {
+        let mut state = fixture_game(7);
+        let far_planet = planet_at_hops(&state, DEFAULT_SUPPLY_RANGE + 1);
+        let fleet_id = park_fleet(&mut state, "alice", 4, &far_planet);
+        let max_wear = 4 * state.ship_config.get(&String::from("ravager")).unwrap().shield;
+
+        let mut wear = Vec::new();
+        let mut reported = false;
+        for _ in 0..12 {
+            let events = end_round(&mut state);
+            reported |= events.iter().any(|event| matches!(
+                event,
+                GameEvent::FleetAttrition { player_id, hops: Some(hops), .. }
+                    if player_id == "alice" && *hops == DEFAULT_SUPPLY_RANGE + 1
+            ));
+            wear.push(total_wear(&state, &fleet_id));
+        }
+        assert!(reported, "Alice must hear about the attrition");
+        assert!(wear.windows(2).all(|pair| pair[0] <= pair[1]), "Wear only grows out of supply: {wear:?}");
+        assert!(wear[wear.len() - 1] > 0 && wear[wear.len() - 1] <= max_wear, "{wear:?}");
+
+        // The same seed wears the fleet down the same way
+        let mut replayed = fixture_game(7);
+        let replayed_fleet = park_fleet(&mut replayed, "alice", 4, &far_planet);
+        for _ in 0..12 {
+            end_round(&mut replayed);
+        }
+        assert_eq!(total_wear(&replayed, &replayed_fleet), wear[wear.len() - 1]);
+
+        // Back home every shield is restored in the next round
+        let home = test_support::home_planet(&state, "alice");
+        state.players.get_mut("alice").unwrap().fleets.get_mut(&fleet_id).unwrap().location = home;
+        let events = end_round(&mut state);
+        assert_eq!(total_wear(&state, &fleet_id), 0);
+        assert!(events.iter().any(|event| matches!(
+            event,
+            GameEvent::FleetResupplied { shield_restored, .. } if *shield_restored == wear[wear.len() - 1]
+        )));
+    }
+
+    #[test]
+    fn test_worn_shields_weaken_defense() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let fleet_id = park_fleet(&mut state, "alice", 2, &home);
+        let alice = Some(String::from("alice"));
+        let ships = state.players["alice"].fleets[&fleet_id].ships.clone();
+        let fresh = state.calculate_defense(&alice, &ships, &String::from("bob"), &[]);
+
+        let ship = state.players.get_mut("alice").unwrap().ships.get_mut(&ships[0]).unwrap();
+        ship.supply_wear = 5;
+        assert_eq!(state.calculate_defense(&alice, &ships, &String::from("bob"), &[]), fresh - 5);
+    }
+}
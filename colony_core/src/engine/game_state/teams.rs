@@ -0,0 +1,191 @@
+use crate::engine::player::PlayerId;
+
+use super::GameState;
+
+impl GameState {
+    /// Whether two players are on the same side: the same player, or teammates.
+    pub fn are_allies(&self, first: &PlayerId, second: &PlayerId) -> bool {
+        if first == second {
+            return true;
+        }
+        let team_of = |player_id: &PlayerId| self.players.get(player_id).and_then(|player| player.team);
+        team_of(first).is_some() && team_of(first) == team_of(second)
+    }
+
+    /// The other members of `player_id`'s team, in turn order. Empty for players without a team.
+    pub fn teammates(&self, player_id: &PlayerId) -> Vec<PlayerId> {
+        self.players_order.iter()
+            .filter(|other| *other != player_id && self.are_allies(player_id, other))
+            .cloned()
+            .collect()
+    }
+
+    /// `player_id` followed by their teammates, in turn order.
+    pub fn side_of(&self, player_id: &PlayerId) -> Vec<PlayerId> {
+        std::iter::once(player_id.clone())
+            .chain(self.teammates(player_id))
+            .collect()
+    }
+
+    /// Turns, this one included, in which players may not bombard, blockade or invade each
+    /// other under the protected expansion rule; zero once hostilities may begin.
+    pub fn protection_turns_left(&self) -> u32 {
+        self.rules.protected_turns.saturating_add(1).saturating_sub(self.turn)
+    }
+
+    /// The side that has won, in turn order. A player without a team wins by owning every
+    /// planet; a team wins once every player outside it has lost all their planets, even if
+    /// some of its own members were eliminated along the way.
+    pub(crate) fn winning_side(&self) -> Option<Vec<PlayerId>> {
+        let total_planets = self.map.planets.len();
+
+        for player_id in &self.players_order {
+            let player = self.players.get(player_id)?;
+            let side = self.side_of(player_id);
+            let has_won = match player.team {
+                None => player.planets.len() == total_planets,
+                Some(_) => {
+                    let side_holds_planets = side.iter()
+                        .any(|member| self.players.get(member).is_some_and(|member| !member.planets.is_empty()));
+                    let opponents_eliminated = self.players.values()
+                        .filter(|other| !side.contains(&other.id))
+                        .all(|other| other.planets.is_empty());
+                    side_holds_planets && opponents_eliminated
+                }
+            };
+            if has_won {
+                return Some(side);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::command::{CommandEffect, CommandError};
+    use crate::engine::commands::parser;
+    use crate::engine::fleet::Fleet;
+    use crate::engine::game_event::{ArrivalKind, GameEvent};
+    use crate::engine::planet::PlanetId;
+    use crate::engine::ship::{FleetId, MovableUnit};
+    use crate::test_support::{self, fixture_team_game};
+
+    /// Gives `player_id` a fleet of the given fixture ships at `location`.
+    fn fleet_of(state: &mut GameState, player_id: &str, ship_types: &[&str], location: &PlanetId) -> FleetId {
+        let player = state.players.get_mut(player_id).unwrap();
+        let fleet_id = player.next_fleet_id();
+        let mut fleet = Fleet::new(fleet_id.clone(), String::from("Landing"), location.clone());
+        for ship_type in ship_types {
+            let ship_id = player.add_ship(ship_type.to_string(), location.clone());
+            player.ships.get_mut(&ship_id).unwrap().fleet_id = Some(fleet_id.clone());
+            fleet.add_ship(ship_id);
+        }
+        player.fleets.insert(fleet_id.clone(), fleet);
+        fleet_id
+    }
+
+    fn invade(state: &mut GameState, player_id: &str, planet_id: &PlanetId) -> Vec<GameEvent> {
+        let fleet_id = fleet_of(state, player_id, &["ark", "interceptor"], planet_id);
+        let effect = CommandEffect::ColonizePlanet { fleet_id, planet_id: planet_id.clone() };
+        state.apply_effect(effect, &player_id.to_string()).unwrap()
+    }
+
+    /// Ends the current turn and returns the events it produced.
+    fn end_turn(state: &mut GameState) -> Vec<GameEvent> {
+        let player_id = state.current_player().clone();
+        let player_name = state.players[&player_id].name.clone();
+        state.apply_effect(CommandEffect::EndTurn { player_name }, &player_id).unwrap()
+    }
+
+    #[test]
+    fn test_teammates_are_allies_and_share_sight() {
+        let state = fixture_team_game(1);
+        let alice = String::from("alice");
+
+        assert!(state.are_allies(&alice, &String::from("carol")));
+        assert!(!state.are_allies(&alice, &String::from("bob")));
+        assert_eq!(state.teammates(&alice), ["carol"]);
+        assert!(state.visible_planets(&alice).contains(&test_support::home_planet(&state, "carol")));
+        assert!(!state.visible_planets(&alice).contains(&test_support::home_planet(&state, "bob")));
+    }
+
+    #[test]
+    fn test_fleet_reaching_allied_planet_does_not_fight() {
+        let mut state = fixture_team_game(1);
+        let alice_home = test_support::home_planet(&state, "alice");
+        let origin = state.map.planets[&alice_home].get_connections()[0].to().clone();
+        state.players.get_mut("alice").unwrap().add_ship(String::from("interceptor"), alice_home.clone());
+        let fleet_id = fleet_of(&mut state, "carol", &["ark", "interceptor"], &origin);
+
+        let events = state.process_arrival(&String::from("carol"), &MovableUnit::Fleet(fleet_id.clone()), &alice_home);
+
+        assert!(matches!(&events[..], [GameEvent::FleetArrived { kind: ArrivalKind::Friendly, .. }]));
+        assert_eq!(state.players["alice"].ships.len(), 1);
+        assert_eq!(state.players["carol"].ships.len(), 2);
+    }
+
+    #[test]
+    fn test_allied_planets_cannot_be_bombarded_or_invaded() {
+        let mut state = fixture_team_game(1);
+        let alice_home = test_support::home_planet(&state, "alice");
+        fleet_of(&mut state, "carol", &["ark", "ravager"], &alice_home);
+        end_turn(&mut state);
+        end_turn(&mut state);
+        assert_eq!(state.current_player(), "carol");
+
+        for command in ["fleet bombard fleet_1", "fleet colonize fleet_1"] {
+            let result = parser::parse(command).unwrap().execute(&state);
+            assert!(matches!(result, Err(CommandError::InvalidArgument { reason, .. }) if reason.contains("allied")));
+        }
+    }
+
+    #[test]
+    fn test_team_wins_together_after_losing_a_member() {
+        let mut state = fixture_team_game(1);
+        let [alice_home, bob_home, carol_home, dave_home] =
+            test_support::FIXTURE_TEAM_PLAYERS.map(|id| test_support::home_planet(&state, id));
+
+        // Bob eliminates carol, then alice takes every enemy planet, carol's old home included
+        invade(&mut state, "bob", &carol_home);
+        assert!(state.players["carol"].planets.is_empty());
+        for planet_id in [&bob_home, &dave_home, &carol_home] {
+            invade(&mut state, "alice", planet_id);
+        }
+        assert_eq!(state.players["alice"].planets, [alice_home, bob_home, dave_home, carol_home]);
+
+        let events: Vec<_> = (0..4).flat_map(|_| end_turn(&mut state)).collect();
+
+        let victory = events.iter().find_map(|event| match event {
+            GameEvent::Victory { player_names, .. } => Some(player_names.clone()),
+            _ => None,
+        });
+        assert_eq!(victory, Some(vec![String::from("Alice"), String::from("Carol")]));
+    }
+
+    #[test]
+    fn test_team_still_playing_while_an_opponent_holds_a_planet() {
+        let mut state = fixture_team_game(1);
+        let bob_home = test_support::home_planet(&state, "bob");
+        invade(&mut state, "alice", &bob_home);
+
+        let events: Vec<_> = (0..4).flat_map(|_| end_turn(&mut state)).collect();
+
+        assert!(!events.iter().any(|event| matches!(event, GameEvent::Victory { .. })));
+    }
+
+    #[test]
+    fn test_status_player_lists_teammates_and_team_totals() {
+        let state = fixture_team_game(1);
+
+        let effect = parser::parse("status player").unwrap().execute(&state).unwrap();
+        let CommandEffect::None { message } = effect else {
+            panic!("Status must only produce a message");
+        };
+
+        assert!(message.contains("TEAM 1\n  Carol [CA] - 1 planet(s)"), "{message}");
+        assert!(message.contains("    Planets: 2\n"), "{message}");
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::engine::clock::Clock;
+use crate::engine::game_event::GameEvent;
+
+use super::{GameState, ProcessingPhase, TurnPhaseContext};
+
+/// Time spent in each processing phase and what was processed, for profiling long rounds.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PhaseTimings {
+    /// Microseconds spent in each phase
+    pub micros: BTreeMap<ProcessingPhase, u64>,
+    /// Planets whose production was resolved
+    pub planets_processed: u64,
+    pub battles_resolved: u64,
+}
+
+impl PhaseTimings {
+    pub fn total_micros(&self) -> u64 {
+        self.micros.values().sum()
+    }
+
+    fn add_phase(&mut self, phase: ProcessingPhase, micros: u64) {
+        *self.micros.entry(phase).or_default() += micros;
+    }
+}
+
+/// How long processing took, measured only while the game state has a clock. Saved with the
+/// game, so totals carry on after loading, but left out of its digest.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RoundTimings {
+    /// Rounds processed with a clock
+    pub rounds: u32,
+    /// The last round processed, and production at the start of the turns since
+    pub last_round: PhaseTimings,
+    /// Every round added up
+    pub cumulative: PhaseTimings,
+}
+
+impl RoundTimings {
+    pub fn is_empty(&self) -> bool {
+        self.rounds == 0 && self.cumulative == PhaseTimings::default()
+    }
+
+    fn add_phase(&mut self, phase: ProcessingPhase, micros: u64) {
+        self.last_round.add_phase(phase, micros);
+        self.cumulative.add_phase(phase, micros);
+    }
+}
+
+impl GameState {
+    /// Times processing with `clock` from here on.
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = Some(clock);
+        self.phase_started = None;
+    }
+
+    /// Starts timing a new round, when the game state has a clock.
+    pub(super) fn begin_timed_round(&mut self) {
+        if self.clock.is_some() {
+            self.timings.rounds += 1;
+            self.timings.last_round = PhaseTimings::default();
+        }
+    }
+
+    /// Closes the timing of the processing phase under way, if any, and starts the next one.
+    /// Called whenever the phase changes, so each reading of the clock ends one phase and
+    /// starts the next.
+    pub(super) fn clock_phase_change(&mut self) {
+        let Some(now) = self.clock.as_ref().map(|clock| clock.elapsed()) else {
+            return;
+        };
+        if let (TurnPhaseContext::Processing { phase, .. }, Some(started)) = (&self.phase, self.phase_started) {
+            let micros = now.saturating_sub(started).as_micros();
+            self.timings.add_phase(*phase, u64::try_from(micros).unwrap_or(u64::MAX));
+        }
+        self.phase_started = Some(now);
+    }
+
+    /// Counts planets and battles resolved during processing, when the game state has a clock.
+    pub(super) fn count_processed(&mut self, planets: u64, battles: u64) {
+        if self.clock.is_some() && matches!(self.phase, TurnPhaseContext::Processing { .. }) {
+            for timings in [&mut self.timings.last_round, &mut self.timings.cumulative] {
+                timings.planets_processed += planets;
+                timings.battles_resolved += battles;
+            }
+        }
+    }
+
+    /// A warning when the round just processed took longer than `options.slow_round_ms`.
+    pub(super) fn slow_round_warning(&self) -> Option<GameEvent> {
+        let threshold_ms = self.options.slow_round_ms;
+        let elapsed = Duration::from_micros(self.timings.last_round.total_micros());
+        (self.clock.is_some() && threshold_ms > 0 && elapsed > Duration::from_millis(threshold_ms.into()))
+            .then(|| GameEvent::SlowRound {
+                turn: self.turn,
+                elapsed_ms: u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+                threshold_ms,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::clock::MockClock;
+    use crate::engine::game_rules::ProductionTiming;
+    use crate::test_support::{end_round, fixture_game};
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    /// A game producing at the end of rounds, so every phase runs once a round, timed by the
+    /// returned clock.
+    fn clocked() -> (GameState, MockClock) {
+        let mut state = fixture_game(1);
+        state.rules.production_timing = ProductionTiming::RoundEnd;
+        let clock = MockClock::new();
+        state.set_clock(Arc::new(clock.clone()));
+        (state, clock)
+    }
+
+    /// Makes the next round spend 1 ms in intel, 2 ms in bombardment and so on, one more
+    /// millisecond each of the eight phases, 36 ms in all.
+    fn queue_round(clock: &MockClock) {
+        clock.queue_steps((0..=8).map(ms));
+    }
+
+    #[test]
+    fn test_each_phase_is_timed_and_rounds_add_up() {
+        let mut state = fixture_game(1);
+        end_round(&mut state);
+        assert!(state.timings.is_empty(), "Nothing is timed without a clock");
+
+        let (mut state, clock) = clocked();
+        queue_round(&clock);
+        end_round(&mut state);
+
+        // Production runs within the completion phase, whose time it does not count towards
+        let last = &state.timings.last_round;
+        assert_eq!(last.micros[&ProcessingPhase::Intel], 1_000);
+        assert_eq!(last.micros[&ProcessingPhase::Completions], 4_000);
+        assert_eq!(last.micros[&ProcessingPhase::Production], 5_000);
+        assert_eq!(last.micros[&ProcessingPhase::Victory], 8_000);
+        assert_eq!(last.total_micros(), 36_000);
+        assert_eq!(last.planets_processed, 2, "Alice's and Bob's home planets produce");
+
+        queue_round(&clock);
+        clock.advance(ms(100));
+        end_round(&mut state);
+
+        // Time passing between rounds counts for no phase
+        assert_eq!(state.timings.rounds, 2);
+        assert_eq!(state.timings.last_round.total_micros(), 36_000);
+        assert_eq!(state.timings.cumulative.total_micros(), 72_000);
+        assert_eq!(state.timings.cumulative.micros[&ProcessingPhase::Supply], 14_000);
+        assert_eq!(state.timings.cumulative.planets_processed, 4);
+    }
+
+    #[test]
+    fn test_rounds_over_the_threshold_warn_and_timings_stay_out_of_the_digest() {
+        let (mut state, clock) = clocked();
+        state.options.slow_round_ms = 36;
+        queue_round(&clock);
+        assert!(!end_round(&mut state).iter().any(|event| matches!(event, GameEvent::SlowRound { .. })));
+
+        state.options.slow_round_ms = 35;
+        queue_round(&clock);
+        let events = end_round(&mut state);
+        assert!(events.contains(&GameEvent::SlowRound { turn: 2, elapsed_ms: 36, threshold_ms: 35 }), "{events:?}");
+
+        let digest = state.digest();
+        state.timings = RoundTimings::default();
+        assert_eq!(state.digest(), digest);
+    }
+}
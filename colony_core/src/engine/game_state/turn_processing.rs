@@ -0,0 +1,1079 @@
+use crate::engine::game_event::{GameEvent, VictoryKind};
+use crate::engine::game_rules::ProductionTiming;
+use crate::engine::pending_action::{ActionType, CONSTRUCTION_HOLD_TURNS, HeldAction, PendingAction};
+use crate::engine::planet::PlanetId;
+use crate::engine::player::PlayerId;
+use crate::engine::resources::Resources;
+use crate::engine::rules::{self, Reason};
+use crate::engine::ship::{FleetId, MovableUnit};
+use crate::engine::structure::{Structure, StructureId};
+
+use super::{GameState, ProcessingPhase, ShieldEstimate};
+use super::alerts::scan_alerts;
+
+impl GameState {
+    /// Passes the turn to the next player and, once every player has played,
+    /// runs end-of-round processing. Returns the events produced.
+    pub(super) fn end_turn(&mut self) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+
+        // Rotate player order - move current player to back of queue
+        let acting_player = self.current_player().clone();
+        self.acted_this_round.insert(acting_player);
+        self.players_order.rotate_left(1);
+
+        // Check if all players have played this turn
+        if self.players_order.iter().all(|player_id| self.acted_this_round.contains(player_id)) {
+            self.acted_this_round.clear();
+
+            // Sightings and sentry alarms only describe the round being processed
+            for player in self.players.values_mut() {
+                player.recent_sightings.clear();
+                for fleet in player.fleets.values_mut() {
+                    fleet.woken_by = None;
+                }
+            }
+
+            // Departing fleets report what they saw before the round changes it
+            self.begin_timed_round();
+            self.begin_phase(ProcessingPhase::Intel);
+            self.refresh_intel();
+
+            // Process bombardments first (happens every turn for ongoing bombardments)
+            self.begin_phase(ProcessingPhase::Bombardments);
+            let bombarded = self.bombarded_planets();
+            let mut bombardment_events = self.process_bombardments();
+
+            // Planets bombarded or blockaded this round grow restless, quiet ones calm down
+            self.begin_phase(ProcessingPhase::Unrest);
+            bombardment_events.extend(self.update_unrest(&bombarded));
+
+            // Overcharged shields only hold through this round's bombardments
+            for planet in self.map.planets.values_mut() {
+                planet.clear_shield_overcharge();
+                planet.tick_consolidation();
+            }
+
+            // Then process pending actions for ALL players at end of turn
+            self.begin_phase(ProcessingPhase::Completions);
+            let mut completion_events = self.process_all_pending_actions();
+            self.begin_phase(ProcessingPhase::GateLinks);
+            completion_events.extend(self.collapse_broken_gate_links());
+            // Supply follows this round's moves and conquests
+            self.begin_phase(ProcessingPhase::Supply);
+            completion_events.extend(self.process_supply());
+
+            if !bombardment_events.is_empty() || !completion_events.is_empty() {
+                events.push(GameEvent::RoundProcessing { turn: self.turn });
+                events.extend(bombardment_events);
+                events.extend(completion_events);
+            }
+
+            // Check for win condition
+            self.begin_phase(ProcessingPhase::Victory);
+            let mut victory = None;
+            if self.rules.victory.allows_elimination() {
+                victory = self.winning_side().map(|winners| (winners, VictoryKind::Elimination));
+            }
+            if self.rules.victory.allows_domination() {
+                let (warnings, dominating) = self.update_domination();
+                events.extend(warnings);
+                victory = victory.or(dominating);
+            }
+            if let Some((winners, kind)) = victory {
+                let player_names = winners.iter()
+                    .map(|winner_id| self.players.get(winner_id).expect("Winner must exist").name.clone())
+                    .collect();
+                events.push(GameEvent::Victory { player_names, turn: self.turn, kind });
+                self.finish();
+                self.end_processing();
+                events.extend(self.slow_round_warning());
+                return events;
+            }
+
+            // Old entries go while the round just played still counts as the current turn
+            self.compact();
+            self.end_processing();
+            events.extend(self.slow_round_warning());
+            self.turn += 1;
+
+            events.push(GameEvent::TurnBegan { turn: self.turn, protection_turns_left: self.protection_turns_left() });
+        } else {
+            let player_names = self.player_names(self.players_waiting());
+            events.push(GameEvent::WaitingOn { turn: self.turn, player_names });
+        }
+
+        // Planets produce just before their owner plays, so alerts already see the new stock
+        if self.rules.production_timing == ProductionTiming::TurnStart && self.turn > 1 {
+            let next_player_id = self.current_player().clone();
+            self.begin_phase(ProcessingPhase::Production);
+            self.act_for(&next_player_id);
+            events.extend(self.produce_resources(|owner| owner == &next_player_id));
+            self.end_processing();
+        }
+
+        let next_player = self.players.get(self.current_player())
+            .expect("Player in rotation must exist in players map");
+        events.push(GameEvent::PlayerTurnStarted {
+            player_name: next_player.name.clone(),
+            alerts: scan_alerts(self, &next_player.id),
+            sightings: next_player.recent_sightings.clone(),
+            notes: next_player.notes.len(),
+        });
+
+        events
+    }
+
+    /// Process bombardment actions for ALL players at the end of a full turn.
+    /// Bombardments deal damage each turn until shields are destroyed. Several bombardments of
+    /// one planet stack: they land in turn order, each against the shield the ones before left,
+    /// and each is credited to its own attacker. The hit that breaks the shield gets the
+    /// shields-down event; later hits that round pass through and end their bombardment.
+    fn process_bombardments(&mut self) -> Vec<GameEvent> {
+        let mut bombardment_events = Vec::new();
+
+        // Iterate in turn order so results are reproducible
+        let player_ids: Vec<_> = self.players_order.iter().cloned().collect();
+
+        for player_id in player_ids {
+            self.act_for(&player_id);
+            // Collect bombardment actions for this player
+            let bombardment_actions: Vec<_> = {
+                let player = self.players.get(&player_id)
+                    .expect("Player must exist");
+
+                player.pending_actions.iter()
+                    .filter_map(|action| {
+                        if let ActionType::BombardPlanet(fleet_id, planet_id) = &action.action_type {
+                            Some((fleet_id.clone(), planet_id.clone(), action.target_structure.clone()))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect()
+            };
+
+            // Process each bombardment
+            for (fleet_id, planet_id, target_structure) in bombardment_actions {
+                // Garrisoned ships shoot back, absorbing part of the bombardment
+                let fleet_power = self.calculate_fleet_bombardment(&player_id, &fleet_id);
+                let intercepted = self.garrison_fire(&planet_id).min(fleet_power);
+                let bombardment_power = fleet_power - intercepted;
+                if intercepted > 0 {
+                    bombardment_events.push(GameEvent::BombardmentIntercepted {
+                        player_id: player_id.clone(),
+                        fleet_id: fleet_id.clone(),
+                        planet_name: self.planet_name(&planet_id),
+                        intercepted,
+                    });
+                }
+
+                // Each hit lands on the shield as the hits before it left it
+                let planet = self.map.planets.get_mut(&planet_id)
+                    .expect("Planet must exist");
+
+                let shields_before = planet.get_shield_hp();
+                let overflow = planet.take_shield_damage(bombardment_power);
+                let shields_after = planet.get_shield_hp();
+                let max_shield_hp = planet.get_max_shield_hp();
+                let planet_name = planet.name.clone();
+                let owner = planet.get_owner().clone();
+
+                let structure_events = self.strike_structures(&player_id, &fleet_id, &planet_id, target_structure, overflow);
+
+                let stats = &mut self.players.get_mut(&player_id).expect("Player must exist").bombardment;
+                stats.shield_damage = stats.shield_damage.saturating_add(bombardment_power - overflow);
+                stats.overflow_damage = stats.overflow_damage.saturating_add(overflow);
+
+                if shields_before == 0 {
+                    // An earlier hit brought the shields down; this one only passes through
+                    bombardment_events.push(GameEvent::BombardmentOverflow {
+                        player_id: player_id.clone(),
+                        fleet_id: fleet_id.clone(),
+                        planet_name,
+                        damage: overflow,
+                    });
+                    bombardment_events.extend(structure_events);
+                    self.end_bombardment(&player_id, &fleet_id, &planet_id);
+                } else {
+                    if shields_after == 0 {
+                        stats.shields_broken += 1;
+                        bombardment_events.push(GameEvent::ShieldsDestroyed {
+                            player_id: player_id.clone(),
+                            fleet_id: fleet_id.clone(),
+                            planet_name: planet_name.clone(),
+                            shields_before,
+                            overflow,
+                        });
+                        bombardment_events.extend(structure_events);
+
+                        // Remove the bombardment action since shields are down
+                        self.end_bombardment(&player_id, &fleet_id, &planet_id);
+                    } else {
+                        bombardment_events.push(GameEvent::ShieldsDamaged {
+                            player_id: player_id.clone(),
+                            fleet_id,
+                            planet_name: planet_name.clone(),
+                            shields_before,
+                            shields_after,
+                        });
+                    }
+
+                    // Everyone else watching the planet only learns roughly how the shield holds
+                    let attacker_name = self.players[&player_id].name.clone();
+                    for observer_id in self.bombardment_observers(&player_id, &planet_id) {
+                        bombardment_events.push(if shields_after == 0 {
+                            GameEvent::ShieldsDownObserved {
+                                own_planet: owner.as_ref() == Some(&observer_id),
+                                observer_id,
+                                attacker_name: attacker_name.clone(),
+                                planet_name: planet_name.clone(),
+                            }
+                        } else {
+                            GameEvent::BombardmentObserved {
+                                observer_id,
+                                attacker_name: attacker_name.clone(),
+                                planet_name: planet_name.clone(),
+                                shields: ShieldEstimate::of(shields_after, max_shield_hp),
+                            }
+                        });
+                    }
+                }
+            }
+        }
+
+        bombardment_events
+    }
+
+    /// Lands `damage` that passed through the shield of `planet_id` on its structures, aimed at
+    /// `target` first. The attacker learns what was hit and whether the target was there at
+    /// all; the owner learns what was torn down.
+    fn strike_structures(
+        &mut self,
+        player_id: &PlayerId,
+        fleet_id: &FleetId,
+        planet_id: &PlanetId,
+        target: Option<StructureId>,
+        damage: u32,
+    ) -> Vec<GameEvent> {
+        if damage == 0 {
+            return Vec::new();
+        }
+        let planet = self.map.planets.get_mut(planet_id)
+            .expect("Planet must exist");
+        let missing = target.clone().filter(|structure_id| !planet.has_built(structure_id));
+        let hits = planet.take_structure_damage(target.as_ref(), damage);
+        let (planet_name, owner) = (planet.name.clone(), planet.get_owner().clone());
+
+        let mut events = Vec::new();
+        if !hits.is_empty() {
+            events.push(GameEvent::StructuresBombarded {
+                player_id: player_id.clone(),
+                fleet_id: fleet_id.clone(),
+                planet_name: planet_name.clone(),
+                hits: hits.clone(),
+            });
+        }
+        if let Some(structure_id) = missing {
+            events.push(GameEvent::BombardmentTargetMissing {
+                player_id: player_id.clone(),
+                fleet_id: fleet_id.clone(),
+                planet_name: planet_name.clone(),
+                structure_id,
+            });
+        }
+        if let Some(owner) = owner.filter(|_| hits.iter().any(|hit| hit.destroyed)) {
+            self.invalidate_empire_effects(&owner);
+            let attacker_name = self.players[player_id].name.clone();
+            events.extend(hits.into_iter().filter(|hit| hit.destroyed).map(|hit| GameEvent::StructureDestroyed {
+                player_id: owner.clone(),
+                planet_name: planet_name.clone(),
+                structure_id: hit.structure_id,
+                attacker_name: attacker_name.clone(),
+            }));
+        }
+        events
+    }
+
+    /// Drops `player_id`'s bombardment of `planet_id` by `fleet_id` once the shields are down.
+    fn end_bombardment(&mut self, player_id: &PlayerId, fleet_id: &FleetId, planet_id: &PlanetId) {
+        let player = self.players.get_mut(player_id)
+            .expect("Player must exist");
+        player.pending_actions.retain(|action| {
+            !matches!(&action.action_type,
+                ActionType::BombardPlanet(fid, pid)
+                if fid == fleet_id && pid == planet_id)
+        });
+    }
+
+    /// Queues the next level after a repeating upgrade completed, repeating again, or tells the
+    /// player why the repetition stops: max level, missing resources or a blocked upgrade slot.
+    fn repeat_upgrade(&mut self, player_id: &PlayerId, planet_id: &PlanetId, structure_id: StructureId) -> GameEvent {
+        let planet_name = self.planet_name(planet_id);
+        match self.queue_upgrade(player_id, planet_id, &structure_id, true) {
+            Ok(upgrade_info) => GameEvent::UpgradeRepeated {
+                player_id: player_id.clone(),
+                structure_id,
+                planet_name,
+                cost: upgrade_info.cost,
+                turns: upgrade_info.turns,
+            },
+            Err(e) => GameEvent::UpgradeRepeatStopped {
+                player_id: player_id.clone(),
+                structure_id,
+                planet_name,
+                reason: e.to_string(),
+            },
+        }
+    }
+
+    /// Puts up a finished structure build. A build whose prerequisite was lost while it was under
+    /// construction, or is still under construction itself, is held instead, `held_turns` giving
+    /// the rounds it had left if it was held already, and refunded once the hold runs out. The
+    /// hold only runs down while the prerequisite is not being built. Rejected builds leave no
+    /// structure behind.
+    fn complete_structure_build(
+        &mut self,
+        player_id: &PlayerId,
+        action: PendingAction,
+        structure_id: StructureId,
+        held_turns: Option<u32>,
+    ) -> Option<GameEvent> {
+        // Another player may have completed a limited structure first
+        if let Err(e) = self.check_structure_limit(player_id, &structure_id, false) {
+            let planet = self.map.planets.get_mut(&action.planet_id)
+                .expect("Planet must exist for pending action");
+            planet.clear_construction_site(&structure_id);
+            let refunded = planet.refund(&action.reserved_resources);
+            return Some(GameEvent::ConstructionRejected {
+                player_id: player_id.clone(),
+                structure_id,
+                planet_name: planet.name.clone(),
+                reason: e.to_string(),
+                refunded,
+            });
+        }
+
+        let planet = self.map.planets.get_mut(&action.planet_id)
+            .expect("Planet must exist for pending action");
+
+        if let Err(Reason::MissingPrerequisite { prerequisite, required_level }) =
+            rules::can_complete_build(planet, &structure_id, &self.structure_config)
+        {
+            let planet_name = planet.name.clone();
+            let awaited_in_construction = planet.get_structures().get(&prerequisite)
+                .is_some_and(Structure::is_under_construction);
+            let turns_left = match held_turns {
+                None => CONSTRUCTION_HOLD_TURNS,
+                Some(turns) if awaited_in_construction => turns,
+                Some(turns) => turns.saturating_sub(1),
+            };
+            if turns_left == 0 {
+                planet.clear_construction_site(&structure_id);
+                let refunded = planet.refund(&action.reserved_resources);
+                return Some(GameEvent::ConstructionRejected {
+                    player_id: player_id.clone(),
+                    structure_id,
+                    planet_name,
+                    reason: format!("{} was not restored within {} turn(s)", prerequisite, CONSTRUCTION_HOLD_TURNS),
+                    refunded,
+                });
+            }
+
+            let player = self.players.get_mut(player_id).expect("Player must exist");
+            player.held_actions.push(HeldAction {
+                action,
+                awaiting: prerequisite.clone(),
+                required_level,
+                turns_left,
+            });
+            // Only the build that just finished is reported; held ones wait quietly
+            return held_turns.is_none().then(|| GameEvent::ConstructionHalted {
+                player_id: player_id.clone(),
+                structure_id,
+                planet_name,
+                awaiting: prerequisite,
+                required_level,
+                turns: turns_left,
+            });
+        }
+
+        match planet.complete_build_structure(structure_id.clone(), &self.structure_config) {
+            Ok(()) => {
+                planet.recalculate_from_structures();
+                let planet_name = planet.name.clone();
+                self.invalidate_empire_effects(player_id);
+                Some(GameEvent::ConstructionCompleted {
+                    player_id: player_id.clone(),
+                    structure_id,
+                    planet_name,
+                })
+            }
+            Err(e) => {
+                planet.clear_construction_site(&structure_id);
+                Some(GameEvent::ConstructionFailed {
+                    player_id: player_id.clone(),
+                    structure_id,
+                    planet_name: planet.name.clone(),
+                    reason: e.to_string(),
+                })
+            }
+        }
+    }
+
+    /// Process pending actions for ALL players at the end of a full turn.
+    /// Returns events describing completed actions.
+    fn process_all_pending_actions(&mut self) -> Vec<GameEvent> {
+        let mut completion_events = Vec::new();
+
+        // Iterate in turn order so results are reproducible
+        let player_ids: Vec<_> = self.players_order.iter().cloned().collect();
+
+        for player_id in player_ids {
+            self.act_for(&player_id);
+            // Builds held from earlier rounds are retried after this round's completions,
+            // which may have restored what they wait for
+            let held_actions = std::mem::take(
+                &mut self.players.get_mut(&player_id).expect("Player must exist").held_actions
+            );
+
+            // Tick and collect completed actions for this player
+            let completed_actions = {
+                let player = self.players.get_mut(&player_id)
+                    .expect("Player must exist");
+
+                // Decrement all cooldowns
+                for action in player.pending_actions.iter_mut() {
+                    action.tick();
+                }
+
+                // Collect completed actions (cooldown reached 0)
+                let mut completed = Vec::new();
+                player.pending_actions.retain(|action| {
+                    if action.is_complete() {
+                        completed.push(action.clone());
+                        false // Remove from pending
+                    } else {
+                        true // Keep in pending
+                    }
+                });
+                // By stage, then in the order the actions were queued
+                completed.sort_by_key(|action| action.action_type.completion_stage());
+                completed
+            };
+
+            // Execute completed actions for this player
+            for action in completed_actions {
+                completion_events.extend(self.complete_action(&player_id, action));
+            }
+
+            for held in held_actions {
+                if let Some(reason) = self.orphan_reason(&player_id, &held.action) {
+                    completion_events.push(GameEvent::ActionOrphaned {
+                        player_id: player_id.clone(),
+                        action: self.describe_action(&held.action),
+                        reason,
+                        refund: None,
+                    });
+                    continue;
+                }
+                if let ActionType::BuildStructure(structure_id) = held.action.action_type.clone() {
+                    completion_events.extend(
+                        self.complete_structure_build(&player_id, held.action, structure_id, Some(held.turns_left))
+                    );
+                }
+            }
+        }
+
+        if self.rules.production_timing == ProductionTiming::RoundEnd {
+            self.begin_phase(ProcessingPhase::Production);
+            completion_events.extend(self.produce_resources(|_| true));
+        }
+
+        completion_events
+    }
+
+    /// Carries out a pending action of `player_id`'s whose time has come, or reports it
+    /// orphaned when what it needed is gone. The caller has taken it off the queue.
+    pub(super) fn complete_action(&mut self, player_id: &PlayerId, action: PendingAction) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+        // Cleanup on ownership changes and disbands should have cancelled these already
+        if let Some(reason) = self.orphan_reason(player_id, &action) {
+            events.push(GameEvent::ActionOrphaned {
+                player_id: player_id.clone(),
+                action: self.describe_action(&action),
+                reason,
+                refund: None,
+            });
+            return events;
+        }
+        match action.action_type {
+            ActionType::BuildStructure(ref structure_id) => {
+                let structure_id = structure_id.clone();
+                events.extend(self.complete_structure_build(player_id, action, structure_id, None));
+            }
+
+            ActionType::UpgradeStructure(structure_id) => {
+                let planet = self.map.planets.get_mut(&action.planet_id)
+                    .expect("Planet must exist for pending action");
+
+                match planet.complete_upgrade_structure(&structure_id) {
+                    Ok(()) => {
+                        planet.recalculate_from_structures();
+                        let planet_name = planet.name.clone();
+                        self.invalidate_empire_effects(player_id);
+                        events.push(GameEvent::UpgradeCompleted {
+                            player_id: player_id.clone(),
+                            structure_id: structure_id.clone(),
+                            planet_name,
+                        });
+                        if action.auto_repeat {
+                            events.push(self.repeat_upgrade(player_id, &action.planet_id, structure_id));
+                        }
+                    }
+                    Err(e) => {
+                        events.push(GameEvent::UpgradeFailed {
+                            player_id: player_id.clone(),
+                            structure_id,
+                            planet_name: planet.name.clone(),
+                            reason: e.to_string(),
+                        });
+                    }
+                }
+            }
+
+            ActionType::BuildShip(ship_type, ship_instance_id) => {
+                // A delivered ship appears where it was sent, and rallies from there
+                let destination = action.deliver_to.clone().unwrap_or_else(|| action.planet_id.clone());
+                let planet_name = self.map.planets.get(&destination)
+                    .expect("Planet must exist for pending action")
+                    .name.clone();
+
+                let player = self.players.get_mut(player_id)
+                    .expect("Player must exist");
+                player.insert_ship(ship_instance_id.clone(), ship_type.clone(), destination.clone());
+                let (fleet_name, rally_broken) =
+                    self.rally_new_ship(player_id, &destination, &ship_instance_id);
+
+                events.extend(rally_broken);
+                events.push(GameEvent::ShipBuilt {
+                    player_id: player_id.clone(),
+                    ship_instance_id,
+                    ship_type,
+                    planet_name,
+                    fleet_name,
+                });
+            }
+
+            ActionType::RetrofitShip(to_type, ship_instance_id) => {
+                let planet_name = self.map.planets.get(&action.planet_id)
+                    .expect("Planet must exist for pending action")
+                    .name.clone();
+
+                let player = self.players.get_mut(player_id)
+                    .expect("Player must exist");
+                match player.ships.get_mut(&ship_instance_id) {
+                    Some(ship) if ship.location == action.planet_id => {
+                        let from_type = std::mem::replace(&mut ship.ship_type, to_type.clone());
+                        let fleet_id = ship.fleet_id.clone();
+                        events.push(GameEvent::ShipRetrofitted {
+                            player_id: player_id.clone(),
+                            ship_instance_id,
+                            from_type,
+                            to_type,
+                            planet_name,
+                        });
+
+                        // Cargo the refitted holds no longer take is left at the shipyard
+                        if let Some(fleet_id) = fleet_id {
+                            let overflow = self.take_cargo_overflow(player_id, &fleet_id);
+                            if overflow != Resources::default() {
+                                events.push(self.unload_cargo(player_id, &fleet_id, &action.planet_id, overflow));
+                            }
+                        }
+                    }
+                    ship => {
+                        let reason = if ship.is_some() { "ship left the shipyard" } else { "ship was lost" };
+                        events.push(GameEvent::ShipRetrofitFailed {
+                            player_id: player_id.clone(),
+                            ship_instance_id,
+                            planet_name,
+                            reason: String::from(reason),
+                        });
+                    }
+                }
+            }
+
+            ActionType::MoveFleet(fleet_id, destination) => {
+                events.extend(self.complete_move(player_id, &MovableUnit::Fleet(fleet_id), &destination));
+            }
+
+            ActionType::MoveShip(ship_id, destination) => {
+                events.extend(self.complete_move(player_id, &MovableUnit::Ship(ship_id), &destination));
+            }
+
+            ActionType::BombardPlanet(_, _) => {
+                // Bombardment actions complete when shields hit 0, handled in process_bombardments
+                // This case should not be reached since bombardments are removed when shields hit 0
+            }
+        }
+        events
+    }
+
+    /// Produces resources on the colonized planets whose owner matches `owned_by`, boosted by
+    /// the owner's empire effects. Structures are first shut down or restarted to fit each
+    /// planet's energy production, so the effects count only what still runs.
+    fn produce_resources(&mut self, owned_by: impl Fn(&PlayerId) -> bool) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+        let mut rationed = Vec::new();
+        for planet in self.map.planets.values_mut() {
+            if let Some(owner) = planet.get_owner().clone().filter(|owner| owned_by(owner)) {
+                let (shut_down, restarted) = planet.balance_energy();
+                if !shut_down.is_empty() || !restarted.is_empty() {
+                    events.push(GameEvent::PowerRationed {
+                        player_id: owner.clone(),
+                        planet_name: planet.name.clone(),
+                        shut_down,
+                        restarted,
+                    });
+                    rationed.push(owner);
+                }
+            }
+        }
+        for owner in &rationed {
+            self.invalidate_empire_effects(owner);
+        }
+
+        let mut produced = 0;
+        self.refresh_empire_effects();
+        for planet in self.map.planets.values_mut() {
+            if let Some(owner) = planet.get_owner().clone().filter(|owner| owned_by(owner)) {
+                produced += 1;
+                let bonus_percent = self.players.get(&owner)
+                    .and_then(|player| player.empire_effects)
+                    .map_or(0, |effects| effects.production_bonus_percent);
+                let penalty_percent = self.rules.unrest.production_penalty_percent(planet.get_unrest());
+                planet.produce_resources(bonus_percent, penalty_percent, self.rules.allocation_shift_percent);
+            }
+        }
+        self.count_processed(produced, 0);
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::commands::command::CommandEffect;
+    use crate::engine::commands::status;
+    use crate::engine::game_event::GameEvent;
+    use crate::engine::game_rules::ProductionTiming;
+    use crate::engine::game_state::GameState;
+    use crate::engine::pending_action::{ActionType, PendingAction};
+    use crate::engine::planet::{PlanetId, StructureHit};
+    use crate::engine::player::BombardmentStats;
+    use crate::engine::resources::Resources;
+    use crate::engine::ship::FleetId;
+    use crate::test_support::{self, end_round, fixture_game};
+
+    /// For each player in turn order: how many turns other players took between the player
+    /// ending their first turn and their home planet's first production.
+    fn turns_waited_for_production(timing: ProductionTiming) -> Vec<(String, usize)> {
+        let mut state = fixture_game(1);
+        state.rules.production_timing = timing;
+        let order: Vec<_> = state.players_order.iter().cloned().collect();
+        for player_id in &order {
+            let home = test_support::home_planet(&state, player_id);
+            state.map.planets.get_mut(&home).unwrap().available_resources = Resources::default();
+        }
+        let home_stock = |state: &GameState, player_id: &str| {
+            state.map.planets[&test_support::home_planet(state, player_id)].available_resources.clone()
+        };
+
+        // Who ended each turn, and which homes had produced by then
+        let mut turns = Vec::new();
+        for _ in 0..2 * order.len() {
+            let player_id = state.current_player().clone();
+            let player_name = state.players[&player_id].name.clone();
+            state.apply_effect(CommandEffect::EndTurn { player_name }, &player_id).unwrap();
+            let produced: Vec<_> = order.iter().map(|id| home_stock(&state, id) != Resources::default()).collect();
+            turns.push((player_id, produced));
+        }
+
+        order.iter().enumerate().map(|(index, player_id)| {
+            let first_turn = turns.iter().position(|(ender, _)| ender == player_id).unwrap();
+            let produced_at = turns.iter().position(|(_, produced)| produced[index]).unwrap();
+            let others = turns[first_turn + 1..=produced_at].iter().filter(|(ender, _)| ender != player_id).count();
+            (player_id.clone(), others)
+        }).collect()
+    }
+
+    #[test]
+    fn test_round_end_production_favours_the_last_player() {
+        let waited = turns_waited_for_production(ProductionTiming::RoundEnd);
+
+        // Alice waits out bob's turn, bob's production lands as soon as he ends his
+        assert_eq!(waited, [(String::from("alice"), 1), (String::from("bob"), 0)]);
+    }
+
+    #[test]
+    fn test_turn_start_production_treats_every_seat_alike() {
+        let waited = turns_waited_for_production(ProductionTiming::TurnStart);
+
+        assert_eq!(waited, [(String::from("alice"), 1), (String::from("bob"), 1)]);
+    }
+
+    #[test]
+    fn test_forecast_matches_production_under_either_timing() {
+        for timing in [ProductionTiming::TurnStart, ProductionTiming::RoundEnd] {
+            let mut state = fixture_game(1);
+            state.rules.production_timing = timing;
+            let home = test_support::home_planet(&state, "alice");
+            state.map.planets.get_mut(&home).unwrap().available_resources = Resources::default();
+            let planet = &state.map.planets[&home];
+            let forecast = planet.forecast(&[], &state.structure_config, 0, 1);
+
+            for player_name in ["Alice", "Bob"] {
+                let player_id = state.current_player().clone();
+                state.apply_effect(CommandEffect::EndTurn { player_name: player_name.to_string() }, &player_id).unwrap();
+            }
+
+            assert_eq!(state.current_player(), "alice");
+            assert_eq!(state.map.planets[&home].available_resources, forecast[0], "{timing}");
+        }
+    }
+
+    /// Ends the current player's turn; returns the turn number afterwards.
+    fn end_current_turn(state: &mut GameState) -> u32 {
+        let player_id = state.current_player().clone();
+        let player_name = state.players[&player_id].name.clone();
+        state.apply_effect(CommandEffect::EndTurn { player_name }, &player_id).unwrap();
+        state.turn
+    }
+
+    #[test]
+    fn test_ending_a_turn_mid_round_announces_who_is_left() {
+        let mut state = test_support::fixture_team_game(1);
+        let events = state.apply_effect(CommandEffect::EndTurn { player_name: String::from("Alice") }, &String::from("alice")).unwrap();
+
+        assert!(events.iter().any(|event| matches!(event,
+            GameEvent::WaitingOn { turn: 1, player_names } if player_names == &["Bob", "Carol", "Dave"])), "{events:?}");
+        assert_eq!(state.players_acted(), ["alice"]);
+        assert_eq!(state.players_waiting(), ["bob", "carol", "dave"]);
+
+        for _ in 0..2 {
+            end_current_turn(&mut state);
+        }
+        let events = state.apply_effect(CommandEffect::EndTurn { player_name: String::from("Dave") }, &String::from("dave")).unwrap();
+
+        assert!(!events.iter().any(|event| matches!(event, GameEvent::WaitingOn { .. })));
+        assert!(state.players_acted().is_empty());
+        assert_eq!(state.players_waiting(), ["alice", "bob", "carol", "dave"]);
+    }
+
+    #[test]
+    fn test_round_ends_on_time_after_a_player_who_acted_leaves() {
+        let mut state = test_support::fixture_team_game(1);
+        assert_eq!([end_current_turn(&mut state), end_current_turn(&mut state)], [1, 1]);
+
+        state.remove_from_rotation(&String::from("alice"));
+
+        assert_eq!(end_current_turn(&mut state), 1);
+        assert_eq!(end_current_turn(&mut state), 2);
+        assert_eq!(state.current_player(), "bob");
+        assert_eq!(state.players_order, ["bob", "carol", "dave"]);
+        assert!(state.acted_this_round.is_empty());
+    }
+
+    #[test]
+    fn test_round_ends_on_time_after_a_player_who_had_not_acted_leaves() {
+        let mut state = test_support::fixture_team_game(1);
+        assert_eq!(end_current_turn(&mut state), 1);
+
+        state.remove_from_rotation(&String::from("dave"));
+
+        assert_eq!(end_current_turn(&mut state), 1);
+        assert_eq!(end_current_turn(&mut state), 2);
+        assert_eq!(state.current_player(), "alice");
+        assert_eq!(end_current_turn(&mut state), 2);
+    }
+
+    /// Alice's home with capital Lv2 and a storage complex, a mining complex queued on it and
+    /// the storage complex then bombarded away.
+    fn mining_complex_losing_storage(state: &mut GameState) -> PlanetId {
+        let home = test_support::home_planet(state, "alice");
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.complete_upgrade_structure(&String::from("planetary_capital")).unwrap();
+        planet.complete_build_structure(String::from("storage_complex"), &state.structure_config).unwrap();
+        planet.recalculate_from_structures();
+        planet.available_resources = Resources { minerals: 1000, gas: 1000, energy: 1000 };
+        let effect = CommandEffect::BuildStructure { planet_id: home.clone(), structure_id: String::from("mining_complex") };
+        state.apply_effect(effect, &String::from("alice")).unwrap();
+
+        state.map.planets.get_mut(&home).unwrap().remove_structure(&String::from("storage_complex"));
+        home
+    }
+
+    fn completed(events: &[GameEvent], structure: &str) -> bool {
+        events.iter().any(|e| matches!(e, GameEvent::ConstructionCompleted { structure_id, .. } if structure_id == structure))
+    }
+
+    #[test]
+    fn test_structures_complete_before_ships_queued_earlier() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let player = state.players.get_mut("alice").unwrap();
+        let ship_id = player.generate_ship_id(&String::from("interceptor"));
+        player.pending_actions.push(PendingAction::new(
+            ActionType::BuildShip(String::from("interceptor"), ship_id), home.clone(), 1, Resources::default(),
+        ));
+        player.pending_actions.push(PendingAction::new(
+            ActionType::BuildStructure(String::from("power_grid")), home, 1, Resources::default(),
+        ));
+
+        let events = end_round(&mut state);
+
+        let position = |wanted: fn(&GameEvent) -> bool| events.iter().position(wanted).unwrap();
+        let structure = position(|e| matches!(e, GameEvent::ConstructionCompleted { .. }));
+        let ship = position(|e| matches!(e, GameEvent::ShipBuilt { .. }));
+        assert!(structure < ship, "{events:?}");
+    }
+
+    #[test]
+    fn test_delivered_ship_appears_at_the_delivery_planet() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let colony = test_support::grant_planet(&mut state, "alice");
+        let player = state.players.get_mut("alice").unwrap();
+        let ship_id = player.generate_ship_id(&String::from("interceptor"));
+        let mut action = PendingAction::new(
+            ActionType::BuildShip(String::from("interceptor"), ship_id.clone()), home, 1, Resources::default(),
+        );
+        action.deliver_to = Some(colony.clone());
+        player.pending_actions.push(action);
+
+        let events = end_round(&mut state);
+
+        let colony_name = state.map.planets[&colony].name.clone();
+        assert!(events.iter().any(|e| matches!(e, GameEvent::ShipBuilt { planet_name, .. } if *planet_name == colony_name)));
+        assert_eq!(state.players["alice"].ships[&ship_id].location, colony);
+    }
+
+    #[test]
+    fn test_build_missing_a_prerequisite_is_held_until_it_is_rebuilt() {
+        let mut state = fixture_game(1);
+        let home = mining_complex_losing_storage(&mut state);
+        let mining = String::from("mining_complex");
+
+        end_round(&mut state);
+        let events = end_round(&mut state);
+
+        assert!(events.iter().any(|e| e.to_string().contains("construction halted: awaiting storage_complex")));
+        assert!(state.map.planets[&home].get_structures()[&mining].is_under_construction());
+        let args = status::StatusArgs { target: status::StatusTarget::Planet { id: home.clone() }, now: None };
+        let CommandEffect::None { message: status } = status::execute(args, &state).unwrap() else {
+            panic!("Status only displays")
+        };
+        assert!(status.contains("Building Mining Complex (mining_complex): construction halted: awaiting Storage Complex (storage_complex) Lv1 (refunded in 3 turns)"));
+
+        // The held build keeps its structure, but not the build slot the rebuild needs
+        let rebuild = |structure: &str| CommandEffect::BuildStructure { planet_id: home.clone(), structure_id: String::from(structure) };
+        assert!(state.apply_effect(rebuild("mining_complex"), &String::from("alice")).is_err());
+        state.apply_effect(rebuild("storage_complex"), &String::from("alice")).unwrap();
+
+        assert!(!completed(&end_round(&mut state), "mining_complex"));
+        let events = end_round(&mut state);
+
+        assert!(completed(&events, "storage_complex"));
+        assert!(completed(&events, "mining_complex"));
+        assert!(state.map.planets[&home].get_structures().contains_key(&mining));
+        assert!(state.players["alice"].held_actions.is_empty());
+    }
+
+    #[test]
+    fn test_held_build_is_refunded_when_the_hold_runs_out() {
+        let mut state = fixture_game(1);
+        let home = mining_complex_losing_storage(&mut state);
+        for _ in 0..4 {
+            end_round(&mut state);
+        }
+        assert_eq!(state.players["alice"].held_actions[0].turns_left, 1);
+
+        let events = end_round(&mut state);
+
+        assert!(events.iter().any(|e| matches!(e, GameEvent::ConstructionRejected { reason, .. }
+            if reason == "storage_complex was not restored within 3 turn(s)")));
+        assert!(state.players["alice"].held_actions.is_empty());
+        assert!(!state.map.planets[&home].get_structures().contains_key(&String::from("mining_complex")));
+    }
+
+    /// Bob's home with a defense shield up, in a four-player game without teams.
+    fn shielded_bob() -> (GameState, PlanetId) {
+        let mut state = test_support::fixture_team_game(1);
+        for player in state.players.values_mut() {
+            player.team = None;
+        }
+        let bob_home = test_support::home_planet(&state, "bob");
+        let planet = state.map.planets.get_mut(&bob_home).unwrap();
+        for structure_id in ["power_grid", "defense_shield"] {
+            planet.complete_build_structure(String::from(structure_id), &state.structure_config).unwrap();
+        }
+        // Shields come up with the planet's next turn processing
+        planet.process_turn();
+        assert_eq!(planet.get_shield_hp(), 100);
+        (state, bob_home)
+    }
+
+    /// Sends `ravagers` new ravagers of `player_id` to bombard `planet_id`.
+    fn besiege(state: &mut GameState, player_id: &str, ravagers: usize, planet_id: &PlanetId) -> FleetId {
+        let player_id = player_id.to_string();
+        let ship_ids: Vec<_> = (0..ravagers)
+            .map(|_| state.players.get_mut(&player_id).unwrap().add_ship(String::from("ravager"), planet_id.clone()))
+            .collect();
+        let first_ship = ship_ids[0].clone();
+        let create = CommandEffect::CreateFleet { name: String::from("siege"), ship_ids, location: planet_id.clone() };
+        state.apply_effect(create, &player_id).unwrap();
+        let fleet_id = state.players[&player_id].ships[&first_ship].fleet_id.clone().unwrap();
+        let bombardment_power = state.calculate_fleet_bombardment(&player_id, &fleet_id);
+        let bombard = CommandEffect::BombardPlanet { fleet_id: fleet_id.clone(), target_planet: planet_id.clone(), bombardment_power, target_structure: None };
+        state.apply_effect(bombard, &player_id).unwrap();
+        fleet_id
+    }
+
+    /// Points `fleet_id`'s bombardment at `structure_id`.
+    fn aim(state: &mut GameState, player_id: &str, fleet_id: &FleetId, structure_id: &str) {
+        let action = state.players.get_mut(player_id).unwrap().pending_actions.iter_mut()
+            .find(|action| matches!(&action.action_type, ActionType::BombardPlanet(bombarding, _) if bombarding == fleet_id))
+            .unwrap();
+        action.target_structure = Some(structure_id.to_string());
+    }
+
+    fn structure_hits(events: &[GameEvent], player_id: &str) -> Vec<StructureHit> {
+        events.iter()
+            .filter_map(|event| match event {
+                GameEvent::StructuresBombarded { player_id: attacker, hits, .. } if attacker == player_id => Some(hits.clone()),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    fn hit(structure_id: &str, damage: u32, destroyed: bool) -> StructureHit {
+        StructureHit { structure_id: structure_id.to_string(), damage, destroyed }
+    }
+
+    fn stats(state: &GameState, player_id: &str) -> BombardmentStats {
+        state.players[player_id].bombardment
+    }
+
+    fn is_bombarding(state: &GameState, player_id: &str) -> bool {
+        state.players[player_id].pending_actions.iter()
+            .any(|action| matches!(action.action_type, ActionType::BombardPlanet(..)))
+    }
+
+    #[test]
+    fn test_bombardments_stack_in_turn_order_and_credit_the_breaking_hit() {
+        let (mut state, bob_home) = shielded_bob();
+        besiege(&mut state, "carol", 3, &bob_home);
+        besiege(&mut state, "alice", 2, &bob_home);
+
+        let events = end_round(&mut state);
+
+        // Alice plays first: 100 -> 50, then carol's 75 breaks the rest with 25 to spare
+        let hits: Vec<_> = events.iter()
+            .filter(|event| matches!(event, GameEvent::ShieldsDamaged { .. } | GameEvent::ShieldsDestroyed { .. }))
+            .collect();
+        assert!(matches!(hits[..], [
+            GameEvent::ShieldsDamaged { player_id: alice, shields_before: 100, shields_after: 50, .. },
+            GameEvent::ShieldsDestroyed { player_id: carol, shields_before: 50, overflow: 25, .. },
+        ] if alice == "alice" && carol == "carol"));
+        assert_eq!(stats(&state, "alice"), BombardmentStats { shield_damage: 50, overflow_damage: 0, shields_broken: 0 });
+        assert_eq!(stats(&state, "carol"), BombardmentStats { shield_damage: 50, overflow_damage: 25, shields_broken: 1 });
+        assert_eq!(state.map.planets[&bob_home].get_shield_hp(), 0);
+        assert!(is_bombarding(&state, "alice"));
+        assert!(!is_bombarding(&state, "carol"));
+    }
+
+    #[test]
+    fn test_hits_after_the_break_pass_through_and_end() {
+        let (mut state, bob_home) = shielded_bob();
+        besiege(&mut state, "alice", 4, &bob_home);
+        besiege(&mut state, "carol", 1, &bob_home);
+
+        let events = end_round(&mut state);
+
+        assert!(events.iter().any(|event| matches!(event,
+            GameEvent::ShieldsDestroyed { player_id, overflow: 0, .. } if player_id == "alice")));
+        assert!(events.iter().any(|event| matches!(event,
+            GameEvent::BombardmentOverflow { player_id, damage: 25, .. } if player_id == "carol")));
+        let total = |stats: BombardmentStats| stats.shield_damage + stats.overflow_damage;
+        assert_eq!(total(stats(&state, "alice")) + total(stats(&state, "carol")), 125);
+        assert_eq!(stats(&state, "alice").shields_broken, 1);
+        assert!(!is_bombarding(&state, "alice") && !is_bombarding(&state, "carol"));
+    }
+
+    #[test]
+    fn test_overflow_lands_on_the_targeted_structure() {
+        let (mut state, bob_home) = shielded_bob();
+        let fleet_id = besiege(&mut state, "alice", 5, &bob_home);
+        aim(&mut state, "alice", &fleet_id, "power_grid");
+
+        let events = end_round(&mut state);
+
+        assert_eq!(structure_hits(&events, "alice"), vec![hit("power_grid", 25, false)]);
+        assert!(!events.iter().any(|event| matches!(event, GameEvent::BombardmentTargetMissing { .. })));
+        let structures = state.map.planets[&bob_home].get_structures();
+        assert_eq!(structures["power_grid"].hitpoints, 475);
+        assert_eq!(structures["defense_shield"].hitpoints, 100);
+    }
+
+    #[test]
+    fn test_absent_target_falls_back_to_the_default_order_with_a_notice() {
+        let (mut state, bob_home) = shielded_bob();
+        let fleet_id = besiege(&mut state, "alice", 5, &bob_home);
+        aim(&mut state, "alice", &fleet_id, "jump_gate");
+
+        let events = end_round(&mut state);
+
+        assert!(events.iter().any(|event| matches!(event,
+            GameEvent::BombardmentTargetMissing { player_id, structure_id, .. } if player_id == "alice" && structure_id == "jump_gate")));
+        // The weakest structure is hit first
+        assert_eq!(structure_hits(&events, "alice"), vec![hit("defense_shield", 25, false)]);
+    }
+
+    #[test]
+    fn test_target_destroyed_earlier_in_the_round_is_reported_missing() {
+        let (mut state, bob_home) = shielded_bob();
+        besiege(&mut state, "alice", 8, &bob_home);
+        let carol_fleet = besiege(&mut state, "carol", 1, &bob_home);
+        aim(&mut state, "carol", &carol_fleet, "defense_shield");
+
+        let events = end_round(&mut state);
+
+        // Alice plays first and her 100 overflow tears down the shield generator
+        assert_eq!(structure_hits(&events, "alice"), vec![hit("defense_shield", 100, true)]);
+        assert!(events.iter().any(|event| matches!(event,
+            GameEvent::StructureDestroyed { player_id, structure_id, .. } if player_id == "bob" && structure_id == "defense_shield")));
+        assert!(events.iter().any(|event| matches!(event,
+            GameEvent::BombardmentTargetMissing { player_id, structure_id, .. } if player_id == "carol" && structure_id == "defense_shield")));
+        assert_eq!(structure_hits(&events, "carol"), vec![hit("power_grid", 25, false)]);
+        assert!(!state.map.planets[&bob_home].get_structures().contains_key("defense_shield"));
+        assert!(state.check_invariants().is_empty());
+    }
+
+    #[test]
+    fn test_cancelling_one_bombardment_leaves_the_other_alone() {
+        let (mut state, bob_home) = shielded_bob();
+        let alice_fleet = besiege(&mut state, "alice", 1, &bob_home);
+        besiege(&mut state, "carol", 1, &bob_home);
+
+        let events = state.apply_effect(CommandEffect::CancelBombard { fleet_id: alice_fleet }, &String::from("alice")).unwrap();
+        assert!(matches!(events[..], [GameEvent::BombardmentCancelled { .. }]));
+        let events = end_round(&mut state);
+
+        assert!(!is_bombarding(&state, "alice"));
+        assert!(is_bombarding(&state, "carol"));
+        assert!(events.iter().any(|event| matches!(event,
+            GameEvent::ShieldsDamaged { player_id, shields_before: 100, shields_after: 75, .. } if player_id == "carol")));
+        assert_eq!(stats(&state, "alice"), BombardmentStats::default());
+    }
+}
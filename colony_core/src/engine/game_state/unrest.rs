@@ -0,0 +1,191 @@
+use std::collections::BTreeSet;
+
+use crate::engine::game_event::GameEvent;
+use crate::engine::pending_action::ActionType;
+use crate::engine::planet::PlanetId;
+use crate::engine::rules;
+
+use super::GameState;
+
+impl GameState {
+    /// Planets some fleet will bombard when this round's bombardments are processed.
+    pub(super) fn bombarded_planets(&self) -> BTreeSet<PlanetId> {
+        self.players.values()
+            .flat_map(|player| player.pending_actions.iter())
+            .filter_map(|action| match &action.action_type {
+                ActionType::BombardPlanet(_, planet_id) => Some(planet_id.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether a fleet of a player not allied with the owner sits in orbit of `planet_id` with
+    /// no orders to leave, as `rules::can_blockade` decides. Neutral planets cannot be blockaded.
+    pub fn is_blockaded(&self, planet_id: &PlanetId) -> bool {
+        let Some(planet) = self.map.planets.get(planet_id) else {
+            return false;
+        };
+        self.players.values().any(|player| player.fleets.values().any(|fleet| {
+            &fleet.location == planet_id && rules::can_blockade(fleet, planet, player, self).is_ok()
+        }))
+    }
+
+    /// Raises unrest on owned planets bombarded or blockaded this round and lets it fade on
+    /// the others. Returns a strike event for each planet whose unrest reached the threshold.
+    pub(super) fn update_unrest(&mut self, bombarded: &BTreeSet<PlanetId>) -> Vec<GameEvent> {
+        let troubled: BTreeSet<PlanetId> = self.map.planets.keys()
+            .filter(|planet_id| bombarded.contains(*planet_id) || self.is_blockaded(planet_id))
+            .cloned()
+            .collect();
+
+        let mut events = Vec::new();
+        for planet in self.map.planets.values_mut() {
+            let Some(owner) = planet.get_owner().clone() else {
+                continue;
+            };
+            if planet.update_unrest(troubled.contains(&planet.id), &self.rules.unrest) {
+                events.push(GameEvent::WorkersStrike {
+                    player_id: owner,
+                    planet_name: planet.name.clone(),
+                    unrest: planet.get_unrest(),
+                });
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::commands::command::CommandEffect;
+    use crate::engine::fleet::Fleet;
+    use crate::engine::game_event::GameEvent;
+    use crate::engine::game_rules::{
+        DEFAULT_STRIKE_THRESHOLD, DEFAULT_UNREST_DECAY, DEFAULT_UNREST_PENALTY_THRESHOLD, DEFAULT_UNREST_RISE,
+    };
+    use crate::engine::game_state::GameState;
+    use crate::engine::planet::PlanetId;
+    use crate::engine::resources::Resources;
+    use crate::engine::ship::FleetId;
+    use crate::test_support::{self, end_round, fixture_game};
+
+    /// Parks a fleet of `player_id`'s ravagers in orbit of `planet_id`.
+    fn park_fleet(state: &mut GameState, player_id: &str, ravagers: usize, planet_id: &PlanetId) -> FleetId {
+        let player = state.players.get_mut(player_id).unwrap();
+        let fleet_id = player.next_fleet_id();
+        let mut fleet = Fleet::new(fleet_id.clone(), String::from("Siege"), planet_id.clone());
+        for _ in 0..ravagers {
+            let ship_id = player.add_ship(String::from("ravager"), planet_id.clone());
+            player.ships.get_mut(&ship_id).unwrap().fleet_id = Some(fleet_id.clone());
+            fleet.add_ship(ship_id);
+        }
+        player.fleets.insert(fleet_id.clone(), fleet);
+        fleet_id
+    }
+
+    /// Bob's home behind a shield alice's lone ravager takes several rounds to break.
+    fn besieged_bob() -> (GameState, PlanetId) {
+        let mut state = fixture_game(1);
+        let bob_home = test_support::home_planet(&state, "bob");
+        let planet = state.map.planets.get_mut(&bob_home).unwrap();
+        for structure_id in ["power_grid", "defense_shield"] {
+            planet.complete_build_structure(String::from(structure_id), &state.structure_config).unwrap();
+        }
+        planet.process_turn();
+
+        let fleet_id = park_fleet(&mut state, "alice", 1, &bob_home);
+        let bombardment_power = state.calculate_fleet_bombardment(&String::from("alice"), &fleet_id);
+        let bombard = CommandEffect::BombardPlanet { fleet_id, target_planet: bob_home.clone(), bombardment_power, target_structure: None };
+        state.apply_effect(bombard, &String::from("alice")).unwrap();
+        (state, bob_home)
+    }
+
+    fn strikes(events: &[GameEvent]) -> Vec<&GameEvent> {
+        events.iter().filter(|event| matches!(event, GameEvent::WorkersStrike { .. })).collect()
+    }
+
+    #[test]
+    fn test_unrest_builds_up_under_sustained_bombardment() {
+        let (mut state, bob_home) = besieged_bob();
+        let alice_home = test_support::home_planet(&state, "alice");
+
+        for round in 1..=3 {
+            end_round(&mut state);
+            assert_eq!(state.map.planets[&bob_home].get_unrest(), round * DEFAULT_UNREST_RISE);
+        }
+        assert_eq!(state.map.planets[&alice_home].get_unrest(), 0);
+    }
+
+    #[test]
+    fn test_reaching_the_threshold_strikes_for_one_production() {
+        let (mut state, bob_home) = besieged_bob();
+        let rounds = DEFAULT_STRIKE_THRESHOLD.div_ceil(DEFAULT_UNREST_RISE);
+        for _ in 1..rounds {
+            assert!(strikes(&end_round(&mut state)).is_empty());
+        }
+
+        let events = end_round(&mut state);
+        assert!(matches!(strikes(&events)[..], [GameEvent::WorkersStrike { player_id, .. }] if player_id == "bob"));
+        let planet = state.map.planets.get_mut(&bob_home).unwrap();
+        assert!(planet.is_on_strike());
+        planet.available_resources = Resources::default();
+
+        // Bob's planets produce as his turn starts: nothing during the strike, then again,
+        // and unrest still above the threshold calls no second strike
+        let events = end_round(&mut state);
+        assert!(strikes(&events).is_empty());
+        assert!(!state.map.planets[&bob_home].is_on_strike());
+        assert_eq!(state.map.planets[&bob_home].available_resources, Resources::default());
+        end_round(&mut state);
+        assert_ne!(state.map.planets[&bob_home].available_resources, Resources::default());
+    }
+
+    #[test]
+    fn test_high_unrest_cuts_production() {
+        let mut state = fixture_game(1);
+        let bob_home = test_support::home_planet(&state, "bob");
+        // Nothing is produced on the first turn
+        end_round(&mut state);
+        let produced = |state: &mut GameState, unrest: u32| {
+            let planet = state.map.planets.get_mut(&bob_home).unwrap();
+            planet.available_resources = Resources::default();
+            planet.set_unrest(unrest);
+            end_round(state);
+            state.map.planets[&bob_home].available_resources.clone()
+        };
+
+        let calm = produced(&mut state, 0);
+        let restless = produced(&mut state, DEFAULT_UNREST_PENALTY_THRESHOLD + 50);
+        assert_eq!(restless, calm.clone() - calm.percentage(50));
+    }
+
+    #[test]
+    fn test_unrest_decays_on_quiet_rounds_and_faster_with_a_morale_center() {
+        let mut state = fixture_game(1);
+        let bob_home = test_support::home_planet(&state, "bob");
+        state.map.planets.get_mut(&bob_home).unwrap().set_unrest(40);
+
+        end_round(&mut state);
+        assert_eq!(state.map.planets[&bob_home].get_unrest(), 40 - DEFAULT_UNREST_DECAY);
+
+        let planet = state.map.planets.get_mut(&bob_home).unwrap();
+        planet.complete_build_structure(String::from("morale_center"), &state.structure_config).unwrap();
+        end_round(&mut state);
+        assert_eq!(state.map.planets[&bob_home].get_unrest(), 40 - 2 * DEFAULT_UNREST_DECAY - 5);
+    }
+
+    #[test]
+    fn test_hostile_fleets_in_orbit_blockade() {
+        let mut state = fixture_game(1);
+        let bob_home = test_support::home_planet(&state, "bob");
+        assert!(!state.is_blockaded(&bob_home));
+
+        park_fleet(&mut state, "bob", 1, &bob_home);
+        assert!(!state.is_blockaded(&bob_home), "Bob's own fleet does not blockade him");
+        park_fleet(&mut state, "alice", 1, &bob_home);
+        assert!(state.is_blockaded(&bob_home));
+
+        end_round(&mut state);
+        assert_eq!(state.map.planets[&bob_home].get_unrest(), DEFAULT_UNREST_RISE);
+    }
+}
@@ -0,0 +1,346 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::engine::configs::ship_config::ShipId;
+use crate::engine::planet::PlanetId;
+use crate::engine::player::PlayerId;
+use crate::engine::resources::Resources;
+use crate::engine::ship::{FleetId, ShipInstanceId};
+use crate::engine::structure::StructureId;
+
+use super::GameState;
+
+/// What one player could see of the game at the start of a turn: planets in sensor range in
+/// full, others as last seen, and only their own ships and fleets.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct GameView {
+    pub turn: u32,
+    /// Explored planets only
+    pub planets: BTreeMap<PlanetId, PlanetView>,
+    pub ships: BTreeMap<ShipInstanceId, ShipView>,
+    pub fleets: BTreeMap<FleetId, FleetView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PlanetView {
+    pub name: String,
+    /// Name of the owner, as last seen for planets outside sensor range
+    pub owner: Option<String>,
+    /// Levels of the structures standing; None outside sensor range
+    pub structures: Option<BTreeMap<StructureId, u16>>,
+    /// Stock of the viewer's own planets only
+    pub resources: Option<Resources>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ShipView {
+    pub ship_type: ShipId,
+    pub location: PlanetId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FleetView {
+    pub name: String,
+    pub location: PlanetId,
+}
+
+/// Every player's view at the start of one turn.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TurnViews {
+    pub turn: u32,
+    pub views: BTreeMap<PlayerId, GameView>,
+}
+
+/// One difference between two views of the same player.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ViewChange {
+    OwnerChanged { planet_name: String, from: Option<String>, to: Option<String> },
+    StructureGained { planet_name: String, structure_id: StructureId, level: u16 },
+    StructureLost { planet_name: String, structure_id: StructureId, level: u16 },
+    StructureLeveled { planet_name: String, structure_id: StructureId, from: u16, to: u16 },
+    ResourcesChanged { planet_name: String, before: Resources, after: Resources },
+    ShipGained { ship_id: ShipInstanceId, ship_type: ShipId, planet_name: String },
+    ShipLost { ship_id: ShipInstanceId, ship_type: ShipId },
+    FleetMoved { fleet_id: FleetId, name: String, from_name: String, to_name: String },
+}
+
+impl fmt::Display for ViewChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ViewChange::OwnerChanged { planet_name, from, to } => write!(
+                f, "{}: {} -> {}", planet_name, owner_text(from), owner_text(to)
+            ),
+            ViewChange::StructureGained { planet_name, structure_id, level } => write!(
+                f, "{}: {} built (Lv{})", planet_name, structure_id, level
+            ),
+            ViewChange::StructureLost { planet_name, structure_id, level } => write!(
+                f, "{}: {} (Lv{}) lost", planet_name, structure_id, level
+            ),
+            ViewChange::StructureLeveled { planet_name, structure_id, from, to } => write!(
+                f, "{}: {} Lv{} -> Lv{}", planet_name, structure_id, from, to
+            ),
+            ViewChange::ResourcesChanged { planet_name, before, after } => {
+                let deltas: Vec<_> = [
+                    ("minerals", before.minerals, after.minerals),
+                    ("gas", before.gas, after.gas),
+                    ("energy", before.energy, after.energy),
+                ]
+                    .into_iter()
+                    .filter(|(_, before, after)| before != after)
+                    .map(|(name, before, after)| format!("{:+} {}", i64::from(after) - i64::from(before), name))
+                    .collect();
+                write!(f, "{}: {}", planet_name, deltas.join(", "))
+            }
+            ViewChange::ShipGained { ship_id, ship_type, planet_name } => write!(
+                f, "{} ({}) gained at {}", ship_id, ship_type, planet_name
+            ),
+            ViewChange::ShipLost { ship_id, ship_type } => write!(f, "{} ({}) lost", ship_id, ship_type),
+            ViewChange::FleetMoved { fleet_id, name, from_name, to_name } => write!(
+                f, "{} ({}): {} -> {}", name, fleet_id, from_name, to_name
+            ),
+        }
+    }
+}
+
+fn owner_text(owner: &Option<String>) -> &str {
+    owner.as_deref().unwrap_or("unowned")
+}
+
+/// Everything that changed between two views of the same player, each change once: owners,
+/// then structures, stocks, ships and fleet positions. Structures and stocks are only compared
+/// where both views saw them.
+pub fn diff_views(before: &GameView, after: &GameView) -> Vec<ViewChange> {
+    let mut changes = Vec::new();
+    let shared_planets = || after.planets.iter()
+        .filter_map(|(planet_id, now)| before.planets.get(planet_id).map(|then| (then, now)));
+
+    for (then, now) in shared_planets() {
+        if then.owner != now.owner {
+            changes.push(ViewChange::OwnerChanged {
+                planet_name: now.name.clone(),
+                from: then.owner.clone(),
+                to: now.owner.clone(),
+            });
+        }
+    }
+
+    for (then, now) in shared_planets() {
+        let (Some(old), Some(new)) = (&then.structures, &now.structures) else {
+            continue;
+        };
+        for (structure_id, level) in new {
+            let planet_name = now.name.clone();
+            let structure_id = structure_id.clone();
+            match old.get(&structure_id) {
+                None => changes.push(ViewChange::StructureGained { planet_name, structure_id, level: *level }),
+                Some(from) if from != level => {
+                    changes.push(ViewChange::StructureLeveled { planet_name, structure_id, from: *from, to: *level });
+                }
+                Some(_) => {}
+            }
+        }
+        for (structure_id, level) in old.iter().filter(|(structure_id, _)| !new.contains_key(*structure_id)) {
+            changes.push(ViewChange::StructureLost { planet_name: now.name.clone(), structure_id: structure_id.clone(), level: *level });
+        }
+    }
+
+    for (then, now) in shared_planets() {
+        if let (Some(before), Some(after)) = (&then.resources, &now.resources)
+            && before != after
+        {
+            changes.push(ViewChange::ResourcesChanged { planet_name: now.name.clone(), before: before.clone(), after: after.clone() });
+        }
+    }
+
+    let planet_name = |planet_id: &PlanetId| after.planets.get(planet_id)
+        .or_else(|| before.planets.get(planet_id))
+        .map_or_else(|| planet_id.clone(), |planet| planet.name.clone());
+    for (ship_id, ship) in after.ships.iter().filter(|(ship_id, _)| !before.ships.contains_key(*ship_id)) {
+        changes.push(ViewChange::ShipGained {
+            ship_id: ship_id.clone(),
+            ship_type: ship.ship_type.clone(),
+            planet_name: planet_name(&ship.location),
+        });
+    }
+    for (ship_id, ship) in before.ships.iter().filter(|(ship_id, _)| !after.ships.contains_key(*ship_id)) {
+        changes.push(ViewChange::ShipLost { ship_id: ship_id.clone(), ship_type: ship.ship_type.clone() });
+    }
+
+    for (fleet_id, now) in &after.fleets {
+        if let Some(then) = before.fleets.get(fleet_id)
+            && then.location != now.location
+        {
+            changes.push(ViewChange::FleetMoved {
+                fleet_id: fleet_id.clone(),
+                name: now.name.clone(),
+                from_name: planet_name(&then.location),
+                to_name: planet_name(&now.location),
+            });
+        }
+    }
+    changes
+}
+
+impl GameState {
+    /// What `viewer` can see of the game right now, by sensor range even while a replay
+    /// shows every planet.
+    pub fn view_for(&self, viewer: &PlayerId) -> GameView {
+        let owner_name = |owner: &Option<PlayerId>| owner.as_ref()
+            .map(|owner| self.players.get(owner).map_or_else(|| owner.clone(), |player| player.name.clone()));
+        let visible = self.visible_planets(viewer);
+        let intel = self.players.get(viewer).map(|player| &player.planet_intel);
+
+        let planets = self.map.planets.values()
+            .filter_map(|planet| {
+                let view = if visible.contains(&planet.id) {
+                    PlanetView {
+                        name: planet.name.clone(),
+                        owner: owner_name(planet.get_owner()),
+                        structures: Some(planet.get_structures().iter()
+                            .map(|(structure_id, structure)| (structure_id.clone(), structure.level))
+                            .collect()),
+                        resources: (planet.get_owner().as_ref() == Some(viewer))
+                            .then(|| planet.available_resources.clone()),
+                    }
+                } else {
+                    let intel = intel?.get(&planet.id)?;
+                    PlanetView {
+                        name: planet.name.clone(),
+                        owner: owner_name(&intel.owner_at_time),
+                        structures: None,
+                        resources: None,
+                    }
+                };
+                Some((planet.id.clone(), view))
+            })
+            .collect();
+
+        let (ships, fleets) = match self.players.get(viewer) {
+            Some(player) => (
+                player.ships.iter()
+                    .map(|(ship_id, ship)| (ship_id.clone(), ShipView { ship_type: ship.ship_type.clone(), location: ship.location.clone() }))
+                    .collect(),
+                player.fleets.iter()
+                    .map(|(fleet_id, fleet)| (fleet_id.clone(), FleetView { name: fleet.name.clone(), location: fleet.location.clone() }))
+                    .collect(),
+            ),
+            None => Default::default(),
+        };
+
+        GameView { turn: self.turn, planets, ships, fleets }
+    }
+
+    /// `viewer`'s view at the start of `turn`, if that turn is still kept.
+    pub fn turn_view(&self, viewer: &PlayerId, turn: u32) -> Option<&GameView> {
+        self.turn_history.iter()
+            .find(|turn_views| turn_views.turn == turn)
+            .and_then(|turn_views| turn_views.views.get(viewer))
+    }
+
+    /// Records every player's view of the turn that just began, dropping the oldest turns
+    /// beyond what the history option keeps.
+    pub(crate) fn record_turn_views(&mut self) {
+        let views = self.players.keys()
+            .map(|player_id| (player_id.clone(), self.view_for(player_id)))
+            .collect();
+        self.turn_history.push_back(TurnViews { turn: self.turn, views });
+        self.trim_turn_history();
+    }
+
+    pub(crate) fn trim_turn_history(&mut self) {
+        while self.turn_history.len() > self.options.history_turns as usize {
+            self.turn_history.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::command::CommandEffect;
+    use crate::test_support::{self, fixture_game};
+
+    fn planet(name: &str, owner: Option<&str>, structures: &[(&str, u16)], resources: Option<Resources>) -> PlanetView {
+        PlanetView {
+            name: name.to_string(),
+            owner: owner.map(str::to_string),
+            structures: Some(structures.iter().map(|(id, level)| (id.to_string(), *level)).collect()),
+            resources,
+        }
+    }
+
+    fn ship(ship_type: &str, location: &str) -> ShipView {
+        ShipView { ship_type: ship_type.to_string(), location: location.to_string() }
+    }
+
+    fn fleet(location: &str) -> FleetView {
+        FleetView { name: String::from("Strike"), location: location.to_string() }
+    }
+
+    #[test]
+    fn test_diff_reports_every_kind_of_change_once() {
+        let stock = |minerals| Some(Resources { minerals, gas: 50, energy: 10 });
+        let before = GameView {
+            turn: 3,
+            planets: BTreeMap::from([
+                (String::from("home"), planet("Home", Some("Alice"), &[("mine", 1), ("power_grid", 1), ("storage_complex", 1)], stock(100))),
+                (String::from("vega"), planet("Vega", None, &[], None)),
+                (String::from("far"), PlanetView { structures: None, ..planet("Far", Some("Bob"), &[], None) }),
+            ]),
+            ships: BTreeMap::from([(String::from("scout_1"), ship("scout", "home")), (String::from("ark_1"), ship("ark", "home"))]),
+            fleets: BTreeMap::from([(String::from("fleet_1"), fleet("home")), (String::from("fleet_2"), fleet("vega"))]),
+        };
+        let after = GameView {
+            turn: 5,
+            planets: BTreeMap::from([
+                (String::from("home"), planet("Home", Some("Alice"), &[("mine", 2), ("power_grid", 1), ("shipyard", 1)], stock(40))),
+                (String::from("vega"), planet("Vega", Some("Alice"), &[], None)),
+                // Out of sight again, so its structures are unknown rather than lost
+                (String::from("far"), PlanetView { structures: None, ..planet("Far", Some("Bob"), &[], None) }),
+            ]),
+            ships: BTreeMap::from([(String::from("scout_1"), ship("scout", "vega")), (String::from("ravager_1"), ship("ravager", "home"))]),
+            fleets: BTreeMap::from([(String::from("fleet_1"), fleet("vega")), (String::from("fleet_2"), fleet("vega"))]),
+        };
+
+        let changes = diff_views(&before, &after);
+
+        let text = |name: &str| name.to_string();
+        assert_eq!(changes, [
+            ViewChange::OwnerChanged { planet_name: text("Vega"), from: None, to: Some(text("Alice")) },
+            ViewChange::StructureLeveled { planet_name: text("Home"), structure_id: text("mine"), from: 1, to: 2 },
+            ViewChange::StructureGained { planet_name: text("Home"), structure_id: text("shipyard"), level: 1 },
+            ViewChange::StructureLost { planet_name: text("Home"), structure_id: text("storage_complex"), level: 1 },
+            ViewChange::ResourcesChanged { planet_name: text("Home"), before: stock(100).unwrap(), after: stock(40).unwrap() },
+            ViewChange::ShipGained { ship_id: text("ravager_1"), ship_type: text("ravager"), planet_name: text("Home") },
+            ViewChange::ShipLost { ship_id: text("ark_1"), ship_type: text("ark") },
+            ViewChange::FleetMoved { fleet_id: text("fleet_1"), name: text("Strike"), from_name: text("Home"), to_name: text("Vega") },
+        ]);
+        assert_eq!(changes[4].to_string(), "Home: -60 minerals");
+        assert!(diff_views(&after, &after).is_empty());
+    }
+
+    #[test]
+    fn test_views_are_kept_for_the_last_turns_and_hide_other_empires() {
+        let mut state = fixture_game(1);
+        state.options.history_turns = 2;
+        state.record_turn_views();
+        for _ in 0..3 {
+            for _ in 0..state.players_order.len() {
+                let player_id = state.current_player().clone();
+                let player_name = state.players[&player_id].name.clone();
+                state.apply_effect(CommandEffect::EndTurn { player_name }, &player_id).unwrap();
+            }
+        }
+
+        let alice = String::from("alice");
+        let kept: Vec<_> = state.turn_history.iter().map(|turn_views| turn_views.turn).collect();
+        assert_eq!(kept, [3, 4]);
+        assert!(state.turn_view(&alice, 2).is_none());
+
+        let view = state.turn_view(&alice, 4).unwrap();
+        let bob_home = test_support::home_planet(&state, "bob");
+        let alice_home = test_support::home_planet(&state, "alice");
+        assert!(view.planets[&alice_home].resources.is_some());
+        assert!(view.planets.get(&bob_home).is_none_or(|planet| planet.resources.is_none()));
+    }
+}
@@ -0,0 +1,881 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use rand::Rng;
+
+use crate::engine::configs::structure_config::StructureConfig;
+use crate::engine::game_rules::GameSpeed;
+use crate::engine::planet::{Connection, ConnectionError, ConnectionKind, Planet, PlanetError, PlanetId, Ruin};
+use crate::engine::planet_name_generator::{PlanetNameGenerator, PlanetNameGeneratorError};
+use crate::engine::player::{Player, PlayerId};
+use crate::engine::progress::{NoProgress, ProgressSink};
+use crate::engine::resources::Resources;
+use crate::engine::utils;
+
+mod graph;
+
+pub use graph::{Graph, GraphError};
+use graph::DistanceCache;
+
+static GRID_HEIGHT: u8 = 40;
+static GRID_WIDTH: u8 = 120;
+/// Longest connection, in turns, a map takes unless it raises its own cap
+pub const MAX_DISTANCE: u8 = 5;
+
+/// Stage name [`Map::generate_with_progress`] reports under
+pub const GENERATION_STAGE: &str = "Generating map";
+
+/// Structures that can be found standing in ruins: a warehouse and an extractor.
+const RUIN_STRUCTURES: &[&str] = &["storage_complex", "mining_complex"];
+
+/// Characters used to draw the map and decorate output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RenderStyle {
+    #[default]
+    Unicode,
+    /// Plain ASCII, for terminals and log files that mangle anything else
+    AsciiOnly,
+}
+
+/// Non-ASCII characters the game prints and their ASCII stand-ins.
+const ASCII_SUBSTITUTIONS: &[(char, &str)] = &[
+    ('◉', "o"),
+    ('│', "|"),
+    ('─', "-"),
+    ('…', "~"),
+    ('·', "."),
+    ('→', "->"),
+    ('—', "-"),
+    ('⚔', "!"),
+    ('⚠', "!"),
+    ('📡', "*"),
+    ('🎉', "*"),
+];
+
+impl RenderStyle {
+    fn planet_icon(self) -> char {
+        self.glyph('◉')
+    }
+
+    /// `unicode` as drawn in this style. Only characters from the substitution table that
+    /// map to a single character can be drawn on the grid.
+    fn glyph(self, unicode: char) -> char {
+        match self {
+            RenderStyle::Unicode => unicode,
+            RenderStyle::AsciiOnly => ASCII_SUBSTITUTIONS.iter()
+                .find(|(from, _)| *from == unicode)
+                .and_then(|(_, to)| to.chars().next())
+                .unwrap_or(unicode),
+        }
+    }
+
+    /// Rewrites `text` for this style. In ASCII only, known glyphs are substituted and any
+    /// other non-ASCII character, such as a letter in a player name, becomes `?`.
+    pub fn apply(self, text: &str) -> Cow<'_, str> {
+        if self == RenderStyle::Unicode || text.is_ascii() {
+            return Cow::Borrowed(text);
+        }
+        let mut ascii = String::with_capacity(text.len());
+        for ch in text.chars() {
+            match ASCII_SUBSTITUTIONS.iter().find(|(from, _)| *from == ch) {
+                Some((_, to)) => ascii.push_str(to),
+                None if ch.is_ascii() => ascii.push(ch),
+                None => ascii.push('?'),
+            }
+        }
+        Cow::Owned(ascii)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MapError {
+    #[error("Not enough planet names: map needs {requested} but only {available} are available")]
+    NotEnoughNames { requested: usize, available: usize },
+
+    #[error(transparent)]
+    PlanetNameGeneratorError(#[from] PlanetNameGeneratorError),
+
+    #[error(transparent)]
+    InvalidConnection(#[from] ConnectionError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum MapSize {
+    Small,
+    Medium,
+    Large
+}
+
+impl MapSize {
+    /// Every size, smallest first
+    pub const ALL: [MapSize; 3] = [MapSize::Small, MapSize::Medium, MapSize::Large];
+
+    pub fn num_planets(&self) -> u32 {
+        match self {
+            MapSize::Small => 10,
+            MapSize::Medium => 20,
+            MapSize::Large => 30,
+        }
+    }
+
+    /// Smallest size with at least `planets` planets, if there is one.
+    pub fn smallest_with(planets: u32) -> Option<MapSize> {
+        MapSize::ALL.into_iter().find(|size| size.num_planets() >= planets)
+    }
+}
+
+impl fmt::Display for MapSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            MapSize::Small => "small",
+            MapSize::Medium => "medium",
+            MapSize::Large => "large",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// What a player knows of a planet outside their sensor range.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fogged {
+    /// Never observed, so even the owner is unknown
+    Unexplored,
+    /// Last observed on `turn`, when `owner` held it
+    LastSeen { turn: u32, owner: Option<PlayerId> },
+}
+
+/// Military activity at a planet as seen by one player, drawn after the planet's map label,
+/// and whether the player's view of the planet is out of date.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PlanetPresence {
+    /// Fleets of the viewing player orbiting the planet
+    pub own_fleets: usize,
+    /// Ships of another player are at the planet
+    pub hostile_ships: bool,
+    /// A bombardment of the planet is under way
+    pub bombarded: bool,
+    /// Set when the planet is outside the viewer's sensor range
+    pub fogged: Option<Fogged>,
+}
+
+impl PlanetPresence {
+    /// Label suffix such as `[F2][!][B]`; empty when nothing is going on.
+    fn markers(&self) -> String {
+        let mut markers = String::new();
+        if self.own_fleets > 0 {
+            markers.push_str(&format!("[F{}]", self.own_fleets));
+        }
+        if self.hostile_ships {
+            markers.push_str("[!]");
+        }
+        if self.bombarded {
+            markers.push_str("[B]");
+        }
+        markers
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct Map {
+    pub planets: BTreeMap<PlanetId, Planet>,
+    pub planet_positions: HashMap<PlanetId, (u8, u8)>,
+    #[allow(dead_code)]
+    pub size: MapSize,
+    /// Longest connection allowed, in turns. Generated maps keep to [`MAX_DISTANCE`]; an
+    /// imported map may raise it
+    #[serde(default = "default_max_distance")]
+    pub max_distance: u8,
+    #[serde(skip)]
+    distances: DistanceCache,
+}
+
+fn default_max_distance() -> u8 {
+    MAX_DISTANCE
+}
+
+impl Map {
+    /// Terminal columns needed to show `render_full` without wrapping
+    pub const RENDER_WIDTH: usize = GRID_WIDTH as usize;
+
+    /// Longest planet id `render_full` shows before cutting it short with "…"
+    pub const DEFAULT_LABEL_ID_CHARS: usize = 12;
+
+    pub fn generate(
+        size: MapSize,
+        name_generator: &mut PlanetNameGenerator,
+        rng: &mut impl Rng,
+    ) -> Result<Self, MapError> {
+        Self::generate_with_progress(size, name_generator, rng, &mut NoProgress)
+    }
+
+    /// Like [`Map::generate`], reporting each planet placed to `progress`.
+    pub fn generate_with_progress(
+        size: MapSize,
+        name_generator: &mut PlanetNameGenerator,
+        rng: &mut impl Rng,
+        progress: &mut dyn ProgressSink,
+    ) -> Result<Self, MapError> {
+        Self::generate_planets(size, size.num_planets(), name_generator, rng, progress)
+    }
+
+    fn generate_planets(
+        size: MapSize,
+        num_planets: u32,
+        name_generator: &mut PlanetNameGenerator,
+        rng: &mut impl Rng,
+        progress: &mut dyn ProgressSink,
+    ) -> Result<Self, MapError> {
+        let available = name_generator.remaining();
+        if available < num_planets as usize {
+            return Err(MapError::NotEnoughNames { requested: num_planets as usize, available });
+        }
+        let mut names = name_generator.generate_batch(num_planets as usize)?.into_iter();
+
+        let mut map = Map {
+            planets: BTreeMap::new(),
+            planet_positions: HashMap::with_capacity(num_planets as usize),
+            size,
+            max_distance: MAX_DISTANCE,
+            distances: DistanceCache::default(),
+        };
+
+        // Generate first planet (root of tree)
+        let root_name = names.next().expect("Batch holds one name per planet");
+        let root_id = utils::name_to_id(&root_name);
+        let root = Planet::new(root_id.clone(), root_name, None, Vec::new());
+        
+        let (rand_pos_x, rand_pos_y) = loop {
+            let x = rng.random_range(1..GRID_WIDTH - 1);
+            let y = rng.random_range(1..GRID_HEIGHT - 1);
+            if !map.planet_positions.values().any(|&pos| pos == (x, y)) {
+                break (x, y);
+            }
+        };
+        map.planet_positions.insert(root_id.clone(), (rand_pos_x, rand_pos_y));
+
+        map.planets.insert(root_id, root);
+        progress.report(GENERATION_STAGE, 1, num_planets as usize);
+
+        // Generate remaining planets, connecting each to a random existing planet
+        for _ in 1..num_planets {
+            let mut planet_name = names.next().expect("Batch holds one name per planet");
+            // Distinct names can still normalize to the same id; draw fresh names until unique
+            while map.planets.contains_key(&utils::name_to_id(&planet_name)) {
+                planet_name = name_generator.generate()?;
+            }
+            let planet_id = utils::name_to_id(&planet_name);
+
+            // Pick random existing planet to connect to (sorted so a seeded rng yields the same map)
+            let keys: Vec<_> = map.planets.keys().collect();
+            let parent_id = keys[rng.random_range(0..keys.len())].clone();
+            let (parent_x, parent_y) = *map.planet_positions.get(&parent_id)
+                .expect("parent_id was just selected from planets.keys()");
+            
+            let rand_pos_x = rng.random_range(0..GRID_WIDTH);
+            let rand_pos_y = rng.random_range(0..GRID_HEIGHT);
+            map.planet_positions.insert(planet_id.clone(), (rand_pos_x, rand_pos_y));
+
+            map.planets.insert(planet_id.clone(), Planet::new(planet_id.clone(), planet_name, None, Vec::new()));
+            let distance = Self::scaled_distance((rand_pos_x, rand_pos_y), (parent_x, parent_y), GRID_WIDTH, GRID_HEIGHT);
+            map.add_edge(&planet_id, &parent_id, distance, ConnectionKind::Lane)?;
+            progress.report(GENERATION_STAGE, map.planets.len(), num_planets as usize);
+        }
+
+        debug_assert_eq!(map.graph().check_consistency(), Ok(()));
+        Ok(map)
+    }
+
+    /// Leaves ruins on neutral planets, each with `chance_percent` chance: half of them a
+    /// ruined structure, the rest a resource cache. Planets are visited in id order, so a
+    /// seeded rng places the same ruins.
+    pub fn place_ruins(&mut self, chance_percent: u32, rng: &mut impl Rng) {
+        let mut neutral: Vec<_> = self.planets.values_mut()
+            .filter(|planet| planet.get_owner().is_none())
+            .collect();
+        neutral.sort_by(|a, b| a.id.cmp(&b.id));
+
+        for planet in neutral {
+            if rng.random_range(0..100) >= chance_percent {
+                continue;
+            }
+            let ruin = if rng.random_bool(0.5) {
+                Ruin::Structure(RUIN_STRUCTURES[rng.random_range(0..RUIN_STRUCTURES.len())].to_string())
+            } else {
+                Ruin::Cache(Resources {
+                    minerals: rng.random_range(2..=6) * 50,
+                    gas: rng.random_range(1..=3) * 50,
+                    energy: 0,
+                })
+            };
+            planet.set_ruin(Some(ruin));
+        }
+    }
+
+    /// Gives every player a random starting planet, colonized and stocked for the game's speed.
+    pub fn assign_starting_planets(
+        &mut self,
+        players: &mut BTreeMap<PlayerId, Player>,
+        structure_config: &StructureConfig,
+        speed: GameSpeed,
+        rng: &mut impl Rng,
+    ) -> Result<(), PlanetError> {
+        let mut available_ids: Vec<_> = self.planets.keys().cloned().collect();
+
+        for player in players.values_mut() {
+            let index = rng.random_range(0..available_ids.len());
+            let planet_id = available_ids.swap_remove(index);
+
+            self.settle_starting_planet(player, planet_id, structure_config, speed)?;
+        }
+
+        Ok(())
+    }
+
+    /// Gives `player` a colonized starting planet, stocked for the game's speed.
+    pub fn settle_starting_planet(
+        &mut self,
+        player: &mut Player,
+        planet_id: PlanetId,
+        structure_config: &StructureConfig,
+        speed: GameSpeed,
+    ) -> Result<(), PlanetError> {
+        if let Some(planet) = self.planets.get_mut(&planet_id) {
+            planet.set_owner(player.id.clone());
+            planet.colonize(structure_config, 1)?;
+            planet.available_resources = planet.available_resources.percentage(speed.starting_resources_percent());
+        }
+
+        player.explored.insert(planet_id.clone());
+        player.planets.push(planet_id);
+        Ok(())
+    }
+
+    /// Marks up to `count` neutral planets as key planets for a domination victory. Planets
+    /// are drawn in id order, so a seeded rng marks the same ones.
+    pub fn place_key_planets(&mut self, count: u32, rng: &mut impl Rng) {
+        let mut neutral: Vec<_> = self.planets.values_mut()
+            .filter(|planet| planet.get_owner().is_none())
+            .collect();
+        neutral.sort_by(|a, b| a.id.cmp(&b.id));
+
+        for _ in 0..count {
+            if neutral.is_empty() {
+                break;
+            }
+            neutral.swap_remove(rng.random_range(0..neutral.len())).set_key_planet(true);
+        }
+    }
+
+    /// Key planets sorted by id; empty unless the game plays for domination.
+    pub fn key_planets(&self) -> Vec<&Planet> {
+        self.planets.values().filter(|planet| planet.is_key_planet()).collect()
+    }
+
+    /// Queries over the connections between planets. Shortest distances are memoized across
+    /// calls until `add_edge` or `remove_edge` changes the connections.
+    pub fn graph(&self) -> Graph<'_> {
+        Graph::new(&self.planets, &self.distances)
+    }
+
+    /// Connects two known planets both ways, or changes the travel time and kind of an
+    /// existing connection, keeping the two directions equal. Fails, changing nothing, when
+    /// `distance` is outside 1..=`max_distance`.
+    pub fn add_edge(
+        &mut self,
+        first: &PlanetId,
+        second: &PlanetId,
+        distance: u8,
+        kind: ConnectionKind,
+    ) -> Result<(), MapError> {
+        let forward = Connection::new(first, second, distance, kind, self.max_distance)?;
+        let backward = Connection::new(second, first, distance, kind, self.max_distance)?;
+        for (from, connection) in [(first, forward), (second, backward)] {
+            self.planets.get_mut(from)
+                .expect("Only known planets can be connected")
+                .set_connection(connection);
+        }
+        self.graph().invalidate();
+        Ok(())
+    }
+
+    /// Checks that every connection takes 1 to `max_distance` turns, as a map read from a
+    /// file may not. Returns the first offending connection, in planet id order.
+    pub fn validate_distances(&self) -> Result<(), MapError> {
+        for (from, planet) in &self.planets {
+            for connection in planet.get_connections() {
+                Connection::new(from, connection.to(), connection.distance(), connection.kind(), self.max_distance)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the connection between two planets in both directions. Returns whether they
+    /// were connected.
+    pub fn remove_edge(&mut self, first: &PlanetId, second: &PlanetId) -> bool {
+        let mut removed = false;
+        for (from, to) in [(first, second), (second, first)] {
+            if let Some(planet) = self.planets.get_mut(from) {
+                removed |= planet.remove_connection(to);
+            }
+        }
+        self.graph().invalidate();
+        removed
+    }
+
+    /// Converts the manhattan distance between two grid positions into travel turns,
+    /// from 1 for neighbours up to MAX_DISTANCE for opposite corners of the grid.
+    /// Works in u32 so corner-to-corner distances on any u8 grid cannot overflow.
+    fn scaled_distance(from: (u8, u8), to: (u8, u8), grid_width: u8, grid_height: u8) -> u8 {
+        let distance = u32::from(from.0.abs_diff(to.0)) + u32::from(from.1.abs_diff(to.1));
+        let norm = ((u32::from(grid_width) + u32::from(grid_height)) / u32::from(MAX_DISTANCE)).max(1);
+        let scaled = distance.div_ceil(norm).clamp(1, u32::from(MAX_DISTANCE));
+        u8::try_from(scaled).expect("Clamped to MAX_DISTANCE")
+    }
+
+    /// Draws the map with owner tags and `presence` markers in the planet labels, followed by
+    /// a legend of the tags. Planet ids longer than `max_id_chars` are truncated.
+    pub fn render_full(
+        &self,
+        players: &BTreeMap<PlayerId, Player>,
+        presence: &HashMap<PlanetId, PlanetPresence>,
+        max_id_chars: usize,
+        style: RenderStyle,
+    ) -> String {
+        let planet_icon = style.planet_icon();
+        let width = GRID_WIDTH as usize;
+        let height = GRID_HEIGHT as usize;
+
+        let mut grid: Vec<char> = vec![' '; width * height];
+
+        // Helper to convert (x, y) to flat index
+        let idx = |x: usize, y: usize| y * width + x;
+
+        // Draw borders
+        for x in 0..width {
+            grid[idx(x, 0)] = '#';
+            grid[idx(x, height - 1)] = '#';
+        }
+        for y in 0..height {
+            grid[idx(0, y)] = '#';
+            grid[idx(width - 1, y)] = '#';
+        }
+
+        // Draw connection lines between planets
+        for (planet_id, planet) in &self.planets {
+            let Some(&(x1, y1)) = self.planet_positions.get(planet_id) else { continue };
+
+            for connection in planet.get_connections() {
+                let Some(&(x2, y2)) = self.planet_positions.get(connection.to()) else { continue };
+                Self::draw_line(&mut grid, width, (x1 as i32, y1 as i32), (x2 as i32, y2 as i32), connection.kind(), style);
+            }
+        }
+
+        // Draw planets on top of lines
+        for &(x, y) in self.planet_positions.values() {
+            grid[idx(x as usize, y as usize)] = planet_icon;
+        }
+
+        // Draw labels on top of everything (so they don't get interrupted by edges)
+        for (planet_id, planet) in &self.planets {
+            let Some(&(x, y)) = self.planet_positions.get(planet_id) else { continue };
+            let planet_presence = presence.get(planet_id);
+            let owner_tag = match Self::known_owner(planet, planet_presence) {
+                Some(owner) => owner.map(|owner_id| players.get(owner_id).map_or("?", |player| player.appearance.tag.as_str())),
+                None => Some("?"),
+            };
+            let id = Self::truncate_id(planet_id, max_id_chars, style);
+            let mut label = format!(" {}", Self::planet_label(&id, owner_tag, planet.is_key_planet(), planet_presence));
+            if let Some(turn) = Self::last_seen_turn(planet_presence) {
+                label.push_str(&format!("[t{}]", turn));
+            }
+
+            // Write label chars into grid, overwriting everything except borders
+            let label_start_x = x as usize + 1;
+            for (i, ch) in label.chars().enumerate() {
+                let label_x = label_start_x + i;
+                if label_x < width - 1 {
+                    let current_char = grid[idx(label_x, y as usize)];
+                    // Don't overwrite borders (#) or planet icons
+                    if current_char != '#' && current_char != planet_icon {
+                        grid[idx(label_x, y as usize)] = ch;
+                    }
+                }
+            }
+        }
+
+        // Convert grid to string
+        let mut map = String::with_capacity((width + 1) * height);
+        for y in 0..height {
+            for x in 0..width {
+                map.push(grid[idx(x, y)]);
+            }
+            map.push('\n');
+        }
+
+        let legend: Vec<_> = players.values()
+            .map(|player| format!("{} = {}", player.appearance.tag, player.name))
+            .collect();
+        if !legend.is_empty() {
+            map.push_str(&format!("Owners: {}\n", legend.join(", ")));
+        }
+        if self.planets.values().any(Planet::is_key_planet) {
+            map.push_str("[K] = key planet, hold a majority of them to win by domination\n");
+        }
+        if presence.values().any(|presence| presence.fogged.is_some()) {
+            map.push_str("Out of sensor range: [tN] = as of turn N, ? = unexplored\n");
+        }
+        // Player names may still hold characters the style cannot show
+        style.apply(&map).into_owned()
+    }
+
+    /// Lists every planet on its own line with its owner, presence markers and connections,
+    /// for terminals too narrow for `render_full`. Planets and connections are sorted by id.
+    pub fn render_list(
+        &self,
+        players: &BTreeMap<PlayerId, Player>,
+        presence: &HashMap<PlanetId, PlanetPresence>,
+    ) -> String {
+        let mut lines = Vec::with_capacity(self.planets.len());
+        for planet in self.planets.values() {
+            let planet_presence = presence.get(&planet.id);
+            let owner_name = match Self::known_owner(planet, planet_presence) {
+                Some(owner) => owner.map(|owner_id| players.get(owner_id).map_or("Unknown", |player| player.name.as_str())),
+                None => Some("?"),
+            };
+            let mut line = Self::planet_label(&planet.id, owner_name, planet.is_key_planet(), planet_presence);
+            if let Some(turn) = Self::last_seen_turn(planet_presence) {
+                line.push_str(&format!(" (as of turn {})", turn));
+            }
+
+            let mut connections: Vec<_> = planet.get_connections().iter().collect();
+            connections.sort_by(|a, b| a.to().cmp(b.to()));
+            for connection in connections {
+                let name = self.planets.get(connection.to()).map_or(connection.to().as_str(), |to| to.name.as_str());
+                match connection.kind() {
+                    ConnectionKind::Lane => line.push_str(&format!("  -> {} ({})", name, connection.distance())),
+                    ConnectionKind::Gate => line.push_str(&format!("  -> {} ({}, gate)", name, connection.distance())),
+                }
+            }
+            lines.push(line);
+        }
+        lines.join("\n")
+    }
+
+    /// Planet id followed by the owner, a key planet marker and any presence markers, e.g.
+    /// `c418 (Alice) [K][F2]`.
+    fn planet_label(id: &str, owner: Option<&str>, key_planet: bool, presence: Option<&PlanetPresence>) -> String {
+        let mut label = match owner {
+            Some(owner) => format!("{} ({})", id, owner),
+            None => id.to_string(),
+        };
+        let mut markers = presence.map(PlanetPresence::markers).unwrap_or_default();
+        if key_planet {
+            markers.insert_str(0, "[K]");
+        }
+        if !markers.is_empty() {
+            label.push(' ');
+            label.push_str(&markers);
+        }
+        label
+    }
+
+    /// Owner as the viewer knows it: live, or as last seen for fogged planets. None when the
+    /// planet was never observed.
+    fn known_owner<'a>(planet: &'a Planet, presence: Option<&'a PlanetPresence>) -> Option<Option<&'a PlayerId>> {
+        match presence.and_then(|presence| presence.fogged.as_ref()) {
+            None => Some(planet.get_owner().as_ref()),
+            Some(Fogged::LastSeen { owner, .. }) => Some(owner.as_ref()),
+            Some(Fogged::Unexplored) => None,
+        }
+    }
+
+    fn last_seen_turn(presence: Option<&PlanetPresence>) -> Option<u32> {
+        match presence?.fogged {
+            Some(Fogged::LastSeen { turn, .. }) => Some(turn),
+            _ => None,
+        }
+    }
+
+    /// Cuts `id` to at most `max_chars` characters, ending in "…" (or "~") when shortened.
+    fn truncate_id(id: &str, max_chars: usize, style: RenderStyle) -> String {
+        if id.chars().count() <= max_chars {
+            return id.to_string();
+        }
+        let mut truncated: String = id.chars().take(max_chars.saturating_sub(1)).collect();
+        truncated.push(style.glyph('…'));
+        truncated
+    }
+
+    /// Draw a line between two points using Bresenham's algorithm. Gate links are dotted.
+    fn draw_line(
+        grid: &mut [char],
+        width: usize,
+        (mut x1, mut y1): (i32, i32),
+        (x2, y2): (i32, i32),
+        kind: ConnectionKind,
+        style: RenderStyle,
+    ) {
+        let dx = (x2 - x1).abs();
+        let dy = -(y2 - y1).abs();
+        let sx = if x1 < x2 { 1 } else { -1 };
+        let sy = if y1 < y2 { 1 } else { -1 };
+        let mut err = dx + dy;
+
+        loop {
+            let idx = y1 as usize * width + x1 as usize;
+
+            // Choose line character based on direction
+            let ch = if kind == ConnectionKind::Gate {
+                style.glyph('·')
+            } else if dx == 0 {
+                style.glyph('│')
+            } else if dy == 0 {
+                style.glyph('─')
+            } else if (sx > 0 && sy > 0) || (sx < 0 && sy < 0) {
+                '\\'
+            } else {
+                '/'
+            };
+
+            // Only draw if cell is empty (don't overwrite planets or borders)
+            if grid[idx] == ' ' {
+                grid[idx] = ch;
+            }
+
+            if x1 == x2 && y1 == y2 {
+                break;
+            }
+
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x1 += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y1 += sy;
+            }
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+    use crate::engine::appearance;
+    use crate::engine::configs::PlanetNameParts;
+    use crate::engine::progress::RecordedProgress;
+    use crate::test_support::{self, fixture_game};
+
+    #[test]
+    fn test_starting_resources_follow_game_speed() {
+        let starting_minerals = |speed: GameSpeed| {
+            let mut state = test_support::fixture_draft_game(1);
+            let mut rng = StdRng::seed_from_u64(1);
+            state.map.assign_starting_planets(&mut state.players, &state.structure_config, speed, &mut rng).unwrap();
+            state.map.planets[&test_support::home_planet(&state, "alice")].available_resources.minerals
+        };
+
+        let normal = starting_minerals(GameSpeed::Normal);
+        assert!(normal > 0);
+        assert_eq!(starting_minerals(GameSpeed::Quick), normal * 2);
+        assert_eq!(starting_minerals(GameSpeed::Epic), normal / 2);
+    }
+
+    #[test]
+    fn test_scaled_distance_on_giant_grid_does_not_overflow() {
+        let far_corner = Map::scaled_distance((0, 0), (u8::MAX, u8::MAX), u8::MAX, u8::MAX);
+        let neighbours = Map::scaled_distance((10, 10), (10, 11), u8::MAX, u8::MAX);
+        let same_spot = Map::scaled_distance((7, 7), (7, 7), 0, 0);
+
+        assert_eq!(far_corner, MAX_DISTANCE);
+        assert_eq!(neighbours, 1);
+        assert_eq!(same_spot, 1);
+    }
+
+    #[test]
+    fn test_add_edge_refuses_distances_outside_the_cap() {
+        let mut map = fixture_game(1).map;
+        let (first, second) = {
+            let (first, planet) = map.planets.iter().find(|(_, planet)| !planet.get_connections().is_empty()).unwrap();
+            (first.clone(), planet.get_connections()[0].to().clone())
+        };
+        let before = map.graph().edge_distance(&first, &second);
+
+        for distance in [0, MAX_DISTANCE + 1] {
+            let result = map.add_edge(&first, &second, distance, ConnectionKind::Lane);
+            assert!(matches!(result, Err(MapError::InvalidConnection(ConnectionError::InvalidDistance { distance: found, .. })) if found == distance));
+        }
+        assert_eq!(map.graph().edge_distance(&first, &second), before);
+
+        map.max_distance = MAX_DISTANCE + 1;
+        map.add_edge(&first, &second, MAX_DISTANCE + 1, ConnectionKind::Lane).unwrap();
+        assert_eq!(map.graph().edge_distance(&second, &first), Some(MAX_DISTANCE + 1));
+        assert!(map.validate_distances().is_ok());
+    }
+
+    #[test]
+    fn test_render_is_identical_for_same_seed() {
+        let first = fixture_game(3);
+        let second = fixture_game(3);
+
+        let render = |map: &Map| map.render_full(&first.players, &HashMap::new(), Map::DEFAULT_LABEL_ID_CHARS, RenderStyle::Unicode);
+        assert_eq!(render(&first.map), render(&second.map));
+    }
+
+    #[test]
+    fn test_ascii_render_has_only_ascii_bytes() {
+        let state = fixture_game(1);
+        let names = ["Élodie", "Bob"];
+        let players: BTreeMap<_, _> = ["alice", "bob"].into_iter()
+            .zip(names)
+            .zip(appearance::assign(&names))
+            .map(|((id, name), appearance)| {
+                (id.to_string(), Player::new(id.to_string(), name.to_string(), appearance))
+            })
+            .collect();
+
+        let unicode = state.map.render_full(&players, &HashMap::new(), 3, RenderStyle::Unicode);
+        let ascii = state.map.render_full(&players, &HashMap::new(), 3, RenderStyle::AsciiOnly);
+
+        assert!(!unicode.is_ascii());
+        assert!(ascii.is_ascii(), "{}", ascii);
+        assert_eq!(ascii.lines().count(), unicode.lines().count());
+    }
+
+    #[test]
+    fn test_ascii_style_substitutes_known_glyphs() {
+        let text = "⚔ BATTLE at Kepler… shields 20 → 10 HP";
+
+        assert_eq!(RenderStyle::AsciiOnly.apply(text), "! BATTLE at Kepler~ shields 20 -> 10 HP");
+        assert_eq!(RenderStyle::Unicode.apply(text), text);
+        assert_eq!(RenderStyle::AsciiOnly.apply("李"), "?");
+    }
+
+    #[test]
+    fn test_render_list_shows_owner_markers_and_connections() {
+        let state = fixture_game(1);
+        let home = &state.players["alice"].planets[0];
+        let presence = HashMap::from([(home.clone(), PlanetPresence { own_fleets: 1, ..Default::default() })]);
+
+        let render = state.map.render_list(&state.players, &presence);
+        let lines: Vec<_> = render.lines().collect();
+
+        assert_eq!(lines.len(), state.map.planets.len());
+        assert!(lines.is_sorted());
+        let home_line = lines.iter().find(|line| line.starts_with(&format!("{} ", home))).unwrap();
+        assert!(home_line.starts_with(&format!("{} (Alice) [F1]  -> ", home)), "{}", home_line);
+        for connection in state.map.planets[home].get_connections() {
+            let expected = format!("-> {} ({})", state.map.planets[connection.to()].name, connection.distance());
+            assert!(home_line.contains(&expected), "{}", home_line);
+        }
+    }
+
+    #[test]
+    fn test_owner_tags_come_from_player_appearance_and_labels_stay_in_budget() {
+        let state = fixture_game(1);
+        let names = ["Alexandria", "Alexandros"];
+        let players: BTreeMap<_, _> = ["alice", "bob"].into_iter()
+            .zip(names)
+            .zip(appearance::assign(&names))
+            .map(|((id, name), appearance)| {
+                (id.to_string(), Player::new(id.to_string(), name.to_string(), appearance))
+            })
+            .collect();
+        let max_id_chars = 6;
+
+        let render = state.map.render_full(&players, &HashMap::new(), max_id_chars, RenderStyle::Unicode);
+
+        assert!(render.ends_with("Owners: AL = Alexandria, AS = Alexandros\n"));
+        for planet in state.map.planets.values() {
+            let id = Map::truncate_id(&planet.id, max_id_chars, RenderStyle::Unicode);
+            let owner_tag = planet.get_owner().as_ref().map(|owner_id| players[owner_id].appearance.tag.as_str());
+            let label = Map::planet_label(&id, owner_tag, false, None);
+
+            assert!(id.chars().count() <= max_id_chars);
+            assert!(label.chars().count() <= max_id_chars + " (ABC)".len(), "{}", label);
+            if planet.id.chars().count() > max_id_chars {
+                assert!(!render.contains(&planet.id), "{} was not truncated", planet.id);
+            }
+        }
+    }
+
+    #[test]
+    fn test_seeded_medium_map_places_ruins_on_neutral_planets_only() {
+        let place = |chance_percent| {
+            let mut rng = StdRng::seed_from_u64(3);
+            let mut map = Map::generate(MapSize::Medium, &mut crate::test_support::fixture_name_generator(3), &mut rng).unwrap();
+            let home = map.planets.keys().min().unwrap().clone();
+            map.planets.get_mut(&home).unwrap().set_owner(String::from("alice"));
+            map.place_ruins(chance_percent, &mut rng);
+            let ruins: Vec<_> = map.planets.values()
+                .filter_map(|planet| planet.get_ruin().map(|ruin| (planet.id.clone(), ruin.clone())))
+                .collect();
+            (home, ruins)
+        };
+
+        let (home, ruins) = place(crate::engine::game_rules::DEFAULT_RUIN_CHANCE_PERCENT);
+
+        assert!(!ruins.is_empty());
+        assert!(ruins.iter().all(|(planet_id, _)| *planet_id != home));
+        assert_eq!(place(crate::engine::game_rules::DEFAULT_RUIN_CHANCE_PERCENT).1, ruins);
+        assert!(place(0).1.is_empty());
+        assert_eq!(place(100).1.len(), MapSize::Medium.num_planets() as usize - 1);
+    }
+
+    #[test]
+    fn test_key_planets_are_neutral_and_marked_on_the_map() {
+        let mut state = fixture_game(1);
+        let mut rng = StdRng::seed_from_u64(1);
+        state.map.place_key_planets(3, &mut rng);
+
+        let key_planets = state.map.key_planets();
+        assert_eq!(key_planets.len(), 3);
+        assert!(key_planets.iter().all(|planet| planet.get_owner().is_none()));
+
+        let list = state.map.render_list(&state.players, &HashMap::new());
+        assert_eq!(list.lines().filter(|line| line.contains("[K]")).count(), 3);
+        let render = state.map.render_full(&state.players, &HashMap::new(), Map::DEFAULT_LABEL_ID_CHARS, RenderStyle::Unicode);
+        assert!(render.contains("[K] = key planet"), "{render}");
+    }
+
+    #[test]
+    fn test_generate_fails_up_front_when_names_run_out() {
+        let name_parts = PlanetNameParts {
+            prefixes: vec![String::from("Crimson")],
+            suffixes: vec![String::from("Theta")],
+        };
+        let mut generator = PlanetNameGenerator::with_seed(name_parts, 1);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let result = Map::generate(MapSize::Medium, &mut generator, &mut rng);
+
+        assert!(matches!(result, Err(MapError::NotEnoughNames { requested: 20, available: 11 })));
+        assert_eq!(generator.remaining(), 11);
+    }
+
+    #[test]
+    fn test_generation_reports_every_planet_in_order() {
+        // The fixture names run out before 200; the shipped pack has enough
+        let name_parts = PlanetNameParts::load_from_string(include_str!("../../../data/planet_names.json")).unwrap();
+        let mut progress = RecordedProgress::default();
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let map = Map::generate_planets(
+            MapSize::Large,
+            200,
+            &mut PlanetNameGenerator::with_seed(name_parts, 1),
+            &mut rng,
+            &mut progress,
+        ).unwrap();
+
+        assert_eq!(map.planets.len(), 200);
+        assert_eq!(progress.reports.len(), 200);
+        assert!(progress.reports.iter().all(|(stage, _, total)| stage == GENERATION_STAGE && *total == 200));
+        assert!(progress.reports.windows(2).all(|pair| pair[1].1 == pair[0].1 + 1));
+        assert_eq!(progress.reports.last().map(|report| report.1), Some(200));
+    }
+}
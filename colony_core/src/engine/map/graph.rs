@@ -0,0 +1,348 @@
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap, VecDeque};
+
+use crate::engine::planet::{Connection, Planet, PlanetId};
+
+/// A connection whose two directions disagree.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum GraphError {
+    #[error("Connection from {from} leads to unknown planet {to}")]
+    UnknownPlanet { from: PlanetId, to: PlanetId },
+
+    #[error("Connection {from} -> {to} has no way back")]
+    MissingReverse { from: PlanetId, to: PlanetId },
+
+    #[error("Connection {from} -> {to} takes {forward} turn(s) but the way back takes {backward}")]
+    DistanceMismatch { from: PlanetId, to: PlanetId, forward: u8, backward: u8 },
+}
+
+/// Shortest distances from each planet asked about so far to every planet it can reach.
+/// Held by the map for the whole game and filled lazily, one source planet at a time.
+#[derive(Default)]
+pub(super) struct DistanceCache {
+    tables: RefCell<HashMap<PlanetId, HashMap<PlanetId, u32>>>,
+}
+
+/// Read-only view of the connections between planets, sharing the map's distance cache.
+pub struct Graph<'a> {
+    planets: &'a BTreeMap<PlanetId, Planet>,
+    cache: &'a DistanceCache,
+}
+
+impl<'a> Graph<'a> {
+    pub(super) fn new(planets: &'a BTreeMap<PlanetId, Planet>, cache: &'a DistanceCache) -> Self {
+        Graph { planets, cache }
+    }
+
+    /// Forgets every memoized distance. `Map::add_edge` and `Map::remove_edge` call it; anything that changes
+    /// connections through `Map::planets` directly must call it too.
+    pub fn invalidate(&self) {
+        self.cache.tables.borrow_mut().clear();
+    }
+
+    /// Planets directly connected to `planet_id`, with the travel time in turns. Empty for
+    /// unknown planets.
+    pub fn neighbors(&self, planet_id: &PlanetId) -> impl Iterator<Item = (&'a PlanetId, u8)> + use<'a> {
+        let connections: &'a [Connection] = self.planets.get(planet_id)
+            .map_or(&[], |planet| planet.get_connections().as_slice());
+        connections.iter().map(|connection| (connection.to(), connection.distance()))
+    }
+
+    /// Travel time of the direct connection from `from` to `to`, if there is one.
+    pub fn edge_distance(&self, from: &PlanetId, to: &PlanetId) -> Option<u8> {
+        self.neighbors(from)
+            .find(|(neighbor, _)| *neighbor == to)
+            .map(|(_, distance)| distance)
+    }
+
+    pub fn are_adjacent(&self, first: &PlanetId, second: &PlanetId) -> bool {
+        self.edge_distance(first, second).is_some()
+    }
+
+    /// Fewest turns needed to travel from `from` to `to`; None when either planet is unknown
+    /// or `to` cannot be reached.
+    pub fn distance(&self, from: &PlanetId, to: &PlanetId) -> Option<u32> {
+        if !self.planets.contains_key(from) {
+            return None;
+        }
+        let mut tables = self.cache.tables.borrow_mut();
+        let table = tables.entry(from.clone()).or_insert_with(|| self.shortest_from(from));
+        table.get(to).copied()
+    }
+
+    /// Planets along a quickest route from `from` to `to`, both included; None when either
+    /// planet is unknown or `to` cannot be reached. Ties between equally quick routes are
+    /// broken by planet id, so the same route is always reported.
+    pub fn route(&self, from: &PlanetId, to: &PlanetId) -> Option<Vec<PlanetId>> {
+        let mut remaining = self.distance(from, to)?;
+        let mut route = vec![to.clone()];
+        // Walk back from the destination through neighbors that lie on a quickest route
+        while remaining > 0 {
+            let current = route.last().expect("Routes start with the destination");
+            let (previous, edge) = self.neighbors(current)
+                .filter_map(|(neighbor, _)| Some((neighbor, self.edge_distance(neighbor, current)?)))
+                .filter(|(neighbor, edge)| {
+                    self.distance(from, neighbor).is_some_and(|distance| distance + u32::from(*edge) == remaining)
+                })
+                .min_by_key(|(neighbor, _)| *neighbor)?;
+            remaining -= u32::from(edge);
+            route.push(previous.clone());
+        }
+        route.reverse();
+        Some(route)
+    }
+
+    /// Fewest connections crossed from `from` to each planet it can reach, whatever each
+    /// takes to travel. Empty for unknown planets.
+    pub fn hops(&self, from: &PlanetId) -> HashMap<PlanetId, u32> {
+        if !self.planets.contains_key(from) {
+            return HashMap::new();
+        }
+        let mut hops = HashMap::from([(from.clone(), 0)]);
+        let mut queue = VecDeque::from([from]);
+        while let Some(planet_id) = queue.pop_front() {
+            let next = hops[planet_id] + 1;
+            for (neighbor, _) in self.neighbors(planet_id) {
+                if !hops.contains_key(neighbor) {
+                    hops.insert(neighbor.clone(), next);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        hops
+    }
+
+    /// Dijkstra's algorithm over the whole graph from `source`.
+    fn shortest_from(&self, source: &PlanetId) -> HashMap<PlanetId, u32> {
+        let mut distances = HashMap::from([(source.clone(), 0)]);
+        let mut queue = BinaryHeap::from([Reverse((0, source))]);
+
+        while let Some(Reverse((distance, planet_id))) = queue.pop() {
+            if distances.get(planet_id).is_some_and(|&best| best < distance) {
+                continue;
+            }
+            for (neighbor, edge) in self.neighbors(planet_id) {
+                let through = distance + u32::from(edge);
+                if distances.get(neighbor).is_none_or(|&best| through < best) {
+                    distances.insert(neighbor.clone(), through);
+                    queue.push(Reverse((through, neighbor)));
+                }
+            }
+        }
+        distances
+    }
+
+    /// Checks that every connection leads to a known planet and has a way back of the same
+    /// length. Problems are returned sorted, so the first one is stable.
+    pub fn check_consistency(&self) -> Result<(), Vec<GraphError>> {
+        let mut problems = Vec::new();
+        for (from, planet) in self.planets {
+            for connection in planet.get_connections() {
+                let to = connection.to();
+                if !self.planets.contains_key(to) {
+                    problems.push(GraphError::UnknownPlanet { from: from.clone(), to: to.clone() });
+                    continue;
+                }
+                match self.edge_distance(to, from) {
+                    None => problems.push(GraphError::MissingReverse { from: from.clone(), to: to.clone() }),
+                    Some(backward) if backward != connection.distance() => problems.push(GraphError::DistanceMismatch {
+                        from: from.clone(),
+                        to: to.clone(),
+                        forward: connection.distance(),
+                        backward,
+                    }),
+                    Some(_) => {}
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            return Ok(());
+        }
+        problems.sort_by_key(|problem| problem.to_string());
+        Err(problems)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    use crate::engine::map::{MAX_DISTANCE, Map, MapSize};
+    use crate::engine::planet::ConnectionKind;
+    use crate::test_support::fixture_game;
+
+    fn planet_ids(map: &Map) -> Vec<PlanetId> {
+        map.planets.keys().cloned().collect()
+    }
+
+    /// A custom-sized map: a binary tree with every planet also linked to the one before it,
+    /// so most pairs have several routes of different lengths.
+    fn large_map(num_planets: usize) -> Map {
+        let ids: Vec<PlanetId> = (0..num_planets).map(|index| format!("p{:03}", index)).collect();
+        let mut map = Map {
+            planets: ids.iter()
+                .map(|id| (id.clone(), Planet::new(id.clone(), id.clone(), None, Vec::new())))
+                .collect(),
+            planet_positions: HashMap::new(),
+            size: MapSize::Large,
+            max_distance: MAX_DISTANCE,
+            distances: DistanceCache::default(),
+        };
+        for index in 1..num_planets {
+            map.add_edge(&ids[index], &ids[(index - 1) / 2], 1 + (index % 4) as u8, ConnectionKind::Lane).unwrap();
+            map.add_edge(&ids[index], &ids[index - 1], 2 + (index % 3) as u8, ConnectionKind::Lane).unwrap();
+        }
+        map
+    }
+
+    #[test]
+    fn test_generated_maps_are_consistent_and_connected() {
+        for seed in 0..20 {
+            let map = fixture_game(seed).map;
+            let graph = map.graph();
+            let ids = planet_ids(&map);
+
+            assert_eq!(graph.check_consistency(), Ok(()), "seed {}", seed);
+            assert!(ids.iter().all(|id| graph.distance(&ids[0], id).is_some()), "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_distance_follows_the_shortest_path() {
+        let state = fixture_game(1);
+        let graph = state.map.graph();
+        let home = &state.players["alice"].planets[0];
+
+        assert_eq!(graph.distance(home, home), Some(0));
+        for (neighbor, edge) in graph.neighbors(home) {
+            assert!(graph.are_adjacent(neighbor, home));
+            assert!(graph.distance(home, neighbor).unwrap() <= u32::from(edge));
+            for (next, next_edge) in graph.neighbors(neighbor) {
+                assert!(graph.distance(home, next).unwrap() <= u32::from(edge) + u32::from(next_edge));
+            }
+        }
+        assert_eq!(graph.distance(home, &String::from("nowhere")), None);
+        assert_eq!(graph.distance(&String::from("nowhere"), home), None);
+    }
+
+    #[test]
+    fn test_route_follows_a_quickest_path_hop_by_hop() {
+        let map = large_map(12);
+        let graph = map.graph();
+        let ids = planet_ids(&map);
+
+        for (from, to) in [(&ids[0], &ids[11]), (&ids[7], &ids[10]), (&ids[3], &ids[3])] {
+            let route = graph.route(from, to).unwrap();
+            assert_eq!((route.first(), route.last()), (Some(from), Some(to)));
+            let turns: u32 = route.windows(2)
+                .map(|hop| u32::from(graph.edge_distance(&hop[0], &hop[1]).expect("Route hops are connected")))
+                .sum();
+            assert_eq!(Some(turns), graph.distance(from, to));
+        }
+        assert_eq!(graph.route(&ids[0], &String::from("nowhere")), None);
+    }
+
+    #[test]
+    fn test_hops_count_connections_not_travel_time() {
+        let map = large_map(12);
+        let graph = map.graph();
+        let hops = graph.hops(&String::from("p000"));
+
+        assert_eq!(hops.len(), 12);
+        assert_eq!(hops["p000"], 0);
+        assert_eq!(hops["p001"], 1);
+        // One connection away, however many turns it takes
+        assert_eq!(hops["p002"], 1);
+        assert_eq!(graph.distance(&String::from("p000"), &String::from("p002")), Some(3));
+        assert_eq!(hops["p007"], 3);
+        assert!(graph.hops(&String::from("nowhere")).is_empty());
+    }
+
+    #[test]
+    fn test_memoized_distances_are_recomputed_after_connecting_planets() {
+        let mut map = fixture_game(1).map;
+        let ids = planet_ids(&map);
+        let (from, to) = ids.iter()
+            .flat_map(|a| ids.iter().map(move |b| (a, b)))
+            .find(|(a, b)| map.graph().distance(a, b).is_some_and(|distance| distance > 1))
+            .map(|(a, b)| (a.clone(), b.clone()))
+            .expect("Fixture map must have planets more than a turn apart");
+
+        let before = map.graph().distance(&from, &to).unwrap();
+        assert_eq!(map.graph().distance(&from, &to), Some(before));
+        assert_eq!(map.distances.tables.borrow().len(), 1);
+
+        map.add_edge(&from, &to, 1, ConnectionKind::Lane).unwrap();
+
+        let graph = map.graph();
+        assert_eq!(graph.distance(&from, &to), Some(1));
+        assert_eq!(graph.distance(&to, &from), Some(1));
+        assert_eq!(graph.check_consistency(), Ok(()));
+    }
+
+    #[test]
+    fn test_one_way_connection_is_reported() {
+        let mut map = fixture_game(1).map;
+        let ids = planet_ids(&map);
+        let (a, b) = ids.iter()
+            .flat_map(|a| ids.iter().map(move |b| (a, b)))
+            .find(|(a, b)| a != b && !map.graph().are_adjacent(a, b))
+            .map(|(a, b)| (a.clone(), b.clone()))
+            .expect("Fixture map must have planets that are not neighbors");
+        map.planets.get_mut(&a).unwrap().set_connection(Connection::new(&a, &b, 2, ConnectionKind::Lane, map.max_distance).unwrap());
+
+        let problems = map.graph().check_consistency().unwrap_err();
+
+        assert_eq!(problems, [GraphError::MissingReverse { from: a, to: b }]);
+    }
+
+    #[test]
+    fn test_cached_distances_match_fresh_search() {
+        let map = large_map(200);
+        let graph = map.graph();
+        let ids = planet_ids(&map);
+
+        for from in ids.iter().step_by(7) {
+            let fresh = graph.shortest_from(from);
+            assert_eq!(fresh.len(), ids.len());
+            for to in &ids {
+                assert_eq!(graph.distance(from, to), fresh.get(to).copied(), "{} -> {}", from, to);
+            }
+        }
+        assert_eq!(map.distances.tables.borrow().len(), ids.iter().step_by(7).count());
+
+        graph.invalidate();
+        assert!(map.distances.tables.borrow().is_empty());
+        assert_eq!(graph.distance(&ids[0], &ids[199]), graph.shortest_from(&ids[0]).get(&ids[199]).copied());
+    }
+
+    /// Query volume of one AI turn on a 200 planet map: a handful of owned planets, each
+    /// scoring every planet on the map as a target, twice over.
+    #[test]
+    fn test_cache_serves_an_ai_turn_from_one_search_per_source() {
+        let map = large_map(200);
+        let ids = planet_ids(&map);
+        let sources: Vec<_> = ids.iter().step_by(50).collect();
+        let queries = || (0..2).flat_map(|_| sources.iter().flat_map(|from| ids.iter().map(move |to| (*from, to))));
+
+        let started = Instant::now();
+        let uncached: Vec<_> = queries()
+            .map(|(from, to)| map.graph().shortest_from(from).get(to).copied())
+            .collect();
+        let uncached_time = started.elapsed();
+
+        let started = Instant::now();
+        let cached: Vec<_> = queries()
+            .map(|(from, to)| map.graph().distance(from, to))
+            .collect();
+        let cached_time = started.elapsed();
+
+        assert_eq!(cached.len(), 2 * sources.len() * ids.len());
+        assert_eq!(cached, uncached);
+        assert_eq!(map.distances.tables.borrow().len(), sources.len());
+        assert!(cached_time < uncached_time, "cached {:?}, uncached {:?}", cached_time, uncached_time);
+    }
+}
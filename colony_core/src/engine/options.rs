@@ -0,0 +1,465 @@
+//! Settings players can list and change during a game with the `options` command.
+//!
+//! Every option is declared once in [`OPTIONS`], which drives both the listing and the
+//! setter. Where an option lives depends on its [`OptionScope`].
+
+use std::fmt;
+
+use crate::engine::game_state::GameState;
+use crate::engine::map::RenderStyle;
+
+/// Most rounds allowed between automatic saves
+pub const MAX_AUTOSAVE_ROUNDS: u32 = 100;
+
+/// Turns whose views are kept for `diff` unless the history option says otherwise
+pub const DEFAULT_HISTORY_TURNS: u32 = 10;
+
+/// Most turns of views a game keeps
+pub const MAX_HISTORY_TURNS: u32 = 50;
+
+/// Milliseconds processing a round may take before everyone is warned, unless changed
+pub const DEFAULT_SLOW_ROUND_MS: u32 = 1000;
+
+/// Highest warning threshold for slow rounds, in milliseconds
+pub const MAX_SLOW_ROUND_MS: u32 = 600_000;
+
+fn default_history_turns() -> u32 {
+    DEFAULT_HISTORY_TURNS
+}
+
+fn default_slow_round_ms() -> u32 {
+    DEFAULT_SLOW_ROUND_MS
+}
+
+/// Where an option is kept, which decides who it affects and whether it can change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionScope {
+    /// Kept by the frontend for this session only; loading a save keeps it
+    Session,
+    /// Saved with the game and shared by every player
+    Game,
+    /// Part of the game's rules, fixed when the game was created
+    Fixed,
+}
+
+impl fmt::Display for OptionScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OptionScope::Session => write!(f, "session"),
+            OptionScope::Game => write!(f, "game"),
+            OptionScope::Fixed => write!(f, "fixed"),
+        }
+    }
+}
+
+/// Options of the frontend session; they are not part of the saved game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionOptions {
+    pub render_style: RenderStyle,
+    /// Whether the advisor speaks up at the start of the first turns
+    pub advisor: bool,
+    /// Whether deleting saves, loading over unsaved progress and exiting ask first
+    pub confirmations: bool,
+}
+
+impl Default for SessionOptions {
+    fn default() -> Self {
+        SessionOptions { render_style: RenderStyle::default(), advisor: true, confirmations: true }
+    }
+}
+
+impl SessionOptions {
+    pub fn apply(&mut self, option: SessionOption) {
+        match option {
+            SessionOption::Style(style) => self.render_style = style,
+            SessionOption::Advisor(on) => self.advisor = on,
+            SessionOption::Confirmations(on) => self.confirmations = on,
+        }
+    }
+}
+
+/// Options saved with the game that can still change while it is played.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct GameOptions {
+    /// Rounds between automatic saves; 0 turns them off
+    #[serde(default)]
+    pub autosave_rounds: u32,
+    /// Most recent turns whose views are kept for `diff`; 0 keeps none
+    #[serde(default = "default_history_turns")]
+    pub history_turns: u32,
+    /// Whether `status game` and save listings show the seed before the game is over
+    #[serde(default)]
+    pub reveal_seed: bool,
+    /// Milliseconds processing a round may take before a warning; 0 turns the warning off
+    #[serde(default = "default_slow_round_ms")]
+    pub slow_round_ms: u32,
+}
+
+impl Default for GameOptions {
+    fn default() -> Self {
+        GameOptions {
+            autosave_rounds: 0,
+            history_turns: DEFAULT_HISTORY_TURNS,
+            reveal_seed: false,
+            slow_round_ms: DEFAULT_SLOW_ROUND_MS,
+        }
+    }
+}
+
+impl GameOptions {
+    pub fn apply(&mut self, option: GameOption) {
+        match option {
+            GameOption::AutosaveRounds(rounds) => self.autosave_rounds = rounds,
+            GameOption::HistoryTurns(turns) => self.history_turns = turns,
+            GameOption::RevealSeed(on) => self.reveal_seed = on,
+            GameOption::SlowRoundMs(millis) => self.slow_round_ms = millis,
+        }
+    }
+
+    /// Whether the round that just brought the game to `turn` ends with an automatic save.
+    pub fn autosave_due(&self, turn: u32) -> bool {
+        self.autosave_rounds > 0 && turn > 1 && (turn - 1).is_multiple_of(self.autosave_rounds)
+    }
+}
+
+/// A new value for a session option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionOption {
+    Style(RenderStyle),
+    Advisor(bool),
+    Confirmations(bool),
+}
+
+impl fmt::Display for SessionOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionOption::Style(style) => write!(f, "Render style set to {}.", match style {
+                RenderStyle::Unicode => "Unicode",
+                RenderStyle::AsciiOnly => "ASCII only",
+            }),
+            SessionOption::Advisor(on) => write!(f, "Advisor turned {}.", on_off(*on)),
+            SessionOption::Confirmations(on) => write!(f, "Confirmation prompts turned {}.", on_off(*on)),
+        }
+    }
+}
+
+/// A new value for a game option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameOption {
+    AutosaveRounds(u32),
+    HistoryTurns(u32),
+    RevealSeed(bool),
+    SlowRoundMs(u32),
+}
+
+impl GameOption {
+    pub fn key(&self) -> &'static str {
+        match self {
+            GameOption::AutosaveRounds(_) => "autosave",
+            GameOption::HistoryTurns(_) => "history",
+            GameOption::RevealSeed(_) => "seed",
+            GameOption::SlowRoundMs(_) => "slow_round",
+        }
+    }
+
+    /// The new value as the listing shows it.
+    pub fn value(&self) -> String {
+        match self {
+            GameOption::AutosaveRounds(rounds) => autosave_text(*rounds),
+            GameOption::HistoryTurns(turns) => history_text(*turns),
+            GameOption::RevealSeed(on) => on_off(*on).to_string(),
+            GameOption::SlowRoundMs(millis) => slow_round_text(*millis),
+        }
+    }
+}
+
+/// A validated change to one option, ready to be applied where the option is kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionChange {
+    Session(SessionOption),
+    Game(GameOption),
+}
+
+/// Checks a new value for an option.
+type ParseValue = fn(&str) -> Result<OptionChange, String>;
+
+/// One entry of the options registry.
+pub struct OptionDef {
+    pub key: &'static str,
+    pub scope: OptionScope,
+    /// Accepted values, as shown in the listing
+    pub values: &'static str,
+    pub description: &'static str,
+    current: fn(&SessionOptions, &GameState) -> String,
+    /// Checks a new value; None for fixed options
+    parse: Option<ParseValue>,
+}
+
+impl OptionDef {
+    pub fn current(&self, session: &SessionOptions, game_state: &GameState) -> String {
+        (self.current)(session, game_state)
+    }
+
+    /// Validates `value` as the option's new value.
+    pub fn parse(&self, value: &str) -> Result<OptionChange, String> {
+        match self.parse {
+            Some(parse) => parse(value),
+            None => Err(String::from("it is part of the game's rules, which are fixed when the game is created")),
+        }
+    }
+}
+
+/// Every option, in the order they are listed.
+pub const OPTIONS: &[OptionDef] = &[
+    OptionDef {
+        key: "style",
+        scope: OptionScope::Session,
+        values: "unicode|ascii",
+        description: "Characters used for the map and output",
+        current: |session, _| String::from(match session.render_style {
+            RenderStyle::Unicode => "unicode",
+            RenderStyle::AsciiOnly => "ascii",
+        }),
+        parse: Some(|value| match value {
+            "unicode" => Ok(OptionChange::Session(SessionOption::Style(RenderStyle::Unicode))),
+            "ascii" => Ok(OptionChange::Session(SessionOption::Style(RenderStyle::AsciiOnly))),
+            _ => Err(String::from("valid styles are: unicode, ascii")),
+        }),
+    },
+    OptionDef {
+        key: "advisor",
+        scope: OptionScope::Session,
+        values: "on|off",
+        description: "Advisor suggestions at the start of the first turns",
+        current: |session, _| on_off(session.advisor).to_string(),
+        parse: Some(|value| parse_on_off(value).map(|on| OptionChange::Session(SessionOption::Advisor(on)))),
+    },
+    OptionDef {
+        key: "confirmations",
+        scope: OptionScope::Session,
+        values: "on|off",
+        description: "Ask before deleting saves, loading over or exiting with unsaved progress",
+        current: |session, _| on_off(session.confirmations).to_string(),
+        parse: Some(|value| parse_on_off(value).map(|on| OptionChange::Session(SessionOption::Confirmations(on)))),
+    },
+    OptionDef {
+        key: "autosave",
+        scope: OptionScope::Game,
+        values: "off|<rounds>",
+        description: "Save to the autosave slot every few rounds",
+        current: |_, game_state| autosave_text(game_state.options.autosave_rounds),
+        parse: Some(|value| {
+            let rounds = match value {
+                "off" => 0,
+                rounds => rounds.parse::<u32>()
+                    .ok()
+                    .filter(|rounds| *rounds <= MAX_AUTOSAVE_ROUNDS)
+                    .ok_or_else(|| format!("expected 'off' or a number of rounds up to {}", MAX_AUTOSAVE_ROUNDS))?,
+            };
+            Ok(OptionChange::Game(GameOption::AutosaveRounds(rounds)))
+        }),
+    },
+    OptionDef {
+        key: "history",
+        scope: OptionScope::Game,
+        values: "off|<turns>",
+        description: "Turns whose views are kept for 'diff'",
+        current: |_, game_state| history_text(game_state.options.history_turns),
+        parse: Some(|value| {
+            let turns = match value {
+                "off" => 0,
+                turns => turns.parse::<u32>()
+                    .ok()
+                    .filter(|turns| *turns <= MAX_HISTORY_TURNS)
+                    .ok_or_else(|| format!("expected 'off' or a number of turns up to {}", MAX_HISTORY_TURNS))?,
+            };
+            Ok(OptionChange::Game(GameOption::HistoryTurns(turns)))
+        }),
+    },
+    OptionDef {
+        key: "seed",
+        scope: OptionScope::Game,
+        values: "on|off",
+        description: "Show the game's seed in 'status game' before the game is over",
+        current: |_, game_state| on_off(game_state.options.reveal_seed).to_string(),
+        parse: Some(|value| parse_on_off(value).map(|on| OptionChange::Game(GameOption::RevealSeed(on)))),
+    },
+    OptionDef {
+        key: "slow_round",
+        scope: OptionScope::Game,
+        values: "off|<ms>",
+        description: "Warn everyone when processing a round takes longer than this",
+        current: |_, game_state| slow_round_text(game_state.options.slow_round_ms),
+        parse: Some(|value| {
+            let millis = match value {
+                "off" => 0,
+                millis => millis.trim_end_matches("ms").parse::<u32>()
+                    .ok()
+                    .filter(|millis| *millis <= MAX_SLOW_ROUND_MS)
+                    .ok_or_else(|| format!("expected 'off' or milliseconds up to {}", MAX_SLOW_ROUND_MS))?,
+            };
+            Ok(OptionChange::Game(GameOption::SlowRoundMs(millis)))
+        }),
+    },
+    OptionDef {
+        key: "rules",
+        scope: OptionScope::Fixed,
+        values: "classic|standard|custom",
+        description: "Construction slots and other pacing rules",
+        current: |_, game_state| game_state.rules_profile().preset().to_string(),
+        parse: None,
+    },
+    OptionDef {
+        key: "speed",
+        scope: OptionScope::Fixed,
+        values: "quick|normal|epic",
+        description: "Build times and starting resources",
+        current: |_, game_state| game_state.rules.speed.to_string(),
+        parse: None,
+    },
+    OptionDef {
+        key: "victory",
+        scope: OptionScope::Fixed,
+        values: "elimination|domination|both",
+        description: "How the game is won",
+        current: |_, game_state| game_state.rules.victory.to_string(),
+        parse: None,
+    },
+    OptionDef {
+        key: "production",
+        scope: OptionScope::Fixed,
+        values: "turn start|round end",
+        description: "When planets produce their resources",
+        current: |_, game_state| game_state.rules.production_timing.to_string(),
+        parse: None,
+    },
+    OptionDef {
+        key: "prerequisites",
+        scope: OptionScope::Fixed,
+        values: "built|under construction",
+        description: "What prerequisites must be for a structure needing them to be queued",
+        current: |_, game_state| {
+            let counted = if game_state.rules.prerequisites_under_construction { "under construction" } else { "built" };
+            String::from(counted)
+        },
+        parse: None,
+    },
+    OptionDef {
+        key: "protection",
+        scope: OptionScope::Fixed,
+        values: "off|<turns>",
+        description: "Opening turns in which players cannot bombard, blockade or invade each other",
+        current: |_, game_state| match game_state.rules.protected_turns {
+            0 => String::from("off"),
+            turns => format!("{} turns", turns),
+        },
+        parse: None,
+    },
+];
+
+/// Looks up an option by its key.
+pub fn find(key: &str) -> Option<&'static OptionDef> {
+    OPTIONS.iter().find(|option| option.key == key)
+}
+
+/// Keys of every option, for error messages and completion.
+pub fn keys() -> Vec<&'static str> {
+    OPTIONS.iter().map(|option| option.key).collect()
+}
+
+/// Every option with its scope, current value and accepted values.
+pub fn format_options(session: &SessionOptions, game_state: &GameState) -> String {
+    let key_width = OPTIONS.iter().map(|option| option.key.len()).max().unwrap_or(0);
+    let current: Vec<_> = OPTIONS.iter().map(|option| option.current(session, game_state)).collect();
+    let value_width = current.iter().map(|value| value.chars().count()).max().unwrap_or(0);
+
+    let mut text = String::from("=== Options ===");
+    for (option, current) in OPTIONS.iter().zip(&current) {
+        text.push_str(&format!(
+            "\n  {:<key_width$}  {:<value_width$}  {:<7}  {} ({})",
+            option.key, current, option.scope.to_string(), option.description, option.values,
+        ));
+    }
+    text.push_str("\nChange one with 'options set <key> <value>'. Session options last until you quit, game options are saved with the game.");
+    text
+}
+
+fn autosave_text(rounds: u32) -> String {
+    match rounds {
+        0 => String::from("off"),
+        1 => String::from("every round"),
+        rounds => format!("every {} rounds", rounds),
+    }
+}
+
+fn history_text(turns: u32) -> String {
+    match turns {
+        0 => String::from("off"),
+        turns => format!("last {} turn(s)", turns),
+    }
+}
+
+fn slow_round_text(millis: u32) -> String {
+    match millis {
+        0 => String::from("off"),
+        millis => format!("{} ms", millis),
+    }
+}
+
+fn on_off(on: bool) -> &'static str {
+    if on { "on" } else { "off" }
+}
+
+fn parse_on_off(value: &str) -> Result<bool, String> {
+    match value {
+        "on" => Ok(true),
+        "off" => Ok(false),
+        _ => Err(String::from("expected 'on' or 'off'")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::fixture_game;
+
+    #[test]
+    fn test_each_scope_parses_into_its_own_change() {
+        assert_eq!(find("style").unwrap().parse("ascii"), Ok(OptionChange::Session(SessionOption::Style(RenderStyle::AsciiOnly))));
+        assert_eq!(find("autosave").unwrap().parse("3"), Ok(OptionChange::Game(GameOption::AutosaveRounds(3))));
+        assert_eq!(find("autosave").unwrap().parse("off"), Ok(OptionChange::Game(GameOption::AutosaveRounds(0))));
+        assert!(find("speed").unwrap().parse("quick").unwrap_err().contains("fixed when the game is created"));
+    }
+
+    #[test]
+    fn test_invalid_values_are_rejected() {
+        assert!(find("advisor").unwrap().parse("maybe").is_err());
+        assert!(find("autosave").unwrap().parse(&(MAX_AUTOSAVE_ROUNDS + 1).to_string()).is_err());
+        assert!(find("autosave").unwrap().parse("-1").is_err());
+    }
+
+    #[test]
+    fn test_listing_shows_every_option_with_its_current_value() {
+        let mut state = fixture_game(1);
+        state.options.autosave_rounds = 2;
+        let session = SessionOptions { confirmations: false, ..SessionOptions::default() };
+
+        let listing = format_options(&session, &state);
+
+        for key in keys() {
+            assert!(listing.contains(&format!("  {key} ")), "{listing}");
+        }
+        let line = |key: &str| listing.lines().find(|line| line.trim_start().starts_with(key)).unwrap().to_string();
+        assert!(line("confirmations").contains("off") && line("confirmations").contains("session"), "{listing}");
+        assert!(line("autosave").contains("every 2 rounds") && line("autosave").contains("game"), "{listing}");
+        assert!(line("speed").contains("normal") && line("speed").contains("fixed"), "{listing}");
+    }
+
+    #[test]
+    fn test_autosave_is_due_every_few_rounds() {
+        let options = GameOptions { autosave_rounds: 2, ..GameOptions::default() };
+        let due: Vec<_> = (1..=6).filter(|turn| options.autosave_due(*turn)).collect();
+        assert_eq!(due, [3, 5]);
+        assert!(!GameOptions::default().autosave_due(2));
+    }
+}
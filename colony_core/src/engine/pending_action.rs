@@ -0,0 +1,132 @@
+use crate::engine::configs::ship_config::ShipId;
+use crate::engine::planet::PlanetId;
+use crate::engine::resources::Resources;
+use crate::engine::ship::{FleetId, MovableUnit, ShipInstanceId};
+use crate::engine::structure::StructureId;
+
+/// Rounds a finished structure build waits for a lost prerequisite before it is refunded
+pub const CONSTRUCTION_HOLD_TURNS: u32 = 3;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum ActionType {
+    BuildStructure(StructureId),
+    UpgradeStructure(StructureId),
+    /// Ship type and the instance id reserved for the ship when it was queued
+    BuildShip(ShipId, ShipInstanceId),
+    /// Target ship type and the instance id of the ship being refitted
+    RetrofitShip(ShipId, ShipInstanceId),
+    MoveFleet(FleetId, PlanetId),
+    /// A ship outside any fleet moving on its own
+    MoveShip(ShipInstanceId, PlanetId),
+    BombardPlanet(FleetId, PlanetId),
+}
+
+/// When an action is carried out among the ones completing in the same round. Structures go
+/// first, so ships see the planet they finished on as it now is; moves go last, so a ship
+/// built at a rally point joins its fleet before the fleet sets off or arrives elsewhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CompletionStage {
+    Structures,
+    Ships,
+    Movement,
+}
+
+impl ActionType {
+    pub fn completion_stage(&self) -> CompletionStage {
+        match self {
+            ActionType::BuildStructure(_) | ActionType::UpgradeStructure(_) => CompletionStage::Structures,
+            ActionType::BuildShip(..) | ActionType::RetrofitShip(..) => CompletionStage::Ships,
+            ActionType::MoveFleet(..) | ActionType::MoveShip(..) | ActionType::BombardPlanet(..) => CompletionStage::Movement,
+        }
+    }
+
+    /// The unit a move carries and where to, for fleet and ship moves.
+    pub fn movement(&self) -> Option<(MovableUnit, &PlanetId)> {
+        match self {
+            ActionType::MoveFleet(fleet_id, destination) => Some((MovableUnit::Fleet(fleet_id.clone()), destination)),
+            ActionType::MoveShip(ship_id, destination) => Some((MovableUnit::Ship(ship_id.clone()), destination)),
+            _ => None,
+        }
+    }
+}
+
+/// Represents an action pending completion (waiting for cooldown to reach 0)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PendingAction {
+    /// Type of action being performed
+    pub action_type: ActionType,
+
+    /// Planet where this action is happening
+    pub planet_id: PlanetId,
+
+    /// Number of turns remaining until completion (0 = completes this turn)
+    pub cooldown_remaining: u32,
+
+    /// Resources reserved for this action (for refund on cancel)
+    pub reserved_resources: Resources,
+
+    /// For upgrades, whether the next level is queued when this one completes
+    #[serde(default)]
+    pub auto_repeat: bool,
+
+    /// For bombardments, the structure damage passing through the shield goes to first
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_structure: Option<StructureId>,
+
+    /// For ship builds, the planet the finished ship is carried to instead of the shipyard's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deliver_to: Option<PlanetId>,
+}
+
+/// A structure build that finished while one of its prerequisites was missing. It goes up as
+/// soon as the prerequisite is back, or is refunded once `turns_left` runs out.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct HeldAction {
+    pub action: PendingAction,
+    pub awaiting: StructureId,
+    pub required_level: u32,
+    /// Rounds left to restore the prerequisite
+    pub turns_left: u32,
+}
+
+impl PendingAction {
+    /// Creates a new pending action
+    pub fn new(
+        action_type: ActionType,
+        planet_id: PlanetId,
+        cooldown: u32,
+        cost: Resources,
+    ) -> Self {
+        Self {
+            action_type,
+            planet_id,
+            cooldown_remaining: cooldown,
+            reserved_resources: cost,
+            auto_repeat: false,
+            target_structure: None,
+            deliver_to: None,
+        }
+    }
+
+    /// Id of what the action works on: the structure, the reserved, refitted or moving ship
+    /// instance, or the fleet.
+    pub fn target_id(&self) -> &str {
+        match &self.action_type {
+            ActionType::BuildStructure(structure_id) | ActionType::UpgradeStructure(structure_id) => structure_id,
+            ActionType::BuildShip(_, ship_instance_id)
+            | ActionType::RetrofitShip(_, ship_instance_id)
+            | ActionType::MoveShip(ship_instance_id, _) => ship_instance_id,
+            ActionType::MoveFleet(fleet_id, _) | ActionType::BombardPlanet(fleet_id, _) => fleet_id,
+        }
+    }
+
+    /// Decrements the cooldown by 1 turn
+    pub fn tick(&mut self) {
+        self.cooldown_remaining = self.cooldown_remaining.saturating_sub(1);
+    }
+
+    /// Checks if the action is complete (cooldown reached 0)
+    pub fn is_complete(&self) -> bool {
+        self.cooldown_remaining == 0
+    }
+}
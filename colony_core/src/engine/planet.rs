@@ -0,0 +1,2116 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::engine::pending_action::{ActionType, PendingAction};
+use crate::engine::player::PlayerId;
+use crate::engine::resources::{self, ResourceName, Resources};
+use crate::engine::ship::{FleetId, ShipInstanceId};
+use crate::engine::configs::structure_config::{StructureConfig, fmt_structure};
+use crate::engine::game_rules::{MAX_UNREST, UnrestRules};
+use crate::engine::structure::{ Power, StructureDebugView, StructureId, Structure, StructureState, StructureError };
+
+pub type PlanetId = String;
+
+/// Energy drained from a planet to overcharge its shield.
+pub const OVERCHARGE_ENERGY_COST: u32 = 50;
+
+/// Share of the maximum shield HP an overcharge adds above the current shield.
+pub const OVERCHARGE_PERCENT: u32 = 50;
+
+pub const JUMP_GATE: &str = "jump_gate";
+
+/// Hit last by bombardment that passes through the shield
+pub const PLANETARY_CAPITAL: &str = "planetary_capital";
+
+/// Travel time in turns between two planets with linked jump gates.
+pub const GATE_LINK_DISTANCE: u8 = 1;
+
+#[derive(Debug)]
+pub struct BuildInfo {
+    pub cost: Resources,
+    pub turns: u32,
+}
+
+pub struct BuildableStructureInfo {
+    pub id: StructureId,
+    pub name: String,
+    pub cost: Resources,
+    pub can_afford: bool,
+}
+
+pub struct BuildableStructures {
+    pub can_build_now: Vec<BuildableStructureInfo>,
+    pub locked: Vec<(StructureId, String, String)>, // (id, name, reason)
+}
+
+/// Damage bombardment did to one structure after passing through the shield.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct StructureHit {
+    pub structure_id: StructureId,
+    pub damage: u32,
+    /// The hit took its last hitpoints and the structure was torn down
+    pub destroyed: bool,
+}
+
+/// A resource projected to reach its storage capacity while an action is pending.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StorageWarning {
+    #[serde(deserialize_with = "resources::deserialize_resource_name")]
+    pub resource: ResourceName,
+    pub turns_until_full: u32,
+}
+
+impl fmt::Display for StorageWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.turns_until_full == 0 {
+            write!(f, "{} storage is already full", self.resource)
+        } else {
+            write!(f, "{} storage will be full in {} turn(s)", self.resource, self.turns_until_full)
+        }
+    }
+}
+
+/// A resource whose output for a full turn will no longer fit in storage once a queued
+/// production structure completes.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StorageAdvice {
+    #[serde(deserialize_with = "resources::deserialize_resource_name")]
+    pub resource: ResourceName,
+    /// Output per turn once the structure is done
+    pub production: u32,
+    /// Storage left at that point
+    pub headroom: u32,
+    /// Name of a structure that adds storage for the resource, if any does
+    pub storage_structure: Option<String>,
+}
+
+impl fmt::Display for StorageAdvice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f, "{} output of {} per turn will not fit in the {} storage left once this completes",
+            self.resource, self.production, self.headroom
+        )?;
+        match &self.storage_structure {
+            Some(name) => write!(f, "; build a {} for more {} storage", name, self.resource),
+            None => write!(f, "; no structure adds {} storage", self.resource),
+        }
+    }
+}
+
+/// What a neutral planet holds from an earlier civilisation, claimed by whoever colonizes it.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Ruin {
+    /// A level 1 structure left standing, kept by the colony
+    Structure(StructureId),
+    /// Resources credited to the colony on top of its filled storage
+    Cache(Resources),
+}
+
+impl Ruin {
+    /// Short description for players, using the structure's display name when it is known.
+    pub fn describe(&self, structure_config: &StructureConfig) -> String {
+        match self {
+            Ruin::Structure(structure_id) => {
+                let name = structure_config.get(structure_id)
+                    .map_or(structure_id.clone(), |definition| definition.name.clone());
+                format!("abandoned {} (Lv1)", name)
+            }
+            Ruin::Cache(resources) => format!("resource cache ({})", resources),
+        }
+    }
+}
+
+/// A one-way link to a neighboring planet. Built only through `Connection::new`, so its
+/// distance is always in range.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Connection {
+    to: PlanetId,
+    distance: u8, // Distance in turns
+    #[serde(default)]
+    kind: ConnectionKind,
+}
+
+#[derive(Debug, Error)]
+pub enum ConnectionError {
+    #[error("Connection {from} -> {to} takes {distance} turn(s), but connections take 1 to {max}")]
+    InvalidDistance { from: PlanetId, to: PlanetId, distance: u8, max: u8 },
+}
+
+impl Connection {
+    /// A connection from `from` to `to` taking `distance` turns. Movement and supply assume
+    /// every hop takes at least a turn, and the map caps how long one may take, so anything
+    /// outside 1..=`max_distance` is refused.
+    pub fn new(
+        from: &PlanetId,
+        to: &PlanetId,
+        distance: u8,
+        kind: ConnectionKind,
+        max_distance: u8,
+    ) -> Result<Self, ConnectionError> {
+        if !(1..=max_distance).contains(&distance) {
+            return Err(ConnectionError::InvalidDistance { from: from.clone(), to: to.clone(), distance, max: max_distance });
+        }
+        Ok(Connection { to: to.clone(), distance, kind })
+    }
+
+    pub fn to(&self) -> &PlanetId {
+        &self.to
+    }
+
+    pub fn distance(&self) -> u8 {
+        self.distance
+    }
+
+    pub fn kind(&self) -> ConnectionKind {
+        self.kind
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ConnectionKind {
+    /// Part of the generated map
+    #[default]
+    Lane,
+    /// Opened between two planets by linking their jump gates
+    Gate,
+}
+
+#[derive(Debug, Error)]
+pub enum PlanetError {
+    #[error("Structure {structure} was not found on planet {planet}")]
+    StructureNotFound {
+        structure: StructureId,
+        planet: PlanetId
+    },
+
+    #[error("Structure {structure} on planet {planet} is already upgrading")]
+    AlreadyUpgrading {
+        structure: StructureId,
+        planet: PlanetId
+    },
+
+    #[error("Structure {structure} on planet {planet} is still under construction")]
+    UnderConstruction {
+        structure: StructureId,
+        planet: PlanetId
+    },
+
+    #[error("Structure {structure} on planet {planet} has reached maximum level")]
+    MaxLevelReached {
+        structure: StructureId,
+        planet: PlanetId
+    },
+
+    #[error("Planet {name} has not enough resources. Resources needed: {cost}")]
+    NotEnoughResources {
+        name: String,
+        cost: Resources
+    },
+
+    #[error("Structure {structure} already exists on planet {planet}")]
+    StructureAlreadyExists {
+        structure: StructureId,
+        planet: PlanetId
+    },
+
+    #[error("Structure definition '{structure}' not found in configuration")]
+    StructureDefinitionNotFound {
+        structure: StructureId
+    },
+
+    #[error("Prerequisites not met for {structure_name}: requires {prerequisite_name} at level {required_level} (current: {current_level})")]
+    PrerequisitesNotMet {
+        structure: StructureId,
+        prerequisite: StructureId,
+        required_level: u32,
+        current_level: u16,
+        /// The structures as players read them, see `fmt_structure`
+        structure_name: String,
+        prerequisite_name: String,
+    },
+
+    #[error("{structure} is unsupported: requires {prerequisite} at level {required_level}")]
+    StructureUnsupported {
+        structure: StructureId,
+        prerequisite: StructureId,
+        required_level: u32,
+    },
+
+    #[error(transparent)]
+    StructureError(#[from] StructureError),
+}
+
+/// How a planet's workforce splits its output between the resources, in percent adding up
+/// to 100. Output above a resource's share is shifted to the resources below theirs, up to
+/// a portion of the total set by the rules, and arrives at half its amount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Allocation {
+    pub minerals: u32,
+    pub gas: u32,
+    pub energy: u32,
+}
+
+/// Shifted units lost for every unit that arrives.
+pub const ALLOCATION_EXCHANGE_RATE: u32 = 2;
+
+impl Allocation {
+    pub fn shares(&self) -> [u32; 3] {
+        [self.minerals, self.gas, self.energy]
+    }
+
+    /// `output` as the workforce splits it, shifting at most `max_shift_percent` of its total.
+    /// What is taken and what arrives is rounded down overall and handed out by largest
+    /// remainder, so the result only depends on the amounts and shares.
+    pub fn apply(&self, output: &Resources, max_shift_percent: u32) -> Resources {
+        let amounts = output.amounts().map(u64::from);
+        let total: u64 = amounts.iter().sum();
+        let targets = self.shares().map(|share| total * u64::from(share) / 100);
+        let surplus: [u64; 3] = std::array::from_fn(|index| amounts[index].saturating_sub(targets[index]));
+        let deficit: [u64; 3] = std::array::from_fn(|index| targets[index].saturating_sub(amounts[index]));
+
+        let shifted = surplus.iter().sum::<u64>()
+            .min(total * u64::from(max_shift_percent.min(100)) / 100)
+            .min(deficit.iter().sum::<u64>() * u64::from(ALLOCATION_EXCHANGE_RATE));
+        let taken = split(shifted, surplus);
+        let arrived = split(shifted / u64::from(ALLOCATION_EXCHANGE_RATE), deficit);
+
+        let [minerals, gas, energy] = std::array::from_fn(|index| {
+            u32::try_from(amounts[index] - taken[index] + arrived[index]).unwrap_or(u32::MAX)
+        });
+        Resources { minerals, gas, energy }
+    }
+}
+
+impl fmt::Display for Allocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}% minerals, {}% gas, {}% energy", self.minerals, self.gas, self.energy)
+    }
+}
+
+/// Splits `amount` in proportion to `weights`. Each part is rounded down and the units left
+/// go to the largest remainders, earlier resources first on ties.
+fn split(amount: u64, weights: [u64; 3]) -> [u64; 3] {
+    let total: u64 = weights.iter().sum();
+    if total == 0 {
+        return [0; 3];
+    }
+    let mut parts = weights.map(|weight| amount * weight / total);
+    let mut by_remainder = [0, 1, 2];
+    by_remainder.sort_by_key(|&index| std::cmp::Reverse(amount * weights[index] % total));
+    let left = amount - parts.iter().sum::<u64>();
+    for &index in by_remainder.iter().take(left as usize) {
+        parts[index] += 1;
+    }
+    parts
+}
+
+/// Where ships finished on a planet are sent.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum RallyPoint {
+    /// Join an existing fleet orbiting the planet
+    Fleet(FleetId),
+    /// Form a fleet with this name on the next completion, then keep joining it
+    NewFleet(String),
+}
+
+impl fmt::Display for RallyPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RallyPoint::Fleet(fleet_id) => write!(f, "fleet {}", fleet_id),
+            RallyPoint::NewFleet(name) => write!(f, "new fleet '{}'", name),
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct Planet {
+    pub id: PlanetId,
+    pub name: String,
+    connections: Vec<Connection>,
+    owner: Option<PlayerId>,
+    structures: HashMap<StructureId, Structure>,
+    production_rate: Resources,
+    pub available_resources: Resources,
+    pub storage_capacity: Resources,
+    /// Current shield HP from defense_shield structure
+    shield_hp: u32,
+    /// Turns since last attack (shield regenerates when this reaches the configured threshold)
+    shield_regen_timer: u32,
+    /// Emergency HP layered above the shield until the end of the turn; absorbs damage first
+    #[serde(default)]
+    shield_overcharge: u32,
+    /// Turn the planet was colonized; unknown for planets from saves older than format version 2
+    colonized_turn: Option<u32>,
+    /// Fleet that newly built ships join; None leaves them standalone
+    rally_point: Option<RallyPoint>,
+    /// Owner's ships stationed planet-side, outside any fleet
+    #[serde(default)]
+    garrison: Vec<ShipInstanceId>,
+    /// Left by map generation on some neutral planets; taken by the first colonization
+    #[serde(default)]
+    ruin: Option<Ruin>,
+    /// Counts towards a domination victory; marked by map generation
+    #[serde(default)]
+    key_planet: bool,
+    /// Full turns left before the planet can be colonized or invaded again after changing hands
+    #[serde(default)]
+    consolidation_turns: u32,
+    /// Discontent from bombardment and blockade, up to `MAX_UNREST`; cuts production when high
+    #[serde(default)]
+    unrest: u32,
+    /// The workers walked out: the next production yields nothing
+    #[serde(default)]
+    on_strike: bool,
+    /// Split of the output between the resources; None keeps the structures' own
+    #[serde(default)]
+    allocation: Option<Allocation>,
+}
+
+impl Planet {
+    pub fn new(
+        id: PlanetId, name: String, owner: Option<PlayerId>, connections: Vec<Connection>
+    ) -> Self {
+        Planet {
+            id,
+            name,
+            owner,
+            connections,
+            structures: HashMap::new(), // No structure is build on created planet
+            production_rate: Resources::default(),
+            available_resources: Resources::default(),
+            storage_capacity: Resources::default(),
+            shield_hp: 0,
+            shield_regen_timer: 0,
+            shield_overcharge: 0,
+            colonized_turn: None,
+            rally_point: None,
+            garrison: Vec::new(),
+            ruin: None,
+            key_planet: false,
+            consolidation_turns: 0,
+            unrest: 0,
+            on_strike: false,
+            allocation: None,
+        }
+    }
+
+    pub fn get_owner(&self) -> &Option<PlayerId> {
+        &self.owner
+    }
+
+    pub fn get_colonized_turn(&self) -> Option<u32> {
+        self.colonized_turn
+    }
+
+    pub fn get_rally_point(&self) -> Option<&RallyPoint> {
+        self.rally_point.as_ref()
+    }
+
+    pub fn set_rally_point(&mut self, rally_point: Option<RallyPoint>) {
+        self.rally_point = rally_point;
+    }
+
+    pub fn get_ruin(&self) -> Option<&Ruin> {
+        self.ruin.as_ref()
+    }
+
+    pub fn set_ruin(&mut self, ruin: Option<Ruin>) {
+        self.ruin = ruin;
+    }
+
+    pub fn is_key_planet(&self) -> bool {
+        self.key_planet
+    }
+
+    pub fn set_key_planet(&mut self, key_planet: bool) {
+        self.key_planet = key_planet;
+    }
+
+    pub fn get_consolidation_turns(&self) -> u32 {
+        self.consolidation_turns
+    }
+
+    pub fn set_consolidation_turns(&mut self, turns: u32) {
+        self.consolidation_turns = turns;
+    }
+
+    /// Counts down the consolidation period; called at the end of every turn.
+    pub fn tick_consolidation(&mut self) {
+        self.consolidation_turns = self.consolidation_turns.saturating_sub(1);
+    }
+
+    pub fn get_unrest(&self) -> u32 {
+        self.unrest
+    }
+
+    pub fn set_unrest(&mut self, unrest: u32) {
+        self.unrest = unrest.min(MAX_UNREST);
+    }
+
+    pub fn is_on_strike(&self) -> bool {
+        self.on_strike
+    }
+
+    /// Unrest the planet's morale structures remove each round.
+    pub fn unrest_relief(&self) -> u32 {
+        self.structures.values()
+            .map(Structure::unrest_relief)
+            .fold(0, u32::saturating_add)
+    }
+
+    /// Raises unrest for a round of bombardment or blockade, or lets it decay after a quiet
+    /// one, less what morale structures relieve. Returns whether this sent the workers on
+    /// strike, which happens when unrest reaches the strike threshold from below.
+    pub fn update_unrest(&mut self, troubled: bool, rules: &UnrestRules) -> bool {
+        let before = self.unrest;
+        let changed = if troubled {
+            before.saturating_add(rules.rise_per_round)
+        } else {
+            before.saturating_sub(rules.decay_per_round)
+        };
+        self.unrest = changed.saturating_sub(self.unrest_relief()).min(MAX_UNREST);
+
+        let strike = before < rules.strike_threshold && self.unrest >= rules.strike_threshold;
+        self.on_strike |= strike;
+        strike
+    }
+
+    pub fn get_allocation(&self) -> Option<Allocation> {
+        self.allocation
+    }
+
+    pub fn set_allocation(&mut self, allocation: Option<Allocation>) {
+        self.allocation = allocation;
+    }
+
+    /// Base production as the allocation splits it.
+    pub fn allocated_production(&self, max_shift_percent: u32) -> Resources {
+        match &self.allocation {
+            Some(allocation) => allocation.apply(&self.production_rate, max_shift_percent),
+            None => self.production_rate.clone(),
+        }
+    }
+
+    pub fn get_garrison(&self) -> &[ShipInstanceId] {
+        &self.garrison
+    }
+
+    pub fn is_garrisoned(&self, ship_id: &ShipInstanceId) -> bool {
+        self.garrison.contains(ship_id)
+    }
+
+    pub fn garrison_ship(&mut self, ship_id: ShipInstanceId) {
+        if !self.is_garrisoned(&ship_id) {
+            self.garrison.push(ship_id);
+        }
+    }
+
+    /// Returns whether the ship was in the garrison.
+    pub fn ungarrison_ship(&mut self, ship_id: &ShipInstanceId) -> bool {
+        let before = self.garrison.len();
+        self.garrison.retain(|id| id != ship_id);
+        self.garrison.len() != before
+    }
+
+    pub fn set_owner(&mut self, new_owner: PlayerId) {
+        self.owner = Some(new_owner);
+    }
+
+    pub fn get_connections(&self) -> &Vec<Connection> {
+        &self.connections
+    }
+
+    /// Replaces the connection to the same planet as `connection`, adding it if there is none yet.
+    pub fn set_connection(&mut self, connection: Connection) {
+        match self.connections.iter_mut().find(|existing| existing.to == connection.to) {
+            Some(existing) => *existing = connection,
+            None => self.connections.push(connection),
+        }
+    }
+
+    /// Returns whether there was a connection to `to`.
+    pub fn remove_connection(&mut self, to: &PlanetId) -> bool {
+        let before = self.connections.len();
+        self.connections.retain(|connection| &connection.to != to);
+        self.connections.len() != before
+    }
+
+    /// Whether a working jump gate stands here: built, and neither damaged nor unsupported.
+    pub fn has_operational_gate(&self) -> bool {
+        self.structures.get(JUMP_GATE)
+            .is_some_and(|gate| gate.level > 0 && matches!(gate.state, StructureState::Operational))
+    }
+
+    /// The planet this planet's jump gate is linked to, if any.
+    pub fn gate_partner(&self) -> Option<&PlanetId> {
+        self.connections.iter()
+            .find(|connection| connection.kind == ConnectionKind::Gate)
+            .map(|connection| &connection.to)
+    }
+
+    /// Whether `structure_id` is built on this planet; a structure under construction is not.
+    pub fn has_built(&self, structure_id: &StructureId) -> bool {
+        self.structures.get(structure_id).is_some_and(|structure| structure.level > 0)
+    }
+
+    /// Returns the level of a structure on this planet, or 0 if not built.
+    pub fn get_structure_level(&self, structure_id: &StructureId) -> u16 {
+        self.structures
+            .get(structure_id)
+            .map(|s| s.level)
+            .unwrap_or(0)
+    }
+
+    /// Returns a reference to all structures on this planet.
+    pub fn get_structures(&self) -> &HashMap<StructureId, Structure> {
+        &self.structures
+    }
+
+    pub fn get_structure_mut(&mut self, structure_id: &StructureId) -> Option<&mut Structure> {
+        self.structures.get_mut(structure_id)
+    }
+
+    pub fn get_production_rate(&self) -> &Resources {
+        &self.production_rate
+    }
+
+    /// Total energy consumed per turn by all structures on the planet.
+    pub fn get_energy_consumption(&self) -> u32 {
+        self.structures.values()
+            .map(|structure| structure.energy_consumption())
+            .fold(0, u32::saturating_add)
+    }
+
+    /// Current shield HP, including any overcharge above the normal maximum.
+    pub fn get_shield_hp(&self) -> u32 {
+        self.shield_hp.saturating_add(self.shield_overcharge)
+    }
+
+    pub fn get_shield_overcharge(&self) -> u32 {
+        self.shield_overcharge
+    }
+
+    /// Layers `OVERCHARGE_PERCENT` of the maximum shield HP above the current shield until
+    /// the end of the turn. Returns the HP added.
+    pub fn overcharge_shield(&mut self) -> u32 {
+        self.shield_overcharge = self.get_max_shield_hp() * OVERCHARGE_PERCENT / 100;
+        self.shield_overcharge
+    }
+
+    /// Drops whatever overcharge is left; called at the end of every turn.
+    pub fn clear_shield_overcharge(&mut self) {
+        self.shield_overcharge = 0;
+    }
+
+    /// Returns max shield HP based on defense_shield structure level.
+    /// Returns 0 if no defense shield is built.
+    pub fn get_max_shield_hp(&self) -> u32 {
+        self.structures
+            .get("defense_shield")
+            .map(|shield| shield.hitpoints)
+            .unwrap_or(0)
+    }
+
+    /// Applies damage to the overcharge, then the shield, and resets the regeneration timer.
+    /// Returns the amount of damage that passed through (overflow damage).
+    pub fn take_shield_damage(&mut self, damage: u32) -> u32 {
+        self.shield_regen_timer = 0;
+
+        // Overcharge soaks up damage before the shield itself
+        let absorbed = damage.min(self.shield_overcharge);
+        self.shield_overcharge -= absorbed;
+        let damage = damage - absorbed;
+
+        if damage >= self.shield_hp {
+            let overflow = damage - self.shield_hp;
+            self.shield_hp = 0;
+            overflow
+        } else {
+            self.shield_hp -= damage;
+            0
+        }
+    }
+
+    /// Applies bombardment damage that passed through the shield to the planet's structures:
+    /// to `target` first when the planet has it, then to the structure with the fewest
+    /// hitpoints left, finishing off what it can, with the planetary capital hit last.
+    /// Structures losing their last hitpoint are removed. Returns every structure hit, in order.
+    pub fn take_structure_damage(&mut self, target: Option<&StructureId>, mut damage: u32) -> Vec<StructureHit> {
+        let mut order: Vec<(bool, bool, u32, StructureId)> = self.structures.iter()
+            .filter(|(_, structure)| structure.hitpoints > 0)
+            .map(|(id, structure)| (Some(id) != target, id == PLANETARY_CAPITAL, structure.hitpoints, id.clone()))
+            .collect();
+        order.sort();
+
+        let mut hits = Vec::new();
+        for (_, _, hitpoints, structure_id) in order {
+            if damage == 0 {
+                break;
+            }
+            let dealt = damage.min(hitpoints);
+            damage -= dealt;
+            let destroyed = dealt == hitpoints;
+            if destroyed {
+                self.remove_structure(&structure_id);
+            } else if let Some(structure) = self.structures.get_mut(&structure_id) {
+                structure.hitpoints -= dealt;
+            }
+            hits.push(StructureHit { structure_id, damage: dealt, destroyed });
+        }
+        hits
+    }
+
+    /// Turns until a damaged shield is back at full strength if it takes no further hits;
+    /// None when the shield is full or there is none. A shield knocked out entirely comes
+    /// back at the next turn.
+    pub fn turns_to_shield_regen(&self) -> Option<u32> {
+        let regen_turns = self.get_shield_regen_turns()?;
+        let max_shield = self.get_max_shield_hp();
+        if max_shield == 0 || self.shield_hp >= max_shield {
+            return None;
+        }
+        if self.shield_hp == 0 {
+            return Some(1);
+        }
+        Some(regen_turns.saturating_sub(self.shield_regen_timer).max(1))
+    }
+
+    /// Projected rounds of bombardment at `power` per round until this planet's shield is
+    /// down; None when it never breaks at that power.
+    pub fn turns_to_shield_collapse(&self, power: u32) -> Option<u32> {
+        self.project_shield_collapse(self.shield_hp, self.shield_overcharge, power)
+    }
+
+    /// Projected rounds of bombardment at `power` per round until a shield at `shield_hp`,
+    /// with `overcharge` soaking up the first hit, is down. Every hit restarts the regeneration
+    /// timer, so only a shield that regenerates within a single turn recovers between hits -
+    /// back to full strength. None when the shield never breaks at that power.
+    pub fn project_shield_collapse(&self, shield_hp: u32, overcharge: u32, power: u32) -> Option<u32> {
+        if shield_hp == 0 {
+            return Some(0);
+        }
+        if power == 0 {
+            return None;
+        }
+        let regenerates_between_hits = self.get_shield_regen_turns().is_some_and(|turns| turns <= 1);
+
+        let mut shield = shield_hp;
+        let mut overcharge = overcharge;
+        let mut rounds = 0;
+        loop {
+            rounds += 1;
+            // Whatever overcharge is left decays at the end of the turn it was raised
+            shield = shield.saturating_sub(power.saturating_sub(overcharge));
+            overcharge = 0;
+            if shield == 0 {
+                return Some(rounds);
+            }
+            if regenerates_between_hits {
+                // From the second round on every round repeats the last one
+                if rounds > 1 {
+                    return None;
+                }
+                shield = self.get_max_shield_hp();
+            }
+        }
+    }
+
+    /// Restores shield to maximum HP.
+    #[allow(dead_code)]
+    fn regenerate_shield(&mut self) {
+        self.shield_hp = self.get_max_shield_hp();
+    }
+
+    /// Returns the number of turns required for shield regeneration.
+    /// Returns None if no defense shield is built.
+    #[allow(dead_code)]
+    fn get_shield_regen_turns(&self) -> Option<u32> {
+        self.structures
+            .get("defense_shield")
+            .and_then(|shield| shield.get_shield_regen_turns())
+    }
+
+    /// Returns information about which structures can be built on this planet.
+    /// Structures are categorized as either buildable now or locked (prerequisites not met).
+    /// With `under_construction`, prerequisites still being built count as level 1.
+    pub fn get_buildable_structures(&self, structure_config: &StructureConfig, under_construction: bool) -> BuildableStructures {
+        let mut can_build_now = Vec::new();
+        let mut locked = Vec::new();
+
+        // Iterate over all structure definitions in id order
+        for (structure_id, structure_def) in structure_config.iter() {
+            // Skip structures already built
+            if self.structures.contains_key(structure_id) {
+                continue;
+            }
+
+            // Check prerequisites
+            match self.check_prerequisites(structure_id, 1, structure_config, under_construction) {
+                Ok(()) => {
+                    // Prerequisites met, get cost info
+                    let build_cost = structure_def.costs.first()
+                        .cloned()
+                        .unwrap_or_default();
+
+                    let can_afford = self.available_resources.has_enough(&build_cost);
+
+                    can_build_now.push(BuildableStructureInfo {
+                        id: structure_id.clone(),
+                        name: structure_def.name.clone(),
+                        cost: build_cost,
+                        can_afford,
+                    });
+                }
+                Err(PlanetError::PrerequisitesNotMet { prerequisite, required_level, current_level, .. }) => {
+                    let reason = format!("Requires {} Lv{} (current: Lv{})",
+                        fmt_structure(structure_config, &prerequisite), required_level, current_level);
+
+                    locked.push((
+                        structure_id.clone(),
+                        structure_def.name.clone(),
+                        reason
+                    ));
+                }
+                _ => {
+                    // Other errors, skip
+                }
+            }
+        }
+
+        BuildableStructures {
+            can_build_now,
+            locked,
+        }
+    }
+
+    /// Checks if prerequisites are met for building or upgrading a structure to a specific level.
+    /// target_level is the level we want to reach (1 for new build, >1 for upgrade). With
+    /// `under_construction`, a prerequisite still being built counts as level 1.
+    pub fn check_prerequisites(
+        &self,
+        structure_id: &StructureId,
+        target_level: u16,
+        structure_config: &StructureConfig,
+        under_construction: bool,
+    ) -> Result<(), PlanetError> {
+        // Get structure definition
+        let structure_def = structure_config.get(structure_id)
+            .ok_or(PlanetError::StructureDefinitionNotFound {
+                structure: structure_id.clone()
+            })?;
+
+        // Check each prerequisite
+        for prereq in &structure_def.prerequisites {
+            // target_level is 1-indexed, but required_levels array is 0-indexed
+            let level_idx = (target_level - 1) as usize;
+
+            // Get the required level for this target level
+            // If required_levels doesn't specify a requirement for this level, skip it
+            if let Some(&required_level) = prereq.required_levels.get(level_idx) {
+                let current_level = match self.structures.get(&prereq.structure_id) {
+                    Some(structure) if under_construction && structure.is_under_construction() => 1,
+                    _ => self.get_structure_level(&prereq.structure_id),
+                };
+
+                if (current_level as u32) < required_level {
+                    return Err(PlanetError::PrerequisitesNotMet {
+                        structure: structure_id.clone(),
+                        prerequisite: prereq.structure_id.clone(),
+                        required_level,
+                        current_level,
+                        structure_name: fmt_structure(structure_config, structure_id),
+                        prerequisite_name: fmt_structure(structure_config, &prereq.structure_id),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validates that a structure can be built and returns the cost/time info, counting
+    /// prerequisites under construction as level 1 when `under_construction` is set.
+    /// Does NOT deduct resources or add the structure - use start_construction for that.
+    pub fn validate_build_structure(
+        &self,
+        structure_id: &StructureId,
+        structure_config: &StructureConfig,
+        under_construction: bool,
+    ) -> Result<BuildInfo, PlanetError> {
+        // Check if structure already exists
+        if self.structures.contains_key(structure_id) {
+            return Err(PlanetError::StructureAlreadyExists {
+                structure: structure_id.clone(),
+                planet: self.id.clone()
+            });
+        }
+
+        // Check prerequisites for level 1 (building new structure)
+        self.check_prerequisites(structure_id, 1, structure_config, under_construction)?;
+
+        // Get structure definition from config
+        let structure_definition = structure_config.get(structure_id)
+            .ok_or(PlanetError::StructureDefinitionNotFound {
+                structure: structure_id.clone()
+            })?;
+
+        // Get build time
+        let build_time = structure_definition.upgrade_time.first().copied()
+            .expect("upgrade_time array validated during config load");
+
+        // Create temporary structure to get cost
+        let structure = Structure::new(structure_definition);
+        let build_cost = structure.cost_to_upgrade()?.clone();
+
+        // Check if planet has enough resources
+        if !self.available_resources.has_enough(&build_cost) {
+            return Err(PlanetError::NotEnoughResources {
+                name: self.name.clone(),
+                cost: build_cost.clone()
+            });
+        }
+
+        Ok(BuildInfo {
+            cost: build_cost,
+            turns: build_time,
+        })
+    }
+
+    /// Puts a structure up under construction when its build is queued. It produces, stores
+    /// and defends nothing until complete_build_structure finishes it.
+    pub fn start_construction(
+        &mut self,
+        structure_id: &StructureId,
+        structure_config: &StructureConfig
+    ) -> Result<(), PlanetError> {
+        let structure_definition = structure_config.get(structure_id)
+            .ok_or(PlanetError::StructureDefinitionNotFound {
+                structure: structure_id.clone()
+            })?;
+        self.structures.insert(structure_id.clone(), Structure::new(structure_definition));
+        Ok(())
+    }
+
+    /// Takes down a structure under construction whose build was cancelled or rejected.
+    /// Built structures are left alone. Returns whether there was one.
+    pub fn clear_construction_site(&mut self, structure_id: &StructureId) -> bool {
+        let under_construction = self.structures.get(structure_id).is_some_and(Structure::is_under_construction);
+        if under_construction {
+            self.structures.remove(structure_id);
+        }
+        under_construction
+    }
+
+    /// Completes a structure build by adding it to the planet at level 1, in place of the
+    /// structure under construction. Should be called when pending action's cooldown reaches 0.
+    pub fn complete_build_structure(
+        &mut self,
+        structure_id: StructureId,
+        structure_config: &StructureConfig
+    ) -> Result<(), PlanetError> {
+        // Get structure definition from config
+        let structure_definition = structure_config.get(&structure_id)
+            .ok_or(PlanetError::StructureDefinitionNotFound {
+                structure: structure_id.clone()
+            })?;
+
+        // Create operational structure at level 1
+        let structure = Structure::new_at_level(structure_definition, 1)?;
+
+        // Insert structure into planet's structures
+        self.structures.insert(structure_id, structure);
+
+        Ok(())
+    }
+
+    /// Validates that a structure can be upgraded and returns the cost/time info.
+    /// Does NOT deduct resources or upgrade the structure - use complete_upgrade_structure for that.
+    pub fn validate_upgrade_structure(
+        &self,
+        structure_id: &StructureId,
+        structure_config: &StructureConfig
+    ) -> Result<BuildInfo, PlanetError> {
+        // Get reference to the structure, return error if not found
+        let structure = self.structures.get(structure_id)
+            .ok_or(PlanetError::StructureNotFound {
+                structure: structure_id.clone(),
+                planet: self.id.clone()
+            })?;
+
+        if structure.is_under_construction() {
+            return Err(PlanetError::UnderConstruction {
+                structure: structure_id.clone(),
+                planet: self.id.clone()
+            });
+        }
+
+        // Check if structure is already upgrading
+        if let StructureState::Upgrading { .. } = structure.state {
+            return Err(PlanetError::AlreadyUpgrading {
+                structure: structure_id.clone(),
+                planet: self.id.clone()
+            });
+        }
+
+        // An unsupported structure must get its prerequisite back first
+        if let StructureState::Unsupported { prerequisite, required_level } = &structure.state {
+            return Err(PlanetError::StructureUnsupported {
+                structure: structure_id.clone(),
+                prerequisite: prerequisite.clone(),
+                required_level: *required_level,
+            });
+        }
+
+        // Check if structure has reached maximum level
+        if structure.is_max_level() {
+            return Err(PlanetError::MaxLevelReached {
+                structure: structure_id.clone(),
+                planet: self.id.clone()
+            });
+        }
+
+        // Check prerequisites for next level
+        let next_level = structure.level + 1;
+        self.check_prerequisites(structure_id, next_level, structure_config, false)?;
+
+        // Calculate upgrade cost
+        let cost_to_upgrade = structure.cost_to_upgrade()?.clone();
+
+        // Check if planet has enough resources for upgrade
+        if !self.available_resources.has_enough(&cost_to_upgrade) {
+            return Err(PlanetError::NotEnoughResources {
+                name: self.name.clone(),
+                cost: cost_to_upgrade.clone()
+            });
+        }
+
+        // Get upgrade time
+        let upgrade_time = structure.get_upgrade_time();
+
+        Ok(BuildInfo {
+            cost: cost_to_upgrade,
+            turns: upgrade_time,
+        })
+    }
+
+    /// Completes a structure upgrade by increasing its level.
+    /// Should be called when pending action's cooldown reaches 0.
+    pub fn complete_upgrade_structure(
+        &mut self,
+        structure_id: &StructureId
+    ) -> Result<(), PlanetError> {
+        // Get mutable reference to the structure
+        let structure = self.structures.get_mut(structure_id)
+            .ok_or(PlanetError::StructureNotFound {
+                structure: structure_id.clone(),
+                planet: self.id.clone()
+            })?;
+
+        // Complete the upgrade
+        structure.complete_upgrade();
+
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub fn process_turn(&mut self) {
+        // Calculate storage capacity and production rate
+        self.storage_capacity = Resources::default();
+        self.production_rate = Resources::default();
+
+        for structure in self.structures.values() {
+            // All structures provide storage (even while upgrading)
+            self.storage_capacity += &structure.storage;
+
+            // Only operational structures that are powered contribute to production rate
+            if matches!(structure.state, StructureState::Operational) && structure.is_powered() {
+                self.production_rate += &structure.production;
+            }
+        }
+
+        // Consume energy, add production (capped at storage), process turns
+        for structure in self.structures.values_mut() {
+            // Consume energy (only operational structures consume energy)
+            // TODO: What happens when we have no energy left?
+            self.available_resources.energy -= structure.energy_consumption();
+            if !structure.is_powered() {
+                structure.process_turn();
+                continue;
+            }
+
+            // Add production, capping each resource at storage capacity
+            self.available_resources.minerals = self.available_resources.minerals
+                .saturating_add(structure.production.minerals)
+                .min(self.storage_capacity.minerals);
+
+            self.available_resources.gas = self.available_resources.gas
+                .saturating_add(structure.production.gas)
+                .min(self.storage_capacity.gas);
+
+            self.available_resources.energy = self.available_resources.energy
+                .saturating_add(structure.production.energy)
+                .min(self.storage_capacity.energy);
+
+            // Process structure turn
+            structure.process_turn();
+        }
+
+        // Shield logic: initialize when defense_shield becomes operational, or regenerate
+        if let Some(regen_turns) = self.get_shield_regen_turns() {
+            let max_shield = self.get_max_shield_hp();
+
+            // Initialize shield if it's 0 but we have a defense_shield (just built)
+            if self.shield_hp == 0 && max_shield > 0 {
+                self.shield_hp = max_shield;
+            } else if self.shield_hp < max_shield {
+                // Regeneration logic
+                self.shield_regen_timer += 1;
+                if self.shield_regen_timer >= regen_turns {
+                    self.regenerate_shield();
+                    self.shield_regen_timer = 0;
+                }
+            }
+        }
+    }
+
+    /// Colonizes the planet by building a planetary capital and filling resources. Any ruin
+    /// is claimed: a ruined structure is kept, a cache is added on top of the filled storage.
+    /// Returns the claimed ruin.
+    pub fn colonize(&mut self, structure_config: &StructureConfig, turn: u32) -> Result<Option<Ruin>, PlanetError> {
+        let capital_id = String::from("planetary_capital");
+
+        let capital_definition = structure_config.get(&capital_id)
+            .ok_or(PlanetError::StructureDefinitionNotFound {
+                structure: capital_id.clone()
+            })?;
+
+        let capital = Structure::new_at_level(capital_definition, 1)?;
+
+        // Add the capital structure
+        self.structures.insert(capital_id, capital);
+
+        let ruin = self.ruin.take();
+        if let Some(Ruin::Structure(structure_id)) = &ruin
+            && !self.structures.contains_key(structure_id)
+            && structure_config.get(structure_id).is_some()
+        {
+            self.complete_build_structure(structure_id.clone(), structure_config)?;
+        }
+
+        // Recalculate totals from structures
+        self.recalculate_from_structures();
+
+        // Fill resources to capacity
+        self.available_resources = self.storage_capacity.clone();
+        if let Some(Ruin::Cache(cache)) = &ruin {
+            self.available_resources += cache;
+        }
+        self.colonized_turn = Some(turn);
+        // A previous owner's rally point refers to their fleets
+        self.rally_point = None;
+
+        Ok(ruin)
+    }
+
+    /// Removes a structure, as bombardment or demolition would, and recalculates the planet.
+    /// Structures that needed it are left standing but become unsupported.
+    pub fn remove_structure(&mut self, structure_id: &StructureId) -> Option<Structure> {
+        let removed = self.structures.remove(structure_id);
+        self.recalculate_from_structures();
+        removed
+    }
+
+    /// Recalculates production_rate and storage_capacity by summing all operational structures,
+    /// after re-checking which structures still have their prerequisites. Unsupported
+    /// structures keep their storage but produce nothing.
+    pub fn recalculate_from_structures(&mut self) {
+        self.update_structure_support();
+        self.production_rate = Resources::default();
+        self.storage_capacity = Resources::default();
+
+        for structure in self.structures.values() {
+            match structure.state {
+                StructureState::Operational if structure.is_powered() => {
+                    self.production_rate += &structure.production;
+                    self.storage_capacity += &structure.storage;
+                }
+                StructureState::Operational | StructureState::Unsupported { .. } => {
+                    self.storage_capacity += &structure.storage;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Powers a structure on or off by hand. A structure powered off stays off until powered on
+    /// again; one powered on may still be shut down by the next energy deficit.
+    pub fn set_structure_power(&mut self, structure_id: &StructureId, on: bool) -> Result<(), PlanetError> {
+        let structure = self.structures.get_mut(structure_id)
+            .ok_or_else(|| PlanetError::StructureNotFound { structure: structure_id.clone(), planet: self.id.clone() })?;
+        structure.power = if on { Power::On } else { Power::Off };
+        self.recalculate_from_structures();
+        Ok(())
+    }
+
+    /// Settles the planet's energy before it produces. While consumption exceeds energy
+    /// production, running structures are shut down biggest consumer first, with the defense
+    /// shield last. Structures shut down earlier then restart, smallest consumer first, as far
+    /// as production covers them. Structures powered off by hand are left alone. Returns the
+    /// names of the structures shut down and of those restarted.
+    pub fn balance_energy(&mut self) -> (Vec<String>, Vec<String>) {
+        let production = self.production_rate.energy;
+        let mut consumption = self.get_energy_consumption();
+
+        // Defense shield last, then biggest consumer first, by id for ties
+        let mut running: Vec<(bool, u32, StructureId)> = self.structures.iter()
+            .filter(|(_, structure)| structure.energy_consumption() > 0)
+            .map(|(id, structure)| (id == "defense_shield", structure.energy_consumption(), id.clone()))
+            .collect();
+        running.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)).then(a.2.cmp(&b.2)));
+
+        let mut shut_down = Vec::new();
+        for (_, draw, structure_id) in running {
+            if consumption <= production {
+                break;
+            }
+            let structure = self.structures.get_mut(&structure_id).expect("Structure was just listed");
+            structure.power = Power::Starved;
+            consumption -= draw;
+            shut_down.push(structure.name.clone());
+        }
+
+        let mut starved: Vec<(u32, StructureId)> = self.structures.iter()
+            .filter(|(_, structure)| structure.power == Power::Starved)
+            .map(|(id, structure)| (structure.rated_energy_consumption(), id.clone()))
+            .collect();
+        starved.sort();
+
+        let mut restarted = Vec::new();
+        if shut_down.is_empty() {
+            for (draw, structure_id) in starved {
+                if consumption + draw > production {
+                    break;
+                }
+                let structure = self.structures.get_mut(&structure_id).expect("Structure was just listed");
+                structure.power = Power::On;
+                consumption += structure.energy_consumption();
+                restarted.push(structure.name.clone());
+            }
+        }
+
+        if !shut_down.is_empty() || !restarted.is_empty() {
+            self.recalculate_from_structures();
+        }
+        (shut_down, restarted)
+    }
+
+    /// Flags built structures whose prerequisites are missing, below the required level or
+    /// unsupported themselves as `Unsupported`, and restores those whose prerequisites are
+    /// back. Unsupported structures only ever get added while checking, so chains of
+    /// dependents settle in a few passes.
+    fn update_structure_support(&mut self) {
+        let mut structure_ids: Vec<_> = self.structures.keys().cloned().collect();
+        structure_ids.sort();
+
+        let mut unsupported: HashMap<StructureId, (StructureId, u32)> = HashMap::new();
+        loop {
+            let mut changed = false;
+            for structure_id in &structure_ids {
+                if unsupported.contains_key(structure_id) {
+                    continue;
+                }
+                if let Some(missing) = self.missing_prerequisite(structure_id, &unsupported) {
+                    unsupported.insert(structure_id.clone(), missing);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        for (structure_id, structure) in self.structures.iter_mut() {
+            match (&structure.state, unsupported.remove(structure_id)) {
+                (StructureState::Operational | StructureState::Unsupported { .. }, Some((prerequisite, required_level))) => {
+                    structure.state = StructureState::Unsupported { prerequisite, required_level };
+                }
+                (StructureState::Unsupported { .. }, None) => structure.state = StructureState::Operational,
+                _ => {}
+            }
+        }
+    }
+
+    /// The first prerequisite a built structure lacks at its current level, counting those in
+    /// `unsupported` as absent.
+    fn missing_prerequisite(
+        &self,
+        structure_id: &StructureId,
+        unsupported: &HashMap<StructureId, (StructureId, u32)>,
+    ) -> Option<(StructureId, u32)> {
+        let structure = self.structures.get(structure_id)?;
+        let level_idx = usize::from(structure.level.checked_sub(1)?);
+
+        structure.prerequisites().iter().find_map(|prereq| {
+            let required_level = *prereq.required_levels.get(level_idx)?;
+            let current_level = if unsupported.contains_key(&prereq.structure_id) {
+                0
+            } else {
+                self.get_structure_level(&prereq.structure_id)
+            };
+            (u32::from(current_level) < required_level).then(|| (prereq.structure_id.clone(), required_level))
+        })
+    }
+
+    /// Returns resources to the planet up to its storage capacity. Returns the amount added.
+    pub fn refund(&mut self, amount: &Resources) -> Resources {
+        let space_available = self.storage_capacity.clone() - self.available_resources.clone();
+        let refunded = amount.capped_at(&space_available);
+        self.available_resources += &refunded;
+        refunded
+    }
+
+    /// Produces resources based on production_rate raised by `bonus_percent`, cut by
+    /// `penalty_percent` and then split by the allocation, shifting at most
+    /// `max_shift_percent`, capped at storage_capacity. Stock already above capacity, such as
+    /// a claimed ruin cache, is kept but does not grow. A planet on strike produces nothing,
+    /// which ends the strike.
+    pub fn produce_resources(&mut self, bonus_percent: u32, penalty_percent: u32, max_shift_percent: u32) {
+        if std::mem::take(&mut self.on_strike) {
+            return;
+        }
+        let before = self.available_resources.clone();
+        let output = self.production_rate.clone() + self.production_rate.percentage(bonus_percent);
+        let lost = output.percentage(penalty_percent);
+        let output = output - lost;
+        self.available_resources += match &self.allocation {
+            Some(allocation) => allocation.apply(&output, max_shift_percent),
+            None => output,
+        };
+        let capacity = Resources {
+            minerals: self.storage_capacity.minerals.max(before.minerals),
+            gas: self.storage_capacity.gas.max(before.gas),
+            energy: self.storage_capacity.energy.max(before.energy),
+        };
+        self.available_resources = self.available_resources.capped_at(&capacity);
+    }
+
+    /// Projects available resources after `turns` rounds of production, capped at storage capacity.
+    pub fn project_resources(&self, turns: u32) -> Resources {
+        self.project_from(&self.available_resources, turns)
+    }
+
+    fn project_from(&self, available: &Resources, turns: u32) -> Resources {
+        let produced = Resources {
+            minerals: self.production_rate.minerals.saturating_mul(turns),
+            gas: self.production_rate.gas.saturating_mul(turns),
+            energy: self.production_rate.energy.saturating_mul(turns),
+        };
+        (available.clone() + produced).capped_at(&self.storage_capacity)
+    }
+
+    /// Forecasts available resources at the end of each of the next `turns` rounds.
+    /// Builds and upgrades in `pending` that finish on this planet change production and
+    /// storage from the round they complete in, as round processing completes actions
+    /// before producing. Costs of pending actions were paid when they were queued.
+    pub fn forecast(
+        &self,
+        pending: &[PendingAction],
+        structure_config: &StructureConfig,
+        bonus_percent: u32,
+        turns: u32,
+    ) -> Vec<Resources> {
+        self.forecast_from(self.available_resources.clone(), pending, structure_config, bonus_percent, turns)
+    }
+
+    fn forecast_from(
+        &self,
+        mut available: Resources,
+        pending: &[PendingAction],
+        structure_config: &StructureConfig,
+        bonus_percent: u32,
+        turns: u32,
+    ) -> Vec<Resources> {
+        let mut levels: HashMap<&str, u16> = self.structures.iter()
+            .map(|(id, structure)| (id.as_str(), structure.level))
+            .collect();
+        let mut production = self.production_rate.clone();
+        let mut storage = self.storage_capacity.clone();
+
+        (1..=turns).map(|round| {
+            // A cooldown of 0 or 1 completes in the coming round
+            let completing = pending.iter()
+                .filter(|action| action.planet_id == self.id && action.cooldown_remaining.max(1) == round);
+            for action in completing {
+                let structure_id = match &action.action_type {
+                    ActionType::BuildStructure(id) | ActionType::UpgradeStructure(id) => id,
+                    _ => continue,
+                };
+                let Some(definition) = structure_config.get(structure_id) else {
+                    continue;
+                };
+
+                let level = levels.entry(structure_id.as_str()).or_insert(0);
+                if let Some(index) = level.checked_sub(1).map(usize::from) {
+                    production -= &definition.production[index];
+                    storage -= &definition.storage_capacity[index];
+                }
+                *level += 1;
+                let index = usize::from(*level - 1);
+                if let (Some(new_production), Some(new_storage)) =
+                    (definition.production.get(index), definition.storage_capacity.get(index))
+                {
+                    production += new_production;
+                    storage += new_storage;
+                }
+            }
+
+            available += &production;
+            available += &production.percentage(bonus_percent);
+            available = available.capped_at(&storage);
+            available.clone()
+        }).collect()
+    }
+
+    /// Checks whether the next level of `structure_id`, completing in `turns` rounds, raises
+    /// production beyond what storage can take in a single turn at that point, using the
+    /// same projection as [`Planet::forecast`] once `spent` is paid. `pending` must include the
+    /// queued action.
+    pub fn storage_advice(
+        &self,
+        structure_id: &StructureId,
+        spent: &Resources,
+        pending: &[PendingAction],
+        structure_config: &StructureConfig,
+        bonus_percent: u32,
+        turns: u32,
+    ) -> Vec<StorageAdvice> {
+        let Some(definition) = structure_config.get(structure_id) else {
+            return Vec::new();
+        };
+        let level = self.structures.get(structure_id).map_or(0, |structure| usize::from(structure.level));
+        let (Some(new_production), Some(new_storage)) =
+            (definition.production.get(level), definition.storage_capacity.get(level))
+        else {
+            return Vec::new();
+        };
+        let (old_production, old_storage) = match level.checked_sub(1) {
+            Some(index) => (definition.production[index].clone(), definition.storage_capacity[index].clone()),
+            None => (Resources::default(), Resources::default()),
+        };
+        let available = self.available_resources.clone() - spent.clone();
+        let Some(projected) = self.forecast_from(available, pending, structure_config, bonus_percent, turns.max(1)).pop() else {
+            return Vec::new();
+        };
+
+        let mut production = self.production_rate.clone() - old_production + new_production.clone();
+        production += &production.percentage(bonus_percent);
+        let storage = self.storage_capacity.clone() - old_storage + new_storage.clone();
+        let resources = [
+            ("minerals", new_production.minerals, production.minerals, storage.minerals, projected.minerals),
+            ("gas", new_production.gas, production.gas, storage.gas, projected.gas),
+            ("energy", new_production.energy, production.energy, storage.energy, projected.energy),
+        ];
+
+        resources.into_iter()
+            .filter(|&(_, produced, rate, capacity, available)| produced > 0 && rate > capacity.saturating_sub(available))
+            .map(|(resource, _, rate, capacity, available)| StorageAdvice {
+                resource,
+                production: rate,
+                headroom: capacity.saturating_sub(available),
+                storage_structure: Self::storage_structure_for(resource, structure_id, structure_config),
+            })
+            .collect()
+    }
+
+    /// A structure other than `except` whose first level adds storage for `resource`,
+    /// preferring dedicated storage structures.
+    fn storage_structure_for(
+        resource: ResourceName,
+        except: &StructureId,
+        structure_config: &StructureConfig,
+    ) -> Option<String> {
+        structure_config.iter()
+            .map(|(_, definition)| definition)
+            .filter(|definition| &definition.id != except)
+            .filter(|definition| definition.storage_capacity.first().is_some_and(|storage| match resource {
+                "minerals" => storage.minerals > 0,
+                "gas" => storage.gas > 0,
+                _ => storage.energy > 0,
+            }))
+            .min_by_key(|definition| definition.category != "storage")
+            .map(|definition| definition.name.clone())
+    }
+
+    /// Returns the resources whose production will be wasted within `turns` rounds
+    /// because storage fills up.
+    pub fn storage_warnings(&self, turns: u32) -> Vec<StorageWarning> {
+        self.storage_warnings_after(&Resources::default(), turns)
+    }
+
+    /// Same as [`Planet::storage_warnings`], once `spent` is paid.
+    pub fn storage_warnings_after(&self, spent: &Resources, turns: u32) -> Vec<StorageWarning> {
+        let available = self.available_resources.clone() - spent.clone();
+        let projected = self.project_from(&available, turns);
+        let resources = [
+            ("minerals", available.minerals, self.production_rate.minerals,
+                self.storage_capacity.minerals, projected.minerals),
+            ("gas", available.gas, self.production_rate.gas,
+                self.storage_capacity.gas, projected.gas),
+            ("energy", available.energy, self.production_rate.energy,
+                self.storage_capacity.energy, projected.energy),
+        ];
+
+        resources.into_iter()
+            .filter(|&(_, _, rate, capacity, projected)| rate > 0 && projected >= capacity)
+            .map(|(resource, available, rate, capacity, _)| StorageWarning {
+                resource,
+                turns_until_full: capacity.saturating_sub(available).div_ceil(rate),
+            })
+            .collect()
+    }
+}
+
+/// Raw state of a planet for the `inspect` debug command, including the fields the rest of the
+/// game only reaches through accessors.
+#[derive(serde::Serialize)]
+pub struct PlanetDebugView<'a> {
+    pub id: &'a PlanetId,
+    pub name: &'a str,
+    pub owner: &'a Option<PlayerId>,
+    pub connections: &'a [Connection],
+    pub available_resources: &'a Resources,
+    pub storage_capacity: &'a Resources,
+    pub production_rate: &'a Resources,
+    pub energy_consumption: u32,
+    pub shield_hp: u32,
+    pub max_shield_hp: u32,
+    pub shield_regen_timer: u32,
+    pub shield_overcharge: u32,
+    pub colonized_turn: Option<u32>,
+    pub rally_point: &'a Option<RallyPoint>,
+    pub garrison: &'a [ShipInstanceId],
+    pub ruin: &'a Option<Ruin>,
+    pub key_planet: bool,
+    pub consolidation_turns: u32,
+    pub structures: BTreeMap<&'a StructureId, StructureDebugView<'a>>,
+}
+
+impl Planet {
+    pub fn debug_view(&self) -> PlanetDebugView<'_> {
+        PlanetDebugView {
+            id: &self.id,
+            name: &self.name,
+            owner: &self.owner,
+            connections: &self.connections,
+            available_resources: &self.available_resources,
+            storage_capacity: &self.storage_capacity,
+            production_rate: &self.production_rate,
+            energy_consumption: self.get_energy_consumption(),
+            shield_hp: self.shield_hp,
+            max_shield_hp: self.get_max_shield_hp(),
+            shield_regen_timer: self.shield_regen_timer,
+            shield_overcharge: self.shield_overcharge,
+            colonized_turn: self.colonized_turn,
+            rally_point: &self.rally_point,
+            garrison: &self.garrison,
+            ruin: &self.ruin,
+            key_planet: self.key_planet,
+            consolidation_turns: self.consolidation_turns,
+            structures: self.structures.iter().map(|(id, structure)| (id, structure.debug_view())).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::fixture_structure_config;
+
+    #[test]
+    fn test_build_structure_without_prerequisites() {
+        let config = fixture_structure_config();
+        let mut planet = Planet::new(
+            "p1".to_string(),
+            "Test Planet".to_string(),
+            Some("player1".to_string()),
+            vec![]
+        );
+
+        // Give planet resources
+        planet.available_resources = Resources {
+            minerals: 1000,
+            gas: 1000,
+            energy: 1000,
+        };
+
+        // Should be able to build planetary_capital (no prerequisites)
+        let result = planet.validate_build_structure(&"planetary_capital".to_string(), &config, false);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_build_structure_prerequisites_not_met() {
+        let config = fixture_structure_config();
+        let mut planet = Planet::new(
+            "p1".to_string(),
+            "Test Planet".to_string(),
+            Some("player1".to_string()),
+            vec![]
+        );
+
+        // Give planet resources
+        planet.available_resources = Resources {
+            minerals: 1000,
+            gas: 1000,
+            energy: 1000,
+        };
+
+        // Try to build storage_complex without capital at level 2
+        let result = planet.validate_build_structure(&"storage_complex".to_string(), &config, false);
+        assert!(result.is_err());
+
+        match result {
+            Err(PlanetError::PrerequisitesNotMet { structure, prerequisite, required_level, current_level, .. }) => {
+                assert_eq!(structure, "storage_complex");
+                assert_eq!(prerequisite, "planetary_capital");
+                assert_eq!(required_level, 2);
+                assert_eq!(current_level, 0);
+            }
+            _ => panic!("Expected PrerequisitesNotMet error")
+        }
+    }
+
+    #[test]
+    fn test_build_structure_prerequisites_met() {
+        let config = fixture_structure_config();
+        let mut planet = Planet::new(
+            "p1".to_string(),
+            "Test Planet".to_string(),
+            Some("player1".to_string()),
+            vec![]
+        );
+
+        // Give planet resources
+        planet.available_resources = Resources {
+            minerals: 10000,
+            gas: 10000,
+            energy: 10000,
+        };
+
+        // Build and add capital at level 2
+        let capital_def = config.get(&"planetary_capital".to_string()).unwrap();
+        let capital = Structure::new_at_level(capital_def, 2).unwrap();
+        planet.structures.insert("planetary_capital".to_string(), capital);
+
+        // Now should be able to build storage_complex
+        let result = planet.validate_build_structure(&"storage_complex".to_string(), &config, false);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_build_structure_chained_prerequisites() {
+        let config = fixture_structure_config();
+        let mut planet = Planet::new(
+            "p1".to_string(),
+            "Test Planet".to_string(),
+            Some("player1".to_string()),
+            vec![]
+        );
+
+        // Give planet resources
+        planet.available_resources = Resources {
+            minerals: 10000,
+            gas: 10000,
+            energy: 10000,
+        };
+
+        // Try to build mining_complex without storage_complex
+        let result = planet.validate_build_structure(&"mining_complex".to_string(), &config, false);
+        assert!(result.is_err());
+
+        // Add storage_complex at level 1
+        let storage_def = config.get(&"storage_complex".to_string()).unwrap();
+        let storage = Structure::new_at_level(storage_def, 1).unwrap();
+        planet.structures.insert("storage_complex".to_string(), storage);
+
+        // But also need capital at level 2 for storage_complex
+        let capital_def = config.get(&"planetary_capital".to_string()).unwrap();
+        let capital = Structure::new_at_level(capital_def, 2).unwrap();
+        planet.structures.insert("planetary_capital".to_string(), capital);
+
+        // Now should be able to build mining_complex
+        let result = planet.validate_build_structure(&"mining_complex".to_string(), &config, false);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_upgrade_structure_prerequisites_not_met() {
+        let config = fixture_structure_config();
+        let mut planet = Planet::new(
+            "p1".to_string(),
+            "Test Planet".to_string(),
+            Some("player1".to_string()),
+            vec![]
+        );
+
+        // Give planet resources
+        planet.available_resources = Resources {
+            minerals: 10000,
+            gas: 10000,
+            energy: 10000,
+        };
+
+        // Add capital at level 2 and storage_complex at level 1
+        let capital_def = config.get(&"planetary_capital".to_string()).unwrap();
+        let capital = Structure::new_at_level(capital_def, 2).unwrap();
+        planet.structures.insert("planetary_capital".to_string(), capital);
+
+        let storage_def = config.get(&"storage_complex".to_string()).unwrap();
+        let storage = Structure::new_at_level(storage_def, 1).unwrap();
+        planet.structures.insert("storage_complex".to_string(), storage);
+
+        // Try to upgrade storage to level 2 (requires capital at level 2, which we have)
+        let result = planet.validate_upgrade_structure(&"storage_complex".to_string(), &config);
+        assert!(result.is_ok());
+
+        // Try to upgrade storage to level 3 would require capital at level 3
+        // (simulated by checking what happens when we set storage to level 2 first)
+        planet.structures.get_mut("storage_complex").unwrap().level = 2;
+        let result = planet.validate_upgrade_structure(&"storage_complex".to_string(), &config);
+        assert!(result.is_err());
+
+        match result {
+            Err(PlanetError::PrerequisitesNotMet { structure, prerequisite, required_level, current_level, .. }) => {
+                assert_eq!(structure, "storage_complex");
+                assert_eq!(prerequisite, "planetary_capital");
+                assert_eq!(required_level, 3);
+                assert_eq!(current_level, 2);
+            }
+            _ => panic!("Expected PrerequisitesNotMet error, got {:?}", result)
+        }
+    }
+
+    fn producing_planet(available: Resources) -> Planet {
+        let config = fixture_structure_config();
+        let mut planet = Planet::new("p1".to_string(), "Test Planet".to_string(), None, vec![]);
+        planet.colonize(&config, 1).unwrap();
+        // Capital Lv1 produces 10/5/10 with storage 500/250/100
+        planet.available_resources = available;
+        planet
+    }
+
+    #[test]
+    fn test_allocation_shifts_at_most_the_cap_and_half_of_it_arrives() {
+        let minerals_only = Resources { minerals: 100, gas: 0, energy: 0 };
+        let even = Allocation { minerals: 50, gas: 50, energy: 0 };
+        assert_eq!(even.apply(&minerals_only, 20), Resources { minerals: 80, gas: 10, energy: 0 });
+        assert_eq!(even.apply(&minerals_only, 100), Resources { minerals: 50, gas: 25, energy: 0 });
+        assert_eq!(even.apply(&minerals_only, 0), minerals_only);
+
+        // 5 of 25 leave minerals and energy alike, the odd unit from minerals; 2 arrive as gas
+        let capital = Resources { minerals: 10, gas: 5, energy: 10 };
+        assert_eq!(Allocation { minerals: 0, gas: 100, energy: 0 }.apply(&capital, 20), Resources { minerals: 7, gas: 7, energy: 8 });
+        assert_eq!(Allocation { minerals: 40, gas: 20, energy: 40 }.apply(&capital, 20), capital);
+
+        // Whatever the amounts, no more than the cap is shifted and the loss is half of it
+        for total in [0, 1, 7, 25, 99, 1000] {
+            for allocation in [even, Allocation { minerals: 0, gas: 0, energy: 100 }, Allocation { minerals: 33, gas: 33, energy: 34 }] {
+                let output = Resources { minerals: total, gas: total / 3, energy: total / 7 };
+                let allocated = allocation.apply(&output, 20);
+                let cap = output.total() * 20 / 100;
+                assert!(output.total() - allocated.total() <= cap - cap / 2, "{output} on {allocation}: {allocated}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_allocated_planet_produces_the_split_until_reset() {
+        let mut planet = producing_planet(Resources::default());
+        planet.set_allocation(Some(Allocation { minerals: 0, gas: 100, energy: 0 }));
+        assert_eq!(planet.allocated_production(20), Resources { minerals: 7, gas: 7, energy: 8 });
+
+        planet.produce_resources(0, 0, 20);
+        assert_eq!(planet.available_resources, Resources { minerals: 7, gas: 7, energy: 8 });
+
+        planet.set_allocation(None);
+        assert_eq!(planet.allocated_production(20), *planet.get_production_rate());
+        planet.produce_resources(0, 0, 20);
+        assert_eq!(planet.available_resources, Resources { minerals: 17, gas: 12, energy: 18 });
+    }
+
+    #[test]
+    fn test_project_resources_adds_production_per_turn() {
+        let planet = producing_planet(Resources { minerals: 100, gas: 100, energy: 0 });
+
+        assert_eq!(planet.project_resources(0), Resources { minerals: 100, gas: 100, energy: 0 });
+        assert_eq!(planet.project_resources(3), Resources { minerals: 130, gas: 115, energy: 30 });
+    }
+
+    #[test]
+    fn test_project_resources_is_capped_at_storage() {
+        let planet = producing_planet(Resources { minerals: 495, gas: 250, energy: 0 });
+
+        assert_eq!(planet.project_resources(2), Resources { minerals: 500, gas: 250, energy: 20 });
+    }
+
+    #[test]
+    fn test_storage_warnings_report_turns_until_full() {
+        let planet = producing_planet(Resources { minerals: 0, gas: 240, energy: 85 });
+
+        let warnings = planet.storage_warnings(2);
+
+        assert_eq!(warnings, vec![
+            StorageWarning { resource: "gas", turns_until_full: 2 },
+            StorageWarning { resource: "energy", turns_until_full: 2 },
+        ]);
+    }
+
+    #[test]
+    fn test_storage_warnings_for_already_capped_resource() {
+        let planet = producing_planet(Resources { minerals: 500, gas: 0, energy: 0 });
+
+        let warnings = planet.storage_warnings(1);
+
+        assert_eq!(warnings, vec![StorageWarning { resource: "minerals", turns_until_full: 0 }]);
+    }
+
+    #[test]
+    fn test_storage_advice_when_new_output_outgrows_storage() {
+        let config = fixture_structure_config();
+        let planet = producing_planet(Resources { minerals: 400, gas: 0, energy: 0 });
+        let structure_id = String::from("mining_complex");
+        let pending = [PendingAction::new(ActionType::BuildStructure(structure_id.clone()), planet.id.clone(), 2, Resources::default())];
+
+        let advice = planet.storage_advice(&structure_id, &Resources::default(), &pending, &config, 0, 2);
+
+        // 410 after the first round, 460 once the complex is done; 50 per turn from then on
+        assert_eq!(advice, vec![StorageAdvice {
+            resource: "minerals",
+            production: 50,
+            headroom: 40,
+            storage_structure: Some(String::from("Storage Complex")),
+        }]);
+        assert!(advice[0].to_string().ends_with("build a Storage Complex for more minerals storage"), "{}", advice[0]);
+    }
+
+    #[test]
+    fn test_no_storage_advice_with_room_or_without_production() {
+        let config = fixture_structure_config();
+        let planet = producing_planet(Resources { minerals: 100, gas: 0, energy: 90 });
+        let mining = String::from("mining_complex");
+        let pending = [PendingAction::new(ActionType::BuildStructure(mining.clone()), planet.id.clone(), 2, Resources::default())];
+        assert!(planet.storage_advice(&mining, &Resources::default(), &pending, &config, 0, 2).is_empty());
+
+        // A full energy store does not matter for a structure producing nothing
+        let shield = String::from("defense_shield");
+        let pending = [PendingAction::new(ActionType::BuildStructure(shield.clone()), planet.id.clone(), 1, Resources::default())];
+        assert!(planet.storage_advice(&shield, &Resources::default(), &pending, &config, 0, 1).is_empty());
+    }
+
+    #[test]
+    fn test_forecast_follows_production_and_storage() {
+        let config = fixture_structure_config();
+        let planet = producing_planet(Resources { minerals: 480, gas: 0, energy: 0 });
+
+        let forecast = planet.forecast(&[], &config, 0, 3);
+
+        assert_eq!(forecast, vec![
+            Resources { minerals: 490, gas: 5, energy: 10 },
+            Resources { minerals: 500, gas: 10, energy: 20 },
+            Resources { minerals: 500, gas: 15, energy: 30 },
+        ]);
+    }
+
+    #[test]
+    fn test_forecast_slope_changes_when_extractor_completes() {
+        let config = fixture_structure_config();
+        let planet = producing_planet(Resources { minerals: 100, gas: 0, energy: 0 });
+        let pending = [
+            // Mining complex Lv1 adds 40 minerals from the round it completes in
+            PendingAction::new(ActionType::BuildStructure(String::from("mining_complex")), planet.id.clone(), 2, Resources::default()),
+            // Actions elsewhere do not affect this planet
+            PendingAction::new(ActionType::BuildStructure(String::from("mining_complex")), String::from("p2"), 1, Resources::default()),
+        ];
+
+        let minerals: Vec<_> = planet.forecast(&pending, &config, 0, 4).iter()
+            .map(|resources| resources.minerals)
+            .collect();
+
+        assert_eq!(minerals, vec![110, 160, 210, 260]);
+    }
+
+    #[test]
+    fn test_forecast_applies_upgrade_stats_and_bonus() {
+        let config = fixture_structure_config();
+        let planet = producing_planet(Resources { minerals: 495, gas: 0, energy: 0 });
+        let pending = [PendingAction::new(
+            ActionType::UpgradeStructure(String::from("planetary_capital")), planet.id.clone(), 1, Resources::default(),
+        )];
+
+        let forecast = planet.forecast(&pending, &config, 20, 2);
+
+        // Capital Lv2 produces 15/8/15 with storage 750/375/150, raised by 20%
+        assert_eq!(forecast[0], Resources { minerals: 513, gas: 9, energy: 18 });
+        assert_eq!(forecast[1], Resources { minerals: 531, gas: 18, energy: 36 });
+    }
+
+    /// A colonized planet with a level 1 defense shield (100 HP) at full strength.
+    fn shielded_planet() -> Planet {
+        let config = fixture_structure_config();
+        let mut planet = producing_planet(Resources::default());
+        planet.complete_build_structure(String::from("power_grid"), &config).unwrap();
+        planet.complete_build_structure(String::from("defense_shield"), &config).unwrap();
+        planet.shield_hp = planet.get_max_shield_hp();
+        planet
+    }
+
+    #[test]
+    fn test_overcharge_absorbs_damage_before_shield() {
+        let mut planet = shielded_planet();
+
+        assert_eq!(planet.overcharge_shield(), 50);
+        assert_eq!(planet.get_shield_hp(), 150);
+
+        assert_eq!(planet.take_shield_damage(70), 0);
+        assert_eq!(planet.get_shield_overcharge(), 0);
+        assert_eq!(planet.get_shield_hp(), 80);
+
+        planet.overcharge_shield();
+        assert_eq!(planet.take_shield_damage(200), 70);
+        assert_eq!(planet.get_shield_hp(), 0);
+    }
+
+    #[test]
+    fn test_unused_overcharge_decays() {
+        let mut planet = shielded_planet();
+        planet.overcharge_shield();
+
+        planet.clear_shield_overcharge();
+
+        assert_eq!(planet.get_shield_hp(), planet.get_max_shield_hp());
+    }
+
+    /// Rounds of hits at `power` followed by the planet's turn, as the engine plays them,
+    /// until the shield is down; None if it still stands after ten rounds.
+    fn rounds_until_collapse(planet: &mut Planet, power: u32) -> Option<u32> {
+        for round in 1..=10 {
+            planet.take_shield_damage(power);
+            if planet.get_shield_hp() == 0 {
+                return Some(round);
+            }
+            planet.clear_shield_overcharge();
+            planet.process_turn();
+        }
+        None
+    }
+
+    /// Checks the projection for `planet` at `power` against playing the rounds out.
+    fn assert_projection(mut planet: Planet, power: u32, expected: Option<u32>) {
+        assert_eq!(planet.turns_to_shield_collapse(power), expected);
+        assert_eq!(rounds_until_collapse(&mut planet, power), expected);
+    }
+
+    /// A Lv2 capital and power grid making 35 energy, with storage, mining, shipyard and
+    /// shield drawing 25 of it.
+    fn powered_planet() -> Planet {
+        let config = fixture_structure_config();
+        let mut planet = producing_planet(Resources { minerals: 1000, gas: 1000, energy: 1000 });
+        planet.complete_upgrade_structure(&String::from("planetary_capital")).unwrap();
+        for structure_id in ["power_grid", "storage_complex", "mining_complex", "orbital_shipyard", "defense_shield"] {
+            planet.complete_build_structure(String::from(structure_id), &config).unwrap();
+        }
+        planet.recalculate_from_structures();
+        planet
+    }
+
+    #[test]
+    fn test_energy_deficit_shuts_down_biggest_consumers_and_the_shield_last() {
+        let mut planet = powered_planet();
+        assert_eq!(planet.get_energy_consumption(), 25);
+        planet.production_rate.energy = 5;
+
+        let (shut_down, restarted) = planet.balance_energy();
+
+        assert_eq!(shut_down, ["Mining Complex", "Orbital Shipyard", "Storage Complex"]);
+        assert!(restarted.is_empty());
+        assert_eq!(planet.structures["defense_shield"].power, Power::On);
+        assert_eq!(planet.get_energy_consumption(), 5);
+        assert_eq!(planet.get_production_rate().minerals, 15);
+
+        // Production is back to 35, so everything restarts, smallest consumer first
+        let (shut_down, restarted) = planet.balance_energy();
+        assert!(shut_down.is_empty());
+        assert_eq!(restarted, ["Orbital Shipyard", "Storage Complex", "Mining Complex"]);
+        assert_eq!(planet.get_energy_consumption(), 25);
+    }
+
+    #[test]
+    fn test_powered_down_structures_keep_storage_and_never_restart() {
+        let mut planet = powered_planet();
+        let storage = planet.storage_capacity.clone();
+        planet.set_structure_power(&String::from("storage_complex"), false).unwrap();
+        assert_eq!(planet.storage_capacity, storage);
+        assert_eq!(planet.get_energy_consumption(), 20);
+
+        // Without the grid only the 15 energy of the capital is left
+        planet.set_structure_power(&String::from("power_grid"), false).unwrap();
+        let (shut_down, _) = planet.balance_energy();
+        assert_eq!(shut_down, ["Mining Complex"]);
+
+        planet.set_structure_power(&String::from("power_grid"), true).unwrap();
+        let (_, restarted) = planet.balance_energy();
+        assert_eq!(restarted, ["Mining Complex"]);
+        assert_eq!(planet.structures["storage_complex"].power, Power::Off);
+        assert_eq!(planet.balance_energy(), (vec![], vec![]));
+    }
+
+    /// The fixture shield at 90 HP with its regeneration timer running, optionally overcharged.
+    fn damaged_shield(overcharged: bool) -> Planet {
+        let mut planet = shielded_planet();
+        planet.available_resources.energy = 1000;
+        planet.take_shield_damage(10);
+        planet.process_turn();
+        if overcharged {
+            planet.overcharge_shield();
+        }
+        planet
+    }
+
+    /// A level 1 shield at full strength that regenerates after a single turn without hits.
+    fn fast_regen_shield(overcharged: bool) -> Planet {
+        let regen_fixture = crate::test_support::STRUCTURES_FIXTURE
+            .replace("\"shield_regen_turns\": 2", "\"shield_regen_turns\": 1");
+        let config = StructureConfig::load_from_string(&regen_fixture).unwrap();
+        let mut planet = producing_planet(Resources { minerals: 0, gas: 0, energy: 1000 });
+        planet.complete_build_structure(String::from("power_grid"), &config).unwrap();
+        planet.complete_build_structure(String::from("defense_shield"), &config).unwrap();
+        planet.shield_hp = planet.get_max_shield_hp();
+        if overcharged {
+            planet.overcharge_shield();
+        }
+        planet
+    }
+
+    #[test]
+    fn test_shield_collapse_projection_under_slow_regen() {
+        // The fixture shield regenerates after two quiet turns, so steady hits keep it from ever doing so
+        assert_projection(damaged_shield(false), 30, Some(3));
+        assert_eq!(damaged_shield(false).turns_to_shield_collapse(0), None);
+
+        assert_projection(damaged_shield(true), 30, Some(4));
+        // Overcharge left over after the first hit is gone by the next one
+        assert_projection(damaged_shield(true), 60, Some(3));
+    }
+
+    #[test]
+    fn test_shield_collapse_projection_with_regen_between_hits() {
+        // A shield back at full strength every turn only falls to a single blow
+        assert_projection(fast_regen_shield(false), 60, None);
+        assert_projection(fast_regen_shield(false), 100, Some(1));
+
+        // The first hit is soaked by overcharge, the second finds a regenerated shield
+        assert_projection(fast_regen_shield(true), 120, Some(2));
+        assert_projection(fast_regen_shield(true), 90, None);
+    }
+
+    /// Capital Lv2 with a storage complex and a mining complex, which needs the storage complex.
+    fn mining_planet() -> Planet {
+        let config = fixture_structure_config();
+        let mut planet = Planet::new("p1".to_string(), "Test Planet".to_string(), Some("player1".to_string()), vec![]);
+        planet.colonize(&config, 1).unwrap();
+        planet.complete_upgrade_structure(&"planetary_capital".to_string()).unwrap();
+        planet.complete_build_structure("storage_complex".to_string(), &config).unwrap();
+        planet.complete_build_structure("mining_complex".to_string(), &config).unwrap();
+        planet.recalculate_from_structures();
+        planet.available_resources = Resources { minerals: 1000, gas: 1000, energy: 1000 };
+        planet
+    }
+
+    #[test]
+    fn test_structure_damage_hits_the_target_then_the_weakest_and_the_capital_last() {
+        let mut planet = mining_planet();
+        let (storage, mining) = (String::from("storage_complex"), String::from("mining_complex"));
+
+        let hits = planet.take_structure_damage(Some(&mining), 800);
+        assert_eq!(hits, vec![
+            StructureHit { structure_id: mining.clone(), damage: 700, destroyed: true },
+            StructureHit { structure_id: storage.clone(), damage: 100, destroyed: false },
+        ]);
+        assert!(!planet.get_structures().contains_key(&mining));
+        assert_eq!(planet.get_structures()[&storage].hitpoints, 500);
+
+        // A target the planet lacks changes nothing about the order
+        let hits = planet.take_structure_damage(Some(&mining), 600);
+        assert_eq!(hits[0], StructureHit { structure_id: storage.clone(), damage: 500, destroyed: true });
+        assert_eq!(hits[1].structure_id, PLANETARY_CAPITAL);
+        assert_eq!(planet.get_structures()[PLANETARY_CAPITAL].hitpoints, 1400);
+        assert_eq!(planet.storage_capacity, planet.get_structures()[PLANETARY_CAPITAL].storage);
+    }
+
+    #[test]
+    fn test_lost_prerequisite_halts_dependent_until_rebuilt() {
+        let config = fixture_structure_config();
+        let mut planet = mining_planet();
+        let mining = "mining_complex".to_string();
+        assert_eq!(planet.get_production_rate().minerals, 55);
+        assert_eq!(planet.get_energy_consumption(), 15);
+
+        planet.remove_structure(&"storage_complex".to_string());
+
+        assert!(matches!(
+            &planet.get_structures()[&mining].state,
+            StructureState::Unsupported { prerequisite, required_level: 1 } if prerequisite == "storage_complex"
+        ));
+        assert_eq!(planet.get_structure_level(&mining), 1);
+        assert_eq!(planet.get_production_rate().minerals, 15);
+        assert_eq!(planet.get_energy_consumption(), 0);
+        assert!(matches!(
+            planet.validate_upgrade_structure(&mining, &config),
+            Err(PlanetError::StructureUnsupported { .. })
+        ));
+
+        planet.complete_build_structure("storage_complex".to_string(), &config).unwrap();
+        planet.recalculate_from_structures();
+
+        assert!(matches!(planet.get_structures()[&mining].state, StructureState::Operational));
+        assert_eq!(planet.get_production_rate().minerals, 55);
+        planet.complete_upgrade_structure(&"storage_complex".to_string()).unwrap();
+        assert!(planet.validate_upgrade_structure(&mining, &config).is_ok());
+    }
+
+    #[test]
+    fn test_unsupported_structure_leaves_its_dependents_unsupported() {
+        let mut planet = mining_planet();
+
+        planet.remove_structure(&"planetary_capital".to_string());
+
+        for structure_id in ["storage_complex", "mining_complex"] {
+            assert!(
+                matches!(planet.get_structures()[structure_id].state, StructureState::Unsupported { .. }),
+                "{structure_id} should be unsupported"
+            );
+        }
+        assert_eq!(planet.get_production_rate(), &Resources::default());
+        // Storage stays, so stockpiles are not lost
+        assert!(planet.storage_capacity.minerals > 0);
+    }
+
+    #[test]
+    fn test_status_shows_why_a_structure_is_unsupported() {
+        let mut state = crate::test_support::fixture_game(1);
+        let home = crate::test_support::home_planet(&state, "alice");
+        let planet = state.map.planets.get_mut(&home).unwrap();
+        planet.complete_build_structure("orbital_shipyard".to_string(), &state.structure_config).unwrap();
+        planet.recalculate_from_structures();
+
+        let effect = crate::engine::commands::parser::parse(&format!("status planet {}", home)).unwrap()
+            .execute(&state).unwrap();
+        let crate::engine::commands::command::CommandEffect::None { message } = effect else {
+            panic!("Status must only produce a message");
+        };
+
+        assert!(message.contains("(UNSUPPORTED: needs Power Grid (power_grid) Lv1, producing nothing)"), "{message}");
+    }
+}
@@ -0,0 +1,304 @@
+// Greek-style planet name generator
+
+use std::collections::HashSet;
+#[cfg(feature = "std-io")]
+use std::path::Path;
+
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use thiserror::Error;
+
+use crate::engine::configs::{PlanetNameParts, PlanetNamesConfigError};
+use crate::engine::utils;
+
+#[derive(Debug, Error)]
+pub enum PlanetNameGeneratorError {
+    #[error("Failed to generate unique planet name: all name combinations exhausted")]
+    AllVariantsExhausted,
+
+    #[error("Planet name parts must contain at least one prefix and one suffix")]
+    EmptyNameParts,
+
+    #[error("Planet name parts produce the name '{0}' more than once")]
+    DuplicateName(String),
+
+    #[error(transparent)]
+    PlanetNamesConfigError(#[from] PlanetNamesConfigError),
+}
+
+/// Names available per prefix/suffix pair: the base name plus Roman numerals I-X
+const VARIANTS_PER_BASE_NAME: usize = 11;
+
+pub struct PlanetNameGenerator {
+    name_parts: PlanetNameParts,
+    used_names: HashSet<String>,
+    rng: StdRng,
+}
+
+impl PlanetNameGenerator {
+    /// Load name parts from configuration
+    #[cfg(feature = "std-io")]
+    pub fn new() -> Result<Self, PlanetNameGeneratorError> {
+        Self::from_parts(PlanetNameParts::load()?)
+    }
+
+    /// Load name parts from an alternative name pack file
+    #[cfg(feature = "std-io")]
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, PlanetNameGeneratorError> {
+        Self::from_parts(PlanetNameParts::load_from_path(path)?)
+    }
+
+    pub fn from_lists(prefixes: Vec<String>, suffixes: Vec<String>) -> Result<Self, PlanetNameGeneratorError> {
+        Self::from_parts(PlanetNameParts { prefixes, suffixes })
+    }
+
+    /// Creates a generator over the given name parts. Fails if either list is empty or if two
+    /// prefix/suffix combinations would normalize to the same planet id. Names are drawn
+    /// from OS entropy; without the `std-io` feature call [`Self::seeded`] before generating.
+    pub fn from_parts(name_parts: PlanetNameParts) -> Result<Self, PlanetNameGeneratorError> {
+        if name_parts.prefixes.is_empty() || name_parts.suffixes.is_empty() {
+            return Err(PlanetNameGeneratorError::EmptyNameParts);
+        }
+
+        let mut seen_ids = HashSet::new();
+        for prefix in &name_parts.prefixes {
+            for suffix in &name_parts.suffixes {
+                let name = format!("{} {}", prefix, suffix);
+                if !seen_ids.insert(utils::name_to_id(&name)) {
+                    return Err(PlanetNameGeneratorError::DuplicateName(name));
+                }
+            }
+        }
+
+        Ok(PlanetNameGenerator {
+            name_parts,
+            used_names: HashSet::new(),
+            rng: entropy_rng(),
+        })
+    }
+
+    /// Creates a generator over the given name parts with a fixed seed, for reproducible names.
+    pub fn with_seed(name_parts: PlanetNameParts, seed: u64) -> Self {
+        PlanetNameGenerator {
+            name_parts,
+            used_names: HashSet::new(),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Draws names from `seed` from now on, for reproducible names from validated parts.
+    pub fn seeded(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Total number of unique names this generator can produce.
+    pub fn capacity(&self) -> usize {
+        self.name_parts.prefixes.len() * self.name_parts.suffixes.len() * VARIANTS_PER_BASE_NAME
+    }
+
+    /// Number of names that can still be generated.
+    pub fn remaining(&self) -> usize {
+        self.capacity().saturating_sub(self.used_names.len())
+    }
+
+    /// Generate a unique Greek-style planet name by combining a random prefix with a random suffix.
+    /// If a collision occurs, appends Roman numerals (I-X) to ensure uniqueness.
+    /// When every variant of the chosen base name is taken, falls back to any other free name.
+    ///
+    /// Examples: "Crimson Theta", "Void Kepler II", "Azure Prime V"
+    ///
+    /// Returns error only if every combination is exhausted.
+    pub fn generate(&mut self) -> Result<String, PlanetNameGeneratorError> {
+        // Pick random prefix and suffix for base name
+        let prefix = &self.name_parts.prefixes[self.rng.random_range(0..self.name_parts.prefixes.len())];
+        let suffix = &self.name_parts.suffixes[self.rng.random_range(0..self.name_parts.suffixes.len())];
+        let base_name = format!("{} {}", prefix, suffix);
+
+        let name = self.first_free_variant(&base_name)
+            .or_else(|| {
+                self.name_parts.prefixes.iter()
+                    .flat_map(|prefix| self.name_parts.suffixes.iter().map(move |suffix| (prefix, suffix)))
+                    .find_map(|(prefix, suffix)| self.first_free_variant(&format!("{} {}", prefix, suffix)))
+            })
+            .ok_or(PlanetNameGeneratorError::AllVariantsExhausted)?;
+
+        self.used_names.insert(name.clone());
+        Ok(name)
+    }
+
+    /// Generates `count` unique names at once.
+    pub fn generate_batch(&mut self, count: usize) -> Result<Vec<String>, PlanetNameGeneratorError> {
+        (0..count).map(|_| self.generate()).collect()
+    }
+
+    /// Returns the base name if unused, otherwise the first unused Roman numeral variant (I-X).
+    fn first_free_variant(&self, base_name: &str) -> Option<String> {
+        if !self.used_names.contains(base_name) {
+            return Some(base_name.to_string());
+        }
+
+        (1..VARIANTS_PER_BASE_NAME as u8)
+            .map(|variant| format!("{} {}", base_name, Self::to_roman_numeral(variant)))
+            .find(|candidate| !self.used_names.contains(candidate))
+    }
+
+    /// Convert numbers 1-10 to Roman numerals
+    fn to_roman_numeral(n: u8) -> &'static str {
+        match n {
+            1 => "I",
+            2 => "II",
+            3 => "III",
+            4 => "IV",
+            5 => "V",
+            6 => "VI",
+            7 => "VII",
+            8 => "VIII",
+            9 => "IX",
+            10 => "X",
+            _ => panic!("to_roman_numeral only supports 1-10"),
+        }
+    }
+}
+
+#[cfg(feature = "std-io")]
+fn entropy_rng() -> StdRng {
+    StdRng::from_os_rng()
+}
+
+/// Without OS entropy generators start from a fixed seed until reseeded.
+#[cfg(not(feature = "std-io"))]
+fn entropy_rng() -> StdRng {
+    StdRng::seed_from_u64(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[test]
+    fn test_generate_unique_names() {
+        let mut generator = test_support::fixture_name_generator(7);
+
+        // Generate 10 names and verify they're all unique
+        let mut generated_names = HashSet::new();
+        for _ in 0..10 {
+            let name = generator.generate().unwrap();
+
+            // Name should not be empty
+            assert!(!name.is_empty());
+
+            // Name should be unique
+            assert!(generated_names.insert(name.clone()), "Generated duplicate name: {}", name);
+        }
+    }
+
+    #[test]
+    fn test_generated_name_format() {
+        let mut generator = test_support::fixture_name_generator(7);
+        let name = generator.generate().unwrap();
+
+        // Name should have 2-3 parts (prefix, suffix, optional roman numeral)
+        let parts: Vec<&str> = name.split(' ').collect();
+        assert!(parts.len() >= 2 && parts.len() <= 3, "Name '{}' has {} parts, expected 2-3", name, parts.len());
+
+        let prefix = parts[0];
+        let suffix = parts[1];
+
+        // Verify prefix and suffix are from the fixture name parts
+        assert!(generator.name_parts.prefixes.contains(&prefix.to_string()));
+        assert!(generator.name_parts.suffixes.contains(&suffix.to_string()));
+
+        // If there's a third part, it should be a Roman numeral
+        if parts.len() == 3 {
+            let roman = parts[2];
+            assert!(
+                ["I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX", "X"].contains(&roman),
+                "Invalid Roman numeral: {}", roman
+            );
+        }
+    }
+
+    #[test]
+    fn test_collision_handling_with_roman_numerals() {
+        let name_parts = PlanetNameParts {
+            prefixes: vec![String::from("Crimson")],
+            suffixes: vec![String::from("Theta")],
+        };
+        let mut generator = PlanetNameGenerator::with_seed(name_parts, 1);
+
+        assert_eq!(generator.generate().unwrap(), "Crimson Theta");
+        assert_eq!(generator.generate().unwrap(), "Crimson Theta I");
+        assert_eq!(generator.generate().unwrap(), "Crimson Theta II");
+    }
+
+    #[test]
+    fn test_all_variants_exhausted() {
+        let name_parts = PlanetNameParts {
+            prefixes: vec![String::from("Crimson")],
+            suffixes: vec![String::from("Theta")],
+        };
+        let mut generator = PlanetNameGenerator::with_seed(name_parts, 1);
+
+        // Base name plus ten Roman numeral variants
+        for _ in 0..11 {
+            generator.generate().unwrap();
+        }
+
+        assert!(matches!(generator.generate(), Err(PlanetNameGeneratorError::AllVariantsExhausted)));
+    }
+
+    #[test]
+    fn test_exhausted_base_name_falls_back_to_other_combinations() {
+        let name_parts = PlanetNameParts {
+            prefixes: vec![String::from("Crimson"), String::from("Void")],
+            suffixes: vec![String::from("Theta")],
+        };
+        let mut generator = PlanetNameGenerator::with_seed(name_parts, 1);
+
+        let names = generator.generate_batch(generator.capacity()).unwrap();
+
+        assert_eq!(names.iter().collect::<HashSet<_>>().len(), 22);
+        assert_eq!(generator.remaining(), 0);
+        assert!(generator.generate().is_err());
+    }
+
+    #[test]
+    fn test_from_lists_rejects_empty_parts() {
+        let result = PlanetNameGenerator::from_lists(vec![String::from("Crimson")], Vec::new());
+
+        assert!(matches!(result, Err(PlanetNameGeneratorError::EmptyNameParts)));
+    }
+
+    #[test]
+    fn test_from_lists_rejects_names_colliding_after_normalization() {
+        let result = PlanetNameGenerator::from_lists(
+            vec![String::from("Crimson"), String::from("crimson")],
+            vec![String::from("Theta")],
+        );
+
+        assert!(matches!(result, Err(PlanetNameGeneratorError::DuplicateName(name)) if name == "crimson Theta"));
+    }
+
+    #[test]
+    fn test_from_lists_generates_from_injected_parts() {
+        let mut generator = PlanetNameGenerator::from_lists(
+            vec![String::from("Crimson")],
+            vec![String::from("Theta")],
+        ).unwrap();
+
+        assert_eq!(generator.generate().unwrap(), "Crimson Theta");
+        assert_eq!(generator.capacity(), 11);
+    }
+
+    #[test]
+    fn test_same_seed_generates_same_names() {
+        let mut first = test_support::fixture_name_generator(42);
+        let mut second = test_support::fixture_name_generator(42);
+
+        for _ in 0..5 {
+            assert_eq!(first.generate().unwrap(), second.generate().unwrap());
+        }
+    }
+}
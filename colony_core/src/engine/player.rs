@@ -0,0 +1,260 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use super::appearance::PlayerAppearance;
+use super::configs::ship_config::ShipId;
+use super::fleet::{Fleet, FleetTemplate};
+use super::game_state::{EmpireEffects, FleetSighting, PlanetIntel};
+use super::planet::PlanetId;
+use super::pending_action::{HeldAction, PendingAction};
+use super::ship::{FleetId, MovableUnit, Ship, ShipInstanceId};
+
+pub type PlayerId = String;
+
+/// Running totals of a player's bombardments, each hit credited to the fleet's owner.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BombardmentStats {
+    /// Damage taken off shields and overcharges
+    pub shield_damage: u32,
+    /// Damage that passed through shields already down
+    pub overflow_damage: u32,
+    /// Shields this player's hits brought down
+    pub shields_broken: u32,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct Player {
+    pub id: PlayerId,
+    pub name: String,
+    pub appearance: PlayerAppearance,
+    /// Team number in team games; players sharing one are allies. None fights alone
+    #[serde(default)]
+    pub team: Option<u8>,
+    /// Played by a bot rather than a person
+    #[serde(default)]
+    pub ai: bool,
+    pub planets: Vec<PlanetId>,
+    pub pending_actions: Vec<PendingAction>,
+    /// Finished structure builds waiting for a lost prerequisite
+    #[serde(default)]
+    pub held_actions: Vec<HeldAction>,
+    pub ships: BTreeMap<ShipInstanceId, Ship>,
+    pub fleets: BTreeMap<FleetId, Fleet>,
+    /// Planets this player has owned or visited with a fleet at some point
+    pub explored: HashSet<PlanetId>,
+    /// Saved fleet compositions by template name
+    #[serde(default)]
+    pub fleet_templates: BTreeMap<String, FleetTemplate>,
+    /// Command shortcuts by name, expanded before parsing
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+    /// Free-text reminders in the order written, kept from the other players
+    #[serde(default)]
+    pub notes: Vec<String>,
+    /// Enemy fleets seen arriving during the last round processing
+    #[serde(default)]
+    pub recent_sightings: Vec<FleetSighting>,
+    /// Last observation of each planet this player has had in sensor range
+    #[serde(default)]
+    pub planet_intel: HashMap<PlanetId, PlanetIntel>,
+    #[serde(default)]
+    pub bombardment: BombardmentStats,
+    ship_id_counters: HashMap<ShipId, u32>,
+    /// Cached aggregate of global structure effects; None when structures changed since last computed
+    #[serde(skip)]
+    pub empire_effects: Option<EmpireEffects>,
+}
+
+impl Player {
+    pub fn new(id: PlayerId, name: String, appearance: PlayerAppearance) -> Self {
+        Self {
+            id,
+            name,
+            appearance,
+            team: None,
+            ai: false,
+            planets: Vec::new(),
+            pending_actions: Vec::new(),
+            held_actions: Vec::new(),
+            ships: BTreeMap::new(),
+            fleets: BTreeMap::new(),
+            explored: HashSet::new(),
+            fleet_templates: BTreeMap::new(),
+            aliases: BTreeMap::new(),
+            notes: Vec::new(),
+            recent_sightings: Vec::new(),
+            planet_intel: HashMap::new(),
+            bombardment: BombardmentStats::default(),
+            ship_id_counters: HashMap::new(),
+            empire_effects: None,
+        }
+    }
+
+    /// Checks if the player has any pending action on the specified planet.
+    pub fn has_pending_action_on_planet(&self, planet_id: &PlanetId) -> bool {
+        self.pending_actions
+            .iter()
+            .any(|action| &action.planet_id == planet_id)
+    }
+
+    /// Finds an immutable reference to the pending action on the specified planet.
+    pub fn find_pending_action_on_planet(&self, planet_id: &PlanetId) -> Option<&PendingAction> {
+        self.pending_actions
+            .iter()
+            .find(|action| &action.planet_id == planet_id)
+    }
+
+    /// Finds a mutable reference to the pending action on the specified planet.
+    pub fn find_pending_action_on_planet_mut(
+        &mut self,
+        planet_id: &PlanetId,
+    ) -> Option<&mut PendingAction> {
+        self.pending_actions
+            .iter_mut()
+            .find(|action| &action.planet_id == planet_id)
+    }
+
+    /// Returns all pending actions on the specified planet, in the order they were queued.
+    pub fn pending_actions_on_planet<'a>(&'a self, planet_id: &'a PlanetId) -> impl Iterator<Item = &'a PendingAction> {
+        self.pending_actions
+            .iter()
+            .filter(move |action| &action.planet_id == planet_id)
+    }
+
+    /// Returns the held structure builds on the specified planet.
+    pub fn held_actions_on_planet<'a>(&'a self, planet_id: &'a PlanetId) -> impl Iterator<Item = &'a HeldAction> {
+        self.held_actions
+            .iter()
+            .filter(move |held| &held.action.planet_id == planet_id)
+    }
+
+    /// Removes and returns the pending action on the specified planet working on `target_id`, if it exists.
+    pub fn remove_pending_action(&mut self, planet_id: &PlanetId, target_id: &str) -> Option<PendingAction> {
+        self.pending_actions
+            .iter()
+            .position(|action| &action.planet_id == planet_id && action.target_id() == target_id)
+            .map(|index| self.pending_actions.remove(index))
+    }
+
+    /// The pending move of a fleet or standalone ship; each has at most one.
+    pub fn pending_move(&self, unit: &MovableUnit) -> Option<&PendingAction> {
+        self.pending_actions
+            .iter()
+            .find(|action| action.action_type.movement().is_some_and(|(moving, _)| &moving == unit))
+    }
+
+    /// Checks if the specified fleet has a pending move action.
+    pub fn has_pending_fleet_move(&self, fleet_id: &FleetId) -> bool {
+        self.pending_move(&MovableUnit::Fleet(fleet_id.clone())).is_some()
+    }
+
+    /// Checks if the specified standalone ship has a pending move action.
+    pub fn has_pending_ship_move(&self, ship_id: &ShipInstanceId) -> bool {
+        self.pending_move(&MovableUnit::Ship(ship_id.clone())).is_some()
+    }
+
+    /// Checks if the specified fleet has a pending bombardment action.
+    /// Since only one bombardment per fleet is allowed, this returns true if any BombardPlanet action exists for that fleet.
+    pub fn has_pending_fleet_bombardment(&self, fleet_id: &FleetId) -> bool {
+        use crate::engine::pending_action::ActionType;
+        self.pending_actions
+            .iter()
+            .any(|action| {
+                if let ActionType::BombardPlanet(fid, _) = &action.action_type {
+                    fid == fleet_id
+                } else {
+                    false
+                }
+            })
+    }
+
+    /// Returns the lowest unused fleet ID of the form fleet_1, fleet_2, etc.
+    pub fn next_fleet_id(&self) -> FleetId {
+        (1..)
+            .map(|n| format!("fleet_{}", n))
+            .find(|id| !self.fleets.contains_key(id))
+            .expect("Fleet ids are unbounded")
+    }
+
+    /// Generates a unique ship instance ID for the given ship type.
+    /// IDs follow the pattern: interceptor_1, interceptor_2, ravager_1, etc.
+    /// Ids are never reused, even if the ship they were reserved for is never built.
+    pub fn generate_ship_id(&mut self, ship_type: &ShipId) -> ShipInstanceId {
+        let counter = self.ship_id_counters.entry(ship_type.clone()).or_insert(0);
+        *counter += 1;
+        format!("{}_{}", ship_type, counter)
+    }
+
+    /// Creates a new ship of the given type at the specified location and adds it to the player's ships.
+    /// Returns the generated ship instance ID.
+    pub fn add_ship(&mut self, ship_type: ShipId, location: PlanetId) -> ShipInstanceId {
+        let ship_id = self.generate_ship_id(&ship_type);
+        self.insert_ship(ship_id.clone(), ship_type, location);
+        ship_id
+    }
+
+    /// Creates a ship under a previously generated instance ID.
+    pub fn insert_ship(&mut self, ship_id: ShipInstanceId, ship_type: ShipId, location: PlanetId) {
+        let ship = Ship::new(ship_id.clone(), ship_type, location);
+        self.ships.insert(ship_id, ship);
+    }
+
+    /// Finds the pending build action that reserved the given ship instance ID.
+    pub fn find_ship_under_construction(&self, ship_id: &ShipInstanceId) -> Option<&PendingAction> {
+        use crate::engine::pending_action::ActionType;
+        self.pending_actions
+            .iter()
+            .find(|action| matches!(&action.action_type, ActionType::BuildShip(_, id) if id == ship_id))
+    }
+
+    /// Finds the pending retrofit of the given ship, if it is in the shipyard.
+    pub fn find_ship_in_retrofit(&self, ship_id: &ShipInstanceId) -> Option<&PendingAction> {
+        use crate::engine::pending_action::ActionType;
+        self.pending_actions
+            .iter()
+            .find(|action| matches!(&action.action_type, ActionType::RetrofitShip(_, id) if id == ship_id))
+    }
+}
+
+/// Raw state of a player for the `inspect` debug command, including the ship id counters.
+/// Maps are sorted so dumps can be compared.
+#[derive(serde::Serialize)]
+pub struct PlayerDebugView<'a> {
+    pub id: &'a PlayerId,
+    pub name: &'a str,
+    pub appearance: &'a PlayerAppearance,
+    pub team: Option<u8>,
+    pub planets: &'a [PlanetId],
+    pub pending_actions: &'a [PendingAction],
+    pub held_actions: &'a [HeldAction],
+    pub ships: BTreeMap<&'a ShipInstanceId, &'a Ship>,
+    pub fleets: BTreeMap<&'a FleetId, &'a Fleet>,
+    pub explored: Vec<&'a PlanetId>,
+    pub fleet_templates: &'a BTreeMap<String, FleetTemplate>,
+    pub aliases: &'a BTreeMap<String, String>,
+    pub ship_id_counters: BTreeMap<&'a ShipId, u32>,
+    /// Whether empire effects are cached or due to be recomputed
+    pub empire_effects_cached: bool,
+}
+
+impl Player {
+    pub fn debug_view(&self) -> PlayerDebugView<'_> {
+        let mut explored: Vec<_> = self.explored.iter().collect();
+        explored.sort();
+        PlayerDebugView {
+            id: &self.id,
+            name: &self.name,
+            appearance: &self.appearance,
+            team: self.team,
+            planets: &self.planets,
+            pending_actions: &self.pending_actions,
+            held_actions: &self.held_actions,
+            ships: self.ships.iter().collect(),
+            fleets: self.fleets.iter().collect(),
+            explored,
+            fleet_templates: &self.fleet_templates,
+            aliases: &self.aliases,
+            ship_id_counters: self.ship_id_counters.iter().map(|(ship_type, count)| (ship_type, *count)).collect(),
+            empire_effects_cached: self.empire_effects.is_some(),
+        }
+    }
+}
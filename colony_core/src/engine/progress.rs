@@ -0,0 +1,28 @@
+//! Progress reports from operations long enough that a frontend should show something
+//! while they run, such as generating a map or simulating many games.
+
+/// Receives progress from a long-running operation. `current` counts finished steps of the
+/// named stage and climbs to `total`, which is reported last.
+pub trait ProgressSink {
+    fn report(&mut self, stage: &str, current: usize, total: usize);
+}
+
+/// Ignores every report, for callers with nothing to show.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoProgress;
+
+impl ProgressSink for NoProgress {
+    fn report(&mut self, _stage: &str, _current: usize, _total: usize) {}
+}
+
+/// Keeps every report, for tests and for frontends that poll instead of drawing.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RecordedProgress {
+    pub reports: Vec<(String, usize, usize)>,
+}
+
+impl ProgressSink for RecordedProgress {
+    fn report(&mut self, stage: &str, current: usize, total: usize) {
+        self.reports.push((stage.to_string(), current, total));
+    }
+}
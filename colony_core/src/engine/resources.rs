@@ -1,6 +1,7 @@
 use core::fmt;
 use std::ops::{Add, AddAssign, Sub, SubAssign};
 
+#[allow(dead_code)]
 #[derive(serde::Deserialize)]
 pub enum ResourceType {
     Minerals(u32),
@@ -8,7 +9,25 @@ pub enum ResourceType {
     Energy(u32)
 }
 
-#[derive(serde::Deserialize, Default, Clone, Debug, PartialEq, PartialOrd)]
+/// A resource name from `RESOURCE_NAMES`. Named so serde reads it with
+/// `deserialize_resource_name` instead of borrowing from the input.
+pub type ResourceName = &'static str;
+
+/// Names of the resources as shown to players, in field order.
+pub const RESOURCE_NAMES: [ResourceName; 3] = ["minerals", "gas", "energy"];
+
+/// Reads a resource name back into its `RESOURCE_NAMES` entry.
+pub fn deserialize_resource_name<'de, D>(deserializer: D) -> Result<ResourceName, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let name = <String as serde::Deserialize>::deserialize(deserializer)?;
+    RESOURCE_NAMES.into_iter()
+        .find(|known| *known == name)
+        .ok_or_else(|| serde::de::Error::unknown_variant(&name, &RESOURCE_NAMES))
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Default, Clone, Debug, PartialEq, Eq, PartialOrd)]
 pub struct Resources {
     pub minerals: u32,
     pub gas: u32,
@@ -22,6 +41,42 @@ impl Resources {
         && self.energy >= cost.energy
     }
 
+    /// Returns `percent` percent of each field, rounded down.
+    pub fn percentage(&self, percent: u32) -> Resources {
+        let scale = |value: u32| {
+            u32::try_from(u64::from(value) * u64::from(percent) / 100).unwrap_or(u32::MAX)
+        };
+        Resources {
+            minerals: scale(self.minerals),
+            gas: scale(self.gas),
+            energy: scale(self.energy),
+        }
+    }
+
+    /// Returns the largest of the three amounts.
+    pub fn max_amount(&self) -> u32 {
+        self.minerals.max(self.gas).max(self.energy)
+    }
+
+    /// The three amounts in `RESOURCE_NAMES` order.
+    pub fn amounts(&self) -> [u32; 3] {
+        [self.minerals, self.gas, self.energy]
+    }
+
+    /// Sum of the three amounts.
+    pub fn total(&self) -> u32 {
+        self.minerals.saturating_add(self.gas).saturating_add(self.energy)
+    }
+
+    /// Returns as much of this as fits in `limit` units altogether, keeping minerals first,
+    /// then gas, then energy.
+    pub fn limited_to_total(&self, limit: u32) -> Resources {
+        let minerals = self.minerals.min(limit);
+        let gas = self.gas.min(limit - minerals);
+        let energy = self.energy.min(limit - minerals - gas);
+        Resources { minerals, gas, energy }
+    }
+
     /// Returns a new Resources with each field capped at the corresponding capacity value.
     pub fn capped_at(&self, capacity: &Resources) -> Resources {
         Resources {
@@ -0,0 +1,762 @@
+//! What a player may do, as pure checks against the game state.
+//!
+//! Command validation wraps these and turns a [`Reason`] into a [`CommandError`]; views
+//! listing a player's options ask the same questions, so both follow the same rules.
+//!
+//! [`CommandError`]: crate::engine::CommandError
+
+use thiserror::Error;
+
+use crate::engine::configs::structure_config::{StructureConfig, StructureDefinition, fmt_structure};
+use crate::engine::fleet::Fleet;
+use crate::engine::game_state::{GameState, GameStateError};
+use crate::engine::planet::{BuildInfo, Planet, PlanetError, PlanetId};
+use crate::engine::player::Player;
+use crate::engine::resources::Resources;
+use crate::engine::ship::{FleetId, MovableUnit, Ship, ShipInstanceId};
+use crate::engine::structure::StructureId;
+
+/// Why an action is not allowed.
+#[derive(Debug, Error)]
+pub enum Reason {
+    #[error("fleet has no ships")]
+    EmptyFleet,
+
+    #[error("already has a pending move")]
+    MovePending,
+
+    #[error("fleet is bombarding - cancel bombardment first")]
+    Bombarding,
+
+    #[error("fleet is already bombarding")]
+    AlreadyBombarding,
+
+    #[error("fleet has no bombardment capability")]
+    NoBombardment,
+
+    #[error("fleet requires an ark ship to colonize")]
+    NoArk,
+
+    #[error("planet {0} does not exist")]
+    UnknownPlanet(PlanetId),
+
+    #[error("already at this planet")]
+    AlreadyThere { planet_id: PlanetId },
+
+    #[error("ship is in fleet '{0}' - move the fleet or remove the ship from it")]
+    InFleet(FleetId),
+
+    #[error("ship is garrisoned - ungarrison it first")]
+    Garrisoned,
+
+    #[error("ship is being retrofitted")]
+    Retrofitting,
+
+    #[error(
+        "{to} is not directly connected to {from}; nearest route is {} ({turns} turns total). \
+         Direct moves only — move hop by hop",
+        .route.join(" → ")
+    )]
+    NoConnection { from: PlanetId, to: PlanetId, route: Vec<PlanetId>, turns: u32 },
+
+    #[error("{to} cannot be reached from {from}: no route connects them")]
+    Unreachable { from: PlanetId, to: PlanetId },
+
+    #[error("cannot {action} your own planet")]
+    OwnPlanet { action: &'static str },
+
+    #[error("you already own this planet")]
+    AlreadyOwned,
+
+    #[error("cannot {action} an allied planet")]
+    AlliedPlanet { action: &'static str },
+
+    #[error("cannot {action} neutral planets - use colonize instead")]
+    NeutralPlanet { action: &'static str },
+
+    #[error("planet shields must be destroyed first (current: {hp} HP)")]
+    ShieldsUp { hp: u32 },
+
+    #[error("planet is consolidating after changing hands - it cannot be taken for {turns} more turn(s)")]
+    Consolidating { turns: u32 },
+
+    #[error("players are in protected expansion - hostilities may begin on turn {turn}")]
+    Protected { turn: u32 },
+
+    #[error("fleet capacity is {limit} - it uses {used} and {adding} more does not fit")]
+    FleetFull { used: usize, adding: usize, limit: usize },
+
+    #[error("planet is not owned by anyone")]
+    PlanetNotOwned,
+
+    #[error("planet belongs to another player")]
+    NotYourPlanet,
+
+    #[error("not enough resources, short {shortfall}")]
+    NotEnoughResources { cost: Resources, shortfall: Resources },
+
+    #[error("colonizing costs {cost} from {origin_name}, {hops} hop(s) away - short {shortfall}")]
+    ColonizationUnaffordable { origin_name: String, hops: u32, cost: Resources, shortfall: Resources },
+
+    #[error("cargo holds take {capacity} - {carried} aboard and {adding} more does not fit")]
+    CargoFull { carried: u32, adding: u32, capacity: u32 },
+
+    #[error("fleet carries no cargo")]
+    NoCargo,
+
+    #[error("fleet carries {carried} cargo and only {capacity} would fit - unload it first")]
+    CargoStranded { carried: u32, capacity: u32 },
+
+    #[error("awaiting {prerequisite} at level {required_level}")]
+    MissingPrerequisite { prerequisite: StructureId, required_level: u32 },
+
+    #[error(transparent)]
+    Blocked(GameStateError),
+}
+
+/// Whether one of the fleet's ships is an ark, able to found a colony.
+pub fn fleet_has_ark(fleet: &Fleet, player: &Player) -> bool {
+    fleet_ark(fleet, player).is_some()
+}
+
+/// The ark a colony founded by `fleet` uses up: the one with the lowest id when it carries several.
+pub fn fleet_ark<'a>(fleet: &'a Fleet, player: &Player) -> Option<&'a ShipInstanceId> {
+    fleet.ships.iter()
+        .filter(|ship_id| player.ships.get(*ship_id).is_some_and(|ship| ship.ship_type == "ark"))
+        .min()
+}
+
+/// Who holds a planet, as seen by a player with a fleet in its orbit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Standing {
+    Own,
+    Allied,
+    Neutral,
+    /// Held by an opponent
+    Hostile,
+}
+
+impl Standing {
+    pub const ALL: [Standing; 4] = [Standing::Own, Standing::Allied, Standing::Neutral, Standing::Hostile];
+
+    pub fn of(planet: &Planet, player: &Player, game_state: &GameState) -> Standing {
+        match planet.get_owner() {
+            Some(owner_id) if owner_id == &player.id => Standing::Own,
+            Some(owner_id) if game_state.are_allies(owner_id, &player.id) => Standing::Allied,
+            Some(_) => Standing::Hostile,
+            None => Standing::Neutral,
+        }
+    }
+
+    /// The planet as `help rules` names it, such as "an opponent's".
+    pub fn label(self) -> &'static str {
+        match self {
+            Standing::Own => "yours",
+            Standing::Allied => "an ally's",
+            Standing::Neutral => "neutral",
+            Standing::Hostile => "an opponent's",
+        }
+    }
+}
+
+/// What a fleet in orbit may do at a planet, before its ships are looked at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrbitRules {
+    pub station: bool,
+    pub blockade: bool,
+    pub bombard: bool,
+    pub colonize: bool,
+    pub invade: bool,
+}
+
+/// The truth table every orbit predicate below follows, and `help rules` shows:
+///
+/// | Planet            | Station | Blockade | Bombard | Colonize | Invade |
+/// |-------------------|---------|----------|---------|----------|--------|
+/// | own or allied     | yes     | no       | no      | no       | no     |
+/// | neutral           | yes     | no       | no      | if down  | no     |
+/// | an opponent's     | yes     | yes      | yes     | no       | if down|
+///
+/// "If down" means only once the planet's shields are down: shields keep landings out, never
+/// fleets. Each predicate then asks what the fleet itself needs, such as an ark to land.
+pub fn orbit_rules(standing: Standing, shields_up: bool) -> OrbitRules {
+    let hostile = standing == Standing::Hostile;
+    OrbitRules {
+        station: true,
+        blockade: hostile,
+        bombard: hostile,
+        colonize: standing == Standing::Neutral && !shields_up,
+        invade: hostile && !shields_up,
+    }
+}
+
+fn planet_rules(planet: &Planet, player: &Player, game_state: &GameState) -> (Standing, OrbitRules) {
+    let standing = Standing::of(planet, player, game_state);
+    (standing, orbit_rules(standing, planet.get_shield_hp() > 0))
+}
+
+/// Checks that `fleet` can take up orbit around `planet`. Any planet may be orbited, an
+/// opponent's behind full shields included; arriving there only gets the fleet noticed.
+pub fn can_station(fleet: &Fleet, planet: &Planet, player: &Player, game_state: &GameState) -> Result<(), Reason> {
+    if fleet.is_empty() {
+        return Err(Reason::EmptyFleet);
+    }
+    debug_assert!(planet_rules(planet, player, game_state).1.station);
+    Ok(())
+}
+
+/// Checks that `fleet` blockades the planet it orbits: an opponent's, with no orders to leave,
+/// once the protected expansion is over.
+pub fn can_blockade(fleet: &Fleet, planet: &Planet, player: &Player, game_state: &GameState) -> Result<(), Reason> {
+    if fleet.is_empty() {
+        return Err(Reason::EmptyFleet);
+    }
+    if player.has_pending_fleet_move(&fleet.id) {
+        return Err(Reason::MovePending);
+    }
+    hostile_target(planet, player, game_state, "blockade", |rules| rules.blockade)
+}
+
+/// Whether `player` could bombard `planet` at all: only opponents' planets can be bombarded,
+/// and only once the protected expansion is over.
+pub fn bombard_target(planet: &Planet, player: &Player, game_state: &GameState) -> Result<(), Reason> {
+    hostile_target(planet, player, game_state, "bombard", |rules| rules.bombard)
+}
+
+fn hostile_target(
+    planet: &Planet,
+    player: &Player,
+    game_state: &GameState,
+    action: &'static str,
+    allowed: fn(OrbitRules) -> bool,
+) -> Result<(), Reason> {
+    match planet_rules(planet, player, game_state) {
+        (_, rules) if allowed(rules) => protection(game_state),
+        (Standing::Own, _) => Err(Reason::OwnPlanet { action }),
+        (Standing::Neutral, _) => Err(Reason::NeutralPlanet { action }),
+        (_, _) => Err(Reason::AlliedPlanet { action }),
+    }
+}
+
+/// Rejects hostilities between players while the protected expansion of the first turns lasts.
+fn protection(game_state: &GameState) -> Result<(), Reason> {
+    match game_state.protection_turns_left() {
+        0 => Ok(()),
+        _ => Err(Reason::Protected { turn: game_state.rules.protected_turns + 1 }),
+    }
+}
+
+/// Checks that `fleet` can bombard the planet it orbits, returning its bombardment power.
+pub fn can_bombard(fleet: &Fleet, planet: &Planet, player: &Player, game_state: &GameState) -> Result<u32, Reason> {
+    if fleet.is_empty() {
+        return Err(Reason::EmptyFleet);
+    }
+    if player.has_pending_fleet_bombardment(&fleet.id) {
+        return Err(Reason::AlreadyBombarding);
+    }
+    let bombardment_power = game_state.calculate_fleet_bombardment(&player.id, &fleet.id);
+    if bombardment_power == 0 {
+        return Err(Reason::NoBombardment);
+    }
+    bombard_target(planet, player, game_state)?;
+    Ok(bombardment_power)
+}
+
+/// Whether `player` could land on `planet` at all, colonizing it when neutral or invading it
+/// when an opponent's: it must not be theirs or an ally's, must not have changed hands too
+/// recently, and its shields must be down. Opponents' planets are off limits while the
+/// protected expansion lasts.
+pub fn colonize_target(planet: &Planet, player: &Player, game_state: &GameState) -> Result<(), Reason> {
+    let (standing, rules) = planet_rules(planet, player, game_state);
+    match standing {
+        Standing::Own => Err(Reason::AlreadyOwned),
+        Standing::Allied => Err(Reason::AlliedPlanet { action: "invade" }),
+        Standing::Hostile if game_state.protection_turns_left() > 0 => protection(game_state),
+        _ if planet.get_consolidation_turns() > 0 => Err(Reason::Consolidating { turns: planet.get_consolidation_turns() }),
+        _ if !(rules.colonize || rules.invade) => Err(Reason::ShieldsUp { hp: planet.get_shield_hp() }),
+        _ => Ok(()),
+    }
+}
+
+/// What founding a colony on a neutral planet costs, and which of the player's planets pays.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColonizationCost {
+    /// The player's planet fewest connections away, ties going to the lowest id
+    pub origin: PlanetId,
+    pub hops: u32,
+    pub cost: Resources,
+}
+
+/// What colonizing `planet` would cost `player`. Only neutral planets cost anything, and only
+/// while the player holds a planet connected to them to pay from.
+pub fn colonization_cost(planet: &Planet, player: &Player, game_state: &GameState) -> Option<ColonizationCost> {
+    if planet.get_owner().is_some() {
+        return None;
+    }
+    let hops = game_state.map.graph().hops(&planet.id);
+    let (hops, origin) = player.planets.iter()
+        .filter_map(|planet_id| Some((*hops.get(planet_id)?, planet_id)))
+        .min()?;
+    Some(ColonizationCost {
+        origin: origin.clone(),
+        hops,
+        cost: game_state.rules_profile().colonization_cost(hops),
+    })
+}
+
+/// Checks that `fleet` can carry out `fleet colonize` at the planet it orbits: founding a
+/// colony on a neutral planet, which the player's nearest planet must be able to pay for, or
+/// invading an opponent's, as `can_invade` checks.
+pub fn can_colonize(fleet: &Fleet, planet: &Planet, player: &Player, game_state: &GameState) -> Result<(), Reason> {
+    if Standing::of(planet, player, game_state) == Standing::Hostile {
+        return can_invade(fleet, planet, player, game_state);
+    }
+    if fleet.is_empty() {
+        return Err(Reason::EmptyFleet);
+    }
+    if !fleet_has_ark(fleet, player) {
+        return Err(Reason::NoArk);
+    }
+    colonize_target(planet, player, game_state)?;
+
+    if let Some(ColonizationCost { origin, hops, cost }) = colonization_cost(planet, player, game_state) {
+        let origin = &game_state.map.planets[&origin];
+        if !origin.available_resources.has_enough(&cost) {
+            let shortfall = cost.clone() - origin.available_resources.clone();
+            return Err(Reason::ColonizationUnaffordable { origin_name: origin.name.clone(), hops, cost, shortfall });
+        }
+    }
+    Ok(())
+}
+
+/// Checks that `fleet` can invade the planet it orbits: an opponent's with its shields down,
+/// not changed hands too recently, with an ark aboard to land, once the protected expansion
+/// is over.
+pub fn can_invade(fleet: &Fleet, planet: &Planet, player: &Player, game_state: &GameState) -> Result<(), Reason> {
+    if fleet.is_empty() {
+        return Err(Reason::EmptyFleet);
+    }
+    if !fleet_has_ark(fleet, player) {
+        return Err(Reason::NoArk);
+    }
+    match planet_rules(planet, player, game_state) {
+        (Standing::Neutral, _) => Err(Reason::NeutralPlanet { action: "invade" }),
+        (Standing::Hostile, _) if game_state.protection_turns_left() > 0 => protection(game_state),
+        (Standing::Hostile, _) if planet.get_consolidation_turns() > 0 => {
+            Err(Reason::Consolidating { turns: planet.get_consolidation_turns() })
+        }
+        (_, rules) if rules.invade => Ok(()),
+        (Standing::Hostile, _) => Err(Reason::ShieldsUp { hp: planet.get_shield_hp() }),
+        _ => colonize_target(planet, player, game_state),
+    }
+}
+
+/// Fleet capacity `ships` of `player` take up together: the sum of their ship sizes.
+pub fn capacity_used(ships: &[ShipInstanceId], player: &Player, game_state: &GameState) -> usize {
+    ships.iter()
+        .filter_map(|ship_id| player.ships.get(ship_id))
+        .map(|ship| game_state.ship_config.size_of(&ship.ship_type))
+        .sum()
+}
+
+/// Resources `ships` of `player` can carry together: the sum of their cargo capacities.
+pub fn cargo_capacity(ships: &[ShipInstanceId], player: &Player, game_state: &GameState) -> u32 {
+    ships.iter()
+        .filter_map(|ship_id| player.ships.get(ship_id))
+        .fold(0, |total, ship| total.saturating_add(game_state.ship_config.cargo_of(&ship.ship_type)))
+}
+
+/// Checks that `fleet` can take `cargo` aboard from the planet it orbits: one of the player's
+/// own, holding that much, with room left in the fleet's holds.
+pub fn can_load(fleet: &Fleet, cargo: &Resources, planet: &Planet, player: &Player, game_state: &GameState) -> Result<(), Reason> {
+    if fleet.is_empty() {
+        return Err(Reason::EmptyFleet);
+    }
+    own_planet(planet, player)?;
+
+    let capacity = cargo_capacity(&fleet.ships, player, game_state);
+    let (carried, adding) = (fleet.cargo.total(), cargo.total());
+    if carried.saturating_add(adding) > capacity {
+        return Err(Reason::CargoFull { carried, adding, capacity });
+    }
+    if !planet.available_resources.has_enough(cargo) {
+        let shortfall = cargo.clone() - planet.available_resources.clone();
+        return Err(Reason::NotEnoughResources { cost: cargo.clone(), shortfall });
+    }
+    Ok(())
+}
+
+/// Checks that `fleet` can unload its cargo onto the planet it orbits, one of the player's own.
+pub fn can_unload(fleet: &Fleet, planet: &Planet, player: &Player) -> Result<(), Reason> {
+    if fleet.cargo.total() == 0 {
+        return Err(Reason::NoCargo);
+    }
+    own_planet(planet, player)
+}
+
+/// Checks that the cargo of `fleet` still fits its holds once the `leaving` ships are gone.
+pub fn cargo_fits_without(fleet: &Fleet, leaving: &[ShipInstanceId], player: &Player, game_state: &GameState) -> Result<(), Reason> {
+    let staying: Vec<ShipInstanceId> = fleet.ships.iter()
+        .filter(|ship_id| !leaving.contains(ship_id))
+        .cloned()
+        .collect();
+    let (carried, capacity) = (fleet.cargo.total(), cargo_capacity(&staying, player, game_state));
+    if carried > capacity {
+        return Err(Reason::CargoStranded { carried, capacity });
+    }
+    Ok(())
+}
+
+fn own_planet(planet: &Planet, player: &Player) -> Result<(), Reason> {
+    match planet.get_owner() {
+        Some(owner) if owner == &player.id => Ok(()),
+        Some(_) => Err(Reason::NotYourPlanet),
+        None => Err(Reason::PlanetNotOwned),
+    }
+}
+
+/// Checks that a fleet using `used` capacity can take ships of `adding` total size without
+/// exceeding the game's fleet capacity.
+pub fn fleet_has_room(used: usize, adding: usize, game_state: &GameState) -> Result<(), Reason> {
+    let limit = game_state.rules_profile().fleet_capacity;
+    if used.saturating_add(adding) > limit {
+        return Err(Reason::FleetFull { used, adding, limit });
+    }
+    Ok(())
+}
+
+/// Checks that `fleet` can set off for the neighbouring planet `target`, returning the
+/// travel time in turns.
+pub fn can_move(fleet: &Fleet, target: &PlanetId, player: &Player, game_state: &GameState) -> Result<u8, Reason> {
+    if fleet.is_empty() {
+        return Err(Reason::EmptyFleet);
+    }
+    if player.has_pending_fleet_bombardment(&fleet.id) {
+        return Err(Reason::Bombarding);
+    }
+    can_set_off(&MovableUnit::Fleet(fleet.id.clone()), &fleet.location, target, player, game_state)
+}
+
+/// Checks that `ship` can set off on its own for the neighbouring planet `target`, returning
+/// the travel time in turns. Ships in a fleet move with it, and garrisons stay put.
+pub fn can_move_ship(ship: &Ship, target: &PlanetId, player: &Player, game_state: &GameState) -> Result<u8, Reason> {
+    if let Some(fleet_id) = &ship.fleet_id {
+        return Err(Reason::InFleet(fleet_id.clone()));
+    }
+    if game_state.is_garrisoned(ship) {
+        return Err(Reason::Garrisoned);
+    }
+    if player.find_ship_in_retrofit(&ship.id).is_some() {
+        return Err(Reason::Retrofitting);
+    }
+    can_set_off(&MovableUnit::Ship(ship.id.clone()), &ship.location, target, player, game_state)
+}
+
+/// What fleet and ship moves share: one move at a time, along a direct connection.
+fn can_set_off(unit: &MovableUnit, from: &PlanetId, target: &PlanetId, player: &Player, game_state: &GameState) -> Result<u8, Reason> {
+    if player.pending_move(unit).is_some() {
+        return Err(Reason::MovePending);
+    }
+    if !game_state.map.planets.contains_key(target) {
+        return Err(Reason::UnknownPlanet(target.clone()));
+    }
+    if from == target {
+        return Err(Reason::AlreadyThere { planet_id: target.clone() });
+    }
+    let graph = game_state.map.graph();
+    if let Some(distance) = graph.edge_distance(from, target) {
+        return Ok(distance);
+    }
+    let (from, to) = (from.clone(), target.clone());
+    match (graph.route(&from, &to), graph.distance(&from, &to)) {
+        (Some(route), Some(turns)) => Err(Reason::NoConnection { from, to, route, turns }),
+        _ => Err(Reason::Unreachable { from, to }),
+    }
+}
+
+/// Checks that `player` can start building `definition` on `planet`: they must own it, and the
+/// build must fit the planet's queue, structure limits, prerequisites and resources.
+pub fn can_build(
+    planet: &Planet,
+    definition: &StructureDefinition,
+    player: &Player,
+    game_state: &GameState,
+) -> Result<BuildInfo, Reason> {
+    own_planet(planet, player)?;
+
+    game_state.check_structure_build(&player.id, &planet.id, &definition.id)
+        .map_err(|error| match error {
+            GameStateError::PlanetError(PlanetError::NotEnoughResources { cost, .. }) => {
+                let shortfall = cost.clone() - planet.available_resources.clone();
+                Reason::NotEnoughResources { cost, shortfall }
+            }
+            error => Reason::Blocked(error),
+        })
+}
+
+/// Checks that a finished build of `structure_id` can go up on `planet`. Prerequisites are met
+/// when the build is queued, but may be bombarded away while it is under construction.
+pub fn can_complete_build(
+    planet: &Planet,
+    structure_id: &StructureId,
+    structure_config: &StructureConfig,
+) -> Result<(), Reason> {
+    planet.check_prerequisites(structure_id, 1, structure_config, false)
+        .map_err(|error| match error {
+            PlanetError::PrerequisitesNotMet { prerequisite, required_level, .. } => {
+                Reason::MissingPrerequisite { prerequisite, required_level }
+            }
+            error => Reason::Blocked(GameStateError::PlanetError(error)),
+        })
+}
+
+/// What a family of orders needs before a player can give any of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Requirement {
+    Ships,
+    Fleets,
+    /// A fleet carrying an ark
+    Ark,
+    /// The structure standing on at least one of the player's planets
+    Structure(&'static str),
+}
+
+/// Why `player` cannot give any of the orders needing `requirement` yet, or `None` when they
+/// can. Each order still checks its own target when given.
+pub fn unmet_requirement(requirement: Requirement, player: &Player, game_state: &GameState) -> Option<String> {
+    let unmet = match requirement {
+        Requirement::Ships => player.ships.is_empty(),
+        Requirement::Fleets => player.fleets.is_empty(),
+        Requirement::Ark => !player.fleets.values().any(|fleet| fleet_has_ark(fleet, player)),
+        Requirement::Structure(structure_id) => !player.planets.iter()
+            .filter_map(|planet_id| game_state.map.planets.get(planet_id))
+            .any(|planet| planet.get_structure_level(&structure_id.to_string()) > 0),
+    };
+    unmet.then(|| match requirement {
+        Requirement::Ships => String::from("you have no ships yet"),
+        Requirement::Fleets => String::from("you have no fleets yet"),
+        Requirement::Ark => String::from("requires a fleet with an ark"),
+        Requirement::Structure(structure_id) => {
+            let structure = fmt_structure(&game_state.structure_config, &structure_id.to_string());
+            let article = if structure.to_lowercase().starts_with(['a', 'e', 'i', 'o', 'u']) { "an" } else { "a" };
+            format!("requires {} {}", article, structure)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{self, fixture_game};
+
+    /// Alice's fleet_1 made of `ship_types`, orbiting her home planet.
+    fn fleet_at_home(state: &mut GameState, ship_types: &[&str]) -> Fleet {
+        let home = test_support::home_planet(state, "alice");
+        let alice = state.players.get_mut("alice").unwrap();
+        let mut fleet = Fleet::new(String::from("fleet_1"), String::from("Vanguard"), home.clone());
+        for ship_type in ship_types {
+            fleet.add_ship(alice.add_ship(ship_type.to_string(), home.clone()));
+        }
+        alice.fleets.insert(fleet.id.clone(), fleet.clone());
+        fleet
+    }
+
+    fn planet<'a>(state: &'a GameState, player_id: &str) -> &'a Planet {
+        &state.map.planets[&test_support::home_planet(state, player_id)]
+    }
+
+    fn neutral_planet(state: &GameState) -> &Planet {
+        state.map.planets.values().find(|planet| planet.get_owner().is_none()).unwrap()
+    }
+
+    #[test]
+    fn test_bombard_needs_bombers_and_an_opponents_planet() {
+        let mut state = fixture_game(1);
+        let scouts = fleet_at_home(&mut state, &["scout"]);
+        let alice = &state.players["alice"];
+        assert!(matches!(can_bombard(&scouts, planet(&state, "bob"), alice, &state), Err(Reason::NoBombardment)));
+
+        let ravagers = fleet_at_home(&mut state, &["ravager"]);
+        let alice = &state.players["alice"];
+        assert!(matches!(can_bombard(&ravagers, planet(&state, "bob"), alice, &state), Ok(25)));
+        assert!(matches!(can_bombard(&ravagers, planet(&state, "alice"), alice, &state), Err(Reason::OwnPlanet { .. })));
+        assert!(matches!(can_bombard(&ravagers, neutral_planet(&state), alice, &state), Err(Reason::NeutralPlanet { .. })));
+    }
+
+    #[test]
+    fn test_bombard_and_invade_spare_allies() {
+        let mut state = test_support::fixture_team_game(1);
+        let fleet = fleet_at_home(&mut state, &["ravager", "ark"]);
+        let alice = &state.players["alice"];
+        let carol_home = planet(&state, "carol");
+
+        let bombard = can_bombard(&fleet, carol_home, alice, &state).unwrap_err();
+        assert_eq!(bombard.to_string(), "cannot bombard an allied planet");
+        let colonize = can_colonize(&fleet, carol_home, alice, &state).unwrap_err();
+        assert_eq!(colonize.to_string(), "cannot invade an allied planet");
+    }
+
+    #[test]
+    fn test_orbit_predicates_follow_the_truth_table() {
+        for shields_up in [false, true] {
+            let mut state = test_support::fixture_team_game(1);
+            let neutral = neutral_planet(&state).id.clone();
+            let targets = [
+                (Standing::Own, test_support::home_planet(&state, "alice")),
+                (Standing::Allied, test_support::home_planet(&state, "carol")),
+                (Standing::Neutral, neutral),
+                (Standing::Hostile, test_support::home_planet(&state, "bob")),
+            ];
+            if shields_up {
+                for (_, planet_id) in &targets {
+                    let planet = state.map.planets.get_mut(planet_id).unwrap();
+                    for structure_id in ["power_grid", "defense_shield"] {
+                        planet.complete_build_structure(String::from(structure_id), &state.structure_config).unwrap();
+                    }
+                    planet.overcharge_shield();
+                }
+            }
+
+            for composition in [&["scout"][..], &["ravager"], &["ark"], &[]] {
+                let fleet = fleet_at_home(&mut state, composition);
+                let alice = &state.players["alice"];
+                let carries = |ship_type: &str| composition.contains(&ship_type);
+
+                for (standing, planet_id) in &targets {
+                    let planet = &state.map.planets[planet_id];
+                    let table = orbit_rules(*standing, shields_up);
+                    let case = format!("{:?} planet, shields up: {}, fleet: {:?}", standing, shields_up, composition);
+                    let landing = if *standing == Standing::Hostile { table.invade } else { table.colonize };
+
+                    assert_eq!(Standing::of(planet, alice, &state), *standing, "{case}");
+                    assert_eq!(planet.get_shield_hp() > 0, shields_up, "{case}");
+                    assert_eq!(can_station(&fleet, planet, alice, &state).is_ok(), table.station && !fleet.is_empty(), "{case}");
+                    assert_eq!(can_blockade(&fleet, planet, alice, &state).is_ok(), table.blockade && !fleet.is_empty(), "{case}");
+                    assert_eq!(can_bombard(&fleet, planet, alice, &state).is_ok(), table.bombard && carries("ravager"), "{case}");
+                    assert_eq!(can_colonize(&fleet, planet, alice, &state).is_ok(), landing && carries("ark"), "{case}");
+                    assert_eq!(can_invade(&fleet, planet, alice, &state).is_ok(), table.invade && carries("ark"), "{case}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_colonize_needs_an_ark_and_shields_down() {
+        let mut state = fixture_game(1);
+        let scouts = fleet_at_home(&mut state, &["scout"]);
+        let alice = &state.players["alice"];
+        assert!(matches!(can_colonize(&scouts, neutral_planet(&state), alice, &state), Err(Reason::NoArk)));
+
+        let arks = fleet_at_home(&mut state, &["ark"]);
+        let bob_home = test_support::home_planet(&state, "bob");
+        let bob_planet = state.map.planets.get_mut(&bob_home).unwrap();
+        for structure_id in ["power_grid", "defense_shield"] {
+            bob_planet.complete_build_structure(String::from(structure_id), &state.structure_config).unwrap();
+        }
+        bob_planet.overcharge_shield();
+
+        let alice = &state.players["alice"];
+        assert!(can_colonize(&arks, neutral_planet(&state), alice, &state).is_ok());
+        assert!(matches!(can_colonize(&arks, planet(&state, "alice"), alice, &state), Err(Reason::AlreadyOwned)));
+        assert!(matches!(can_colonize(&arks, &state.map.planets[&bob_home], alice, &state), Err(Reason::ShieldsUp { hp: 50 })));
+    }
+
+    #[test]
+    fn test_protected_expansion_holds_off_hostilities_until_it_ends() {
+        let mut state = fixture_game(1);
+        state.rules.protected_turns = 3;
+        let fleet = fleet_at_home(&mut state, &["ravager", "ark"]);
+
+        state.turn = 3;
+        let alice = &state.players["alice"];
+        let bob_planet = planet(&state, "bob");
+        let bombard = can_bombard(&fleet, bob_planet, alice, &state).unwrap_err();
+        assert_eq!(bombard.to_string(), "players are in protected expansion - hostilities may begin on turn 4");
+        assert!(matches!(can_blockade(&fleet, bob_planet, alice, &state), Err(Reason::Protected { turn: 4 })));
+        assert!(matches!(can_colonize(&fleet, bob_planet, alice, &state), Err(Reason::Protected { turn: 4 })));
+        assert!(can_colonize(&fleet, neutral_planet(&state), alice, &state).is_ok());
+
+        state.turn = 4;
+        let alice = &state.players["alice"];
+        let bob_planet = planet(&state, "bob");
+        assert!(can_bombard(&fleet, bob_planet, alice, &state).is_ok());
+        assert!(can_blockade(&fleet, bob_planet, alice, &state).is_ok());
+        assert!(can_colonize(&fleet, bob_planet, alice, &state).is_ok());
+    }
+
+    #[test]
+    fn test_move_only_along_a_connection() {
+        let mut state = fixture_game(1);
+        let fleet = fleet_at_home(&mut state, &["scout"]);
+        let alice = &state.players["alice"];
+        let (neighbor, distance) = state.map.graph().neighbors(&fleet.location)
+            .map(|(planet_id, distance)| (planet_id.clone(), distance))
+            .next()
+            .unwrap();
+        let unconnected = state.map.planets.keys()
+            .find(|planet_id| **planet_id != fleet.location && !state.map.graph().are_adjacent(&fleet.location, planet_id))
+            .unwrap();
+
+        assert_eq!(can_move(&fleet, &neighbor, alice, &state).unwrap(), distance);
+        assert!(matches!(can_move(&fleet, &fleet.location, alice, &state), Err(Reason::AlreadyThere { .. })));
+        assert!(matches!(can_move(&fleet, unconnected, alice, &state), Err(Reason::NoConnection { .. })));
+        assert!(matches!(can_move(&fleet, &String::from("nowhere"), alice, &state), Err(Reason::UnknownPlanet(_))));
+
+        let empty = Fleet::new(String::from("fleet_9"), String::from("Empty"), fleet.location.clone());
+        assert!(matches!(can_move(&empty, &neighbor, alice, &state), Err(Reason::EmptyFleet)));
+    }
+
+    #[test]
+    fn test_distant_target_reports_the_quickest_route() {
+        let mut state = fixture_game(1);
+        let fleet = fleet_at_home(&mut state, &["scout"]);
+        let alice = &state.players["alice"];
+        let graph = state.map.graph();
+        let distant = state.map.planets.keys()
+            .find(|planet_id| **planet_id != fleet.location && !graph.are_adjacent(&fleet.location, planet_id))
+            .unwrap();
+
+        let Err(reason) = can_move(&fleet, distant, alice, &state) else {
+            panic!("a planet two or more hops away cannot be moved to directly");
+        };
+        let route = graph.route(&fleet.location, distant).unwrap();
+        let turns = graph.distance(&fleet.location, distant).unwrap();
+        assert!(route.len() > 2);
+        assert_eq!(
+            reason.to_string(),
+            format!(
+                "{} is not directly connected to {}; nearest route is {} ({} turns total). \
+                 Direct moves only — move hop by hop",
+                distant, fleet.location, route.join(" → "), turns,
+            ),
+        );
+    }
+
+    #[test]
+    fn test_disconnected_target_is_unreachable() {
+        let mut state = fixture_game(1);
+        let fleet = fleet_at_home(&mut state, &["scout"]);
+        let isolated = neutral_planet(&state).id.clone();
+        let neighbors: Vec<_> = state.map.graph().neighbors(&isolated).map(|(planet_id, _)| planet_id.clone()).collect();
+        for neighbor in &neighbors {
+            state.map.remove_edge(&isolated, neighbor);
+        }
+
+        let alice = &state.players["alice"];
+        assert!(matches!(can_move(&fleet, &isolated, alice, &state), Err(Reason::Unreachable { .. })));
+    }
+
+    #[test]
+    fn test_build_checks_ownership_and_reports_the_shortfall() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        state.map.planets.get_mut(&home).unwrap().available_resources = Resources { minerals: 30, gas: 0, energy: 0 };
+        let power_grid = state.structure_config.get(&String::from("power_grid")).unwrap().clone();
+        let alice = &state.players["alice"];
+
+        let result = can_build(&state.map.planets[&home], &power_grid, alice, &state);
+        assert!(matches!(result, Err(Reason::NotEnoughResources { shortfall, .. }) if shortfall.minerals == 20));
+        assert!(matches!(can_build(planet(&state, "bob"), &power_grid, alice, &state), Err(Reason::NotYourPlanet)));
+        assert!(matches!(can_build(neutral_planet(&state), &power_grid, alice, &state), Err(Reason::PlanetNotOwned)));
+    }
+}
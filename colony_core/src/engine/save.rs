@@ -0,0 +1,782 @@
+// Saving and loading game sessions as JSON files, optionally gzip-compressed. Without the
+// `std-io` feature only the in-memory encoding is available.
+
+#[cfg(feature = "std-io")]
+use std::fs;
+#[cfg(feature = "std-io")]
+use std::io::{ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+#[cfg(feature = "std-io")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "std-io")]
+use flate2::Compression;
+#[cfg(feature = "std-io")]
+use flate2::read::GzDecoder;
+#[cfg(feature = "std-io")]
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::engine::appearance;
+use crate::engine::configs::structure_config::StructureDefinition;
+use crate::engine::game_state::{GameState, GameSummary};
+use crate::engine::map::MapError;
+use crate::engine::structure::Structure;
+
+/// Directory saves are written to, relative to the working directory
+pub const DEFAULT_SAVE_DIR: &str = "saves";
+
+/// Slot used by the exit prompt and the quicksave commands
+pub const QUICKSAVE_SLOT: &str = "quick";
+
+/// Slot written every few rounds when the game's autosave option is on
+pub const AUTOSAVE_SLOT: &str = "autosave";
+
+const SAVE_EXTENSION: &str = "json";
+
+/// Leading bytes of every gzip stream, used to tell compressed saves and replays from plain JSON
+#[cfg(feature = "std-io")]
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Version written into new saves. Bump it together with a new entry in `MIGRATIONS`
+/// whenever the serialized game state changes shape.
+pub const CURRENT_FORMAT_VERSION: u32 = 6;
+
+/// Saves written before versioning carry no `format_version` field.
+const UNVERSIONED_FORMAT_VERSION: u32 = 1;
+
+type Migration = fn(Value) -> Result<Value, String>;
+
+/// `MIGRATIONS[n]` upgrades a save from version `n + 1` to `n + 2`.
+const MIGRATIONS: &[Migration] = &[
+    migrate_v1_to_v2,
+    migrate_v2_to_v3,
+    migrate_v3_to_v4,
+    migrate_v4_to_v5,
+    migrate_v5_to_v6,
+];
+
+#[derive(Debug, Error)]
+pub enum SaveError {
+    #[error("Invalid save name '{0}': use letters, digits, '-' and '_' only")]
+    InvalidName(String),
+
+    #[error("Save '{0}' does not exist")]
+    NotFound(String),
+
+    #[error("Failed to access save file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to encode or decode save: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Save file is corrupt or truncated (compressed data): {0}")]
+    Compression(std::io::Error),
+
+    #[error("Save format version {found} is newer than supported version {supported}; update the game to load it")]
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    #[error("Failed to migrate save from format version {from}: {reason}")]
+    Migration { from: u32, reason: String },
+
+    #[error("Save holds an invalid map: {0}")]
+    InvalidMap(#[from] MapError),
+}
+
+/// Summary stored at the top of every save so saves can be listed without loading them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaveHeader {
+    pub name: String,
+    pub turn: u32,
+    /// Player display names in turn order
+    pub player_names: Vec<String>,
+    /// Seconds since the Unix epoch
+    pub saved_at: u64,
+    /// Absent in saves from before sizes were recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sizes: Option<SaveSizes>,
+    /// Absent in saves from before summaries were recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<GameSummary>,
+}
+
+/// Bytes of JSON the collections that grow over a game take up in a save, before compression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SaveSizes {
+    pub action_log: u64,
+    pub battles: u64,
+    /// Every player's planet intel together
+    pub intel: u64,
+    pub turn_history: u64,
+}
+
+impl SaveSizes {
+    fn measure(game_state: &GameState) -> Self {
+        SaveSizes {
+            action_log: json_len(&game_state.action_log),
+            battles: json_len(&game_state.battles),
+            intel: game_state.players.values().map(|player| json_len(&player.planet_intel)).sum(),
+            turn_history: json_len(&game_state.turn_history),
+        }
+    }
+}
+
+/// Counts the bytes written to it instead of keeping them.
+struct ByteCounter(u64);
+
+impl std::io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn json_len(value: &impl Serialize) -> u64 {
+    let mut counter = ByteCounter(0);
+    serde_json::to_writer(&mut counter, value).map_or(0, |_| counter.0)
+}
+
+impl SaveHeader {
+    fn new(name: &str, game_state: &GameState, saved_at: u64) -> Self {
+        SaveHeader {
+            name: name.to_string(),
+            turn: game_state.turn,
+            player_names: game_state.players_order.iter()
+                .filter_map(|id| game_state.players.get(id))
+                .map(|player| player.name.clone())
+                .collect(),
+            saved_at,
+            sizes: Some(SaveSizes::measure(game_state)),
+            summary: Some(game_state.game_summary()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SaveFileRef<'a> {
+    format_version: u32,
+    header: SaveHeader,
+    game_state: &'a GameState,
+}
+
+#[derive(Deserialize)]
+struct SaveFile {
+    game_state: GameState,
+}
+
+#[cfg(feature = "std-io")]
+#[derive(Deserialize)]
+struct SaveFileHeader {
+    header: SaveHeader,
+}
+
+/// A file found in the save directory. Files that are not readable saves are still listed,
+/// with the reason they could not be read.
+#[derive(Debug)]
+pub struct SaveEntry {
+    pub file_name: String,
+    /// Bytes on disk, compressed or not
+    pub size: u64,
+    pub header: Result<SaveHeader, String>,
+    /// Save time from the header, or the file modification time for unreadable files
+    pub timestamp: u64,
+}
+
+/// Returns the file path of a named save inside `save_dir`.
+/// Names are plain identifiers so they can never point outside the directory.
+pub fn save_path(save_dir: &Path, name: &str) -> Result<PathBuf, SaveError> {
+    let valid = !name.is_empty()
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(SaveError::InvalidName(name.to_string()));
+    }
+    Ok(save_dir.join(format!("{}.{}", name, SAVE_EXTENSION)))
+}
+
+/// Encodes the game state as save JSON with a metadata header naming it `name`, for
+/// frontends that store saves somewhere other than the file system. `saved_at` is in
+/// seconds since the Unix epoch, since the engine itself reads no clock.
+pub fn encode_save(game_state: &GameState, name: &str, saved_at: u64) -> Result<Vec<u8>, SaveError> {
+    let save_file = SaveFileRef {
+        format_version: CURRENT_FORMAT_VERSION,
+        header: SaveHeader::new(name, game_state, saved_at),
+        game_state,
+    };
+    Ok(serde_json::to_vec(&save_file)?)
+}
+
+/// Decodes save JSON written by [`encode_save`] or [`save_game`], migrating it first if it
+/// was written by an older format version. Compressed saves must be decompressed first.
+pub fn decode_save(json: &[u8]) -> Result<GameState, SaveError> {
+    let save = migrate(serde_json::from_slice(json)?)?;
+    let mut save_file: SaveFile = serde_json::from_value(save)?;
+    save_file.game_state.map.validate_distances()?;
+    // Views of a finished game show everything, which is not saved as such
+    save_file.game_state.omniscient = save_file.game_state.is_over();
+    Ok(save_file.game_state)
+}
+
+/// Writes the game state with a metadata header, creating the save directory if needed.
+/// Loading detects compression on its own, so `compress` only affects the written file.
+#[cfg(feature = "std-io")]
+pub fn save_game(game_state: &GameState, path: &Path, compress: bool) -> Result<(), SaveError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let name = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+    let json = encode_save(game_state, &name, unix_now())?;
+    let bytes = if compress { gzip(&json)? } else { json };
+    fs::write(path, bytes)?;
+    Ok(())
+}
+
+/// Loads a save, migrating it first if it was written by an older format version.
+#[cfg(feature = "std-io")]
+pub fn load_game(path: &Path) -> Result<GameState, SaveError> {
+    decode_save(&read_save(path)?)
+}
+
+#[cfg(feature = "std-io")]
+pub fn read_header(path: &Path) -> Result<SaveHeader, SaveError> {
+    let json = read_save(path)?;
+    let save_file: SaveFileHeader = serde_json::from_slice(&json)?;
+    Ok(save_file.header)
+}
+
+/// Lists every file in `save_dir`, most recent first. A missing directory has no saves.
+#[cfg(feature = "std-io")]
+pub fn list_saves(save_dir: &Path) -> Result<Vec<SaveEntry>, SaveError> {
+    let dir = match fs::read_dir(save_dir) {
+        Ok(dir) => dir,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut entries = Vec::new();
+    for dir_entry in dir {
+        let dir_entry = dir_entry?;
+        let metadata = dir_entry.metadata()?;
+        if !metadata.is_file() {
+            continue;
+        }
+
+        let path = dir_entry.path();
+        let file_name = dir_entry.file_name().to_string_lossy().into_owned();
+        let header = if path.extension().is_some_and(|ext| ext == SAVE_EXTENSION) {
+            read_header(&path).map_err(|e| e.to_string())
+        } else {
+            Err(String::from("not a save file"))
+        };
+        let timestamp = match &header {
+            Ok(header) => header.saved_at,
+            Err(_) => metadata.modified().ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |age| age.as_secs()),
+        };
+
+        entries.push(SaveEntry { file_name, size: metadata.len(), header, timestamp });
+    }
+
+    entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then_with(|| a.file_name.cmp(&b.file_name)));
+    Ok(entries)
+}
+
+#[cfg(feature = "std-io")]
+pub fn delete_save(save_dir: &Path, name: &str) -> Result<(), SaveError> {
+    let path = save_path(save_dir, name)?;
+    fs::remove_file(&path).map_err(|e| not_found_or_io(e, &path))
+}
+
+#[cfg(feature = "std-io")]
+pub fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_secs())
+}
+
+/// Applies every migration between the save's format version and the current one.
+fn migrate(mut save: Value) -> Result<Value, SaveError> {
+    let version = match save.get("format_version") {
+        None => UNVERSIONED_FORMAT_VERSION,
+        Some(version) => version.as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| SaveError::Migration {
+                from: 0,
+                reason: format!("invalid format_version {}", version),
+            })?,
+    };
+    if version > CURRENT_FORMAT_VERSION {
+        return Err(SaveError::UnsupportedVersion { found: version, supported: CURRENT_FORMAT_VERSION });
+    }
+    if version < UNVERSIONED_FORMAT_VERSION {
+        return Err(SaveError::Migration {
+            from: version,
+            reason: format!("format versions start at {}", UNVERSIONED_FORMAT_VERSION),
+        });
+    }
+
+    for from in version..CURRENT_FORMAT_VERSION {
+        let migration = MIGRATIONS[(from - 1) as usize];
+        save = migration(save).map_err(|reason| SaveError::Migration { from, reason })?;
+        save["format_version"] = Value::from(from + 1);
+    }
+
+    Ok(save)
+}
+
+/// Version 2 records the turn each planet was colonized. Older saves do not know it.
+fn migrate_v1_to_v2(mut save: Value) -> Result<Value, String> {
+    let planets = save.pointer_mut("/game_state/map/planets")
+        .and_then(Value::as_object_mut)
+        .ok_or("missing game_state.map.planets")?;
+
+    for planet in planets.values_mut() {
+        let planet = planet.as_object_mut().ok_or("planet is not an object")?;
+        planet.insert(String::from("colonized_turn"), Value::Null);
+    }
+
+    Ok(save)
+}
+
+/// Version 3 stores each player's color and tag. Older saves never recorded who joined
+/// first, so appearances are handed out in turn order.
+fn migrate_v2_to_v3(mut save: Value) -> Result<Value, String> {
+    let order: Vec<String> = save.pointer("/game_state/players_order")
+        .and_then(Value::as_array)
+        .ok_or("missing game_state.players_order")?
+        .iter()
+        .map(|id| id.as_str().map(str::to_string).ok_or("player id is not a string"))
+        .collect::<Result<_, _>>()?;
+    let players = save.pointer_mut("/game_state/players")
+        .and_then(Value::as_object_mut)
+        .ok_or("missing game_state.players")?;
+
+    let names = order.iter()
+        .map(|id| players.get(id)
+            .and_then(|player| player.get("name"))
+            .and_then(Value::as_str)
+            .ok_or_else(|| format!("player '{}' has no name", id)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let appearances = appearance::assign(&names);
+
+    for (id, appearance) in order.iter().zip(appearances) {
+        let player = players.get_mut(id)
+            .and_then(Value::as_object_mut)
+            .ok_or_else(|| format!("player '{}' is not an object", id))?;
+        let appearance = serde_json::to_value(appearance).map_err(|e| e.to_string())?;
+        player.insert(String::from("appearance"), appearance);
+    }
+
+    Ok(save)
+}
+
+/// Version 4 records who has acted this round instead of how many players are left. Players
+/// move to the back of the rotation as they end their turn, so the ones that acted are last.
+fn migrate_v3_to_v4(mut save: Value) -> Result<Value, String> {
+    let game_state = save.pointer_mut("/game_state")
+        .and_then(Value::as_object_mut)
+        .ok_or("missing game_state")?;
+    let remaining = game_state.remove("players_remaining_this_turn")
+        .and_then(|remaining| remaining.as_u64())
+        .ok_or("missing game_state.players_remaining_this_turn")?;
+    let order = game_state.get("players_order")
+        .and_then(Value::as_array)
+        .ok_or("missing game_state.players_order")?;
+
+    let acted: Vec<_> = order.iter().skip(remaining as usize).cloned().collect();
+    game_state.insert(String::from("acted_this_round"), Value::Array(acted));
+
+    Ok(save)
+}
+
+/// Version 5 records the phase of the game instead of whether it is over.
+fn migrate_v4_to_v5(mut save: Value) -> Result<Value, String> {
+    let game_state = save.pointer_mut("/game_state")
+        .and_then(Value::as_object_mut)
+        .ok_or("missing game_state")?;
+    let game_over = game_state.remove("game_over").and_then(|over| over.as_bool()).unwrap_or(false);
+    let phase = if game_over { "finished" } else { "playing" };
+    game_state.insert(String::from("game_phase"), Value::from(phase));
+
+    Ok(save)
+}
+
+/// Version 6 puts queued structure builds on their planet as structures under construction.
+fn migrate_v5_to_v6(mut save: Value) -> Result<Value, String> {
+    let players = save.pointer("/game_state/players")
+        .and_then(Value::as_object)
+        .ok_or("missing game_state.players")?;
+    let mut builds = Vec::new();
+    for player in players.values() {
+        let pending = player.get("pending_actions").and_then(Value::as_array).into_iter().flatten();
+        let held = player.get("held_actions").and_then(Value::as_array).into_iter().flatten()
+            .filter_map(|held| held.get("action"));
+        for action in pending.chain(held) {
+            let structure_id = action.pointer("/action_type/BuildStructure").and_then(Value::as_str);
+            let planet_id = action.get("planet_id").and_then(Value::as_str);
+            if let (Some(structure_id), Some(planet_id)) = (structure_id, planet_id) {
+                builds.push((planet_id.to_string(), structure_id.to_string()));
+            }
+        }
+    }
+
+    for (planet_id, structure_id) in builds {
+        let definition = save.pointer(&format!("/game_state/structure_config/structures/{}", structure_id))
+            .cloned()
+            .ok_or_else(|| format!("queued structure '{}' is not configured", structure_id))?;
+        let definition: StructureDefinition = serde_json::from_value(definition).map_err(|e| e.to_string())?;
+        let site = serde_json::to_value(Structure::new(Arc::new(definition))).map_err(|e| e.to_string())?;
+        let structures = save.pointer_mut(&format!("/game_state/map/planets/{}/structures", planet_id))
+            .and_then(Value::as_object_mut)
+            .ok_or_else(|| format!("planet '{}' has no structures", planet_id))?;
+        structures.entry(structure_id).or_insert(site);
+    }
+
+    Ok(save)
+}
+
+/// Reads a save file as JSON bytes, decompressing it if it starts with the gzip magic bytes.
+#[cfg(feature = "std-io")]
+fn read_save(path: &Path) -> Result<Vec<u8>, SaveError> {
+    let bytes = fs::read(path).map_err(|e| not_found_or_io(e, path))?;
+    gunzip_if_compressed(bytes).map_err(SaveError::Compression)
+}
+
+/// Gzip-compresses `bytes`, the way compressed saves and replays are written.
+#[cfg(feature = "std-io")]
+pub(crate) fn gzip(bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(bytes)?;
+    encoder.finish()
+}
+
+/// Decompresses `bytes` if they start with the gzip magic bytes and returns them unchanged
+/// otherwise, so compressed and plain files both load. Errors mean a corrupt or truncated
+/// compressed stream.
+#[cfg(feature = "std-io")]
+pub(crate) fn gunzip_if_compressed(bytes: Vec<u8>) -> std::io::Result<Vec<u8>> {
+    if !bytes.starts_with(&GZIP_MAGIC) {
+        return Ok(bytes);
+    }
+
+    let mut decompressed = Vec::new();
+    GzDecoder::new(bytes.as_slice()).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+#[cfg(feature = "std-io")]
+fn not_found_or_io(error: std::io::Error, path: &Path) -> SaveError {
+    if error.kind() == ErrorKind::NotFound {
+        let name = path.file_stem().map_or_else(String::new, |stem| stem.to_string_lossy().into_owned());
+        SaveError::NotFound(name)
+    } else {
+        SaveError::Io(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "std-io")]
+    use crate::engine::commands::command::CommandEffect;
+    #[cfg(feature = "std-io")]
+    use crate::engine::fleet::FleetTemplate;
+    #[cfg(feature = "std-io")]
+    use crate::engine::game_state::GamePhase;
+    use crate::engine::map::MAX_DISTANCE;
+    use crate::engine::planet::{ConnectionError, PlanetId};
+    #[cfg(feature = "std-io")]
+    use crate::test_support;
+    use crate::test_support::fixture_game;
+
+    #[test]
+    #[cfg(feature = "std-io")]
+    fn test_save_and_load_round_trip() {
+        let dir = test_support::temp_dir("save_round_trip");
+        let mut state = fixture_game(5);
+        state.turn = 7;
+
+        for compress in [false, true] {
+            let path = save_path(&dir, &format!("round_trip_{}", compress)).unwrap();
+            save_game(&state, &path, compress).unwrap();
+            let loaded = load_game(&path).unwrap();
+
+            assert_eq!(fs::read(&path).unwrap().starts_with(&GZIP_MAGIC), compress);
+            assert_eq!(read_header(&path).unwrap().turn, 7);
+            assert_eq!(loaded.turn, 7);
+            assert_eq!(loaded.players_order, state.players_order);
+            assert!(loaded.map.planets.keys().eq(state.map.planets.keys()));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std-io")]
+    fn test_fleet_templates_survive_save_and_load() {
+        let dir = test_support::temp_dir("save_templates");
+        let mut state = fixture_game(5);
+        let template = FleetTemplate { ships: [(String::from("ravager"), 2)].into_iter().collect() };
+        state.players.get_mut("alice").unwrap().fleet_templates.insert(String::from("siege"), template.clone());
+
+        let path = save_path(&dir, "templates").unwrap();
+        save_game(&state, &path, true).unwrap();
+        let loaded = load_game(&path).unwrap();
+
+        assert_eq!(loaded.players["alice"].fleet_templates.get("siege"), Some(&template));
+    }
+
+    #[test]
+    #[cfg(feature = "std-io")]
+    fn test_truncated_compressed_save_reports_compression_error() {
+        let dir = test_support::temp_dir("save_truncated");
+        let compressed = save_path(&dir, "compressed").unwrap();
+        let plain = save_path(&dir, "plain").unwrap();
+        save_game(&fixture_game(1), &compressed, true).unwrap();
+        save_game(&fixture_game(1), &plain, false).unwrap();
+        for path in [&compressed, &plain] {
+            let bytes = fs::read(path).unwrap();
+            fs::write(path, &bytes[..bytes.len() / 2]).unwrap();
+        }
+
+        assert!(matches!(load_game(&compressed), Err(SaveError::Compression(_))));
+        assert!(matches!(load_game(&plain), Err(SaveError::Json(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "std-io")]
+    fn test_load_migrates_v1_fixture() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/save_v1.json");
+
+        let state = load_game(&path).unwrap();
+
+        assert_eq!(state.turn, 3);
+        assert_eq!(state.players_order, vec!["alice", "bob"]);
+        assert!(state.acted_this_round.is_empty());
+        assert_eq!(state.map.planets.len(), 10);
+        assert!(state.map.planets.values().all(|planet| planet.get_colonized_turn().is_none()));
+        let home = crate::test_support::home_planet(&state, "alice");
+        let pending: Vec<_> = state.players["alice"].pending_actions_on_planet(&home)
+            .map(|action| action.target_id())
+            .collect();
+        assert_eq!(pending, vec!["power_grid"]);
+        assert_eq!(state.appearance(&String::from("alice")).unwrap().color_index, 0);
+        assert_eq!(state.appearance(&String::from("bob")).unwrap().tag, "BO");
+    }
+
+    #[test]
+    #[cfg(feature = "std-io")]
+    fn test_finished_games_from_v4_load_finished_and_omniscient() {
+        let dir = test_support::temp_dir("save_v4_game_over");
+        let path = save_path(&dir, "over").unwrap();
+        save_game(&fixture_game(1), &path, false).unwrap();
+        let mut save: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        save["format_version"] = Value::from(4);
+        let game_state = save["game_state"].as_object_mut().unwrap();
+        game_state.remove("game_phase");
+        game_state.insert(String::from("game_over"), Value::from(true));
+        fs::write(&path, save.to_string()).unwrap();
+
+        let state = load_game(&path).unwrap();
+
+        assert_eq!(state.game_phase, GamePhase::Finished);
+        assert!(state.omniscient);
+    }
+
+    #[test]
+    #[cfg(feature = "std-io")]
+    fn test_queued_builds_from_v5_load_under_construction() {
+        let dir = test_support::temp_dir("save_v5_construction");
+        let path = save_path(&dir, "building").unwrap();
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let effect = CommandEffect::BuildStructure { planet_id: home.clone(), structure_id: String::from("power_grid") };
+        state.apply_effect(effect, &String::from("alice")).unwrap();
+        save_game(&state, &path, false).unwrap();
+        let mut save: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        save["format_version"] = Value::from(5);
+        save["game_state"]["map"]["planets"][&home]["structures"].as_object_mut().unwrap().remove("power_grid");
+        fs::write(&path, save.to_string()).unwrap();
+
+        let state = load_game(&path).unwrap();
+
+        assert!(state.map.planets[&home].get_structures()["power_grid"].is_under_construction());
+        assert!(state.check_invariants().is_empty(), "{:?}", state.check_invariants());
+    }
+
+    #[test]
+    #[cfg(feature = "std-io")]
+    fn test_new_saves_record_current_version() {
+        let dir = test_support::temp_dir("save_version");
+        let path = save_path(&dir, "versioned").unwrap();
+
+        save_game(&fixture_game(1), &path, false).unwrap();
+
+        let save: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(save["format_version"], CURRENT_FORMAT_VERSION);
+        let state = load_game(&path).unwrap();
+        let home = crate::test_support::home_planet(&state, "alice");
+        assert_eq!(state.map.planets[&home].get_colonized_turn(), Some(1));
+    }
+
+    #[test]
+    #[cfg(feature = "std-io")]
+    fn test_load_rejects_newer_format_version() {
+        let dir = test_support::temp_dir("save_future_version");
+        let path = save_path(&dir, "future").unwrap();
+        save_game(&fixture_game(1), &path, false).unwrap();
+        let mut save: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        save["format_version"] = Value::from(CURRENT_FORMAT_VERSION + 1);
+        fs::write(&path, save.to_string()).unwrap();
+
+        let result = load_game(&path);
+
+        assert!(matches!(
+            result,
+            Err(SaveError::UnsupportedVersion { found, supported: CURRENT_FORMAT_VERSION })
+                if found == CURRENT_FORMAT_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn test_decode_rejects_format_version_zero() {
+        let mut save: Value = serde_json::from_slice(&encode_save(&fixture_game(1), "zero", 0).unwrap()).unwrap();
+        save["format_version"] = Value::from(0);
+
+        let result = decode_save(save.to_string().as_bytes());
+
+        assert!(matches!(result, Err(SaveError::Migration { from: 0, .. })));
+    }
+
+    /// Encodes a fixture game with both directions of one of its connections set to `distance`
+    /// turns. Returns the JSON and the connection's ends.
+    fn save_with_distance(distance: u8) -> (Value, PlanetId, PlanetId) {
+        let state = fixture_game(1);
+        let (from, planet) = state.map.planets.iter().find(|(_, planet)| !planet.get_connections().is_empty()).unwrap();
+        let to = planet.get_connections()[0].to().clone();
+        let mut save: Value = serde_json::from_slice(&encode_save(&state, "distance", 0).unwrap()).unwrap();
+        for (first, second) in [(from, &to), (&to, from)] {
+            let connections = save["game_state"]["map"]["planets"][first]["connections"].as_array_mut().unwrap();
+            let connection = connections.iter_mut().find(|connection| connection["to"] == second.as_str()).unwrap();
+            connection["distance"] = Value::from(distance);
+        }
+        (save, from.clone(), to)
+    }
+
+    #[test]
+    fn test_load_rejects_connections_outside_the_distance_cap() {
+        for distance in [0, 200] {
+            let (save, from, to) = save_with_distance(distance);
+
+            let result = decode_save(save.to_string().as_bytes());
+
+            let Err(SaveError::InvalidMap(MapError::InvalidConnection(ConnectionError::InvalidDistance { from: bad_from, to: bad_to, distance: found, max }))) = result else {
+                panic!("distance {distance} must be rejected");
+            };
+            assert_eq!((found, max), (distance, MAX_DISTANCE));
+            assert!([(&from, &to), (&to, &from)].contains(&(&bad_from, &bad_to)), "{bad_from} -> {bad_to}");
+        }
+    }
+
+    #[test]
+    fn test_imported_map_may_raise_the_distance_cap() {
+        let (mut save, from, to) = save_with_distance(12);
+        save["game_state"]["map"]["max_distance"] = Value::from(20);
+
+        let state = decode_save(save.to_string().as_bytes()).unwrap();
+
+        assert_eq!(state.map.graph().edge_distance(&from, &to), Some(12));
+        assert!(state.check_invariants().is_empty(), "{:?}", state.check_invariants());
+    }
+
+    #[test]
+    fn test_migration_chain_covers_every_version() {
+        assert_eq!(MIGRATIONS.len() as u32, CURRENT_FORMAT_VERSION - UNVERSIONED_FORMAT_VERSION);
+    }
+
+    #[test]
+    fn test_save_name_must_be_a_plain_file_name() {
+        let dir = Path::new(DEFAULT_SAVE_DIR);
+
+        assert!(matches!(save_path(dir, "../escape"), Err(SaveError::InvalidName(_))));
+        assert!(matches!(save_path(dir, "nested/save"), Err(SaveError::InvalidName(_))));
+        assert!(matches!(save_path(dir, "nested\\save"), Err(SaveError::InvalidName(_))));
+        assert!(matches!(save_path(dir, ""), Err(SaveError::InvalidName(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "std-io")]
+    fn test_first_save_creates_directory() {
+        let dir = test_support::temp_dir("save_creates_dir").join("nested");
+
+        save_game(&fixture_game(1), &save_path(&dir, "first").unwrap(), false).unwrap();
+
+        assert!(dir.join("first.json").is_file());
+    }
+
+    #[test]
+    #[cfg(feature = "std-io")]
+    fn test_list_saves_reads_headers_and_flags_bad_files() {
+        let dir = test_support::temp_dir("save_listing");
+        let mut state = fixture_game(1);
+        state.turn = 4;
+        save_game(&state, &save_path(&dir, "good").unwrap(), false).unwrap();
+        fs::write(dir.join("corrupt.json"), "{ not json").unwrap();
+        fs::write(dir.join("foreign.json"), r#"{"something": "else"}"#).unwrap();
+        fs::write(dir.join("notes.txt"), "hello").unwrap();
+
+        let entries = list_saves(&dir).unwrap();
+
+        assert_eq!(entries.len(), 4);
+        let good = entries.iter().find(|entry| entry.file_name == "good.json").unwrap();
+        let header = good.header.as_ref().unwrap();
+        assert_eq!(header.name, "good");
+        assert_eq!(header.turn, 4);
+        assert_eq!(header.player_names, vec!["Alice", "Bob"]);
+        assert_eq!(header.summary, Some(state.game_summary()));
+        assert!(entries.iter().filter(|entry| entry.file_name != "good.json").all(|entry| entry.header.is_err()));
+    }
+
+    #[test]
+    #[cfg(feature = "std-io")]
+    fn test_list_saves_sorts_most_recent_first() {
+        let dir = test_support::temp_dir("save_listing_order");
+        let state = fixture_game(1);
+        for (name, saved_at) in [("old", 100), ("new", 300), ("middle", 200)] {
+            let save_file = SaveFileRef {
+                format_version: CURRENT_FORMAT_VERSION,
+                header: SaveHeader::new(name, &state, saved_at),
+                game_state: &state,
+            };
+            fs::write(save_path(&dir, name).unwrap(), serde_json::to_string(&save_file).unwrap()).unwrap();
+        }
+
+        let names: Vec<_> = list_saves(&dir).unwrap().into_iter().map(|entry| entry.file_name).collect();
+
+        assert_eq!(names, vec!["new.json", "middle.json", "old.json"]);
+    }
+
+    #[test]
+    #[cfg(feature = "std-io")]
+    fn test_list_saves_in_missing_directory_is_empty() {
+        let dir = test_support::temp_dir("save_listing_missing").join("absent");
+
+        assert!(list_saves(&dir).unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std-io")]
+    fn test_delete_save() {
+        let dir = test_support::temp_dir("save_delete");
+        save_game(&fixture_game(1), &save_path(&dir, "doomed").unwrap(), false).unwrap();
+
+        delete_save(&dir, "doomed").unwrap();
+
+        assert!(!dir.join("doomed.json").exists());
+        assert!(matches!(delete_save(&dir, "doomed"), Err(SaveError::NotFound(name)) if name == "doomed"));
+        assert!(matches!(delete_save(&dir, ".."), Err(SaveError::InvalidName(_))));
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt;
+
+use crate::engine::configs::ship_config::ShipId;
+use crate::engine::planet::PlanetId;
+
+pub type ShipInstanceId = String;
+pub type FleetId = String;
+
+/// Something that can be ordered to another planet: a fleet, or a ship outside any fleet.
+/// Moves of both are validated and resolved by the same code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MovableUnit {
+    Fleet(FleetId),
+    Ship(ShipInstanceId),
+}
+
+impl MovableUnit {
+    pub fn id(&self) -> &str {
+        match self {
+            MovableUnit::Fleet(fleet_id) => fleet_id,
+            MovableUnit::Ship(ship_id) => ship_id,
+        }
+    }
+}
+
+impl fmt::Display for MovableUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MovableUnit::Fleet(fleet_id) => write!(f, "fleet {}", fleet_id),
+            MovableUnit::Ship(ship_id) => write!(f, "ship {}", ship_id),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Ship {
+    pub id: ShipInstanceId,
+    pub ship_type: ShipId,
+    pub location: PlanetId,
+    pub fleet_id: Option<FleetId>,
+    /// Shield lost to attrition while out of supply; restored once the fleet is supplied again
+    #[serde(default)]
+    pub supply_wear: u32,
+}
+
+impl Ship {
+    pub fn new(id: ShipInstanceId, ship_type: ShipId, location: PlanetId) -> Self {
+        Self {
+            id,
+            ship_type,
+            location,
+            fleet_id: None,
+            supply_wear: 0,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn is_in_fleet(&self) -> bool {
+        self.fleet_id.is_some()
+    }
+}
@@ -1,8 +1,8 @@
 use std::sync::Arc;
 use thiserror::Error;
 
-use crate::resources::Resources;
-use crate::configs::structure_config::StructureDefinition;
+use crate::engine::resources::Resources;
+use crate::engine::configs::structure_config::{GlobalEffect, Prerequisity, StructureDefinition};
 
 pub type StructureId = String;
 
@@ -16,15 +16,38 @@ pub enum StructureError {
     },
 }
 
+#[derive(serde::Serialize, serde::Deserialize)]
 pub enum StructureState {
+    /// Queued and being built. Level 0, with no hitpoints, production or storage; the build's
+    /// pending action keeps the turns left.
+    UnderConstruction,
     Operational,
     Upgrading {
         turns_remaining: u32,
         target_level: u16,
     },
+    #[allow(dead_code)]
     Damaged,
+    /// Built, but a prerequisite is gone or below the level this structure needs. Produces
+    /// nothing, draws no energy and cannot be upgraded until the prerequisite is rebuilt.
+    Unsupported {
+        prerequisite: StructureId,
+        required_level: u32,
+    },
+}
+
+/// Whether a built structure is switched on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Power {
+    #[default]
+    On,
+    /// Powered down by its owner; stays off until they power it on again
+    Off,
+    /// Shut down for an energy deficit; restarts on its own once production covers it
+    Starved,
 }
 
+#[derive(serde::Serialize, serde::Deserialize)]
 pub struct Structure {
     pub name: String,
     pub hitpoints: u32,
@@ -33,16 +56,16 @@ pub struct Structure {
     pub production: Resources,
     pub storage: Resources,
     pub state: StructureState,
+    /// A structure that is not powered keeps its storage but produces nothing and draws no energy
+    #[serde(default)]
+    pub power: Power,
     structure_definition: Arc<StructureDefinition>,
 }
 
 impl Structure {
+    /// Creates a structure under construction, which stands on its planet from the moment its
+    /// build is queued.
     pub fn new(definition: Arc<StructureDefinition>) -> Self {
-        // Structure starts at level 0 (not yet built)
-        // The upgrade_time[0] represents the build time from level 0 -> level 1
-        let build_time = definition.upgrade_time.get(0).copied()
-            .expect("upgrade_time array validated during config load");
-
         Structure {
             name: definition.name.clone(),
             hitpoints: 0, // No hitpoints until built
@@ -50,10 +73,8 @@ impl Structure {
             max_level: definition.max_level,
             production: Resources::default(), // No production until built
             storage: Resources::default(), // No storage until built
-            state: StructureState::Upgrading {
-                turns_remaining: build_time,
-                target_level: 1
-            },
+            state: StructureState::UnderConstruction,
+            power: Power::On,
             structure_definition: definition
         }
     }
@@ -79,6 +100,7 @@ impl Structure {
             production: definition.production[level_idx].clone(),
             storage: definition.storage_capacity[level_idx].clone(),
             state: StructureState::Operational,
+            power: Power::On,
             structure_definition: definition,
         })
     }
@@ -94,6 +116,10 @@ impl Structure {
         Ok(cost)
     }
 
+    pub fn is_under_construction(&self) -> bool {
+        matches!(self.state, StructureState::UnderConstruction)
+    }
+
     pub fn is_max_level(&self) -> bool {
         self.level >= self.max_level
     }
@@ -108,6 +134,7 @@ impl Structure {
             .expect("upgrade_time array validated during config load; level should be in bounds")
     }
 
+    #[allow(dead_code)]
     pub fn upgrade(&mut self) {
         let curr_level_idx = self.level as usize;
         let upgrade_time = self.structure_definition.upgrade_time[curr_level_idx];
@@ -131,6 +158,7 @@ impl Structure {
         self.state = StructureState::Operational;
     }
 
+    #[allow(dead_code)]
     pub fn process_turn(&mut self) {
         if let StructureState::Upgrading { 
             turns_remaining, 
@@ -152,13 +180,76 @@ impl Structure {
     }
 
     pub fn energy_consumption(&self) -> u32 {
-        if let StructureState::Upgrading { .. } = self.state {
+        if let StructureState::UnderConstruction | StructureState::Upgrading { .. } | StructureState::Unsupported { .. } = self.state {
+            return 0;
+        }
+        if !self.is_powered() {
             return 0;
         }
-        self.structure_definition.energy_consumption[(self.level-1) as usize]
+        self.rated_energy_consumption()
     }
 
+    /// Energy the structure draws at its current level while running, whatever its state.
+    pub fn rated_energy_consumption(&self) -> u32 {
+        self.level.checked_sub(1)
+            .and_then(|index| self.structure_definition.energy_consumption.get(usize::from(index)))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    pub fn is_powered(&self) -> bool {
+        self.power == Power::On
+    }
+
+    #[allow(dead_code)]
     pub fn get_shield_regen_turns(&self) -> Option<u32> {
         self.structure_definition.shield_regen_turns
     }
-}
\ No newline at end of file
+
+    pub fn global_effect(&self) -> Option<GlobalEffect> {
+        self.structure_definition.global_effect
+    }
+
+    /// Unrest this structure removes from its planet each round, zero unless it is operational.
+    pub fn unrest_relief(&self) -> u32 {
+        if !matches!(self.state, StructureState::Operational) || !self.is_powered() {
+            return 0;
+        }
+        self.structure_definition.unrest_relief
+            .map_or(0, |relief| relief.saturating_mul(u32::from(self.level)))
+    }
+
+    pub fn prerequisites(&self) -> &[Prerequisity] {
+        &self.structure_definition.prerequisites
+    }
+}
+
+/// Raw state of a structure for the `inspect` debug command.
+#[derive(serde::Serialize)]
+pub struct StructureDebugView<'a> {
+    pub name: &'a str,
+    pub level: u16,
+    pub max_level: u16,
+    pub state: &'a StructureState,
+    pub power: Power,
+    pub production: &'a Resources,
+    pub storage: &'a Resources,
+    pub energy_consumption: u32,
+    pub rated_energy_consumption: u32,
+}
+
+impl Structure {
+    pub fn debug_view(&self) -> StructureDebugView<'_> {
+        StructureDebugView {
+            name: &self.name,
+            level: self.level,
+            max_level: self.max_level,
+            state: &self.state,
+            power: self.power,
+            production: &self.production,
+            storage: &self.storage,
+            energy_consumption: self.energy_consumption(),
+            rated_energy_consumption: self.rated_energy_consumption(),
+        }
+    }
+}
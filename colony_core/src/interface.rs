@@ -0,0 +1,17 @@
+//! Terminal frontend built on the [`engine`](crate::engine): setup prompts, the hot-seat
+//! run loop and printing of game events.
+
+pub mod crash;
+pub mod event_log;
+pub mod final_report;
+pub mod game;
+pub mod game_configuration;
+pub mod input;
+pub mod output;
+pub mod replay;
+pub mod round_report;
+pub mod setup_file;
+pub mod setup_wizard;
+
+pub use game::Game;
+pub use game_configuration::GameConfiguration;
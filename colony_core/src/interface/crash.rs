@@ -0,0 +1,120 @@
+//! Emergency saves for sessions that die on a panic or a fatal error, and finding them again
+//! on the next start.
+//!
+//! The session encodes a snapshot of the game after every command that changed it. A panic can
+//! strike halfway through applying a command, so the emergency save writes that snapshot rather
+//! than the live state, which may be half updated.
+
+use std::cell::Cell;
+use std::fs;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::engine::game_state::GameState;
+use crate::engine::save::{self, SaveError, SaveHeader};
+
+/// Slot names of emergency saves start with this, followed by the time of the crash
+pub const CRASH_SAVE_PREFIX: &str = "crash_";
+
+/// Crash saves are renamed to start with this once offered, so each is offered only once
+pub const RECOVERED_SAVE_PREFIX: &str = "recovered_";
+
+thread_local! {
+    /// Set while this thread writes an emergency save, so a panic during it does not start another
+    static SAVING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The last game state known to be whole, as save JSON. Clones share the snapshot, so a panic
+/// hook can hold one while the session keeps it up to date.
+#[derive(Debug, Clone, Default)]
+pub struct CrashSnapshot(Arc<Mutex<Option<Vec<u8>>>>);
+
+impl CrashSnapshot {
+    /// Replaces the snapshot with `game_state`. A state that fails to encode keeps the previous one.
+    pub fn update(&self, game_state: &GameState) {
+        if let Ok(json) = save::encode_save(game_state, "crash", save::unix_now()) {
+            *self.0.lock().unwrap_or_else(PoisonError::into_inner) = Some(json);
+        }
+    }
+
+    fn json(&self) -> Option<Vec<u8>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+}
+
+/// Writes the snapshot to `crash_<unix time>` in `save_dir`. Returns the file written, or
+/// `None` when there is no snapshot yet, the thread is already writing one or writing failed.
+pub fn emergency_save(snapshot: &CrashSnapshot, save_dir: &Path) -> Option<PathBuf> {
+    if SAVING.replace(true) {
+        return None;
+    }
+    let written = snapshot.json().and_then(|json| {
+        let path = save::save_path(save_dir, &format!("{}{}", CRASH_SAVE_PREFIX, save::unix_now())).ok()?;
+        fs::create_dir_all(save_dir).ok()?;
+        fs::write(&path, json).ok()?;
+        Some(path)
+    });
+    SAVING.set(false);
+    written
+}
+
+/// Makes every panic from here on write an emergency save of `snapshot` to `save_dir` after the
+/// usual panic message, while the stack is still unwinding.
+pub fn install_crash_hook(snapshot: CrashSnapshot, save_dir: PathBuf) {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        previous(info);
+        if let Some(path) = emergency_save(&snapshot, &save_dir) {
+            eprintln!("Progress up to the last command was saved to {}.", path.display());
+            eprintln!("The game offers to resume it when it starts next.");
+        }
+    }));
+}
+
+/// Crash saves in `save_dir` that can be read, newest first, by slot name.
+pub fn find_crash_saves(save_dir: &Path) -> Result<Vec<(String, SaveHeader)>, SaveError> {
+    Ok(save::list_saves(save_dir)?.into_iter()
+        .filter_map(|entry| {
+            let name = entry.file_name.strip_suffix(".json")?;
+            name.starts_with(CRASH_SAVE_PREFIX).then_some(())?;
+            Some((name.to_string(), entry.header.ok()?))
+        })
+        .collect())
+}
+
+/// Renames crash save `name` so it is not offered again, keeping it loadable under the new
+/// slot name, which is returned.
+pub fn mark_recovered(save_dir: &Path, name: &str) -> Result<String, SaveError> {
+    let recovered = name.replacen(CRASH_SAVE_PREFIX, RECOVERED_SAVE_PREFIX, 1);
+    fs::rename(save::save_path(save_dir, name)?, save::save_path(save_dir, &recovered)?)?;
+    Ok(recovered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{self, fixture_game};
+
+    #[test]
+    fn test_emergency_save_writes_the_snapshot_not_the_live_state() {
+        let save_dir = test_support::temp_dir("crash_snapshot");
+        let snapshot = CrashSnapshot::default();
+        assert_eq!(emergency_save(&snapshot, &save_dir), None, "Nothing to save before the first snapshot");
+
+        let mut state = fixture_game(1);
+        snapshot.update(&state);
+        state.turn = 9;
+
+        let path = emergency_save(&snapshot, &save_dir).unwrap();
+        assert_eq!(save::load_game(&path).unwrap().turn, 1);
+        let crashes = find_crash_saves(&save_dir).unwrap();
+        assert_eq!(crashes.len(), 1);
+        assert!(path.ends_with(format!("{}.json", crashes[0].0)));
+
+        let recovered = mark_recovered(&save_dir, &crashes[0].0).unwrap();
+        assert!(recovered.starts_with(RECOVERED_SAVE_PREFIX));
+        assert!(find_crash_saves(&save_dir).unwrap().is_empty());
+        assert!(save::save_path(&save_dir, &recovered).unwrap().exists());
+    }
+}
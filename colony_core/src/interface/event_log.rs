@@ -0,0 +1,346 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::engine::game_event::GameEvent;
+use crate::engine::player::PlayerId;
+
+/// Version of the line format, raised whenever a field or event type changes incompatibly.
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// One line of the event log. The event is an object tagged with its variant name in `type`,
+/// e.g. `{"schema_version":1,"turn":3,"player":"alice","event":{"type":"TurnEnded",...}}`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EventRecord {
+    pub schema_version: u32,
+    /// Turn on which the command that produced the event was given
+    pub turn: u32,
+    /// Player whose command produced the event
+    pub player: PlayerId,
+    pub event: GameEvent,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum EventLogError {
+    #[error("Event log {path} is not writable: {source}")]
+    Io { path: PathBuf, source: io::Error },
+
+    #[error("Event log {path} line {line} is not a valid event: {source}")]
+    Parse { path: PathBuf, line: usize, source: serde_json::Error },
+}
+
+/// Appends game events to a file, one JSON line per event, for external tools such as
+/// visualizers. Each line is flushed as soon as it is written so readers can follow along.
+pub struct EventLog {
+    file: File,
+    path: PathBuf,
+}
+
+impl EventLog {
+    /// Opens `path` for appending, creating it if needed.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, EventLogError> {
+        let path = path.into();
+        let file = OpenOptions::new().create(true).append(true).open(&path)
+            .map_err(|source| EventLogError::Io { path: path.clone(), source })?;
+        Ok(EventLog { file, path })
+    }
+
+    pub fn append(&mut self, turn: u32, player: &PlayerId, events: &[GameEvent]) -> Result<(), EventLogError> {
+        for event in events {
+            let record = EventRecord {
+                schema_version: EVENT_SCHEMA_VERSION,
+                turn,
+                player: player.clone(),
+                event: event.clone(),
+            };
+            let mut line = serde_json::to_string(&record).expect("Game events always serialize");
+            line.push('\n');
+            self.file.write_all(line.as_bytes())
+                .and_then(|()| self.file.flush())
+                .map_err(|source| EventLogError::Io { path: self.path.clone(), source })?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads every record of an event log, skipping blank lines.
+pub fn read_event_log(path: &Path) -> Result<Vec<EventRecord>, EventLogError> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|source| EventLogError::Io { path: path.to_path_buf(), source })?;
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .map_err(|source| EventLogError::Parse { path: path.to_path_buf(), line: index + 1, source })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::fleet::FleetTemplate;
+    use crate::engine::game_event::{ArrivalKind, VictoryKind};
+    use crate::engine::game_state::{Alert, FleetSighting, FleetSizeClass, ObservedSize, ShieldEstimate};
+    use crate::engine::planet::{Allocation, RallyPoint, StorageAdvice, StorageWarning, StructureHit};
+    use crate::engine::resources::Resources;
+    use crate::test_support;
+
+    /// The `type` tag external tools see for each event. The match has no catch-all, so a new
+    /// event cannot be added without deciding its tag and covering it in `sample_events`.
+    fn tag(event: &GameEvent) -> &'static str {
+        match event {
+            GameEvent::Info { .. } => "Info",
+            GameEvent::StructureQueued { .. } => "StructureQueued",
+            GameEvent::UpgradeQueued { .. } => "UpgradeQueued",
+            GameEvent::ShipQueued { .. } => "ShipQueued",
+            GameEvent::ShipRetrofitQueued { .. } => "ShipRetrofitQueued",
+            GameEvent::ActionCancelled { .. } => "ActionCancelled",
+            GameEvent::FleetCreated { .. } => "FleetCreated",
+            GameEvent::ShipsAddedToFleet { .. } => "ShipsAddedToFleet",
+            GameEvent::ShipsRemovedFromFleet { .. } => "ShipsRemovedFromFleet",
+            GameEvent::FleetDisbanded { .. } => "FleetDisbanded",
+            GameEvent::FleetsMerged { .. } => "FleetsMerged",
+            GameEvent::FleetSplit { .. } => "FleetSplit",
+            GameEvent::FleetTemplateSaved { .. } => "FleetTemplateSaved",
+            GameEvent::StructureQueuedOnPlanets { .. } => "StructureQueuedOnPlanets",
+            GameEvent::FleetTemplateQueued { .. } => "FleetTemplateQueued",
+            GameEvent::RallyPointSet { .. } => "RallyPointSet",
+            GameEvent::ShipsGarrisoned { .. } => "ShipsGarrisoned",
+            GameEvent::ShipsUngarrisoned { .. } => "ShipsUngarrisoned",
+            GameEvent::AllocationSet { .. } => "AllocationSet",
+            GameEvent::ShieldOvercharged { .. } => "ShieldOvercharged",
+            GameEvent::StructurePowerChanged { .. } => "StructurePowerChanged",
+            GameEvent::PowerRationed { .. } => "PowerRationed",
+            GameEvent::GatesLinked { .. } => "GatesLinked",
+            GameEvent::GateLinkCollapsed { .. } => "GateLinkCollapsed",
+            GameEvent::AliasDefined { .. } => "AliasDefined",
+            GameEvent::AliasRemoved { .. } => "AliasRemoved",
+            GameEvent::WorkersStrike { .. } => "WorkersStrike",
+            GameEvent::FleetAttrition { .. } => "FleetAttrition",
+            GameEvent::FleetResupplied { .. } => "FleetResupplied",
+            GameEvent::NoteAdded { .. } => "NoteAdded",
+            GameEvent::NoteDone { .. } => "NoteDone",
+            GameEvent::GameOptionChanged { .. } => "GameOptionChanged",
+            GameEvent::RallyPointBroken { .. } => "RallyPointBroken",
+            GameEvent::FleetMoveOrdered { .. } => "FleetMoveOrdered",
+            GameEvent::FleetsMoveOrdered { .. } => "FleetsMoveOrdered",
+            GameEvent::ShipMoveOrdered { .. } => "ShipMoveOrdered",
+            GameEvent::BombardmentStarted { .. } => "BombardmentStarted",
+            GameEvent::BombardmentCancelled { .. } => "BombardmentCancelled",
+            GameEvent::FleetSentrySet { .. } => "FleetSentrySet",
+            GameEvent::CargoLoaded { .. } => "CargoLoaded",
+            GameEvent::CargoUnloaded { .. } => "CargoUnloaded",
+            GameEvent::CargoLost { .. } => "CargoLost",
+            GameEvent::PlanetColonized { .. } => "PlanetColonized",
+            GameEvent::RuinClaimed { .. } => "RuinClaimed",
+            GameEvent::ColonizationFailed { .. } => "ColonizationFailed",
+            GameEvent::ColonizationPaid { .. } => "ColonizationPaid",
+            GameEvent::ColonizationRefunded { .. } => "ColonizationRefunded",
+            GameEvent::TurnEnded { .. } => "TurnEnded",
+            GameEvent::DevAction { .. } => "DevAction",
+            GameEvent::WaitingOn { .. } => "WaitingOn",
+            GameEvent::RoundProcessing { .. } => "RoundProcessing",
+            GameEvent::ShieldsDamaged { .. } => "ShieldsDamaged",
+            GameEvent::ShieldsDestroyed { .. } => "ShieldsDestroyed",
+            GameEvent::BombardmentOverflow { .. } => "BombardmentOverflow",
+            GameEvent::StructuresBombarded { .. } => "StructuresBombarded",
+            GameEvent::BombardmentTargetMissing { .. } => "BombardmentTargetMissing",
+            GameEvent::StructureDestroyed { .. } => "StructureDestroyed",
+            GameEvent::ConstructionCompleted { .. } => "ConstructionCompleted",
+            GameEvent::ConstructionFailed { .. } => "ConstructionFailed",
+            GameEvent::ConstructionHalted { .. } => "ConstructionHalted",
+            GameEvent::ConstructionRejected { .. } => "ConstructionRejected",
+            GameEvent::ActionOrphaned { .. } => "ActionOrphaned",
+            GameEvent::UpgradeCompleted { .. } => "UpgradeCompleted",
+            GameEvent::UpgradeFailed { .. } => "UpgradeFailed",
+            GameEvent::UpgradeRepeated { .. } => "UpgradeRepeated",
+            GameEvent::UpgradeRepeatStopped { .. } => "UpgradeRepeatStopped",
+            GameEvent::ShipBuilt { .. } => "ShipBuilt",
+            GameEvent::ShipRetrofitted { .. } => "ShipRetrofitted",
+            GameEvent::ShipRetrofitFailed { .. } => "ShipRetrofitFailed",
+            GameEvent::FleetArrived { .. } => "FleetArrived",
+            GameEvent::ShipArrived { .. } => "ShipArrived",
+            GameEvent::Battle { .. } => "Battle",
+            GameEvent::GarrisonAssault { .. } => "GarrisonAssault",
+            GameEvent::BombardmentIntercepted { .. } => "BombardmentIntercepted",
+            GameEvent::Victory { .. } => "Victory",
+            GameEvent::DominationWarning { .. } => "DominationWarning",
+            GameEvent::SlowRound { .. } => "SlowRound",
+            GameEvent::EngineWarning { .. } => "EngineWarning",
+            GameEvent::TurnBegan { .. } => "TurnBegan",
+            GameEvent::BombardmentObserved { .. } => "BombardmentObserved",
+            GameEvent::ShieldsDownObserved { .. } => "ShieldsDownObserved",
+            GameEvent::FleetDetected { .. } => "FleetDetected",
+            GameEvent::PlayerTurnStarted { .. } => "PlayerTurnStarted",
+        }
+    }
+
+    /// One event of every type, with every nested value filled in.
+    fn sample_events() -> Vec<GameEvent> {
+        let s = String::from;
+        let cost = Resources { minerals: 100, gas: 50, energy: 5 };
+        let warnings = vec![StorageWarning { resource: "gas", turns_until_full: 2 }];
+        let sighting = FleetSighting { owner_name: s("Bob"), planet_name: s("Kepler"), size: ObservedSize::Class(FleetSizeClass::Small) };
+        let template = FleetTemplate { ships: [(s("interceptor"), 2)].into_iter().collect() };
+
+        vec![
+            GameEvent::Info { message: s("Turn 3") },
+            GameEvent::StructureQueued {
+                structure_id: s("power_grid"),
+                cost: cost.clone(),
+                turns: 2,
+                warnings: warnings.clone(),
+                advice: vec![StorageAdvice { resource: "minerals", production: 50, headroom: 10, storage_structure: Some(s("Storage Complex")) }],
+            },
+            GameEvent::UpgradeQueued { structure_id: s("power_grid"), cost: cost.clone(), turns: 3, warnings: warnings.clone() },
+            GameEvent::ShipQueued { ship_id: s("scout"), ship_instance_id: s("scout_1"), cost: cost.clone(), turns: 1, warnings: warnings.clone(), delivery_planet: Some(s("Vega")) },
+            GameEvent::ShipRetrofitQueued { ship_instance_id: s("scout_1"), from_type: s("scout"), to_type: s("ravager"), cost: cost.clone(), turns: 2, warnings },
+            GameEvent::ActionCancelled { planet_name: s("Kepler"), refunded: cost.clone(), penalty: Resources::default(), penalty_percent: 10, wasted: Some(cost.clone()) },
+            GameEvent::FleetCreated { fleet_id: s("fleet_1"), name: s("Strike"), ship_count: 2 },
+            GameEvent::ShipsAddedToFleet { fleet_name: s("Strike"), ship_count: 1 },
+            GameEvent::ShipsRemovedFromFleet { fleet_name: s("Strike"), ship_count: 1 },
+            GameEvent::FleetDisbanded { fleet_name: s("Strike"), ship_count: 2 },
+            GameEvent::FleetsMerged { into_name: s("Strike"), from_name: s("Reserve"), ship_count: 4 },
+            GameEvent::FleetSplit { fleet_id: s("fleet_2"), name: s("Wing"), source_name: s("Strike"), ship_count: 1 },
+            GameEvent::FleetTemplateSaved { name: s("raid"), template, replaced: false },
+            GameEvent::StructureQueuedOnPlanets { structure_name: s("Mining Complex"), results: vec![(s("Kepler"), None), (s("Vega"), Some(s("already built")))] },
+            GameEvent::FleetTemplateQueued { template_name: s("raid"), planet_name: s("Kepler"), queued: 1, skipped: vec![(s("ravager"), s("shipyard too low"))] },
+            GameEvent::RallyPointSet { planet_name: s("Kepler"), rally_point: Some(RallyPoint::NewFleet(s("Home Guard"))) },
+            GameEvent::ShipsGarrisoned { planet_name: s("Kepler"), ship_count: 2 },
+            GameEvent::ShipsUngarrisoned { planet_name: s("Kepler"), ship_count: 1 },
+            GameEvent::AllocationSet {
+                planet_name: s("Kepler"),
+                allocation: Some(Allocation { minerals: 60, gas: 20, energy: 20 }),
+                production: Resources { minerals: 55, gas: 20, energy: 10 },
+            },
+            GameEvent::ShieldOvercharged { planet_name: s("Kepler"), added: 50, shield_hp: 150, energy_spent: 40 },
+            GameEvent::StructurePowerChanged {
+                planet_name: s("Kepler"), structure_name: s("Gas Refinery"), on: false, energy: 15,
+            },
+            GameEvent::PowerRationed {
+                player_id: s("alice"), planet_name: s("Kepler"), shut_down: vec![s("Gas Refinery")], restarted: vec![],
+            },
+            GameEvent::GatesLinked { first_name: s("Kepler"), second_name: s("Vega"), distance: 1 },
+            GameEvent::GateLinkCollapsed { first_name: s("Kepler"), second_name: s("Vega"), reason: s("the jump gate on Vega is down") },
+            GameEvent::AliasDefined { name: s("b"), expansion: s("build"), replaced: true },
+            GameEvent::AliasRemoved { name: s("b") },
+            GameEvent::WorkersStrike { player_id: s("bob"), planet_name: s("Vega"), unrest: 60 },
+            GameEvent::FleetAttrition { player_id: s("alice"), fleet_name: s("Raiders"), hops: Some(5), ships_worn: 2, shield_lost: 10 },
+            GameEvent::FleetResupplied { player_id: s("alice"), fleet_name: s("Raiders"), shield_restored: 10 },
+            GameEvent::NoteAdded { number: 2 },
+            GameEvent::NoteDone { number: 1, remaining: 1 },
+            GameEvent::GameOptionChanged { player_name: s("Alice"), key: s("autosave"), value: s("every 2 rounds") },
+            GameEvent::RallyPointBroken { player_id: s("alice"), planet_name: s("Kepler"), fleet_id: s("fleet_1"), reason: s("fleet left") },
+            GameEvent::FleetMoveOrdered { fleet_id: s("fleet_1"), fleet_name: s("Strike"), source_name: s("Kepler"), target_name: s("Vega"), turns: 2 },
+            GameEvent::FleetsMoveOrdered { target_name: s("Vega"), results: vec![(s("fleet_1"), None), (s("fleet_2"), Some(s("fleet has no ships")))] },
+            GameEvent::ShipMoveOrdered { ship_instance_id: s("ark_1"), ship_type: s("ark"), source_name: s("Kepler"), target_name: s("Vega"), turns: 1 },
+            GameEvent::BombardmentStarted { fleet_id: s("fleet_1"), fleet_name: s("Strike"), target_name: s("Vega"), bombardment_power: 25, target_structure: Some(s("power_grid")) },
+            GameEvent::BombardmentCancelled { fleet_id: s("fleet_1") },
+            GameEvent::FleetSentrySet { fleet_id: s("fleet_1"), sentry: true },
+            GameEvent::CargoLoaded { fleet_id: s("fleet_1"), planet_name: s("Kepler"), cargo: cost.clone(), carried: 150, capacity: 200 },
+            GameEvent::CargoUnloaded { player_id: s("alice"), fleet_id: s("fleet_1"), planet_name: s("Vega"), stored: cost.clone(), wasted: Resources::default() },
+            GameEvent::CargoLost { player_id: s("alice"), fleet_name: s("Strike"), lost: cost.clone() },
+            GameEvent::PlanetColonized { fleet_id: s("fleet_1"), planet_name: s("Vega"), player_name: s("Alice"), ark_bonus: cost.clone() },
+            GameEvent::RuinClaimed { planet_name: s("Vega"), description: s("abandoned Storage Complex (Lv1)") },
+            GameEvent::ColonizationFailed { planet_name: s("Vega"), reason: s("no ark") },
+            GameEvent::ColonizationPaid { planet_name: s("Vega"), origin_name: s("Sol"), cost: cost.clone() },
+            GameEvent::ColonizationRefunded { planet_name: s("Vega"), origin_name: s("Sol"), refunded: cost.clone() },
+            GameEvent::TurnEnded { player_name: s("Alice") },
+            GameEvent::DevAction { player_name: s("Alice"), description: s("set the turn to 40") },
+            GameEvent::WaitingOn { turn: 3, player_names: vec![s("Bob")] },
+            GameEvent::RoundProcessing { turn: 3 },
+            GameEvent::ShieldsDamaged { player_id: s("alice"), fleet_id: s("fleet_1"), planet_name: s("Vega"), shields_before: 100, shields_after: 75 },
+            GameEvent::ShieldsDestroyed { player_id: s("alice"), fleet_id: s("fleet_1"), planet_name: s("Vega"), shields_before: 25, overflow: 5 },
+            GameEvent::BombardmentOverflow { player_id: s("carol"), fleet_id: s("fleet_2"), planet_name: s("Vega"), damage: 30 },
+            GameEvent::StructuresBombarded {
+                player_id: s("carol"),
+                fleet_id: s("fleet_2"),
+                planet_name: s("Vega"),
+                hits: vec![StructureHit { structure_id: s("power_grid"), damage: 30, destroyed: true }],
+            },
+            GameEvent::BombardmentTargetMissing { player_id: s("carol"), fleet_id: s("fleet_2"), planet_name: s("Vega"), structure_id: s("jump_gate") },
+            GameEvent::StructureDestroyed { player_id: s("bob"), planet_name: s("Vega"), structure_id: s("power_grid"), attacker_name: s("Carol") },
+            GameEvent::ConstructionCompleted { player_id: s("alice"), structure_id: s("power_grid"), planet_name: s("Kepler") },
+            GameEvent::ConstructionFailed { player_id: s("alice"), structure_id: s("power_grid"), planet_name: s("Kepler"), reason: s("planet lost") },
+            GameEvent::ConstructionHalted { player_id: s("alice"), structure_id: s("mining_complex"), planet_name: s("Kepler"), awaiting: s("storage_complex"), required_level: 1, turns: 3 },
+            GameEvent::ConstructionRejected { player_id: s("alice"), structure_id: s("power_grid"), planet_name: s("Kepler"), reason: s("limit reached"), refunded: cost.clone() },
+            GameEvent::ActionOrphaned { player_id: s("bob"), action: s("build of power_grid on Kepler"), reason: s("planet Kepler is no longer yours"), refund: None },
+            GameEvent::UpgradeCompleted { player_id: s("alice"), structure_id: s("power_grid"), planet_name: s("Kepler") },
+            GameEvent::UpgradeFailed { player_id: s("alice"), structure_id: s("power_grid"), planet_name: s("Kepler"), reason: s("planet lost") },
+            GameEvent::UpgradeRepeated { player_id: s("alice"), structure_id: s("power_grid"), planet_name: s("Kepler"), cost, turns: 2 },
+            GameEvent::UpgradeRepeatStopped { player_id: s("alice"), structure_id: s("power_grid"), planet_name: s("Kepler"), reason: s("max level") },
+            GameEvent::ShipBuilt { player_id: s("alice"), ship_instance_id: s("scout_1"), ship_type: s("scout"), planet_name: s("Kepler"), fleet_name: Some(s("Home Guard")) },
+            GameEvent::ShipRetrofitted { player_id: s("alice"), ship_instance_id: s("scout_1"), from_type: s("scout"), to_type: s("ravager"), planet_name: s("Kepler") },
+            GameEvent::ShipRetrofitFailed { player_id: s("alice"), ship_instance_id: s("scout_1"), planet_name: s("Kepler"), reason: s("ship left") },
+            GameEvent::FleetArrived { player_id: s("alice"), fleet_id: s("fleet_1"), planet_name: s("Vega"), kind: ArrivalKind::UndefendedEnemy },
+            GameEvent::ShipArrived { player_id: s("alice"), ship_instance_id: s("ark_1"), planet_name: s("Vega"), kind: ArrivalKind::Neutral },
+            GameEvent::Battle {
+                fleet_id: s("fleet_1"), planet_name: s("Vega"), attacker_name: s("Alice"), defender_name: s("Bob"),
+                attacker_strength: 30, defender_strength: 20, attacker_wins: true, ships_destroyed: 3,
+            },
+            GameEvent::GarrisonAssault {
+                fleet_id: s("fleet_1"), planet_name: s("Vega"), attacker_name: s("Alice"), defender_name: s("Bob"),
+                attacker_strength: 10, defender_strength: 20, attacker_wins: false, ships_destroyed: 2,
+            },
+            GameEvent::BombardmentIntercepted { player_id: s("alice"), fleet_id: s("fleet_1"), planet_name: s("Vega"), intercepted: 10 },
+            GameEvent::Victory { player_names: vec![s("Alice"), s("Carol")], turn: 12, kind: VictoryKind::Elimination },
+            GameEvent::Victory {
+                player_names: vec![s("Bob")],
+                turn: 14,
+                kind: VictoryKind::Domination { held: 3, total: 5, turns: 5 },
+            },
+            GameEvent::DominationWarning { player_name: s("Bob"), held: 3, total: 5, turns_left: 2 },
+            GameEvent::SlowRound { turn: 4, elapsed_ms: 1250, threshold_ms: 1000 },
+            GameEvent::EngineWarning { message: s("current_player() called during production processing") },
+            GameEvent::TurnBegan { turn: 4, protection_turns_left: 2 },
+            GameEvent::BombardmentObserved { observer_id: s("bob"), attacker_name: s("Alice"), planet_name: s("Vega"), shields: ShieldEstimate::Weakened },
+            GameEvent::ShieldsDownObserved { observer_id: s("bob"), attacker_name: s("Alice"), planet_name: s("Vega"), own_planet: true },
+            GameEvent::FleetDetected { observer_id: s("bob"), observer_name: s("Bob"), sighting: sighting.clone() },
+            GameEvent::PlayerTurnStarted {
+                player_name: s("Bob"),
+                alerts: vec![
+                    Alert::StorageNearlyFull { planet_name: s("Kepler"), resource: "minerals", percent: 96 },
+                    Alert::EnergyDeficit { planet_name: s("Kepler"), balance: -5 },
+                ],
+                sightings: vec![FleetSighting { size: ObservedSize::Exact(3), ..sighting }],
+                notes: 2,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_every_event_type_round_trips_with_a_stable_tag() {
+        for event in sample_events() {
+            let record = EventRecord { schema_version: EVENT_SCHEMA_VERSION, turn: 3, player: String::from("alice"), event };
+            let line = serde_json::to_string(&record).unwrap();
+
+            let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+            assert_eq!(value["event"]["type"], tag(&record.event), "{line}");
+            assert_eq!(value["schema_version"], EVENT_SCHEMA_VERSION, "{line}");
+            assert_eq!(serde_json::from_str::<EventRecord>(&line).unwrap(), record, "{line}");
+        }
+    }
+
+    #[test]
+    fn test_unknown_resource_name_is_rejected() {
+        let line = r#"{"resource":"plasma","turns_until_full":1}"#;
+        assert!(serde_json::from_str::<StorageWarning>(line).is_err());
+    }
+
+    #[test]
+    fn test_log_appends_one_line_per_event() {
+        let path = test_support::temp_dir("event_log_append").join("events.jsonl");
+        let events = sample_events();
+
+        let mut log = EventLog::open(&path).unwrap();
+        log.append(1, &String::from("alice"), &events[..2]).unwrap();
+        drop(log);
+        EventLog::open(&path).unwrap().append(2, &String::from("bob"), &events[2..3]).unwrap();
+
+        let records = read_event_log(&path).unwrap();
+        let summary: Vec<_> = records.iter().map(|record| (record.turn, record.player.as_str(), &record.event)).collect();
+        assert_eq!(summary, [(1, "alice", &events[0]), (1, "alice", &events[1]), (2, "bob", &events[2])]);
+    }
+}
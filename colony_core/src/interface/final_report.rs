@@ -0,0 +1,307 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::engine::game_event::VictoryKind;
+use crate::engine::game_state::{ActionOutcome, GameState, Highlight};
+use crate::engine::player::{BombardmentStats, Player};
+use crate::engine::resources::Resources;
+
+/// Score for every planet held at the end
+const PLANET_POINTS: u32 = 100;
+/// Score for every structure level built
+const STRUCTURE_LEVEL_POINTS: u32 = 10;
+/// Score for every ship still in service
+const SHIP_POINTS: u32 = 5;
+/// Score for every battle won, attacking or defending
+const BATTLE_WON_POINTS: u32 = 25;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReportError {
+    #[error("Report {path} could not be written: {source}")]
+    Io { path: PathBuf, source: io::Error },
+}
+
+/// Everything worth keeping once a game is over: who won, how long it took, how every player
+/// ended up and the highlights along the way. Taken before the end it is an interim report.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FinalReport {
+    pub game_over: bool,
+    /// Full turns played; the turn a victory came on counts as played
+    pub turns_played: u32,
+    pub winner: Option<Winner>,
+    /// Highest score first
+    pub players: Vec<PlayerReport>,
+    pub timeline: Vec<TimelineEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Winner {
+    pub player_names: Vec<String>,
+    pub kind: VictoryKind,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PlayerReport {
+    pub name: String,
+    pub eliminated: bool,
+    pub planets: usize,
+    /// Levels of every structure on the player's planets, added up
+    pub structure_levels: u32,
+    pub ships: usize,
+    pub fleets: usize,
+    pub production: Resources,
+    pub orders: usize,
+    pub rejected_orders: usize,
+    pub battles_won: usize,
+    pub battles_lost: usize,
+    /// Damage this player's bombardments dealt and the shields they broke
+    #[serde(default)]
+    pub bombardment: BombardmentStats,
+    pub score: ScoreBreakdown,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ScoreBreakdown {
+    pub planets: u32,
+    pub structures: u32,
+    pub ships: u32,
+    pub battles: u32,
+    pub total: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TimelineEntry {
+    pub turn: u32,
+    pub highlight: Highlight,
+}
+
+/// Builds the report from the game state and its action log. Works at any point of the game;
+/// before a victory it describes the game so far.
+pub fn final_report(game_state: &GameState) -> FinalReport {
+    let highlights = game_state.highlights();
+    let winner = highlights.iter().rev().find_map(|(_, highlight)| match highlight {
+        Highlight::Victory { player_names, kind } => Some(Winner {
+            player_names: player_names.clone(),
+            kind: kind.clone(),
+        }),
+        _ => None,
+    });
+
+    // Players taken out of the rotation follow the others, by id
+    let out_of_rotation: Vec<_> = game_state.players.keys()
+        .filter(|player_id| !game_state.players_order.contains(player_id))
+        .collect();
+    let mut players: Vec<_> = game_state.players_order.iter()
+        .chain(out_of_rotation)
+        .filter_map(|player_id| game_state.players.get(player_id))
+        .map(|player| player_report(game_state, player))
+        .collect();
+    // Stable, so tied players stay in turn order
+    players.sort_by_key(|player| std::cmp::Reverse(player.score.total));
+
+    FinalReport {
+        game_over: game_state.is_over(),
+        turns_played: if game_state.is_over() { game_state.turn } else { game_state.turn.saturating_sub(1) },
+        winner,
+        players,
+        timeline: highlights.into_iter()
+            .map(|(turn, highlight)| TimelineEntry { turn, highlight: highlight.clone() })
+            .collect(),
+    }
+}
+
+fn player_report(game_state: &GameState, player: &Player) -> PlayerReport {
+    let planets: Vec<_> = player.planets.iter().filter_map(|id| game_state.map.planets.get(id)).collect();
+    let structure_levels = planets.iter()
+        .flat_map(|planet| planet.get_structures().values())
+        .map(|structure| u32::from(structure.level))
+        .sum();
+    let mut production = Resources::default();
+    for planet in &planets {
+        production += planet.get_production_rate();
+    }
+
+    let orders: Vec<_> = game_state.action_log.iter().filter(|entry| entry.player_id == player.id).collect();
+    let (mut battles_won, mut battles_lost) = (0, 0);
+    for highlight in game_state.action_log.iter().flat_map(|entry| &entry.highlights) {
+        if let Highlight::Battle { attacker_name, defender_name, attacker_wins, .. } = highlight {
+            if (attacker_name == &player.name && *attacker_wins) || (defender_name == &player.name && !attacker_wins) {
+                battles_won += 1;
+            } else if attacker_name == &player.name || defender_name == &player.name {
+                battles_lost += 1;
+            }
+        }
+    }
+
+    let mut score = ScoreBreakdown {
+        planets: PLANET_POINTS * planets.len() as u32,
+        structures: STRUCTURE_LEVEL_POINTS * structure_levels,
+        ships: SHIP_POINTS * player.ships.len() as u32,
+        battles: BATTLE_WON_POINTS * battles_won as u32,
+        total: 0,
+    };
+    score.total = score.planets + score.structures + score.ships + score.battles;
+
+    PlayerReport {
+        name: player.name.clone(),
+        eliminated: player.planets.is_empty(),
+        planets: planets.len(),
+        structure_levels,
+        ships: player.ships.len(),
+        fleets: player.fleets.len(),
+        production,
+        orders: orders.len(),
+        rejected_orders: orders.iter().filter(|entry| matches!(entry.outcome, ActionOutcome::Rejected { .. })).count(),
+        battles_won,
+        battles_lost,
+        bombardment: player.bombardment,
+        score,
+    }
+}
+
+impl fmt::Display for FinalReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.game_over {
+            writeln!(f, "=== Final Report ===")?;
+        } else {
+            writeln!(f, "=== Interim Report ===")?;
+        }
+        match &self.winner {
+            Some(winner) => writeln!(f, "Winner: {} ({})", winner.player_names.join(" & "), winner.kind)?,
+            None => writeln!(f, "Winner: none yet")?,
+        }
+        writeln!(f, "Turns played: {}", self.turns_played)?;
+
+        let width = self.players.iter()
+            .map(|player| player.name.chars().count() + if player.eliminated { 2 } else { 0 })
+            .max().unwrap_or(0).max(6);
+        writeln!(f, "\nPLAYERS")?;
+        writeln!(
+            f, "  {:<width$}  {:>7}  {:>10}  {:>5}  {:>6}  {:>14}  {:>15}  {:>7}  {:>16}",
+            "Player", "Planets", "Structures", "Ships", "Fleets", "Production", "Orders (reject)", "Battles",
+            "Bombard (broke)"
+        )?;
+        for player in &self.players {
+            let name = if player.eliminated { format!("{} †", player.name) } else { player.name.clone() };
+            let production = format!(
+                "{}/{}/{}", player.production.minerals, player.production.gas, player.production.energy
+            );
+            let bombardment = &player.bombardment;
+            writeln!(
+                f, "  {:<width$}  {:>7}  {:>10}  {:>5}  {:>6}  {:>14}  {:>15}  {:>7}  {:>16}",
+                name, player.planets, player.structure_levels, player.ships, player.fleets, production,
+                format!("{} ({})", player.orders, player.rejected_orders),
+                format!("{}-{}", player.battles_won, player.battles_lost),
+                format!(
+                    "{} ({})",
+                    bombardment.shield_damage.saturating_add(bombardment.overflow_damage), bombardment.shields_broken,
+                ),
+            )?;
+        }
+
+        writeln!(f, "\nSCORE")?;
+        writeln!(
+            f, "  {:<width$}  {:>7}  {:>10}  {:>5}  {:>7}  {:>6}",
+            "Player", "Planets", "Structures", "Ships", "Battles", "Total"
+        )?;
+        for player in &self.players {
+            let score = &player.score;
+            writeln!(
+                f, "  {:<width$}  {:>7}  {:>10}  {:>5}  {:>7}  {:>6}",
+                player.name, score.planets, score.structures, score.ships, score.battles, score.total
+            )?;
+        }
+
+        writeln!(f, "\nTIMELINE")?;
+        if self.timeline.is_empty() {
+            writeln!(f, "  (nothing memorable happened)")?;
+        }
+        for entry in &self.timeline {
+            writeln!(f, "  Turn {:>3}  {}", entry.turn, entry.highlight)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes `report` to `path`, as JSON when the file name ends in `.json` and as the text shown
+/// in game otherwise.
+pub fn export_report(report: &FinalReport, path: &Path) -> Result<(), ReportError> {
+    let is_json = path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+    let contents = if is_json {
+        serde_json::to_string_pretty(report).expect("Reports always serialize")
+    } else {
+        report.to_string()
+    };
+    std::fs::write(path, contents).map_err(|source| ReportError::Io { path: path.to_path_buf(), source })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::commands::command::CommandEffect;
+    use crate::test_support::{self, fixture_game};
+
+    /// Alice takes over every planet; bob ends the round, which alice wins.
+    fn won_game() -> GameState {
+        let mut state = fixture_game(1);
+        let bob_home = test_support::home_planet(&state, "bob");
+        state.players.get_mut("bob").unwrap().planets.clear();
+        let alice_planets = state.map.planets.keys().cloned().collect();
+        state.players.get_mut("alice").unwrap().planets = alice_planets;
+        state.map.planets.get_mut(&bob_home).unwrap().set_owner(String::from("alice"));
+
+        for (player_id, name) in [("alice", "Alice"), ("bob", "Bob")] {
+            let player_id = String::from(player_id);
+            let turn = state.turn;
+            let effect = CommandEffect::EndTurn { player_name: String::from(name) };
+            let events = state.apply_effect(effect, &player_id).unwrap();
+            state.record_action(&player_id, turn, "end_turn", ActionOutcome::Applied, &events);
+        }
+        assert!(state.is_over());
+        state
+    }
+
+    #[test]
+    fn test_winner_matches_victory_checker() {
+        let state = won_game();
+        let expected: Vec<_> = state.winning_side().unwrap().iter()
+            .map(|player_id| state.players[player_id].name.clone())
+            .collect();
+
+        let report = final_report(&state);
+
+        let winner = report.winner.as_ref().expect("A won game must name its winner");
+        assert_eq!(winner.player_names, expected);
+        assert_eq!(winner.kind, VictoryKind::Elimination);
+        assert_eq!(report.turns_played, 1);
+        assert_eq!(report.players[0].name, "Alice");
+        assert!(report.players[1].eliminated);
+        assert!(report.to_string().contains("Winner: Alice (conquered the entire system)"));
+    }
+
+    #[test]
+    fn test_json_report_round_trips() {
+        let report = final_report(&won_game());
+        let path = test_support::temp_dir("final_report").join("report.json");
+
+        export_report(&report, &path).unwrap();
+        let read: FinalReport = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+
+        assert_eq!(read, report);
+    }
+
+    #[test]
+    fn test_interim_report_has_no_winner() {
+        let mut state = fixture_game(1);
+        state.turn = 3;
+
+        let report = final_report(&state);
+
+        assert!(!report.game_over);
+        assert_eq!(report.winner, None);
+        assert_eq!(report.turns_played, 2);
+        assert_eq!(report.players.len(), 2);
+        assert!(report.to_string().starts_with("=== Interim Report ===\nWinner: none yet"));
+    }
+}
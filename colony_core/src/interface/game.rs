@@ -0,0 +1,1175 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use rand::Rng;
+
+use crate::engine::clock::SystemClock;
+use crate::engine::commands::advisor;
+use crate::engine::commands::alias;
+use crate::engine::commands::command::{Command, CommandEffect, CommandError};
+use crate::engine::commands::completion;
+use crate::engine::commands::log as log_command;
+use crate::engine::commands::parser;
+use crate::engine::commands::save as save_command;
+use crate::engine::commands::status::{StatusArgs, StatusTarget};
+use crate::engine::configs::ship_config::ShipConfigError;
+use crate::engine::configs::structure_config::StructureConfigError;
+use crate::engine::game_state::{GamePhase, GameState, GameStateError, advise};
+use crate::engine::map::MapError;
+use crate::engine::options::{self, SessionOptions};
+use crate::engine::game_event::GameEvent;
+use crate::engine::planet::PlanetError;
+use crate::engine::planet_name_generator::PlanetNameGeneratorError;
+use crate::engine::player::PlayerId;
+use crate::engine::save::{self, AUTOSAVE_SLOT, QUICKSAVE_SLOT, SaveError};
+
+mod draft;
+
+use super::crash::{self, CrashSnapshot};
+use super::event_log::{EventLog, EventLogError};
+use super::final_report::{export_report, final_report};
+use super::game_configuration::{GameConfigurationError, GameConfiguration};
+use super::input::{InputError, InputSource, StdinSource, read_player_input, read_with_completion};
+use super::output::{OutputSink, OutputTarget, PlayerAppearance, StdoutSink, StyledSink};
+use super::replay::{GameSetup, Replay};
+use super::round_report::round_report;
+
+/// Turns at the start of a game during which the advisor speaks up on its own
+pub const ADVISOR_TURNS: u32 = 5;
+
+#[derive(Debug, thiserror::Error)]
+pub enum GameError {
+    #[error(transparent)]
+    GameConfigurationError(#[from] GameConfigurationError),
+
+    #[error(transparent)]
+    PlanetNameGeneratorError(#[from] PlanetNameGeneratorError),
+
+    #[error(transparent)]
+    MapError(#[from] MapError),
+
+    #[error(transparent)]
+    GameStateError(#[from] GameStateError),
+
+    #[error(transparent)]
+    CommandError(#[from] CommandError),
+
+    #[error(transparent)]
+    PlanetError(#[from] PlanetError),
+
+    #[error(transparent)]
+    StructureConfigError(#[from] StructureConfigError),
+
+    #[error(transparent)]
+    ShipConfigError(#[from] ShipConfigError),
+
+    #[error(transparent)]
+    InputError(#[from] InputError),
+
+    #[error(transparent)]
+    SaveError(#[from] SaveError),
+
+    #[error(transparent)]
+    EventLogError(#[from] EventLogError),
+
+    #[error("Player names collide on id '{0}'")]
+    DuplicatePlayer(PlayerId),
+}
+
+/// Whether the session continues after a command.
+enum SessionControl {
+    Continue,
+    Exit,
+}
+
+/// Answer to the unsaved progress prompt on exit.
+enum ExitChoice {
+    Save,
+    Discard,
+    Cancel,
+}
+
+pub struct Game {
+    pub(crate) game_state: GameState,
+    /// Set when an applied command changed the game state since the last save or load
+    dirty: bool,
+    save_dir: PathBuf,
+    compress_saves: bool,
+    terminal_width: Option<usize>,
+    /// Render style, advisor and confirmation prompts, as the `options` command changes them
+    options: SessionOptions,
+    /// Where applied commands' events are streamed for external tools, if anywhere
+    event_log: Option<EventLog>,
+    /// Whether galaxy news is broadcast when a round ends
+    round_report: bool,
+    /// What applied commands produced since the last round ended, for the galaxy news
+    round_events: Vec<GameEvent>,
+    /// Whether developer tools such as `inspect` are available
+    dev_mode: bool,
+    /// Commands recorded so far, when the session records a replay
+    replay: Option<Replay>,
+    /// Where the replay is written when the session ends
+    replay_path: Option<PathBuf>,
+    /// What the session's game was generated from; a rematch starts over from it on a fresh seed
+    setup: GameSetup,
+    /// The game as of the last command that changed it, for emergency saves
+    crash_snapshot: CrashSnapshot,
+}
+
+impl Game {
+    pub fn new(game_configuration: GameConfiguration) -> Result<Self, GameError> {
+        game_configuration.check_map_capacity()?;
+        // Every random choice of the setup comes from one seed, which a replay records
+        let seed = game_configuration.seed.unwrap_or_else(|| rand::rng().random());
+        let setup = GameSetup::from_configuration(&game_configuration, seed);
+        let mut game_state = setup.load_game()?;
+        game_state.started_at = Some(save::unix_now());
+        game_state.set_clock(Arc::new(SystemClock::new()));
+        Self::from_setup(game_configuration, setup, game_state)
+    }
+
+    /// A session over `game_state`, the game `setup` generates.
+    pub(crate) fn from_setup(
+        game_configuration: GameConfiguration,
+        setup: GameSetup,
+        game_state: GameState,
+    ) -> Result<Self, GameError> {
+        let event_log = game_configuration.event_log_path.as_deref().map(EventLog::open).transpose()?;
+        let replay = game_configuration.replay_path.as_ref().map(|_| Replay::new(setup.clone()));
+
+        Ok(
+            Game {
+                game_state,
+                dirty: false,
+                save_dir: game_configuration.save_dir,
+                compress_saves: game_configuration.compress_saves,
+                terminal_width: game_configuration.terminal_width,
+                options: SessionOptions {
+                    render_style: game_configuration.render_style,
+                    advisor: game_configuration.advisor,
+                    confirmations: true,
+                },
+                event_log,
+                round_report: game_configuration.round_report,
+                round_events: Vec::new(),
+                dev_mode: game_configuration.dev_mode,
+                replay,
+                replay_path: game_configuration.replay_path,
+                setup,
+                crash_snapshot: CrashSnapshot::default(),
+            }
+        )
+    }
+
+    /// Runs the command loop on plain stdin and stdout.
+    pub fn run(&mut self) -> Result<(), GameError> {
+        self.run_with(&mut StdinSource::stdin(), &mut StdoutSink)
+    }
+
+    /// Runs the command loop reading from `input`, such as a frontend's line editor,
+    /// which is offered command completions for the current player, and writing to `output`.
+    /// Scripted (non-interactive) sessions never prompt and discard unsaved progress on exit.
+    pub fn run_with(&mut self, input: &mut dyn InputSource, output: &mut dyn OutputSink) -> Result<(), GameError> {
+        output.emit(OutputTarget::Broadcast, "Initializing command interface...");
+        output.emit(OutputTarget::Broadcast, "Type 'help' for available commands\n");
+        output.players_changed(&self.player_appearances());
+
+        self.start(input, output)?;
+
+        loop {
+            let completer = |line: &str| {
+                completion::complete(&self.game_state, self.game_state.current_player(), &completion::tokenize(line))
+            };
+            let line = match read_with_completion(input, &completer, |line| Ok(String::from(line))) {
+                Ok(line) => line,
+                Err(InputError::EndOfInput) => break,
+                Err(error) => return Err(error.into()),
+            };
+            // Pressing enter on an empty prompt just asks again
+            if line.is_empty() {
+                continue;
+            }
+
+            let acting_player = self.game_state.current_player().clone();
+            if let SessionControl::Exit = self.execute_command(&line, input, output)? {
+                break;
+            }
+            if self.game_state.current_player() != &acting_player {
+                self.offer_advice(output);
+            }
+            if self.game_state.current_player() != &acting_player
+                && input.guards_turn_boundaries()
+                && let SessionControl::Exit = self.hand_over(input, output)?
+            {
+                break;
+            }
+        }
+
+        self.finish_replay(output);
+        output.emit(OutputTarget::Broadcast, "\nTerminating session...");
+        output.emit(OutputTarget::Broadcast, "Colony management interface offline.");
+        Ok(())
+    }
+
+    /// Gets the game going: runs the starting planet draft if players still have to pick their
+    /// planets, then snapshots the game and offers the first player advice.
+    fn start(&mut self, input: &mut dyn InputSource, output: &mut dyn OutputSink) -> Result<(), GameError> {
+        if self.game_state.game_phase == GamePhase::Setup {
+            if self.replay.take().is_some() {
+                output.emit_error(OutputTarget::Broadcast, "Games with a starting planet draft cannot be recorded as replays.");
+            }
+            self.run_starting_draft(input, output, &mut rand::rng())?;
+        }
+        self.crash_snapshot.update(&self.game_state);
+        self.offer_advice(output);
+        Ok(())
+    }
+
+    /// Replaces the finished game with a new one from the same setup on a fresh seed, and
+    /// starts it. Structures and ships stay as the finished game knew them. The replay, if
+    /// recorded, covers the finished game only.
+    fn rematch(&mut self, input: &mut dyn InputSource, output: &mut dyn OutputSink) -> Result<(), GameError> {
+        if self.replay.is_some() {
+            self.finish_replay(output);
+            output.emit(OutputTarget::Broadcast, "The replay covers the finished game; the rematch is not recorded.");
+        }
+        self.setup.seed = rand::rng().random();
+        let structure_config = self.game_state.structure_config.clone();
+        let ship_config = self.game_state.ship_config.clone();
+        let mut game_state = self.setup.new_game(structure_config, ship_config, self.setup.load_name_parts()?)?;
+        game_state.started_at = Some(save::unix_now());
+        game_state.set_clock(Arc::new(SystemClock::new()));
+        self.game_state = game_state;
+        self.dirty = false;
+        self.round_events.clear();
+
+        output.players_changed(&self.player_appearances());
+        output.emit(OutputTarget::Broadcast, "=== Rematch ===\nA new galaxy awaits, with the same players and rules.");
+        self.start(input, output)
+    }
+
+    /// Parses, validates and applies one line of input, reporting the outcome to `output`.
+    /// Confirmation prompts for exiting and loading read their answer from `input`.
+    fn execute_command(
+        &mut self,
+        line: &str,
+        input: &mut dyn InputSource,
+        output: &mut dyn OutputSink,
+    ) -> Result<SessionControl, GameError> {
+        let mut styled = StyledSink::new(output, self.options.render_style);
+        let output: &mut dyn OutputSink = &mut styled;
+
+        let player = CommandError::acting_player(&self.game_state)?;
+        let line = alias::expand(line, &player.aliases).into_owned();
+        let acting_player = player.id.clone();
+        let turn = self.game_state.turn;
+        let command = parser::parse(&line).and_then(|command| match command {
+            Command::Inspect(_) if !self.dev_mode => Err(CommandError::DevOnly(String::from("inspect"))),
+            Command::Dev(_) if !self.dev_mode => Err(CommandError::DevOnly(String::from("dev"))),
+            Command::Status(StatusArgs { target: StatusTarget::Perf, .. }) if !self.dev_mode => {
+                Err(CommandError::DevOnly(String::from("status perf")))
+            }
+            command => Ok(command),
+        });
+        // Orders turned away because the game is over are not part of it
+        let is_order = command.as_ref().is_ok_and(|command| command.is_order() && command.allowed_in(self.game_state.game_phase));
+        let result = command
+            .map(|mut command| {
+                match &mut command {
+                    Command::Map(args) => {
+                        args.terminal_width = self.terminal_width;
+                        args.style = self.options.render_style;
+                    }
+                    Command::Help(args) => args.dev_mode = self.dev_mode,
+                    Command::Status(args) => args.now = Some(save::unix_now()),
+                    _ => {}
+                }
+                command
+            })
+            // Everyone at a shared terminal types as the player whose turn it is
+            .and_then(|command| command.execute_as(&self.game_state, &acting_player));
+
+        match result {
+            Ok(CommandEffect::Exit) => {
+                if self.confirm_exit(input, output)? {
+                    // Quitting ends the game for everyone at the terminal
+                    if !self.game_state.is_over() {
+                        output.emit(OutputTarget::Broadcast, &final_report(&self.game_state).to_string());
+                    }
+                    return Ok(SessionControl::Exit);
+                }
+            }
+            Ok(CommandEffect::Rematch) => self.rematch(input, output)?,
+            Ok(CommandEffect::ExportReport { path }) => match export_report(&final_report(&self.game_state), &path) {
+                Ok(()) => output.emit(OutputTarget::CurrentPlayer, &format!("Report written to {}", path.display())),
+                Err(e) => output.emit_error(OutputTarget::CurrentPlayer, &e.to_string()),
+            },
+            Ok(CommandEffect::Save { name }) => match self.save(&name) {
+                Ok(path) => output.emit(OutputTarget::CurrentPlayer, &format!("Game saved to {}", path.display())),
+                Err(e) => output.emit_error(OutputTarget::CurrentPlayer, &e.to_string()),
+            },
+            Ok(CommandEffect::ListOptions) => {
+                output.emit(OutputTarget::CurrentPlayer, &options::format_options(&self.options, &self.game_state));
+            }
+            Ok(CommandEffect::SetSessionOption { option }) => {
+                self.options.apply(option);
+                output.emit(OutputTarget::CurrentPlayer, &option.to_string());
+            }
+            Ok(CommandEffect::ListSaves) => match save::list_saves(&self.save_dir) {
+                Ok(entries) => output.emit(
+                    OutputTarget::CurrentPlayer,
+                    &save_command::format_save_list(&entries, save::unix_now()),
+                ),
+                Err(e) => output.emit_error(OutputTarget::CurrentPlayer, &e.to_string()),
+            },
+            Ok(CommandEffect::DeleteSave { name }) => {
+                if !self.confirm(&format!("Delete save '{name}'? (y/N)"), input, output)? {
+                    output.emit(OutputTarget::CurrentPlayer, "Deletion cancelled.");
+                } else {
+                    match save::delete_save(&self.save_dir, &name) {
+                        Ok(()) => output.emit(OutputTarget::CurrentPlayer, &format!("Save '{name}' deleted.")),
+                        Err(e) => output.emit_error(OutputTarget::CurrentPlayer, &e.to_string()),
+                    }
+                }
+            }
+            Ok(CommandEffect::Load { name }) => {
+                let question = "Unsaved progress will be lost — load anyway? (y/N)";
+                if self.dirty && !self.confirm(question, input, output)? {
+                    output.emit(OutputTarget::CurrentPlayer, "Load cancelled.");
+                } else {
+                    match self.load(&name) {
+                        // Everyone's game just changed under them
+                        Ok(path) => {
+                            output.players_changed(&self.player_appearances());
+                            output.emit(OutputTarget::Broadcast, &format!("Game loaded from {}", path.display()));
+                            if self.replay.is_some() {
+                                output.emit(OutputTarget::Broadcast, "The replay stops here: the loaded game was not set up in this session.");
+                                self.finish_replay(output);
+                            }
+                        }
+                        Err(e) => output.emit_error(OutputTarget::CurrentPlayer, &e.to_string()),
+                    }
+                }
+            }
+            result => {
+                let mutates = result.as_ref().is_ok_and(|effect| !matches!(effect, CommandEffect::None { .. }));
+                let was_over = self.game_state.is_over();
+                // Rejected orders count too, since they end up in the action log
+                if let Some(replay) = self.replay.as_mut().filter(|_| mutates || is_order) {
+                    replay.record(turn, &acting_player, &line);
+                }
+                match self.game_state.apply_command(&acting_player, &line, is_order, result) {
+                    Ok(events) => {
+                        self.dirty |= mutates;
+                        if mutates {
+                            self.crash_snapshot.update(&self.game_state);
+                        }
+                        // Views only describe the game, they are not part of the stream
+                        if let Some(event_log) = self.event_log.as_mut().filter(|_| mutates)
+                            && let Err(e) = event_log.append(turn, &acting_player, &events)
+                        {
+                            output.emit_error(OutputTarget::CurrentPlayer, &e.to_string());
+                        }
+                        self.render_applied_events(events, mutates, output);
+                        if self.game_state.turn > turn && self.game_state.options.autosave_due(self.game_state.turn) {
+                            self.autosave(output);
+                        }
+                        if self.game_state.is_over() && !was_over {
+                            let report = final_report(&self.game_state);
+                            output.emit(OutputTarget::Broadcast, &format!("{}{}", report, log_command::GAME_OVER_HINT));
+                        }
+                    }
+                    Err(message) => output.emit_error(OutputTarget::CurrentPlayer, &message),
+                }
+            }
+        }
+
+        Ok(SessionControl::Continue)
+    }
+
+    /// Shows the current player the advisor's suggestions, during the first turns of a game
+    /// unless the advisor was turned off.
+    fn offer_advice(&self, output: &mut dyn OutputSink) {
+        if !self.options.advisor || self.game_state.is_over() || self.game_state.turn > ADVISOR_TURNS {
+            return;
+        }
+        let player_id = self.game_state.current_player();
+        let suggestions = advise(&self.game_state, player_id);
+        if !suggestions.is_empty() {
+            output.emit(OutputTarget::Player(player_id.clone()), &advisor::format_advice(&suggestions));
+        }
+    }
+
+    /// Guards the seat change after the acting player changed: input queued for the previous
+    /// player is dropped and the next player takes over by pressing enter on an empty line.
+    /// Lines read while waiting were typed or pasted ahead, so they are dropped as well rather
+    /// than acted on for the next player. Exits when input ends while waiting.
+    fn hand_over(&self, input: &mut dyn InputSource, output: &mut dyn OutputSink) -> Result<SessionControl, GameError> {
+        let dropped = input.flush_pending();
+        if dropped > 0 {
+            output.emit_error(OutputTarget::Broadcast, &format!("Dropped {} line(s) of input queued before the turn changed.", dropped));
+        }
+
+        let next_player = self.game_state.players.get(self.game_state.current_player())
+            .map_or_else(String::new, |player| player.name.clone());
+        output.emit(OutputTarget::Broadcast, &format!("Turn passes to {}. Press enter when {} has the terminal.", next_player, next_player));
+        loop {
+            match read_player_input(input, |line| Ok(String::from(line))) {
+                Ok(line) if line.is_empty() => return Ok(SessionControl::Continue),
+                Ok(line) => output.emit_error(
+                    OutputTarget::Broadcast,
+                    &format!("Ignored '{}' - press enter on an empty line to start {}'s turn.", line, next_player),
+                ),
+                Err(InputError::EndOfInput) => return Ok(SessionControl::Exit),
+                Err(error) => return Err(error.into()),
+            }
+        }
+    }
+
+    /// Asks a yes/no question before a destructive action. Scripted sessions, and sessions with
+    /// confirmation prompts turned off, proceed without asking.
+    fn confirm(
+        &self,
+        question: &str,
+        input: &mut dyn InputSource,
+        output: &mut dyn OutputSink,
+    ) -> Result<bool, GameError> {
+        if !input.is_interactive() || !self.options.confirmations {
+            return Ok(true);
+        }
+
+        output.emit(OutputTarget::CurrentPlayer, question);
+        let answer = read_player_input(input, |answer| {
+            match answer.to_lowercase().as_str() {
+                "y" => Ok(true),
+                "n" | "" => Ok(false),
+                _ => Err(String::from("Invalid response. Protocol requires affirmative (Y) or negative (N).")),
+            }
+        });
+
+        match answer {
+            Ok(confirmed) => Ok(confirmed),
+            Err(InputError::EndOfInput) => Ok(false),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Asks whether to keep unsaved progress before leaving. Returns false if the player
+    /// cancelled or the save failed, in which case the session goes on.
+    fn confirm_exit(&mut self, input: &mut dyn InputSource, output: &mut dyn OutputSink) -> Result<bool, GameError> {
+        if !self.dirty || !input.is_interactive() || !self.options.confirmations {
+            return Ok(true);
+        }
+
+        output.emit(OutputTarget::CurrentPlayer, "Unsaved progress — save before exiting? (save/discard/cancel)");
+        let choice = read_player_input(input, |answer| {
+            match answer.to_lowercase().as_str() {
+                "save" => Ok(ExitChoice::Save),
+                "discard" => Ok(ExitChoice::Discard),
+                "cancel" => Ok(ExitChoice::Cancel),
+                _ => Err(String::from("Invalid response. Options: save, discard, cancel")),
+            }
+        });
+
+        match choice {
+            Ok(ExitChoice::Save) => match self.save(QUICKSAVE_SLOT) {
+                Ok(path) => {
+                    output.emit(OutputTarget::CurrentPlayer, &format!("Game saved to {}", path.display()));
+                    Ok(true)
+                }
+                Err(e) => {
+                    output.emit_error(OutputTarget::CurrentPlayer, &e.to_string());
+                    Ok(false)
+                }
+            },
+            Ok(ExitChoice::Discard) | Err(InputError::EndOfInput) => Ok(true),
+            Ok(ExitChoice::Cancel) => Ok(false),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    fn save(&mut self, name: &str) -> Result<PathBuf, SaveError> {
+        let path = save::save_path(&self.save_dir, name)?;
+        save::save_game(&self.game_state, &path, self.compress_saves)?;
+        self.dirty = false;
+        Ok(path)
+    }
+
+    /// Writes the autosave slot. Unlike saves players ask for, it leaves the session dirty,
+    /// so exiting still offers to save.
+    fn autosave(&self, output: &mut dyn OutputSink) {
+        let saved = save::save_path(&self.save_dir, AUTOSAVE_SLOT)
+            .and_then(|path| save::save_game(&self.game_state, &path, self.compress_saves).map(|()| path));
+        match saved {
+            Ok(path) => output.emit(OutputTarget::Broadcast, &format!("Autosaved to {}", path.display())),
+            Err(e) => output.emit_error(OutputTarget::Broadcast, &e.to_string()),
+        }
+    }
+
+    fn load(&mut self, name: &str) -> Result<PathBuf, SaveError> {
+        let path = save::save_path(&self.save_dir, name)?;
+        self.game_state = save::load_game(&path)?;
+        self.game_state.set_clock(Arc::new(SystemClock::new()));
+        self.dirty = false;
+        self.crash_snapshot.update(&self.game_state);
+        Ok(path)
+    }
+
+    /// Makes a panic anywhere in the process write the game as of its last completed command
+    /// to a `crash_<time>` save in the session's save directory. Call once, before running.
+    pub fn install_crash_hook(&self) {
+        crash::install_crash_hook(self.crash_snapshot.clone(), self.save_dir.clone());
+    }
+
+    /// Writes the game as of its last completed command to a `crash_<time>` save, for a
+    /// session that ended on a fatal error. Returns where it went, if anywhere.
+    pub fn emergency_save(&self) -> Option<PathBuf> {
+        crash::emergency_save(&self.crash_snapshot, &self.save_dir)
+    }
+
+    /// Offers to resume from the newest crash save left by an earlier session, before this
+    /// one starts. Resuming replaces the new game, including any pending starting draft. The
+    /// crash save is renamed to `recovered_<time>` either way, so it is offered once but can
+    /// still be loaded. Scripted sessions only mention it.
+    pub fn offer_crash_recovery(&mut self, input: &mut dyn InputSource, output: &mut dyn OutputSink) -> Result<(), GameError> {
+        let crashes = crash::find_crash_saves(&self.save_dir)?;
+        let Some((name, header)) = crashes.first() else {
+            return Ok(());
+        };
+        if !input.is_interactive() {
+            output.emit(OutputTarget::Broadcast, &format!("A crashed session left save '{}'; 'load {}' resumes it.", name, name));
+            return Ok(());
+        }
+
+        output.emit(OutputTarget::Broadcast, &format!(
+            "The last session crashed on turn {} ({}). Resume it? (y/N)",
+            header.turn, header.player_names.join(", "),
+        ));
+        let resume = read_player_input(input, |answer| match answer.to_lowercase().as_str() {
+            "y" => Ok(true),
+            "n" | "" => Ok(false),
+            _ => Err(String::from("Invalid response. Protocol requires affirmative (Y) or negative (N).")),
+        });
+        let resume = match resume {
+            Ok(resume) => resume,
+            Err(InputError::EndOfInput) => false,
+            Err(error) => return Err(error.into()),
+        };
+
+        if resume {
+            let path = self.load(name)?;
+            if self.replay.take().is_some() {
+                output.emit(OutputTarget::Broadcast, "The replay is off: the resumed game was not set up in this session.");
+            }
+            output.emit(OutputTarget::Broadcast, &format!("Game resumed from {}", path.display()));
+        }
+        let recovered = crash::mark_recovered(&self.save_dir, name)?;
+        if !resume {
+            output.emit(OutputTarget::Broadcast, &format!("The crash save is kept as '{}'.", recovered));
+        }
+        Ok(())
+    }
+
+    /// Writes the replay recorded so far, if the session records one, and stops recording.
+    fn finish_replay(&mut self, output: &mut dyn OutputSink) {
+        let (Some(replay), Some(path)) = (self.replay.take(), self.replay_path.as_ref()) else {
+            return;
+        };
+        match replay.write(path, self.compress_saves) {
+            Ok(()) => output.emit(OutputTarget::Broadcast, &format!("Replay written to {}", path.display())),
+            Err(e) => output.emit_error(OutputTarget::Broadcast, &e.to_string()),
+        }
+    }
+
+    /// Names and appearances of the players in turn order, for `OutputSink::players_changed`.
+    fn player_appearances(&self) -> Vec<(String, PlayerAppearance)> {
+        self.game_state.players_order.iter()
+            .filter_map(|id| self.game_state.players.get(id))
+            .map(|player| (player.name.clone(), player.appearance.clone()))
+            .collect()
+    }
+
+    /// Renders what an applied command produced. With galaxy news on, a round ending also
+    /// broadcasts the news for it right before the new turn's banner.
+    fn render_applied_events(&mut self, events: Vec<GameEvent>, mutates: bool, output: &mut dyn OutputSink) {
+        if !self.round_report || !mutates {
+            Self::render_events(&self.game_state, &events, output);
+            return;
+        }
+
+        match events.iter().position(|event| matches!(event, GameEvent::TurnBegan { .. })) {
+            Some(turn_began) => {
+                Self::render_events(&self.game_state, &events[..turn_began], output);
+                self.round_events.extend_from_slice(&events[..turn_began]);
+                let report = round_report(&self.game_state, &self.round_events);
+                output.emit(OutputTarget::Broadcast, &report);
+                self.round_events.clear();
+                Self::render_events(&self.game_state, &events[turn_began..], output);
+                self.round_events.extend_from_slice(&events[turn_began..]);
+            }
+            None => {
+                Self::render_events(&self.game_state, &events, output);
+                self.round_events.extend(events);
+            }
+        }
+    }
+
+    fn render_events(game_state: &GameState, events: &[GameEvent], output: &mut dyn OutputSink) {
+        for event in events {
+            output.emit(event.target(), &event.named(&game_state.structure_config, &game_state.ship_config).to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::engine::clock::MockClock;
+    use crate::engine::configs::PlanetNameParts;
+    use crate::engine::game_state::ActionOutcome;
+    use crate::engine::map::RenderStyle;
+    use crate::interface::game_configuration::{GameConfigurationBuilder, MapSize};
+    use crate::interface::input::{ReaderSource, ScriptSource};
+    use crate::interface::output::CaptureSink;
+    use crate::test_support::{self, fixture_game};
+
+    fn fixture_session(name: &str) -> Game {
+        Game {
+            game_state: fixture_game(1),
+            dirty: false,
+            save_dir: test_support::temp_dir(name),
+            compress_saves: true,
+            terminal_width: None,
+            options: SessionOptions { advisor: false, ..SessionOptions::default() },
+            event_log: None,
+            round_report: false,
+            round_events: Vec::new(),
+            dev_mode: false,
+            replay: None,
+            replay_path: None,
+            setup: test_support::fixture_setup(1),
+            crash_snapshot: CrashSnapshot::default(),
+        }
+    }
+
+    fn run_script(game: &mut Game, script: &str, interactive: bool) {
+        let mut input = ScriptSource::new(script.lines()).with_interactive(interactive);
+        game.run_with(&mut input, &mut CaptureSink::new()).unwrap();
+    }
+
+    fn quicksave_path(game: &Game) -> PathBuf {
+        save::save_path(&game.save_dir, QUICKSAVE_SLOT).unwrap()
+    }
+
+    #[test]
+    fn test_panic_mid_session_leaves_a_crash_save_to_resume() {
+        let mut game = fixture_session("crash_resume");
+        let home = test_support::home_planet(&game.game_state, "alice");
+        game.install_crash_hook();
+        let crashed = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            run_script(&mut game, &format!("build {home} power_grid\nend\ncrash!\n"), false);
+        }));
+        // Later panics elsewhere in the test run should not write here
+        drop(std::panic::take_hook());
+        assert!(crashed.is_err());
+
+        // A test panicking elsewhere while the hook was installed saves the same snapshot
+        let crashes = crash::find_crash_saves(&game.save_dir).unwrap();
+        assert!(!crashes.is_empty(), "The panic must leave a crash save");
+        assert!(crashes.iter().all(|(_, header)| header.turn == 1), "{crashes:?}");
+        let name = &crashes[0].0;
+
+        // The next session is offered the crash save and picks up after the last command
+        let mut next = fixture_session("crash_resume_next");
+        next.save_dir = game.save_dir.clone();
+        next.game_state.game_phase = GamePhase::Setup;
+        let mut output = CaptureSink::new();
+        next.offer_crash_recovery(&mut ScriptSource::new(["y"]).with_interactive(true), &mut output).unwrap();
+
+        assert_eq!(next.game_state.current_player(), "bob");
+        assert_eq!(pending_targets(&next, "alice"), ["power_grid"]);
+        assert_eq!(next.game_state.game_phase, GamePhase::Playing);
+        assert!(crash::find_crash_saves(&next.save_dir).unwrap().iter().all(|(left, _)| left != name));
+        let recovered = name.replacen(crash::CRASH_SAVE_PREFIX, crash::RECOVERED_SAVE_PREFIX, 1);
+        assert!(save::save_path(&next.save_dir, &recovered).unwrap().exists());
+    }
+
+    #[test]
+    fn test_crash_saves_are_only_mentioned_to_scripts() {
+        let mut game = fixture_session("crash_scripted");
+        game.crash_snapshot.update(&game.game_state);
+        let path = game.emergency_save().unwrap();
+        let turn = game.game_state.turn;
+        game.game_state.turn += 3;
+
+        let mut output = CaptureSink::new();
+        game.offer_crash_recovery(&mut ScriptSource::new(["y"]), &mut output).unwrap();
+
+        assert_eq!(game.game_state.turn, turn + 3, "Scripts must not resume on their own");
+        assert!(path.exists());
+        let broadcast = output.texts_for(&OutputTarget::Broadcast);
+        assert!(broadcast.iter().any(|text| text.contains("'load crash_")), "{broadcast:?}");
+    }
+
+    #[test]
+    fn test_exit_prompt_save_writes_quicksave() {
+        let mut game = fixture_session("exit_save");
+
+        run_script(&mut game, "end\nexit\nsave\n", true);
+
+        assert!(quicksave_path(&game).exists());
+        assert!(!game.dirty);
+    }
+
+    #[test]
+    fn test_exit_prompt_discard_does_not_save() {
+        let mut game = fixture_session("exit_discard");
+
+        run_script(&mut game, "end\nexit\ndiscard\n", true);
+
+        assert!(!quicksave_path(&game).exists());
+        assert!(game.dirty);
+    }
+
+    #[test]
+    fn test_exit_prompt_cancel_keeps_session_running() {
+        let mut game = fixture_session("exit_cancel");
+        let turn = game.game_state.turn;
+
+        run_script(&mut game, "end\nexit\ncancel\nend\nexit\ndiscard\n", true);
+
+        assert_eq!(game.game_state.turn, turn + 1);
+        assert!(!quicksave_path(&game).exists());
+    }
+
+    #[test]
+    fn test_input_pasted_across_a_turn_change_is_not_run_for_the_next_player() {
+        let mut game = fixture_session("pasted_turn_change");
+        let alice_home = test_support::home_planet(&game.game_state, "alice");
+        let bob_home = test_support::home_planet(&game.game_state, "bob");
+
+        // A terminal replaying a pasted block, as a person typing it would
+        let pasted = format!("build {alice_home} power_grid\nend\nbuild {bob_home} power_grid\n");
+        let mut input = ReaderSource::new(Cursor::new(pasted.into_bytes()), true);
+        let mut output = CaptureSink::new();
+        game.run_with(&mut input, &mut output).unwrap();
+
+        assert_eq!(pending_targets(&game, "alice"), ["power_grid"]);
+        assert!(pending_targets(&game, "bob").is_empty());
+        assert_eq!(game.game_state.current_player(), "bob");
+        assert!(game.game_state.action_log.iter().all(|entry| entry.player_id == "alice"), "{:?}", game.game_state.action_log);
+        let broadcast = output.texts_for(&OutputTarget::Broadcast);
+        assert!(broadcast.iter().any(|text| text.contains("Ignored 'build")), "{broadcast:?}");
+    }
+
+    #[test]
+    fn test_turn_change_waits_for_an_empty_line_before_the_next_player_acts() {
+        let mut game = fixture_session("turn_change_ack");
+        let bob_home = test_support::home_planet(&game.game_state, "bob");
+
+        let typed = format!("end\n\nbuild {bob_home} power_grid\n");
+        let mut input = ReaderSource::new(Cursor::new(typed.into_bytes()), true);
+        game.run_with(&mut input, &mut CaptureSink::new()).unwrap();
+
+        assert_eq!(pending_targets(&game, "bob"), ["power_grid"]);
+    }
+
+    #[test]
+    fn test_scripted_exit_discards_without_prompt() {
+        let mut game = fixture_session("exit_scripted");
+        let turn = game.game_state.turn;
+
+        // "end" after exit would advance the turn if exit had prompted and continued
+        run_script(&mut game, "end\nexit\nend\nend\n", false);
+
+        assert!(!quicksave_path(&game).exists());
+        assert_eq!(game.game_state.turn, turn);
+    }
+
+    fn pending_targets(game: &Game, player_id: &str) -> Vec<String> {
+        let home = test_support::home_planet(&game.game_state, player_id);
+        game.game_state.players[player_id].pending_actions_on_planet(&home)
+            .map(|action| action.target_id().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_script_drives_full_two_player_turn() {
+        let mut game = fixture_session("two_player_turn");
+        let turn = game.game_state.turn;
+        let alice_home = test_support::home_planet(&game.game_state, "alice");
+        let bob_home = test_support::home_planet(&game.game_state, "bob");
+
+        let script = [
+            format!("build {alice_home} power_grid"),
+            String::from("end"),
+            format!("build {bob_home} power_grid"),
+            String::from("end"),
+        ];
+        let mut output = CaptureSink::new();
+        game.run_with(&mut ScriptSource::new(script), &mut output).unwrap();
+
+        assert_eq!(game.game_state.turn, turn + 1);
+        assert_eq!(game.game_state.current_player(), "alice");
+        for (player, home) in [("alice", &alice_home), ("bob", &bob_home)] {
+            let planet = &game.game_state.map.planets[home];
+            assert!(planet.get_structures().contains_key("power_grid"), "{player} did not build");
+
+            // Each player hears only about their own construction
+            let seen = output.texts_for(&OutputTarget::Player(player.to_string()));
+            let home_name = &planet.name;
+            assert_eq!(seen, [format!("Construction completed: Power Grid (power_grid) on planet {home_name}")]);
+        }
+        let broadcast = output.texts_for(&OutputTarget::Broadcast);
+        assert!(broadcast.contains(&"Alice ends their turn."));
+        assert!(broadcast.contains(&format!("\n=== Turn {} Begins ===", turn + 1).as_str()));
+    }
+
+    #[test]
+    fn test_commented_script_runs_without_errors() {
+        let mut game = fixture_session("commented_script");
+        let turn = game.game_state.turn;
+        let script = include_str!("../../tests/fixtures/commented_script.txt");
+
+        let mut output = CaptureSink::new();
+        game.run_with(&mut ScriptSource::new(script.lines()), &mut output).unwrap();
+
+        let errors: Vec<_> = output.entries.iter()
+            .filter(|(_, text)| text.starts_with("ERROR"))
+            .collect();
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(game.game_state.turn, turn + 1);
+        assert!(game.game_state.players["alice"].aliases.contains_key("pl"));
+    }
+
+    #[test]
+    fn test_blank_interactive_line_is_not_an_error() {
+        let mut game = fixture_session("blank_interactive");
+
+        let mut output = CaptureSink::new();
+        let mut input = ScriptSource::new(["", "   "]).with_interactive(true);
+        game.run_with(&mut input, &mut output).unwrap();
+
+        assert!(output.entries.iter().all(|(_, text)| !text.starts_with("ERROR")), "{:?}", output.entries);
+    }
+
+    #[test]
+    fn test_ascii_style_applies_to_map_and_events() {
+        let mut game = fixture_session("ascii_style");
+
+        let mut output = CaptureSink::new();
+        game.run_with(&mut ScriptSource::new(["set style ascii", "map grid", "end"]), &mut output).unwrap();
+
+        assert_eq!(game.options.render_style, RenderStyle::AsciiOnly);
+        assert!(output.entries.iter().any(|(_, text)| text.contains("Owners:")), "{:?}", output.entries);
+        assert!(output.entries.iter().all(|(_, text)| text.is_ascii()), "{:?}", output.entries);
+    }
+
+    #[test]
+    fn test_quickload_restores_quicksaved_state() {
+        let mut game = fixture_session("quickload");
+        let home = test_support::home_planet(&game.game_state, "alice");
+
+        let script = format!(
+            "build {home} power_grid\nqs\nupgrade {home} planetary_capital\nend\nql\n"
+        );
+        run_script(&mut game, &script, false);
+
+        assert_eq!(pending_targets(&game, "alice"), vec!["power_grid"]);
+        assert_eq!(game.game_state.current_player(), "alice");
+        assert!(!game.dirty);
+    }
+
+    #[test]
+    fn test_quickload_declined_keeps_unsaved_progress() {
+        let mut game = fixture_session("quickload_declined");
+        let home = test_support::home_planet(&game.game_state, "alice");
+
+        let script = format!("qs\nbuild {home} power_grid\nql\nn\n");
+        run_script(&mut game, &script, true);
+
+        assert_eq!(pending_targets(&game, "alice"), vec!["power_grid"]);
+        assert!(game.dirty);
+    }
+
+    #[test]
+    fn test_saves_delete_asks_for_confirmation() {
+        let mut game = fixture_session("saves_delete");
+        let path = save::save_path(&game.save_dir, "keep").unwrap();
+
+        run_script(&mut game, "save keep\nsaves delete keep\nn\n", true);
+        assert!(path.exists());
+
+        run_script(&mut game, "saves delete keep\ny\n", true);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_session_options_change_the_session_only() {
+        let mut game = fixture_session("session_options");
+        let path = save::save_path(&game.save_dir, "keep").unwrap();
+
+        run_script(&mut game, "save keep\noptions set confirmations off\noptions set style ascii\nsaves delete keep\n", true);
+
+        // Deleted without asking, and nothing of it went into the game
+        assert!(!path.exists());
+        assert_eq!(game.options.render_style, RenderStyle::AsciiOnly);
+        assert!(!game.dirty);
+        assert_eq!(game.game_state.options, Default::default());
+    }
+
+    #[test]
+    fn test_game_options_are_saved_and_autosave() {
+        let mut game = fixture_session("game_options");
+        let autosave = save::save_path(&game.save_dir, AUTOSAVE_SLOT).unwrap();
+
+        let mut output = CaptureSink::new();
+        let script = ["options set autosave 1", "options set speed quick", "options", "end", "end"];
+        game.run_with(&mut ScriptSource::new(script), &mut output).unwrap();
+
+        assert!(autosave.exists());
+        assert_eq!(save::load_game(&autosave).unwrap().options.autosave_rounds, 1);
+        assert!(game.dirty);
+        let texts: Vec<_> = output.entries.iter().map(|(_, text)| text.as_str()).collect();
+        assert!(texts.iter().any(|text| text.starts_with("ERROR") && text.contains("fixed when the game is created")), "{texts:?}");
+        assert!(texts.iter().any(|text| text.contains("autosave") && text.contains("every round")), "{texts:?}");
+    }
+
+    #[test]
+    fn test_exit_without_changes_does_not_prompt() {
+        let mut game = fixture_session("exit_clean");
+
+        run_script(&mut game, "status turn\nexit\nend\nend\n", true);
+
+        assert!(!game.dirty);
+        assert_eq!(game.game_state.current_player(), "alice");
+    }
+
+    #[test]
+    fn test_scripted_orders_are_logged_and_opened_at_game_end() {
+        let mut game = fixture_session("action_log");
+        let alice_home = test_support::home_planet(&game.game_state, "alice");
+        run_script(&mut game, &format!("build {alice_home} power_grid\nbuild {alice_home} nowhere\nstatus turn\nend\nend\n"), false);
+
+        let alice = game.game_state.player_actions(&String::from("alice"), 1, 1);
+        let commands: Vec<_> = alice.iter().map(|entry| (entry.command.as_str(), &entry.outcome)).collect();
+        assert_eq!(commands[0], (format!("build {alice_home} power_grid").as_str(), &ActionOutcome::Applied));
+        assert!(matches!(commands[1].1, ActionOutcome::Rejected { reason } if reason.contains("nowhere")));
+        assert_eq!(commands[2], ("end", &ActionOutcome::Applied));
+        assert_eq!(commands.len(), 3);
+
+        // Alice takes bob's last planet, so the round ends in her victory
+        let bob_home = test_support::home_planet(&game.game_state, "bob");
+        let state = &mut game.game_state;
+        state.players.get_mut("bob").unwrap().planets.clear();
+        let alice_planets = state.map.planets.keys().cloned().collect();
+        state.players.get_mut("alice").unwrap().planets = alice_planets;
+        state.map.planets.get_mut(&bob_home).unwrap().set_owner(String::from("alice"));
+
+        let mut output = CaptureSink::new();
+        let script = ["log bob", "end", "end", "log bob 1"];
+        game.run_with(&mut ScriptSource::new(script), &mut output).unwrap();
+
+        let errors: Vec<_> = output.entries.iter().filter(|(_, text)| text.starts_with("ERROR")).collect();
+        assert!(matches!(&errors[..], [(_, text)] if text.contains("game is over")), "{errors:?}");
+        let broadcast = output.texts_for(&OutputTarget::Broadcast);
+        assert!(broadcast.iter().any(|text| text.contains("=== Final Report ===") && text.contains("Bob was eliminated")), "{broadcast:?}");
+        assert!(output.entries.iter().any(|(_, text)| text.contains("Action Log: Bob (turn 1)") && text.contains("end -> ok")));
+    }
+
+    #[test]
+    fn test_finished_games_take_views_and_a_rematch_but_no_orders() {
+        let mut game = fixture_session("rematch");
+        let home = test_support::home_planet(&game.game_state, "alice");
+        let mut output = CaptureSink::new();
+        game.run_with(&mut ScriptSource::new(["rematch"]), &mut output).unwrap();
+        assert!(output.entries.iter().any(|(_, text)| text.contains("rematch is only available once the game has ended")));
+
+        game.game_state.game_phase = GamePhase::Finished;
+        let mut output = CaptureSink::new();
+        let script = format!("build {home} power_grid\nstatus planets\nmap");
+        game.run_with(&mut ScriptSource::new(script.lines()), &mut output).unwrap();
+        let errors: Vec<_> = output.entries.iter().filter(|(_, text)| text.starts_with("ERROR")).collect();
+        assert!(matches!(&errors[..], [(_, text)] if text.contains("The game has ended: build is no longer possible")), "{errors:?}");
+        assert!(game.game_state.action_log.is_empty(), "Turned away orders are not logged");
+        assert!(!game.dirty);
+
+        let mut output = CaptureSink::new();
+        game.run_with(&mut ScriptSource::new(["rematch", "status game"]), &mut output).unwrap();
+        assert!(output.entries.iter().any(|(_, text)| text.contains("=== Rematch ===")));
+        let state = &game.game_state;
+        assert_eq!(state.game_phase, GamePhase::Playing);
+        assert!(!state.omniscient);
+        assert_eq!(state.turn, 1);
+        assert_ne!(game.setup.seed, 1, "A rematch is played on a fresh seed");
+        let names: Vec<_> = state.players.values().map(|player| player.name.as_str()).collect();
+        assert_eq!(names, ["Alice", "Bob"]);
+    }
+
+    #[test]
+    fn test_scripted_game_streams_events_to_jsonl() {
+        let mut game = fixture_session("event_stream");
+        let path = game.save_dir.join("events.jsonl");
+        game.event_log = Some(EventLog::open(&path).unwrap());
+        let alice_home = test_support::home_planet(&game.game_state, "alice");
+
+        run_script(&mut game, &format!("build {alice_home} power_grid\nstatus turn\nend\nend\n"), false);
+
+        let records = crate::interface::event_log::read_event_log(&path).unwrap();
+        assert!(records.iter().all(|record| record.schema_version == crate::interface::event_log::EVENT_SCHEMA_VERSION));
+        assert!(matches!(&records[0], record if record.player == "alice" && matches!(record.event, GameEvent::StructureQueued { .. })));
+        assert!(!records.iter().any(|record| matches!(record.event, GameEvent::Info { .. })));
+        let completed = records.iter()
+            .find(|record| matches!(record.event, GameEvent::ConstructionCompleted { .. }))
+            .expect("Round processing must be streamed");
+        assert_eq!((completed.turn, completed.player.as_str()), (1, "bob"));
+        assert!(records.iter().any(|record| record.event == GameEvent::TurnBegan { turn: 2, protection_turns_left: 0 }));
+    }
+
+    #[test]
+    fn test_round_report_precedes_next_turn_banner() {
+        let mut game = fixture_session("round_report");
+        game.round_report = true;
+        let alice_home = test_support::home_planet(&game.game_state, "alice");
+        let mut output = CaptureSink::new();
+
+        let script = [format!("build {alice_home} power_grid"), String::from("end"), String::from("end")];
+        game.run_with(&mut ScriptSource::new(script), &mut output).unwrap();
+
+        let texts: Vec<_> = output.entries.iter().map(|(_, text)| text.as_str()).collect();
+        let report = texts.iter().position(|text| text.starts_with("=== Galaxy News: Turn 1 ===")).expect("Report must be broadcast");
+        let banner = texts.iter().position(|text| text.contains("=== Turn 2 Begins ===")).unwrap();
+        assert_eq!(report + 1, banner);
+        assert!(!texts[report].contains("power_grid"), "{}", texts[report]);
+        assert!(texts[report].contains("Down to their last planet: Alice, Bob"), "{}", texts[report]);
+        assert!(game.round_events.iter().all(|event| !matches!(event, GameEvent::StructureQueued { .. })));
+    }
+
+    #[test]
+    fn test_advisor_speaks_up_during_the_first_turns_only() {
+        let advice_for = |advisor: bool, turn: u32| {
+            let mut game = fixture_session("advisor");
+            game.options.advisor = advisor;
+            game.game_state.turn = turn;
+            let mut output = CaptureSink::new();
+            game.run_with(&mut ScriptSource::new(["end"]), &mut output).unwrap();
+            ["alice", "bob"].map(|player_id| output.texts_for(&OutputTarget::Player(String::from(player_id)))
+                .iter().filter(|text| text.starts_with("ADVISOR")).count())
+        };
+
+        assert_eq!(advice_for(true, 1), [1, 1]);
+        assert_eq!(advice_for(true, ADVISOR_TURNS + 1), [0, 0]);
+        assert_eq!(advice_for(false, 1), [0, 0]);
+    }
+
+    #[test]
+    fn test_inspect_needs_dev_mode() {
+        let mut game = fixture_session("inspect");
+        let home = test_support::home_planet(&game.game_state, "alice");
+        let script = format!("inspect planet {home}");
+
+        let mut output = CaptureSink::new();
+        game.run_with(&mut ScriptSource::new([script.as_str()]), &mut output).unwrap();
+        assert!(output.entries.iter().any(|(_, text)| text.contains("start the game with --dev")));
+
+        game.dev_mode = true;
+        let mut output = CaptureSink::new();
+        game.run_with(&mut ScriptSource::new([script.as_str()]), &mut output).unwrap();
+        assert!(output.entries.iter().any(|(_, text)| text.contains("\"shield_regen_timer\"")));
+    }
+
+    #[test]
+    fn test_dev_commands_need_dev_mode_and_announce_themselves() {
+        let mut game = fixture_session("dev_commands");
+        let turn = game.game_state.turn;
+
+        let mut output = CaptureSink::new();
+        game.run_with(&mut ScriptSource::new(["dev set-turn 30"]), &mut output).unwrap();
+        assert!(output.entries.iter().any(|(_, text)| text.contains("Command dev is a developer tool")));
+        assert_eq!(game.game_state.turn, turn);
+
+        game.dev_mode = true;
+        let mut output = CaptureSink::new();
+        game.run_with(&mut ScriptSource::new(["dev set-turn 30"]), &mut output).unwrap();
+        let broadcast = output.texts_for(&OutputTarget::Broadcast);
+        assert!(broadcast.contains(&"[dev] Alice set the turn to 30"), "{broadcast:?}");
+        assert_eq!(game.game_state.turn, 30);
+    }
+
+    #[test]
+    fn test_status_perf_needs_dev_mode_and_times_each_phase() {
+        let mut game = fixture_session("perf");
+        let clock = MockClock::new();
+        // Seven phases taking 1 ms more each, 28 ms in all
+        clock.queue_steps((0..8).map(Duration::from_millis));
+        game.game_state.set_clock(Arc::new(clock));
+
+        let mut output = CaptureSink::new();
+        game.run_with(&mut ScriptSource::new(["status perf"]), &mut output).unwrap();
+        assert!(output.entries.iter().any(|(_, text)| text.contains("Command status perf is a developer tool")));
+
+        game.dev_mode = true;
+        let mut output = CaptureSink::new();
+        game.run_with(&mut ScriptSource::new(["end", "end", "status perf"]), &mut output).unwrap();
+        let report = output.entries.iter().map(|(_, text)| text.as_str()).find(|text| text.contains("Round Timings")).unwrap();
+        let line = |name: &str| report.lines().find(|line| line.trim_start().starts_with(name)).unwrap().to_string();
+        assert!(report.starts_with("=== Round Timings (1 round(s)) ==="), "{report}");
+        assert_eq!(line("completion").split_whitespace().collect::<Vec<_>>(), ["completion", "4.00", "ms", "4.00", "ms"]);
+        assert!(line("total").contains("28.00 ms"), "{report}");
+        // Planets produce at the start of their owner's turn, and only Alice's has come yet
+        assert!(report.contains("Planets processed: 1 last round, 1 in all"), "{report}");
+    }
+
+    #[test]
+    fn test_new_rejects_duplicate_player_ids() {
+        // Bypass builder validation to make sure Game::new does not merge the players
+        let mut config = GameConfigurationBuilder::new()
+            .player_name("Bob")
+            .player_name("Alice")
+            .build()
+            .unwrap();
+        config.player_names[1] = String::from("bob");
+
+        assert!(matches!(Game::new(config), Err(GameError::DuplicatePlayer(id)) if id == "bob"));
+    }
+
+    fn six_players(map_size: MapSize) -> GameConfiguration {
+        ["Alice", "Bob", "Carol", "Dave", "Erin", "Frank"].into_iter()
+            .fold(GameConfigurationBuilder::new(), |builder, name| builder.player_name(name))
+            .map_size(map_size)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new_rejects_maps_too_small_for_the_factions() {
+        let error = Game::new(six_players(MapSize::Small)).err().expect("Six factions must not fit a small map");
+
+        assert!(matches!(
+            error,
+            GameError::GameConfigurationError(GameConfigurationError::MapTooSmall { factions: 6, planets_per_faction: 3, .. })
+        ));
+        assert!(error.to_string().contains("Choose a medium map or larger"), "{error}");
+    }
+
+    #[test]
+    fn test_six_players_set_up_on_a_large_map() {
+        let config = six_players(MapSize::Large);
+        config.check_map_capacity().unwrap();
+
+        let name_parts = PlanetNameParts::load_from_string(test_support::PLANET_NAMES_FIXTURE).unwrap();
+        let state = GameSetup::from_configuration(&config, 3)
+            .new_game(test_support::fixture_structure_config(), test_support::fixture_ship_config(), name_parts)
+            .unwrap();
+
+        assert_eq!(state.players_order.len(), 6);
+        assert_eq!(state.map.planets.len(), 30);
+        let mut homes: Vec<_> = state.players.values().map(|player| player.planets.clone()).collect();
+        assert!(homes.iter().all(|planets| planets.len() == 1));
+        homes.dedup();
+        assert_eq!(homes.len(), 6);
+    }
+}
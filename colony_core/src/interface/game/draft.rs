@@ -0,0 +1,215 @@
+use std::collections::BTreeSet;
+
+use rand::Rng;
+use rand::seq::{IndexedRandom, SliceRandom};
+
+use crate::engine::game_state::GamePhase;
+use crate::engine::map::Map;
+use crate::engine::planet::PlanetId;
+use crate::interface::input::{InputError, InputSource, read_player_input};
+use crate::interface::output::{OutputSink, OutputTarget};
+
+use super::{Game, GameError};
+
+/// Starting planets offered to each player in the draft.
+pub const DRAFT_CANDIDATES: usize = 3;
+
+/// Planets other than `planet_id` reachable in at most two jumps.
+fn planets_within_two_jumps(map: &Map, planet_id: &PlanetId) -> usize {
+    let graph = map.graph();
+    let mut nearby = BTreeSet::new();
+    for (neighbor, _) in graph.neighbors(planet_id) {
+        nearby.insert(neighbor);
+        nearby.extend(graph.neighbors(neighbor).map(|(second, _)| second));
+    }
+    nearby.remove(planet_id);
+    nearby.len()
+}
+
+/// A candidate line: name, id, lane count and how crowded the surroundings are.
+fn describe_candidate(map: &Map, planet_id: &PlanetId) -> String {
+    let planet = &map.planets[planet_id];
+    format!(
+        "{} ({}) - {} connection(s), {} planet(s) within 2 jumps",
+        planet.name, planet.id, planet.get_connections().len(), planets_within_two_jumps(map, planet_id)
+    )
+}
+
+fn parse_choice(answer: &str, count: usize) -> Result<usize, String> {
+    let invalid = || format!("Type 'choose <n>' with n from 1 to {} to pick a starting planet.", count);
+    let number = answer.strip_prefix("choose").ok_or_else(invalid)?;
+    match number.trim().parse::<usize>() {
+        Ok(choice) if (1..=count).contains(&choice) => Ok(choice - 1),
+        _ => Err(invalid()),
+    }
+}
+
+impl Game {
+    /// Lets each player, in random order, pick a starting planet among a few candidates.
+    /// A chosen planet and its neighbors are not offered to later players. A player gets a
+    /// random planet when no candidates are left, or a random candidate when input runs out.
+    /// Ruins are placed once every player has settled, as they are without a draft.
+    pub(super) fn run_starting_draft(
+        &mut self,
+        input: &mut dyn InputSource,
+        output: &mut dyn OutputSink,
+        rng: &mut impl Rng,
+    ) -> Result<(), GameError> {
+        let state = &mut self.game_state;
+        let mut order: Vec<_> = state.players_order.iter().cloned().collect();
+        order.shuffle(rng);
+
+        let mut pool: Vec<PlanetId> = state.map.planets.keys().cloned().collect();
+
+        output.emit(OutputTarget::Broadcast, "=== Starting Planet Draft ===");
+        for player_id in order {
+            let player_name = state.players[&player_id].name.clone();
+            let mut candidates: Vec<_> = pool.choose_multiple(rng, DRAFT_CANDIDATES).cloned().collect();
+            candidates.sort();
+
+            let planet_id = if candidates.is_empty() {
+                let unowned: Vec<_> = state.map.planets.values()
+                    .filter(|planet| planet.get_owner().is_none())
+                    .map(|planet| planet.id.clone())
+                    .collect();
+                let planet_id = unowned.choose(rng).expect("Maps have a planet for every player").clone();
+                output.emit(
+                    OutputTarget::Player(player_id.clone()),
+                    &format!("{}, no candidates are left; you start at {}.", player_name, describe_candidate(&state.map, &planet_id)),
+                );
+                planet_id
+            } else {
+                let mut listing = format!("{}, choose your starting planet:", player_name);
+                for (index, planet_id) in candidates.iter().enumerate() {
+                    listing.push_str(&format!("\n  {}. {}", index + 1, describe_candidate(&state.map, planet_id)));
+                }
+                output.emit(OutputTarget::Player(player_id.clone()), &listing);
+
+                let index = match read_player_input(input, |answer| parse_choice(answer, candidates.len())) {
+                    Ok(index) => index,
+                    Err(InputError::EndOfInput) => rng.random_range(0..candidates.len()),
+                    Err(error) => return Err(error.into()),
+                };
+                candidates.swap_remove(index)
+            };
+
+            let graph = state.map.graph();
+            pool.retain(|other| *other != planet_id && !graph.are_adjacent(other, &planet_id));
+
+            let player = state.players.get_mut(&player_id).expect("Drafting player must exist");
+            state.map.settle_starting_planet(player, planet_id, &state.structure_config, state.rules.speed)?;
+            output.emit(OutputTarget::Broadcast, &format!("{} has chosen a starting planet.", player_name));
+        }
+
+        state.map.place_ruins(state.rules.ruin_chance_percent, rng);
+        if state.rules.victory.allows_domination() {
+            state.map.place_key_planets(state.rules.key_planets, rng);
+        }
+        state.refresh_intel();
+        state.game_phase = GamePhase::Playing;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+    use crate::engine::options::SessionOptions;
+    use crate::engine::planet::ConnectionKind;
+    use crate::interface::crash::CrashSnapshot;
+    use crate::interface::input::ScriptSource;
+    use crate::interface::output::CaptureSink;
+    use crate::test_support;
+
+    fn draft_session(name: &str) -> Game {
+        Game {
+            game_state: test_support::fixture_draft_game(1),
+            dirty: false,
+            save_dir: test_support::temp_dir(name),
+            compress_saves: true,
+            terminal_width: None,
+            options: SessionOptions { advisor: false, ..SessionOptions::default() },
+            event_log: None,
+            round_report: false,
+            round_events: Vec::new(),
+            dev_mode: false,
+            replay: None,
+            replay_path: None,
+            setup: test_support::fixture_setup(1),
+            crash_snapshot: CrashSnapshot::default(),
+        }
+    }
+
+    /// The candidate ids listed to `player_id`, in listing order.
+    fn offered(output: &CaptureSink, player_id: &str) -> Vec<String> {
+        let listing = output.texts_for(&OutputTarget::Player(player_id.to_string()))[0];
+        listing.lines().skip(1)
+            .map(|line| line.split(['(', ')']).nth(1).unwrap().to_string())
+            .collect()
+    }
+
+    /// Players in the order they were addressed, which is the draft order.
+    fn drafters(output: &CaptureSink) -> Vec<String> {
+        output.entries.iter()
+            .filter_map(|(target, _)| match target {
+                OutputTarget::Player(player_id) => Some(player_id.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_players_settle_their_chosen_candidate_apart_from_each_other() {
+        let mut game = draft_session("draft_choose");
+        let mut output = CaptureSink::new();
+        let mut input = ScriptSource::new(["choose 2", "choose 9", "pick 1", "choose 1"]);
+
+        game.run_starting_draft(&mut input, &mut output, &mut StdRng::seed_from_u64(7)).unwrap();
+
+        let state = &game.game_state;
+        let [first, second] = <[String; 2]>::try_from(drafters(&output)).unwrap();
+        for id in [&first, &second] {
+            assert_eq!(offered(&output, id).len(), DRAFT_CANDIDATES);
+            let home = test_support::home_planet(state, id);
+            assert!(state.map.planets[&home].get_structures().contains_key("planetary_capital"));
+            assert!(state.players[id].explored.contains(&home));
+        }
+        let first_home = test_support::home_planet(state, &first);
+        let second_home = test_support::home_planet(state, &second);
+        assert_eq!(first_home, offered(&output, &first)[1]);
+        assert_eq!(second_home, offered(&output, &second)[0]);
+        assert!(!offered(&output, &second).contains(&first_home));
+        assert!(!state.map.graph().are_adjacent(&first_home, &second_home));
+        assert_eq!(game.game_state.game_phase, GamePhase::Playing);
+    }
+
+    #[test]
+    fn test_draft_falls_back_to_random_planets() {
+        let mut game = draft_session("draft_fallback");
+        // Link every planet to every other, so the first pick leaves no candidates
+        let map = &mut game.game_state.map;
+        let ids: Vec<_> = map.planets.keys().cloned().collect();
+        for (index, first) in ids.iter().enumerate() {
+            for second in &ids[index + 1..] {
+                if !map.graph().are_adjacent(first, second) {
+                    map.add_edge(first, second, 1, ConnectionKind::Lane).unwrap();
+                }
+            }
+        }
+        let mut output = CaptureSink::new();
+
+        // Input runs out before the first player chooses
+        let mut input = ScriptSource::new(Vec::<String>::new());
+        game.run_starting_draft(&mut input, &mut output, &mut StdRng::seed_from_u64(3)).unwrap();
+
+        let [first, second] = <[String; 2]>::try_from(drafters(&output)).unwrap();
+        let state = &game.game_state;
+        assert!(offered(&output, &first).contains(&test_support::home_planet(state, &first)));
+        let told = output.texts_for(&OutputTarget::Player(second.clone()));
+        assert!(told[0].contains("no candidates are left"), "{told:?}");
+        assert_ne!(test_support::home_planet(state, &first), test_support::home_planet(state, &second));
+    }
+}
@@ -0,0 +1,618 @@
+use std::path::PathBuf;
+
+pub use crate::engine::map::{MapSize, RenderStyle};
+use crate::engine::save::DEFAULT_SAVE_DIR;
+
+use crate::engine::configs::player_names::{PlayerNameConfigError, generate_random_names};
+pub use crate::engine::game_rules::MAX_FACTIONS;
+use crate::engine::game_rules::{GameRules, GameSpeed, ProductionTiming, RetentionRules, RulesProfile, RulesProfileError, UnrestRules, VictoryCondition};
+use crate::engine::resources::Resources;
+use crate::engine::utils;
+
+use super::input::{InputError, InputSource, StdinSource};
+use super::setup_file::SetupFileError;
+use super::setup_wizard::SetupWizard;
+
+#[derive(Debug, thiserror::Error)]
+pub enum GameConfigurationError {
+    #[error(transparent)]
+    PlayerNameConfigError(#[from] PlayerNameConfigError),
+
+    #[error("Configuration aborted: {0}")]
+    InputError(#[from] InputError),
+
+    #[error("Commander name cannot be empty.")]
+    EmptyPlayerName,
+
+    #[error("Commander name '{0}' exceeds {MAX_PLAYER_NAME_LENGTH} characters.")]
+    PlayerNameTooLong(String),
+
+    #[error("Commander name '{0}' already registered. Choose unique designation.")]
+    DuplicatePlayerName(String),
+
+    #[error("Invalid commander count: {humans} human and {ai} AI. Colonial doctrine requires at least 1 human and 2-{MAX_FACTIONS} factions.")]
+    InvalidPlayerCount { humans: usize, ai: u8 },
+
+    #[error("Every faction needs at least one planet.")]
+    NoPlanetsPerFaction,
+
+    #[error(
+        "A {map_size} star system has {} planets, too few for {factions} factions at {planets_per_faction} each. {}",
+        .map_size.num_planets(), larger_map_hint(*.factions, *.planets_per_faction),
+    )]
+    MapTooSmall { map_size: MapSize, factions: usize, planets_per_faction: u32 },
+
+    #[error(transparent)]
+    InvalidRulesProfile(#[from] RulesProfileError),
+
+    #[error("Ruin chance must be between 0 and 100 percent, got {0}")]
+    InvalidRuinChance(u32),
+
+    #[error("Team member '{0}' is not a registered commander.")]
+    UnknownTeamMember(String),
+
+    #[error("Commander '{0}' cannot serve on more than one team.")]
+    DuplicateTeamMember(String),
+
+    #[error("Teams must leave at least two opposing sides.")]
+    NoOpposingSides,
+
+    /// An answer to a setup query that does not parse; the query is asked again
+    #[error("{0}")]
+    InvalidAnswer(String),
+
+    #[error(transparent)]
+    SetupFile(#[from] SetupFileError),
+
+    /// A value from a setup file or `--set` that does not fit the rest of the setup
+    #[error("{field} = {value} ({origin}): {reason}{}", related_sources(.related))]
+    SetupConflict { field: String, value: String, origin: String, reason: String, related: Vec<String> },
+}
+
+pub const MAX_PLAYER_NAME_LENGTH: usize = 24;
+
+/// Planets the map must hold for each faction unless configured otherwise.
+pub const DEFAULT_PLANETS_PER_FACTION: u32 = 3;
+
+fn larger_map_hint(factions: usize, planets_per_faction: u32) -> String {
+    match MapSize::smallest_with(factions as u32 * planets_per_faction) {
+        Some(size) => format!("Choose a {} map or larger.", size),
+        None => String::from("Deploy fewer factions."),
+    }
+}
+
+fn related_sources(related: &[String]) -> String {
+    match related {
+        [] => String::new(),
+        related => format!(" Depends on {}.", related.join(", ")),
+    }
+}
+
+/// Checks a `map_size` map holds `planets_per_faction` planets for each of `factions`, so
+/// they start with room to expand.
+pub(crate) fn check_map_capacity(map_size: MapSize, factions: usize, planets_per_faction: u32) -> Result<(), GameConfigurationError> {
+    if (map_size.num_planets() as usize) < factions * planets_per_faction as usize {
+        return Err(GameConfigurationError::MapTooSmall { map_size, factions, planets_per_faction });
+    }
+    Ok(())
+}
+
+/// Trims a player name and checks it is non-empty, not too long and does not collide with
+/// any existing name once normalized to a PlayerId.
+pub(crate) fn validate_player_name(name: &str, existing: &[String]) -> Result<String, GameConfigurationError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(GameConfigurationError::EmptyPlayerName);
+    }
+    if name.chars().count() > MAX_PLAYER_NAME_LENGTH {
+        return Err(GameConfigurationError::PlayerNameTooLong(name.to_string()));
+    }
+
+    let id = utils::name_to_id(name);
+    if existing.iter().any(|other| utils::name_to_id(other) == id) {
+        return Err(GameConfigurationError::DuplicatePlayerName(name.to_string()));
+    }
+
+    Ok(name.to_string())
+}
+
+/// Gives each player the number of the team listing them, counting teams from 1, or None
+/// for players fighting alone. Team members are matched by name like player names are.
+pub(crate) fn assign_teams(player_names: &[String], teams: &[Vec<String>]) -> Result<Vec<Option<u8>>, GameConfigurationError> {
+    let mut assigned: Vec<Option<u8>> = vec![None; player_names.len()];
+    for (team, members) in (1..).zip(teams) {
+        for member in members {
+            let id = utils::name_to_id(member.trim());
+            let index = player_names.iter()
+                .position(|name| utils::name_to_id(name) == id)
+                .ok_or_else(|| GameConfigurationError::UnknownTeamMember(member.clone()))?;
+            if assigned[index].is_some() {
+                return Err(GameConfigurationError::DuplicateTeamMember(player_names[index].clone()));
+            }
+            assigned[index] = Some(team);
+        }
+    }
+
+    // Every team is one side, and so is every player without a team
+    let solo = assigned.iter().filter(|team| team.is_none()).count();
+    let mut team_numbers: Vec<_> = assigned.iter().flatten().collect();
+    team_numbers.sort();
+    team_numbers.dedup();
+    if solo + team_numbers.len() < 2 {
+        return Err(GameConfigurationError::NoOpposingSides);
+    }
+
+    Ok(assigned)
+}
+
+pub struct GameConfiguration {
+    #[allow(dead_code)]
+    pub(crate) num_of_players: u8,
+    pub(crate) player_names: Vec<String>,
+    /// Team number of each player in `player_names`; players sharing one are allies
+    pub(crate) teams: Vec<Option<u8>>,
+    #[allow(dead_code)]
+    pub(crate) num_of_ai: u8,
+    pub(crate) map_size: MapSize,
+    /// Planets the map must hold for each faction, checked when the game is created
+    pub(crate) planets_per_faction: u32,
+    /// Alternative planet name pack; the shipped `data/planet_names.json` is used when unset
+    pub(crate) planet_names_path: Option<PathBuf>,
+    pub(crate) rules: GameRules,
+    pub(crate) save_dir: PathBuf,
+    /// Whether saves and replays are written gzip-compressed; both kinds can always be loaded
+    pub(crate) compress_saves: bool,
+    /// Columns of the player's terminal when known; narrow terminals get the list map view
+    pub(crate) terminal_width: Option<usize>,
+    /// Characters the player's terminal can show; can be changed during the session
+    pub(crate) render_style: RenderStyle,
+    /// File every game event is appended to as a JSON line, for external tools
+    pub(crate) event_log_path: Option<PathBuf>,
+    /// Whether players pick their starting planets from candidates instead of getting random ones
+    pub(crate) starting_draft: bool,
+    /// Whether a galaxy news summary is broadcast after every full round
+    pub(crate) round_report: bool,
+    /// Whether developer tools such as `inspect` are available
+    pub(crate) dev_mode: bool,
+    /// Whether the advisor's suggestions are shown at the start of the first turns
+    pub(crate) advisor: bool,
+    /// File the session's replay is written to when it ends
+    pub(crate) replay_path: Option<PathBuf>,
+    /// Seed the game is set up from; a fresh one when unset
+    pub(crate) seed: Option<u64>,
+}
+
+impl GameConfiguration {
+    /// Checks the map is large enough for every faction, human and AI.
+    pub(crate) fn check_map_capacity(&self) -> Result<(), GameConfigurationError> {
+        check_map_capacity(self.map_size, self.player_names.len() + self.num_of_ai as usize, self.planets_per_faction)
+    }
+
+    /// Records the width of the player's terminal, as detected by the frontend.
+    pub fn with_terminal_width(mut self, columns: Option<usize>) -> Self {
+        self.terminal_width = columns;
+        self
+    }
+
+    /// Records which characters the player's terminal can show, as chosen by the frontend.
+    pub fn with_render_style(mut self, style: RenderStyle) -> Self {
+        self.render_style = style;
+        self
+    }
+
+    /// Appends every game event to `path` as a JSON line, as requested on the frontend's command line.
+    pub fn with_event_log(mut self, path: Option<PathBuf>) -> Self {
+        self.event_log_path = path;
+        self
+    }
+
+    /// Records the session as a replay written to `path`, as requested on the frontend's command line.
+    pub fn with_replay(mut self, path: Option<PathBuf>) -> Self {
+        self.replay_path = path;
+        self
+    }
+
+    /// Makes developer tools such as `inspect` available, as requested on the frontend's command line.
+    pub fn with_dev_mode(mut self, dev_mode: bool) -> Self {
+        self.dev_mode = dev_mode;
+        self
+    }
+
+    /// Shows the advisor's suggestions at the start of the first turns, unless the frontend's
+    /// command line turned them off.
+    pub fn with_advisor(mut self, advisor: bool) -> Self {
+        self.advisor = advisor;
+        self
+    }
+
+    /// Creates a debug configuration with preset values.
+    /// Use this during development to skip interactive prompts.
+    #[cfg(debug_assertions)]
+    pub fn debug_default() -> Result<GameConfiguration, GameConfigurationError> {
+        let player_names = generate_random_names(2)?;
+
+        println!("Players: {:?}", player_names);
+        println!("Map size: Small\n");
+
+        Ok(GameConfiguration {
+            num_of_players: 2,
+            teams: vec![None; player_names.len()],
+            player_names,
+            num_of_ai: 0,
+            map_size: MapSize::Small,
+            planets_per_faction: DEFAULT_PLANETS_PER_FACTION,
+            planet_names_path: None,
+            rules: GameRules::default(),
+            save_dir: PathBuf::from(DEFAULT_SAVE_DIR),
+            compress_saves: true,
+            terminal_width: None,
+            render_style: RenderStyle::default(),
+            event_log_path: None,
+            starting_draft: false,
+            round_report: false,
+            dev_mode: false,
+            advisor: true,
+            replay_path: None,
+            seed: None,
+        })
+    }
+
+    /// Asks for the configuration on plain stdin.
+    pub fn new() -> Result<GameConfiguration, GameConfigurationError> {
+        Self::from_prompts(&mut StdinSource::stdin())
+    }
+
+    /// Asks for the configuration, reading answers from `input` such as a frontend's line editor.
+    pub fn from_prompts(input: &mut dyn InputSource) -> Result<GameConfiguration, GameConfigurationError> {
+        SetupWizard::new().run(input)
+    }
+}
+
+/// Builds a configuration without interactive prompts, applying the same validation.
+#[derive(Default)]
+pub struct GameConfigurationBuilder {
+    player_names: Vec<String>,
+    teams: Vec<Vec<String>>,
+    num_of_ai: u8,
+    map_size: Option<MapSize>,
+    planets_per_faction: Option<u32>,
+    planet_names_path: Option<PathBuf>,
+    rules: GameRules,
+    save_dir: Option<PathBuf>,
+    compress_saves: Option<bool>,
+    terminal_width: Option<usize>,
+    render_style: RenderStyle,
+    event_log_path: Option<PathBuf>,
+    starting_draft: bool,
+    round_report: bool,
+    seed: Option<u64>,
+}
+
+impl GameConfigurationBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn player_name(mut self, name: impl Into<String>) -> Self {
+        self.player_names.push(name.into());
+        self
+    }
+
+    /// Puts the named players on one team; teams are numbered in the order they are added.
+    pub fn team<I>(mut self, members: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.teams.push(members.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn ai_players(mut self, count: u8) -> Self {
+        self.num_of_ai = count;
+        self
+    }
+
+    /// Defaults to `MapSize::Small` when not set.
+    pub fn map_size(mut self, map_size: MapSize) -> Self {
+        self.map_size = Some(map_size);
+        self
+    }
+
+    /// Planets the map must hold for each faction; `DEFAULT_PLANETS_PER_FACTION` when not set.
+    pub fn planets_per_faction(mut self, planets: u32) -> Self {
+        self.planets_per_faction = Some(planets);
+        self
+    }
+
+    /// Uses an alternative planet name pack instead of the shipped one.
+    pub fn planet_names_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.planet_names_path = Some(path.into());
+        self
+    }
+
+    /// Defaults to `saves` in the working directory when not set.
+    pub fn save_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.save_dir = Some(path.into());
+        self
+    }
+
+    /// Defaults to compressed saves and replays when not set.
+    pub fn compress_saves(mut self, compress: bool) -> Self {
+        self.compress_saves = Some(compress);
+        self
+    }
+
+    /// Unknown width when not set, which keeps the grid map view.
+    pub fn terminal_width(mut self, columns: usize) -> Self {
+        self.terminal_width = Some(columns);
+        self
+    }
+
+    /// Unicode when not set.
+    pub fn render_style(mut self, style: RenderStyle) -> Self {
+        self.render_style = style;
+        self
+    }
+
+    /// No event log when not set.
+    pub fn event_log(mut self, path: impl Into<PathBuf>) -> Self {
+        self.event_log_path = Some(path.into());
+        self
+    }
+
+    /// Random starting planets when not set.
+    pub fn starting_draft(mut self, draft: bool) -> Self {
+        self.starting_draft = draft;
+        self
+    }
+
+    /// No galaxy news between rounds when not set.
+    pub fn round_report(mut self, enabled: bool) -> Self {
+        self.round_report = enabled;
+        self
+    }
+
+    /// Classic rules when not set. Set knobs afterwards to adjust the profile.
+    pub fn rules_profile(mut self, profile: RulesProfile) -> Self {
+        self.rules.profile = profile;
+        self
+    }
+
+    pub fn cancellation_penalty_percent(mut self, percent: u32) -> Self {
+        self.rules.profile.cancellation_penalty_percent = percent;
+        self
+    }
+
+    /// Chance for each neutral planet to hold ruins.
+    pub fn ruin_chance_percent(mut self, percent: u32) -> Self {
+        self.rules.ruin_chance_percent = percent;
+        self
+    }
+
+    /// Normal speed when not set.
+    pub fn speed(mut self, speed: GameSpeed) -> Self {
+        self.rules.speed = speed;
+        self
+    }
+
+    /// Production at the start of each player's turn when not set.
+    pub fn production_timing(mut self, timing: ProductionTiming) -> Self {
+        self.rules.production_timing = timing;
+        self
+    }
+
+    /// Whether builds can be queued on prerequisites still under construction; off when not set.
+    pub fn prerequisites_under_construction(mut self, enabled: bool) -> Self {
+        self.rules.prerequisites_under_construction = enabled;
+        self
+    }
+
+    /// Opening turns in which players cannot bombard, blockade or invade each other, such as
+    /// `DEFAULT_PROTECTED_TURNS`; no protected expansion when not set.
+    pub fn protected_turns(mut self, turns: u32) -> Self {
+        self.rules.protected_turns = turns;
+        self
+    }
+
+    /// Total ship size a fleet can hold; `DEFAULT_FLEET_CAPACITY` when not set.
+    pub fn fleet_capacity(mut self, capacity: usize) -> Self {
+        self.rules.profile.fleet_capacity = capacity;
+        self
+    }
+
+    /// What colonizing a neutral planet costs next to one of the player's planets, and what
+    /// each further connection adds; `DEFAULT_COLONIZATION_BASE_COST` and
+    /// `DEFAULT_COLONIZATION_COST_PER_HOP` when not set.
+    pub fn colonization_cost(mut self, base: Resources, per_hop: Resources) -> Self {
+        self.rules.profile.colonization_base_cost = base;
+        self.rules.profile.colonization_cost_per_hop = per_hop;
+        self
+    }
+
+    /// What a colony gets from the ark used up founding it; `DEFAULT_ARK_COLONY_BONUS` when not set.
+    pub fn ark_colony_bonus(mut self, bonus: Resources) -> Self {
+        self.rules.profile.ark_colony_bonus = bonus;
+        self
+    }
+
+    /// How many hops from its owner's nearest planet a fleet stays supplied, and how fast
+    /// fleets beyond wear down; `DEFAULT_SUPPLY_RANGE`, `DEFAULT_ATTRITION_CHANCE_PERCENT` and
+    /// `DEFAULT_ATTRITION_SHIELD_LOSS` when not set.
+    pub fn supply(mut self, range: u32, attrition_chance_percent: u32, shield_loss: u32) -> Self {
+        self.rules.profile.supply_range = range;
+        self.rules.profile.attrition_chance_percent = attrition_chance_percent;
+        self.rules.profile.attrition_shield_loss = shield_loss;
+        self
+    }
+
+    /// How bombardment and blockade raise unrest and how it fades; `UnrestRules::default()`
+    /// when not set.
+    pub fn unrest(mut self, unrest: UnrestRules) -> Self {
+        self.rules.unrest = unrest;
+        self
+    }
+
+    /// How much of the game's past saves keep; `RetentionRules::default()` when not set.
+    pub fn retention(mut self, retention: RetentionRules) -> Self {
+        self.rules.retention = retention;
+        self
+    }
+
+    /// Sets the game up from `seed`, so the same setup generates the same galaxy; a fresh
+    /// seed when not set.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Elimination only when not set.
+    pub fn victory(mut self, victory: VictoryCondition) -> Self {
+        self.rules.victory = victory;
+        self
+    }
+
+    pub fn build(self) -> Result<GameConfiguration, GameConfigurationError> {
+        let mut player_names: Vec<String> = Vec::with_capacity(self.player_names.len());
+        for name in &self.player_names {
+            let name = validate_player_name(name, &player_names)?;
+            player_names.push(name);
+        }
+
+        let humans = player_names.len();
+        if humans == 0 || !(2..=MAX_FACTIONS).contains(&(humans + self.num_of_ai as usize)) {
+            return Err(GameConfigurationError::InvalidPlayerCount { humans, ai: self.num_of_ai });
+        }
+        let planets_per_faction = self.planets_per_faction.unwrap_or(DEFAULT_PLANETS_PER_FACTION);
+        if planets_per_faction == 0 {
+            return Err(GameConfigurationError::NoPlanetsPerFaction);
+        }
+
+        self.rules.profile.validate()?;
+        if self.rules.ruin_chance_percent > 100 {
+            return Err(GameConfigurationError::InvalidRuinChance(self.rules.ruin_chance_percent));
+        }
+
+        let teams = assign_teams(&player_names, &self.teams)?;
+
+        Ok(GameConfiguration {
+            num_of_players: humans as u8,
+            player_names,
+            teams,
+            num_of_ai: self.num_of_ai,
+            map_size: self.map_size.unwrap_or(MapSize::Small),
+            planets_per_faction,
+            planet_names_path: self.planet_names_path,
+            rules: self.rules,
+            save_dir: self.save_dir.unwrap_or_else(|| PathBuf::from(DEFAULT_SAVE_DIR)),
+            compress_saves: self.compress_saves.unwrap_or(true),
+            terminal_width: self.terminal_width,
+            render_style: self.render_style,
+            event_log_path: self.event_log_path,
+            starting_draft: self.starting_draft,
+            round_report: self.round_report,
+            dev_mode: false,
+            advisor: true,
+            replay_path: None,
+            seed: self.seed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_trims_names() {
+        let config = GameConfigurationBuilder::new()
+            .player_name("  Alice ")
+            .player_name("Bob")
+            .build()
+            .unwrap();
+
+        assert_eq!(config.player_names, vec!["Alice", "Bob"]);
+    }
+
+    #[test]
+    fn test_builder_rejects_names_with_same_id() {
+        let result = GameConfigurationBuilder::new()
+            .player_name("Bob")
+            .player_name("bob")
+            .build();
+
+        assert!(matches!(result, Err(GameConfigurationError::DuplicatePlayerName(name)) if name == "bob"));
+    }
+
+    #[test]
+    fn test_builder_rejects_empty_and_long_names() {
+        let empty = GameConfigurationBuilder::new().player_name("   ").player_name("Bob").build();
+        assert!(matches!(empty, Err(GameConfigurationError::EmptyPlayerName)));
+
+        let long = GameConfigurationBuilder::new()
+            .player_name("A".repeat(MAX_PLAYER_NAME_LENGTH + 1))
+            .player_name("Bob")
+            .build();
+        assert!(matches!(long, Err(GameConfigurationError::PlayerNameTooLong(_))));
+    }
+
+    #[test]
+    fn test_builder_rejects_penalty_above_100_percent() {
+        let result = GameConfigurationBuilder::new()
+            .player_name("Alice")
+            .player_name("Bob")
+            .cancellation_penalty_percent(101)
+            .build();
+
+        assert!(matches!(
+            result,
+            Err(GameConfigurationError::InvalidRulesProfile(RulesProfileError::InvalidCancellationPenalty(101)))
+        ));
+    }
+
+    #[test]
+    fn test_builder_requires_two_factions() {
+        let result = GameConfigurationBuilder::new().player_name("Alice").build();
+
+        assert!(matches!(result, Err(GameConfigurationError::InvalidPlayerCount { humans: 1, ai: 0 })));
+    }
+
+    #[test]
+    fn test_builder_caps_the_factions() {
+        let eight = four_players().ai_players(4).build();
+        assert!(eight.is_ok());
+
+        let nine = four_players().ai_players(5).build();
+        assert!(matches!(nine, Err(GameConfigurationError::InvalidPlayerCount { humans: 4, ai: 5 })));
+
+        let no_room = four_players().planets_per_faction(0).build();
+        assert!(matches!(no_room, Err(GameConfigurationError::NoPlanetsPerFaction)));
+    }
+
+    fn four_players() -> GameConfigurationBuilder {
+        ["Alice", "Bob", "Carol", "Dave"].into_iter()
+            .fold(GameConfigurationBuilder::new(), |builder, name| builder.player_name(name))
+    }
+
+    #[test]
+    fn test_builder_assigns_team_numbers_in_order() {
+        let config = four_players().team(["alice", "Carol"]).team(["Bob", "Dave"]).build().unwrap();
+
+        assert_eq!(config.teams, vec![Some(1), Some(2), Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_teams() {
+        let unknown = four_players().team(["Alice", "Mallory"]).build();
+        assert!(matches!(unknown, Err(GameConfigurationError::UnknownTeamMember(name)) if name == "Mallory"));
+
+        let twice = four_players().team(["Alice", "Bob"]).team(["Alice", "Carol"]).build();
+        assert!(matches!(twice, Err(GameConfigurationError::DuplicateTeamMember(name)) if name == "Alice"));
+
+        let one_side = four_players().team(["Alice", "Bob", "Carol", "Dave"]).build();
+        assert!(matches!(one_side, Err(GameConfigurationError::NoOpposingSides)));
+
+        // A lone player still opposes a team
+        assert!(four_players().team(["Alice", "Bob", "Carol"]).build().is_ok());
+    }
+}
@@ -0,0 +1,339 @@
+use std::collections::VecDeque;
+use std::io::{self, BufRead, IsTerminal, StdinLock, Write, stdin, stdout};
+use std::sync::mpsc::Receiver;
+
+#[derive(Debug, thiserror::Error)]
+pub enum InputError {
+    #[error("Input stream closed")]
+    EndOfInput,
+
+    #[error("Input is not valid UTF-8")]
+    InvalidUtf8,
+
+    #[error("Terminal input failure - {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Offers completions for the last word of a partially typed line.
+pub type Completer<'a> = &'a dyn Fn(&str) -> Vec<String>;
+
+/// Outcome of reading one line.
+#[derive(Debug, PartialEq)]
+pub enum ReadLine {
+    Line(String),
+    /// The player cancelled the line being typed (Ctrl-C in a line editor)
+    Interrupted,
+}
+
+/// Where player input comes from, so the game loop and setup prompts can be driven by stdin,
+/// a frontend's line editor, a script or a channel fed from elsewhere.
+pub trait InputSource {
+    /// Shows `prompt` and returns the next line, or `InputError::EndOfInput` once the source
+    /// is exhausted. Sources without completion support ignore `completer`. Non-interactive
+    /// sources skip blank lines and `#` comments, so script files can be annotated.
+    fn next_line(&mut self, prompt: &str, completer: Completer) -> Result<ReadLine, InputError>;
+
+    /// Whether a person is typing, so invalid input can be re-prompted and questions asked.
+    fn is_interactive(&self) -> bool;
+
+    /// Drops lines that already arrived but were not read yet, returning how many. Sources
+    /// that cannot tell queued input from input still being typed drop nothing.
+    fn flush_pending(&mut self) -> usize {
+        0
+    }
+
+    /// Whether queued input is flushed and the next player has to acknowledge taking the seat
+    /// when the acting player changes, so pasted lines cannot spill into their turn.
+    fn guards_turn_boundaries(&self) -> bool {
+        self.is_interactive()
+    }
+}
+
+/// Plain line reading over any buffered source, without history or completion.
+pub struct ReaderSource<R> {
+    reader: R,
+    interactive: bool,
+}
+
+/// Standard input read line by line.
+pub type StdinSource = ReaderSource<StdinLock<'static>>;
+
+impl<R: BufRead> ReaderSource<R> {
+    pub fn new(reader: R, interactive: bool) -> Self {
+        ReaderSource { reader, interactive }
+    }
+}
+
+impl StdinSource {
+    /// Reads stdin, treating it as interactive when it is a terminal.
+    pub fn stdin() -> Self {
+        ReaderSource::new(stdin().lock(), stdin().is_terminal())
+    }
+}
+
+impl<R: BufRead> InputSource for ReaderSource<R> {
+    fn next_line(&mut self, prompt: &str, _completer: Completer) -> Result<ReadLine, InputError> {
+        print!("{prompt}");
+        stdout().flush()?;
+
+        loop {
+            let mut bytes = Vec::new();
+            if self.reader.read_until(b'\n', &mut bytes)? == 0 {
+                return Err(InputError::EndOfInput);
+            }
+
+            let line = String::from_utf8(bytes).map_err(|_| InputError::InvalidUtf8)?;
+            if self.interactive || !is_annotation(&line) {
+                return Ok(ReadLine::Line(line));
+            }
+        }
+    }
+
+    fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+}
+
+/// Canned lines, for scripted sessions and tests. Prompts are not printed.
+pub struct ScriptSource {
+    lines: VecDeque<String>,
+    interactive: bool,
+}
+
+impl ScriptSource {
+    /// A non-interactive script: confirmation questions are skipped, as for piped input.
+    pub fn new<I>(lines: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        ScriptSource { lines: lines.into_iter().map(Into::into).collect(), interactive: false }
+    }
+
+    /// Replays the lines as if a person typed them, answering confirmation prompts.
+    pub fn with_interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+}
+
+impl InputSource for ScriptSource {
+    fn next_line(&mut self, _prompt: &str, _completer: Completer) -> Result<ReadLine, InputError> {
+        loop {
+            let line = self.lines.pop_front().ok_or(InputError::EndOfInput)?;
+            if self.interactive || !is_annotation(&line) {
+                return Ok(ReadLine::Line(line));
+            }
+        }
+    }
+
+    fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+
+    /// Scripts are written for several players on purpose.
+    fn guards_turn_boundaries(&self) -> bool {
+        false
+    }
+}
+
+/// Lines sent from another thread, such as a network connection handler. Input ends when
+/// every sender is dropped.
+pub struct ChannelSource {
+    receiver: Receiver<String>,
+}
+
+impl ChannelSource {
+    pub fn new(receiver: Receiver<String>) -> Self {
+        ChannelSource { receiver }
+    }
+}
+
+impl InputSource for ChannelSource {
+    fn next_line(&mut self, _prompt: &str, _completer: Completer) -> Result<ReadLine, InputError> {
+        self.receiver.recv().map(ReadLine::Line).map_err(|_| InputError::EndOfInput)
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
+    fn flush_pending(&mut self) -> usize {
+        self.receiver.try_iter().count()
+    }
+}
+
+/// Blank lines and lines starting with `#`, which scripts use for layout and notes.
+fn is_annotation(line: &str) -> bool {
+    let line = line.trim();
+    line.is_empty() || line.starts_with('#')
+}
+
+/// Never offers anything; for prompts that are not commands.
+pub fn no_completion(_line: &str) -> Vec<String> {
+    Vec::new()
+}
+
+/// Prompts until `parser` accepts a line of input.
+/// Returns `InputError::EndOfInput` once input is closed. Invalid UTF-8 is reported and
+/// re-prompted when interactive, but ends the session when input is piped. A cancelled
+/// line is simply prompted for again.
+pub fn read_player_input<F, T>(input: &mut dyn InputSource, parser: F) -> Result<T, InputError>
+where
+    F: Fn(&str) -> Result<T, String>
+{
+    read_with_completion(input, &no_completion, parser)
+}
+
+/// Same as `read_player_input`, offering `completer`'s suggestions while typing.
+pub fn read_with_completion<F, T>(input: &mut dyn InputSource, completer: Completer, parser: F) -> Result<T, InputError>
+where
+    F: Fn(&str) -> Result<T, String>
+{
+    loop {
+        let line = match input.next_line("> ", completer) {
+            Ok(ReadLine::Line(line)) => line,
+            Ok(ReadLine::Interrupted) => continue,
+            Err(InputError::InvalidUtf8) if input.is_interactive() => {
+                eprintln!("ERROR: {}", InputError::InvalidUtf8);
+                continue;
+            }
+            Err(error) => return Err(error),
+        };
+
+        match parser(line.trim()) {
+            Ok(parsed) => break Ok(parsed),
+            Err(error) => {
+                eprintln!("ERROR: {}", error);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::sync::mpsc;
+    use std::thread;
+
+    use super::*;
+
+    /// Replays canned reads and records what completions were offered for each prompt.
+    struct ScriptedReader {
+        reads: VecDeque<ReadLine>,
+        offered: Vec<Vec<String>>,
+    }
+
+    impl InputSource for ScriptedReader {
+        fn next_line(&mut self, _prompt: &str, completer: Completer) -> Result<ReadLine, InputError> {
+            self.offered.push(completer("st"));
+            self.reads.pop_front().ok_or(InputError::EndOfInput)
+        }
+
+        fn is_interactive(&self) -> bool {
+            true
+        }
+    }
+
+    fn scripted(reads: Vec<ReadLine>) -> ScriptedReader {
+        ScriptedReader { reads: reads.into(), offered: Vec::new() }
+    }
+
+    fn parse_number(input: &str) -> Result<u8, String> {
+        input.parse().map_err(|_| String::from("not a number"))
+    }
+
+    #[test]
+    fn test_reader_source_reprompts_until_parsed() {
+        let mut reader = ReaderSource::new(Cursor::new(&b"abc\n 7 \n"[..]), false);
+
+        assert_eq!(read_player_input(&mut reader, parse_number).unwrap(), 7);
+        assert!(matches!(read_player_input(&mut reader, parse_number), Err(InputError::EndOfInput)));
+    }
+
+    #[test]
+    fn test_invalid_utf8_ends_piped_input_only() {
+        let bytes = b"\xff\n5\n";
+
+        let mut piped = ReaderSource::new(Cursor::new(&bytes[..]), false);
+        assert!(matches!(read_player_input(&mut piped, parse_number), Err(InputError::InvalidUtf8)));
+
+        let mut terminal = ReaderSource::new(Cursor::new(&bytes[..]), true);
+        assert_eq!(read_player_input(&mut terminal, parse_number).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_interrupted_line_is_prompted_again() {
+        let mut reader = scripted(vec![ReadLine::Interrupted, ReadLine::Line(String::from("3"))]);
+
+        assert_eq!(read_player_input(&mut reader, parse_number).unwrap(), 3);
+        assert_eq!(reader.offered.len(), 2);
+    }
+
+    #[test]
+    fn test_completer_reaches_reader() {
+        let mut reader = scripted(vec![ReadLine::Line(String::from("1"))]);
+        let completer = |partial: &str| vec![format!("{partial}atus")];
+
+        read_with_completion(&mut reader, &completer, parse_number).unwrap();
+        read_player_input(&mut reader, parse_number).unwrap_err();
+
+        assert_eq!(reader.offered, vec![vec![String::from("status")], vec![]]);
+    }
+
+    #[test]
+    fn test_script_source_ends_after_last_line() {
+        let mut script = ScriptSource::new(["9", "x"]);
+
+        assert_eq!(read_player_input(&mut script, parse_number).unwrap(), 9);
+        assert!(!script.is_interactive());
+        assert!(matches!(read_player_input(&mut script, parse_number), Err(InputError::EndOfInput)));
+    }
+
+    #[test]
+    fn test_non_interactive_sources_skip_blank_lines_and_comments() {
+        let mut script = ScriptSource::new(["# setup", "", "   ", "  # note", "6"]);
+        assert_eq!(read_player_input(&mut script, parse_number).unwrap(), 6);
+
+        let mut piped = ReaderSource::new(Cursor::new(&b"# setup\n\n8\n"[..]), false);
+        assert_eq!(piped.next_line("> ", &no_completion).unwrap(), ReadLine::Line(String::from("8\n")));
+    }
+
+    #[test]
+    fn test_interactive_sources_return_blank_lines() {
+        let mut terminal = ReaderSource::new(Cursor::new(&b"\n# hi\n"[..]), true);
+
+        assert_eq!(terminal.next_line("> ", &no_completion).unwrap(), ReadLine::Line(String::from("\n")));
+        assert_eq!(terminal.next_line("> ", &no_completion).unwrap(), ReadLine::Line(String::from("# hi\n")));
+    }
+
+    #[test]
+    fn test_channel_source_ends_when_senders_drop() {
+        let (sender, receiver) = mpsc::channel();
+        let feeder = thread::spawn(move || {
+            for line in ["four", "4"] {
+                sender.send(String::from(line)).unwrap();
+            }
+        });
+        let mut channel = ChannelSource::new(receiver);
+
+        assert_eq!(read_player_input(&mut channel, parse_number).unwrap(), 4);
+        feeder.join().unwrap();
+        assert!(matches!(read_player_input(&mut channel, parse_number), Err(InputError::EndOfInput)));
+    }
+
+    #[test]
+    fn test_channel_source_flushes_queued_lines() {
+        let (sender, receiver) = mpsc::channel();
+        for line in ["1", "2", "3"] {
+            sender.send(String::from(line)).unwrap();
+        }
+        let mut channel = ChannelSource::new(receiver);
+
+        assert_eq!(read_player_input(&mut channel, parse_number).unwrap(), 1);
+        assert_eq!(channel.flush_pending(), 2);
+        sender.send(String::from("4")).unwrap();
+        assert_eq!(read_player_input(&mut channel, parse_number).unwrap(), 4);
+        assert!(!ScriptSource::new(["1"]).with_interactive(true).guards_turn_boundaries());
+    }
+}
@@ -0,0 +1,85 @@
+pub use crate::engine::appearance::PlayerAppearance;
+pub use crate::engine::game_event::OutputTarget;
+pub use crate::engine::map::RenderStyle;
+
+/// Destination for everything the game shows players, so a frontend can deliver each
+/// piece of output only to the players meant to see it.
+pub trait OutputSink {
+    fn emit(&mut self, target: OutputTarget, text: &str);
+
+    /// Reports a rejected command or failed action. Defaults to an `ERROR:` line.
+    fn emit_error(&mut self, target: OutputTarget, text: &str) {
+        self.emit(target, &format!("ERROR: {text}"));
+    }
+
+    /// Called with each player's name and appearance, in turn order, when a session starts
+    /// and whenever a load replaces the players. Sinks that highlight players use it.
+    fn players_changed(&mut self, _players: &[(String, PlayerAppearance)]) {}
+}
+
+/// Hot-seat output: players share the terminal, so every target is printed. Errors go to stderr.
+pub struct StdoutSink;
+
+impl OutputSink for StdoutSink {
+    fn emit(&mut self, _target: OutputTarget, text: &str) {
+        println!("{text}");
+    }
+
+    fn emit_error(&mut self, _target: OutputTarget, text: &str) {
+        eprintln!("ERROR: {text}");
+    }
+}
+
+/// Rewrites everything passed to `inner` for a render style, so terminals and log files
+/// that cannot show Unicode get plain ASCII.
+pub struct StyledSink<'a> {
+    inner: &'a mut dyn OutputSink,
+    style: RenderStyle,
+}
+
+impl<'a> StyledSink<'a> {
+    pub fn new(inner: &'a mut dyn OutputSink, style: RenderStyle) -> Self {
+        StyledSink { inner, style }
+    }
+}
+
+impl OutputSink for StyledSink<'_> {
+    fn emit(&mut self, target: OutputTarget, text: &str) {
+        self.inner.emit(target, &self.style.apply(text));
+    }
+
+    fn emit_error(&mut self, target: OutputTarget, text: &str) {
+        self.inner.emit_error(target, &self.style.apply(text));
+    }
+
+    fn players_changed(&mut self, players: &[(String, PlayerAppearance)]) {
+        self.inner.players_changed(players);
+    }
+}
+
+/// Keeps output in memory along with its target, for tests and for frontends that forward
+/// it to each player's own connection.
+#[derive(Debug, Default)]
+pub struct CaptureSink {
+    pub entries: Vec<(OutputTarget, String)>,
+}
+
+impl CaptureSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Texts emitted with exactly `target`, in order.
+    pub fn texts_for(&self, target: &OutputTarget) -> Vec<&str> {
+        self.entries.iter()
+            .filter(|(entry_target, _)| entry_target == target)
+            .map(|(_, text)| text.as_str())
+            .collect()
+    }
+}
+
+impl OutputSink for CaptureSink {
+    fn emit(&mut self, target: OutputTarget, text: &str) {
+        self.entries.push((target, text.to_string()));
+    }
+}
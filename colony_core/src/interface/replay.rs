@@ -0,0 +1,690 @@
+//! Recording sessions as replays and stepping through them afterwards. A replay stores the
+//! setup a game was generated from, seed included, and every command that changed the game;
+//! viewing it sets the same game up again and applies the commands one at a time.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::engine::appearance;
+use crate::engine::commands::command::{Command, CommandEffect};
+use crate::engine::commands::parser;
+use crate::engine::configs::PlanetNameParts;
+use crate::engine::configs::ship_config::ShipConfig;
+use crate::engine::configs::structure_config::StructureConfig;
+use crate::engine::game_event::GameEvent;
+use crate::engine::game_rules::GameRules;
+use crate::engine::game_state::{GamePhase, GameState};
+use crate::engine::map::{Map, MapSize, RenderStyle};
+use crate::engine::planet_name_generator::{PlanetNameGenerator, PlanetNameGeneratorError};
+use crate::engine::save;
+use crate::engine::player::{Player, PlayerId};
+use crate::engine::utils;
+
+use super::game::GameError;
+use super::game_configuration::GameConfiguration;
+use super::input::{InputError, InputSource, read_player_input};
+use super::output::{OutputSink, OutputTarget, StyledSink};
+
+/// Version written into new replays; older games cannot be set up the same way again.
+pub const REPLAY_FORMAT_VERSION: u32 = 1;
+
+/// Turns between the states a viewer keeps, so `goto` only replays the turns since the last one
+pub const SNAPSHOT_INTERVAL: u32 = 10;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReplayError {
+    #[error("Failed to access replay file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to encode or decode replay: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Replay file is corrupt or truncated (compressed data): {0}")]
+    Compression(std::io::Error),
+
+    #[error("Replay format version {found} is not supported (expected {supported})")]
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    #[error(transparent)]
+    Setup(#[from] GameError),
+
+    #[error(
+        "Replay diverged at command {index}: it was recorded for {recorded}, but it is {actual}. \
+         The data files may have changed since the game was played"
+    )]
+    Diverged { index: usize, recorded: String, actual: String },
+
+    #[error("The replay ends on turn {last}, before turn {turn}")]
+    TurnNotReached { turn: u32, last: u32 },
+
+    #[error("No player '{name}' in this game; choose one of {players} or 'omniscient'")]
+    UnknownPlayer { name: String, players: String },
+}
+
+/// Everything a game is generated from besides the data files. The same setup always gives
+/// the same map, turn order and starting planets.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameSetup {
+    pub seed: u64,
+    /// Names in the order players joined, which decides their colors and tags
+    pub player_names: Vec<String>,
+    pub teams: Vec<Option<u8>>,
+    pub map_size: MapSize,
+    /// Alternative planet name pack, when the game did not use the shipped one
+    pub planet_names_path: Option<PathBuf>,
+    pub rules: GameRules,
+    /// Whether starting planets are left for players to draft
+    pub starting_draft: bool,
+}
+
+impl GameSetup {
+    pub(crate) fn from_configuration(configuration: &GameConfiguration, seed: u64) -> Self {
+        GameSetup {
+            seed,
+            player_names: configuration.player_names.clone(),
+            teams: configuration.teams.clone(),
+            map_size: configuration.map_size,
+            planet_names_path: configuration.planet_names_path.clone(),
+            rules: configuration.rules.clone(),
+            starting_draft: configuration.starting_draft,
+        }
+    }
+
+    /// Sets the game up with the configs from the data directory.
+    pub fn load_game(&self) -> Result<GameState, GameError> {
+        let players = self.players()?;
+        self.generate(players, StructureConfig::load()?, ShipConfig::load()?, self.load_name_parts()?)
+    }
+
+    /// Reads the planet name pack the setup names its planets from.
+    pub fn load_name_parts(&self) -> Result<PlanetNameParts, GameError> {
+        let name_parts = match &self.planet_names_path {
+            Some(path) => PlanetNameParts::load_from_path(path),
+            None => PlanetNameParts::load(),
+        };
+        Ok(name_parts.map_err(PlanetNameGeneratorError::from)?)
+    }
+
+    /// Sets the game up with the given configs.
+    pub fn new_game(
+        &self,
+        structure_config: StructureConfig,
+        ship_config: ShipConfig,
+        name_parts: PlanetNameParts,
+    ) -> Result<GameState, GameError> {
+        self.generate(self.players()?, structure_config, ship_config, name_parts)
+    }
+
+    fn players(&self) -> Result<BTreeMap<PlayerId, Player>, GameError> {
+        // Colors and tags follow the order players joined in
+        let names: Vec<_> = self.player_names.iter().map(String::as_str).collect();
+        let mut players = BTreeMap::new();
+        for (index, (name, appearance)) in names.iter().zip(appearance::assign(&names)).enumerate() {
+            let player_id = utils::name_to_id(name);
+            if players.contains_key(&player_id) {
+                return Err(GameError::DuplicatePlayer(player_id));
+            }
+            let mut player = Player::new(player_id.clone(), name.to_string(), appearance);
+            player.team = self.teams.get(index).copied().flatten();
+            players.insert(player_id, player);
+        }
+        Ok(players)
+    }
+
+    fn generate(
+        &self,
+        mut players: BTreeMap<PlayerId, Player>,
+        structure_config: StructureConfig,
+        ship_config: ShipConfig,
+        name_parts: PlanetNameParts,
+    ) -> Result<GameState, GameError> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut players_order: Vec<_> = players.keys().cloned().collect();
+        players_order.shuffle(&mut rng);
+
+        let mut name_generator = PlanetNameGenerator::from_parts(name_parts)?.seeded(self.seed);
+        let mut map = Map::generate(self.map_size, &mut name_generator, &mut rng)?;
+
+        // Assign starting planets to players and colonize them, unless players draft them
+        if !self.starting_draft {
+            map.assign_starting_planets(&mut players, &structure_config, self.rules.speed, &mut rng)?;
+            // Only the remaining neutral planets get ruins and can be key planets
+            map.place_ruins(self.rules.ruin_chance_percent, &mut rng);
+            if self.rules.victory.allows_domination() {
+                map.place_key_planets(self.rules.key_planets, &mut rng);
+            }
+        }
+
+        let mut game_state = GameState::new(players, players_order.into(), map, structure_config, ship_config, self.rules.clone())?
+            .with_seed(self.seed);
+        if self.starting_draft {
+            game_state.game_phase = GamePhase::Setup;
+        }
+        Ok(game_state)
+    }
+}
+
+/// One command line as a session applied it, after alias expansion.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayCommand {
+    pub turn: u32,
+    pub player_id: PlayerId,
+    pub line: String,
+}
+
+/// A replayed command with the events it produced, or the reason it was rejected.
+pub type Step = (ReplayCommand, Result<Vec<GameEvent>, String>);
+
+/// A recorded game: its setup and every command that changed it or was an order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub format_version: u32,
+    pub setup: GameSetup,
+    pub commands: Vec<ReplayCommand>,
+}
+
+impl Replay {
+    pub fn new(setup: GameSetup) -> Self {
+        Replay { format_version: REPLAY_FORMAT_VERSION, setup, commands: Vec::new() }
+    }
+
+    pub fn record(&mut self, turn: u32, player_id: &PlayerId, line: &str) {
+        self.commands.push(ReplayCommand { turn, player_id: player_id.clone(), line: line.to_string() });
+    }
+
+    /// Writes the replay as JSON, gzip-compressed if `compress` is set.
+    pub fn write(&self, path: &Path, compress: bool) -> Result<(), ReplayError> {
+        let json = serde_json::to_vec(self)?;
+        let bytes = if compress { save::gzip(&json)? } else { json };
+        fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    /// Reads a replay written by `write`, compressed or not.
+    pub fn read(path: &Path) -> Result<Replay, ReplayError> {
+        let json = save::gunzip_if_compressed(fs::read(path)?).map_err(ReplayError::Compression)?;
+        let replay: Replay = serde_json::from_slice(&json)?;
+        if replay.format_version != REPLAY_FORMAT_VERSION {
+            return Err(ReplayError::UnsupportedVersion {
+                found: replay.format_version,
+                supported: REPLAY_FORMAT_VERSION,
+            });
+        }
+        Ok(replay)
+    }
+}
+
+/// The game at the start of a turn, serialized the way saves are.
+struct Snapshot {
+    /// Commands applied when it was taken
+    position: usize,
+    turn: u32,
+    state: serde_json::Value,
+}
+
+impl Snapshot {
+    fn take(position: usize, state: &GameState) -> Result<Self, ReplayError> {
+        Ok(Snapshot { position, turn: state.turn, state: serde_json::to_value(state)? })
+    }
+}
+
+/// Whose knowledge a replay's views show.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Perspective {
+    /// Every planet as it is
+    #[default]
+    Omniscient,
+    /// Only what the player knew at that point of the game: their fog of war, intel and battles
+    Player(PlayerId),
+}
+
+/// Steps through a replay, showing every planet as it is at each point of the game, or as one
+/// player saw it.
+pub struct ReplayViewer {
+    replay: Replay,
+    state: GameState,
+    /// Commands of the replay applied to `state` so far
+    position: usize,
+    /// States at the start of turn 1 and every `SNAPSHOT_INTERVAL` turns after, oldest first
+    snapshots: Vec<Snapshot>,
+    perspective: Perspective,
+    terminal_width: Option<usize>,
+    render_style: RenderStyle,
+}
+
+impl ReplayViewer {
+    /// Views `replay` from the start. `initial` is the game its setup generates.
+    pub fn new(replay: Replay, mut initial: GameState) -> Result<Self, ReplayError> {
+        initial.omniscient = true;
+        Ok(ReplayViewer {
+            replay,
+            snapshots: vec![Snapshot::take(0, &initial)?],
+            state: initial,
+            position: 0,
+            perspective: Perspective::Omniscient,
+            terminal_width: None,
+            render_style: RenderStyle::default(),
+        })
+    }
+
+    /// Reads a replay file and sets its game up with the configs from the data directory.
+    pub fn open(path: &Path) -> Result<Self, ReplayError> {
+        let replay = Replay::read(path)?;
+        let initial = replay.setup.load_game()?;
+        Self::new(replay, initial)
+    }
+
+    /// Records the width of the terminal, as detected by the frontend.
+    pub fn with_terminal_width(mut self, columns: Option<usize>) -> Self {
+        self.terminal_width = columns;
+        self
+    }
+
+    /// Records which characters the terminal can show, as chosen by the frontend.
+    pub fn with_render_style(mut self, style: RenderStyle) -> Self {
+        self.render_style = style;
+        self
+    }
+
+    /// The game as it is at the current point of the replay.
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    pub fn perspective(&self) -> &Perspective {
+        &self.perspective
+    }
+
+    /// Shows views from `who`'s perspective from now on: a player's name or id, or
+    /// `omniscient` for every planet as it is.
+    pub fn set_perspective(&mut self, who: &str) -> Result<(), ReplayError> {
+        if who.eq_ignore_ascii_case("omniscient") {
+            self.perspective = Perspective::Omniscient;
+            return Ok(());
+        }
+        let player = self.state.players.values()
+            .find(|player| player.id == who || player.name.eq_ignore_ascii_case(who))
+            .ok_or_else(|| ReplayError::UnknownPlayer {
+                name: who.to_string(),
+                players: self.state.players.values().map(|player| player.name.as_str()).collect::<Vec<_>>().join(", "),
+            })?;
+        self.perspective = Perspective::Player(player.id.clone());
+        Ok(())
+    }
+
+    /// Applies the next command; None at the end of the replay.
+    pub fn step(&mut self) -> Result<Option<Step>, ReplayError> {
+        let Some(recorded) = self.replay.commands.get(self.position).cloned() else {
+            return Ok(None);
+        };
+        let current = self.state.current_player();
+        if recorded.player_id != *current || recorded.turn != self.state.turn {
+            return Err(ReplayError::Diverged {
+                index: self.position + 1,
+                recorded: format!("{} on turn {}", recorded.player_id, recorded.turn),
+                actual: format!("{}'s turn {}", current, self.state.turn),
+            });
+        }
+
+        let command = parser::parse(&recorded.line);
+        let is_order = command.as_ref().is_ok_and(Command::is_order);
+        let result = command.and_then(|command| command.execute(&self.state));
+        let outcome = self.state.apply_command(&recorded.player_id, &recorded.line, is_order, result);
+        self.position += 1;
+
+        let turn = self.state.turn;
+        let snapshot_due = (turn - 1).is_multiple_of(SNAPSHOT_INTERVAL)
+            && self.snapshots.last().is_some_and(|snapshot| snapshot.turn < turn);
+        if snapshot_due {
+            self.snapshots.push(Snapshot::take(self.position, &self.state)?);
+        }
+        Ok(Some((recorded, outcome)))
+    }
+
+    /// Applies commands until the turn changes or the replay ends.
+    pub fn next_turn(&mut self) -> Result<Vec<Step>, ReplayError> {
+        let turn = self.state.turn;
+        let mut applied = Vec::new();
+        while self.state.turn == turn {
+            match self.step()? {
+                Some(step) => applied.push(step),
+                None => break,
+            }
+        }
+        Ok(applied)
+    }
+
+    /// Moves to the start of `turn`, from the latest snapshot before it unless the viewer is
+    /// already on the way there.
+    pub fn goto(&mut self, turn: u32) -> Result<(), ReplayError> {
+        let snapshot = self.snapshots.iter().rev()
+            .find(|snapshot| snapshot.turn <= turn)
+            .unwrap_or(&self.snapshots[0]);
+        if self.state.turn >= turn || self.position < snapshot.position {
+            self.position = snapshot.position;
+            self.state = serde_json::from_value(snapshot.state.clone())?;
+            self.state.omniscient = true;
+        }
+
+        while self.state.turn < turn {
+            if self.step()?.is_none() {
+                return Err(ReplayError::TurnNotReached { turn, last: self.state.turn });
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads viewer commands from `input` until it ends or the viewer exits: `next`,
+    /// `next turn`, `goto <turn>` and the game's views, which are evaluated for whoever is to
+    /// move at that point.
+    pub fn run_with(&mut self, input: &mut dyn InputSource, output: &mut dyn OutputSink) -> Result<(), InputError> {
+        let mut styled = StyledSink::new(output, self.render_style);
+        let output: &mut dyn OutputSink = &mut styled;
+
+        let names = self.replay.setup.player_names.join(", ");
+        output.emit(OutputTarget::Broadcast, &format!(
+            "Replay of a game between {}: {} commands recorded.\n\
+             Type 'next', 'next turn' or 'goto <turn>' to step through it, views such as status and map \
+             to look around, 'perspective <player|omniscient>' to see only what one player knew, and 'exit' to leave.",
+            names, self.replay.commands.len(),
+        ));
+        output.emit(OutputTarget::Broadcast, &self.describe_position());
+
+        loop {
+            let line = match read_player_input(input, |line| Ok(String::from(line))) {
+                Ok(line) => line,
+                Err(InputError::EndOfInput) => return Ok(()),
+                Err(error) => return Err(error),
+            };
+            let words: Vec<_> = line.split_whitespace().collect();
+            let moved = match words[..] {
+                [] => continue,
+                ["next"] => self.step().map(|step| match step {
+                    Some(step) => Self::show_step(&self.state, &step, output),
+                    None => output.emit_error(OutputTarget::Broadcast, "The replay is over."),
+                }),
+                ["next", "turn"] => self.next_turn().map(|steps| {
+                    for step in &steps {
+                        Self::show_step(&self.state, step, output);
+                    }
+                }),
+                ["perspective", who] => {
+                    match self.set_perspective(who).map(|()| &self.perspective) {
+                        Ok(Perspective::Omniscient) => output.emit(OutputTarget::Broadcast, "Views now show every planet as it is."),
+                        Ok(Perspective::Player(player_id)) => output.emit(OutputTarget::Broadcast, &format!(
+                            "Views now show only what {} knew at each point.", self.state.players[player_id].name,
+                        )),
+                        Err(e) => output.emit_error(OutputTarget::Broadcast, &e.to_string()),
+                    }
+                    continue;
+                }
+                ["goto", turn] => match turn.parse::<u32>() {
+                    Ok(turn) if turn > 0 => self.goto(turn),
+                    _ => {
+                        output.emit_error(OutputTarget::Broadcast, &format!("Invalid turn '{}': turns start at 1", turn));
+                        continue;
+                    }
+                },
+                _ => {
+                    if !self.show_view(&line, output) {
+                        return Ok(());
+                    }
+                    continue;
+                }
+            };
+            if let Err(e) = moved {
+                output.emit_error(OutputTarget::Broadcast, &e.to_string());
+            }
+            output.emit(OutputTarget::Broadcast, &self.describe_position());
+        }
+    }
+
+    /// Shows a view of the game at the current point; false when the viewer asked to exit.
+    fn show_view(&mut self, line: &str, output: &mut dyn OutputSink) -> bool {
+        let command = parser::parse(line).map(|mut command| {
+            if let Command::Map(args) = &mut command {
+                args.terminal_width = self.terminal_width;
+                args.style = self.render_style;
+            }
+            command
+        });
+        let effect = command.and_then(|command| match self.perspective.clone() {
+            Perspective::Omniscient => command.execute(&self.state),
+            Perspective::Player(player_id) => self.as_player(&player_id, |state| command.execute(state)),
+        });
+        match effect {
+            Ok(CommandEffect::Exit) => return false,
+            Ok(CommandEffect::None { message }) => output.emit(OutputTarget::Broadcast, &message),
+            Ok(_) => output.emit_error(
+                OutputTarget::Broadcast,
+                "Replays cannot be changed: only views such as status and map are available",
+            ),
+            Err(e) => output.emit_error(OutputTarget::Broadcast, &e.to_string()),
+        }
+        true
+    }
+
+    /// Runs `view` on the game as `player_id` would see it if it were their turn, then puts the
+    /// turn order and omniscience back the way the replay has them.
+    fn as_player<T>(&mut self, player_id: &PlayerId, view: impl FnOnce(&GameState) -> T) -> T {
+        let players_order = self.state.players_order.clone();
+        match players_order.iter().position(|id| id == player_id) {
+            Some(index) => self.state.players_order.rotate_left(index),
+            // Eliminated players still have what they knew when they fell
+            None => self.state.players_order.push_front(player_id.clone()),
+        }
+        self.state.omniscient = false;
+        let result = view(&self.state);
+        self.state.omniscient = true;
+        self.state.players_order = players_order;
+        result
+    }
+
+    fn show_step(state: &GameState, step: &Step, output: &mut dyn OutputSink) {
+        let (recorded, outcome) = step;
+        let player_name = state.players.get(&recorded.player_id).map_or(recorded.player_id.as_str(), |player| &player.name);
+        output.emit(OutputTarget::Broadcast, &format!("Turn {} - {}: {}", recorded.turn, player_name, recorded.line));
+        match outcome {
+            Ok(events) => {
+                for event in events {
+                    output.emit(OutputTarget::Broadcast, &event.named(&state.structure_config, &state.ship_config).to_string());
+                }
+            }
+            Err(reason) => output.emit_error(OutputTarget::Broadcast, reason),
+        }
+    }
+
+    fn describe_position(&self) -> String {
+        let total = self.replay.commands.len();
+        if self.position == total {
+            let ending = if self.state.is_over() { ", game over" } else { "" };
+            return format!("[End of replay: turn {}{}]", self.state.turn, ending);
+        }
+        let player_id = self.state.current_player();
+        let player_name = self.state.players.get(player_id).map_or(player_id.as_str(), |player| &player.name);
+        let seen_by = match &self.perspective {
+            Perspective::Omniscient => String::new(),
+            Perspective::Player(viewer_id) => format!(", seen by {}", self.state.players[viewer_id].name),
+        };
+        format!("[Turn {}, {} to move - command {} of {}{}]", self.state.turn, player_name, self.position + 1, total, seen_by)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::game::Game;
+    use crate::interface::game_configuration::GameConfigurationBuilder;
+    use crate::interface::input::ScriptSource;
+    use crate::interface::output::CaptureSink;
+    use crate::test_support::{self, PLANET_NAMES_FIXTURE};
+
+    /// Three rounds of orders any seat can give, including one the engine rejects.
+    const FIRST_ROUND: [&str; 5] = ["build_all power_grid", "end", "build_all mining_complex", "build_all no_such_thing", "end"];
+    const LATER_ROUNDS: [&str; 5] = ["status", "end", "end", "end", "end"];
+
+    fn setup() -> GameSetup {
+        GameSetup {
+            seed: 7,
+            player_names: vec![String::from("Alice"), String::from("Bob")],
+            teams: vec![None, None],
+            map_size: MapSize::Small,
+            planet_names_path: None,
+            rules: GameRules::default(),
+            starting_draft: false,
+        }
+    }
+
+    fn fixture_state(setup: &GameSetup) -> GameState {
+        let name_parts = PlanetNameParts::load_from_string(PLANET_NAMES_FIXTURE).unwrap();
+        setup.new_game(test_support::fixture_structure_config(), test_support::fixture_ship_config(), name_parts)
+            .unwrap()
+    }
+
+    /// Plays `lines` in a session over the fixture game, recording a replay to `replay_path` if given.
+    fn play(name: &str, lines: &[&str], replay_path: Option<PathBuf>) -> GameState {
+        let setup = setup();
+        let config = GameConfigurationBuilder::new()
+            .player_name("Alice")
+            .player_name("Bob")
+            .save_dir(test_support::temp_dir(name))
+            .build()
+            .unwrap()
+            .with_advisor(false)
+            .with_replay(replay_path);
+        let mut game = Game::from_setup(config, setup.clone(), fixture_state(&setup)).unwrap();
+        game.run_with(&mut ScriptSource::new(lines.iter().copied()), &mut CaptureSink::new()).unwrap();
+        game.game_state
+    }
+
+    fn recorded_viewer(name: &str) -> ReplayViewer {
+        let path = test_support::temp_dir(name).join("game.replay");
+        let lines: Vec<_> = FIRST_ROUND.iter().chain(&LATER_ROUNDS).copied().collect();
+        play(name, &lines, Some(path.clone()));
+
+        let replay = Replay::read(&path).unwrap();
+        let initial = fixture_state(&replay.setup);
+        ReplayViewer::new(replay, initial).unwrap()
+    }
+
+    fn comparable(state: &GameState) -> serde_json::Value {
+        serde_json::to_value(state).unwrap()
+    }
+
+    #[test]
+    fn test_state_at_turn_two_matches_a_live_game_stopped_there() {
+        let mut viewer = recorded_viewer("replay_goto");
+        let live = play("replay_live", &FIRST_ROUND, None);
+        assert_eq!(live.turn, 2);
+
+        viewer.goto(2).unwrap();
+
+        assert_eq!(viewer.state().turn, 2);
+        assert_eq!(viewer.state().action_log.len(), 5);
+        assert_eq!(comparable(viewer.state()), comparable(&live));
+
+        viewer.goto(3).unwrap();
+        viewer.goto(2).unwrap();
+        assert_eq!(comparable(viewer.state()), comparable(&live));
+        assert!(matches!(viewer.goto(9), Err(ReplayError::TurnNotReached { turn: 9, last: 4 })));
+    }
+
+    #[test]
+    fn test_stepping_applies_one_command_or_one_turn_at_a_time() {
+        let mut viewer = recorded_viewer("replay_step");
+        // Views are left out of the recording
+        assert_eq!(viewer.replay.commands.len(), 9);
+
+        let (first, outcome) = viewer.step().unwrap().unwrap();
+        assert_eq!(first.line, "build_all power_grid");
+        assert!(outcome.is_ok());
+        assert_eq!(viewer.state().action_log.len(), 1);
+
+        let rest_of_turn = viewer.next_turn().unwrap();
+        assert_eq!(rest_of_turn.len(), 4);
+        assert!(rest_of_turn[2].1.is_err());
+        assert_eq!(viewer.state().turn, 2);
+
+        while viewer.step().unwrap().is_some() {}
+        assert_eq!(viewer.state().turn, 4);
+        assert!(viewer.next_turn().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_viewer_shows_views_omnisciently_and_refuses_orders() {
+        let mut viewer = recorded_viewer("replay_views");
+        let current = viewer.state().current_player().clone();
+        let other = viewer.state().players_order.iter().find(|id| **id != current).unwrap().clone();
+        let other_home = test_support::home_planet(viewer.state(), &other);
+        let status = format!("status planet {}", other_home);
+        let mut output = CaptureSink::new();
+
+        let script = ["next", "goto 2", status.as_str(), "end", "goto 0", "exit", "next"];
+        viewer.run_with(&mut ScriptSource::new(script), &mut output).unwrap();
+
+        let texts: Vec<_> = output.entries.iter().map(|(_, text)| text.as_str()).collect();
+        assert!(texts.iter().any(|text| text.ends_with(": build_all power_grid")), "{texts:?}");
+        assert!(texts.iter().any(|text| text.starts_with("[Turn 2, ")), "{texts:?}");
+        assert!(texts.iter().any(|text| text.contains("RESOURCES")), "{texts:?}");
+        assert!(texts.iter().any(|text| text.contains("only views such as status and map")), "{texts:?}");
+        assert!(texts.iter().any(|text| text.contains("turns start at 1")), "{texts:?}");
+        // Nothing after exit is read
+        assert_eq!(viewer.state().turn, 2);
+    }
+
+    #[test]
+    fn test_a_players_perspective_hides_what_they_never_scouted() {
+        let mut viewer = recorded_viewer("replay_perspective");
+        let mut output = CaptureSink::new();
+
+        let script = ["goto 3", "perspective Alice", "status planets", "perspective omniscient", "status planets", "perspective Zed"];
+        viewer.run_with(&mut ScriptSource::new(script), &mut output).unwrap();
+
+        let texts: Vec<_> = output.entries.iter().map(|(_, text)| text.as_str()).collect();
+        let listings: Vec<_> = texts.iter().filter(|text| text.starts_with("=== Planets ===")).collect();
+        let [as_alice, omniscient] = listings[..] else {
+            panic!("Expected two planet listings: {texts:?}");
+        };
+        let hidden: Vec<_> = as_alice.lines().filter(|line| line.ends_with("- unexplored")).collect();
+        assert!(!hidden.is_empty(), "Alice scouted nothing in three turns, so some planets stay unexplored:\n{as_alice}");
+        for line in hidden {
+            let planet = line.split(" - ").next().unwrap();
+            let seen = omniscient.lines().find(|line| line.starts_with(planet)).unwrap();
+            assert!(!seen.ends_with("unexplored"), "{seen}");
+        }
+        assert!(texts.contains(&"Views now show only what Alice knew at each point."), "{texts:?}");
+        assert!(texts.iter().any(|text| text.contains("No player 'Zed' in this game; choose one of Alice, Bob")), "{texts:?}");
+
+        // Viewing as someone leaves the replay's own state alone
+        assert_eq!(viewer.perspective(), &Perspective::Omniscient);
+        assert!(viewer.state().omniscient);
+        assert_eq!(viewer.state().turn, 3);
+    }
+
+    #[test]
+    fn test_compressed_and_plain_replays_round_trip() {
+        let dir = test_support::temp_dir("replay_compression");
+        let mut replay = Replay::new(setup());
+        replay.record(1, &String::from("alice"), "end_turn");
+        replay.record(1, &String::from("bob"), "end_turn");
+
+        for compress in [false, true] {
+            let path = dir.join(format!("game_{compress}.replay"));
+            replay.write(&path, compress).unwrap();
+
+            assert_eq!(fs::read(&path).unwrap().starts_with(&[0x1f, 0x8b]), compress);
+            assert_eq!(Replay::read(&path).unwrap(), replay);
+        }
+
+        let path = dir.join("game_true.replay");
+        let bytes = fs::read(&path).unwrap();
+        fs::write(&path, &bytes[..bytes.len() / 2]).unwrap();
+        assert!(matches!(Replay::read(&path), Err(ReplayError::Compression(_))));
+    }
+}
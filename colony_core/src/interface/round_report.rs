@@ -0,0 +1,109 @@
+use crate::engine::game_event::GameEvent;
+use crate::engine::game_state::{GameState, Highlight};
+
+/// Galaxy news for the round that just ended, shown to everyone before the next player's
+/// banner: the round's colonizations and battles, the players it eliminated, planet
+/// standings and the players down to their last planet. Only public happenings are reported;
+/// builds, fleet orders and other private events in `last_round_events` are left out.
+pub fn round_report(game_state: &GameState, last_round_events: &[GameEvent]) -> String {
+    let finished_turn = game_state.turn.saturating_sub(1);
+
+    let mut news: Vec<_> = last_round_events.iter().filter_map(Highlight::from_event).collect();
+    news.extend(
+        game_state.action_log.iter()
+            .filter(|entry| entry.turn == finished_turn)
+            .flat_map(|entry| &entry.highlights)
+            .filter(|highlight| matches!(highlight, Highlight::Eliminated { .. }))
+            .cloned(),
+    );
+
+    let mut msg = format!("=== Galaxy News: Turn {} ===\n", finished_turn);
+    if news.is_empty() {
+        msg.push_str("  (a quiet round)\n");
+    }
+    for highlight in &news {
+        msg.push_str(&format!("  - {}\n", highlight));
+    }
+
+    let mut standings: Vec<_> = game_state.players_order.iter()
+        .filter_map(|player_id| game_state.players.get(player_id))
+        .map(|player| (player.name.as_str(), player.planets.len()))
+        .collect();
+    // Stable, so tied players stay in turn order
+    standings.sort_by_key(|(_, planets)| std::cmp::Reverse(*planets));
+
+    msg.push_str("\nSTANDINGS\n");
+    let width = standings.iter().map(|(name, _)| name.chars().count()).max().unwrap_or(0);
+    for (name, planets) in &standings {
+        msg.push_str(&format!("  {:<width$}  {} planet(s)\n", name, planets));
+    }
+
+    let in_danger: Vec<_> = standings.iter()
+        .filter(|(_, planets)| *planets == 1)
+        .map(|(name, _)| *name)
+        .collect();
+    if !in_danger.is_empty() {
+        msg.push_str(&format!("\nDown to their last planet: {}\n", in_danger.join(", ")));
+    }
+
+    msg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::resources::Resources;
+    use crate::test_support::{self, fixture_game};
+
+    #[test]
+    fn test_report_keeps_public_events_and_standings_only() {
+        let mut state = fixture_game(1);
+        let home = test_support::home_planet(&state, "alice");
+        let colony = test_support::grant_planet(&mut state, "alice");
+        state.turn = 4;
+        let colony_name = state.map.planets[&colony].name.clone();
+        let events = [
+            GameEvent::StructureQueued {
+                structure_id: String::from("secret_lab"), cost: Resources::default(), turns: 2, warnings: Vec::new(), advice: Vec::new(),
+            },
+            GameEvent::ConstructionCompleted { player_id: String::from("bob"), structure_id: String::from("hidden_bunker"), planet_name: home.clone() },
+            GameEvent::ShipBuilt {
+                player_id: String::from("alice"),
+                ship_instance_id: String::from("ravager_1"),
+                ship_type: String::from("ravager"),
+                planet_name: home,
+                fleet_name: None,
+            },
+            GameEvent::PlanetColonized {
+                fleet_id: String::from("fleet_1"),
+                planet_name: colony_name.clone(),
+                player_name: String::from("Alice"),
+                ark_bonus: Resources::default(),
+            },
+        ];
+
+        let report = round_report(&state, &events);
+
+        assert!(report.starts_with("=== Galaxy News: Turn 3 ==="), "{report}");
+        assert!(report.contains(&format!("  - Alice colonized {}\n", colony_name)), "{report}");
+        for private in ["secret_lab", "hidden_bunker", "ravager"] {
+            assert!(!report.contains(private), "{report}");
+        }
+        assert!(report.contains("STANDINGS\n  Alice  2 planet(s)\n  Bob    1 planet(s)\n"), "{report}");
+        assert!(report.contains("Down to their last planet: Bob"), "{report}");
+    }
+
+    #[test]
+    fn test_report_lists_players_eliminated_that_round() {
+        let mut state = fixture_game(1);
+        state.players.get_mut("bob").unwrap().planets.clear();
+        let alice = String::from("alice");
+        state.record_action(&alice, 1, "end_turn", crate::engine::game_state::ActionOutcome::Applied, &[]);
+        state.turn = 2;
+
+        let report = round_report(&state, &[]);
+
+        assert!(report.contains("  - Bob was eliminated\n"), "{report}");
+        assert!(!report.contains("a quiet round"), "{report}");
+    }
+}
@@ -0,0 +1,283 @@
+//! Reusable setups: a JSON file answering some or all of the setup questions, values given
+//! on the command line with `--set`, and the answers the wizard still has to ask for. Later
+//! sources win: the file, then the command line, then the prompts.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::game_configuration::{GameConfiguration, GameConfigurationError};
+use super::input::InputSource;
+use super::setup_wizard::SetupWizard;
+
+/// Answers to the setup questions, each optional. Choices are spelled as they are answered at
+/// the prompts, such as `"small"` or `"round"`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SetupFile {
+    /// Human commanders; implied by `player_names` when only those are given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub players: Option<u8>,
+    /// Commander names in turn order; random names are drawn when absent and not asked for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub player_names: Option<Vec<String>>,
+    /// Commander names of each team; empty for a free-for-all
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub teams: Option<Vec<Vec<String>>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ai_players: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub map_size: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speed: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub production: Option<String>,
+    /// Rules profile; the slot counts below are only used with `"custom"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_slots: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upgrade_slots: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ship_slots: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fleet_capacity: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub victory: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub starting_draft: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub round_report: Option<bool>,
+    /// Seed every random choice of the setup is drawn from; a fresh one each game when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+/// Where a setup value came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SetupSource {
+    File(PathBuf),
+    /// `--set` on the command line
+    Flag,
+    /// Answered at the setup prompts
+    Prompt,
+}
+
+impl fmt::Display for SetupSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SetupSource::File(path) => write!(f, "from {}", path.display()),
+            SetupSource::Flag => write!(f, "from --set"),
+            SetupSource::Prompt => write!(f, "answered at the prompt"),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SetupFileError {
+    #[error("Failed to access setup file {path}: {source}")]
+    Io { path: PathBuf, source: std::io::Error },
+
+    #[error("Invalid setup {origin}: {source}")]
+    Json { origin: SetupSource, source: serde_json::Error },
+
+    #[error("Invalid --set '{0}': expected <field>=<value>")]
+    InvalidFlag(String),
+}
+
+/// Setup values gathered from files and flags, remembering where each came from. Values added
+/// later replace earlier ones.
+#[derive(Debug, Clone, Default)]
+pub struct SetupLayers {
+    values: Map<String, Value>,
+    sources: BTreeMap<String, SetupSource>,
+}
+
+impl SetupLayers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the values of the setup file at `path`.
+    pub fn add_file(&mut self, path: &Path) -> Result<(), SetupFileError> {
+        let json = fs::read_to_string(path)
+            .map_err(|source| SetupFileError::Io { path: path.to_path_buf(), source })?;
+        self.add_json(&json, SetupSource::File(path.to_path_buf()))
+    }
+
+    /// Adds the values of a setup written as JSON, checking every field is known.
+    pub fn add_json(&mut self, json: &str, origin: SetupSource) -> Result<(), SetupFileError> {
+        let invalid = |source| SetupFileError::Json { origin: origin.clone(), source };
+        let values: Map<String, Value> = serde_json::from_str(json).map_err(invalid)?;
+        serde_json::from_value::<SetupFile>(Value::Object(values.clone())).map_err(invalid)?;
+
+        for (field, value) in values {
+            self.sources.insert(field.clone(), origin.clone());
+            self.values.insert(field, value);
+        }
+        Ok(())
+    }
+
+    /// Adds one `<field>=<value>` from `--set`. Values are read as JSON where they parse and as
+    /// text otherwise; names and teams may also be listed as `Alice,Bob` and `Alice,Bob;Carol`.
+    pub fn add_flag(&mut self, assignment: &str) -> Result<(), SetupFileError> {
+        let (field, text) = assignment.split_once('=')
+            .filter(|(field, _)| !field.trim().is_empty())
+            .ok_or_else(|| SetupFileError::InvalidFlag(assignment.to_string()))?;
+        let (field, text) = (field.trim(), text.trim());
+        let list = |text: &str| Value::from(text.split(',').map(str::trim).filter(|name| !name.is_empty()).collect::<Vec<_>>());
+
+        let value = match serde_json::from_str(text) {
+            Ok(value) => value,
+            Err(_) if field == "player_names" => list(text),
+            Err(_) if field == "teams" => Value::from(text.split(';').map(list).collect::<Vec<_>>()),
+            Err(_) => Value::from(text),
+        };
+        let layer = Value::Object(Map::from_iter([(field.to_string(), value)]));
+        self.add_json(&layer.to_string(), SetupSource::Flag)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Every value gathered so far.
+    pub fn setup(&self) -> SetupFile {
+        serde_json::from_value(Value::Object(self.values.clone()))
+            .expect("Every layer was checked when it was added")
+    }
+
+    /// Where `field` was given, or None when it was not.
+    pub fn source(&self, field: &str) -> Option<&SetupSource> {
+        self.sources.get(field)
+    }
+}
+
+/// Asks the setup questions `layers` leaves open and builds the configuration. The resolved
+/// setup, every answer included, is written to `save_to` when given, for the next game.
+pub fn configure(
+    layers: &SetupLayers,
+    input: &mut dyn InputSource,
+    save_to: Option<&Path>,
+) -> Result<GameConfiguration, GameConfigurationError> {
+    let mut wizard = SetupWizard::with_setup(layers)?;
+    wizard.ask(input)?;
+    let resolved = wizard.resolved_setup();
+    let config = wizard.finish()?;
+    if let Some(path) = save_to {
+        write_setup(path, &resolved)?;
+    }
+    Ok(config)
+}
+
+/// Writes `setup` to `path` as pretty JSON.
+pub fn write_setup(path: &Path, setup: &SetupFile) -> Result<(), SetupFileError> {
+    let io_error = |source| SetupFileError::Io { path: path.to_path_buf(), source };
+    let json = serde_json::to_string_pretty(setup).expect("Setups always serialize");
+    fs::write(path, json + "\n").map_err(io_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::game_rules::{GameSpeed, ProductionTiming, VictoryCondition};
+    use crate::interface::game_configuration::MapSize;
+    use crate::interface::input::ScriptSource;
+    use crate::test_support;
+
+    fn file_layers(dir: &Path, json: &str) -> (SetupLayers, PathBuf) {
+        let path = dir.join("setup.json");
+        fs::write(&path, json).unwrap();
+        let mut layers = SetupLayers::new();
+        layers.add_file(&path).unwrap();
+        (layers, path)
+    }
+
+    #[test]
+    fn test_partial_file_asks_only_the_rest_and_writes_the_resolved_setup() {
+        let dir = test_support::temp_dir("setup_partial");
+        let (mut layers, _) = file_layers(&dir, r#"{
+            "player_names": ["Alice", "Bob"],
+            "map_size": "medium",
+            "speed": "quick",
+            "seed": 42
+        }"#);
+        layers.add_flag("speed=epic").unwrap();
+        assert_eq!(layers.source("speed"), Some(&SetupSource::Flag));
+
+        // AI count, production, rules, fleet capacity, victory, draft and news are left to ask
+        let mut script = ScriptSource::new(["1", "round", "default", "default", "domination", "n", "y"]);
+        let saved = dir.join("resolved.json");
+        let config = configure(&layers, &mut script, Some(&saved)).unwrap();
+
+        assert_eq!(config.player_names, ["Alice", "Bob"]);
+        assert_eq!(config.num_of_ai, 1);
+        assert_eq!(config.map_size, MapSize::Medium);
+        assert_eq!(config.rules.speed, GameSpeed::Epic);
+        assert_eq!(config.rules.production_timing, ProductionTiming::RoundEnd);
+        assert_eq!(config.rules.victory, VictoryCondition::Domination);
+        assert!(config.round_report);
+        assert_eq!(config.seed, Some(42));
+
+        let written: SetupFile = serde_json::from_str(&fs::read_to_string(&saved).unwrap()).unwrap();
+        assert_eq!(written, SetupFile {
+            players: Some(2),
+            player_names: Some(vec![String::from("Alice"), String::from("Bob")]),
+            teams: None,
+            ai_players: Some(1),
+            map_size: Some(String::from("medium")),
+            speed: Some(String::from("epic")),
+            production: Some(String::from("round")),
+            rules: Some(String::from("classic")),
+            build_slots: None,
+            upgrade_slots: None,
+            ship_slots: None,
+            fleet_capacity: Some(crate::engine::game_rules::DEFAULT_FLEET_CAPACITY),
+            victory: Some(String::from("domination")),
+            starting_draft: Some(false),
+            round_report: Some(true),
+            seed: Some(42),
+        });
+
+        // The written setup answers every question on its own
+        let mut layers = SetupLayers::new();
+        layers.add_file(&saved).unwrap();
+        let config = configure(&layers, &mut ScriptSource::new(Vec::<&str>::new()), None).unwrap();
+        assert_eq!(config.num_of_ai, 1);
+        assert_eq!(config.rules.speed, GameSpeed::Epic);
+    }
+
+    #[test]
+    fn test_conflicts_name_each_field_and_where_it_came_from() {
+        let dir = test_support::temp_dir("setup_conflict");
+        let (mut layers, path) = file_layers(&dir, r#"{"players": 4, "teams": [], "ai_players": 2, "map_size": "small"}"#);
+        layers.add_flag("player_names=Alice,Bob,Carol,Dave").unwrap();
+
+        let error = SetupWizard::with_setup(&layers).err().unwrap().to_string();
+        assert!(error.starts_with(&format!("map_size = small (from {})", path.display())), "{error}");
+        assert!(error.contains("player_names from --set") && error.contains(&format!("ai_players from {}", path.display())), "{error}");
+
+        layers.add_flag("players=3").unwrap();
+        let error = SetupWizard::with_setup(&layers).err().unwrap().to_string();
+        assert!(error.starts_with("players = 3 (from --set)") && error.contains("player_names from --set"), "{error}");
+    }
+
+    #[test]
+    fn test_unknown_fields_and_bad_flags_are_rejected() {
+        let mut layers = SetupLayers::new();
+        let error = layers.add_json(r#"{"map": "small"}"#, SetupSource::Flag).unwrap_err();
+        assert!(error.to_string().contains("unknown field `map`"), "{error}");
+        assert!(matches!(layers.add_flag("small"), Err(SetupFileError::InvalidFlag(_))));
+        assert!(layers.add_flag("players=many").is_err());
+        assert!(layers.is_empty());
+
+        layers.add_flag("teams=Alice,Bob;Carol").unwrap();
+        let teams = layers.setup().teams.unwrap();
+        assert_eq!(teams, vec![vec![String::from("Alice"), String::from("Bob")], vec![String::from("Carol")]]);
+    }
+}
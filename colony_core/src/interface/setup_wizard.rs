@@ -0,0 +1,792 @@
+use std::collections::HashSet;
+
+use crate::engine::configs::player_names::generate_random_names;
+use crate::engine::game_rules::{
+    DEFAULT_FLEET_CAPACITY, GameSpeed, MAX_CONSTRUCTION_SLOTS, ProductionTiming, RulesPreset, RulesProfile, VictoryCondition,
+};
+use crate::engine::utils;
+
+use super::game_configuration::{
+    DEFAULT_PLANETS_PER_FACTION, GameConfiguration, GameConfigurationBuilder, GameConfigurationError, MAX_FACTIONS, MapSize,
+    assign_teams, check_map_capacity, validate_player_name,
+};
+use super::input::{InputSource, read_player_input};
+use super::setup_file::{SetupFile, SetupLayers, SetupSource};
+
+/// Answer that returns to the previous question.
+pub const BACK: &str = "back";
+
+/// Answer that accepts the value suggested in brackets.
+pub const DEFAULT: &str = "default";
+
+/// One question of the setup wizard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SetupStep {
+    PlayerCount,
+    NamePlayers,
+    /// Name of the commander at this index
+    PlayerName(usize),
+    FormTeams,
+    /// Team of the commander at this index
+    Team(usize),
+    AiCount,
+    MapSize,
+    Speed,
+    ProductionTiming,
+    RulesProfile,
+    /// Asked for custom rules only, like the two steps after it
+    BuildSlots,
+    UpgradeSlots,
+    ShipSlots,
+    FleetCapacity,
+    Victory,
+    StartingDraft,
+    RoundReport,
+}
+
+/// Walks through the setup questions one step at a time. Answers are kept when going `back`,
+/// so only the question being corrected has to be answered again, and the finished answers
+/// are turned into a configuration by `GameConfigurationBuilder`. Questions a setup file or
+/// `--set` already answers are filled in without asking, unless `back` returns to them.
+pub struct SetupWizard {
+    /// Question being asked; None once every question is answered
+    step: Option<SetupStep>,
+    /// Questions answered so far, most recent last
+    answered: Vec<SetupStep>,
+    player_count: u8,
+    name_players: bool,
+    player_names: Vec<String>,
+    form_teams: bool,
+    /// Team number picked for each commander so far, None for those fighting alone
+    teams: Vec<Option<usize>>,
+    ai_count: u8,
+    map_size: MapSize,
+    speed: GameSpeed,
+    production_timing: ProductionTiming,
+    preset: RulesPreset,
+    profile: RulesProfile,
+    victory: VictoryCondition,
+    starting_draft: bool,
+    round_report: bool,
+    /// Values from setup files and flags, answering questions before they are asked
+    layers: SetupLayers,
+    /// Steps `back` returned to, asked even when the setup answers them
+    asked: HashSet<SetupStep>,
+}
+
+impl Default for SetupWizard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SetupWizard {
+    pub fn new() -> Self {
+        SetupWizard {
+            step: Some(SetupStep::PlayerCount),
+            answered: Vec::new(),
+            player_count: 2,
+            name_players: false,
+            player_names: Vec::new(),
+            form_teams: false,
+            teams: Vec::new(),
+            ai_count: 0,
+            map_size: MapSize::Small,
+            speed: GameSpeed::Normal,
+            production_timing: ProductionTiming::TurnStart,
+            preset: RulesPreset::Classic,
+            profile: RulesProfile::classic(),
+            victory: VictoryCondition::Elimination,
+            starting_draft: false,
+            round_report: false,
+            layers: SetupLayers::new(),
+            asked: HashSet::new(),
+        }
+    }
+
+    /// A wizard with every question `layers` answers filled in, up to the first it leaves open.
+    /// Values that contradict each other or fail validation are reported with where they came from.
+    pub fn with_setup(layers: &SetupLayers) -> Result<Self, GameConfigurationError> {
+        let mut wizard = SetupWizard { layers: layers.clone(), ..Self::new() };
+        let setup = layers.setup();
+        if let (Some(players), Some(names)) = (setup.players, &setup.player_names)
+            && names.len() != players as usize
+        {
+            let reason = format!("{} commander names are listed.", names.len());
+            return Err(wizard.conflict("players", &players.to_string(), reason));
+        }
+        let custom_only = [("build_slots", setup.build_slots), ("upgrade_slots", setup.upgrade_slots), ("ship_slots", setup.ship_slots)];
+        if let Some(rules) = setup.rules.as_deref().filter(|rules| *rules != "custom")
+            && let Some((field, Some(slots))) = custom_only.into_iter().find(|(_, slots)| slots.is_some())
+        {
+            let reason = format!("Slot counts only apply to custom rules, not {}.", rules);
+            return Err(wizard.conflict(field, &slots.to_string(), reason));
+        }
+        wizard.fill_from_setup()?;
+        Ok(wizard)
+    }
+
+    pub fn step(&self) -> Option<SetupStep> {
+        self.step
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.step.is_none()
+    }
+
+    /// Asks every question on `input`, re-asking after invalid answers, and builds the configuration.
+    pub fn run(mut self, input: &mut dyn InputSource) -> Result<GameConfiguration, GameConfigurationError> {
+        println!("\n=== COLONY PROTOCOL INITIALIZATION ===");
+        println!("Establishing secure connection to Colonial Command...");
+        println!("Connection established.\n");
+
+        self.ask(input)?;
+
+        println!("\n[INITIALIZING STAR SYSTEM...]");
+        println!("[DEPLOYING COLONIAL FLEETS...]");
+        println!("[ESTABLISHING QUANTUM LINKS...]");
+        println!("\nColony Protocol active. Command interface ready.\n");
+
+        self.finish()
+    }
+
+    /// Asks the questions still open on `input`, re-asking after invalid answers.
+    pub fn ask(&mut self, input: &mut dyn InputSource) -> Result<(), GameConfigurationError> {
+        if self.is_done() {
+            return Ok(());
+        }
+        println!("Answer '{BACK}' to revisit the previous query or '{DEFAULT}' to accept the value in brackets.\n");
+        while let Some(prompt) = self.prompt() {
+            println!("{}", prompt);
+            let answer = read_player_input(input, |answer| Ok::<_, String>(answer.to_string()))?;
+            match self.answer(&answer) {
+                Ok(()) => {}
+                Err(error @ GameConfigurationError::PlayerNameConfigError(_)) => return Err(error),
+                Err(error) => eprintln!("ERROR: {}", error),
+            }
+        }
+        Ok(())
+    }
+
+    /// Question for the current step with its suggested answer, or None once done.
+    pub fn prompt(&self) -> Option<String> {
+        let question = match self.step? {
+            SetupStep::PlayerCount => format!("QUERY: Number of human commanders in this sector (1-{MAX_FACTIONS})"),
+            SetupStep::NamePlayers => String::from("QUERY: Designate commander identities manually? (y/N)"),
+            SetupStep::PlayerName(index) => format!("Commander {} of {} name", index + 1, self.player_count),
+            SetupStep::FormTeams => String::from("QUERY: Form alliances between commanders? (y/N)"),
+            SetupStep::Team(index) => format!(
+                "Team number for {} (1-{}, blank to fight alone)",
+                self.player_names[index], self.player_names.len()
+            ),
+            SetupStep::AiCount => format!(
+                "QUERY: Number of AI-controlled factions to deploy (0-{})", MAX_FACTIONS - self.player_count as usize
+            ),
+            SetupStep::MapSize => String::from("QUERY: Star system density configuration (small|medium|large)"),
+            SetupStep::Speed => String::from("QUERY: Game speed (quick|normal|epic)"),
+            SetupStep::ProductionTiming => String::from(
+                "QUERY: Produce resources at the start of each commander's turn or at the end of the round (turn|round)"
+            ),
+            SetupStep::RulesProfile => String::from(
+                "QUERY: Rules profile: one build and one upgrade per planet, two of each, or your own (classic|standard|custom)"
+            ),
+            SetupStep::BuildSlots => format!("QUERY: Structures a planet can build at once (1-{MAX_CONSTRUCTION_SLOTS})"),
+            SetupStep::UpgradeSlots => format!("QUERY: Structures a planet can upgrade at once (1-{MAX_CONSTRUCTION_SLOTS})"),
+            SetupStep::ShipSlots => format!("QUERY: Ships built at once per shipyard level (1-{MAX_CONSTRUCTION_SLOTS})"),
+            SetupStep::FleetCapacity => String::from("QUERY: Fleet capacity, counted in ship sizes"),
+            SetupStep::Victory => String::from("QUERY: Victory condition (elimination|domination|both)"),
+            SetupStep::StartingDraft => String::from(
+                "QUERY: Draft starting planets from candidates instead of random placement? (y/N)"
+            ),
+            SetupStep::RoundReport => String::from("QUERY: Broadcast galaxy news after every round? (y/N)"),
+        };
+        // A blank suggestion is already spelled out in the question
+        Some(match self.suggestion().filter(|suggestion| !suggestion.is_empty()) {
+            Some(suggestion) => format!("{} [{}]:", question, suggestion),
+            None => format!("{}:", question),
+        })
+    }
+
+    /// Answer `default` stands for at the current step; commander names have none.
+    fn suggestion(&self) -> Option<String> {
+        let suggestion = match self.step? {
+            SetupStep::PlayerCount => String::from("2"),
+            SetupStep::NamePlayers | SetupStep::FormTeams | SetupStep::StartingDraft | SetupStep::RoundReport => {
+                String::from("n")
+            }
+            SetupStep::PlayerName(_) => return None,
+            SetupStep::Team(_) => String::new(),
+            SetupStep::AiCount if self.player_count < 2 => String::from("1"),
+            SetupStep::AiCount => String::from("0"),
+            SetupStep::MapSize => MapSize::smallest_with(self.factions() as u32 * DEFAULT_PLANETS_PER_FACTION)
+                .unwrap_or(MapSize::Large)
+                .to_string(),
+            SetupStep::Speed => String::from("normal"),
+            SetupStep::ProductionTiming => String::from("turn"),
+            SetupStep::RulesProfile => String::from("classic"),
+            SetupStep::BuildSlots => self.profile.build_slots.to_string(),
+            SetupStep::UpgradeSlots => self.profile.upgrade_slots.to_string(),
+            SetupStep::ShipSlots => self.profile.ships_per_shipyard_level.to_string(),
+            SetupStep::FleetCapacity => self.profile.fleet_capacity.to_string(),
+            SetupStep::Victory => String::from("elimination"),
+        };
+        Some(suggestion)
+    }
+
+    /// Records an answer to the current step and moves on, filling in the questions after it
+    /// that the setup answers. `back` returns to the previous step instead and `default`
+    /// answers with the suggested value. Invalid answers leave the step unchanged; a setup
+    /// value that no longer fits after the answer is reported, and its question asked.
+    pub fn answer(&mut self, answer: &str) -> Result<(), GameConfigurationError> {
+        self.record(answer)?;
+        if answer.trim().eq_ignore_ascii_case(BACK) {
+            return Ok(());
+        }
+        self.fill_from_setup()
+    }
+
+    /// Applies an answer to the current step, or goes `back`.
+    fn record(&mut self, answer: &str) -> Result<(), GameConfigurationError> {
+        let Some(step) = self.step else {
+            return Err(GameConfigurationError::InvalidAnswer(String::from("Setup is already complete.")));
+        };
+        let answer = answer.trim();
+        if answer.eq_ignore_ascii_case(BACK) {
+            return self.back();
+        }
+        let answer = if answer.eq_ignore_ascii_case(DEFAULT) {
+            self.suggestion().ok_or_else(|| {
+                GameConfigurationError::InvalidAnswer(String::from("No suggested value for this query."))
+            })?
+        } else {
+            answer.to_string()
+        };
+        let invalid = GameConfigurationError::InvalidAnswer;
+
+        match step {
+            SetupStep::PlayerCount => self.player_count = parse_player_count(&answer).map_err(invalid)?,
+            SetupStep::NamePlayers => {
+                let name_players = parse_yes_no(&answer).map_err(invalid)?;
+                self.player_names = if name_players {
+                    Vec::new()
+                } else {
+                    generate_random_names(self.player_count as usize)?
+                };
+                self.name_players = name_players;
+            }
+            SetupStep::PlayerName(index) => {
+                self.player_names.truncate(index);
+                let name = validate_player_name(&answer, &self.player_names)?;
+                self.player_names.push(name);
+            }
+            SetupStep::FormTeams => {
+                self.form_teams = parse_yes_no(&answer).map_err(invalid)?;
+                self.teams.clear();
+            }
+            SetupStep::Team(index) => {
+                self.teams.truncate(index);
+                self.teams.push(parse_team(&answer, self.player_names.len()).map_err(invalid)?);
+                if index + 1 == self.player_names.len() {
+                    self.check_teams()?;
+                }
+            }
+            SetupStep::AiCount => self.ai_count = parse_ai_count(&answer, self.player_count).map_err(invalid)?,
+            SetupStep::MapSize => {
+                let map_size = parse_map_size(&answer).map_err(invalid)?;
+                check_map_capacity(map_size, self.factions(), DEFAULT_PLANETS_PER_FACTION)?;
+                self.map_size = map_size;
+            }
+            SetupStep::Speed => self.speed = parse_speed(&answer).map_err(invalid)?,
+            SetupStep::ProductionTiming => {
+                self.production_timing = parse_production_timing(&answer).map_err(invalid)?;
+            }
+            SetupStep::RulesProfile => {
+                self.preset = parse_rules_preset(&answer).map_err(invalid)?;
+                self.profile = RulesProfile::from_preset(self.preset);
+            }
+            SetupStep::BuildSlots => self.profile.build_slots = parse_slots(&answer).map_err(invalid)?,
+            SetupStep::UpgradeSlots => self.profile.upgrade_slots = parse_slots(&answer).map_err(invalid)?,
+            SetupStep::ShipSlots => self.profile.ships_per_shipyard_level = parse_slots(&answer).map_err(invalid)?,
+            SetupStep::FleetCapacity => {
+                self.profile.fleet_capacity = parse_fleet_capacity(&answer).map_err(invalid)?;
+            }
+            SetupStep::Victory => self.victory = parse_victory(&answer).map_err(invalid)?,
+            SetupStep::StartingDraft => self.starting_draft = parse_yes_no(&answer).map_err(invalid)?,
+            SetupStep::RoundReport => self.round_report = parse_yes_no(&answer).map_err(invalid)?,
+        }
+
+        self.answered.push(step);
+        self.step = self.next_step(step);
+        Ok(())
+    }
+
+    /// Factions answered for so far, human and AI.
+    fn factions(&self) -> usize {
+        self.player_count as usize + self.ai_count as usize
+    }
+
+    /// Returns to the most recently answered step.
+    fn back(&mut self) -> Result<(), GameConfigurationError> {
+        let previous = self.answered.pop()
+            .ok_or_else(|| GameConfigurationError::InvalidAnswer(String::from("This is the first query.")))?;
+        self.step = Some(previous);
+        self.asked.insert(previous);
+        Ok(())
+    }
+
+    /// Answers questions from the setup until one it leaves open. A value that does not fit
+    /// stops there, and its question is asked instead.
+    fn fill_from_setup(&mut self) -> Result<(), GameConfigurationError> {
+        while let Some(step) = self.step.filter(|step| !self.asked.contains(step)) {
+            let Some((field, value)) = self.setup_answer(step) else {
+                break;
+            };
+            if let Err(error) = self.check_setup_teams(step).and_then(|()| self.record(&value)) {
+                self.asked.insert(step);
+                return Err(self.conflict(field, &value, error.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    /// The setup's answer to `step` with the field it comes from, if it has one.
+    fn setup_answer(&self, step: SetupStep) -> Option<(&'static str, String)> {
+        let setup = self.layers.setup();
+        let yes_no = |yes: bool| String::from(if yes { "y" } else { "n" });
+        match step {
+            SetupStep::PlayerCount => setup.players.map(|players| ("players", players.to_string()))
+                .or_else(|| setup.player_names.map(|names| ("player_names", names.len().to_string()))),
+            SetupStep::NamePlayers => setup.player_names.map(|_| ("player_names", yes_no(true))),
+            SetupStep::PlayerName(index) => setup.player_names?.get(index).map(|name| ("player_names", name.clone())),
+            SetupStep::FormTeams => setup.teams.map(|teams| ("teams", yes_no(!teams.is_empty()))),
+            SetupStep::Team(index) => {
+                let id = utils::name_to_id(&self.player_names[index]);
+                let team = setup.teams?.iter()
+                    .position(|members| members.iter().any(|member| utils::name_to_id(member.trim()) == id));
+                Some(("teams", team.map_or_else(String::new, |team| (team + 1).to_string())))
+            }
+            SetupStep::AiCount => setup.ai_players.map(|count| ("ai_players", count.to_string())),
+            SetupStep::MapSize => setup.map_size.map(|size| ("map_size", size)),
+            SetupStep::Speed => setup.speed.map(|speed| ("speed", speed)),
+            SetupStep::ProductionTiming => setup.production.map(|timing| ("production", timing)),
+            SetupStep::RulesProfile => setup.rules.map(|rules| ("rules", rules)),
+            SetupStep::BuildSlots => setup.build_slots.map(|slots| ("build_slots", slots.to_string())),
+            SetupStep::UpgradeSlots => setup.upgrade_slots.map(|slots| ("upgrade_slots", slots.to_string())),
+            SetupStep::ShipSlots => setup.ship_slots.map(|slots| ("ship_slots", slots.to_string())),
+            SetupStep::FleetCapacity => setup.fleet_capacity.map(|capacity| ("fleet_capacity", capacity.to_string())),
+            SetupStep::Victory => setup.victory.map(|victory| ("victory", victory)),
+            SetupStep::StartingDraft => setup.starting_draft.map(|draft| ("starting_draft", yes_no(draft))),
+            SetupStep::RoundReport => setup.round_report.map(|report| ("round_report", yes_no(report))),
+        }
+    }
+
+    /// Teams from the setup must name commanders before they are spread over the team steps,
+    /// where unknown names would go unnoticed.
+    fn check_setup_teams(&self, step: SetupStep) -> Result<(), GameConfigurationError> {
+        match self.layers.setup().teams {
+            Some(teams) if step == SetupStep::FormTeams && !teams.is_empty() => {
+                assign_teams(&self.player_names, &teams).map(|_| ())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// A setup value that does not fit, with where it and the values it depends on came from.
+    fn conflict(&self, field: &str, value: &str, reason: String) -> GameConfigurationError {
+        let related: &[&str] = match field {
+            "players" => &["player_names"],
+            "player_names" => &["players"],
+            "teams" => &["player_names"],
+            "ai_players" => &["players", "player_names"],
+            "map_size" => &["players", "player_names", "ai_players"],
+            "build_slots" | "upgrade_slots" | "ship_slots" => &["rules"],
+            _ => &[],
+        };
+        GameConfigurationError::SetupConflict {
+            field: field.to_string(),
+            value: value.to_string(),
+            origin: self.layers.source(field).cloned().unwrap_or(SetupSource::Prompt).to_string(),
+            reason,
+            related: related.iter()
+                .filter_map(|related| Some(format!("{} {}", related, self.layers.source(related)?)))
+                .collect(),
+        }
+    }
+
+    /// Every answer so far as a setup file, which answers every question of a later game
+    /// the same way. Names drawn at random are kept.
+    pub fn resolved_setup(&self) -> SetupFile {
+        let custom = self.preset == RulesPreset::Custom;
+        let teams = (self.player_count >= 3).then(|| {
+            (1..=self.player_names.len())
+                .map(|team| self.members_of(team))
+                .filter(|members| !members.is_empty())
+                .collect()
+        });
+        SetupFile {
+            players: Some(self.player_count),
+            player_names: Some(self.player_names.clone()),
+            teams,
+            ai_players: Some(self.ai_count),
+            map_size: Some(self.map_size.to_string()),
+            speed: Some(self.speed.to_string()),
+            production: Some(String::from(match self.production_timing {
+                ProductionTiming::TurnStart => "turn",
+                ProductionTiming::RoundEnd => "round",
+            })),
+            rules: Some(self.preset.to_string()),
+            build_slots: custom.then_some(self.profile.build_slots),
+            upgrade_slots: custom.then_some(self.profile.upgrade_slots),
+            ship_slots: custom.then_some(self.profile.ships_per_shipyard_level),
+            fleet_capacity: Some(self.profile.fleet_capacity),
+            victory: Some(String::from(match self.victory {
+                VictoryCondition::Elimination => "elimination",
+                VictoryCondition::Domination => "domination",
+                VictoryCondition::Both => "both",
+            })),
+            starting_draft: Some(self.starting_draft),
+            round_report: Some(self.round_report),
+            seed: self.layers.setup().seed,
+        }
+    }
+
+    /// Checks the teams once the last commander picked one; on failure every team is asked again.
+    fn check_teams(&mut self) -> Result<(), GameConfigurationError> {
+        let teams: Vec<Vec<String>> = (1..=self.player_names.len())
+            .map(|team| self.members_of(team))
+            .collect();
+        if let Err(error) = assign_teams(&self.player_names, &teams) {
+            while self.answered.last().is_some_and(|step| matches!(step, SetupStep::Team(_))) {
+                self.answered.pop();
+            }
+            self.teams.clear();
+            self.step = Some(SetupStep::Team(0));
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// Names of the commanders who picked team number `team`.
+    fn members_of(&self, team: usize) -> Vec<String> {
+        self.player_names.iter().zip(&self.teams)
+            .filter(|(_, picked)| **picked == Some(team))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Step following `step`, given the answers so far; None after the last one.
+    fn next_step(&self, step: SetupStep) -> Option<SetupStep> {
+        let after_names = if self.player_count >= 3 { SetupStep::FormTeams } else { SetupStep::AiCount };
+        let next = match step {
+            SetupStep::PlayerCount => SetupStep::NamePlayers,
+            SetupStep::NamePlayers if self.name_players => SetupStep::PlayerName(0),
+            SetupStep::NamePlayers => after_names,
+            SetupStep::PlayerName(index) if index + 1 < self.player_count as usize => SetupStep::PlayerName(index + 1),
+            SetupStep::PlayerName(_) => after_names,
+            SetupStep::FormTeams if self.form_teams => SetupStep::Team(0),
+            SetupStep::FormTeams => SetupStep::AiCount,
+            SetupStep::Team(index) if index + 1 < self.player_names.len() => SetupStep::Team(index + 1),
+            SetupStep::Team(_) => SetupStep::AiCount,
+            SetupStep::AiCount => SetupStep::MapSize,
+            SetupStep::MapSize => SetupStep::Speed,
+            SetupStep::Speed => SetupStep::ProductionTiming,
+            SetupStep::ProductionTiming => SetupStep::RulesProfile,
+            SetupStep::RulesProfile if self.preset == RulesPreset::Custom => SetupStep::BuildSlots,
+            SetupStep::RulesProfile => SetupStep::FleetCapacity,
+            SetupStep::BuildSlots => SetupStep::UpgradeSlots,
+            SetupStep::UpgradeSlots => SetupStep::ShipSlots,
+            SetupStep::ShipSlots => SetupStep::FleetCapacity,
+            SetupStep::FleetCapacity => SetupStep::Victory,
+            SetupStep::Victory => SetupStep::StartingDraft,
+            SetupStep::StartingDraft => SetupStep::RoundReport,
+            SetupStep::RoundReport => return None,
+        };
+        Some(next)
+    }
+
+    /// Builds the configuration from the answers, with the builder's validation.
+    pub fn finish(self) -> Result<GameConfiguration, GameConfigurationError> {
+        if !self.is_done() {
+            return Err(GameConfigurationError::InvalidAnswer(String::from("Setup is not complete.")));
+        }
+
+        let mut builder = self.player_names.iter()
+            .fold(GameConfigurationBuilder::new(), |builder, name| builder.player_name(name.as_str()));
+        if self.form_teams {
+            for team in 1..=self.player_names.len() {
+                let members = self.members_of(team);
+                if !members.is_empty() {
+                    builder = builder.team(members);
+                }
+            }
+        }
+
+        if let Some(seed) = self.layers.setup().seed {
+            builder = builder.seed(seed);
+        }
+
+        builder
+            .ai_players(self.ai_count)
+            .map_size(self.map_size)
+            .speed(self.speed)
+            .production_timing(self.production_timing)
+            .rules_profile(self.profile)
+            .victory(self.victory)
+            .starting_draft(self.starting_draft)
+            .round_report(self.round_report)
+            .build()
+    }
+}
+
+pub fn parse_player_count(answer: &str) -> Result<u8, String> {
+    match answer.parse::<u8>() {
+        Ok(count) if (1..=MAX_FACTIONS).contains(&(count as usize)) => Ok(count),
+        Ok(_) => Err(format!("Invalid parameter. Colonial doctrine allows 1-{MAX_FACTIONS} commanders.")),
+        Err(_) => Err(String::from("Invalid input format. Numerical value required.")),
+    }
+}
+
+/// A blank answer means no.
+pub fn parse_yes_no(answer: &str) -> Result<bool, String> {
+    match answer.to_lowercase().as_str() {
+        "y" => Ok(true),
+        "n" | "" => Ok(false),
+        _ => Err(String::from("Invalid response. Protocol requires affirmative (Y) or negative (N).")),
+    }
+}
+
+/// A team number from 1 to `player_count`, or None for a blank answer.
+pub fn parse_team(answer: &str, player_count: usize) -> Result<Option<usize>, String> {
+    match answer {
+        "" => Ok(None),
+        _ => match answer.parse::<usize>() {
+            Ok(team) if (1..=player_count).contains(&team) => Ok(Some(team)),
+            _ => Err(format!("Invalid team. Enter a number from 1 to {} or leave blank.", player_count)),
+        },
+    }
+}
+
+/// Enough AI factions to give `humans` an opponent, without going over `MAX_FACTIONS`.
+pub fn parse_ai_count(answer: &str, humans: u8) -> Result<u8, String> {
+    match answer.parse::<u8>() {
+        Ok(count) if (2..=MAX_FACTIONS).contains(&(humans as usize + count as usize)) => Ok(count),
+        Ok(_) => Err(format!("Invalid parameter. Colonial doctrine allows 2-{MAX_FACTIONS} factions in total.")),
+        Err(_) => Err(String::from("Invalid input format. Numerical value required.")),
+    }
+}
+
+pub fn parse_map_size(answer: &str) -> Result<MapSize, String> {
+    match answer {
+        "small" => Ok(MapSize::Small),
+        "medium" => Ok(MapSize::Medium),
+        "large" => Ok(MapSize::Large),
+        _ => Err(String::from("Unknown configuration. Valid options: small, medium, large")),
+    }
+}
+
+/// A blank answer means normal speed.
+pub fn parse_speed(answer: &str) -> Result<GameSpeed, String> {
+    match answer {
+        "quick" => Ok(GameSpeed::Quick),
+        "normal" | "" => Ok(GameSpeed::Normal),
+        "epic" => Ok(GameSpeed::Epic),
+        _ => Err(String::from("Unknown configuration. Valid options: quick, normal, epic")),
+    }
+}
+
+/// A blank answer means production at the start of each turn.
+pub fn parse_production_timing(answer: &str) -> Result<ProductionTiming, String> {
+    match answer {
+        "turn" | "" => Ok(ProductionTiming::TurnStart),
+        "round" => Ok(ProductionTiming::RoundEnd),
+        _ => Err(String::from("Unknown configuration. Valid options: turn, round")),
+    }
+}
+
+/// A blank answer means the classic rules.
+pub fn parse_rules_preset(answer: &str) -> Result<RulesPreset, String> {
+    match answer {
+        "classic" | "" => Ok(RulesPreset::Classic),
+        "standard" => Ok(RulesPreset::Standard),
+        "custom" => Ok(RulesPreset::Custom),
+        _ => Err(String::from("Unknown configuration. Valid options: classic, standard, custom")),
+    }
+}
+
+pub fn parse_slots(answer: &str) -> Result<u32, String> {
+    match answer.parse::<u32>() {
+        Ok(slots) if (1..=MAX_CONSTRUCTION_SLOTS).contains(&slots) => Ok(slots),
+        Ok(_) => Err(format!("Invalid parameter. Choose between 1 and {MAX_CONSTRUCTION_SLOTS}.")),
+        Err(_) => Err(String::from("Invalid input format. Numerical value required.")),
+    }
+}
+
+/// A blank answer means `DEFAULT_FLEET_CAPACITY`.
+pub fn parse_fleet_capacity(answer: &str) -> Result<usize, String> {
+    match answer {
+        "" => Ok(DEFAULT_FLEET_CAPACITY),
+        answer => match answer.parse::<usize>() {
+            Ok(capacity) if capacity > 0 => Ok(capacity),
+            _ => Err(String::from("Invalid parameter. A fleet must be able to hold at least one ship.")),
+        },
+    }
+}
+
+/// A blank answer means elimination.
+pub fn parse_victory(answer: &str) -> Result<VictoryCondition, String> {
+    match answer {
+        "elimination" | "" => Ok(VictoryCondition::Elimination),
+        "domination" => Ok(VictoryCondition::Domination),
+        "both" => Ok(VictoryCondition::Both),
+        _ => Err(String::from("Unknown configuration. Valid options: elimination, domination, both")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::input::ScriptSource;
+
+    #[test]
+    fn test_step_parsers_validate_answers() {
+        assert_eq!(parse_player_count("3"), Ok(3));
+        assert_eq!(parse_player_count("8"), Ok(8));
+        assert!(parse_player_count("9").is_err());
+        assert!(parse_player_count("two").is_err());
+
+        assert_eq!(parse_ai_count("0", 2), Ok(0));
+        assert!(parse_ai_count("0", 1).is_err());
+        assert_eq!(parse_ai_count("7", 1), Ok(7));
+        assert!(parse_ai_count("5", 4).is_err());
+
+        assert_eq!(parse_yes_no("Y"), Ok(true));
+        assert_eq!(parse_yes_no(""), Ok(false));
+        assert!(parse_yes_no("maybe").is_err());
+
+        assert_eq!(parse_team("", 3), Ok(None));
+        assert_eq!(parse_team("3", 3), Ok(Some(3)));
+        assert!(parse_team("4", 3).is_err());
+
+        assert_eq!(parse_map_size("medium"), Ok(MapSize::Medium));
+        assert!(parse_map_size("").is_err());
+        assert_eq!(parse_speed(""), Ok(GameSpeed::Normal));
+        assert_eq!(parse_production_timing("round"), Ok(ProductionTiming::RoundEnd));
+        assert_eq!(parse_rules_preset(""), Ok(RulesPreset::Classic));
+        assert!(parse_rules_preset("fast").is_err());
+        assert_eq!(parse_slots("2"), Ok(2));
+        assert!(parse_slots("0").is_err());
+        assert_eq!(parse_fleet_capacity("12"), Ok(12));
+        assert!(parse_fleet_capacity("0").is_err());
+        assert_eq!(parse_victory("both"), Ok(VictoryCondition::Both));
+    }
+
+    #[test]
+    fn test_wizard_walks_every_step_with_a_back_correction() {
+        let mut script = ScriptSource::new([
+            "2", "y", "Alice", "Bobb",
+            // The typo is only noticed at the next query
+            "back", "Bob",
+            "1", "default", "epic", "default", "default", "default", "both", "n", "y",
+        ]);
+
+        let config = SetupWizard::new().run(&mut script).unwrap();
+
+        assert_eq!(config.player_names, ["Alice", "Bob"]);
+        assert_eq!(config.teams, [None, None]);
+        assert_eq!(config.num_of_ai, 1);
+        assert_eq!(config.map_size, MapSize::Small);
+        assert_eq!(config.rules.speed, GameSpeed::Epic);
+        assert_eq!(config.rules.production_timing, ProductionTiming::TurnStart);
+        assert_eq!(config.rules.profile, RulesProfile::classic());
+        assert_eq!(config.rules.victory, VictoryCondition::Both);
+        assert!(!config.starting_draft);
+        assert!(config.round_report);
+    }
+
+    #[test]
+    fn test_custom_rules_ask_for_every_slot() {
+        let mut wizard = SetupWizard::new();
+        for answer in ["2", "y", "Alice", "Bob", "0", "small", "normal", "turn", "custom"] {
+            wizard.answer(answer).unwrap();
+        }
+        assert_eq!(wizard.step(), Some(SetupStep::BuildSlots));
+        for answer in ["3", "default", "2", "12", "elimination", "n", "n"] {
+            wizard.answer(answer).unwrap();
+        }
+
+        let config = wizard.finish().unwrap();
+        let expected = RulesProfile { build_slots: 3, ships_per_shipyard_level: 2, fleet_capacity: 12, ..RulesProfile::classic() };
+        assert_eq!(config.rules.profile, expected);
+        assert_eq!(config.rules.profile.preset(), RulesPreset::Custom);
+    }
+
+    #[test]
+    fn test_back_revisits_team_choices_and_invalid_teams_start_over() {
+        let mut wizard = SetupWizard::new();
+        for answer in ["3", "y", "Alice", "Bob", "Carol", "y", "1", "2"] {
+            wizard.answer(answer).unwrap();
+        }
+        assert_eq!(wizard.step(), Some(SetupStep::Team(2)));
+
+        wizard.answer(BACK).unwrap();
+        wizard.answer(BACK).unwrap();
+        assert_eq!(wizard.step(), Some(SetupStep::Team(0)));
+        wizard.answer("2").unwrap();
+        assert_eq!(wizard.step(), Some(SetupStep::Team(1)));
+
+        // One team holding everyone leaves nobody to fight, so all teams are asked again
+        wizard.answer("2").unwrap();
+        assert!(matches!(wizard.answer("2"), Err(GameConfigurationError::NoOpposingSides)));
+        assert_eq!(wizard.step(), Some(SetupStep::Team(0)));
+        for answer in ["1", "1", ""] {
+            wizard.answer(answer).unwrap();
+        }
+        assert_eq!(wizard.step(), Some(SetupStep::AiCount));
+    }
+
+    #[test]
+    fn test_name_prompts_count_commanders_and_reject_bad_names_on_the_spot() {
+        let mut wizard = SetupWizard::new();
+        for answer in ["3", "y", "Alice"] {
+            wizard.answer(answer).unwrap();
+        }
+        assert_eq!(wizard.prompt().unwrap(), "Commander 2 of 3 name:");
+
+        assert!(matches!(wizard.answer("  "), Err(GameConfigurationError::EmptyPlayerName)));
+        assert!(matches!(wizard.answer("alice"), Err(GameConfigurationError::DuplicatePlayerName(_))));
+        assert_eq!(wizard.step(), Some(SetupStep::PlayerName(1)));
+        wizard.answer("Bob").unwrap();
+        assert_eq!(wizard.prompt().unwrap(), "Commander 3 of 3 name:");
+    }
+
+    #[test]
+    fn test_map_size_must_fit_every_faction() {
+        let mut wizard = SetupWizard::new();
+        for answer in ["4", "y", "Alice", "Bob", "Carol", "Dave", "n", "2"] {
+            wizard.answer(answer).unwrap();
+        }
+        assert_eq!(wizard.step(), Some(SetupStep::MapSize));
+        assert_eq!(wizard.prompt().unwrap(), "QUERY: Star system density configuration (small|medium|large) [medium]:");
+
+        assert!(matches!(wizard.answer("small"), Err(GameConfigurationError::MapTooSmall { factions: 6, .. })));
+        assert_eq!(wizard.step(), Some(SetupStep::MapSize));
+        wizard.answer(DEFAULT).unwrap();
+        assert_eq!(wizard.step(), Some(SetupStep::Speed));
+    }
+
+    #[test]
+    fn test_invalid_answers_and_back_at_the_start_leave_the_step() {
+        let mut wizard = SetupWizard::new();
+
+        assert!(matches!(wizard.answer(BACK), Err(GameConfigurationError::InvalidAnswer(_))));
+        assert!(matches!(wizard.answer("9"), Err(GameConfigurationError::InvalidAnswer(_))));
+        assert_eq!(wizard.step(), Some(SetupStep::PlayerCount));
+        assert_eq!(wizard.prompt().unwrap(), "QUERY: Number of human commanders in this sector (1-8) [2]:");
+
+        wizard.answer(DEFAULT).unwrap();
+        wizard.answer("y").unwrap();
+        assert!(matches!(wizard.answer(DEFAULT), Err(GameConfigurationError::InvalidAnswer(_))));
+        assert!(matches!(wizard.answer("  "), Err(GameConfigurationError::EmptyPlayerName)));
+        assert_eq!(wizard.step(), Some(SetupStep::PlayerName(0)));
+    }
+}
@@ -1,16 +1,38 @@
-pub mod game;
-pub mod game_configuration;
+//! Colony Protocol, a turn-based space strategy game.
+//!
+//! The crate is split in two layers:
+//! - [`engine`] holds the game rules: state, command validation and application, turn
+//!   processing and saves. It performs no terminal I/O, so any frontend can drive it.
+//! - [`interface`] is the terminal frontend: setup prompts, the hot-seat run loop and
+//!   rendering of engine events.
+//! - [`sim`] plays headless games between bots to gather balance statistics.
+//!
+//! Frontends other than the terminal should depend on [`engine`] only.
+//!
+//! # Features
+//! - `std-io` (default): reading data files, save files on disk, OS entropy, and the
+//!   [`interface`] and [`sim`] modules. Without it the engine never touches the file system
+//!   or stdin; configs are built with the `load_from_string` constructors and every rng is
+//!   passed in by the caller.
+//! - `embedded-data`: the shipped data files compiled in, see [`engine::configs::embedded`].
+//! - `wasm-api`: the `wasm_api` module, JSON string entry points for a JavaScript frontend.
+//!   Builds for `wasm32-unknown-unknown` together with `default-features = false`.
 
-mod resources;
-mod configs;
-mod structure;
-mod planet;
-mod planet_name_generator;
-mod player;
-mod pending_action;
-mod map;
-mod game_state;
-mod commands;
-mod utils;
-mod ship;
-mod fleet;
\ No newline at end of file
+pub mod engine;
+#[cfg(feature = "std-io")]
+pub mod interface;
+#[cfg(feature = "std-io")]
+pub mod sim;
+#[cfg(feature = "wasm-api")]
+pub mod wasm_api;
+
+pub use engine::{
+    Command, CommandEffect, CommandError, GameEvent, GameRules, GameState, GameStateError, parse_command,
+};
+#[cfg(feature = "std-io")]
+pub use engine::configs::validation::{DataFilePaths, ValidationReport, validate_data_files};
+#[cfg(feature = "std-io")]
+pub use interface::{Game, GameConfiguration};
+
+#[cfg(test)]
+mod test_support;
@@ -0,0 +1,491 @@
+//! Headless games between bots, for balancing the configs. A simulation plays complete games
+//! on seeded maps without any I/O and collects how they ended: game length, wins by seat and
+//! by personality, planets held at victory and the resources stockpiled along the way.
+
+mod bot;
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::fmt::Write;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use crate::engine::appearance;
+use crate::engine::configs::{self, DataFileError, PlanetNameParts, PlanetNamesConfigError};
+use crate::engine::configs::planet_names::PLANET_NAMES_CONFIG_FILE;
+use crate::engine::configs::ship_config::{SHIP_CONFIG_FILE, ShipConfig, ShipConfigError};
+use crate::engine::configs::structure_config::{STRUCTURE_CONFIG_FILE, StructureConfig, StructureConfigError};
+use crate::engine::game_event::{GameEvent, VictoryKind};
+use crate::engine::game_rules::{GameRules, VictoryCondition};
+use crate::engine::game_state::{GameState, GameStateError};
+use crate::engine::map::{Map, MapError, MapSize};
+use crate::engine::planet_name_generator::{PlanetNameGenerator, PlanetNameGeneratorError};
+use crate::engine::player::{Player, PlayerId};
+use crate::engine::progress::{NoProgress, ProgressSink};
+use crate::engine::{CommandEffect, utils};
+use crate::interface::game::GameError;
+
+pub use bot::{Personality, play_turn};
+
+/// Rounds after which a game without a winner counts as a draw.
+pub const DEFAULT_TURN_CAP: u32 = 150;
+
+/// Stage name [`simulate_with_progress`] reports under
+pub const SIMULATION_STAGE: &str = "Simulating games";
+
+/// Rounds between the rows of the resource curve in the summary table.
+const CURVE_STEP: usize = 10;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SimError {
+    #[error(transparent)]
+    DataFile(#[from] DataFileError),
+
+    #[error(transparent)]
+    StructureConfig(#[from] StructureConfigError),
+
+    #[error(transparent)]
+    ShipConfig(#[from] ShipConfigError),
+
+    #[error(transparent)]
+    PlanetNames(#[from] PlanetNamesConfigError),
+
+    #[error(transparent)]
+    PlanetNameGenerator(#[from] PlanetNameGeneratorError),
+
+    #[error(transparent)]
+    Map(#[from] MapError),
+
+    #[error(transparent)]
+    GameState(#[from] GameStateError),
+
+    #[error(transparent)]
+    Game(#[from] GameError),
+
+    #[error("A simulation needs at least two bots, got {0}")]
+    TooFewBots(usize),
+}
+
+/// The config files every simulated game is built from, kept as text since each game needs
+/// configs of its own.
+pub struct SimData {
+    structures: String,
+    ships: String,
+    planet_names: String,
+}
+
+impl SimData {
+    /// Reads the data files from the data directory.
+    pub fn load() -> Result<Self, SimError> {
+        Ok(SimData {
+            structures: configs::read_config_file(configs::data_file(STRUCTURE_CONFIG_FILE))?,
+            ships: configs::read_config_file(configs::data_file(SHIP_CONFIG_FILE))?,
+            planet_names: configs::read_config_file(configs::data_file(PLANET_NAMES_CONFIG_FILE))?,
+        })
+    }
+
+    pub fn from_strings(structures: impl Into<String>, ships: impl Into<String>, planet_names: impl Into<String>) -> Self {
+        SimData { structures: structures.into(), ships: ships.into(), planet_names: planet_names.into() }
+    }
+}
+
+/// What to simulate.
+#[derive(Debug, Clone)]
+pub struct SimOptions {
+    pub games: u32,
+    /// Seed of the first game; game `n` is played with `seed + n`
+    pub seed: u64,
+    pub turn_cap: u32,
+    pub map_size: MapSize,
+    /// One bot per entry. Each game seats them one place further round the table, so every
+    /// personality plays from every starting position.
+    pub personalities: Vec<Personality>,
+    pub rules: GameRules,
+}
+
+impl SimOptions {
+    /// One bot of each personality on a small map, able to win by elimination or domination.
+    pub fn new(games: u32) -> Self {
+        SimOptions {
+            games,
+            seed: 0,
+            turn_cap: DEFAULT_TURN_CAP,
+            map_size: MapSize::Small,
+            personalities: Personality::ALL.to_vec(),
+            rules: GameRules { victory: VictoryCondition::Both, ..GameRules::default() },
+        }
+    }
+}
+
+/// How one simulated game ended.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameOutcome {
+    pub seed: u64,
+    /// Personality in each seat, in turn order
+    pub seats: Vec<Personality>,
+    /// Rounds completed; the turn cap for a draw
+    pub turns: u32,
+    /// Seats of the winning side, empty for a draw
+    pub winners: Vec<usize>,
+    pub victory: Option<VictoryKind>,
+    /// Planets held by each seat when the game ended
+    pub planets: Vec<usize>,
+    /// Minerals, gas and energy stockpiled by each seat at the end of every round
+    pub resource_curve: Vec<Vec<u32>>,
+}
+
+/// Everything a simulation found out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimReport {
+    pub turn_cap: u32,
+    pub games: Vec<GameOutcome>,
+}
+
+/// Plays `options.games` games between bots and reports on them.
+pub fn simulate(options: &SimOptions, data: &SimData) -> Result<SimReport, SimError> {
+    simulate_with_progress(options, data, &mut NoProgress)
+}
+
+/// Like [`simulate`], reporting each finished game to `progress`.
+pub fn simulate_with_progress(
+    options: &SimOptions,
+    data: &SimData,
+    progress: &mut dyn ProgressSink,
+) -> Result<SimReport, SimError> {
+    if options.personalities.len() < 2 {
+        return Err(SimError::TooFewBots(options.personalities.len()));
+    }
+    let total = options.games as usize;
+    let games = (0..options.games)
+        .map(|game| {
+            let mut seats = options.personalities.clone();
+            seats.rotate_left(game as usize % options.personalities.len());
+            let outcome = play_game(options, data, &seats, options.seed.wrapping_add(u64::from(game)))?;
+            progress.report(SIMULATION_STAGE, game as usize + 1, total);
+            Ok(outcome)
+        })
+        .collect::<Result<_, SimError>>()?;
+    Ok(SimReport { turn_cap: options.turn_cap, games })
+}
+
+/// Plays one game to a victory or the turn cap, with the bots seated in `seats` order.
+pub fn play_game(options: &SimOptions, data: &SimData, seats: &[Personality], seed: u64) -> Result<GameOutcome, SimError> {
+    let mut state = new_game(options, data, seats, seed)?;
+    let seat_ids: Vec<PlayerId> = state.players_order.iter().cloned().collect();
+    let mut resource_curve = Vec::new();
+    let mut winners = Vec::new();
+    let mut victory = None;
+
+    while !state.is_over() && state.turn <= options.turn_cap {
+        let player_id = state.current_player().clone();
+        let seat = seat_ids.iter().position(|id| *id == player_id).expect("Every player has a seat");
+        let round = state.turn;
+        play_turn(&mut state, seats[seat]);
+
+        let player_name = state.players[&player_id].name.clone();
+        let events = state.apply_effect(CommandEffect::EndTurn { player_name }, &player_id)?;
+        for event in events {
+            if let GameEvent::Victory { player_names, kind, .. } = event {
+                winners = (0..seat_ids.len())
+                    .filter(|seat| player_names.contains(&state.players[&seat_ids[*seat]].name))
+                    .collect();
+                victory = Some(kind);
+            }
+        }
+        if state.turn != round || state.is_over() {
+            resource_curve.push(seat_ids.iter().map(|id| stockpile(&state, id)).collect());
+        }
+    }
+
+    Ok(GameOutcome {
+        seed,
+        seats: seats.to_vec(),
+        turns: resource_curve.len() as u32,
+        winners,
+        victory,
+        planets: seat_ids.iter().map(|id| state.players[id].planets.len()).collect(),
+        resource_curve,
+    })
+}
+
+/// Sets up a game the way a new session would, but drawing every random choice from `seed`.
+fn new_game(options: &SimOptions, data: &SimData, seats: &[Personality], seed: u64) -> Result<GameState, SimError> {
+    let structure_config = StructureConfig::load_from_string(&data.structures)?;
+    let ship_config = ShipConfig::load_from_string(&data.ships)?;
+    let name_parts = PlanetNameParts::load_from_string(&data.planet_names)?;
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut name_generator = PlanetNameGenerator::with_seed(name_parts, seed);
+    let mut map = Map::generate(options.map_size, &mut name_generator, &mut rng)?;
+
+    let names: Vec<String> = seats.iter().enumerate()
+        .map(|(seat, personality)| format!("Bot {} {}", seat + 1, personality))
+        .collect();
+    let name_refs: Vec<&str> = names.iter().map(String::as_str).collect();
+    let mut players: BTreeMap<PlayerId, Player> = names.iter()
+        .zip(appearance::assign(&name_refs))
+        .map(|(name, appearance)| {
+            let mut player = Player::new(utils::name_to_id(name), name.clone(), appearance);
+            player.ai = true;
+            (player.id.clone(), player)
+        })
+        .collect();
+    let players_order: VecDeque<PlayerId> = names.iter().map(|name| utils::name_to_id(name)).collect();
+
+    map.assign_starting_planets(&mut players, &structure_config, options.rules.speed, &mut rng)
+        .map_err(GameError::from)?;
+    map.place_ruins(options.rules.ruin_chance_percent, &mut rng);
+    if options.rules.victory.allows_domination() {
+        map.place_key_planets(options.rules.key_planets, &mut rng);
+    }
+
+    Ok(GameState::new(players, players_order, map, structure_config, ship_config, options.rules.clone())?.with_seed(seed))
+}
+
+/// Resources a player has stockpiled over all their planets.
+fn stockpile(state: &GameState, player_id: &PlayerId) -> u32 {
+    state.players[player_id].planets.iter()
+        .filter_map(|planet_id| state.map.planets.get(planet_id))
+        .map(|planet| {
+            let resources = &planet.available_resources;
+            resources.minerals.saturating_add(resources.gas).saturating_add(resources.energy)
+        })
+        .fold(0, u32::saturating_add)
+}
+
+impl GameOutcome {
+    pub fn is_draw(&self) -> bool {
+        self.winners.is_empty()
+    }
+}
+
+impl SimReport {
+    /// Games that reached the turn cap without a winner.
+    pub fn draws(&self) -> usize {
+        self.games.iter().filter(|game| game.is_draw()).count()
+    }
+
+    /// Rounds played per game on average.
+    pub fn average_length(&self) -> f64 {
+        average(self.games.iter().map(|game| f64::from(game.turns)))
+    }
+
+    /// Share of the games won from each seat, in turn order.
+    pub fn win_rate_by_seat(&self) -> Vec<f64> {
+        let seats = self.games.iter().map(|game| game.seats.len()).max().unwrap_or(0);
+        (0..seats)
+            .map(|seat| {
+                let won = self.games.iter().filter(|game| game.winners.contains(&seat)).count();
+                share(won, self.games.len())
+            })
+            .collect()
+    }
+
+    /// Share of the games each personality took part in that it won.
+    pub fn win_rate_by_personality(&self) -> Vec<(Personality, f64)> {
+        let mut personalities: Vec<_> = self.games.iter().flat_map(|game| game.seats.iter().copied()).collect();
+        personalities.sort();
+        personalities.dedup();
+        personalities.into_iter()
+            .map(|personality| {
+                let played = self.games.iter().filter(|game| game.seats.contains(&personality)).count();
+                let won = self.games.iter()
+                    .filter(|game| game.winners.iter().any(|seat| game.seats[*seat] == personality))
+                    .count();
+                (personality, share(won, played))
+            })
+            .collect()
+    }
+
+    /// Planets the winning side held when the game ended, on average over the decided games.
+    pub fn average_planets_at_victory(&self) -> Option<f64> {
+        let decided: Vec<_> = self.games.iter().filter(|game| !game.is_draw()).collect();
+        if decided.is_empty() {
+            return None;
+        }
+        Some(average(decided.iter().map(|game| {
+            game.winners.iter().map(|seat| game.planets[*seat]).sum::<usize>() as f64
+        })))
+    }
+
+    /// Resources stockpiled per bot at the end of each round, on average over the games still
+    /// running by then.
+    pub fn resource_curve(&self) -> Vec<f64> {
+        let rounds = self.games.iter().map(|game| game.resource_curve.len()).max().unwrap_or(0);
+        (0..rounds)
+            .map(|round| average(self.games.iter()
+                .filter_map(|game| game.resource_curve.get(round))
+                .flatten()
+                .map(|stockpile| f64::from(*stockpile))))
+            .collect()
+    }
+
+    /// One line per game: seed, seating, length, winners, victory and planets per seat.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("seed,seats,turns,winners,victory,planets\n");
+        for game in &self.games {
+            let seats: Vec<_> = game.seats.iter().map(Personality::to_string).collect();
+            let winners: Vec<_> = game.winners.iter().map(|seat| (seat + 1).to_string()).collect();
+            let victory = match &game.victory {
+                Some(VictoryKind::Elimination) => "elimination",
+                Some(VictoryKind::Domination { .. }) => "domination",
+                None => "draw",
+            };
+            let planets: Vec<_> = game.planets.iter().map(usize::to_string).collect();
+            let _ = writeln!(
+                csv, "{},{},{},{},{},{}",
+                game.seed, seats.join(" "), game.turns, winners.join(" "), victory, planets.join(" ")
+            );
+        }
+        csv
+    }
+}
+
+impl fmt::Display for SimReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "=== SIMULATION: {} game(s), turn cap {} ===", self.games.len(), self.turn_cap)?;
+        writeln!(f, "Average length: {:.1} rounds", self.average_length())?;
+        writeln!(f, "Draws: {}", self.draws())?;
+        match self.average_planets_at_victory() {
+            Some(planets) => writeln!(f, "Planets held at victory: {:.1}", planets)?,
+            None => writeln!(f, "Planets held at victory: -")?,
+        }
+
+        writeln!(f, "\n{:<14} {:>8}", "Seat", "Win rate")?;
+        for (seat, rate) in self.win_rate_by_seat().iter().enumerate() {
+            writeln!(f, "{:<14} {:>7.0}%", seat + 1, rate * 100.0)?;
+        }
+        writeln!(f, "\n{:<14} {:>8}", "Personality", "Win rate")?;
+        for (personality, rate) in self.win_rate_by_personality() {
+            writeln!(f, "{:<14} {:>7.0}%", personality.to_string(), rate * 100.0)?;
+        }
+
+        writeln!(f, "\n{:<14} {:>8}", "Round", "Stockpile")?;
+        let curve = self.resource_curve();
+        for (round, stockpile) in curve.iter().enumerate().skip(CURVE_STEP - 1).step_by(CURVE_STEP) {
+            writeln!(f, "{:<14} {:>8.0}", round + 1, stockpile)?;
+        }
+        Ok(())
+    }
+}
+
+fn average(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, count) = values.fold((0.0, 0usize), |(sum, count), value| (sum + value, count + 1));
+    if count == 0 { 0.0 } else { sum / count as f64 }
+}
+
+fn share(part: usize, whole: usize) -> f64 {
+    if whole == 0 { 0.0 } else { part as f64 / whole as f64 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::progress::RecordedProgress;
+    use crate::test_support::{PLANET_NAMES_FIXTURE, SHIPS_FIXTURE, STRUCTURES_FIXTURE};
+
+    fn fixture_data() -> SimData {
+        SimData::from_strings(STRUCTURES_FIXTURE, SHIPS_FIXTURE, PLANET_NAMES_FIXTURE)
+    }
+
+    #[test]
+    fn test_tiny_simulations_terminate_with_a_winner_or_draw() {
+        let options = SimOptions { seed: 11, turn_cap: 30, ..SimOptions::new(3) };
+
+        let report = simulate(&options, &fixture_data()).unwrap();
+
+        assert_eq!(report.games.len(), 3);
+        for game in &report.games {
+            assert!(game.turns <= options.turn_cap, "{:?}", game);
+            assert_eq!(game.is_draw(), game.victory.is_none(), "{:?}", game);
+            assert!(game.winners.iter().all(|seat| game.planets[*seat] > 0), "{:?}", game);
+            assert_eq!(game.resource_curve.len() as u32, game.turns, "{:?}", game);
+        }
+        // Rotating the seats lets every personality start from every position
+        let openers: Vec<_> = report.games.iter().map(|game| game.seats[0]).collect();
+        assert_eq!(openers, Personality::ALL);
+        let summary = report.to_string();
+        assert!(summary.contains("Average length"), "{summary}");
+        assert_eq!(report.to_csv().lines().count(), 4);
+    }
+
+    #[test]
+    fn test_same_seed_plays_the_same_game() {
+        let options = SimOptions { seed: 5, turn_cap: 15, ..SimOptions::new(1) };
+
+        let first = simulate(&options, &fixture_data()).unwrap();
+        let second = simulate(&options, &fixture_data()).unwrap();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_progress_counts_finished_games() {
+        let options = SimOptions { seed: 2, turn_cap: 5, ..SimOptions::new(2) };
+        let mut progress = RecordedProgress::default();
+
+        let report = simulate_with_progress(&options, &fixture_data(), &mut progress).unwrap();
+
+        assert_eq!(report, simulate(&options, &fixture_data()).unwrap());
+        assert_eq!(progress.reports, [
+            (String::from(SIMULATION_STAGE), 1, 2),
+            (String::from(SIMULATION_STAGE), 2, 2),
+        ]);
+    }
+
+    /// Digest of the state after each turn of a bot game, up to `turns` turns.
+    fn digests(options: &SimOptions, turns: usize) -> Vec<u64> {
+        let seats = Personality::ALL.to_vec();
+        let mut state = new_game(options, &fixture_data(), &seats, options.seed).unwrap();
+        let seat_ids: Vec<PlayerId> = state.players_order.iter().cloned().collect();
+        let mut digests = Vec::new();
+        while !state.is_over() && digests.len() < turns {
+            let player_id = state.current_player().clone();
+            let seat = seat_ids.iter().position(|id| *id == player_id).unwrap();
+            play_turn(&mut state, seats[seat]);
+            let player_name = state.players[&player_id].name.clone();
+            state.apply_effect(CommandEffect::EndTurn { player_name }, &player_id).unwrap();
+            digests.push(state.digest());
+        }
+        digests
+    }
+
+    #[test]
+    fn test_same_seed_matches_digest_after_every_turn() {
+        let options = SimOptions { seed: 9, ..SimOptions::new(1) };
+
+        let first = digests(&options, 60);
+        let second = digests(&options, 60);
+
+        assert!(first.len() > 30);
+        for (turn, (first, second)) in first.iter().zip(&second).enumerate() {
+            assert_eq!(first, second, "Runs diverged after turn {}", turn + 1);
+        }
+        assert_eq!(first.len(), second.len());
+    }
+
+    #[test]
+    fn test_report_statistics() {
+        let game = |winners: Vec<usize>, turns| GameOutcome {
+            seed: 0,
+            seats: vec![Personality::Raider, Personality::Turtle],
+            turns,
+            victory: (!winners.is_empty()).then_some(VictoryKind::Elimination),
+            winners,
+            planets: vec![4, 1],
+            resource_curve: vec![vec![100, 300]; turns as usize],
+        };
+        let report = SimReport { turn_cap: 20, games: vec![game(vec![0], 10), game(Vec::new(), 20)] };
+
+        assert_eq!(report.average_length(), 15.0);
+        assert_eq!(report.draws(), 1);
+        assert_eq!(report.win_rate_by_seat(), [0.5, 0.0]);
+        assert_eq!(report.win_rate_by_personality(), [(Personality::Raider, 0.5), (Personality::Turtle, 0.0)]);
+        assert_eq!(report.average_planets_at_victory(), Some(4.0));
+        assert_eq!(report.resource_curve()[15], 200.0);
+
+        let alone = SimOptions { personalities: vec![Personality::Raider], ..SimOptions::new(1) };
+        assert!(matches!(simulate(&alone, &fixture_data()), Err(SimError::TooFewBots(1))));
+    }
+}
@@ -0,0 +1,302 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::engine::game_state::{GameState, ScheduledItem};
+use crate::engine::parse_command;
+use crate::engine::planet::PlanetId;
+use crate::engine::player::PlayerId;
+use crate::engine::rules;
+use crate::engine::ship::FleetId;
+
+/// Playing style of a simulated commander.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Personality {
+    /// Grows its economy towards arks and settles every neutral planet it can reach
+    Expansionist,
+    /// Puts warships first and bombards the nearest enemy planet
+    Raider,
+    /// Shields its planets and only sends ships out in force
+    Turtle,
+}
+
+impl Personality {
+    pub const ALL: [Personality; 3] = [Personality::Expansionist, Personality::Raider, Personality::Turtle];
+
+    /// Goals each planet works through in order, saving up for the next one instead of spending
+    /// on whatever is affordable.
+    fn plan(self) -> &'static [Goal] {
+        match self {
+            Personality::Expansionist => EXPANSIONIST_PLAN,
+            Personality::Raider => RAIDER_PLAN,
+            Personality::Turtle => TURTLE_PLAN,
+        }
+    }
+
+    /// Structures to upgrade once the plan is done, most wanted first.
+    fn upgrade_order(self) -> &'static [&'static str] {
+        match self {
+            Personality::Expansionist | Personality::Raider => {
+                &["mining_complex", "planetary_capital", "power_grid", "storage_complex"]
+            }
+            Personality::Turtle => &["defense_shield", "mining_complex", "planetary_capital", "power_grid"],
+        }
+    }
+
+    /// Ship types to build once the plan is done, most wanted first, with how many of each to
+    /// keep at most.
+    fn ship_order(self) -> &'static [(&'static str, usize)] {
+        match self {
+            Personality::Expansionist => &[("ark", usize::MAX), ("interceptor", 2)],
+            Personality::Raider => &[("ravager", usize::MAX), ("ark", 1)],
+            Personality::Turtle => &[("ark", 1), ("interceptor", usize::MAX)],
+        }
+    }
+
+    /// Ships a fleet without an ark gathers before it goes after enemy planets.
+    fn strike_size(self) -> usize {
+        match self {
+            Personality::Expansionist => 4,
+            Personality::Raider => 2,
+            Personality::Turtle => 6,
+        }
+    }
+}
+
+/// One step of a personality's plan.
+#[derive(Debug, Clone, Copy)]
+enum Goal {
+    /// Structure at this level or above on the planet
+    Level(&'static str, u16),
+    /// At least this many ships of a type across the empire
+    Ships(&'static str, usize),
+}
+
+/// The economy every plan starts with, up to a level 2 shipyard.
+macro_rules! economy {
+    ($($extra:expr),* $(,)?) => {
+        &[
+            Goal::Level("power_grid", 1),
+            Goal::Level("planetary_capital", 2),
+            Goal::Level("storage_complex", 1),
+            Goal::Level("mining_complex", 1),
+            Goal::Level("power_grid", 2),
+            Goal::Level("gas_refinery", 1),
+            Goal::Level("mining_complex", 2),
+            Goal::Level("orbital_shipyard", 1),
+            Goal::Level("gas_refinery", 2),
+            Goal::Level("orbital_shipyard", 2),
+            $($extra,)*
+            Goal::Level("planetary_capital", 3),
+            Goal::Level("storage_complex", 2),
+            Goal::Level("mining_complex", 3),
+            Goal::Level("power_grid", 3),
+            Goal::Level("gas_refinery", 3),
+            Goal::Level("orbital_shipyard", 3),
+        ]
+    };
+}
+
+const EXPANSIONIST_PLAN: &[Goal] = economy![];
+const RAIDER_PLAN: &[Goal] = economy![Goal::Ships("ravager", 3)];
+const TURTLE_PLAN: &[Goal] = economy![Goal::Level("defense_shield", 2), Goal::Ships("interceptor", 2)];
+
+impl fmt::Display for Personality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Personality::Expansionist => write!(f, "expansionist"),
+            Personality::Raider => write!(f, "raider"),
+            Personality::Turtle => write!(f, "turtle"),
+        }
+    }
+}
+
+impl FromStr for Personality {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Personality::ALL.into_iter()
+            .find(|personality| personality.to_string() == name)
+            .ok_or_else(|| format!("Unknown personality '{}'. Valid options: expansionist, raider, turtle", name))
+    }
+}
+
+/// Gives the current player's orders for the turn, without ending it. Every order goes through
+/// the same parsing and validation as typed commands; the ones the engine rejects are dropped.
+pub fn play_turn(state: &mut GameState, personality: Personality) {
+    let player_id = state.current_player().clone();
+    let mut planets = state.players[&player_id].planets.clone();
+    planets.sort();
+
+    for planet_id in &planets {
+        if !follow_plan(state, &player_id, planet_id, personality) {
+            continue;
+        }
+        build_ships(state, &player_id, planet_id, personality);
+        for structure in personality.upgrade_order() {
+            if order(state, &format!("upgrade {} {}", planet_id, structure)) {
+                break;
+            }
+        }
+    }
+
+    gather_ships(state, &player_id);
+    let fleets: Vec<FleetId> = state.players[&player_id].fleets.keys().cloned().collect();
+    for fleet_id in fleets {
+        command_fleet(state, &player_id, &fleet_id, personality);
+    }
+}
+
+/// Parses, validates and applies one order for the current player; false when it was rejected.
+fn order(state: &mut GameState, line: &str) -> bool {
+    let player_id = state.current_player().clone();
+    let Ok(effect) = parse_command(line).and_then(|command| command.execute(state)) else {
+        return false;
+    };
+    state.apply_effect(effect, &player_id).is_ok()
+}
+
+/// Orders the planet's first unmet goal unless it is already underway; true once every goal is met.
+fn follow_plan(state: &mut GameState, player_id: &PlayerId, planet_id: &PlanetId, personality: Personality) -> bool {
+    let planet = &state.map.planets[planet_id];
+    let next = personality.plan().iter().find(|goal| match goal {
+        Goal::Level(structure, level) => planet.get_structure_level(&structure.to_string()) < *level,
+        Goal::Ships(ship, count) => ships_of_type(state, player_id, ship) < *count,
+    });
+    let line = match next {
+        None => return true,
+        Some(Goal::Level(structure, _)) => {
+            let underway = state.completion_schedule(player_id).values().flatten().any(|item| matches!(
+                item,
+                ScheduledItem::Structure { structure_id, planet_id: at, .. } if at == planet_id && structure_id == structure
+            ));
+            if underway {
+                return false;
+            }
+            let verb = if planet.get_structure_level(&structure.to_string()) == 0 { "build" } else { "upgrade" };
+            format!("{} {} {}", verb, planet_id, structure)
+        }
+        Some(Goal::Ships(ship, _)) => format!("build_ship {} {}", planet_id, ship),
+    };
+    order(state, &line);
+    false
+}
+
+fn build_ships(state: &mut GameState, player_id: &PlayerId, planet_id: &PlanetId, personality: Personality) {
+    while state.free_shipyard_slots(player_id, planet_id) > 0 {
+        let wanted: Vec<_> = personality.ship_order().iter()
+            .filter(|(ship, most)| ships_of_type(state, player_id, ship) < *most)
+            .map(|(ship, _)| *ship)
+            .collect();
+        let queued = wanted.iter()
+            .any(|ship| order(state, &format!("build_ship {} {}", planet_id, ship)));
+        if !queued {
+            break;
+        }
+    }
+}
+
+/// Ships of `ship_type` a player has, counting the ones being built.
+fn ships_of_type(state: &GameState, player_id: &PlayerId, ship_type: &str) -> usize {
+    let player = &state.players[player_id];
+    let built = player.ships.values().filter(|ship| ship.ship_type == ship_type).count();
+    let queued = state.completion_schedule(player_id).values()
+        .flatten()
+        .filter(|item| matches!(item, ScheduledItem::Ship { ship_type: queued, .. } if queued == ship_type))
+        .count();
+    built + queued
+}
+
+/// Puts standalone ships into a fleet at their planet, joining one that stays put if there is any.
+fn gather_ships(state: &mut GameState, player_id: &PlayerId) {
+    let player = &state.players[player_id];
+    let mut standalone: Vec<_> = player.ships.values()
+        .filter(|ship| ship.fleet_id.is_none())
+        .map(|ship| (ship.location.clone(), ship.id.clone()))
+        .collect();
+    standalone.sort();
+
+    let mut locations: Vec<_> = standalone.iter().map(|(location, _)| location.clone()).collect();
+    locations.dedup();
+    for location in locations {
+        let ship_ids: Vec<_> = standalone.iter()
+            .filter(|(at, _)| *at == location)
+            .map(|(_, ship_id)| ship_id.as_str())
+            .collect();
+        let player = &state.players[player_id];
+        let idle_fleet = player.fleets.values()
+            .filter(|fleet| fleet.location == location)
+            .find(|fleet| !player.has_pending_fleet_move(&fleet.id) && !player.has_pending_fleet_bombardment(&fleet.id))
+            .map(|fleet| fleet.id.clone());
+        let joined = idle_fleet
+            .is_some_and(|fleet_id| order(state, &format!("fleet add {} {}", fleet_id, ship_ids.join(" "))));
+        if !joined {
+            let name = format!("{}_{}", location, state.turn);
+            order(state, &format!("fleet create {} {}", name, ship_ids.join(" ")));
+        }
+    }
+}
+
+/// Settles with an ark, bombards an enemy planet in orbit, or heads one hop towards a target.
+fn command_fleet(state: &mut GameState, player_id: &PlayerId, fleet_id: &FleetId, personality: Personality) {
+    let player = &state.players[player_id];
+    let Some(fleet) = player.fleets.get(fleet_id) else {
+        return;
+    };
+    if player.has_pending_fleet_move(fleet_id) || player.has_pending_fleet_bombardment(fleet_id) {
+        return;
+    }
+    let location = fleet.location.clone();
+    let has_ark = rules::fleet_has_ark(fleet, player);
+    let strength = fleet.ship_count();
+    let planet = &state.map.planets[&location];
+
+    if has_ark && rules::can_colonize(fleet, planet, player, state).is_ok() {
+        order(state, &format!("fleet colonize {}", fleet_id));
+        return;
+    }
+    if rules::can_bombard(fleet, planet, player, state).is_ok() && planet.get_shield_hp() > 0 {
+        order(state, &format!("fleet bombard {}", fleet_id));
+        return;
+    }
+
+    let target = if has_ark {
+        nearest(state, &location, |planet_id| {
+            let planet = &state.map.planets[planet_id];
+            planet.get_owner().is_none() && rules::colonize_target(planet, player, state).is_ok()
+        })
+    } else if strength >= personality.strike_size() {
+        nearest(state, &location, |planet_id| {
+            let planet = &state.map.planets[planet_id];
+            rules::bombard_target(planet, player, state).is_ok()
+        })
+    } else {
+        None
+    };
+    if let Some(next_hop) = target.and_then(|target| next_hop(state, &location, &target)) {
+        order(state, &format!("fleet move {} {}", fleet_id, next_hop));
+    }
+}
+
+/// Closest planet other than `from` matching `wanted`, ties broken by id.
+fn nearest(state: &GameState, from: &PlanetId, wanted: impl Fn(&PlanetId) -> bool) -> Option<PlanetId> {
+    let graph = state.map.graph();
+    let mut candidates: Vec<_> = state.map.planets.keys()
+        .filter(|planet_id| *planet_id != from && wanted(planet_id))
+        .filter_map(|planet_id| graph.distance(from, planet_id).map(|distance| (distance, planet_id.clone())))
+        .collect();
+    candidates.sort();
+    candidates.into_iter().next().map(|(_, planet_id)| planet_id)
+}
+
+/// Neighbor of `from` on a shortest route to `target`.
+fn next_hop(state: &GameState, from: &PlanetId, target: &PlanetId) -> Option<PlanetId> {
+    let graph = state.map.graph();
+    let mut hops: Vec<_> = graph.neighbors(from)
+        .filter_map(|(neighbor, edge)| {
+            let rest = if neighbor == target { 0 } else { graph.distance(neighbor, target)? };
+            Some((u32::from(edge) + rest, neighbor.clone()))
+        })
+        .collect();
+    hops.sort();
+    hops.into_iter().next().map(|(_, planet_id)| planet_id)
+}
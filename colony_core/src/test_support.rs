@@ -0,0 +1,166 @@
+// Shared fixtures for unit tests. Configs come from tests/fixtures so balance changes
+// to the shipped data/ files never break gameplay tests.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use crate::engine::appearance;
+use crate::engine::commands::command::{CommandEffect, CommandError};
+use crate::engine::commands::parser;
+use crate::engine::configs::planet_names::PlanetNameParts;
+use crate::engine::configs::ship_config::ShipConfig;
+use crate::engine::configs::structure_config::StructureConfig;
+use crate::engine::game_event::GameEvent;
+use crate::engine::game_rules::GameRules;
+use crate::engine::game_state::{GamePhase, GameState};
+use crate::engine::map::{Map, MapSize};
+use crate::engine::planet_name_generator::PlanetNameGenerator;
+use crate::engine::player::{Player, PlayerId};
+#[cfg(feature = "std-io")]
+use crate::interface::replay::GameSetup;
+
+pub const STRUCTURES_FIXTURE: &str = include_str!("../tests/fixtures/structures.json");
+pub const SHIPS_FIXTURE: &str = include_str!("../tests/fixtures/ships.json");
+pub const PLANET_NAMES_FIXTURE: &str = include_str!("../tests/fixtures/planet_names.json");
+
+/// Player ids of the players created by `fixture_game`, in turn order.
+pub const FIXTURE_PLAYERS: [&str; 2] = ["alice", "bob"];
+
+pub fn fixture_structure_config() -> StructureConfig {
+    StructureConfig::load_from_string(STRUCTURES_FIXTURE).expect("Structure fixture must be valid")
+}
+
+pub fn fixture_ship_config() -> ShipConfig {
+    ShipConfig::load_from_string(SHIPS_FIXTURE).expect("Ship fixture must be valid")
+}
+
+pub fn fixture_name_generator(seed: u64) -> PlanetNameGenerator {
+    let name_parts = PlanetNameParts::load_from_string(PLANET_NAMES_FIXTURE)
+        .expect("Planet names fixture must be valid");
+    PlanetNameGenerator::with_seed(name_parts, seed)
+}
+
+/// Builds a small two-player game from fixture configs. The same seed always yields the same map
+/// and starting planets.
+pub fn fixture_game(seed: u64) -> GameState {
+    fixture_game_with(seed, &FIXTURE_PLAYERS)
+}
+
+/// Player ids of the players created by `fixture_team_game`, in turn order.
+pub const FIXTURE_TEAM_PLAYERS: [&str; 4] = ["alice", "bob", "carol", "dave"];
+
+/// Builds a small 2v2 game: alice and carol form team 1, bob and dave team 2.
+pub fn fixture_team_game(seed: u64) -> GameState {
+    let mut state = fixture_game_with(seed, &FIXTURE_TEAM_PLAYERS);
+    for (index, id) in FIXTURE_TEAM_PLAYERS.iter().enumerate() {
+        state.players.get_mut(*id).unwrap().team = Some(index as u8 % 2 + 1);
+    }
+    state
+}
+
+/// Builds the game `fixture_game` would, but with every planet still neutral, as it is
+/// before a starting planet draft.
+pub fn fixture_draft_game(seed: u64) -> GameState {
+    build_fixture_game(seed, &FIXTURE_PLAYERS, false)
+}
+
+fn fixture_game_with(seed: u64, player_ids: &[&str]) -> GameState {
+    build_fixture_game(seed, player_ids, true)
+}
+
+fn build_fixture_game(seed: u64, player_ids: &[&str], assign_starting_planets: bool) -> GameState {
+    let structure_config = fixture_structure_config();
+    let ship_config = fixture_ship_config();
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut name_generator = fixture_name_generator(seed);
+    let mut map = Map::generate(MapSize::Small, &mut name_generator, &mut rng)
+        .expect("Fixture map generation must succeed");
+
+    let names: Vec<_> = player_ids.iter().map(|id| id[..1].to_uppercase() + &id[1..]).collect();
+    let appearances = appearance::assign(&names.iter().map(String::as_str).collect::<Vec<_>>());
+    let mut players: BTreeMap<PlayerId, Player> = player_ids.iter()
+        .zip(names)
+        .zip(appearances)
+        .map(|((id, name), appearance)| (id.to_string(), Player::new(id.to_string(), name, appearance)))
+        .collect();
+    let players_order: VecDeque<PlayerId> = player_ids.iter().map(|id| id.to_string()).collect();
+
+    let rules = GameRules::default();
+    if assign_starting_planets {
+        map.assign_starting_planets(&mut players, &structure_config, rules.speed, &mut rng)
+            .expect("Fixture starting planets must colonize");
+    }
+
+    let mut game_state = GameState::new(players, players_order, map, structure_config, ship_config, rules)
+        .expect("Fixture game state must be valid")
+        .with_seed(seed);
+    if !assign_starting_planets {
+        game_state.game_phase = GamePhase::Setup;
+    }
+    game_state
+}
+
+/// A setup like the one fixture games stand for: Alice and Bob on a small map, named from
+/// the fixture planet names.
+#[cfg(feature = "std-io")]
+pub fn fixture_setup(seed: u64) -> GameSetup {
+    GameSetup {
+        seed,
+        player_names: vec![String::from("Alice"), String::from("Bob")],
+        teams: vec![None, None],
+        map_size: MapSize::Small,
+        planet_names_path: Some(std::path::PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/planet_names.json"))),
+        rules: GameRules::default(),
+        starting_draft: false,
+    }
+}
+
+/// Returns the starting planet of a fixture player.
+pub fn home_planet(game_state: &GameState, player_id: &str) -> String {
+    game_state.players[player_id].planets[0].clone()
+}
+
+/// Creates an empty directory under the system temp dir, unique to `name` and this process.
+#[cfg(feature = "std-io")]
+pub fn temp_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("colony_protocol_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("Failed to create temp dir");
+    dir
+}
+
+/// Gives a fixture player a second, freshly colonized planet and returns its id.
+pub fn grant_planet(game_state: &mut GameState, player_id: &str) -> String {
+    let planet_id = game_state.map.planets.values()
+        .find(|planet| planet.get_owner().is_none())
+        .map(|planet| planet.id.clone())
+        .expect("Fixture map must have a neutral planet");
+
+    let planet = game_state.map.planets.get_mut(&planet_id).unwrap();
+    planet.set_owner(player_id.to_string());
+    planet.colonize(&game_state.structure_config, game_state.turn)
+        .expect("Fixture planet must colonize");
+    game_state.players.get_mut(player_id).unwrap().planets.push(planet_id.clone());
+    planet_id
+}
+
+/// Parses, validates and applies a command line for alice.
+pub fn run(game_state: &mut GameState, input: &str) -> Result<Vec<GameEvent>, CommandError> {
+    let effect = parser::parse(input)?.execute(game_state)?;
+    Ok(game_state.apply_effect(effect, &String::from("alice")).unwrap())
+}
+
+/// Ends the turn of every player in the rotation, in turn order, so the round is processed.
+/// Returns the events of all of them.
+pub fn end_round(game_state: &mut GameState) -> Vec<GameEvent> {
+    let mut events = Vec::new();
+    for _ in 0..game_state.players_order.len() {
+        let player_id = game_state.current_player().clone();
+        let player_name = game_state.players[&player_id].name.clone();
+        events.extend(game_state.apply_effect(CommandEffect::EndTurn { player_name }, &player_id).unwrap());
+    }
+    events
+}
@@ -0,0 +1,298 @@
+//! JSON entry points for frontends outside Rust, such as a JavaScript frontend driving the
+//! engine compiled to WebAssembly.
+//!
+//! Every function takes and returns JSON strings and keeps nothing between calls: the game
+//! travels as save JSON, the same format the terminal frontend loads. A failed call returns
+//! `{"error": "..."}`.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use thiserror::Error;
+
+use crate::engine::appearance;
+use crate::engine::commands::alias;
+use crate::engine::commands::command::{Command, CommandEffect, CommandError};
+use crate::engine::configs::planet_names::{PlanetNameParts, PlanetNamesConfigError};
+use crate::engine::configs::ship_config::{ShipConfig, ShipConfigError};
+use crate::engine::configs::structure_config::{StructureConfig, StructureConfigError};
+use crate::engine::game_event::OutputTarget;
+use crate::engine::game_rules::{GameRules, MAX_FACTIONS};
+use crate::engine::game_state::{GameState, GameStateError};
+use crate::engine::map::{Fogged, Map, MapError, MapSize};
+use crate::engine::planet::{Connection, PlanetError, PlanetId};
+use crate::engine::planet_name_generator::{PlanetNameGenerator, PlanetNameGeneratorError};
+use crate::engine::player::{Player, PlayerId};
+use crate::engine::resources::Resources;
+use crate::engine::save::{self, SaveError};
+use crate::engine::{parse_command, utils};
+
+/// Name written into the header of every save this API hands out
+const SAVE_NAME: &str = "web";
+
+#[derive(Debug, Error)]
+pub enum WasmApiError {
+    #[error("Invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Save(#[from] SaveError),
+
+    #[error(transparent)]
+    StructureConfig(#[from] StructureConfigError),
+
+    #[error(transparent)]
+    ShipConfig(#[from] ShipConfigError),
+
+    #[error(transparent)]
+    PlanetNames(#[from] PlanetNamesConfigError),
+
+    #[error(transparent)]
+    PlanetNameGenerator(#[from] PlanetNameGeneratorError),
+
+    #[error(transparent)]
+    Map(#[from] MapError),
+
+    #[error(transparent)]
+    Planet(#[from] PlanetError),
+
+    #[error(transparent)]
+    GameState(#[from] GameStateError),
+
+    #[error("A game needs 2-{MAX_FACTIONS} players, got {0}")]
+    InvalidPlayerCount(usize),
+
+    #[error("Player name '{0}' is empty or taken")]
+    InvalidPlayerName(String),
+
+    #[error("A {map_size} map has {} planets, too few for {players} players", .map_size.num_planets())]
+    MapTooSmall { map_size: MapSize, players: usize },
+
+    #[error("Player {0} does not exist")]
+    UnknownPlayer(PlayerId),
+
+    #[error("'{0}' is not available here")]
+    Unsupported(&'static str),
+}
+
+/// Everything `new_game` needs to set up a game. Only `players` and `seed` are required.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NewGameConfig {
+    /// Player names, in turn order
+    pub players: Vec<String>,
+    /// Seeds every random choice, so the same config always sets up the same game
+    pub seed: u64,
+    #[serde(default = "default_map_size")]
+    pub map_size: MapSize,
+    #[serde(default)]
+    pub rules: GameRules,
+    /// Config JSON replacing the shipped `data/structure.json`
+    #[serde(default)]
+    pub structures: Option<String>,
+    /// Config JSON replacing the shipped `data/ships.json`
+    #[serde(default)]
+    pub ships: Option<String>,
+    /// Config JSON replacing the shipped `data/planet_names.json`
+    #[serde(default)]
+    pub planet_names: Option<String>,
+}
+
+fn default_map_size() -> MapSize {
+    MapSize::Small
+}
+
+/// A game as one player sees it: their own empire in full, other planets through the fog.
+#[derive(Debug, Serialize)]
+struct PlayerView<'a> {
+    turn: u32,
+    current_player: &'a PlayerId,
+    game_over: bool,
+    player_id: &'a PlayerId,
+    resources: Resources,
+    production: Resources,
+    planets: Vec<PlanetView<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct PlanetView<'a> {
+    id: &'a PlanetId,
+    name: &'a str,
+    position: Option<(u8, u8)>,
+    connections: &'a [Connection],
+    #[serde(flatten)]
+    sighting: Sighting<'a>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "visibility", rename_all = "snake_case")]
+enum Sighting<'a> {
+    Visible { owner: Option<&'a PlayerId>, shield: u32, structures: usize },
+    LastSeen { turn: u32, owner: Option<PlayerId> },
+    Unexplored,
+}
+
+/// Sets up a game from [`NewGameConfig`] JSON. Returns `{"save": ...}`.
+pub fn new_game(config_json: &str) -> String {
+    respond(try_new_game(config_json))
+}
+
+/// Runs one command line for the player whose turn it is. Returns the updated
+/// `{"save": ...}`, the `events` it produced as `{"player": id or null, "text": ...}` with
+/// null meaning everyone, and the `rejected` reason when the command was refused. Rejected
+/// orders still change the save, since they are kept in the action log.
+pub fn execute_command(save_json: &str, line: &str) -> String {
+    respond(try_execute_command(save_json, line))
+}
+
+/// What `player_id` can see of the game in a save.
+pub fn view_for(save_json: &str, player_id: &str) -> String {
+    respond(try_view_for(save_json, player_id))
+}
+
+fn try_new_game(config_json: &str) -> Result<Value, WasmApiError> {
+    let config: NewGameConfig = serde_json::from_str(config_json)?;
+    let structure_config = match &config.structures {
+        Some(json) => StructureConfig::load_from_string(json)?,
+        None => StructureConfig::embedded()?,
+    };
+    let ship_config = match &config.ships {
+        Some(json) => ShipConfig::load_from_string(json)?,
+        None => ShipConfig::embedded()?,
+    };
+    let name_parts = match &config.planet_names {
+        Some(json) => PlanetNameParts::load_from_string(json)?,
+        None => PlanetNameParts::embedded()?,
+    };
+
+    if !(2..=MAX_FACTIONS).contains(&config.players.len()) {
+        return Err(WasmApiError::InvalidPlayerCount(config.players.len()));
+    }
+    if config.map_size.num_planets() < config.players.len() as u32 {
+        return Err(WasmApiError::MapTooSmall { map_size: config.map_size, players: config.players.len() });
+    }
+    let names: Vec<&str> = config.players.iter().map(|name| name.trim()).collect();
+    let mut players = BTreeMap::new();
+    for (name, appearance) in names.iter().zip(appearance::assign(&names)) {
+        let id = utils::name_to_id(name);
+        if id.is_empty() || players.contains_key(&id) {
+            return Err(WasmApiError::InvalidPlayerName(name.to_string()));
+        }
+        players.insert(id.clone(), Player::new(id, name.to_string(), appearance));
+    }
+    let players_order: VecDeque<PlayerId> = names.iter().map(|name| utils::name_to_id(name)).collect();
+
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut name_generator = PlanetNameGenerator::from_parts(name_parts)?.seeded(config.seed);
+    let mut map = Map::generate(config.map_size, &mut name_generator, &mut rng)?;
+    map.assign_starting_planets(&mut players, &structure_config, config.rules.speed, &mut rng)?;
+    map.place_ruins(config.rules.ruin_chance_percent, &mut rng);
+    if config.rules.victory.allows_domination() {
+        map.place_key_planets(config.rules.key_planets, &mut rng);
+    }
+
+    let state = GameState::new(players, players_order, map, structure_config, ship_config, config.rules)?.with_seed(config.seed);
+    Ok(json!({ "save": encode(&state)? }))
+}
+
+fn try_execute_command(save_json: &str, line: &str) -> Result<Value, WasmApiError> {
+    let mut state = save::decode_save(save_json.as_bytes())?;
+    let player_id = state.checked_current_player()?;
+    let player = state.players.get(player_id)
+        .ok_or_else(|| WasmApiError::UnknownPlayer(player_id.clone()))?;
+    let line = alias::expand(line, &player.aliases).into_owned();
+    let acting_player = player.id.clone();
+
+    let command = parse_command(&line).and_then(|command| match command {
+        Command::Inspect(_) => Err(CommandError::DevOnly(String::from("inspect"))),
+        Command::Dev(_) => Err(CommandError::DevOnly(String::from("dev"))),
+        command => Ok(command),
+    });
+    let is_order = command.as_ref().is_ok_and(Command::is_order);
+    let name = command.as_ref().map_or("", Command::name);
+    let result = command.and_then(|command| command.execute(&state));
+    if result.as_ref().is_ok_and(is_frontend_only) {
+        return Err(WasmApiError::Unsupported(name));
+    }
+
+    let (events, rejected) = match state.apply_command(&acting_player, &line, is_order, result) {
+        Ok(events) => (events, None),
+        Err(reason) => (Vec::new(), Some(reason)),
+    };
+    let events: Vec<Value> = events.iter()
+        .map(|event| {
+            let player = match event.target() {
+                OutputTarget::CurrentPlayer => Some(acting_player.clone()),
+                OutputTarget::Player(player_id) => Some(player_id),
+                OutputTarget::Broadcast => None,
+            };
+            json!({ "player": player, "text": event.named(&state.structure_config, &state.ship_config).to_string() })
+        })
+        .collect();
+
+    Ok(json!({ "save": encode(&state)?, "events": events, "rejected": rejected }))
+}
+
+fn try_view_for(save_json: &str, player_id: &str) -> Result<Value, WasmApiError> {
+    let state = save::decode_save(save_json.as_bytes())?;
+    let player_id = player_id.to_string();
+    let player = state.players.get(&player_id)
+        .ok_or_else(|| WasmApiError::UnknownPlayer(player_id.clone()))?;
+    let summary = state.player_resource_summary(&player_id).unwrap_or_default();
+
+    let planets = state.map.planets.values()
+        .map(|planet| PlanetView {
+            id: &planet.id,
+            name: &planet.name,
+            position: state.map.planet_positions.get(&planet.id).copied(),
+            connections: planet.get_connections(),
+            sighting: match state.fogged_view(&player_id, &planet.id) {
+                None => Sighting::Visible {
+                    owner: planet.get_owner().as_ref(),
+                    shield: planet.get_shield_hp(),
+                    structures: planet.get_structures().len(),
+                },
+                Some(Fogged::LastSeen { turn, owner }) => Sighting::LastSeen { turn, owner },
+                Some(Fogged::Unexplored) => Sighting::Unexplored,
+            },
+        })
+        .collect();
+
+    let view = PlayerView {
+        turn: state.turn,
+        current_player: state.checked_current_player()?,
+        game_over: state.is_over(),
+        player_id: &player.id,
+        resources: summary.available,
+        production: summary.production,
+        planets,
+    };
+    Ok(serde_json::to_value(view)?)
+}
+
+/// Effects the engine leaves to the frontend: save files, the report file and the terminal.
+fn is_frontend_only(effect: &CommandEffect) -> bool {
+    matches!(
+        effect,
+        CommandEffect::Save { .. }
+            | CommandEffect::Load { .. }
+            | CommandEffect::ListSaves
+            | CommandEffect::DeleteSave { .. }
+            | CommandEffect::ExportReport { .. }
+            | CommandEffect::ListOptions
+            | CommandEffect::SetSessionOption { .. }
+            | CommandEffect::Exit
+            | CommandEffect::Rematch
+    )
+}
+
+fn encode(state: &GameState) -> Result<Value, WasmApiError> {
+    Ok(serde_json::from_slice(&save::encode_save(state, SAVE_NAME, 0)?)?)
+}
+
+fn respond(result: Result<Value, WasmApiError>) -> String {
+    result.unwrap_or_else(|error| json!({ "error": error.to_string() })).to_string()
+}
@@ -0,0 +1,104 @@
+// Builds and plays a game the way an embedder would: configs and rng injected, no file
+// system or stdin. Run it without default features to check the slim configuration:
+//   cargo test -p colony_core --no-default-features --test embedded
+
+use std::collections::{BTreeMap, VecDeque};
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use colony_core::engine::appearance;
+use colony_core::engine::configs::planet_names::PlanetNameParts;
+use colony_core::engine::configs::player_names;
+use colony_core::engine::configs::ship_config::ShipConfig;
+use colony_core::engine::configs::structure_config::StructureConfig;
+use colony_core::engine::map::{Map, MapSize};
+use colony_core::engine::planet_name_generator::PlanetNameGenerator;
+use colony_core::engine::player::{Player, PlayerId};
+use colony_core::engine::save;
+use colony_core::engine::utils;
+use colony_core::{GameRules, GameState, parse_command};
+
+const STRUCTURES_FIXTURE: &str = include_str!("fixtures/structures.json");
+const SHIPS_FIXTURE: &str = include_str!("fixtures/ships.json");
+const PLANET_NAMES_FIXTURE: &str = include_str!("fixtures/planet_names.json");
+const PLAYER_NAMES_FIXTURE: &str = include_str!("fixtures/player_names.json");
+
+fn build_game(
+    structure_config: StructureConfig,
+    ship_config: ShipConfig,
+    name_parts: PlanetNameParts,
+    seed: u64,
+) -> GameState {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut name_generator = PlanetNameGenerator::from_parts(name_parts).unwrap().seeded(seed);
+    let mut map = Map::generate(MapSize::Small, &mut name_generator, &mut rng).unwrap();
+
+    let names = player_names::choose_names_from_string(PLAYER_NAMES_FIXTURE, 2, &mut rng).unwrap();
+    let name_refs: Vec<&str> = names.iter().map(String::as_str).collect();
+    let mut players: BTreeMap<PlayerId, Player> = names.iter()
+        .zip(appearance::assign(&name_refs))
+        .map(|(name, appearance)| (utils::name_to_id(name), Player::new(utils::name_to_id(name), name.clone(), appearance)))
+        .collect();
+    let players_order: VecDeque<PlayerId> = names.iter().map(|name| utils::name_to_id(name)).collect();
+
+    let rules = GameRules::default();
+    map.assign_starting_planets(&mut players, &structure_config, rules.speed, &mut rng).unwrap();
+    GameState::new(players, players_order, map, structure_config, ship_config, rules).unwrap()
+}
+
+fn fixture_game(seed: u64) -> GameState {
+    build_game(
+        StructureConfig::load_from_string(STRUCTURES_FIXTURE).unwrap(),
+        ShipConfig::load_from_string(SHIPS_FIXTURE).unwrap(),
+        PlanetNameParts::load_from_string(PLANET_NAMES_FIXTURE).unwrap(),
+        seed,
+    )
+}
+
+/// Ends every player's turn once, through the same parse, execute and apply steps a frontend uses.
+fn play_round(state: &mut GameState) {
+    for _ in 0..state.players_order.len() {
+        let player_id = state.current_player().clone();
+        let effect = parse_command("end").unwrap().execute(state).unwrap();
+        state.apply_effect(effect, &player_id).unwrap();
+    }
+}
+
+#[test]
+fn test_game_runs_from_injected_configs_and_rng() {
+    let mut state = fixture_game(7);
+    assert_eq!(state.players.len(), 2);
+    assert!(state.players.values().all(|player| player.planets.len() == 1));
+
+    play_round(&mut state);
+    assert_eq!(state.turn, 2);
+
+    // The same seed replays to the same game
+    let mut again = fixture_game(7);
+    play_round(&mut again);
+    assert_eq!(again.digest(), state.digest());
+}
+
+#[test]
+fn test_saves_round_trip_in_memory() {
+    let mut state = fixture_game(3);
+    play_round(&mut state);
+
+    let bytes = save::encode_save(&state, "embedded", 0).unwrap();
+    let loaded = save::decode_save(&bytes).unwrap();
+    assert_eq!(loaded.digest(), state.digest());
+}
+
+#[cfg(feature = "embedded-data")]
+#[test]
+fn test_game_runs_from_embedded_data() {
+    let mut state = build_game(
+        StructureConfig::embedded().unwrap(),
+        ShipConfig::embedded().unwrap(),
+        PlanetNameParts::embedded().unwrap(),
+        5,
+    );
+    play_round(&mut state);
+    assert_eq!(state.turn, 2);
+}
@@ -0,0 +1,87 @@
+// Issues every command to a game that has no players yet, the state an embedder or a lobby
+// holds before anyone joins. Commands must refuse with an error, never panic.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use colony_core::engine::commands::completion::{ArgKind, SIGNATURES};
+use colony_core::engine::configs::planet_names::PlanetNameParts;
+use colony_core::engine::configs::ship_config::ShipConfig;
+use colony_core::engine::configs::structure_config::StructureConfig;
+use colony_core::engine::map::{Map, MapSize};
+use colony_core::engine::planet_name_generator::PlanetNameGenerator;
+use colony_core::{Command, CommandError, GameRules, GameState, GameStateError, parse_command};
+
+const STRUCTURES_FIXTURE: &str = include_str!("fixtures/structures.json");
+const SHIPS_FIXTURE: &str = include_str!("fixtures/ships.json");
+const PLANET_NAMES_FIXTURE: &str = include_str!("fixtures/planet_names.json");
+
+fn empty_game() -> GameState {
+    let mut rng = StdRng::seed_from_u64(1);
+    let name_parts = PlanetNameParts::load_from_string(PLANET_NAMES_FIXTURE).unwrap();
+    let mut name_generator = PlanetNameGenerator::from_parts(name_parts).unwrap().seeded(1);
+    let map = Map::generate(MapSize::Small, &mut name_generator, &mut rng).unwrap();
+
+    GameState::new(
+        BTreeMap::new(),
+        VecDeque::new(),
+        map,
+        StructureConfig::load_from_string(STRUCTURES_FIXTURE).unwrap(),
+        ShipConfig::load_from_string(SHIPS_FIXTURE).unwrap(),
+        GameRules::default(),
+    )
+    .unwrap()
+}
+
+/// One line per command form, with a plausible value for every required argument.
+fn sample_lines(state: &GameState) -> Vec<String> {
+    let planet = state.map.planets.keys().next().unwrap().clone();
+    let structure = state.structure_config.iter().next().unwrap().0.clone();
+    let ship = state.ship_config.iter().next().unwrap().0.clone();
+
+    SIGNATURES.iter()
+        .map(|signature| {
+            let words: Vec<String> = signature.args.iter()
+                .filter_map(|kind| match kind {
+                    ArgKind::Literal(word) => Some(word.to_string()),
+                    ArgKind::OneOf(words) => Some(words[0].to_string()),
+                    ArgKind::OwnedPlanet | ArgKind::Planet => Some(planet.clone()),
+                    ArgKind::StructureId | ArgKind::BuiltStructure => Some(structure.clone()),
+                    ArgKind::ShipType => Some(ship.clone()),
+                    ArgKind::Number(min, _) => Some(min.to_string()),
+                    ArgKind::TurnRange => Some(String::from("1")),
+                    ArgKind::Optional(_) => None,
+                    _ => Some(String::from("x")),
+                })
+                .collect();
+            words.join(" ")
+        })
+        .collect()
+}
+
+#[test]
+fn test_current_player_is_an_error_before_the_game_starts() {
+    let state = empty_game();
+    assert!(matches!(state.checked_current_player(), Err(GameStateError::NotStarted)));
+}
+
+#[test]
+fn test_every_command_refuses_before_the_game_starts() {
+    let state = empty_game();
+    for line in sample_lines(&state) {
+        let command = parse_command(&line).unwrap_or_else(|error| panic!("'{line}' does not parse: {error}"));
+        // Help, saves and session settings do not involve a player
+        let needs_players = !matches!(
+            command,
+            Command::Help(_) | Command::Save(_) | Command::Load(_) | Command::Saves(_)
+                | Command::Set(_) | Command::Options(_) | Command::Exit | Command::Rematch
+        );
+
+        let result = command.execute(&state);
+        if needs_players {
+            assert!(matches!(result, Err(CommandError::GameNotStarted)), "'{line}' gave {:?}", result.err());
+        }
+    }
+}
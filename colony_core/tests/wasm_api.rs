@@ -0,0 +1,79 @@
+// Drives a game through the JSON API the way a JavaScript frontend would. Needs the
+// `wasm-api` feature, and runs without the default features too:
+//   cargo test -p colony_core --no-default-features --features wasm-api --test wasm_api
+
+use serde_json::{Value, json};
+
+use colony_core::wasm_api;
+
+const STRUCTURES_FIXTURE: &str = include_str!("fixtures/structures.json");
+const SHIPS_FIXTURE: &str = include_str!("fixtures/ships.json");
+const PLANET_NAMES_FIXTURE: &str = include_str!("fixtures/planet_names.json");
+
+fn call(response: String) -> Value {
+    serde_json::from_str(&response).expect("Responses must be JSON")
+}
+
+fn new_game(seed: u64) -> String {
+    let config = json!({
+        "players": ["Ada", "Grace"],
+        "seed": seed,
+        "structures": STRUCTURES_FIXTURE,
+        "ships": SHIPS_FIXTURE,
+        "planet_names": PLANET_NAMES_FIXTURE,
+    });
+    let response = call(wasm_api::new_game(&config.to_string()));
+    assert!(response.get("error").is_none(), "{response}");
+    response["save"].to_string()
+}
+
+fn execute(save: &str, line: &str) -> (String, Value) {
+    let response = call(wasm_api::execute_command(save, line));
+    assert!(response.get("error").is_none(), "{response}");
+    (response["save"].to_string(), response)
+}
+
+#[test]
+fn test_json_api_plays_a_round() {
+    let save = new_game(1);
+    let view = call(wasm_api::view_for(&save, "ada"));
+    assert_eq!(view["turn"], 1);
+    assert_eq!(view["current_player"], "ada");
+    let home = view["planets"].as_array().unwrap().iter()
+        .find(|planet| planet["owner"] == "ada")
+        .expect("Ada must see her home planet");
+    assert_eq!(home["visibility"], "visible");
+
+    let (save, response) = execute(&save, "end");
+    assert!(response["rejected"].is_null());
+    assert!(!response["events"].as_array().unwrap().is_empty());
+    let (save, _) = execute(&save, "end");
+
+    let view = call(wasm_api::view_for(&save, "grace"));
+    assert_eq!(view["turn"], 2);
+    assert_eq!(view["current_player"], "ada");
+}
+
+#[test]
+fn test_json_api_reports_rejections_and_errors() {
+    let save = new_game(2);
+
+    let (save, response) = execute(&save, "build nowhere power_grid");
+    assert!(response["rejected"].as_str().is_some_and(|reason| reason.contains("nowhere")), "{response}");
+    assert_eq!(call(wasm_api::view_for(&save, "ada"))["turn"], 1);
+
+    for response in [
+        wasm_api::execute_command(&save, "save slot"),
+        wasm_api::execute_command("{ not json", "end"),
+        wasm_api::view_for(&save, "nobody"),
+        wasm_api::new_game(r#"{"players": ["Solo"], "seed": 1}"#),
+    ] {
+        assert!(call(response)["error"].is_string());
+    }
+}
+
+#[test]
+fn test_json_api_defaults_to_the_shipped_data() {
+    let response = call(wasm_api::new_game(r#"{"players": ["Ada", "Grace"], "seed": 3}"#));
+    assert!(response["save"]["game_state"].is_object(), "{response}");
+}